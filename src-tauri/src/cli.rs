@@ -0,0 +1,324 @@
+//! Headless `resonance send` mode for scripting requests without the UI.
+//!
+//! ```text
+//! resonance send --request request.json [--body <file>|-] [--content-type <mime>]
+//! cat big.json | resonance send --request request.json --body -
+//! ```
+//!
+//! `request.json` holds the same request options the app sends (`method`,
+//! `url`, `headers`, `body`, `timeout`, `verifySsl`, ...), with variables
+//! already resolved. The body comes from `--body` (`-` for stdin) or from the
+//! file's own `body`. Files and stdin are streamed to the server in chunks, so
+//! large payloads are never held in memory; the response body is streamed to
+//! stdout the same way and the status line goes to stderr.
+//!
+//! The request goes through the same client as in the app, with the proxy,
+//! offline mode, trusted certificates, client certificates and CA bundle saved
+//! in the active workspace.
+
+use futures_util::stream;
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::commands::api_request::{build_client, BodyType, RequestOptions};
+use crate::commands::certificates::{saved_client_cert, url_host_port};
+use crate::commands::proxy::ProxyState;
+use crate::commands::runner::saved_runner_settings;
+use crate::commands::workspaces::read_active_store;
+
+/// Size of the chunks a streamed body is read and sent in.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Must match `identifier` in tauri.conf.json, which names the app data
+/// directory.
+const APP_IDENTIFIER: &str = "io.github.db-mobile.resonance";
+
+const USAGE: &str =
+    "Usage: resonance send --request <request.json> [--body <file>|-] [--content-type <mime>]";
+
+/// Where a streamed request body is read from.
+#[derive(Debug, PartialEq, Eq)]
+enum BodySource {
+    Stdin,
+    File(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SendArgs {
+    request: String,
+    body: Option<BodySource>,
+    content_type: Option<String>,
+}
+
+fn parse_send_args(args: &[String]) -> Result<SendArgs, String> {
+    let mut request = None;
+    let mut body = None;
+    let mut content_type = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--request" | "-r" => request = Some(value()?),
+            "--body" | "-b" => {
+                body = Some(match value()?.as_str() {
+                    "-" => BodySource::Stdin,
+                    path => BodySource::File(path.to_string()),
+                })
+            }
+            "--content-type" => content_type = Some(value()?),
+            other => return Err(format!("Unknown option {}", other)),
+        }
+    }
+    Ok(SendArgs {
+        request: request.ok_or("--request is required")?,
+        body,
+        content_type,
+    })
+}
+
+/// A body read from `reader` chunk by chunk as the request is sent.
+fn streamed_body<R>(reader: R) -> reqwest::Body
+where
+    R: AsyncRead + Unpin + Send + Sync + 'static,
+{
+    reqwest::Body::wrap_stream(stream::unfold(reader, |mut reader| async move {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        match reader.read(&mut chunk).await {
+            Ok(0) => None,
+            Ok(n) => {
+                chunk.truncate(n);
+                Some((Ok(chunk), reader))
+            }
+            Err(e) => Some((Err(e), reader)),
+        }
+    }))
+}
+
+/// The headers to send: the request file's, with its Content-Type replaced
+/// by `content_type` when one is given.
+fn request_headers<'a>(
+    options: &'a RequestOptions,
+    content_type: Option<&'a str>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut headers: Vec<(&str, &str)> = options
+        .headers
+        .iter()
+        .flatten()
+        .filter(|(key, _)| content_type.is_none() || !key.eq_ignore_ascii_case("content-type"))
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    if let Some(content_type) = content_type {
+        headers.push(("Content-Type", content_type));
+    }
+    headers
+}
+
+/// The client the app would use for `options`, with the settings saved in
+/// the active workspace applied to them.
+fn build_saved_client(options: &mut RequestOptions) -> Result<reqwest::Client, String> {
+    let app_data_dir = dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .unwrap_or_else(PathBuf::new);
+    let store = read_active_store(&app_data_dir);
+    let saved = |key: &str| store.get(key);
+
+    let proxy_state = ProxyState::default();
+    proxy_state.load_saved(saved);
+    proxy_state.check_offline(&options.url)?;
+
+    if options.ca_bundle_path.is_none() {
+        options.ca_bundle_path = saved_runner_settings(saved).ca_bundle_path;
+    }
+    if options.client_cert.is_none() {
+        options.client_cert =
+            url_host_port(&options.url).and_then(|host| saved_client_cert(saved, &host));
+    }
+    options.apply_ca_bundle();
+
+    let trusted = if options.verify_ssl == Some(false) {
+        Vec::new()
+    } else {
+        proxy_state.trusted_hosts.fingerprints_for(&options.url)
+    };
+    build_client(
+        options,
+        options.ip_family.unwrap_or_default(),
+        &trusted,
+        proxy_state.get_proxy_config(&options.url, options.proxy.as_ref()),
+        proxy_state.redirect_policy(),
+    )
+}
+
+async fn send(args: SendArgs) -> Result<reqwest::StatusCode, String> {
+    let content = std::fs::read_to_string(&args.request)
+        .map_err(|e| format!("Failed to read {}: {}", args.request, e))?;
+    let mut options: RequestOptions = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid request file {}: {}", args.request, e))?;
+    let method = options
+        .method
+        .parse::<reqwest::Method>()
+        .map_err(|e| format!("Invalid HTTP method: {}", e))?;
+
+    let client = build_saved_client(&mut options)?;
+    let mut request = client.request(method, &options.url);
+    let headers = request_headers(&options, args.content_type.as_deref());
+    let has_content_type = headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("content-type"));
+    for (key, value) in headers {
+        request = request.header(key, value);
+    }
+
+    request = match args.body {
+        Some(BodySource::Stdin) => request.body(streamed_body(tokio::io::stdin())),
+        Some(BodySource::File(path)) => {
+            let file = tokio::fs::File::open(&path)
+                .await
+                .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+            let length = file
+                .metadata()
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?
+                .len();
+            request
+                .header(reqwest::header::CONTENT_LENGTH, length)
+                .body(streamed_body(file))
+        }
+        None => match (&options.body, options.body_type.unwrap_or_default()) {
+            (None, _) => request,
+            (Some(body), BodyType::Json) => request.json(body),
+            (Some(body), BodyType::Text) => {
+                if !has_content_type {
+                    request = request.header("Content-Type", "text/plain");
+                }
+                request.body(body.as_str().unwrap_or("").to_string())
+            }
+            (Some(_), _) => {
+                return Err(
+                    "Form and file bodies are not supported here; pass the payload with --body"
+                        .to_string(),
+                )
+            }
+        },
+    };
+
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    eprintln!("{:?} {}", response.version(), status);
+
+    let mut stdout = std::io::stdout().lock();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?
+    {
+        stdout
+            .write_all(&chunk)
+            .map_err(|e| format!("Failed to write response: {}", e))?;
+    }
+    stdout
+        .flush()
+        .map_err(|e| format!("Failed to write response: {}", e))?;
+    Ok(status)
+}
+
+/// Run the CLI when the app was launched with a subcommand, returning the
+/// process exit code; `None` means start the UI. Exits 0 for a response below
+/// 400, 1 for an error status and 2 when the request could not be sent.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, rest) = args.split_first()?;
+    if command != "send" {
+        return None;
+    }
+    let args = match parse_send_args(rest) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            return Some(2);
+        }
+    };
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start the runtime: {}", e);
+            return Some(2);
+        }
+    };
+    Some(match runtime.block_on(send(args)) {
+        Ok(status) if status.as_u16() < 400 => 0,
+        Ok(_) => 1,
+        Err(message) => {
+            eprintln!("{}", message);
+            2
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_send_options() {
+        assert_eq!(
+            parse_send_args(&args(&["--request", "req.json", "--body", "-"])).unwrap(),
+            SendArgs {
+                request: "req.json".to_string(),
+                body: Some(BodySource::Stdin),
+                content_type: None,
+            }
+        );
+        assert_eq!(
+            parse_send_args(&args(&[
+                "-r",
+                "req.json",
+                "-b",
+                "big.json",
+                "--content-type",
+                "application/json"
+            ]))
+            .unwrap()
+            .body,
+            Some(BodySource::File("big.json".to_string()))
+        );
+        assert!(parse_send_args(&args(&["--body", "-"])).is_err());
+        assert!(parse_send_args(&args(&["--request"])).is_err());
+        assert!(parse_send_args(&args(&["--request", "r.json", "--verbose"])).is_err());
+    }
+
+    #[test]
+    fn content_type_option_replaces_the_header() {
+        let options: RequestOptions = serde_json::from_value(serde_json::json!({
+            "method": "POST",
+            "url": "https://api.test/upload",
+            "headers": { "content-type": "text/plain", "X-Trace": "1" }
+        }))
+        .unwrap();
+        let mut headers = request_headers(&options, Some("application/json"));
+        headers.sort();
+        assert_eq!(
+            headers,
+            vec![("Content-Type", "application/json"), ("X-Trace", "1")]
+        );
+        assert!(request_headers(&options, None).contains(&("content-type", "text/plain")));
+    }
+
+    #[test]
+    fn other_launch_arguments_start_the_ui() {
+        assert_eq!(run(&args(&[])), None);
+        assert_eq!(run(&args(&["collection.json"])), None);
+        assert_eq!(run(&args(&["send", "--bogus"])), Some(2));
+    }
+}
@@ -3,6 +3,7 @@ use reqwest::{Client, Method, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tauri::State;
@@ -11,7 +12,22 @@ use tokio::sync::oneshot;
 use tokio::time::timeout as tokio_timeout;
 use uuid::Uuid;
 
-use super::proxy::{ProxyAction, ProxyState};
+use super::client_pool::{ClientPool, FileVersion};
+use super::idempotency::{endpoint_idempotency, IdempotencyKeys, SentIdempotencyKey};
+use super::ip_family::{
+    endpoint_ip_family, family_mismatch, order_addresses, FamilyResolver, IpFamily, RemoteAddress,
+};
+use super::mime_sniff::{sniff_content, ContentSniff};
+use super::otlp_export::export_request_span;
+use super::proxy::{ProxyAction, ProxyOverride, ProxyState};
+use super::request_timeline::Timeline;
+use super::response_file::{KeptResponse, KeptResponses};
+use super::response_transform::{
+    apply_response_transform, endpoint_response_transform, TransformReport,
+};
+use super::time_budget::{endpoint_time_budget, BudgetCheck};
+use super::trace_headers::{self, TraceIds};
+use super::usage_stats::record_usage;
 
 /// Maximum time to spend on the TCP+TLS timing probe before giving up.
 const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
@@ -19,9 +35,9 @@ const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 /// Dangerous cert verifier used only when the request has `verify_ssl: false`.
 /// Kept in sync with reqwest's `danger_accept_invalid_certs(true)` behavior so
 /// the probe does not fail on self-signed certs where the real request succeeds.
-use super::tls::NoCertVerifier;
+use super::tls::{endpoint_ignores_tls_errors, NoCertVerifier};
 
-fn build_probe_tls_config(verify_ssl: bool) -> rustls::ClientConfig {
+pub(crate) fn build_probe_tls_config(verify_ssl: bool) -> rustls::ClientConfig {
     let provider = Arc::new(rustls::crypto::ring::default_provider());
     let builder = rustls::ClientConfig::builder_with_provider(provider)
         .with_safe_default_protocol_versions()
@@ -44,26 +60,29 @@ fn build_probe_tls_config(verify_ssl: bool) -> rustls::ClientConfig {
 /// Measure TCP and (for HTTPS) TLS handshake times against `host:port` via a
 /// separate short-lived probe connection. Returns `(tcp_ms, tls_ms)`. Any error
 /// is logged and reported as `None` — the caller must proceed regardless.
+/// Each completed handshake is reported to `timeline` as it happens.
 async fn measure_connection_timings(
     host: &str,
-    port: u16,
+    addrs: &[SocketAddr],
     is_https: bool,
     verify_ssl: bool,
+    timeline: &Timeline,
 ) -> (Option<u64>, Option<u64>) {
     let tcp_start = Instant::now();
-    let connect_future = TcpStream::connect((host, port));
+    let connect_future = TcpStream::connect(addrs);
     let tcp_stream = match tokio_timeout(PROBE_TIMEOUT, connect_future).await {
         Ok(Ok(stream)) => stream,
         Ok(Err(e)) => {
-            tracing::warn!("TCP timing probe failed for {}:{} - {}", host, port, e);
+            tracing::warn!("TCP timing probe failed for {} - {}", host, e);
             return (None, None);
         }
         Err(_) => {
-            tracing::warn!("TCP timing probe timed out for {}:{}", host, port);
+            tracing::warn!("TCP timing probe timed out for {}", host);
             return (None, None);
         }
     };
     let tcp_ms = tcp_start.elapsed().as_millis() as u64;
+    timeline.phase("connected");
 
     if !is_https {
         return (Some(tcp_ms), None);
@@ -83,7 +102,10 @@ async fn measure_connection_timings(
     let tls_start = Instant::now();
     let tls_future = connector.connect(server_name, tcp_stream);
     let tls_ms = match tokio_timeout(PROBE_TIMEOUT, tls_future).await {
-        Ok(Ok(_tls_stream)) => Some(tls_start.elapsed().as_millis() as u64),
+        Ok(Ok(_tls_stream)) => {
+            timeline.phase("tls");
+            Some(tls_start.elapsed().as_millis() as u64)
+        }
         Ok(Err(e)) => {
             tracing::warn!("TLS timing probe failed for {} - {}", host, e);
             None
@@ -238,7 +260,7 @@ fn build_digest_auth_header(
 type HmacSha256 = Hmac<Sha256>;
 
 /// Compute SHA-256 hex digest of arbitrary bytes.
-fn sha256_hex(data: &[u8]) -> String {
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(data);
     hex::encode(hasher.finalize())
@@ -289,7 +311,7 @@ fn aws_derive_signing_key(secret: &str, date: &str, region: &str, service: &str)
 /// Returns a map of headers that must be added to the request:
 /// `Authorization`, `x-amz-date`, `x-amz-content-sha256`, and optionally
 /// `x-amz-security-token`.
-fn build_aws_v4_headers(
+pub(crate) fn build_aws_v4_headers(
     aws: &AwsAuthConfig,
     method: &str,
     url_str: &str,
@@ -416,7 +438,7 @@ fn build_aws_v4_headers(
     Ok(out)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestOptions {
     pub method: String,
@@ -435,15 +457,76 @@ pub struct RequestOptions {
     /// Whether to follow HTTP redirects (defaults to true)
     #[serde(default)]
     pub follow_redirects: Option<bool>,
-    /// Body encoding type: "json" (default) | "formdata" | "urlencoded" | "text" | "binary"
+    /// Which IP family to resolve the host to (defaults to the system order)
     #[serde(default)]
-    pub body_type: Option<String>,
+    pub ip_family: Option<IpFamily>,
+    /// How `body` is encoded; JSON when unset
+    #[serde(default)]
+    pub body_type: Option<BodyType>,
     /// AWS Signature V4 authentication configuration
     #[serde(default)]
     pub aws_auth: Option<AwsAuthConfig>,
     /// Client certificate (mTLS) and custom CA configuration, resolved by host
     #[serde(default)]
     pub client_cert: Option<ClientCertConfig>,
+    /// CA bundle from the settings, trusted for every host without a CA
+    /// bundle of its own; read from the settings when unset
+    #[serde(default)]
+    pub ca_bundle_path: Option<String>,
+    /// Collection the request belongs to, whose proxy override applies
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    /// Proxy override of the request's collection, looked up before sending
+    #[serde(skip)]
+    pub proxy: Option<ProxyOverride>,
+    /// Keep the raw response body under this key (the workspace tab id) for
+    /// `save_response_to_file`
+    #[serde(default)]
+    pub response_key: Option<String>,
+    /// The user confirmed sending despite the collection's safety policy
+    #[serde(default)]
+    pub confirmed: bool,
+    /// Endpoint the request was sent from, whose time budget applies
+    #[serde(default)]
+    pub endpoint_id: Option<String>,
+    /// The request's own auth is `inherit`: the backend applies the auth of
+    /// its folder or collection before sending
+    #[serde(default)]
+    pub inherit_auth: bool,
+    /// Variable values that win over the environment and collection ones for
+    /// this send only, applied wherever the backend resolves variables
+    #[serde(default)]
+    pub variable_overrides: HashMap<String, String>,
+    /// Add generated `X-Request-Id` and `traceparent` headers
+    #[serde(default)]
+    pub trace_headers: bool,
+    /// Trace the `traceparent` joins; a fresh one when unset
+    #[serde(default)]
+    pub trace_id: Option<String>,
+    /// Send the endpoint's last idempotency key again instead of a new one
+    #[serde(default)]
+    pub reuse_idempotency_key: bool,
+}
+
+/// Encoding of `RequestOptions::body`. The names used by other tools
+/// (`raw`, `form-urlencoded`, `multipart`) are accepted as aliases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyType {
+    /// Any JSON value, serialized as `application/json`
+    #[default]
+    Json,
+    /// A string sent as is, `text/plain` unless a Content-Type header is set
+    #[serde(alias = "raw")]
+    Text,
+    /// [`FormPart`] rows sent as `application/x-www-form-urlencoded`
+    #[serde(alias = "form-urlencoded")]
+    Urlencoded,
+    /// [`FormPart`] rows, text or local files, sent as `multipart/form-data`
+    #[serde(alias = "multipart")]
+    Formdata,
+    /// A [`BinaryBody`] file sent verbatim
+    Binary,
 }
 
 /// One row of a "formdata" or "urlencoded" body sent as a JSON array.
@@ -466,7 +549,7 @@ pub struct FormPart {
     pub content_type: Option<String>,
 }
 
-/// Body payload for `body_type: "binary"`: a file sent verbatim as the request
+/// Body payload for [`BodyType::Binary`]: a file sent verbatim as the request
 /// body. Only the path travels over IPC; bytes are read here at send time.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -531,6 +614,8 @@ fn form_rows_to_pairs(rows: &[serde_json::Value]) -> Vec<(String, String)> {
 pub struct AuthConfig {
     pub username: String,
     pub password: String,
+    /// Scheme answering a 401 challenge: "digest" (default) or "negotiate",
+    /// which uses the signed-in user's Kerberos ticket instead of the credentials
     #[serde(default)]
     pub auth_type: Option<String>,
 }
@@ -563,6 +648,29 @@ pub struct ClientCertConfig {
     /// PEM CA bundle used to verify the server's certificate chain.
     #[serde(default)]
     pub ca_path: Option<String>,
+    /// Pinned server certificates (`sha256/<base64>` public key hashes or hex
+    /// SHA-256 fingerprints); the handshake fails unless one matches.
+    #[serde(default)]
+    pub pins: Vec<String>,
+}
+
+impl RequestOptions {
+    /// Carry the settings' CA bundle over into the client certificate config,
+    /// unless the host has a CA bundle of its own.
+    pub(crate) fn apply_ca_bundle(&mut self) {
+        let Some(bundle) = self.ca_bundle_path.take().filter(|p| !p.is_empty()) else {
+            return;
+        };
+        let cert = self.client_cert.get_or_insert_with(|| ClientCertConfig {
+            cert_path: None,
+            key_path: None,
+            ca_path: None,
+            pins: Vec::new(),
+        });
+        if cert.ca_path.as_deref().is_none_or(str::is_empty) {
+            cert.ca_path = Some(bundle);
+        }
+    }
 }
 
 impl ClientCertConfig {
@@ -572,9 +680,54 @@ impl ClientCertConfig {
     fn is_active(&self) -> bool {
         self.cert_path.as_deref().is_some_and(|p| !p.is_empty())
             || self.ca_path.as_deref().is_some_and(|p| !p.is_empty())
+            || !self.pins.is_empty()
     }
 }
 
+/// Pin the server certificate: the client uses a rustls config of its own
+/// (`use_preconfigured_tls` replaces reqwest's TLS settings), so the client
+/// identity, CA bundle and verification switch are carried over into it.
+fn apply_certificate_pins(
+    builder: reqwest::ClientBuilder,
+    cert: &ClientCertConfig,
+    verify_ssl: bool,
+    http_version: Option<&str>,
+) -> Result<reqwest::ClientBuilder, String> {
+    let identity = super::tls::load_identity_pems(&cert.cert_path, &cert.key_path)?;
+    let ca_pem = super::tls::load_ca_pem(&cert.ca_path)?;
+    let mut config = super::tls::build_pinned_tls_config(&cert.pins, verify_ssl, ca_pem, identity)?;
+    config.alpn_protocols = match http_version {
+        Some("http1") => vec![b"http/1.1".to_vec()],
+        _ => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+    };
+    Ok(builder.use_preconfigured_tls(config))
+}
+
+/// Accept the certificates the user trusted for the request's host (see
+/// [`super::trusted_hosts`]) on top of the usual verification. Like pinning,
+/// this needs a rustls config of its own, so any client identity and CA
+/// bundle are carried over into it.
+fn apply_trusted_certificates(
+    builder: reqwest::ClientBuilder,
+    cert: Option<&ClientCertConfig>,
+    trusted: &[String],
+    http_version: Option<&str>,
+) -> Result<reqwest::ClientBuilder, String> {
+    let (identity, ca_pem) = match cert {
+        Some(cert) => (
+            super::tls::load_identity_pems(&cert.cert_path, &cert.key_path)?,
+            super::tls::load_ca_pem(&cert.ca_path)?,
+        ),
+        None => (None, None),
+    };
+    let mut config = super::tls::build_trusted_tls_config(trusted, ca_pem, identity)?;
+    config.alpn_protocols = match http_version {
+        Some("http1") => vec![b"http/1.1".to_vec()],
+        _ => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+    };
+    Ok(builder.use_preconfigured_tls(config))
+}
+
 /// Apply a [`ClientCertConfig`] to a reqwest [`ClientBuilder`]: load the client
 /// identity (cert chain + key) for mTLS and add any custom CA roots. Returns a
 /// descriptive error so the UI can surface load/parse failures instead of an
@@ -657,30 +810,455 @@ pub struct ApiResponse {
     pub timings: RequestTimings,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cancelled: Option<bool>,
+    /// Not sent: the collection's safety policy wants the user to confirm
+    /// first (`message` says why). Resend with `confirmed` set to go ahead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_required: Option<bool>,
+    /// How the response time compares to the endpoint's time budget, when it
+    /// has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_budget: Option<BudgetCheck>,
+    /// The server's certificate failed verification; the user can trust it
+    /// for the host (see `trusted_hosts_inspect`) and resend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub untrusted_certificate: Option<bool>,
+    /// What the body actually contains, compared with its `Content-Type`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_sniff: Option<ContentSniff>,
+    /// The address the response came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_address: Option<RemoteAddress>,
+    /// The endpoint's response transform ran over `data`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_transform: Option<TransformReport>,
+    /// The generated `X-Request-Id` and `traceparent`, when the request
+    /// asked for them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_ids: Option<TraceIds>,
+    /// The idempotency key sent, when the endpoint sends one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<SentIdempotencyKey>,
+    /// The body exactly as received, kept for `save_response_to_file`
+    #[serde(skip)]
+    pub body: Option<bytes::Bytes>,
 }
 
 pub struct RequestState {
     pub cancel_tx: Mutex<Option<oneshot::Sender<()>>>,
+    pub kept_responses: KeptResponses,
+    pub idempotency_keys: IdempotencyKeys,
 }
 
 impl Default for RequestState {
     fn default() -> Self {
         Self {
             cancel_tx: Mutex::new(None),
+            kept_responses: KeptResponses::default(),
+            idempotency_keys: IdempotencyKeys::default(),
         }
     }
 }
 
+impl RequestState {
+    /// Make the request about to be sent the one `cancel_request` cancels.
+    /// The returned future resolves once it is cancelled; call
+    /// [`RequestState::finish_cancellable`] when the request is done.
+    pub(crate) fn start_cancellable(&self) -> impl std::future::Future<Output = ()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        *self.cancel_tx.lock().unwrap() = Some(cancel_tx);
+        async move {
+            let _ = cancel_rx.await;
+        }
+    }
+
+    pub(crate) fn finish_cancellable(&self) {
+        *self.cancel_tx.lock().unwrap() = None;
+    }
+}
+
 #[tauri::command]
 pub async fn send_api_request(
+    app: tauri::AppHandle,
     state: State<'_, RequestState>,
     proxy_state: State<'_, ProxyState>,
+    mut request_options: RequestOptions,
+) -> Result<ApiResponse, String> {
+    if let Some(reason) = safety_policy_reason(&app, &request_options)? {
+        return Ok(confirmation_required(reason));
+    }
+    let cookie_jar = super::cookie_jar::attach_cookies(&app, &mut request_options);
+    let url = request_options.url.clone();
+    let remember_cookies = |response: &ApiResponse| {
+        if let Some(environment_id) = cookie_jar.as_deref() {
+            // A jar that cannot be saved must not fail the request
+            if let Err(e) = super::cookie_jar::remember_cookies(
+                &app,
+                environment_id,
+                &url,
+                &response.set_cookies,
+            ) {
+                eprintln!("Failed to save response cookies: {}", e);
+            }
+        }
+    };
+
+    // A collection login flow may answer a 401 by logging in and resending
+    let login_flow = match request_options.collection_id.as_deref() {
+        Some(collection_id) => super::collections::collection_login_flow(&app, collection_id)?
+            .filter(|flow| flow.retry_on_unauthorized)
+            .map(|flow| (collection_id.to_string(), flow)),
+        None => None,
+    };
+    let retry = login_flow.as_ref().map(|_| request_options.clone());
+
+    let response = send_cancellable(&app, &state, &proxy_state, request_options).await?;
+    remember_cookies(&response);
+    let (Some((collection_id, flow)), Some(mut retry)) = (login_flow, retry) else {
+        return Ok(response);
+    };
+    if response.status != Some(401) {
+        return Ok(response);
+    }
+    let login = super::login_flow::run_login_flow(&app, &proxy_state, &collection_id, &flow)
+        .await
+        .map_err(|e| format!("401 Unauthorized, and the login flow failed: {}", e))?;
+    super::login_flow::apply_token(&mut retry, &login);
+    // The retry is the same operation: it keeps the rejected send's key
+    retry.reuse_idempotency_key |= response.idempotency_key.is_some();
+    let response = send_cancellable(&app, &state, &proxy_state, retry).await?;
+    remember_cookies(&response);
+    Ok(response)
+}
+
+/// Why the request's collection wants it confirmed before sending, unless the
+/// user already did.
+pub(crate) fn safety_policy_reason(
+    app: &tauri::AppHandle,
+    request_options: &RequestOptions,
+) -> Result<Option<String>, String> {
+    let Some(collection_id) = request_options.collection_id.as_deref() else {
+        return Ok(None);
+    };
+    if request_options.confirmed {
+        return Ok(None);
+    }
+    Ok(
+        super::collections::collection_safety_policy(app, collection_id)?.and_then(|policy| {
+            policy.confirmation_reason(&request_options.method, &request_options.url)
+        }),
+    )
+}
+
+/// The response for a request held back by a safety policy; nothing was sent.
+pub(crate) fn confirmation_required(reason: String) -> ApiResponse {
+    ApiResponse {
+        success: false,
+        data: None,
+        status: None,
+        status_text: Some("Confirmation required".to_string()),
+        headers: HashMap::new(),
+        set_cookies: vec![],
+        message: Some(reason),
+        ttfb: None,
+        size: None,
+        timings: RequestTimings {
+            start_time: chrono::Utc::now().timestamp_millis() as u64,
+            dns_lookup: 0,
+            tcp_connection: 0,
+            tls_handshake: 0,
+            first_byte: 0,
+            download: 0,
+            total: 0,
+        },
+        cancelled: None,
+        confirmation_required: Some(true),
+        time_budget: None,
+        untrusted_certificate: None,
+        content_sniff: None,
+        remote_address: None,
+        response_transform: None,
+        trace_ids: None,
+        idempotency_key: None,
+        body: None,
+    }
+}
+
+/// Resolve secrets and the collection proxy, then send with a cancellation
+/// channel that `cancel_api_request` can fire.
+pub(crate) async fn send_cancellable(
+    app: &tauri::AppHandle,
+    state: &RequestState,
+    proxy_state: &ProxyState,
     request_options: RequestOptions,
 ) -> Result<ApiResponse, String> {
-    // Create cancellation channel
-    let (cancel_tx, cancel_rx) = oneshot::channel();
-    *state.cancel_tx.lock().unwrap() = Some(cancel_tx);
+    let timeline = match request_options.response_key.as_deref() {
+        Some(tab_id) => Timeline::start(app, tab_id),
+        None => Timeline::none(),
+    };
+    let mut request_options = request_options;
+    if request_options.inherit_auth {
+        super::runner::apply_inherited_auth(app, &mut request_options).await?;
+    }
+    let mut request_options =
+        super::secrets::resolve_secret_references(app, request_options).await?;
+    if let Some(collection_id) = request_options.collection_id.as_deref() {
+        request_options.proxy = super::collections::collection_proxy(app, collection_id)?;
+    }
+    let endpoint = match (
+        request_options.collection_id.as_deref(),
+        request_options.endpoint_id.as_deref(),
+    ) {
+        (Some(collection_id), Some(endpoint_id)) => {
+            super::collections::collection_endpoint(app, collection_id, endpoint_id)?
+        }
+        _ => None,
+    };
+    let time_budget = endpoint.as_ref().and_then(endpoint_time_budget);
+    let transform = endpoint.as_ref().and_then(endpoint_response_transform);
+    if let Some(ip_family) = endpoint.as_ref().and_then(endpoint_ip_family) {
+        request_options.ip_family = Some(ip_family);
+    }
+    if endpoint.as_ref().is_some_and(endpoint_ignores_tls_errors) {
+        request_options.verify_ssl = Some(false);
+    }
+    if request_options.ca_bundle_path.is_none() {
+        request_options.ca_bundle_path = super::runner::load_runner_settings(app).ca_bundle_path;
+    }
+    let idempotency_key = match endpoint.as_ref().and_then(endpoint_idempotency) {
+        Some(settings) => Some(
+            super::idempotency::assign_key(
+                app,
+                &state.idempotency_keys,
+                &mut request_options,
+                &settings,
+            )
+            .await?,
+        ),
+        None if request_options.reuse_idempotency_key => {
+            return Err("This request does not send an idempotency key".to_string());
+        }
+        None => None,
+    };
+
+    let cancelled = state.start_cancellable();
+    let usage_target = (
+        request_options.url.clone(),
+        request_options.collection_id.clone(),
+    );
+    let method = request_options.method.clone();
+    let keep = request_options
+        .response_key
+        .clone()
+        .map(|key| (key, request_options.url.clone()));
+    let mut result =
+        execute_with_timeline(proxy_state, request_options, cancelled, &timeline).await;
+    state.finish_cancellable();
+    if let Ok(response) = &mut result {
+        response.idempotency_key = idempotency_key;
+    }
+    if let (Ok(response), Some(budget)) = (&mut result, time_budget) {
+        if response.status.is_some() {
+            response.time_budget = Some(BudgetCheck::new(budget, response.timings.total));
+        }
+    }
+    if let Ok(response) = &result {
+        let (url, collection_id) = &usage_target;
+        record_usage(app, url, collection_id.as_deref(), response);
+        export_request_span(app, proxy_state, &method, url, response);
+    }
+    if let (Ok(response), Some(transform)) = (&mut result, &transform) {
+        apply_response_transform(response, transform);
+    }
+    if let (Ok(response), Some((key, url))) = (&result, keep) {
+        if let Some(body) = response.body.clone() {
+            state.kept_responses.keep(
+                key,
+                KeptResponse {
+                    url,
+                    headers: response.headers.clone(),
+                    body,
+                },
+            );
+        }
+    }
+    result
+}
+
+/// Build the reqwest client for `options`: HTTP version, IP family, TLS
+/// verification, client certificates or pins, trusted certificates, redirect
+/// policy and proxy. Redirects follow `redirect` unless the request turns them
+/// off.
+pub(crate) fn build_client(
+    options: &RequestOptions,
+    ip_family: IpFamily,
+    trusted: &[String],
+    proxy_action: ProxyAction,
+    redirect: reqwest::redirect::Policy,
+) -> Result<Client, String> {
+    let mut client_builder =
+        Client::builder().user_agent(format!("resonance/{}", env!("CARGO_PKG_VERSION")));
+
+    // Only set timeout if provided and > 0
+    if let Some(timeout_ms) = options.timeout {
+        if timeout_ms > 0 {
+            client_builder = client_builder.timeout(Duration::from_millis(timeout_ms));
+        }
+    }
 
+    // Configure HTTP version based on settings
+    // Note: With rustls-tls + http2 feature, ALPN will negotiate HTTP/2 by default for HTTPS
+    match options.http_version.as_deref() {
+        Some("http1") => {
+            // Force HTTP/1.1 only - disable HTTP/2 completely
+            client_builder = client_builder.http1_only();
+        }
+        Some("http2") => {
+            // Force HTTP/2 with prior knowledge (no ALPN negotiation)
+            client_builder = client_builder.http2_prior_knowledge();
+        }
+        _ => {
+            // "auto" or unset - let ALPN negotiate
+        }
+    }
+
+    // Filter/order the resolved addresses by IP family. Through a proxy this
+    // applies to reaching the proxy; the proxy resolves the target itself.
+    if ip_family != IpFamily::Auto {
+        client_builder = client_builder.dns_resolver(Arc::new(FamilyResolver(ip_family)));
+    }
+
+    // Disable SSL verification if requested (e.g. for self-signed certs in dev)
+    if options.verify_ssl == Some(false) {
+        client_builder = client_builder.danger_accept_invalid_certs(true);
+    }
+
+    // Apply client certificate (mTLS) and/or custom CA trust resolved for this
+    // host, and the certificates trusted for it. Pins take precedence over
+    // trust exceptions.
+    let applied = match &options.client_cert {
+        Some(client_cert) if !client_cert.pins.is_empty() => apply_certificate_pins(
+            client_builder,
+            client_cert,
+            options.verify_ssl != Some(false),
+            options.http_version.as_deref(),
+        ),
+        client_cert if !trusted.is_empty() => apply_trusted_certificates(
+            client_builder,
+            client_cert.as_ref(),
+            trusted,
+            options.http_version.as_deref(),
+        ),
+        Some(client_cert) => apply_client_cert(client_builder, client_cert),
+        None => Ok(client_builder),
+    };
+    let mut client_builder = applied?;
+
+    // Disable redirect following if requested
+    if options.follow_redirects == Some(false) {
+        client_builder = client_builder.redirect(reqwest::redirect::Policy::none());
+    } else {
+        client_builder = client_builder.redirect(redirect);
+    }
+
+    // Apply the proxy decision. `Disable` must call `no_proxy()`
+    // explicitly — otherwise reqwest would still honour HTTP(S)_PROXY env vars
+    // and platform settings by default.
+    match proxy_action {
+        ProxyAction::Disable => {
+            client_builder = client_builder.no_proxy();
+        }
+        ProxyAction::UseSystem => {
+            // reqwest auto-detects system proxy; nothing to configure.
+        }
+        ProxyAction::Manual(proxy) => {
+            client_builder = client_builder.proxy(*proxy);
+        }
+    }
+
+    client_builder
+        .build()
+        .map_err(|e| format!("Client build error: {}", e))
+}
+
+/// When each certificate file of `cert` was last modified, so replacing a CA
+/// bundle or client identity on disk under the same path gets a new client.
+fn certificate_versions(
+    cert: Option<&ClientCertConfig>,
+    pool: &ClientPool,
+) -> Vec<Option<FileVersion>> {
+    let Some(cert) = cert else {
+        return Vec::new();
+    };
+    [&cert.cert_path, &cert.key_path, &cert.ca_path]
+        .into_iter()
+        .map(|path| path.as_deref().map(|path| pool.file_version(path)))
+        .collect()
+}
+
+/// Identifies the client [`build_client`] makes for these settings, so requests
+/// that would build the same client share one from the pool.
+pub(crate) fn client_key(
+    options: &RequestOptions,
+    ip_family: IpFamily,
+    trusted: &[String],
+    proxy_route: &str,
+    pool: &ClientPool,
+) -> String {
+    serde_json::json!([
+        options.timeout.filter(|ms| *ms > 0),
+        options.http_version,
+        ip_family,
+        options.verify_ssl == Some(false),
+        options.follow_redirects == Some(false),
+        options.client_cert,
+        certificate_versions(options.client_cert.as_ref(), pool),
+        trusted,
+        proxy_route,
+    ])
+    .to_string()
+}
+
+/// Send a request and build the `ApiResponse`, shared by the `send_api_request`
+/// command and backend callers such as the collection runner. The request is
+/// abandoned with a cancelled response as soon as `cancelled` resolves.
+pub(crate) async fn execute_api_request(
+    proxy_state: &ProxyState,
+    request_options: RequestOptions,
+    cancelled: impl std::future::Future<Output = ()>,
+) -> Result<ApiResponse, String> {
+    execute_with_timeline(proxy_state, request_options, cancelled, &Timeline::none()).await
+}
+
+/// [`execute_api_request`], reporting each lifecycle phase to `timeline`.
+async fn execute_with_timeline(
+    proxy_state: &ProxyState,
+    mut request_options: RequestOptions,
+    cancelled: impl std::future::Future<Output = ()>,
+    timeline: &Timeline,
+) -> Result<ApiResponse, String> {
+    let trace_ids = if request_options.trace_headers {
+        let headers = request_options.headers.get_or_insert_with(HashMap::new);
+        Some(trace_headers::inject(
+            headers,
+            request_options.trace_id.as_deref(),
+        ))
+    } else {
+        None
+    };
+    let mut result = send_with_timeline(proxy_state, request_options, cancelled, timeline).await;
+    if let Ok(response) = &mut result {
+        response.trace_ids = trace_ids;
+    }
+    result
+}
+
+async fn send_with_timeline(
+    proxy_state: &ProxyState,
+    mut request_options: RequestOptions,
+    cancelled: impl std::future::Future<Output = ()>,
+    timeline: &Timeline,
+) -> Result<ApiResponse, String> {
+    request_options.apply_ca_bundle();
     let start_time = Instant::now();
     let start_timestamp = chrono::Utc::now().timestamp_millis() as u64;
 
@@ -708,6 +1286,41 @@ pub async fn send_api_request(
             size: None,
             timings,
             cancelled: None,
+            confirmation_required: None,
+            time_budget: None,
+            untrusted_certificate: None,
+            content_sniff: None,
+            remote_address: None,
+            response_transform: None,
+            trace_ids: None,
+            idempotency_key: None,
+            body: None,
+        });
+    }
+
+    if let Err(message) = proxy_state.check_offline(&request_options.url) {
+        timeline.phase("failed");
+        return Ok(ApiResponse {
+            success: false,
+            data: None,
+            status: None,
+            status_text: None,
+            headers: HashMap::new(),
+            set_cookies: vec![],
+            message: Some(message),
+            ttfb: None,
+            size: None,
+            timings,
+            cancelled: None,
+            confirmation_required: None,
+            time_budget: None,
+            untrusted_certificate: None,
+            content_sniff: None,
+            remote_address: None,
+            response_transform: None,
+            trace_ids: None,
+            idempotency_key: None,
+            body: None,
         });
     }
 
@@ -719,10 +1332,12 @@ pub async fn send_api_request(
     // Probe is skipped when a proxy is active — measuring through a CONNECT
     // tunnel would require reimplementing proxy auth, which is out of scope.
     let parsed_url = url::Url::parse(&request_options.url).ok();
+    let ip_family = request_options.ip_family.unwrap_or_default();
     let is_https = request_options.url.starts_with("https://");
     // Resolve the proxy decision once: used below to skip the timing probe and
     // again when building the reqwest client.
-    let proxy_action = proxy_state.get_proxy_config(&request_options.url);
+    let proxy_action =
+        proxy_state.get_proxy_config(&request_options.url, request_options.proxy.as_ref());
     // Skip the timing probe through a proxy (would require CONNECT-tunnel auth)
     // and when client-cert/custom-CA material is configured (the probe uses the
     // default trust roots and no client auth, so it would fail or mislead).
@@ -731,6 +1346,15 @@ pub async fn send_api_request(
         .as_ref()
         .is_some_and(ClientCertConfig::is_active);
     let skip_probe = !matches!(proxy_action, ProxyAction::Disable) || client_cert_active;
+    // Certificates the user trusted for this host, unless verification is off
+    // altogether
+    let trusted = if request_options.verify_ssl == Some(false) {
+        Vec::new()
+    } else {
+        proxy_state
+            .trusted_hosts
+            .fingerprints_for(&request_options.url)
+    };
 
     if let Some(ref url) = parsed_url {
         if let Some(host) = url.host_str() {
@@ -740,97 +1364,44 @@ pub async fn send_api_request(
             let lookup_addr = format!("{}:{}", host, port);
 
             let dns_start = Instant::now();
-            let _ = tokio::net::lookup_host(&lookup_addr).await;
+            let addrs = tokio::net::lookup_host(&lookup_addr)
+                .await
+                .map(|addrs| order_addresses(addrs.collect(), ip_family))
+                .unwrap_or_default();
             timings.dns_lookup = dns_start.elapsed().as_millis() as u64;
+            timeline.phase("dns");
 
             if !skip_probe {
-                let verify_ssl = request_options.verify_ssl != Some(false);
+                // A trusted certificate would fail the probe's verification
+                let verify_ssl = request_options.verify_ssl != Some(false) && trusted.is_empty();
                 let (tcp_ms, tls_ms) =
-                    measure_connection_timings(host, port, is_https, verify_ssl).await;
+                    measure_connection_timings(host, &addrs, is_https, verify_ssl, timeline).await;
                 timings.tcp_connection = tcp_ms.unwrap_or(0);
                 timings.tls_handshake = tls_ms.unwrap_or(0);
             }
         }
     }
 
-    // Build client with optional proxy and HTTP version
-    // Use timeout from request options: None means no timeout, Some(0) also means no timeout
-    let mut client_builder =
-        Client::builder().user_agent(format!("resonance/{}", env!("CARGO_PKG_VERSION")));
-
-    // Only set timeout if provided and > 0
-    if let Some(timeout_ms) = request_options.timeout {
-        if timeout_ms > 0 {
-            client_builder = client_builder.timeout(Duration::from_millis(timeout_ms));
-        }
-    }
-
-    // Configure HTTP version based on settings
-    // Note: With rustls-tls + http2 feature, ALPN will negotiate HTTP/2 by default for HTTPS
-    match request_options.http_version.as_deref() {
-        Some("http1") => {
-            // Force HTTP/1.1 only - disable HTTP/2 completely
-            client_builder = client_builder.http1_only();
-        }
-        Some("http2") => {
-            // Force HTTP/2 with prior knowledge (no ALPN negotiation)
-            client_builder = client_builder.http2_prior_knowledge();
-        }
-        _ => {
-            // "auto" or unset - let ALPN negotiate
-        }
-    }
-
-    // Disable SSL verification if requested (e.g. for self-signed certs in dev)
-    if request_options.verify_ssl == Some(false) {
-        client_builder = client_builder.danger_accept_invalid_certs(true);
-    }
-
-    // Apply client certificate (mTLS) and/or custom CA trust resolved for this host.
-    if let Some(client_cert) = &request_options.client_cert {
-        match apply_client_cert(client_builder, client_cert) {
-            Ok(b) => client_builder = b,
-            Err(message) => {
-                return Ok(ApiResponse {
-                    success: false,
-                    data: None,
-                    status: None,
-                    status_text: None,
-                    headers: HashMap::new(),
-                    set_cookies: vec![],
-                    message: Some(message),
-                    ttfb: None,
-                    size: None,
-                    timings,
-                    cancelled: None,
-                });
-            }
-        }
-    }
-
-    // Disable redirect following if requested
-    if request_options.follow_redirects == Some(false) {
-        client_builder = client_builder.redirect(reqwest::redirect::Policy::none());
-    }
-
-    // Apply proxy decision resolved earlier. `Disable` must call `no_proxy()`
-    // explicitly — otherwise reqwest would still honour HTTP(S)_PROXY env vars
-    // and platform settings by default.
-    match proxy_action {
-        ProxyAction::Disable => {
-            client_builder = client_builder.no_proxy();
-        }
-        ProxyAction::UseSystem => {
-            // reqwest auto-detects system proxy; nothing to configure.
-        }
-        ProxyAction::Manual(proxy) => {
-            client_builder = client_builder.proxy(*proxy);
-        }
-    }
-
-    let client = match client_builder.build() {
+    // Clients are shared between requests with the same client settings, so
+    // their pooled connections are reused
+    let key = client_key(
+        &request_options,
+        ip_family,
+        &trusted,
+        &proxy_state.route_key(&request_options.url, request_options.proxy.as_ref()),
+        &proxy_state.clients,
+    );
+    let client = match proxy_state.clients.get_or_build(&key, || {
+        build_client(
+            &request_options,
+            ip_family,
+            &trusted,
+            proxy_action,
+            proxy_state.redirect_policy(),
+        )
+    }) {
         Ok(c) => c,
-        Err(e) => {
+        Err(message) => {
             return Ok(ApiResponse {
                 success: false,
                 data: None,
@@ -838,11 +1409,20 @@ pub async fn send_api_request(
                 status_text: None,
                 headers: HashMap::new(),
                 set_cookies: vec![],
-                message: Some(format!("Client build error: {}", e)),
+                message: Some(message),
                 ttfb: None,
                 size: None,
                 timings,
                 cancelled: None,
+                confirmation_required: None,
+                time_budget: None,
+                untrusted_certificate: None,
+                content_sniff: None,
+                remote_address: None,
+                response_transform: None,
+                trace_ids: None,
+                idempotency_key: None,
+                body: None,
             });
         }
     };
@@ -853,12 +1433,7 @@ pub async fn send_api_request(
         .parse::<Method>()
         .map_err(|e| format!("Invalid HTTP method: {}", e))?;
 
-    // Extract body_type before the closure to avoid borrow issues
-    let body_type = request_options
-        .body_type
-        .as_deref()
-        .unwrap_or("json")
-        .to_string();
+    let body_type = request_options.body_type.unwrap_or_default();
 
     // Detect whether the user already supplied a Content-Type header
     let user_has_content_type = request_options
@@ -877,7 +1452,7 @@ pub async fn send_api_request(
         // signature is not possible here (pre-existing limitation); other body
         // types keep the historical JSON serialization.
         let body_bytes = match &request_options.body {
-            Some(b) if request_options.body_type.as_deref() == Some("binary") => {
+            Some(b) if body_type == BodyType::Binary => {
                 let binary: BinaryBody = serde_json::from_value(b.clone())
                     .map_err(|e| format!("Invalid binary body: {}", e))?;
                 read_body_file(&binary.file_path)?
@@ -905,7 +1480,7 @@ pub async fn send_api_request(
         if let Some(headers) = &request_options.headers {
             for (key, value) in headers {
                 // Skip Content-Type for form modes — reqwest sets it automatically
-                if (body_type == "formdata" || body_type == "urlencoded")
+                if matches!(body_type, BodyType::Formdata | BodyType::Urlencoded)
                     && key.to_lowercase() == "content-type"
                 {
                     continue;
@@ -919,8 +1494,8 @@ pub async fn send_api_request(
                 rb = rb.header(key, value);
             }
         }
-        match body_type.as_str() {
-            "urlencoded" => {
+        match body_type {
+            BodyType::Urlencoded => {
                 if let Some(body) = &request_options.body {
                     if let Some(rows) = body.as_array() {
                         let pairs = form_rows_to_pairs(rows);
@@ -935,7 +1510,7 @@ pub async fn send_api_request(
                     }
                 }
             }
-            "formdata" => {
+            BodyType::Formdata => {
                 if let Some(body) = &request_options.body {
                     if let Some(rows) = body.as_array() {
                         rb = rb.multipart(build_multipart_form(rows)?);
@@ -949,7 +1524,7 @@ pub async fn send_api_request(
                     }
                 }
             }
-            "binary" => {
+            BodyType::Binary => {
                 if let Some(body) = &request_options.body {
                     let binary: BinaryBody = serde_json::from_value(body.clone())
                         .map_err(|e| format!("Invalid binary body: {}", e))?;
@@ -967,7 +1542,7 @@ pub async fn send_api_request(
                     }
                 }
             }
-            "text" => {
+            BodyType::Text => {
                 if let Some(body) = &request_options.body {
                     let raw = body.as_str().unwrap_or("").to_string();
                     rb = rb.body(raw);
@@ -976,7 +1551,7 @@ pub async fn send_api_request(
                     }
                 }
             }
-            _ => {
+            BodyType::Json => {
                 if let Some(body) = &request_options.body {
                     rb = rb.json(body);
                 }
@@ -988,8 +1563,10 @@ pub async fn send_api_request(
         Ok(rb)
     };
 
-    // Execute request with cancellation support
+    // Execute request with cancellation support. reqwest has no hook for when
+    // the request is written, so `requestSent` marks handing it to the client.
     let request_future = build_request(None)?.send();
+    timeline.phase("requestSent");
 
     tokio::select! {
         result = request_future => {
@@ -998,6 +1575,25 @@ pub async fn send_api_request(
                     // Check for 401 with Digest challenge - retry with auth if credentials provided
                     if response.status().as_u16() == 401 {
                         if let Some(auth_config) = &request_options.auth {
+                            let challenges = response.headers().get_all("www-authenticate");
+                            if auth_config.auth_type.as_deref() == Some("negotiate")
+                                && super::negotiate::offers_negotiate(
+                                    challenges.iter().filter_map(|v| v.to_str().ok()),
+                                )
+                            {
+                                let host = url::Url::parse(&request_options.url)
+                                    .ok()
+                                    .and_then(|u| u.host_str().map(str::to_string))
+                                    .ok_or_else(|| "Negotiate authentication needs a host name".to_string())?;
+                                let auth_header = tokio::task::spawn_blocking(move || {
+                                    super::negotiate::authorization_header(&host)
+                                })
+                                .await
+                                .map_err(|e| e.to_string())??;
+                                // Retry with the Kerberos token
+                                let retry_result = build_request(Some(auth_header))?.send().await;
+                                return process_response(retry_result, &mut timings, start_time, timeline).await;
+                            }
                             if let Some(www_auth) = response.headers().get("www-authenticate") {
                                 if let Ok(www_auth_str) = www_auth.to_str() {
                                     if let Some(challenge) = DigestChallenge::parse(www_auth_str) {
@@ -1013,7 +1609,7 @@ pub async fn send_api_request(
                                             Ok(auth_header) => {
                                                 // Retry with digest auth
                                                 let retry_result = build_request(Some(auth_header))?.send().await;
-                                                return process_response(retry_result, &mut timings, start_time, &state).await;
+                                                return process_response(retry_result, &mut timings, start_time, timeline).await;
                                             }
                                             Err(e) => {
                                                 let _ = e;
@@ -1025,16 +1621,16 @@ pub async fn send_api_request(
                         }
                     }
 
-                    process_response(Ok(response), &mut timings, start_time, &state).await
+                    process_response(Ok(response), &mut timings, start_time, timeline).await
                 }
                 Err(e) => {
-                    process_response(Err(e), &mut timings, start_time, &state).await
+                    process_response(Err(e), &mut timings, start_time, timeline).await
                 }
             }
         }
-        _ = cancel_rx => {
+        _ = cancelled => {
             timings.total = start_time.elapsed().as_millis() as u64;
-            *state.cancel_tx.lock().unwrap() = None;
+            timeline.phase("cancelled");
 
             Ok(ApiResponse {
                 success: false,
@@ -1048,23 +1644,61 @@ pub async fn send_api_request(
                 size: None,
                 timings,
                 cancelled: Some(true),
+                confirmation_required: None,
+                time_budget: None,
+                untrusted_certificate: None,
+                content_sniff: None,
+                remote_address: None,
+                response_transform: None,
+                trace_ids: None,
+                idempotency_key: None,
+                body: None,
             })
         }
     }
 }
 
+/// The pin mismatch a request failed with, if any. The handshake error is
+/// nested a few sources deep and its text carries the details.
+fn pin_mismatch(error: &reqwest::Error) -> Option<String> {
+    let mut current: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = current {
+        let text = e.to_string();
+        if let Some(start) = text.find(super::tls::PIN_MISMATCH) {
+            return Some(text[start..].to_string());
+        }
+        current = e.source();
+    }
+    None
+}
+
+/// The certificate verification error a request failed with, if any.
+fn certificate_problem(error: &reqwest::Error) -> Option<String> {
+    let mut current: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = current {
+        let text = e.to_string();
+        if let Some(start) = text.find("invalid peer certificate") {
+            return Some(text[start..].to_string());
+        }
+        current = e.source();
+    }
+    None
+}
+
 /// Process response and build ApiResponse
 async fn process_response(
     result: Result<Response, reqwest::Error>,
     timings: &mut RequestTimings,
     start_time: Instant,
-    state: &State<'_, RequestState>,
+    timeline: &Timeline,
 ) -> Result<ApiResponse, String> {
     match result {
-        Ok(response) => {
+        Ok(mut response) => {
             timings.first_byte = start_time.elapsed().as_millis() as u64;
+            timeline.phase("firstByte");
 
             let status = response.status().as_u16();
+            let remote_address = response.remote_addr().map(RemoteAddress::from);
             let status_text = response
                 .status()
                 .canonical_reason()
@@ -1086,12 +1720,31 @@ async fn process_response(
                 .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
                 .collect();
 
-            let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+            // Read chunk by chunk so the timeline can report download progress
+            let mut body = Vec::new();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        body.extend_from_slice(&chunk);
+                        timeline.chunk(body.len());
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        timeline.phase("failed");
+                        return Err(e.to_string());
+                    }
+                }
+            }
+            let bytes = bytes::Bytes::from(body);
             let size = bytes.len();
+            timeline.complete(size);
 
             timings.download = start_time.elapsed().as_millis() as u64 - timings.first_byte;
             timings.total = start_time.elapsed().as_millis() as u64;
 
+            let content_sniff =
+                sniff_content(&bytes, headers.get("content-type").map(String::as_str));
+
             // Try to parse as JSON first, fall back to raw text
             let data: Option<serde_json::Value> = serde_json::from_slice(&bytes).ok();
 
@@ -1109,8 +1762,6 @@ async fn process_response(
                 }
             };
 
-            *state.cancel_tx.lock().unwrap() = None;
-
             Ok(ApiResponse {
                 success: (200..300).contains(&status),
                 data,
@@ -1123,14 +1774,30 @@ async fn process_response(
                 size: Some(size),
                 timings: timings.clone(),
                 cancelled: None,
+                confirmation_required: None,
+                time_budget: None,
+                untrusted_certificate: None,
+                content_sniff,
+                remote_address,
+                response_transform: None,
+                trace_ids: None,
+                idempotency_key: None,
+                body: Some(bytes),
             })
         }
         Err(e) => {
             timings.total = start_time.elapsed().as_millis() as u64;
-            *state.cancel_tx.lock().unwrap() = None;
+            timeline.phase("failed");
 
             // Provide specific error messages for common error types
-            let message = if e.is_timeout() {
+            let certificate_problem = certificate_problem(&e);
+            let message = if let Some(mismatch) = pin_mismatch(&e) {
+                mismatch
+            } else if let Some(mismatch) = family_mismatch(&e) {
+                format!("Connection failed: {}.", mismatch)
+            } else if let Some(problem) = &certificate_problem {
+                format!("The server's certificate is not trusted: {}", problem)
+            } else if e.is_timeout() {
                 "Request timed out. Try increasing the timeout in settings.".to_string()
             } else if e.is_connect() {
                 "Connection failed. Check the URL and your network connection.".to_string()
@@ -1158,6 +1825,15 @@ async fn process_response(
                 size: None,
                 timings: timings.clone(),
                 cancelled: None,
+                confirmation_required: None,
+                time_budget: None,
+                untrusted_certificate: certificate_problem.is_some().then_some(true),
+                content_sniff: None,
+                remote_address: None,
+                response_transform: None,
+                trace_ids: None,
+                idempotency_key: None,
+                body: None,
             })
         }
     }
@@ -1220,6 +1896,7 @@ mod tests {
             cert_path: None,
             key_path: None,
             ca_path: Some(String::new()),
+            pins: Vec::new(),
         };
         assert!(!empty.is_active());
 
@@ -1227,6 +1904,7 @@ mod tests {
             cert_path: Some("/certs/client.crt".into()),
             key_path: Some("/certs/client.key".into()),
             ca_path: None,
+            pins: Vec::new(),
         };
         assert!(with_cert.is_active());
 
@@ -1234,10 +1912,70 @@ mod tests {
             cert_path: None,
             key_path: None,
             ca_path: Some("/certs/ca.pem".into()),
+            pins: Vec::new(),
         };
         assert!(ca_only.is_active());
     }
 
+    #[test]
+    fn settings_ca_bundle_applies_to_hosts_without_their_own() {
+        let mut options: RequestOptions = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "url": "https://dev.local/",
+            "caBundlePath": "/certs/dev-ca.pem"
+        }))
+        .unwrap();
+        options.apply_ca_bundle();
+        let cert = options.client_cert.as_ref().unwrap();
+        assert_eq!(cert.ca_path.as_deref(), Some("/certs/dev-ca.pem"));
+        assert!(cert.is_active());
+
+        options.client_cert = Some(ClientCertConfig {
+            cert_path: None,
+            key_path: None,
+            ca_path: Some("/certs/host-ca.pem".into()),
+            pins: Vec::new(),
+        });
+        options.ca_bundle_path = Some("/certs/dev-ca.pem".into());
+        options.apply_ca_bundle();
+        assert_eq!(
+            options.client_cert.unwrap().ca_path.as_deref(),
+            Some("/certs/host-ca.pem")
+        );
+    }
+
+    #[test]
+    fn replacing_the_ca_bundle_changes_the_client_key() {
+        let path = std::env::temp_dir().join(format!("resonance-ca-{}.pem", Uuid::new_v4()));
+        std::fs::write(&path, "first").unwrap();
+        let mut options: RequestOptions = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "url": "https://dev.local/"
+        }))
+        .unwrap();
+        options.ca_bundle_path = Some(path.to_string_lossy().to_string());
+        options.apply_ca_bundle();
+        // A fresh pool per key, so the file is not served from the cache
+        let key = || {
+            client_key(
+                &options,
+                IpFamily::Auto,
+                &[],
+                "direct",
+                &ClientPool::default(),
+            )
+        };
+        let before = key();
+        assert_eq!(key(), before);
+
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(std::time::UNIX_EPOCH + Duration::from_secs(1))
+            .unwrap();
+        assert_ne!(key(), before);
+        std::fs::remove_file(&path).unwrap();
+        assert!(key().contains("\"Err\""));
+    }
+
     #[test]
     fn form_part_deserializes_camel_case_rows() {
         let json = serde_json::json!({
@@ -1277,6 +2015,22 @@ mod tests {
         assert!(minimal.content_type.is_none());
     }
 
+    #[test]
+    fn body_type_accepts_aliases_and_serializes_canonical_names() {
+        let parse =
+            |name: &str| serde_json::from_value::<BodyType>(serde_json::json!(name)).unwrap();
+        assert_eq!(parse("json"), BodyType::Json);
+        assert_eq!(parse("raw"), BodyType::Text);
+        assert_eq!(parse("form-urlencoded"), BodyType::Urlencoded);
+        assert_eq!(parse("multipart"), BodyType::Formdata);
+        assert_eq!(parse("binary"), BodyType::Binary);
+        assert!(serde_json::from_value::<BodyType>(serde_json::json!("xml")).is_err());
+        assert_eq!(
+            serde_json::to_value(BodyType::Formdata).unwrap(),
+            serde_json::json!("formdata")
+        );
+    }
+
     #[test]
     fn build_multipart_form_mixes_text_and_file_parts() {
         let dir = std::env::temp_dir().join(format!("resonance-upload-test-{}", Uuid::new_v4()));
@@ -1341,6 +2095,7 @@ mod tests {
             cert_path: Some("/certs/client.crt".into()),
             key_path: None,
             ca_path: None,
+            pins: Vec::new(),
         };
         let err = apply_client_cert(Client::builder(), &cfg).unwrap_err();
         assert!(err.contains("both a certificate and a key"));
@@ -1352,6 +2107,7 @@ mod tests {
             cert_path: Some("/nonexistent/client.crt".into()),
             key_path: Some("/nonexistent/client.key".into()),
             ca_path: None,
+            pins: Vec::new(),
         };
         let err = apply_client_cert(Client::builder(), &cfg).unwrap_err();
         assert!(err.contains("could not be read"));
@@ -1363,6 +2119,7 @@ mod tests {
             cert_path: None,
             key_path: None,
             ca_path: None,
+            pins: Vec::new(),
         };
         // Should succeed and leave the builder usable.
         let builder = apply_client_cert(Client::builder(), &cfg).unwrap();
@@ -1424,6 +2181,7 @@ mod tests {
             cert_path: Some(cert_path.to_string_lossy().into()),
             key_path: Some(key_path.to_string_lossy().into()),
             ca_path: None,
+            pins: Vec::new(),
         };
         let builder = apply_client_cert(Client::builder(), &identity_cfg)
             .expect("client identity should load from real PEM");
@@ -1434,6 +2192,7 @@ mod tests {
             cert_path: None,
             key_path: None,
             ca_path: Some(cert_path.to_string_lossy().into()),
+            pins: Vec::new(),
         };
         let builder = apply_client_cert(Client::builder(), &ca_cfg)
             .expect("custom CA should load from real PEM");
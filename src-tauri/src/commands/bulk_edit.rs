@@ -0,0 +1,166 @@
+//! Bulk editing of headers and parameters as a raw `Key: Value` text block,
+//! one per line (like Postman's bulk edit), converted to and from the
+//! endpoint `parameters` shape (`{ name: { example } }`). Lines starting with
+//! `//` are disabled entries and are skipped.
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+
+const LOCATIONS: &[&str] = &["header", "query", "path"];
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditError {
+    /// 1-based line number in the text block
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkEditResult {
+    /// Entries in text order, as `{ name: { example } }`
+    pub parameters: Map<String, Value>,
+    /// Lines that were skipped; the other lines are still parsed
+    pub errors: Vec<BulkEditError>,
+}
+
+/// RFC 9110 token characters, which header names are made of. `{{variables}}`
+/// are allowed too and resolved at send time.
+fn is_valid_header_name(name: &str) -> bool {
+    static VARIABLE: OnceLock<Regex> = OnceLock::new();
+    let without_variables = VARIABLE
+        .get_or_init(|| Regex::new(r"\{\{[^}]*\}\}").unwrap())
+        .replace_all(name, "");
+    without_variables
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+fn parse_bulk_text(text: &str, location: &str) -> Result<BulkEditResult, String> {
+    if !LOCATIONS.contains(&location) {
+        return Err(format!("Unknown parameter location: {}", location));
+    }
+    let is_header = location == "header";
+
+    let mut parameters = Map::new();
+    let mut errors = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        let mut error = |message: String| {
+            errors.push(BulkEditError {
+                line: index + 1,
+                message,
+            })
+        };
+
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            // A bare name is a parameter with an empty value; headers need the colon
+            None if !is_header => (line, ""),
+            None => {
+                error(format!("Expected \"Name: Value\", got \"{}\"", line));
+                continue;
+            }
+        };
+        if key.is_empty() {
+            error("Missing name before \":\"".to_string());
+            continue;
+        }
+        if is_header && !is_valid_header_name(key) {
+            error(format!("\"{}\" is not a valid header name", key));
+            continue;
+        }
+        if is_header && value.chars().any(|c| c.is_control() && c != '\t') {
+            error(format!(
+                "The value of \"{}\" contains control characters",
+                key
+            ));
+            continue;
+        }
+        if parameters.contains_key(key) {
+            error(format!("\"{}\" is listed more than once", key));
+            continue;
+        }
+        parameters.insert(key.to_string(), serde_json::json!({ "example": value }));
+    }
+
+    Ok(BulkEditResult { parameters, errors })
+}
+
+/// Text block for `{ name: { example } }` parameters; plain `{ name: value }`
+/// maps are accepted too.
+fn serialize_bulk_text(parameters: &Value) -> Result<String, String> {
+    let parameters = parameters
+        .as_object()
+        .ok_or_else(|| "Parameters must be an object".to_string())?;
+    let lines: Vec<String> = parameters
+        .iter()
+        .map(|(name, param)| {
+            let value = match param.get("example").unwrap_or(param) {
+                Value::String(text) => text.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            format!("{}: {}", name, value).trim_end().to_string()
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+#[tauri::command]
+pub fn bulk_edit_parse(text: String, location: String) -> Result<BulkEditResult, String> {
+    parse_bulk_text(&text, &location)
+}
+
+#[tauri::command]
+pub fn bulk_edit_serialize(parameters: Value) -> Result<String, String> {
+    serialize_bulk_text(&parameters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_text_and_reports_bad_lines() {
+        let text = "Accept: application/json\n\
+                    // X-Disabled: 1\n\
+                    \n\
+                    Authorization: Bearer {{token}}\n\
+                    X-Time: 12:30\n\
+                    Bad Header: 1\n\
+                    NoColon\n\
+                    accept-language:\n\
+                    Accept: text/html";
+
+        let result = parse_bulk_text(text, "header").unwrap();
+        assert_eq!(
+            result.parameters.keys().collect::<Vec<_>>(),
+            vec!["Accept", "Authorization", "X-Time", "accept-language"]
+        );
+        assert_eq!(result.parameters["X-Time"]["example"], "12:30");
+        assert_eq!(
+            result.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![6, 7, 9]
+        );
+
+        assert_eq!(
+            serialize_bulk_text(&Value::Object(result.parameters)).unwrap(),
+            "Accept: application/json\n\
+             Authorization: Bearer {{token}}\n\
+             X-Time: 12:30\n\
+             accept-language:"
+        );
+
+        let query = parse_bulk_text("page: 2\nverbose", "query").unwrap();
+        assert!(query.errors.is_empty());
+        assert_eq!(query.parameters["verbose"]["example"], "");
+        assert!(parse_bulk_text("", "body").is_err());
+    }
+}
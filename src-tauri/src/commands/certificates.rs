@@ -3,12 +3,20 @@
 //! The certificate store itself lives in the frontend (persisted via the
 //! key-value store as `clientCertificates`, holding only file paths). The only
 //! backend surface needed is a native file dialog to choose PEM cert/key/CA
-//! files, mirroring [`grpc_select_proto_file`](super::grpc_proto::grpc_select_proto_file).
+//! files, mirroring [`grpc_select_proto_file`](super::grpc_proto::grpc_select_proto_file),
+//! plus a read-only host lookup for requests built in the backend.
 
+use serde_json::Value;
 use tauri::AppHandle;
 use tauri_plugin_dialog::{DialogExt, FilePath};
+use tauri_plugin_store::StoreExt;
 use tokio::sync::oneshot;
 
+use super::api_request::ClientCertConfig;
+use super::workspaces::store_file;
+
+const CERTIFICATES_KEY: &str = "clientCertificates";
+
 /// Open a file dialog to select a certificate-related PEM file.
 ///
 /// `kind` selects the dialog's file filter: `"cert"` for a client certificate
@@ -39,3 +47,85 @@ pub async fn pick_certificate_file(app: AppHandle, kind: String) -> Result<Optio
 
     rx.await.map_err(|e| format!("Dialog error: {}", e))
 }
+
+/// Backend counterpart of the frontend `CertificateService.getForHost`, used
+/// by requests that are built without the UI (e.g. the collection runner).
+///
+/// An exact `host:port` entry wins over a bare-host entry; disabled entries
+/// and entries without usable material are ignored.
+pub(crate) fn client_cert_for_host(app: &AppHandle, host_port: &str) -> Option<ClientCertConfig> {
+    let store = app.store(store_file(app)).ok()?;
+    saved_client_cert(|key| store.get(key), host_port)
+}
+
+/// [`client_cert_for_host`] from the entries `saved` returns by store key.
+pub(crate) fn saved_client_cert(
+    saved: impl Fn(&str) -> Option<Value>,
+    host_port: &str,
+) -> Option<ClientCertConfig> {
+    let items = saved(CERTIFICATES_KEY)?.get("items")?.as_array()?.clone();
+
+    let host_port = host_port.trim().to_lowercase();
+    let bare_host = host_port.split(':').next().unwrap_or("").to_string();
+    let non_empty = |entry: &Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    let usable: Vec<&Value> = items
+        .iter()
+        .filter(|entry| entry.get("enabled").and_then(Value::as_bool) != Some(false))
+        .filter(|entry| {
+            (non_empty(entry, "certPath").is_some() && non_empty(entry, "keyPath").is_some())
+                || non_empty(entry, "caPath").is_some()
+                || !pins_of(entry).is_empty()
+        })
+        .collect();
+    let host_of = |entry: &Value| {
+        entry
+            .get("host")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .trim()
+            .to_lowercase()
+    };
+
+    let entry = usable
+        .iter()
+        .find(|entry| host_of(entry) == host_port)
+        .or_else(|| usable.iter().find(|entry| host_of(entry) == bare_host))?;
+
+    Some(ClientCertConfig {
+        cert_path: non_empty(entry, "certPath"),
+        key_path: non_empty(entry, "keyPath"),
+        ca_path: non_empty(entry, "caPath"),
+        pins: pins_of(entry),
+    })
+}
+
+/// The `host[:port]` of `url` that certificate entries are matched against;
+/// the port only when the URL names one.
+pub(crate) fn url_host_port(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(
+        url.port()
+            .map_or(host.to_string(), |port| format!("{}:{}", host, port)),
+    )
+}
+
+/// The pins of a certificate entry, entered one per line or comma-separated.
+fn pins_of(entry: &Value) -> Vec<String> {
+    entry
+        .get("pins")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|pin| !pin.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,281 @@
+//! Shared HTTP clients and connection prewarming.
+//!
+//! Requests with the same client settings (timeout, HTTP version, TLS
+//! verification, certificates, redirect policy and proxy route) share one
+//! reqwest client, so its pooled keep-alive connections are reused instead of
+//! every send paying for DNS, TCP and TLS again. `connection_prewarm` builds
+//! that client ahead of time, without sending a request, and times the
+//! connection setup step by step on a connection of its own, to tell
+//! connection cost apart from server cost before a latency-sensitive demo.
+//! The pooled client only opens its connections with the first real request.
+
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+use tokio::net::TcpStream;
+use tokio::time::timeout as tokio_timeout;
+
+use super::api_request::{build_client, build_probe_tls_config, client_key, RequestOptions};
+use super::ip_family::order_addresses;
+use super::proxy::{ProxyAction, ProxyState};
+use super::tls::protocol_version_name;
+
+/// Clients kept at most; the pool starts over once this is reached, which only
+/// happens when settings keep changing.
+const MAX_CLIENTS: usize = 32;
+/// Time allowed for each prewarm step.
+const PREWARM_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a certificate file's modification time is trusted before the
+/// file is checked again.
+const FILE_VERSION_TTL: Duration = Duration::from_secs(2);
+
+/// When a file was last modified (ns since the epoch), or why that could not
+/// be read.
+pub type FileVersion = Result<u128, String>;
+
+#[derive(Default)]
+pub struct ClientPool {
+    clients: Mutex<HashMap<String, Client>>,
+    file_versions: Mutex<HashMap<String, (Instant, FileVersion)>>,
+}
+
+impl ClientPool {
+    /// The client for `key`, built with `build` the first time it is needed.
+    pub fn get_or_build(
+        &self,
+        key: &str,
+        build: impl FnOnce() -> Result<Client, String>,
+    ) -> Result<Client, String> {
+        if let Some(client) = self.clients.lock().unwrap().get(key) {
+            return Ok(client.clone());
+        }
+        let client = build()?;
+        let mut clients = self.clients.lock().unwrap();
+        if clients.len() >= MAX_CLIENTS {
+            clients.clear();
+        }
+        Ok(clients.entry(key.to_string()).or_insert(client).clone())
+    }
+
+    /// The modification time of the file at `path`, read from disk at most
+    /// once per [`FILE_VERSION_TTL`] so sends don't stat certificates every
+    /// time.
+    pub fn file_version(&self, path: &str) -> FileVersion {
+        let mut versions = self.file_versions.lock().unwrap();
+        if let Some((checked, version)) = versions.get(path) {
+            if checked.elapsed() < FILE_VERSION_TTL {
+                return version.clone();
+            }
+        }
+        let version = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|e| e.kind().to_string())
+            .map(|modified| {
+                modified
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos()
+            });
+        versions.insert(path.to_string(), (Instant::now(), version.clone()));
+        version
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PrewarmReport {
+    /// Addresses the host resolved to, in the order they are tried
+    pub addresses: Vec<String>,
+    pub dns_lookup: u64,
+    /// `None` when the connection goes through a proxy, which connects itself
+    pub tcp_connection: Option<u64>,
+    pub tls_handshake: Option<u64>,
+    /// Protocol the server picked through ALPN (`h2`, `http/1.1`)
+    pub alpn: Option<String>,
+    pub tls_version: Option<String>,
+}
+
+/// ALPN protocols offered for a request's HTTP version setting.
+fn alpn_protocols(http_version: Option<&str>) -> Vec<Vec<u8>> {
+    match http_version {
+        Some("http1") => vec![b"http/1.1".to_vec()],
+        Some("http2") => vec![b"h2".to_vec()],
+        _ => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+    }
+}
+
+/// Resolve, connect and complete the TLS handshake (with ALPN) to `host` on a
+/// connection of its own, timing each step. Through a proxy only the lookup is
+/// timed, since the proxy connects itself.
+async fn measure_connection(
+    options: &RequestOptions,
+    host: &str,
+    port: u16,
+    direct: bool,
+    verify: bool,
+) -> Result<PrewarmReport, String> {
+    let mut report = PrewarmReport::default();
+    let dns_start = Instant::now();
+    let addrs = tokio_timeout(PREWARM_TIMEOUT, tokio::net::lookup_host((host, port)))
+        .await
+        .map_err(|_| format!("DNS lookup of {} timed out", host))?
+        .map_err(|e| format!("DNS lookup of {} failed: {}", host, e))?;
+    let addrs = order_addresses(addrs.collect(), options.ip_family.unwrap_or_default());
+    report.dns_lookup = dns_start.elapsed().as_millis() as u64;
+    report.addresses = addrs.iter().map(ToString::to_string).collect();
+    if !direct {
+        return Ok(report);
+    }
+
+    let tcp_start = Instant::now();
+    let stream = tokio_timeout(PREWARM_TIMEOUT, TcpStream::connect(addrs.as_slice()))
+        .await
+        .map_err(|_| format!("Connecting to {} timed out", host))?
+        .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+    report.tcp_connection = Some(tcp_start.elapsed().as_millis() as u64);
+    if !options.url.starts_with("https://") {
+        return Ok(report);
+    }
+
+    let server_name = rustls::pki_types::ServerName::try_from(host.to_string())
+        .map_err(|e| format!("Invalid TLS server name {}: {}", host, e))?;
+    let mut config = build_probe_tls_config(verify);
+    config.alpn_protocols = alpn_protocols(options.http_version.as_deref());
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    let tls_start = Instant::now();
+    let tls = tokio_timeout(PREWARM_TIMEOUT, connector.connect(server_name, stream))
+        .await
+        .map_err(|_| format!("TLS handshake with {} timed out", host))?
+        .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+    report.tls_handshake = Some(tls_start.elapsed().as_millis() as u64);
+    let (_, session) = tls.get_ref();
+    report.alpn = session
+        .alpn_protocol()
+        .map(|protocol| String::from_utf8_lossy(protocol).to_string());
+    report.tls_version = session.protocol_version().map(protocol_version_name);
+    Ok(report)
+}
+
+/// Build and pool the shared client requests with `options` will use, then
+/// time the connection setup to `url` without sending a request. Without
+/// `options` the default request settings apply.
+#[tauri::command]
+pub async fn connection_prewarm(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    url: String,
+    options: Option<RequestOptions>,
+) -> Result<PrewarmReport, String> {
+    let mut options = match options {
+        Some(options) => options,
+        None => serde_json::from_value(serde_json::json!({ "method": "GET", "url": "" }))
+            .map_err(|e| format!("Invalid request options: {}", e))?,
+    };
+    options.url = url;
+    if let Some(collection_id) = options.collection_id.as_deref() {
+        options.proxy = super::collections::collection_proxy(&app, collection_id)?;
+    }
+    if options.ca_bundle_path.is_none() {
+        options.ca_bundle_path = super::runner::load_runner_settings(&app).ca_bundle_path;
+    }
+    options.apply_ca_bundle();
+    proxy_state.check_offline(&options.url)?;
+
+    let parsed = url::Url::parse(&options.url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = parsed.host_str().ok_or("URL has no host")?.to_string();
+    let port = parsed.port_or_known_default().ok_or("URL has no port")?;
+
+    let ip_family = options.ip_family.unwrap_or_default();
+    let trusted = if options.verify_ssl == Some(false) {
+        Vec::new()
+    } else {
+        proxy_state.trusted_hosts.fingerprints_for(&options.url)
+    };
+    let route = proxy_state.route_key(&options.url, options.proxy.as_ref());
+    let proxy_action = proxy_state.get_proxy_config(&options.url, options.proxy.as_ref());
+    let direct = matches!(proxy_action, ProxyAction::Disable);
+    let key = client_key(&options, ip_family, &trusted, &route, &proxy_state.clients);
+    proxy_state.clients.get_or_build(&key, || {
+        build_client(
+            &options,
+            ip_family,
+            &trusted,
+            proxy_action,
+            proxy_state.redirect_policy(),
+        )
+    })?;
+
+    // Trusted certificates would fail the default verification
+    let verify = options.verify_ssl != Some(false) && trusted.is_empty();
+    measure_connection(&options, &host, port, direct, verify).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_reuses_clients_per_key() {
+        let pool = ClientPool::default();
+        let mut builds = 0;
+        for key in ["a", "a", "b"] {
+            pool.get_or_build(key, || {
+                builds += 1;
+                Ok(Client::new())
+            })
+            .unwrap();
+        }
+        assert_eq!(builds, 2);
+
+        assert!(pool
+            .get_or_build("c", || Err("Client build error".to_string()))
+            .is_err());
+        assert_eq!(pool.clients.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn file_versions_are_cached_and_failures_reported() {
+        let pool = ClientPool::default();
+        let path =
+            std::env::temp_dir().join(format!("resonance-cert-{}.pem", uuid::Uuid::new_v4()));
+        let path_str = path.to_string_lossy().to_string();
+        assert!(pool.file_version(&path_str).is_err());
+
+        std::fs::write(&path, "cert").unwrap();
+        // Still the cached failure until the entry expires
+        assert!(pool.file_version(&path_str).is_err());
+        assert!(ClientPool::default().file_version(&path_str).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn plain_http_is_timed_up_to_the_tcp_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options: RequestOptions = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "url": format!("http://127.0.0.1:{}/", port)
+        }))
+        .unwrap();
+        let report = measure_connection(&options, "127.0.0.1", port, true, true)
+            .await
+            .unwrap();
+        assert_eq!(report.addresses, vec![format!("127.0.0.1:{}", port)]);
+        assert!(report.tcp_connection.is_some());
+        assert_eq!(report.tls_handshake, None);
+
+        let proxied = measure_connection(&options, "127.0.0.1", port, false, true)
+            .await
+            .unwrap();
+        assert_eq!(proxied.tcp_connection, None);
+    }
+
+    #[test]
+    fn alpn_follows_the_http_version_setting() {
+        assert_eq!(alpn_protocols(Some("http1")), vec![b"http/1.1".to_vec()]);
+        assert_eq!(alpn_protocols(None).len(), 2);
+    }
+}
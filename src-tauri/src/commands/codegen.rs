@@ -0,0 +1,1060 @@
+//! Code snippets for a request: curl, JavaScript `fetch`, Python `requests`,
+//! Go `net/http` and Rust `reqwest`.
+//!
+//! Snippets are generated from the same [`RequestOptions`] `send_api_request`
+//! takes and prepared the same way, so inherited auth, the collection's proxy,
+//! TLS and redirect settings and the body encoding all show up in the code.
+//! Settings a client cannot express are listed in a comment at the top instead
+//! of being dropped silently. Values produced per send (idempotency keys,
+//! trace headers) are left out, and `{{$secret:...}}` references in the
+//! request stay as written so snippets don't leak stored secrets.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use tauri::{AppHandle, State};
+
+use super::api_request::{AwsAuthConfig, BinaryBody, BodyType, FormPart, RequestOptions};
+use super::proxy::ProxyState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodegenLanguage {
+    Curl,
+    JavascriptFetch,
+    Python,
+    Go,
+    Rust,
+}
+
+enum Body {
+    /// JSON, text and urlencoded bodies, as the exact string sent
+    Raw(String),
+    /// Urlencoded rows, kept as pairs for clients with a form API
+    Form(Vec<(String, String)>),
+    Multipart(Vec<Part>),
+    File(String),
+}
+
+enum Part {
+    Text {
+        key: String,
+        value: String,
+    },
+    File {
+        key: String,
+        path: String,
+        content_type: String,
+    },
+}
+
+/// Scheme answering a 401 challenge, from `RequestOptions::auth`.
+enum ChallengeAuth {
+    Digest(String, String),
+    Negotiate,
+}
+
+/// A request resolved down to what goes over the wire.
+struct Snippet {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<Body>,
+    challenge: Option<ChallengeAuth>,
+    aws: Option<AwsAuthConfig>,
+    proxy: Option<String>,
+    insecure: bool,
+    follow_redirects: bool,
+    timeout_ms: Option<u64>,
+    http_version: Option<String>,
+    cert: Option<(String, String)>,
+    ca_path: Option<String>,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+fn file_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "file".to_string())
+}
+
+/// Form rows in the array shape, or the legacy flat `{key: value}` object.
+fn form_parts(body: &Value) -> Vec<FormPart> {
+    if let Some(rows) = body.as_array() {
+        return rows
+            .iter()
+            .filter_map(|row| serde_json::from_value(row.clone()).ok())
+            .collect();
+    }
+    body.as_object()
+        .into_iter()
+        .flatten()
+        .map(|(key, value)| FormPart {
+            key: key.clone(),
+            value: Some(value.as_str().unwrap_or("").to_string()),
+            part_type: None,
+            file_path: None,
+            content_type: None,
+        })
+        .collect()
+}
+
+fn urlencode(pairs: &[(String, String)]) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish()
+}
+
+impl Snippet {
+    /// Mirror the header and body handling of `send_with_timeline`.
+    fn new(options: &RequestOptions, proxy: Option<String>) -> Result<Self, String> {
+        let body_type = options.body_type.unwrap_or_default();
+        let mut headers: Vec<(String, String)> = options
+            .headers
+            .iter()
+            .flatten()
+            .filter(|(key, _)| {
+                // Form bodies get their Content-Type from the client
+                !(matches!(body_type, BodyType::Formdata | BodyType::Urlencoded)
+                    && key.eq_ignore_ascii_case("content-type"))
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        headers.sort_by_key(|(key, _)| key.to_lowercase());
+        let has_content_type = headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case("content-type"));
+        let content_type = |headers: &mut Vec<(String, String)>, value: &str| {
+            if !has_content_type {
+                headers.push(("Content-Type".to_string(), value.to_string()));
+            }
+        };
+
+        let body = match (&options.body, body_type) {
+            (None, _) => None,
+            (Some(body), BodyType::Json) => {
+                content_type(&mut headers, "application/json");
+                Some(Body::Raw(body.to_string()))
+            }
+            (Some(body), BodyType::Text) => {
+                content_type(&mut headers, "text/plain");
+                Some(Body::Raw(body.as_str().unwrap_or("").to_string()))
+            }
+            (Some(body), BodyType::Urlencoded) => Some(Body::Form(
+                form_parts(body)
+                    .into_iter()
+                    .map(|part| (part.key, part.value.unwrap_or_default()))
+                    .collect(),
+            )),
+            (Some(body), BodyType::Formdata) => Some(Body::Multipart(
+                form_parts(body)
+                    .into_iter()
+                    .map(|part| match part.part_type.as_deref() {
+                        Some("file") => Part::File {
+                            key: part.key,
+                            path: part.file_path.unwrap_or_default(),
+                            content_type: non_empty(part.content_type.as_deref())
+                                .unwrap_or_else(|| "application/octet-stream".to_string()),
+                        },
+                        _ => Part::Text {
+                            key: part.key,
+                            value: part.value.unwrap_or_default(),
+                        },
+                    })
+                    .collect(),
+            )),
+            (Some(body), BodyType::Binary) => {
+                let binary: BinaryBody = serde_json::from_value(body.clone())
+                    .map_err(|e| format!("Invalid binary body: {}", e))?;
+                content_type(
+                    &mut headers,
+                    &non_empty(binary.content_type.as_deref())
+                        .unwrap_or_else(|| "application/octet-stream".to_string()),
+                );
+                Some(Body::File(binary.file_path))
+            }
+        };
+
+        let challenge = options
+            .auth
+            .as_ref()
+            .map(|auth| match auth.auth_type.as_deref() {
+                Some("negotiate") => ChallengeAuth::Negotiate,
+                _ => ChallengeAuth::Digest(auth.username.clone(), auth.password.clone()),
+            });
+        let client_cert = options.client_cert.as_ref();
+        let cert = client_cert.and_then(|cert| {
+            Some((
+                non_empty(cert.cert_path.as_deref())?,
+                non_empty(cert.key_path.as_deref())?,
+            ))
+        });
+
+        Ok(Self {
+            method: options.method.clone(),
+            url: options.url.clone(),
+            headers,
+            body,
+            challenge,
+            aws: options.aws_auth.clone(),
+            proxy,
+            insecure: options.verify_ssl == Some(false),
+            follow_redirects: options.follow_redirects != Some(false),
+            timeout_ms: options.timeout.filter(|ms| *ms > 0),
+            http_version: options
+                .http_version
+                .clone()
+                .filter(|v| v == "http1" || v == "http2"),
+            cert,
+            ca_path: client_cert.and_then(|cert| non_empty(cert.ca_path.as_deref())),
+        })
+    }
+
+    fn render(&self, language: CodegenLanguage) -> String {
+        let mut notes = Vec::new();
+        let code = match language {
+            CodegenLanguage::Curl => self.curl(),
+            CodegenLanguage::JavascriptFetch => self.javascript_fetch(&mut notes),
+            CodegenLanguage::Python => self.python(&mut notes),
+            CodegenLanguage::Go => self.go(&mut notes),
+            CodegenLanguage::Rust => self.rust(&mut notes),
+        };
+        if notes.is_empty() {
+            return code;
+        }
+        let comment = match language {
+            CodegenLanguage::Curl | CodegenLanguage::Python => "#",
+            _ => "//",
+        };
+        let mut header = format!("{} Not expressed in this snippet:\n", comment);
+        for note in notes {
+            header.push_str(&format!("{} - {}\n", comment, note));
+        }
+        format!("{}\n{}", header, code)
+    }
+
+    fn note_challenge(&self, notes: &mut Vec<String>) {
+        match &self.challenge {
+            Some(ChallengeAuth::Digest(..)) => notes.push("digest authentication".to_string()),
+            Some(ChallengeAuth::Negotiate) => {
+                notes.push("Negotiate (Kerberos) authentication".to_string())
+            }
+            None => {}
+        }
+    }
+
+    fn note_aws(&self, notes: &mut Vec<String>) {
+        if self.aws.is_some() {
+            notes.push("AWS Signature V4 signing".to_string());
+        }
+    }
+
+    fn timeout_secs(&self) -> Option<String> {
+        self.timeout_ms.map(|ms| (ms as f64 / 1000.0).to_string())
+    }
+
+    fn curl(&self) -> String {
+        let mut parts = vec!["curl".to_string()];
+        if self.method.eq_ignore_ascii_case("HEAD") {
+            parts.push("--head".to_string());
+        } else if !self.method.eq_ignore_ascii_case("GET") || self.body.is_some() {
+            parts.push(format!("-X {}", self.method));
+        }
+        if self.follow_redirects {
+            parts.push("-L".to_string());
+        }
+        if self.insecure {
+            parts.push("-k".to_string());
+        }
+        match self.http_version.as_deref() {
+            Some("http1") => parts.push("--http1.1".to_string()),
+            Some("http2") => parts.push("--http2-prior-knowledge".to_string()),
+            _ => {}
+        }
+        if let Some(secs) = self.timeout_secs() {
+            parts.push(format!("--max-time {}", secs));
+        }
+        if let Some(proxy) = &self.proxy {
+            parts.push(format!("--proxy {}", shell_quote(proxy)));
+        }
+        if let Some((cert, key)) = &self.cert {
+            parts.push(format!("--cert {}", shell_quote(cert)));
+            parts.push(format!("--key {}", shell_quote(key)));
+        }
+        if let Some(ca) = &self.ca_path {
+            parts.push(format!("--cacert {}", shell_quote(ca)));
+        }
+        match &self.challenge {
+            Some(ChallengeAuth::Digest(user, password)) => {
+                parts.push("--digest".to_string());
+                parts.push(format!(
+                    "-u {}",
+                    shell_quote(&format!("{}:{}", user, password))
+                ));
+            }
+            Some(ChallengeAuth::Negotiate) => parts.push("--negotiate -u :".to_string()),
+            None => {}
+        }
+        if let Some(aws) = &self.aws {
+            parts.push(format!(
+                "--aws-sigv4 {}",
+                shell_quote(&format!("aws:amz:{}:{}", aws.region, aws.service))
+            ));
+            parts.push(format!(
+                "--user {}",
+                shell_quote(&format!("{}:{}", aws.access_key_id, aws.secret_access_key))
+            ));
+            if let Some(token) = &aws.session_token {
+                parts.push(format!(
+                    "-H {}",
+                    shell_quote(&format!("x-amz-security-token: {}", token))
+                ));
+            }
+        }
+        for (key, value) in &self.headers {
+            parts.push(format!(
+                "-H {}",
+                shell_quote(&format!("{}: {}", key, value))
+            ));
+        }
+        match &self.body {
+            Some(Body::Raw(text)) => parts.push(format!("--data-raw {}", shell_quote(text))),
+            Some(Body::Form(pairs)) => {
+                parts.push(format!("--data-raw {}", shell_quote(&urlencode(pairs))))
+            }
+            Some(Body::Multipart(form)) => {
+                for part in form {
+                    parts.push(match part {
+                        Part::Text { key, value } => {
+                            format!(
+                                "--form-string {}",
+                                shell_quote(&format!("{}={}", key, value))
+                            )
+                        }
+                        Part::File {
+                            key,
+                            path,
+                            content_type,
+                        } => format!(
+                            "-F {}",
+                            shell_quote(&format!("{}=@{};type={}", key, path, content_type))
+                        ),
+                    });
+                }
+            }
+            Some(Body::File(path)) => parts.push(format!(
+                "--data-binary {}",
+                shell_quote(&format!("@{}", path))
+            )),
+            None => {}
+        }
+        parts.push(shell_quote(&self.url));
+        parts.join(" \\\n  ")
+    }
+
+    fn javascript_fetch(&self, notes: &mut Vec<String>) -> String {
+        if let Some(proxy) = &self.proxy {
+            notes.push(format!("the proxy {}", proxy));
+        }
+        if self.insecure {
+            notes.push("disabled TLS certificate verification".to_string());
+        }
+        if self.cert.is_some() || self.ca_path.is_some() {
+            notes.push("the client certificate and CA bundle".to_string());
+        }
+        if let Some(version) = &self.http_version {
+            notes.push(format!("forcing {}", http_version_label(version)));
+        }
+        self.note_challenge(notes);
+        self.note_aws(notes);
+
+        let mut lines = Vec::new();
+        let reads_files = match &self.body {
+            Some(Body::File(_)) => true,
+            Some(Body::Multipart(form)) => form.iter().any(|p| matches!(p, Part::File { .. })),
+            _ => false,
+        };
+        if reads_files {
+            lines.push("import { openAsBlob } from 'node:fs';".to_string());
+            lines.push(String::new());
+        }
+        let body = match &self.body {
+            Some(Body::Raw(text)) => Some(quote(text)),
+            Some(Body::Form(pairs)) => Some(format!(
+                "new URLSearchParams([{}])",
+                pairs
+                    .iter()
+                    .map(|(k, v)| format!("[{}, {}]", quote(k), quote(v)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Some(Body::Multipart(form)) => {
+                lines.push("const form = new FormData();".to_string());
+                for part in form {
+                    lines.push(match part {
+                        Part::Text { key, value } => {
+                            format!("form.append({}, {});", quote(key), quote(value))
+                        }
+                        Part::File {
+                            key,
+                            path,
+                            content_type,
+                        } => format!(
+                            "form.append({}, await openAsBlob({}, {{ type: {} }}), {});",
+                            quote(key),
+                            quote(path),
+                            quote(content_type),
+                            quote(&file_name(path))
+                        ),
+                    });
+                }
+                lines.push(String::new());
+                Some("form".to_string())
+            }
+            Some(Body::File(path)) => Some(format!("await openAsBlob({})", quote(path))),
+            None => None,
+        };
+
+        lines.push(format!(
+            "const response = await fetch({}, {{",
+            quote(&self.url)
+        ));
+        lines.push(format!("  method: {},", quote(&self.method)));
+        if !self.headers.is_empty() {
+            lines.push("  headers: {".to_string());
+            for (key, value) in &self.headers {
+                lines.push(format!("    {}: {},", quote(key), quote(value)));
+            }
+            lines.push("  },".to_string());
+        }
+        if let Some(body) = body {
+            lines.push(format!("  body: {},", body));
+        }
+        if !self.follow_redirects {
+            lines.push("  redirect: 'manual',".to_string());
+        }
+        if let Some(ms) = self.timeout_ms {
+            lines.push(format!("  signal: AbortSignal.timeout({}),", ms));
+        }
+        lines.push("});".to_string());
+        lines.push(String::new());
+        lines.push("console.log(response.status);".to_string());
+        lines.push("console.log(await response.text());".to_string());
+        lines.join("\n")
+    }
+
+    fn python(&self, notes: &mut Vec<String>) -> String {
+        if self.http_version.as_deref() == Some("http2") {
+            notes.push("forcing HTTP/2 (requests only speaks HTTP/1.1)".to_string());
+        }
+        if matches!(self.challenge, Some(ChallengeAuth::Negotiate)) {
+            notes.push("Negotiate (Kerberos) authentication".to_string());
+        }
+        self.note_aws(notes);
+
+        let digest = match &self.challenge {
+            Some(ChallengeAuth::Digest(user, password)) => Some((user, password)),
+            _ => None,
+        };
+        let mut lines = vec!["import requests".to_string()];
+        if digest.is_some() {
+            lines.push("from requests.auth import HTTPDigestAuth".to_string());
+        }
+        lines.push(String::new());
+        lines.push(format!("url = {}", quote(&self.url)));
+
+        let mut args = vec![quote(&self.method), "url".to_string()];
+        if !self.headers.is_empty() {
+            lines.push("headers = {".to_string());
+            for (key, value) in &self.headers {
+                lines.push(format!("    {}: {},", quote(key), quote(value)));
+            }
+            lines.push("}".to_string());
+            args.push("headers=headers".to_string());
+        }
+        match &self.body {
+            Some(Body::Raw(text)) => {
+                lines.push(format!("data = {}", quote(text)));
+                args.push("data=data.encode()".to_string());
+            }
+            Some(Body::Form(pairs)) => {
+                lines.push("data = [".to_string());
+                for (key, value) in pairs {
+                    lines.push(format!("    ({}, {}),", quote(key), quote(value)));
+                }
+                lines.push("]".to_string());
+                args.push("data=data".to_string());
+            }
+            Some(Body::Multipart(form)) => {
+                lines.push("files = [".to_string());
+                for part in form {
+                    lines.push(match part {
+                        Part::Text { key, value } => {
+                            format!("    ({}, (None, {})),", quote(key), quote(value))
+                        }
+                        Part::File {
+                            key,
+                            path,
+                            content_type,
+                        } => format!(
+                            "    ({}, ({}, open({}, \"rb\"), {})),",
+                            quote(key),
+                            quote(&file_name(path)),
+                            quote(path),
+                            quote(content_type)
+                        ),
+                    });
+                }
+                lines.push("]".to_string());
+                args.push("files=files".to_string());
+            }
+            Some(Body::File(path)) => {
+                lines.push(format!("data = open({}, \"rb\")", quote(path)));
+                args.push("data=data".to_string());
+            }
+            None => {}
+        }
+        if let Some((user, password)) = digest {
+            args.push(format!(
+                "auth=HTTPDigestAuth({}, {})",
+                quote(user),
+                quote(password)
+            ));
+        }
+        if let Some(proxy) = &self.proxy {
+            lines.push(format!(
+                "proxies = {{\"http\": {0}, \"https\": {0}}}",
+                quote(proxy)
+            ));
+            args.push("proxies=proxies".to_string());
+        }
+        if self.insecure {
+            args.push("verify=False".to_string());
+        } else if let Some(ca) = &self.ca_path {
+            args.push(format!("verify={}", quote(ca)));
+        }
+        if let Some((cert, key)) = &self.cert {
+            args.push(format!("cert=({}, {})", quote(cert), quote(key)));
+        }
+        if !self.follow_redirects {
+            args.push("allow_redirects=False".to_string());
+        }
+        if let Some(secs) = self.timeout_secs() {
+            args.push(format!("timeout={}", secs));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("response = requests.request({})", args.join(", ")));
+        lines.push(String::new());
+        lines.push("print(response.status_code)".to_string());
+        lines.push("print(response.text)".to_string());
+        lines.join("\n")
+    }
+
+    fn go(&self, notes: &mut Vec<String>) -> String {
+        if self.cert.is_some() || self.ca_path.is_some() {
+            notes.push("the client certificate and CA bundle".to_string());
+        }
+        if self.http_version.as_deref() == Some("http2") {
+            notes.push("forcing HTTP/2 with prior knowledge".to_string());
+        }
+        self.note_challenge(notes);
+        self.note_aws(notes);
+
+        let mut imports: BTreeSet<&str> = ["fmt", "io", "log", "net/http"].into();
+        let mut lines = vec!["func main() {".to_string()];
+        let body = match &self.body {
+            Some(Body::Raw(text)) => {
+                imports.insert("strings");
+                lines.push(format!("\tbody := strings.NewReader({})", quote(text)));
+                "body"
+            }
+            Some(Body::Form(pairs)) => {
+                imports.insert("strings");
+                lines.push(format!(
+                    "\tbody := strings.NewReader({})",
+                    quote(&urlencode(pairs))
+                ));
+                "body"
+            }
+            Some(Body::Multipart(form)) => {
+                imports.extend(["bytes", "mime/multipart"]);
+                lines.push("\tbody := &bytes.Buffer{}".to_string());
+                lines.push("\tform := multipart.NewWriter(body)".to_string());
+                for part in form {
+                    match part {
+                        Part::Text { key, value } => lines.push(format!(
+                            "\tform.WriteField({}, {})",
+                            quote(key),
+                            quote(value)
+                        )),
+                        Part::File {
+                            key,
+                            path,
+                            content_type,
+                        } => {
+                            imports.extend(["net/textproto", "os"]);
+                            let disposition = format!(
+                                "form-data; name=\"{}\"; filename=\"{}\"",
+                                key,
+                                file_name(path)
+                            );
+                            lines.extend([
+                                "\t{".to_string(),
+                                "\t\theader := make(textproto.MIMEHeader)".to_string(),
+                                format!(
+                                    "\t\theader.Set(\"Content-Disposition\", {})",
+                                    quote(&disposition)
+                                ),
+                                format!(
+                                    "\t\theader.Set(\"Content-Type\", {})",
+                                    quote(content_type)
+                                ),
+                                "\t\tpart, err := form.CreatePart(header)".to_string(),
+                                "\t\tif err != nil {".to_string(),
+                                "\t\t\tlog.Fatal(err)".to_string(),
+                                "\t\t}".to_string(),
+                                format!("\t\tfile, err := os.Open({})", quote(path)),
+                                "\t\tif err != nil {".to_string(),
+                                "\t\t\tlog.Fatal(err)".to_string(),
+                                "\t\t}".to_string(),
+                                "\t\tio.Copy(part, file)".to_string(),
+                                "\t\tfile.Close()".to_string(),
+                                "\t}".to_string(),
+                            ]);
+                        }
+                    }
+                }
+                lines.push("\tform.Close()".to_string());
+                "body"
+            }
+            Some(Body::File(path)) => {
+                imports.insert("os");
+                lines.extend([
+                    format!("\tbody, err := os.Open({})", quote(path)),
+                    "\tif err != nil {".to_string(),
+                    "\t\tlog.Fatal(err)".to_string(),
+                    "\t}".to_string(),
+                    "\tdefer body.Close()".to_string(),
+                ]);
+                "body"
+            }
+            None => "nil",
+        };
+        if body != "nil" {
+            lines.push(String::new());
+        }
+
+        lines.extend([
+            format!(
+                "\treq, err := http.NewRequest({}, {}, {})",
+                quote(&self.method),
+                quote(&self.url),
+                body
+            ),
+            "\tif err != nil {".to_string(),
+            "\t\tlog.Fatal(err)".to_string(),
+            "\t}".to_string(),
+        ]);
+        for (key, value) in &self.headers {
+            lines.push(format!(
+                "\treq.Header.Set({}, {})",
+                quote(key),
+                quote(value)
+            ));
+        }
+        if matches!(self.body, Some(Body::Multipart(_))) {
+            lines
+                .push("\treq.Header.Set(\"Content-Type\", form.FormDataContentType())".to_string());
+        } else if matches!(self.body, Some(Body::Form(_))) {
+            lines.push(
+                "\treq.Header.Set(\"Content-Type\", \"application/x-www-form-urlencoded\")"
+                    .to_string(),
+            );
+        }
+        lines.push(String::new());
+
+        let mut transport = Vec::new();
+        if let Some(proxy) = &self.proxy {
+            imports.insert("net/url");
+            lines.extend([
+                format!("\tproxyURL, err := url.Parse({})", quote(proxy)),
+                "\tif err != nil {".to_string(),
+                "\t\tlog.Fatal(err)".to_string(),
+                "\t}".to_string(),
+            ]);
+            transport.push("\t\t\tProxy: http.ProxyURL(proxyURL),".to_string());
+        }
+        if self.insecure {
+            imports.insert("crypto/tls");
+            transport
+                .push("\t\t\tTLSClientConfig: &tls.Config{InsecureSkipVerify: true},".to_string());
+        }
+        if self.http_version.as_deref() == Some("http1") {
+            imports.insert("crypto/tls");
+            transport.push(
+                "\t\t\tTLSNextProto: map[string]func(string, *tls.Conn) http.RoundTripper{},"
+                    .to_string(),
+            );
+        }
+        lines.push("\tclient := &http.Client{".to_string());
+        if !transport.is_empty() {
+            lines.push("\t\tTransport: &http.Transport{".to_string());
+            lines.extend(transport);
+            lines.push("\t\t},".to_string());
+        }
+        if let Some(ms) = self.timeout_ms {
+            imports.insert("time");
+            lines.push(format!("\t\tTimeout: {} * time.Millisecond,", ms));
+        }
+        if !self.follow_redirects {
+            lines.extend([
+                "\t\tCheckRedirect: func(req *http.Request, via []*http.Request) error {"
+                    .to_string(),
+                "\t\t\treturn http.ErrUseLastResponse".to_string(),
+                "\t\t},".to_string(),
+            ]);
+        }
+        lines.extend([
+            "\t}".to_string(),
+            "\tres, err := client.Do(req)".to_string(),
+            "\tif err != nil {".to_string(),
+            "\t\tlog.Fatal(err)".to_string(),
+            "\t}".to_string(),
+            "\tdefer res.Body.Close()".to_string(),
+            String::new(),
+            "\tdata, err := io.ReadAll(res.Body)".to_string(),
+            "\tif err != nil {".to_string(),
+            "\t\tlog.Fatal(err)".to_string(),
+            "\t}".to_string(),
+            "\tfmt.Println(res.Status)".to_string(),
+            "\tfmt.Println(string(data))".to_string(),
+            "}".to_string(),
+        ]);
+
+        let mut code = vec![
+            "package main".to_string(),
+            String::new(),
+            "import (".to_string(),
+        ];
+        code.extend(imports.iter().map(|import| format!("\t\"{}\"", import)));
+        code.push(")".to_string());
+        code.push(String::new());
+        code.extend(lines);
+        code.join("\n")
+    }
+
+    fn rust(&self, notes: &mut Vec<String>) -> String {
+        self.note_challenge(notes);
+        self.note_aws(notes);
+
+        let mut builder = Vec::new();
+        if let Some(proxy) = &self.proxy {
+            builder.push(format!(
+                ".proxy(reqwest::Proxy::all({})?)",
+                rust_quote(proxy)
+            ));
+        }
+        if self.insecure {
+            builder.push(".danger_accept_invalid_certs(true)".to_string());
+        }
+        if let Some((cert, key)) = &self.cert {
+            builder.push(format!(
+                ".identity(reqwest::Identity::from_pem(&[std::fs::read({})?, std::fs::read({})?].concat())?)",
+                rust_quote(cert),
+                rust_quote(key)
+            ));
+        }
+        if let Some(ca) = &self.ca_path {
+            builder.push(format!(
+                ".add_root_certificate(reqwest::Certificate::from_pem(&std::fs::read({})?)?)",
+                rust_quote(ca)
+            ));
+        }
+        match self.http_version.as_deref() {
+            Some("http1") => builder.push(".http1_only()".to_string()),
+            Some("http2") => builder.push(".http2_prior_knowledge()".to_string()),
+            _ => {}
+        }
+        if let Some(ms) = self.timeout_ms {
+            builder.push(format!(
+                ".timeout(std::time::Duration::from_millis({}))",
+                ms
+            ));
+        }
+        if !self.follow_redirects {
+            builder.push(".redirect(reqwest::redirect::Policy::none())".to_string());
+        }
+
+        let mut lines = vec![
+            "#[tokio::main]".to_string(),
+            "async fn main() -> Result<(), Box<dyn std::error::Error>> {".to_string(),
+        ];
+        if builder.is_empty() {
+            lines.push("    let client = reqwest::Client::new();".to_string());
+        } else {
+            lines.push("    let client = reqwest::Client::builder()".to_string());
+            lines.extend(builder.into_iter().map(|call| format!("        {}", call)));
+            lines.push("        .build()?;".to_string());
+        }
+        if let Some(Body::Multipart(form)) = &self.body {
+            lines.push("    let form = reqwest::multipart::Form::new()".to_string());
+            for part in form {
+                lines.push(match part {
+                    Part::Text { key, value } => format!(
+                        "        .text({}, {})",
+                        rust_quote(key),
+                        rust_quote(value)
+                    ),
+                    Part::File {
+                        key,
+                        path,
+                        content_type,
+                    } => format!(
+                        "        .part({}, reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}).mime_str({})?)",
+                        rust_quote(key),
+                        rust_quote(path),
+                        rust_quote(&file_name(path)),
+                        rust_quote(content_type)
+                    ),
+                });
+            }
+            if let Some(last) = lines.last_mut() {
+                last.push(';');
+            }
+        }
+        lines.push(String::new());
+        lines.push("    let response = client".to_string());
+        lines.push(format!(
+            "        .request({}.parse()?, {})",
+            rust_quote(&self.method),
+            rust_quote(&self.url)
+        ));
+        for (key, value) in &self.headers {
+            lines.push(format!(
+                "        .header({}, {})",
+                rust_quote(key),
+                rust_quote(value)
+            ));
+        }
+        match &self.body {
+            Some(Body::Raw(text)) => lines.push(format!("        .body({})", rust_quote(text))),
+            Some(Body::Form(pairs)) => lines.push(format!(
+                "        .form(&[{}])",
+                pairs
+                    .iter()
+                    .map(|(k, v)| format!("({}, {})", rust_quote(k), rust_quote(v)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Some(Body::Multipart(_)) => lines.push("        .multipart(form)".to_string()),
+            Some(Body::File(path)) => lines.push(format!(
+                "        .body(std::fs::read({})?)",
+                rust_quote(path)
+            )),
+            None => {}
+        }
+        lines.extend([
+            "        .send()".to_string(),
+            "        .await?;".to_string(),
+            String::new(),
+            "    println!(\"{}\", response.status());".to_string(),
+            "    println!(\"{}\", response.text().await?);".to_string(),
+            "    Ok(())".to_string(),
+            "}".to_string(),
+        ]);
+        lines.join("\n")
+    }
+}
+
+fn http_version_label(version: &str) -> &'static str {
+    match version {
+        "http1" => "HTTP/1.1",
+        _ => "HTTP/2 with prior knowledge",
+    }
+}
+
+/// Single-quoted POSIX shell argument.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Double-quoted string literal valid in JavaScript, Python and Go.
+fn quote(value: &str) -> String {
+    Value::from(value).to_string()
+}
+
+/// Rust string literal (`Debug` escapes are valid Rust syntax).
+fn rust_quote(value: &str) -> String {
+    format!("{:?}", value)
+}
+
+/// Generate a code snippet sending `request_options` the way
+/// `send_api_request` would.
+#[tauri::command]
+pub async fn codegen_generate(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    mut request_options: RequestOptions,
+    language: CodegenLanguage,
+) -> Result<String, String> {
+    if request_options.inherit_auth {
+        super::runner::apply_inherited_auth(&app, &mut request_options).await?;
+    }
+    if let Some(collection_id) = request_options.collection_id.as_deref() {
+        request_options.proxy = super::collections::collection_proxy(&app, collection_id)?;
+    }
+    if request_options.ca_bundle_path.is_none() {
+        request_options.ca_bundle_path = super::runner::load_runner_settings(&app).ca_bundle_path;
+    }
+    request_options.apply_ca_bundle();
+    let proxy = proxy_state.proxy_url(&request_options.url, request_options.proxy.as_ref());
+    Ok(Snippet::new(&request_options, proxy)?.render(language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> RequestOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn generate(value: Value, proxy: Option<&str>, language: CodegenLanguage) -> String {
+        Snippet::new(&options(value), proxy.map(str::to_string))
+            .unwrap()
+            .render(language)
+    }
+
+    #[test]
+    fn curl_reflects_body_encoding_auth_and_client_settings() {
+        let code = generate(
+            json!({
+                "method": "POST",
+                "url": "https://api.test/login",
+                "headers": { "Content-Type": "text/plain", "X-Trace": "it's" },
+                "body": [
+                    { "key": "user", "value": "ada lovelace" },
+                    { "key": "next", "value": "/home?a=1" }
+                ],
+                "bodyType": "form-urlencoded",
+                "auth": { "username": "ada", "password": "secret" },
+                "verifySsl": false,
+                "followRedirects": false,
+                "timeout": 2500
+            }),
+            Some("http://proxy.test:3128"),
+            CodegenLanguage::Curl,
+        );
+        assert_eq!(
+            code,
+            [
+                "curl",
+                "-X POST",
+                "-k",
+                "--max-time 2.5",
+                "--proxy 'http://proxy.test:3128'",
+                "--digest",
+                "-u 'ada:secret'",
+                r"-H 'X-Trace: it'\''s'",
+                "--data-raw 'user=ada+lovelace&next=%2Fhome%3Fa%3D1'",
+                "'https://api.test/login'",
+            ]
+            .join(" \\\n  ")
+        );
+    }
+
+    #[test]
+    fn json_bodies_get_a_content_type_unless_one_is_set() {
+        let code = generate(
+            json!({
+                "method": "PUT",
+                "url": "https://api.test/users/1",
+                "body": { "name": "Ada" }
+            }),
+            None,
+            CodegenLanguage::Python,
+        );
+        assert!(code.contains("    \"Content-Type\": \"application/json\","));
+        assert!(code.contains("data = \"{\\\"name\\\":\\\"Ada\\\"}\""));
+        assert!(code.contains(
+            "response = requests.request(\"PUT\", url, headers=headers, data=data.encode())"
+        ));
+
+        let code = generate(
+            json!({
+                "method": "POST",
+                "url": "https://api.test/users",
+                "headers": { "content-type": "application/vnd.api+json" },
+                "body": { "name": "Ada" }
+            }),
+            None,
+            CodegenLanguage::JavascriptFetch,
+        );
+        assert!(code.contains("    \"content-type\": \"application/vnd.api+json\","));
+        assert!(!code.contains("application/json\""));
+    }
+
+    #[test]
+    fn multipart_bodies_read_files_in_every_language() {
+        let request = json!({
+            "method": "POST",
+            "url": "https://api.test/upload",
+            "body": [
+                { "key": "title", "value": "Report" },
+                { "key": "file", "type": "file", "filePath": "/tmp/report.pdf", "contentType": "application/pdf" }
+            ],
+            "bodyType": "multipart"
+        });
+
+        let curl = generate(request.clone(), None, CodegenLanguage::Curl);
+        assert!(curl.contains("--form-string 'title=Report'"));
+        assert!(curl.contains("-F 'file=@/tmp/report.pdf;type=application/pdf'"));
+
+        let go = generate(request.clone(), None, CodegenLanguage::Go);
+        assert!(
+            go.contains("\t\"mime/multipart\"\n\t\"net/http\"\n\t\"net/textproto\"\n\t\"os\"\n")
+        );
+        assert!(go.contains("req.Header.Set(\"Content-Type\", form.FormDataContentType())"));
+
+        let rust = generate(request.clone(), None, CodegenLanguage::Rust);
+        assert!(rust.contains(".part(\"file\", reqwest::multipart::Part::bytes(std::fs::read(\"/tmp/report.pdf\")?).file_name(\"report.pdf\").mime_str(\"application/pdf\")?);"));
+        assert!(rust.contains("let client = reqwest::Client::new();"));
+
+        let fetch = generate(request, None, CodegenLanguage::JavascriptFetch);
+        assert!(fetch.starts_with("import { openAsBlob } from 'node:fs';"));
+    }
+
+    #[test]
+    fn unsupported_settings_are_listed_in_a_comment() {
+        let code = generate(
+            json!({
+                "method": "GET",
+                "url": "https://api.test/",
+                "auth": { "username": "", "password": "", "authType": "negotiate" },
+                "verifySsl": false
+            }),
+            Some("http://proxy.test:3128"),
+            CodegenLanguage::JavascriptFetch,
+        );
+        assert!(code.starts_with(
+            "// Not expressed in this snippet:\n\
+             // - the proxy http://proxy.test:3128\n\
+             // - disabled TLS certificate verification\n\
+             // - Negotiate (Kerberos) authentication\n\n"
+        ));
+
+        let code = generate(
+            json!({ "method": "GET", "url": "https://api.test/", "verifySsl": false }),
+            None,
+            CodegenLanguage::Go,
+        );
+        assert!(code.starts_with("package main"));
+        assert!(code.contains("TLSClientConfig: &tls.Config{InsecureSkipVerify: true},"));
+    }
+}
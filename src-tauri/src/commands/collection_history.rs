@@ -0,0 +1,381 @@
+//! Bounded version history per collection.
+//!
+//! Every save through `persist_collection` records a snapshot of the
+//! collection file, its per-endpoint data and its variables under
+//! `<data dir>/history/<collection id>/`. A snapshot is only written when the
+//! content differs from the latest one, and the oldest snapshots are dropped
+//! beyond `MAX_VERSIONS`. Rolling back is itself a save, so it can be undone.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use super::collections::{
+    collection_save_endpoint_data, collection_save_variables, find_endpoint_data_file,
+    persist_collection, read_collection_value, resolve_collection_dir, Collection,
+};
+
+const HISTORY_DIR: &str = "history";
+const MAX_VERSIONS: usize = 50;
+
+/// The state of a collection at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+    collection: Value,
+    #[serde(default)]
+    endpoints: serde_json::Map<String, Value>,
+    #[serde(default)]
+    variables: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Version {
+    id: String,
+    /// Unix timestamp (ms) of the save
+    created_at: i64,
+    snapshot: Snapshot,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionSummary {
+    pub id: String,
+    pub created_at: i64,
+    pub name: String,
+    pub endpoint_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Change {
+    /// Location of the change, e.g. `collection.endpoints[e1].path`
+    pub path: String,
+    /// "added", "removed" or "changed"
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+fn history_dir(app: &AppHandle, collection_id: &str) -> Result<PathBuf, String> {
+    Ok(super::workspaces::data_dir(app)?
+        .join(HISTORY_DIR)
+        .join(super::collections::sanitize_file_component(collection_id)))
+}
+
+fn endpoint_ids(collection: &Value) -> Vec<String> {
+    let top = collection.get("endpoints").and_then(Value::as_array);
+    let folders = collection
+        .get("folders")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|folder| folder.get("endpoints").and_then(Value::as_array));
+    top.into_iter()
+        .chain(folders)
+        .flatten()
+        .filter_map(|e| e.get("id").and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn take_snapshot(collection_dir: &Path) -> Result<Snapshot, String> {
+    let mut collection = read_collection_value(collection_dir)?;
+    if let Some(obj) = collection.as_object_mut() {
+        obj.remove("storagePath");
+    }
+
+    let requests_dir = collection_dir.join("requests");
+    let mut endpoints = serde_json::Map::new();
+    for id in endpoint_ids(&collection) {
+        if let Some(file) = find_endpoint_data_file(&requests_dir, &id)? {
+            if let Some(data) = read_json(&file) {
+                endpoints.insert(id, data);
+            }
+        }
+    }
+
+    let variables = read_json(&collection_dir.join("variables.json"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    Ok(Snapshot {
+        collection,
+        endpoints,
+        variables,
+    })
+}
+
+/// All versions of a collection, newest first.
+fn read_versions(dir: &Path) -> Vec<Version> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<Version> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| read_json(&entry.path()))
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect();
+    versions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    versions
+}
+
+fn find_version(app: &AppHandle, collection_id: &str, version_id: &str) -> Result<Version, String> {
+    read_versions(&history_dir(app, collection_id)?)
+        .into_iter()
+        .find(|v| v.id == version_id)
+        .ok_or_else(|| format!("Version {} not found", version_id))
+}
+
+/// Record the current on-disk state of a collection unless it matches the
+/// latest version. Called by `persist_collection` before and after each write.
+pub(crate) fn record_version(app: &AppHandle, collection_id: &str, collection_dir: &Path) {
+    if let Err(e) = try_record_version(app, collection_id, collection_dir) {
+        eprintln!("Failed to record history of {}: {}", collection_id, e);
+    }
+}
+
+fn try_record_version(
+    app: &AppHandle,
+    collection_id: &str,
+    collection_dir: &Path,
+) -> Result<(), String> {
+    let snapshot = take_snapshot(collection_dir)?;
+    let dir = history_dir(app, collection_id)?;
+    let versions = read_versions(&dir);
+    if versions
+        .first()
+        .is_some_and(|latest| latest.snapshot == snapshot)
+    {
+        return Ok(());
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create history dir: {}", e))?;
+    let version = Version {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        snapshot,
+    };
+    let json = serde_json::to_string(&version).map_err(|e| e.to_string())?;
+    fs::write(dir.join(format!("{}.json", version.id)), json)
+        .map_err(|e| format!("Failed to write version: {}", e))?;
+
+    for old in versions.iter().skip(MAX_VERSIONS - 1) {
+        let _ = fs::remove_file(dir.join(format!("{}.json", old.id)));
+    }
+    Ok(())
+}
+
+/// Drop the whole history of a collection. Called when it is purged from the trash.
+pub(crate) fn remove_history(app: &AppHandle, collection_id: &str) {
+    if let Ok(dir) = history_dir(app, collection_id) {
+        if dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                eprintln!("Failed to remove history of {}: {}", collection_id, e);
+            }
+        }
+    }
+}
+
+fn element_key(value: &Value, index: usize) -> String {
+    value
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| index.to_string())
+}
+
+fn push_change(out: &mut Vec<Change>, path: &str, before: Option<&Value>, after: Option<&Value>) {
+    let kind = match (before, after) {
+        (None, _) => "added",
+        (_, None) => "removed",
+        _ => "changed",
+    };
+    out.push(Change {
+        path: path.to_string(),
+        kind: kind.to_string(),
+        before: before.cloned(),
+        after: after.cloned(),
+    });
+}
+
+/// Structural diff. Array elements with an `id` are matched by id so that
+/// reordering or inserting endpoints does not show up as a change to every
+/// following element.
+fn diff_values(path: &str, before: &Value, after: &Value, out: &mut Vec<Change>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, value) in a {
+                let child = format!("{}.{}", path, key);
+                match b.get(key) {
+                    Some(other) => diff_values(&child, value, other, out),
+                    None => push_change(out, &child, Some(value), None),
+                }
+            }
+            for (key, value) in b {
+                if !a.contains_key(key) {
+                    push_change(out, &format!("{}.{}", path, key), None, Some(value));
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            let keyed_a: Vec<(String, &Value)> = a
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (element_key(v, i), v))
+                .collect();
+            let keyed_b: Vec<(String, &Value)> = b
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (element_key(v, i), v))
+                .collect();
+            for (key, value) in &keyed_a {
+                let child = format!("{}[{}]", path, key);
+                match keyed_b.iter().find(|(k, _)| k == key) {
+                    Some((_, other)) => diff_values(&child, value, other, out),
+                    None => push_change(out, &child, Some(value), None),
+                }
+            }
+            for (key, value) in &keyed_b {
+                if !keyed_a.iter().any(|(k, _)| k == key) {
+                    push_change(out, &format!("{}[{}]", path, key), None, Some(value));
+                }
+            }
+        }
+        _ if before != after => push_change(out, path, Some(before), Some(after)),
+        _ => {}
+    }
+}
+
+fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_values(
+        "collection",
+        &before.collection,
+        &after.collection,
+        &mut changes,
+    );
+    diff_values(
+        "endpoints",
+        &Value::Object(before.endpoints.clone()),
+        &Value::Object(after.endpoints.clone()),
+        &mut changes,
+    );
+    diff_values(
+        "variables",
+        &Value::Array(before.variables.clone()),
+        &Value::Array(after.variables.clone()),
+        &mut changes,
+    );
+    changes
+}
+
+fn collection_dir(app: &AppHandle, collection_id: &str) -> Result<PathBuf, String> {
+    resolve_collection_dir(app, collection_id)?
+        .ok_or_else(|| format!("Collection {} not found", collection_id))
+}
+
+/// Saved versions of a collection, newest first.
+#[tauri::command]
+pub async fn collection_history_list(
+    app: AppHandle,
+    collection_id: String,
+) -> Result<Vec<VersionSummary>, String> {
+    Ok(read_versions(&history_dir(&app, &collection_id)?)
+        .into_iter()
+        .map(|v| VersionSummary {
+            name: v
+                .snapshot
+                .collection
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            endpoint_count: endpoint_ids(&v.snapshot.collection).len(),
+            id: v.id,
+            created_at: v.created_at,
+        })
+        .collect())
+}
+
+/// Changes from `version_id` to `compare_to`, or to the current state when
+/// `compare_to` is omitted.
+#[tauri::command]
+pub async fn collection_history_diff(
+    app: AppHandle,
+    collection_id: String,
+    version_id: String,
+    compare_to: Option<String>,
+) -> Result<Vec<Change>, String> {
+    let base = find_version(&app, &collection_id, &version_id)?.snapshot;
+    let other = match compare_to {
+        Some(id) => find_version(&app, &collection_id, &id)?.snapshot,
+        None => take_snapshot(&collection_dir(&app, &collection_id)?)?,
+    };
+    Ok(diff_snapshots(&base, &other))
+}
+
+/// Restore a collection, its endpoint data and variables to a saved version.
+#[tauri::command]
+pub async fn collection_history_rollback(
+    app: AppHandle,
+    collection_id: String,
+    version_id: String,
+) -> Result<Collection, String> {
+    let snapshot = find_version(&app, &collection_id, &version_id)?.snapshot;
+    let collection: Collection = serde_json::from_value(snapshot.collection)
+        .map_err(|e| format!("Failed to parse version: {}", e))?;
+    collection_dir(&app, &collection_id)?;
+
+    // Data files first, so the snapshot taken by the final save is complete
+    for (endpoint_id, data) in snapshot.endpoints {
+        let data = serde_json::from_value(data)
+            .map_err(|e| format!("Failed to parse endpoint data: {}", e))?;
+        collection_save_endpoint_data(app.clone(), collection_id.clone(), endpoint_id, data)
+            .await?;
+    }
+    collection_save_variables(app.clone(), collection_id.clone(), snapshot.variables).await?;
+    persist_collection(&app, collection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_matches_array_elements_by_id() {
+        let before = json!({
+            "name": "Users",
+            "endpoints": [{"id": "e1", "path": "/users"}, {"id": "e2", "path": "/users/{id}"}]
+        });
+        let after = json!({
+            "name": "Users API",
+            "endpoints": [{"id": "e2", "path": "/users/{id}"}, {"id": "e3", "path": "/health"}]
+        });
+
+        let mut changes = Vec::new();
+        diff_values("collection", &before, &after, &mut changes);
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("collection.name", "changed"),
+                ("collection.endpoints[e1]", "removed"),
+                ("collection.endpoints[e3]", "added"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Detects external edits to collection directories (a `git pull`, an editor,
+//! a teammate's sync tool) and tells the frontend to reload.
+//!
+//! Collection directories are polled rather than watched through OS
+//! notifications: there are few of them, the files are small, and polling
+//! behaves the same on every platform and on network drives. Writes made by
+//! the app itself call [`mark_synced`] so they don't echo back as changes.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Latest modification time and file count of a collection directory.
+type Fingerprint = (Option<SystemTime>, usize);
+
+pub struct CollectionWatchState {
+    fingerprints: Mutex<HashMap<String, Fingerprint>>,
+}
+
+impl Default for CollectionWatchState {
+    fn default() -> Self {
+        Self {
+            fingerprints: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionsChangedPayload {
+    collection_id: String,
+    /// "changed" | "removed"
+    event_type: String,
+    path: String,
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fingerprint of the files the app reads: the collection file,
+/// `variables.json`, the per-request files and the saved examples. `None`
+/// when the directory no longer holds a collection.
+fn fingerprint(dir: &Path) -> Option<Fingerprint> {
+    super::collections::collection_file_in(dir)?;
+
+    let mut files = files_in(dir);
+    files.extend(files_in(&dir.join("requests")));
+    files.extend(files_in(&dir.join(super::endpoint_examples::EXAMPLES_DIR)));
+
+    let latest = files
+        .iter()
+        .filter_map(|path| path.metadata().and_then(|m| m.modified()).ok())
+        .max();
+    Some((latest, files.len()))
+}
+
+/// Record the current state of a collection directory after the app wrote to
+/// it, so the next poll does not report the app's own change.
+pub(crate) fn mark_synced(app: &AppHandle, collection_id: &str, dir: &Path) {
+    let Some(state) = app.try_state::<CollectionWatchState>() else {
+        return;
+    };
+    if let Some(print) = fingerprint(dir) {
+        state
+            .fingerprints
+            .lock()
+            .unwrap()
+            .insert(collection_id.to_string(), print);
+    }
+}
+
+/// Compare the indexed collection directories against the last known
+/// fingerprints, returning `(collection id, event type, dir)` per change.
+fn poll(app: &AppHandle, state: &CollectionWatchState) -> Vec<(String, &'static str, String)> {
+    let index = super::collections::get_collection_index(app).unwrap_or_default();
+    let mut fingerprints = state.fingerprints.lock().unwrap();
+    let mut changes = Vec::new();
+
+    for (collection_id, dir) in &index {
+        let current = fingerprint(Path::new(dir));
+        match (fingerprints.get(collection_id), current) {
+            // First sighting: remember it without reporting
+            (None, Some(print)) => {
+                fingerprints.insert(collection_id.clone(), print);
+            }
+            (Some(known), Some(print)) if *known != print => {
+                fingerprints.insert(collection_id.clone(), print);
+                changes.push((collection_id.clone(), "changed", dir.clone()));
+            }
+            (Some(_), None) => {
+                fingerprints.remove(collection_id);
+                changes.push((collection_id.clone(), "removed", dir.clone()));
+            }
+            _ => {}
+        }
+    }
+    fingerprints.retain(|id, _| index.contains_key(id));
+    changes
+}
+
+/// Poll collection directories for the lifetime of the app and emit
+/// `collections-changed` for every external modification.
+pub fn start_collection_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let state = app.state::<CollectionWatchState>();
+            for (collection_id, event_type, path) in poll(&app, &state) {
+                let _ = app.emit(
+                    "collections-changed",
+                    CollectionsChangedPayload {
+                        collection_id,
+                        event_type: event_type.to_string(),
+                        path,
+                    },
+                );
+            }
+        }
+    });
+}
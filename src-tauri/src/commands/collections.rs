@@ -4,13 +4,20 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
-use tauri::Manager;
 use tauri_plugin_dialog::{DialogExt, FilePath};
 use tauri_plugin_store::StoreExt;
 use tokio::sync::oneshot;
 
-const STORE_FILE: &str = "resonance-store.json";
+use super::grpc_metadata::GrpcMetadataDefaults;
+use super::login_flow::LoginFlow;
+use super::proxy::ProxyOverride;
+use super::safety_policy::SafetyPolicy;
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
 const COLLECTIONS_DIR: &str = "collections";
+const COLLECTION_FILE: &str = "collection.json";
+const COLLECTION_YAML_FILE: &str = "collection.yaml";
 const COLLECTION_INDEX_KEY: &str = "collectionIndex";
 const LAST_COLLECTION_DIR_KEY: &str = "lastCollectionDirectory";
 
@@ -39,6 +46,23 @@ pub struct Collection {
     pub storage_path: Option<String>,
     #[serde(skip_serializing, default)]
     pub storage_parent_path: Option<String>,
+    /// On-disk format of the collection file: "json" (default) or "yaml".
+    /// Reported on read and honoured on save; never written into the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_format: Option<String>,
+    /// Proxy settings used for this collection's requests instead of the
+    /// global ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyOverride>,
+    /// Login request whose response supplies the collection's token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_flow: Option<LoginFlow>,
+    /// Methods and hosts whose requests need confirmation before sending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_policy: Option<SafetyPolicy>,
+    /// Metadata sent with the collection's gRPC requests unless they set it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_metadata: Option<GrpcMetadataDefaults>,
 }
 
 /// Request data stored per-endpoint
@@ -70,11 +94,7 @@ pub struct EndpointData {
 }
 
 fn get_default_collections_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
-    Ok(app_data_dir.join(COLLECTIONS_DIR))
+    Ok(super::workspaces::data_dir(app)?.join(COLLECTIONS_DIR))
 }
 
 /// Get the collections directory path for user reference
@@ -90,8 +110,8 @@ fn ensure_default_collections_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(dir)
 }
 
-fn get_collection_index(app: &AppHandle) -> Result<HashMap<String, String>, String> {
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+pub(crate) fn get_collection_index(app: &AppHandle) -> Result<HashMap<String, String>, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
     let value = store
         .get(COLLECTION_INDEX_KEY)
         .unwrap_or(Value::Object(serde_json::Map::new()));
@@ -100,20 +120,20 @@ fn get_collection_index(app: &AppHandle) -> Result<HashMap<String, String>, Stri
 }
 
 fn save_collection_index(app: &AppHandle, index: &HashMap<String, String>) -> Result<(), String> {
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
     store.set(
         COLLECTION_INDEX_KEY.to_string(),
         serde_json::to_value(index).map_err(|e| e.to_string())?,
     );
-    store.save().map_err(|e| e.to_string())?;
-    Ok(())
+    save_store(app)
 }
 
-fn register_collection_path(
+pub(crate) fn register_collection_path(
     app: &AppHandle,
     collection_id: &str,
     path: &Path,
 ) -> Result<(), String> {
+    let _guard = lock_store();
     let mut index = get_collection_index(app)?;
     index.insert(
         collection_id.to_string(),
@@ -122,14 +142,18 @@ fn register_collection_path(
     save_collection_index(app, &index)
 }
 
-fn unregister_collection_path(app: &AppHandle, collection_id: &str) -> Result<(), String> {
+pub(crate) fn unregister_collection_path(
+    app: &AppHandle,
+    collection_id: &str,
+) -> Result<(), String> {
+    let _guard = lock_store();
     let mut index = get_collection_index(app)?;
     index.remove(collection_id);
     save_collection_index(app, &index)
 }
 
 fn get_last_collection_directory(app: &AppHandle) -> Option<PathBuf> {
-    let store = app.store(STORE_FILE).ok()?;
+    let store = app.store(store_file(app)).ok()?;
     let dir_str = store.get(LAST_COLLECTION_DIR_KEY)?.as_str()?.to_string();
     if dir_str.is_empty() {
         return None;
@@ -144,12 +168,12 @@ fn get_last_collection_directory(app: &AppHandle) -> Option<PathBuf> {
 }
 
 fn save_last_collection_directory(app: &AppHandle, dir: &Path) {
-    if let Ok(store) = app.store(STORE_FILE) {
+    if let Ok(store) = app.store(store_file(app)) {
         store.set(
             LAST_COLLECTION_DIR_KEY.to_string(),
             Value::String(dir.to_string_lossy().to_string()),
         );
-        let _ = store.save();
+        let _ = save_store(app);
     }
 }
 
@@ -187,7 +211,7 @@ fn is_template_ref(value: &str) -> bool {
 /// git-friendly collection files even if the frontend fails to redact them. Template
 /// references and empty values are preserved. The real values are kept in the
 /// frontend SecretStore and rehydrated on read.
-fn redact_auth_secrets(auth_config: &mut Value) {
+pub(crate) fn redact_auth_secrets(auth_config: &mut Value) {
     let auth_type = match auth_config.get("type").and_then(|t| t.as_str()) {
         Some(t) => t.to_string(),
         None => return,
@@ -215,17 +239,73 @@ fn read_json_file<T: for<'de> Deserialize<'de>>(path: &PathBuf) -> Result<T, Str
     serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
 }
 
+/// The collection file inside a collection directory, JSON taking precedence
+/// over YAML when both exist.
+pub(crate) fn collection_file_in(dir: &Path) -> Option<PathBuf> {
+    [COLLECTION_FILE, COLLECTION_YAML_FILE]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+fn is_yaml_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("yaml") | Some("yml")
+    )
+}
+
 fn is_collection_dir(path: &Path) -> bool {
-    path.is_dir() && path.join("collection.json").exists()
+    path.is_dir() && collection_file_in(path).is_some()
+}
+
+/// Raw contents of a collection directory's collection file (JSON or YAML).
+pub(crate) fn read_collection_value(dir: &Path) -> Result<Value, String> {
+    let file = collection_file_in(dir)
+        .ok_or_else(|| format!("No collection file in {}", dir.display()))?;
+    let content = fs::read_to_string(&file).map_err(|e| format!("Failed to read file: {}", e))?;
+    if is_yaml_file(&file) {
+        serde_yaml_ng::from_str(&content).map_err(|e| format!("Failed to parse YAML: {}", e))
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+    }
 }
 
 fn read_collection_from_dir(path: &Path) -> Result<Collection, String> {
-    let collection_file = path.join("collection.json");
-    let mut collection: Collection = read_json_file(&collection_file)?;
+    let value = read_collection_value(path)?;
+    let mut collection: Collection =
+        serde_json::from_value(value).map_err(|e| format!("Failed to parse collection: {}", e))?;
     collection.storage_path = Some(path.to_string_lossy().to_string());
+    collection.storage_format = collection_file_in(path)
+        .filter(|file| is_yaml_file(file))
+        .map(|_| "yaml".to_string());
     Ok(collection)
 }
 
+fn write_collection_file(dir: &Path, collection: &Collection, yaml: bool) -> Result<(), String> {
+    let (file_name, stale_name) = if yaml {
+        (COLLECTION_YAML_FILE, COLLECTION_FILE)
+    } else {
+        (COLLECTION_FILE, COLLECTION_YAML_FILE)
+    };
+    let content = if yaml {
+        serde_yaml_ng::to_string(collection)
+            .map_err(|e| format!("Failed to serialize YAML: {}", e))?
+    } else {
+        serde_json::to_string_pretty(collection)
+            .map_err(|e| format!("Failed to serialize JSON: {}", e))?
+    };
+    fs::write(dir.join(file_name), content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    // Switching formats must not leave the old file behind, since JSON wins
+    // when both exist.
+    let stale = dir.join(stale_name);
+    if stale.exists() {
+        fs::remove_file(&stale).map_err(|e| format!("Failed to remove {}: {}", stale_name, e))?;
+    }
+    Ok(())
+}
+
 fn slugify(input: &str) -> String {
     let mut slug = String::new();
     let mut last_was_dash = false;
@@ -249,7 +329,7 @@ fn slugify(input: &str) -> String {
     }
 }
 
-fn sanitize_file_component(input: &str) -> String {
+pub(crate) fn sanitize_file_component(input: &str) -> String {
     let mut out = String::new();
     for c in input.chars() {
         let ch = c.to_ascii_lowercase();
@@ -268,7 +348,11 @@ fn sanitize_file_component(input: &str) -> String {
     }
 }
 
-fn find_available_dir(parent: &Path, base_name: &str, current_dir: Option<&Path>) -> PathBuf {
+pub(crate) fn find_available_dir(
+    parent: &Path,
+    base_name: &str,
+    current_dir: Option<&Path>,
+) -> PathBuf {
     let mut candidate = parent.join(base_name);
     if current_dir == Some(candidate.as_path()) || !candidate.exists() {
         return candidate;
@@ -399,6 +483,83 @@ fn sync_endpoint_data_file_names(
     Ok(())
 }
 
+/// A top-level field of a collection file, if it is set.
+fn collection_field<T: for<'de> Deserialize<'de>>(
+    app: &AppHandle,
+    collection_id: &str,
+    key: &str,
+) -> Result<Option<T>, String> {
+    let Some(dir) = resolve_collection_dir(app, collection_id)? else {
+        return Ok(None);
+    };
+    match read_collection_value(&dir)?.get(key) {
+        Some(value) if !value.is_null() => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| format!("Invalid {} settings: {}", key, e)),
+        _ => Ok(None),
+    }
+}
+
+/// The proxy override of a collection, if it has one.
+pub(crate) fn collection_proxy(
+    app: &AppHandle,
+    collection_id: &str,
+) -> Result<Option<ProxyOverride>, String> {
+    collection_field(app, collection_id, "proxy")
+}
+
+/// The login flow of a collection, if it has one.
+pub(crate) fn collection_login_flow(
+    app: &AppHandle,
+    collection_id: &str,
+) -> Result<Option<LoginFlow>, String> {
+    collection_field(app, collection_id, "loginFlow")
+}
+
+/// The safety policy of a collection, if it has one.
+pub(crate) fn collection_safety_policy(
+    app: &AppHandle,
+    collection_id: &str,
+) -> Result<Option<SafetyPolicy>, String> {
+    collection_field(app, collection_id, "safetyPolicy")
+}
+
+/// The default gRPC metadata of a collection, if it has any.
+pub(crate) fn collection_grpc_metadata(
+    app: &AppHandle,
+    collection_id: &str,
+) -> Result<Option<GrpcMetadataDefaults>, String> {
+    collection_field(app, collection_id, "grpcMetadata")
+}
+
+/// The time budget of an endpoint at the root of a collection or in one of
+/// its folders, if it has one.
+/// An endpoint of a collection as stored in its collection file, for the
+/// per-request settings kept there (time budget, IP family).
+pub(crate) fn collection_endpoint(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> Result<Option<Value>, String> {
+    let Some(dir) = resolve_collection_dir(app, collection_id)? else {
+        return Ok(None);
+    };
+    let collection = read_collection_value(&dir)?;
+    let root = collection.get("endpoints").and_then(Value::as_array);
+    let nested = collection
+        .get("folders")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|folder| folder.get("endpoints").and_then(Value::as_array));
+    Ok(root
+        .into_iter()
+        .chain(nested)
+        .flatten()
+        .find(|endpoint| endpoint.get("id").and_then(Value::as_str) == Some(endpoint_id))
+        .cloned())
+}
+
 pub(crate) fn resolve_collection_dir(
     app: &AppHandle,
     collection_id: &str,
@@ -448,6 +609,10 @@ pub(crate) fn persist_collection(
     ensure_default_collections_dir(app)?;
 
     let existing_dir = resolve_collection_dir(app, &collection.id)?;
+    // Capture edits made since the last save (endpoint data, external changes)
+    if let Some(current_dir) = existing_dir.as_ref() {
+        super::collection_history::record_version(app, &collection.id, current_dir);
+    }
     let target_parent = if let Some(parent) = collection
         .storage_parent_path
         .as_ref()
@@ -483,9 +648,15 @@ pub(crate) fn persist_collection(
             .map_err(|e| format!("Failed to create collection dir: {}", e))?;
     }
 
+    let yaml = match collection.storage_format.as_deref() {
+        Some(format) => format == "yaml",
+        None => collection_file_in(&target_dir).is_some_and(|file| is_yaml_file(&file)),
+    };
+
     let mut persisted = collection.clone();
     persisted.storage_path = Some(target_dir.to_string_lossy().to_string());
     persisted.storage_parent_path = None;
+    persisted.storage_format = None;
     if let Some(auth) = persisted.auth_config.as_mut() {
         redact_auth_secrets(auth);
     }
@@ -495,10 +666,12 @@ pub(crate) fn persist_collection(
         }
     }
 
-    let collection_file = target_dir.join("collection.json");
-    write_json_file(&collection_file, &persisted)?;
+    write_collection_file(&target_dir, &persisted, yaml)?;
     sync_endpoint_data_file_names(&target_dir, &persisted)?;
     register_collection_path(app, &persisted.id, &target_dir)?;
+    super::collection_history::record_version(app, &persisted.id, &target_dir);
+    super::collection_watcher::mark_synced(app, &persisted.id, &target_dir);
+    persisted.storage_format = yaml.then(|| "yaml".to_string());
 
     if let Some(parent) = target_dir.parent() {
         save_last_collection_directory(app, parent);
@@ -582,18 +755,10 @@ pub async fn collection_save(app: AppHandle, collection: Collection) -> Result<(
     Ok(())
 }
 
+/// Move a collection to the trash; it is deleted for good when purged.
 #[tauri::command]
 pub async fn collection_delete(app: AppHandle, collection_id: String) -> Result<(), String> {
-    if let Some(collection_dir) = resolve_collection_dir(&app, &collection_id)? {
-        if collection_dir.exists() {
-            fs::remove_dir_all(&collection_dir)
-                .map_err(|e| format!("Failed to delete collection: {}", e))?;
-        }
-    }
-
-    unregister_collection_path(&app, &collection_id)?;
-    super::scripts::purge_store_scripts_for_collection(&app, &collection_id);
-    Ok(())
+    super::trash::trash_collection(&app, &collection_id)
 }
 
 #[tauri::command]
@@ -654,6 +819,7 @@ pub async fn collection_save_endpoint_data(
     }
 
     write_json_file(&desired_file, &data)?;
+    super::collection_watcher::mark_synced(&app, &collection_id, &collection_dir);
     Ok(())
 }
 
@@ -670,7 +836,10 @@ pub async fn collection_delete_endpoint_data(
     if let Some(endpoint_file) = find_endpoint_data_file(&requests_dir, &endpoint_id)? {
         fs::remove_file(&endpoint_file)
             .map_err(|e| format!("Failed to delete endpoint data: {}", e))?;
+        super::collection_watcher::mark_synced(&app, &collection_id, &collection_dir);
     }
+    super::endpoint_examples::delete_examples(&collection_dir, &endpoint_id)?;
+    super::endpoint_drafts::remove_draft(&app, &collection_id, &endpoint_id)?;
 
     Ok(())
 }
@@ -712,6 +881,7 @@ pub async fn collection_save_variables(
 
     let variables_file = collection_dir.join("variables.json");
     write_json_file(&variables_file, &variables)?;
+    super::collection_watcher::mark_synced(&app, &collection_id, &collection_dir);
 
     Ok(())
 }
@@ -728,7 +898,7 @@ pub async fn collections_needs_migration(app: AppHandle) -> Result<bool, String>
         }
     }
 
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
     let old_collections = store.get("collections").unwrap_or(Value::Null);
 
     match old_collections {
@@ -739,7 +909,7 @@ pub async fn collections_needs_migration(app: AppHandle) -> Result<bool, String>
 
 #[tauri::command]
 pub async fn collections_migrate(app: AppHandle) -> Result<u32, String> {
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
 
     let old_collections = store.get("collections").unwrap_or(Value::Null);
     let collections: Vec<Value> = match old_collections {
@@ -766,7 +936,7 @@ pub async fn collections_migrate(app: AppHandle) -> Result<u32, String> {
         let backup_collections = store.get("collections").unwrap_or(Value::Null);
         store.set("_backup_collections".to_string(), backup_collections);
         store.set("collections".to_string(), serde_json::json!([]));
-        store.save().map_err(|e| e.to_string())?;
+        save_store(&app)?;
     }
 
     Ok(migrated_count)
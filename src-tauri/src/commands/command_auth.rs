@@ -0,0 +1,365 @@
+//! Command auth: the token comes from a user-configured shell command such as
+//! `gcloud auth print-access-token` or `aws sts get-session-token`.
+//!
+//! The command's trimmed stdout is the token, or, when `tokenField` is set, a
+//! dot path into its JSON output (`Credentials.SessionToken`). Tokens are
+//! cached per command for `cacheSeconds`, so a collection run does not spawn
+//! the command for every request, and a command that hangs is killed after
+//! `timeoutMs`.
+//!
+//! `{{variables}}` in the command never become shell syntax: each resolved
+//! reference is replaced by a quoted reference to an environment variable
+//! holding its value.
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use super::variables::{
+    load_active_environment_variables, load_collection_variables, VariableResolver,
+};
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_CACHE_SECONDS: u64 = 300;
+/// Prefix of the environment variables carrying resolved `{{variables}}`.
+const VARIABLE_ENV_PREFIX: &str = "RESONANCE_AUTH_VAR_";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandAuthConfig {
+    pub command: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub cache_seconds: Option<u64>,
+    #[serde(default)]
+    pub token_field: Option<String>,
+    /// Values of the resolved variable references, passed to the command as
+    /// environment variables
+    #[serde(skip)]
+    pub variables: Vec<(String, String)>,
+}
+
+impl CommandAuthConfig {
+    /// Cache key: the command together with the variable values it runs with.
+    fn cache_key(&self) -> String {
+        self.variables
+            .iter()
+            .fold(self.command.clone(), |key, (_, value)| key + "\0" + value)
+    }
+}
+
+#[derive(Default)]
+pub struct CommandAuthState {
+    /// Tokens by command, with the time they were obtained.
+    tokens: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl CommandAuthState {
+    fn cached(&self, config: &CommandAuthConfig) -> Option<String> {
+        let max_age = Duration::from_secs(config.cache_seconds.unwrap_or(DEFAULT_CACHE_SECONDS));
+        let tokens = self.tokens.lock().ok()?;
+        let (token, obtained) = tokens.get(&config.cache_key())?;
+        (obtained.elapsed() < max_age).then(|| token.clone())
+    }
+
+    fn store(&self, config: &CommandAuthConfig, token: &str) {
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.insert(config.cache_key(), (token.to_string(), Instant::now()));
+        }
+    }
+}
+
+/// The token in a command's output: the trimmed output itself, or the string
+/// at `token_field` when the command prints JSON.
+fn extract_token(stdout: &str, token_field: Option<&str>) -> Result<String, String> {
+    let token = match token_field.map(str::trim).filter(|f| !f.is_empty()) {
+        None => stdout.trim().to_string(),
+        Some(field) => {
+            let json: Value = serde_json::from_str(stdout)
+                .map_err(|e| format!("Command output is not JSON: {}", e))?;
+            let value = field
+                .split('.')
+                .try_fold(&json, |value, key| value.get(key))
+                .ok_or_else(|| format!("Command output has no field {}", field))?;
+            match value {
+                Value::String(s) => s.trim().to_string(),
+                other => other.to_string(),
+            }
+        }
+    };
+    if token.is_empty() {
+        return Err("Command printed no token".to_string());
+    }
+    Ok(token)
+}
+
+/// How the shell reads the environment variable `name`. cmd expands `!name!`
+/// only after parsing the line (delayed expansion), so its value is never
+/// taken for `&`, `|` or redirections.
+fn env_reference(name: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("\"!{}!\"", name)
+    } else {
+        format!("\"${{{}}}\"", name)
+    }
+}
+
+/// Replaces each `{{reference}}` in `command` that `resolver` can resolve by
+/// a quoted environment variable reference, returning the command and the
+/// variables to run it with. Unresolved references are left as they are.
+fn bind_variables(
+    command: &str,
+    resolver: &mut VariableResolver,
+) -> (String, Vec<(String, String)>) {
+    static REFERENCE: OnceLock<Regex> = OnceLock::new();
+    let reference = REFERENCE.get_or_init(|| Regex::new(r"\{\{[^{}]+\}\}").unwrap());
+    let mut variables = Vec::new();
+    let command = reference
+        .replace_all(command, |caps: &regex::Captures| {
+            let value = resolver.resolve(&caps[0]);
+            if value == caps[0] {
+                return value;
+            }
+            let name = format!("{}{}", VARIABLE_ENV_PREFIX, variables.len());
+            let shell_reference = env_reference(&name);
+            variables.push((name, value));
+            shell_reference
+        })
+        .into_owned();
+    (command, variables)
+}
+
+async fn run_command(config: &CommandAuthConfig) -> Result<String, String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = tokio::process::Command::new("cmd");
+        command.arg("/V:ON").arg("/C").arg(&config.command);
+        command
+    } else {
+        let mut command = tokio::process::Command::new("sh");
+        command.arg("-c").arg(&config.command);
+        command
+    };
+    command
+        .envs(config.variables.iter().cloned())
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    let limit = Duration::from_millis(config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let output = tokio::time::timeout(limit, command.output())
+        .await
+        .map_err(|_| {
+            format!(
+                "Auth command did not finish within {} ms",
+                limit.as_millis()
+            )
+        })?
+        .map_err(|e| format!("Failed to run auth command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Auth command failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    extract_token(
+        &String::from_utf8_lossy(&output.stdout),
+        config.token_field.as_deref(),
+    )
+}
+
+async fn command_token(
+    state: &CommandAuthState,
+    config: &CommandAuthConfig,
+) -> Result<String, String> {
+    if config.command.trim().is_empty() {
+        return Err("No auth command configured".to_string());
+    }
+    if let Some(token) = state.cached(config) {
+        return Ok(token);
+    }
+    let token = run_command(config).await?;
+    state.store(config, &token);
+    Ok(token)
+}
+
+/// Bind the variables in `config`'s command, resolved against the active
+/// environment and, when given, the collection's variables.
+async fn resolve_command(
+    app: &AppHandle,
+    collection_id: Option<&str>,
+    config: &mut CommandAuthConfig,
+) {
+    if !config.command.contains("{{") {
+        return;
+    }
+    let mut variables = load_active_environment_variables(app).await;
+    if let Some(collection_id) = collection_id {
+        variables.extend(load_collection_variables(app, collection_id).await);
+    }
+    let (command, variables) =
+        bind_variables(&config.command, &mut VariableResolver::new(variables));
+    config.command = command;
+    config.variables = variables;
+}
+
+/// Fill in the token of a command auth config, running its command unless a
+/// cached token is still fresh.
+pub(crate) async fn apply_command_token(
+    app: &AppHandle,
+    collection_id: &str,
+    auth: &mut Value,
+) -> Result<(), String> {
+    if auth.get("type").and_then(Value::as_str) != Some("command") {
+        return Ok(());
+    }
+    let config = auth.get("config").cloned().unwrap_or_default();
+    let mut config = serde_json::from_value::<CommandAuthConfig>(config)
+        .map_err(|e| format!("Invalid command auth config: {}", e))?;
+    resolve_command(app, Some(collection_id), &mut config).await;
+    let token = command_token(&app.state::<CommandAuthState>(), &config).await?;
+    auth["config"]["token"] = Value::String(token);
+    Ok(())
+}
+
+/// The token `config`'s command prints, from the cache while it is fresh.
+#[tauri::command]
+pub async fn auth_command_token(
+    app: AppHandle,
+    state: State<'_, CommandAuthState>,
+    config: CommandAuthConfig,
+    collection_id: Option<String>,
+) -> Result<String, String> {
+    let mut config = config;
+    resolve_command(&app, collection_id.as_deref(), &mut config).await;
+    command_token(&state, &config).await
+}
+
+/// Forget cached command tokens, so the next request runs its command again.
+#[tauri::command]
+pub fn auth_command_clear_cache(state: State<'_, CommandAuthState>) -> Result<(), String> {
+    state
+        .tokens
+        .lock()
+        .map_err(|e| format!("Failed to lock token cache: {}", e))?
+        .clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(command: &str) -> CommandAuthConfig {
+        CommandAuthConfig {
+            command: command.to_string(),
+            timeout_ms: None,
+            cache_seconds: None,
+            token_field: None,
+            variables: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn extracts_plain_and_json_tokens() {
+        assert_eq!(extract_token("ya29.abc\n", None).unwrap(), "ya29.abc");
+        let json = r#"{"Credentials":{"SessionToken":"FwoG","Expiration":1}}"#;
+        assert_eq!(
+            extract_token(json, Some("Credentials.SessionToken")).unwrap(),
+            "FwoG"
+        );
+        assert_eq!(
+            extract_token(json, Some("Credentials.Expiration")).unwrap(),
+            "1"
+        );
+        assert!(extract_token(json, Some("Credentials.AccessKeyId")).is_err());
+        assert!(extract_token("not json", Some("token")).is_err());
+        assert!(extract_token("  \n", None).is_err());
+    }
+
+    #[test]
+    fn cached_tokens_expire() {
+        let state = CommandAuthState::default();
+        let mut config = config("print-token");
+        assert_eq!(state.cached(&config), None);
+        state.store(&config, "abc");
+        assert_eq!(state.cached(&config).as_deref(), Some("abc"));
+        config.cache_seconds = Some(0);
+        assert_eq!(state.cached(&config), None);
+    }
+
+    #[test]
+    fn tokens_are_cached_per_variable_value() {
+        let state = CommandAuthState::default();
+        let mut config = config("print-token --profile \"${RESONANCE_AUTH_VAR_0}\"");
+        config.variables = vec![("RESONANCE_AUTH_VAR_0".to_string(), "dev".to_string())];
+        state.store(&config, "dev-token");
+        config.variables[0].1 = "prod".to_string();
+        assert_eq!(state.cached(&config), None);
+    }
+
+    #[test]
+    fn variables_become_environment_references() {
+        let mut resolver = VariableResolver::new(HashMap::from([(
+            "profile".to_string(),
+            "dev; rm -rf ~".to_string(),
+        )]));
+        let (command, variables) = bind_variables(
+            "aws --profile {{profile}} --region {{region}}",
+            &mut resolver,
+        );
+        assert_eq!(
+            command,
+            format!(
+                "aws --profile {} --region {{{{region}}}}",
+                env_reference("RESONANCE_AUTH_VAR_0")
+            )
+        );
+        assert_eq!(
+            variables,
+            vec![(
+                "RESONANCE_AUTH_VAR_0".to_string(),
+                "dev; rm -rf ~".to_string()
+            )]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_the_command_with_a_timeout() {
+        let state = CommandAuthState::default();
+        assert_eq!(
+            command_token(&state, &config("echo token-1"))
+                .await
+                .unwrap(),
+            "token-1"
+        );
+        assert!(command_token(&state, &config("echo oops >&2; exit 3"))
+            .await
+            .unwrap_err()
+            .contains("oops"));
+
+        let mut resolver = VariableResolver::new(HashMap::from([(
+            "name".to_string(),
+            "x'; echo injected; '$(id)".to_string(),
+        )]));
+        let (command, variables) = bind_variables("printf %s {{name}}", &mut resolver);
+        let mut quoted = config(&command);
+        quoted.variables = variables;
+        assert_eq!(
+            command_token(&state, &quoted).await.unwrap(),
+            "x'; echo injected; '$(id)"
+        );
+
+        let mut slow = config("sleep 5");
+        slow.timeout_ms = Some(50);
+        assert!(command_token(&state, &slow)
+            .await
+            .unwrap_err()
+            .contains("did not finish"));
+    }
+}
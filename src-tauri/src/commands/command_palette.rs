@@ -0,0 +1,359 @@
+//! Data behind the Ctrl+K command palette: one index of actions, collections,
+//! endpoints, environments and history entries, searched with fuzzy
+//! subsequence matching. The index is built once when the palette opens and
+//! kept here, so each keystroke only scores in-memory items instead of
+//! re-reading every collection.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use tauri_plugin_store::StoreExt;
+
+use super::collections::collections_get_all;
+use super::workspaces::store_file;
+
+const ENVIRONMENTS_KEY: &str = "environments";
+const HISTORY_KEY: &str = "requestHistory";
+const DEFAULT_LIMIT: usize = 50;
+
+/// Palette actions, as (id, title, keywords). Ids are the ids of the buttons
+/// that trigger them, so the palette can click the button.
+const ACTIONS: &[(&str, &str, &str)] = &[
+    ("send-request-btn", "Send Request", "run execute"),
+    (
+        "resend-idempotency-btn",
+        "Resend With Same Idempotency Key",
+        "retry replay idempotent",
+    ),
+    ("curl-btn", "Generate Code Snippet", "curl export copy"),
+    ("runner-btn", "Open Collection Runner", "run tests batch"),
+    (
+        "import-collection-btn",
+        "Import Collection",
+        "openapi postman har curl",
+    ),
+    ("mock-server-btn", "Mock Server", "mock stub"),
+    (
+        "webhook-receiver-btn",
+        "Webhook Receiver",
+        "request bin catcher callback",
+    ),
+    ("cookie-jar-btn", "Cookie Jar", "cookies"),
+    (
+        "network-diagnostics-btn",
+        "Network Diagnostics",
+        "ping traceroute dns",
+    ),
+    ("history-toggle-btn", "Toggle History", "recent requests"),
+    ("keyboard-shortcuts-btn", "Keyboard Shortcuts", "keys help"),
+    ("settings-btn", "Settings", "preferences options"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteItem {
+    /// "action", "collection", "endpoint", "environment" or "history"
+    pub kind: &'static str,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    /// Collection the endpoint belongs to, or the collection a history entry was sent from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Extra words that match without being shown
+    #[serde(skip)]
+    keywords: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteMatch {
+    #[serde(flatten)]
+    pub item: PaletteItem,
+    pub score: i64,
+    /// Char indices of the matched characters in `title`, for highlighting
+    pub highlights: Vec<usize>,
+}
+
+#[derive(Default)]
+pub struct CommandPaletteState {
+    index: Mutex<Option<Vec<PaletteItem>>>,
+}
+
+fn item(kind: &'static str, id: &str, title: &str) -> PaletteItem {
+    PaletteItem {
+        kind,
+        id: id.to_string(),
+        title: title.to_string(),
+        subtitle: None,
+        collection_id: None,
+        method: None,
+        keywords: String::new(),
+    }
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn endpoint_items(
+    endpoints: &[Value],
+    collection_id: &str,
+    location: &str,
+    items: &mut Vec<PaletteItem>,
+) {
+    for endpoint in endpoints {
+        let id = str_field(endpoint, "id");
+        if id.is_empty() {
+            continue;
+        }
+        let method = str_field(endpoint, "method").to_ascii_uppercase();
+        let path = str_field(endpoint, "path");
+        let name = str_field(endpoint, "name");
+        let mut entry = item("endpoint", id, if name.is_empty() { path } else { name });
+        entry.subtitle = Some(format!("{} {} · {}", method, path, location));
+        entry.collection_id = Some(collection_id.to_string());
+        entry.keywords = format!("{} {}", method, path);
+        entry.method = (!method.is_empty()).then_some(method);
+        items.push(entry);
+    }
+}
+
+async fn build_index(app: &AppHandle) -> Result<Vec<PaletteItem>, String> {
+    let mut items: Vec<PaletteItem> = ACTIONS
+        .iter()
+        .map(|(id, title, keywords)| {
+            let mut action = item("action", id, title);
+            action.keywords = keywords.to_string();
+            action
+        })
+        .collect();
+
+    for collection in collections_get_all(app.clone()).await? {
+        let mut entry = item("collection", &collection.id, &collection.name);
+        entry.subtitle = (!collection.base_url.is_empty()).then(|| collection.base_url.clone());
+        items.push(entry);
+
+        endpoint_items(
+            &collection.endpoints,
+            &collection.id,
+            &collection.name,
+            &mut items,
+        );
+        for folder in &collection.folders {
+            let endpoints = folder
+                .get("endpoints")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let location = format!("{} / {}", collection.name, str_field(folder, "name"));
+            endpoint_items(endpoints, &collection.id, &location, &mut items);
+        }
+    }
+
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+    for environment in environments.as_array().into_iter().flatten() {
+        let id = str_field(environment, "id");
+        if !id.is_empty() {
+            items.push(item("environment", id, str_field(environment, "name")));
+        }
+    }
+
+    let history = store.get(HISTORY_KEY).unwrap_or(Value::Null);
+    for entry in history.as_array().into_iter().flatten() {
+        let id = str_field(entry, "id");
+        let Some(request) = entry.get("request") else {
+            continue;
+        };
+        if id.is_empty() {
+            continue;
+        }
+        let method = str_field(request, "method").to_ascii_uppercase();
+        let mut history_item = item("history", id, str_field(request, "url"));
+        history_item.subtitle = entry
+            .pointer("/response/status")
+            .and_then(Value::as_u64)
+            .map(|status| format!("{} · {}", method, status));
+        history_item.collection_id = request
+            .get("collectionId")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        history_item.method = (!method.is_empty()).then_some(method);
+        items.push(history_item);
+    }
+
+    Ok(items)
+}
+
+fn is_word_start(text: &[char], pos: usize) -> bool {
+    pos == 0
+        || matches!(
+            text[pos - 1],
+            ' ' | '/' | '-' | '_' | '.' | ':' | '{' | '?' | '='
+        )
+}
+
+/// Fuzzy subsequence score of `query` (lowercase, no spaces) against `text`,
+/// with the matched char positions. Word starts and runs of consecutive
+/// characters score higher, gaps and a late first match lower; a plain
+/// substring match beats any scattered one.
+fn fuzzy_match(query: &[char], text: &str) -> Option<(i64, Vec<usize>)> {
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+
+    let substring = text.windows(query.len()).position(|window| window == query);
+    let positions: Vec<usize> = match substring {
+        Some(start) => (start..start + query.len()).collect(),
+        None => {
+            let mut positions = Vec::with_capacity(query.len());
+            let mut from = 0;
+            for c in query {
+                let pos = from + text[from..].iter().position(|t| t == c)?;
+                positions.push(pos);
+                from = pos + 1;
+            }
+            positions
+        }
+    };
+
+    let mut score: i64 = if substring.is_some() { 20 } else { 0 };
+    score -= positions[0].min(10) as i64;
+    for (i, &pos) in positions.iter().enumerate() {
+        score += 1;
+        if is_word_start(&text, pos) {
+            score += 8;
+        }
+        if i > 0 {
+            let gap = pos - positions[i - 1] - 1;
+            if gap == 0 {
+                score += 5;
+            } else {
+                score -= gap.min(3) as i64;
+            }
+        }
+    }
+    Some((score, positions))
+}
+
+fn kind_rank(kind: &str) -> usize {
+    ["action", "collection", "endpoint", "environment", "history"]
+        .iter()
+        .position(|k| *k == kind)
+        .unwrap_or(usize::MAX)
+}
+
+fn search(items: &[PaletteItem], query: &str, limit: usize) -> Vec<PaletteMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    let mut matches: Vec<PaletteMatch> = items
+        .iter()
+        .filter_map(|item| {
+            // Matches outside the title count for less and have nothing to highlight
+            let (score, highlights) = fuzzy_match(&query, &item.title).or_else(|| {
+                let other = format!(
+                    "{} {}",
+                    item.subtitle.as_deref().unwrap_or_default(),
+                    item.keywords
+                );
+                fuzzy_match(&query, &other).map(|(score, _)| (score / 2, Vec::new()))
+            })?;
+            Some(PaletteMatch {
+                item: item.clone(),
+                score,
+                highlights,
+            })
+        })
+        .collect();
+
+    // Stable sort keeps index order (recent history first) among equals
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| kind_rank(a.item.kind).cmp(&kind_rank(b.item.kind)))
+            .then_with(|| a.item.title.len().cmp(&b.item.title.len()))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Rebuilds the index from disk. Call when the palette opens; returns the
+/// number of items.
+#[tauri::command]
+pub async fn command_palette_refresh(
+    app: AppHandle,
+    state: State<'_, CommandPaletteState>,
+) -> Result<usize, String> {
+    let items = build_index(&app).await?;
+    let count = items.len();
+    *state.index.lock().unwrap() = Some(items);
+    Ok(count)
+}
+
+/// Best matches for `query`, best first. An empty query lists the index in
+/// order. Builds the index first if it was never refreshed.
+#[tauri::command]
+pub async fn command_palette_search(
+    app: AppHandle,
+    state: State<'_, CommandPaletteState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<PaletteMatch>, String> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let cached = state
+        .index
+        .lock()
+        .unwrap()
+        .as_deref()
+        .map(|items| search(items, &query, limit));
+    if let Some(matches) = cached {
+        return Ok(matches);
+    }
+    let items = build_index(&app).await?;
+    let matches = search(&items, &query, limit);
+    *state.index.lock().unwrap() = Some(items);
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_word_starts_and_substrings_first() {
+        let mut list_users = item("endpoint", "e1", "List Users");
+        list_users.subtitle = Some("GET /users · Petstore".to_string());
+        let items = vec![
+            item("endpoint", "e2", "Delete user session"),
+            list_users,
+            item("collection", "c1", "Petstore"),
+            item("action", "settings-btn", "Settings"),
+        ];
+
+        let matches = search(&items, "lu", 10);
+        assert_eq!(matches[0].item.id, "e1");
+        assert_eq!(matches[0].highlights, vec![0, 5]);
+
+        let matches = search(&items, "user", 10);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].item.id, "e1");
+
+        // Subtitle-only matches are found but carry no highlights
+        let matches = search(&items, "get users", 10);
+        assert_eq!(matches[0].item.id, "e1");
+        assert!(matches[0].highlights.is_empty());
+
+        assert_eq!(search(&items, "", 2).len(), 2);
+        assert!(search(&items, "zzz", 10).is_empty());
+    }
+}
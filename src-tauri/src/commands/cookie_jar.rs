@@ -0,0 +1,575 @@
+//! Persistent cookie jar.
+//!
+//! Cookies set by responses are kept per active environment in the `cookieJar`
+//! store key, grouped by domain, and sent again with later requests whose URL
+//! they match (RFC 6265 domain, path, `Secure` and expiry rules). A request
+//! with its own `Cookie` header is sent as it is. The jar can be turned off
+//! with the `cookieJarEnabled` setting.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use url::Url;
+
+use super::api_request::RequestOptions;
+use super::store::{lock_store, save_store};
+use super::variables::active_environment_id;
+use super::workspaces::store_file;
+
+const COOKIE_JAR_KEY: &str = "cookieJar";
+const SETTINGS_KEY: &str = "settings";
+/// Jar of the requests sent without an active environment.
+const DEFAULT_ENVIRONMENT: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredCookie {
+    /// `environmentId|domain|path|name`
+    #[serde(default)]
+    pub id: String,
+    #[serde(default = "default_environment")]
+    pub environment_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+    pub domain: String,
+    #[serde(default = "root_path")]
+    pub path: String,
+    /// Unix timestamp (ms) the cookie expires at; a session cookie when unset
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub same_site: Option<String>,
+    /// Sent to the exact host only, as the cookie had no `Domain` attribute
+    #[serde(default)]
+    pub host_only: bool,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// A cookie added or edited in the cookie manager.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookieInput {
+    #[serde(default)]
+    pub environment_id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+    pub domain: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub expires: Option<i64>,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub same_site: Option<String>,
+    #[serde(default)]
+    pub host_only: bool,
+}
+
+fn default_environment() -> String {
+    DEFAULT_ENVIRONMENT.to_string()
+}
+
+fn root_path() -> String {
+    "/".to_string()
+}
+
+/// Cookies by domain.
+type Jar = BTreeMap<String, Vec<StoredCookie>>;
+
+fn cookie_id(environment_id: &str, domain: &str, path: &str, name: &str) -> String {
+    format!("{}|{}|{}|{}", environment_id, domain, path, name)
+}
+
+fn canonical_domain(domain: &str) -> String {
+    domain.trim().trim_start_matches('.').to_lowercase()
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_lowercase();
+        let in_domain = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        in_domain
+            && path_matches(&self.path, url.path())
+            && (!self.secure || url.scheme() == "https")
+    }
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn path_matches(cookie_path: &str, request_path: &str) -> bool {
+    request_path == cookie_path
+        || request_path
+            .strip_prefix(cookie_path)
+            .is_some_and(|rest| cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+/// The path a cookie without a `Path` attribute gets: the directory of the
+/// request path.
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => root_path(),
+        Some(index) => request_path[..index].to_string(),
+    }
+}
+
+/// An `Expires` date in the format of RFC 1123 or the older `01-Jan-2030`
+/// variant.
+fn parse_expires(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc2822(value)
+        .map(|date| date.timestamp_millis())
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+                .map(|date| date.and_utc().timestamp_millis())
+        })
+        .ok()
+}
+
+/// The cookie a `Set-Cookie` header of a response to `url` sets, or `None`
+/// when the header is malformed or names a domain the URL is not in.
+fn parse_set_cookie(
+    header: &str,
+    url: &Url,
+    environment_id: &str,
+    now: i64,
+) -> Option<StoredCookie> {
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+
+    let mut domain = None;
+    let mut path = None;
+    let mut expires = None;
+    let mut max_age = None;
+    let mut http_only = false;
+    let mut secure = false;
+    let mut same_site = None;
+    for attribute in parts {
+        let (key, value) = match attribute.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (attribute.trim(), ""),
+        };
+        match key.to_lowercase().as_str() {
+            "domain" if !value.is_empty() => domain = Some(canonical_domain(value)),
+            "path" if value.starts_with('/') => path = Some(value.to_string()),
+            "expires" => expires = parse_expires(value).or(expires),
+            "max-age" => max_age = value.parse::<i64>().ok(),
+            "httponly" => http_only = true,
+            "secure" => secure = true,
+            "samesite" if value.is_empty() => same_site = Some("None".to_string()),
+            "samesite" => same_site = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if domain
+        .as_deref()
+        .is_some_and(|domain| !domain_matches(&host, domain))
+    {
+        return None;
+    }
+    // Max-Age wins over Expires; zero or less deletes the cookie
+    if let Some(seconds) = max_age {
+        expires = Some(if seconds <= 0 {
+            0
+        } else {
+            now.saturating_add(seconds.saturating_mul(1000))
+        });
+    }
+    let host_only = domain.is_none();
+    let domain = domain.unwrap_or(host);
+    let path = path.unwrap_or_else(|| default_path(url.path()));
+    Some(StoredCookie {
+        id: cookie_id(environment_id, &domain, &path, name),
+        environment_id: environment_id.to_string(),
+        name: name.to_string(),
+        value: value.trim().to_string(),
+        domain,
+        path,
+        expires,
+        http_only,
+        secure,
+        same_site,
+        host_only,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// The stored jar; the flat list earlier versions kept is grouped by domain,
+/// and ids are brought to the `environmentId|domain|path|name` form.
+fn jar_from_value(value: Value) -> Jar {
+    let cookies: Vec<StoredCookie> = match value {
+        Value::Array(cookies) => cookies
+            .into_iter()
+            .filter_map(|cookie| serde_json::from_value(cookie).ok())
+            .collect(),
+        Value::Object(domains) => domains
+            .into_iter()
+            .filter_map(|(_, cookies)| serde_json::from_value::<Vec<StoredCookie>>(cookies).ok())
+            .flatten()
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut jar = Jar::new();
+    for mut cookie in cookies {
+        cookie.domain = canonical_domain(&cookie.domain);
+        cookie.id = cookie_id(
+            &cookie.environment_id,
+            &cookie.domain,
+            &cookie.path,
+            &cookie.name,
+        );
+        put(&mut jar, cookie);
+    }
+    jar
+}
+
+/// Add `cookie` in place of the cookie with the same id, keeping that one's
+/// creation time; a newer stored cookie is kept instead.
+fn put(jar: &mut Jar, mut cookie: StoredCookie) {
+    let cookies = jar.entry(cookie.domain.clone()).or_default();
+    match cookies.iter().position(|existing| existing.id == cookie.id) {
+        Some(index) => {
+            if cookies[index].updated_at > cookie.updated_at {
+                return;
+            }
+            cookie.created_at = cookies[index].created_at;
+            cookies[index] = cookie;
+        }
+        None => cookies.push(cookie),
+    }
+}
+
+fn remove_where(jar: &mut Jar, remove: impl Fn(&StoredCookie) -> bool) {
+    for cookies in jar.values_mut() {
+        cookies.retain(|cookie| !remove(cookie));
+    }
+    jar.retain(|_, cookies| !cookies.is_empty());
+}
+
+/// Store the cookies `set_cookies` set; expired ones delete the cookie they
+/// name. Returns whether the jar changed.
+fn store_set_cookies(
+    jar: &mut Jar,
+    set_cookies: &[String],
+    url: &Url,
+    environment_id: &str,
+    now: i64,
+) -> bool {
+    let mut changed = false;
+    for header in set_cookies {
+        let Some(cookie) = parse_set_cookie(header, url, environment_id, now) else {
+            continue;
+        };
+        if cookie.is_expired(now) {
+            let id = cookie.id;
+            remove_where(jar, |existing| existing.id == id);
+        } else {
+            put(jar, cookie);
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// The `Cookie` header of a request to `url`: longer paths first, then the
+/// older cookies, as RFC 6265 recommends.
+fn cookie_header(jar: &Jar, url: &Url, environment_id: &str, now: i64) -> Option<String> {
+    let mut matching: Vec<&StoredCookie> = jar
+        .values()
+        .flatten()
+        .filter(|cookie| {
+            cookie.environment_id == environment_id
+                && !cookie.is_expired(now)
+                && cookie.matches(url)
+        })
+        .collect();
+    if matching.is_empty() {
+        return None;
+    }
+    matching.sort_by(|a, b| {
+        b.path
+            .len()
+            .cmp(&a.path.len())
+            .then(a.created_at.cmp(&b.created_at))
+    });
+    let pairs: Vec<String> = matching
+        .iter()
+        .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+        .collect();
+    Some(pairs.join("; "))
+}
+
+fn read_jar(app: &AppHandle) -> Result<Jar, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    Ok(jar_from_value(
+        store.get(COOKIE_JAR_KEY).unwrap_or(Value::Null),
+    ))
+}
+
+/// Apply `change` to the stored jar, dropping expired cookies on the way.
+fn update_jar<T>(app: &AppHandle, change: impl FnOnce(&mut Jar) -> T) -> Result<T, String> {
+    let _guard = lock_store();
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let mut jar = jar_from_value(store.get(COOKIE_JAR_KEY).unwrap_or(Value::Null));
+    let result = change(&mut jar);
+    let now = chrono::Utc::now().timestamp_millis();
+    remove_where(&mut jar, |cookie| cookie.is_expired(now));
+    let value = serde_json::to_value(&jar).map_err(|e| e.to_string())?;
+    store.set(COOKIE_JAR_KEY.to_string(), value);
+    save_store(app)?;
+    Ok(result)
+}
+
+/// The environment whose cookies requests send and receive, or `None` when
+/// the cookie jar is turned off.
+fn jar_environment(app: &AppHandle) -> Option<String> {
+    let enabled = app
+        .store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(SETTINGS_KEY))
+        .and_then(|settings| settings.get("cookieJarEnabled")?.as_bool())
+        .unwrap_or(true);
+    enabled.then(|| active_environment_id(app).unwrap_or_else(default_environment))
+}
+
+/// Add the jar's cookies for the request's URL, unless it sets its own
+/// `Cookie` header. Returns the environment whose jar the response's cookies
+/// go into, `None` when the jar is off.
+pub(crate) fn attach_cookies(app: &AppHandle, options: &mut RequestOptions) -> Option<String> {
+    let environment_id = jar_environment(app)?;
+    let has_cookie_header = options
+        .headers
+        .as_ref()
+        .is_some_and(|headers| headers.keys().any(|key| key.eq_ignore_ascii_case("cookie")));
+    if has_cookie_header {
+        return Some(environment_id);
+    }
+    let (Ok(url), Ok(jar)) = (Url::parse(&options.url), read_jar(app)) else {
+        return Some(environment_id);
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    if let Some(header) = cookie_header(&jar, &url, &environment_id, now) {
+        options
+            .headers
+            .get_or_insert_with(Default::default)
+            .insert("Cookie".to_string(), header);
+    }
+    Some(environment_id)
+}
+
+/// Keep the cookies a response to `url` set in the environment's jar.
+pub(crate) fn remember_cookies(
+    app: &AppHandle,
+    environment_id: &str,
+    url: &str,
+    set_cookies: &[String],
+) -> Result<(), String> {
+    if set_cookies.is_empty() {
+        return Ok(());
+    }
+    let Ok(url) = Url::parse(url) else {
+        return Ok(());
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    update_jar(app, |jar| {
+        store_set_cookies(jar, set_cookies, &url, environment_id, now)
+    })
+    .map(|_| ())
+}
+
+/// Cookies in the jar, of one environment and domain when given.
+#[tauri::command]
+pub async fn cookies_list(
+    app: AppHandle,
+    environment_id: Option<String>,
+    domain: Option<String>,
+) -> Result<Vec<StoredCookie>, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let domain = domain.map(|domain| canonical_domain(&domain));
+    Ok(read_jar(&app)?
+        .into_iter()
+        .filter(|(key, _)| domain.is_none() || domain.as_ref() == Some(key))
+        .flat_map(|(_, cookies)| cookies)
+        .filter(|cookie| {
+            !cookie.is_expired(now)
+                && (environment_id.is_none()
+                    || environment_id.as_deref() == Some(cookie.environment_id.as_str()))
+        })
+        .collect())
+}
+
+/// Add a cookie or replace the one with the same environment, domain, path
+/// and name.
+#[tauri::command]
+pub async fn cookies_set(app: AppHandle, cookie: CookieInput) -> Result<StoredCookie, String> {
+    let name = cookie.name.trim();
+    let domain = canonical_domain(&cookie.domain);
+    if name.is_empty() || domain.is_empty() {
+        return Err("A cookie needs a name and a domain".to_string());
+    }
+    let environment_id = cookie.environment_id.unwrap_or_else(default_environment);
+    let path = cookie
+        .path
+        .filter(|path| path.starts_with('/'))
+        .unwrap_or_else(root_path);
+    let now = chrono::Utc::now().timestamp_millis();
+    let stored = StoredCookie {
+        id: cookie_id(&environment_id, &domain, &path, name),
+        environment_id,
+        name: name.to_string(),
+        value: cookie.value,
+        domain,
+        path,
+        expires: cookie.expires,
+        http_only: cookie.http_only,
+        secure: cookie.secure,
+        same_site: cookie.same_site,
+        host_only: cookie.host_only,
+        created_at: now,
+        updated_at: now,
+    };
+    update_jar(&app, |jar| put(jar, stored.clone()))?;
+    Ok(stored)
+}
+
+#[tauri::command]
+pub async fn cookies_delete(app: AppHandle, id: String) -> Result<(), String> {
+    update_jar(&app, |jar| remove_where(jar, |cookie| cookie.id == id))
+}
+
+/// Remove the cookies of a domain, in one environment when given.
+#[tauri::command]
+pub async fn cookies_clear_domain(
+    app: AppHandle,
+    domain: String,
+    environment_id: Option<String>,
+) -> Result<(), String> {
+    let domain = canonical_domain(&domain);
+    update_jar(&app, |jar| {
+        remove_where(jar, |cookie| {
+            cookie.domain == domain
+                && (environment_id.is_none()
+                    || environment_id.as_deref() == Some(cookie.environment_id.as_str()))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    #[test]
+    fn stores_set_cookies_and_sends_them_back_to_matching_urls() {
+        let url = Url::parse("https://api.example.com/v1/login").unwrap();
+        let mut jar = Jar::new();
+        let changed = store_set_cookies(
+            &mut jar,
+            &[
+                "session=abc; Path=/; Secure; HttpOnly".to_string(),
+                "theme=dark; Domain=.example.com; Max-Age=3600".to_string(),
+                "scoped=1".to_string(),
+                "evil=1; Domain=other.com".to_string(),
+            ],
+            &url,
+            "env-1",
+            NOW,
+        );
+        assert!(changed);
+        assert_eq!(
+            jar.keys().collect::<Vec<_>>(),
+            ["api.example.com", "example.com"]
+        );
+        let theme = &jar["example.com"][0];
+        assert_eq!(theme.expires, Some(NOW + 3_600_000));
+        assert!(!theme.host_only);
+        assert_eq!(theme.id, "env-1|example.com|/|theme");
+        assert_eq!(jar["api.example.com"][1].path, "/v1");
+
+        let header = |url: &str, environment: &str| {
+            cookie_header(&jar, &Url::parse(url).unwrap(), environment, NOW)
+        };
+        assert_eq!(
+            header("https://api.example.com/v1/users", "env-1").as_deref(),
+            Some("scoped=1; session=abc; theme=dark")
+        );
+        assert_eq!(
+            header("http://www.example.com/", "env-1").as_deref(),
+            Some("theme=dark")
+        );
+        assert_eq!(header("https://api.example.com/v1/users", "env-2"), None);
+        assert_eq!(header("https://notexample.com/", "env-1"), None);
+
+        let root = Url::parse("https://api.example.com/").unwrap();
+        store_set_cookies(&mut jar, &["session=prod".to_string()], &root, "env-2", NOW);
+        store_set_cookies(
+            &mut jar,
+            &["session=; Expires=Thu, 01 Jan 1970 00:00:00 GMT".to_string()],
+            &root,
+            "env-1",
+            NOW,
+        );
+        assert_eq!(
+            header("https://api.example.com/v1/users", "env-1").as_deref(),
+            Some("scoped=1; theme=dark")
+        );
+        assert_eq!(
+            header("https://api.example.com/", "env-2").as_deref(),
+            Some("session=prod")
+        );
+        assert_eq!(
+            parse_expires("Wed, 21-Oct-2015 07:28:00 GMT"),
+            parse_expires("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+
+        // Earlier versions kept a flat list with `domain|path|name` ids
+        let legacy = serde_json::json!([
+            { "id": "example.com|/|a", "name": "a", "value": "old", "domain": "example.com",
+              "path": "/", "expires": null, "updatedAt": 2 },
+            { "id": "example.com|/|a", "name": "a", "value": "new", "domain": ".Example.com",
+              "path": "/", "expires": null, "updatedAt": 3 }
+        ]);
+        let migrated = jar_from_value(legacy);
+        assert_eq!(migrated["example.com"].len(), 1);
+        assert_eq!(migrated["example.com"][0].id, "default|example.com|/|a");
+        assert_eq!(migrated["example.com"][0].value, "new");
+        let round_trip = jar_from_value(serde_json::to_value(&migrated).unwrap());
+        assert_eq!(round_trip, migrated);
+    }
+}
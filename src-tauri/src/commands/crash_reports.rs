@@ -0,0 +1,266 @@
+//! Crash reports written to disk, for bug reports like "white screen on
+//! startup" that leave nothing else behind. A panic hook writes one report per
+//! panic, and the frontend reports its first uncaught error of each launch.
+//! Every report carries the version, OS, a backtrace where there is one, and
+//! the last commands invoked (plus later frontend errors) leading up to it.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Manager, Runtime};
+
+const CRASH_REPORTS_DIR: &str = "crash-reports";
+/// Older reports are deleted beyond this many.
+const MAX_REPORTS: usize = 20;
+const RECENT_ACTIVITY_LEN: usize = 50;
+
+static REPORTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+static APP_VERSION: OnceLock<String> = OnceLock::new();
+static RECENT_ACTIVITY: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static FRONTEND_ERROR_REPORTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+    /// File name of the report, used to fetch it
+    pub id: String,
+    pub created_at: String,
+    pub kind: String,
+    pub message: String,
+}
+
+fn record_activity(entry: String) {
+    // A panic while the lock was held must not stop later reports
+    let mut recent = RECENT_ACTIVITY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if recent.len() == RECENT_ACTIVITY_LEN {
+        recent.pop_front();
+    }
+    recent.push_back(format!(
+        "{} {}",
+        chrono::Utc::now().format("%H:%M:%S%.3f"),
+        entry
+    ));
+}
+
+/// Wraps the invoke handler so the commands leading up to a crash end up in
+/// its report. Only command names are kept, never arguments.
+pub fn track_commands<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        record_activity(invoke.message.command().to_string());
+        handler(invoke)
+    }
+}
+
+fn format_report(kind: &str, message: &str, details: &str) -> String {
+    let recent = RECENT_ACTIVITY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut report = format!(
+        "Resonance crash report\n\
+         Kind: {}\n\
+         Time: {}\n\
+         Version: {}\n\
+         OS: {} {} ({})\n\
+         Message: {}\n",
+        kind,
+        chrono::Utc::now().to_rfc3339(),
+        APP_VERSION.get().map(String::as_str).unwrap_or("unknown"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::FAMILY,
+        message.trim(),
+    );
+    if !details.trim().is_empty() {
+        report.push_str(&format!("\n{}\n", details.trim_end()));
+    }
+    report.push_str("\nRecent commands (oldest first):\n");
+    if recent.is_empty() {
+        report.push_str("  (none)\n");
+    }
+    for entry in recent.iter() {
+        report.push_str(&format!("  {}\n", entry));
+    }
+    report
+}
+
+fn prune_reports(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    // Names start with a sortable timestamp, newest last
+    reports.sort();
+    let excess = reports.len().saturating_sub(MAX_REPORTS);
+    for path in reports.into_iter().take(excess) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn write_report(kind: &str, message: &str, details: &str) -> Option<PathBuf> {
+    let dir = REPORTS_DIR.get()?;
+    std::fs::create_dir_all(dir).ok()?;
+    let path = dir.join(format!(
+        "crash-{}-{}.txt",
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+        kind
+    ));
+    std::fs::write(&path, format_report(kind, message, details)).ok()?;
+    prune_reports(dir);
+    Some(path)
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string());
+    match info.location() {
+        Some(location) => format!("{} (at {})", message, location),
+        None => message,
+    }
+}
+
+/// Points reports at the app data directory and installs the panic hook,
+/// keeping the default hook's output on stderr. Call first in setup.
+pub fn start_crash_reporter(app: &AppHandle) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(CRASH_REPORTS_DIR);
+    let _ = REPORTS_DIR.set(dir);
+    let _ = APP_VERSION.set(app.package_info().version.to_string());
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let details = format!(
+            "Thread: {}\n\nBacktrace:\n{}",
+            thread.name().unwrap_or("<unnamed>"),
+            std::backtrace::Backtrace::force_capture()
+        );
+        if let Some(path) = write_report("panic", &panic_message(info), &details) {
+            eprintln!("Crash report written to {}", path.display());
+        }
+        default_hook(info);
+    }));
+    Ok(())
+}
+
+fn reports_dir() -> Result<&'static PathBuf, String> {
+    REPORTS_DIR
+        .get()
+        .ok_or_else(|| "Crash reporting is not initialized".to_string())
+}
+
+fn report_path(id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    Ok(reports_dir()?.join(id))
+}
+
+fn report_field(report: &str, name: &str) -> String {
+    report
+        .lines()
+        .find_map(|line| line.strip_prefix(name))
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Reports an uncaught frontend error. Only the first one of a launch gets a
+/// report (that is the one behind a blank window); later ones are kept as
+/// context for the next report.
+#[tauri::command]
+pub fn crash_report_frontend_error(message: String, stack: Option<String>) -> Option<String> {
+    let path = if FRONTEND_ERROR_REPORTED.swap(true, Ordering::SeqCst) {
+        None
+    } else {
+        let details = stack
+            .map(|stack| format!("Stack:\n{}", stack))
+            .unwrap_or_default();
+        write_report("frontend", &message, &details)
+    };
+    record_activity(format!("frontend error: {}", message));
+    path.and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    })
+}
+
+/// Saved reports, newest first.
+#[tauri::command]
+pub fn crash_reports_list() -> Result<Vec<CrashReportSummary>, String> {
+    let dir = reports_dir()?;
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut summaries: Vec<CrashReportSummary> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|entry| {
+            let content = std::fs::read_to_string(entry.path()).ok()?;
+            Some(CrashReportSummary {
+                id: entry.file_name().to_string_lossy().to_string(),
+                created_at: report_field(&content, "Time:"),
+                kind: report_field(&content, "Kind:"),
+                message: report_field(&content, "Message:"),
+            })
+        })
+        .collect();
+    summaries.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(summaries)
+}
+
+/// Full text of a report, to attach to an issue.
+#[tauri::command]
+pub fn crash_report_get(id: String) -> Result<String, String> {
+    std::fs::read_to_string(report_path(&id)?)
+        .map_err(|e| format!("Failed to read crash report {}: {}", id, e))
+}
+
+#[tauri::command]
+pub fn crash_reports_clear() -> Result<(), String> {
+    let dir = reports_dir()?;
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)
+            .map_err(|e| format!("Failed to clear crash reports: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_includes_environment_and_recent_commands() {
+        record_activity("collections_list".to_string());
+        record_activity("send_api_request".to_string());
+
+        let report = format_report("panic", "index out of bounds", "Backtrace:\n  0: main");
+        assert_eq!(report_field(&report, "Kind:"), "panic");
+        assert_eq!(report_field(&report, "Message:"), "index out of bounds");
+        assert!(report.contains(std::env::consts::OS));
+        assert!(report.contains("Backtrace:\n  0: main"));
+        let commands = report.split("Recent commands").nth(1).unwrap();
+        assert!(commands.find("collections_list") < commands.find("send_api_request"));
+
+        assert!(report_path("../settings.json").is_err());
+    }
+}
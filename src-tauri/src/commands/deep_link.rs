@@ -0,0 +1,310 @@
+//! `resonance://` deep links, so documentation sites can link into the app
+//! ("Run in Resonance" buttons):
+//!
+//! - `resonance://collection?id=<id>` or `?name=<name>` reveals a collection
+//! - `resonance://import?url=<spec url>` imports an OpenAPI spec from a URL
+//! - `resonance://request?method=POST&url=<url>&header=Name:%20Value&body=<body>`
+//!   opens a new tab with the request pre-filled (`header` may repeat)
+//! - `resonance://bundle?data=<base64url bundle>` imports a shared request
+//!   bundle
+//!
+//! Files passed on the command line (or through "Open with" in a file
+//! manager) take the same route: their import format is detected here and
+//! they are queued as `ImportFile` actions.
+//!
+//! Links and files are queued until the frontend takes them, since whatever
+//! launches the app arrives before the webview is listening.
+
+use crate::commands::import_export::detect_import_kind;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+pub const SCHEME: &str = "resonance";
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(
+    tag = "action",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum DeepLinkAction {
+    OpenCollection {
+        collection_id: Option<String>,
+        name: Option<String>,
+    },
+    ImportSpec {
+        url: String,
+    },
+    NewRequest {
+        method: String,
+        url: String,
+        headers: Vec<(String, String)>,
+        body: Option<String>,
+    },
+    /// A shared request bundle; `link` is the whole link, read with
+    /// `request_bundle_open`
+    ImportBundle {
+        link: String,
+    },
+    /// A local file to import; `kind` is "openapi", "postman", "postman_dump",
+    /// "request_bundle", "har", "http" or "proto"
+    ImportFile {
+        path: String,
+        kind: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedDeepLink {
+    /// The link, or the path of an opened file
+    pub source: String,
+    pub action: Option<DeepLinkAction>,
+    /// Why the link could not be understood
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<Vec<ReceivedDeepLink>>,
+}
+
+fn is_http_url(value: &str) -> bool {
+    url::Url::parse(value)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+pub fn parse_deep_link(link: &str) -> Result<DeepLinkAction, String> {
+    let url = url::Url::parse(link).map_err(|e| format!("Invalid link: {}", e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {}:// link", SCHEME));
+    }
+    // `resonance://import?...` carries the action as host, `resonance:import?...` as path
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/')
+        .to_ascii_lowercase();
+    let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    match action.as_str() {
+        "collection" => {
+            let collection_id = param("id");
+            let name = param("name");
+            if collection_id.is_none() && name.is_none() {
+                return Err("Collection links need an id or name".to_string());
+            }
+            Ok(DeepLinkAction::OpenCollection {
+                collection_id,
+                name,
+            })
+        }
+        "import" => {
+            let spec_url = param("url").ok_or("Import links need a url")?;
+            if !is_http_url(&spec_url) {
+                return Err(format!(
+                    "Specs can only be imported over HTTP(S): {}",
+                    spec_url
+                ));
+            }
+            Ok(DeepLinkAction::ImportSpec { url: spec_url })
+        }
+        "request" => {
+            let request_url = param("url").ok_or("Request links need a url")?;
+            let method = param("method")
+                .unwrap_or_else(|| "GET".to_string())
+                .to_ascii_uppercase();
+            if !method.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("Invalid method: {}", method));
+            }
+            let headers = params
+                .iter()
+                .filter(|(key, _)| key == "header")
+                .filter_map(|(_, value)| value.split_once(':'))
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+                .filter(|(name, _)| !name.is_empty())
+                .collect();
+            let body = params
+                .iter()
+                .find(|(key, _)| key == "body")
+                .map(|(_, value)| value.clone());
+            Ok(DeepLinkAction::NewRequest {
+                method,
+                url: request_url,
+                headers,
+                body,
+            })
+        }
+        "bundle" => {
+            super::request_bundle::parse_bundle(link)?;
+            Ok(DeepLinkAction::ImportBundle {
+                link: link.to_string(),
+            })
+        }
+        "" => Err("The link has no action".to_string()),
+        other => Err(format!("Unknown link action: {}", other)),
+    }
+}
+
+fn enqueue(app: &AppHandle, received: Vec<(String, Result<DeepLinkAction, String>)>) {
+    if received.is_empty() {
+        return;
+    }
+    let state = app.state::<DeepLinkState>();
+    let mut pending = state.pending.lock().unwrap();
+    for (source, parsed) in received {
+        let (action, error) = match parsed {
+            Ok(action) => (Some(action), None),
+            Err(e) => (None, Some(e)),
+        };
+        pending.push(ReceivedDeepLink {
+            source,
+            action,
+            error,
+        });
+    }
+    drop(pending);
+    let _ = app.emit("deep-link-received", ());
+}
+
+fn receive(app: &AppHandle, urls: Vec<String>) {
+    enqueue(
+        app,
+        urls.into_iter()
+            .map(|url| {
+                let parsed = parse_deep_link(&url);
+                (url, parsed)
+            })
+            .collect(),
+    );
+}
+
+/// Existing files among launch arguments, relative ones resolved against
+/// `cwd`. Flags and links are skipped.
+pub fn launch_file_args(args: impl IntoIterator<Item = String>, cwd: &Path) -> Vec<PathBuf> {
+    args.into_iter()
+        .filter(|arg| !arg.starts_with('-') && !arg.contains("://"))
+        .map(|arg| cwd.join(arg))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Queues files opened with the app for import.
+pub fn receive_files(app: &AppHandle, paths: Vec<PathBuf>) {
+    enqueue(
+        app,
+        paths
+            .into_iter()
+            .map(|path| {
+                let parsed = detect_import_kind(&path).map(|kind| DeepLinkAction::ImportFile {
+                    path: path.to_string_lossy().to_string(),
+                    kind: kind.to_string(),
+                });
+                (path.to_string_lossy().to_string(), parsed)
+            })
+            .collect(),
+    );
+}
+
+/// Registers the scheme where that happens at runtime (Linux, and Windows in
+/// development; installers register it otherwise) and queues the link the app
+/// was launched with as well as every later one.
+pub fn start_deep_links(app: &AppHandle) -> Result<(), String> {
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    app.deep_link()
+        .register_all()
+        .map_err(|e| format!("Failed to register the {}:// scheme: {}", SCHEME, e))?;
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        receive(app, urls.iter().map(|url| url.to_string()).collect());
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        receive(
+            &handle,
+            event.urls().iter().map(|url| url.to_string()).collect(),
+        );
+    });
+    Ok(())
+}
+
+/// Links received since the last call, oldest first.
+#[tauri::command]
+pub fn deep_link_take_pending(state: State<'_, DeepLinkState>) -> Vec<ReceivedDeepLink> {
+    std::mem::take(&mut *state.pending.lock().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_deep_link_actions() {
+        assert_eq!(
+            parse_deep_link("resonance://collection?name=Petstore").unwrap(),
+            DeepLinkAction::OpenCollection {
+                collection_id: None,
+                name: Some("Petstore".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_deep_link("resonance://import?url=https%3A%2F%2Fexample.com%2Fopenapi.yaml")
+                .unwrap(),
+            DeepLinkAction::ImportSpec {
+                url: "https://example.com/openapi.yaml".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_deep_link(
+                "resonance://request?method=post&url=https://api.example.com/users\
+                 &header=Content-Type:%20application/json&header=X-Trace:1&body=%7B%7D"
+            )
+            .unwrap(),
+            DeepLinkAction::NewRequest {
+                method: "POST".to_string(),
+                url: "https://api.example.com/users".to_string(),
+                headers: vec![
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    ("X-Trace".to_string(), "1".to_string()),
+                ],
+                body: Some("{}".to_string()),
+            }
+        );
+
+        assert!(parse_deep_link("resonance://import?url=file:///etc/passwd").is_err());
+        assert!(parse_deep_link("resonance://collection").is_err());
+        assert!(parse_deep_link("resonance://unknown").is_err());
+        assert!(parse_deep_link("https://example.com").is_err());
+    }
+
+    #[test]
+    fn keeps_existing_files_from_launch_args() {
+        let dir = std::env::temp_dir().join(format!("resonance-args-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("api.yaml"), "openapi: 3.0.0").unwrap();
+
+        let args = [
+            "--flag",
+            "api.yaml",
+            "missing.json",
+            "resonance://collection?name=Pets",
+        ]
+        .map(String::from);
+        assert_eq!(launch_file_args(args, &dir), vec![dir.join("api.yaml")]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,516 @@
+//! Network diagnostics for telling API failures from network ones: TCP and
+//! ICMP ping, traceroute and DNS record lookups for the host of a request.
+//! ICMP needs raw sockets, so ping and traceroute run the system tools and
+//! parse their output; TCP ping and DNS are done in-process.
+
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::proto::rr::RecordType;
+use hickory_resolver::TokioAsyncResolver;
+use regex::Regex;
+use serde::Serialize;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+
+const DEFAULT_PING_COUNT: u32 = 4;
+const MAX_PING_COUNT: u32 = 20;
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+const DEFAULT_MAX_HOPS: u32 = 30;
+const MAX_HOPS: u32 = 64;
+/// Pause between TCP connection attempts, like the one-second rhythm of ping
+/// but shorter since nothing is waiting on a reply timer.
+const TCP_PING_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingReply {
+    pub seq: u32,
+    /// Round-trip (ICMP) or connect (TCP) time, None when the probe failed
+    pub time_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingResult {
+    pub host: String,
+    pub address: String,
+    /// "tcp" or "icmp"
+    pub method: String,
+    /// Port connected to by a TCP ping
+    pub port: Option<u16>,
+    pub replies: Vec<PingReply>,
+    pub sent: u32,
+    pub received: u32,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceHop {
+    pub hop: u32,
+    /// Address that answered the hop, None when every probe timed out
+    pub address: Option<String>,
+    pub times_ms: Vec<f64>,
+    /// Probes of the hop that got no answer
+    pub lost: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerouteResult {
+    pub host: String,
+    pub address: String,
+    pub hops: Vec<TraceHop>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsRecord {
+    pub record_type: String,
+    pub name: String,
+    pub ttl: u32,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsLookupError {
+    pub record_type: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsLookupResult {
+    pub host: String,
+    pub records: Vec<DnsRecord>,
+    /// Lookups that failed; a type without records is not an error
+    pub errors: Vec<DnsLookupError>,
+    pub duration_ms: f64,
+}
+
+/// Host and port of a diagnostics target, which may be a bare host, a
+/// `host:port` pair or a full request URL.
+pub(super) fn parse_target(target: &str) -> Result<(String, Option<u16>), String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("No host given".to_string());
+    }
+    if target.contains("://") {
+        let url = url::Url::parse(target).map_err(|e| format!("Invalid URL: {}", e))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| format!("URL has no host: {}", target))?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        return Ok((host.to_string(), url.port_or_known_default()));
+    }
+    if target.parse::<IpAddr>().is_ok() {
+        return Ok((target.to_string(), None));
+    }
+    let bracketed = target
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'));
+    if let Some((host, rest)) = bracketed {
+        let port = rest.strip_prefix(':').and_then(|port| port.parse().ok());
+        return Ok((host.to_string(), port));
+    }
+    match target.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse()
+                .map_err(|_| format!("Invalid port in {}", target))?;
+            Ok((host.to_string(), Some(port)))
+        }
+        None => Ok((target.to_string(), None)),
+    }
+}
+
+pub(super) async fn resolve(host: &str, port: u16) -> Result<SocketAddr, String> {
+    tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Could not resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("Could not resolve {}: no addresses", host))
+}
+
+fn round_ms(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 100_000.0).round() / 100.0
+}
+
+fn ping_time_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?i)time[=<]\s*([\d.]+)\s*ms").unwrap())
+}
+
+fn icmp_seq_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"icmp_seq=(\d+)").unwrap())
+}
+
+/// Replies in the output of the system `ping`. Windows prints no sequence
+/// numbers, so replies are then numbered in order.
+fn parse_ping_output(output: &str) -> Vec<PingReply> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let time = ping_time_pattern().captures(line)?[1].parse().ok()?;
+            Some((icmp_seq_pattern().captures(line), time))
+        })
+        .enumerate()
+        .map(|(index, (seq, time))| PingReply {
+            seq: seq
+                .and_then(|caps| caps[1].parse().ok())
+                .unwrap_or(index as u32 + 1),
+            time_ms: Some(time),
+            error: None,
+        })
+        .collect()
+}
+
+/// A hop line of `traceroute -n` or `tracert -d` output, e.g.
+/// ` 3  10.0.0.1  1.234 ms  1.102 ms *` or `  3    <1 ms    2 ms     *     10.0.0.1`.
+fn parse_trace_line(line: &str) -> Option<TraceHop> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let hop = tokens.first()?.parse().ok()?;
+    let mut address = None;
+    let mut times_ms = Vec::new();
+    let mut lost = 0;
+    for (index, token) in tokens.iter().enumerate().skip(1) {
+        if *token == "*" {
+            lost += 1;
+        } else if token.eq_ignore_ascii_case("ms") {
+            if let Ok(time) = tokens[index - 1].trim_start_matches('<').parse() {
+                times_ms.push(time);
+            }
+        } else if address.is_none() && token.parse::<IpAddr>().is_ok() {
+            address = Some(token.to_string());
+        }
+    }
+    Some(TraceHop {
+        hop,
+        address,
+        times_ms,
+        lost,
+    })
+}
+
+fn summarize(
+    host: String,
+    address: String,
+    method: &str,
+    port: Option<u16>,
+    replies: Vec<PingReply>,
+    sent: u32,
+) -> PingResult {
+    let times: Vec<f64> = replies.iter().filter_map(|reply| reply.time_ms).collect();
+    let received = times.len() as u32;
+    let min_ms = times.iter().copied().reduce(f64::min);
+    let max_ms = times.iter().copied().reduce(f64::max);
+    let avg_ms = (received > 0)
+        .then(|| (times.iter().sum::<f64>() / received as f64 * 100.0).round() / 100.0);
+    PingResult {
+        host,
+        address,
+        method: method.to_string(),
+        port,
+        replies,
+        sent,
+        received,
+        min_ms,
+        avg_ms,
+        max_ms,
+    }
+}
+
+async fn tcp_ping(addr: SocketAddr, count: u32, timeout: Duration) -> Vec<PingReply> {
+    let mut replies = Vec::new();
+    for seq in 1..=count {
+        if seq > 1 {
+            tokio::time::sleep(TCP_PING_INTERVAL).await;
+        }
+        let started = Instant::now();
+        let reply = match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+            Ok(Ok(_)) => PingReply {
+                seq,
+                time_ms: Some(round_ms(started.elapsed())),
+                error: None,
+            },
+            Ok(Err(e)) => PingReply {
+                seq,
+                time_ms: None,
+                error: Some(e.to_string()),
+            },
+            Err(_) => PingReply {
+                seq,
+                time_ms: None,
+                error: Some(format!("No answer within {} ms", timeout.as_millis())),
+            },
+        };
+        replies.push(reply);
+    }
+    replies
+}
+
+/// Runs a system diagnostics tool, which exits non-zero when some probes
+/// are lost; the output is parsed either way.
+async fn run_tool(program: &str, args: &[String], limit: Duration) -> Result<String, String> {
+    let output = tokio::time::timeout(limit, Command::new(program).args(args).output())
+        .await
+        .map_err(|_| format!("{} did not finish within {} s", program, limit.as_secs()))?
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() && stdout.trim().is_empty() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(stdout)
+}
+
+fn ping_command(ip: IpAddr, count: u32, timeout_ms: u64) -> (&'static str, Vec<String>) {
+    if cfg!(target_os = "windows") {
+        let args = vec![
+            "-n".into(),
+            count.to_string(),
+            "-w".into(),
+            timeout_ms.to_string(),
+            ip.to_string(),
+        ];
+        ("ping", args)
+    } else {
+        // macOS keeps IPv6 in a separate binary; Linux `ping` handles both
+        let program = if cfg!(target_os = "macos") && ip.is_ipv6() {
+            "ping6"
+        } else {
+            "ping"
+        };
+        (
+            program,
+            vec!["-c".into(), count.to_string(), ip.to_string()],
+        )
+    }
+}
+
+fn traceroute_command(ip: IpAddr, max_hops: u32) -> (&'static str, Vec<String>) {
+    if cfg!(target_os = "windows") {
+        let args = vec![
+            "-d".into(),
+            "-h".into(),
+            max_hops.to_string(),
+            "-w".into(),
+            "2000".into(),
+            ip.to_string(),
+        ];
+        ("tracert", args)
+    } else {
+        let program = if cfg!(target_os = "macos") && ip.is_ipv6() {
+            "traceroute6"
+        } else {
+            "traceroute"
+        };
+        let args = vec![
+            "-n".into(),
+            "-m".into(),
+            max_hops.to_string(),
+            "-w".into(),
+            "2".into(),
+            ip.to_string(),
+        ];
+        (program, args)
+    }
+}
+
+/// Pings a host, either by opening TCP connections to a port (works through
+/// firewalls that drop ICMP, and measures what a request sees) or with ICMP
+/// echo via the system `ping`.
+///
+/// The TCP port defaults to the port of the target URL, else 443.
+#[tauri::command]
+pub async fn network_ping(
+    target: String,
+    method: String,
+    port: Option<u16>,
+    count: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> Result<PingResult, String> {
+    let (host, target_port) = parse_target(&target)?;
+    let count = count.unwrap_or(DEFAULT_PING_COUNT).clamp(1, MAX_PING_COUNT);
+    let timeout_ms = timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).max(100);
+
+    match method.as_str() {
+        "tcp" => {
+            let port = port.or(target_port).unwrap_or(443);
+            let addr = resolve(&host, port).await?;
+            let replies = tcp_ping(addr, count, Duration::from_millis(timeout_ms)).await;
+            Ok(summarize(
+                host,
+                addr.ip().to_string(),
+                "tcp",
+                Some(port),
+                replies,
+                count,
+            ))
+        }
+        "icmp" => {
+            let ip = resolve(&host, 0).await?.ip();
+            let (program, args) = ping_command(ip, count, timeout_ms);
+            let limit = Duration::from_millis((timeout_ms + 1000) * count as u64 + 5000);
+            let output = run_tool(program, &args, limit).await?;
+            let replies = parse_ping_output(&output);
+            Ok(summarize(
+                host,
+                ip.to_string(),
+                "icmp",
+                None,
+                replies,
+                count,
+            ))
+        }
+        other => Err(format!("Unknown ping method: {}", other)),
+    }
+}
+
+/// Traces the route to a host with the system `traceroute` (`tracert` on
+/// Windows), without reverse DNS so it finishes quickly.
+#[tauri::command]
+pub async fn network_traceroute(
+    target: String,
+    max_hops: Option<u32>,
+) -> Result<TracerouteResult, String> {
+    let (host, _) = parse_target(&target)?;
+    let max_hops = max_hops.unwrap_or(DEFAULT_MAX_HOPS).clamp(1, MAX_HOPS);
+    let ip = resolve(&host, 0).await?.ip();
+
+    let (program, args) = traceroute_command(ip, max_hops);
+    // Three probes of up to two seconds per hop
+    let limit = Duration::from_secs(max_hops as u64 * 6 + 10);
+    let output = run_tool(program, &args, limit).await?;
+    let hops = output.lines().filter_map(parse_trace_line).collect();
+
+    Ok(TracerouteResult {
+        host,
+        address: ip.to_string(),
+        hops,
+    })
+}
+
+/// Looks up DNS records of a host with the system resolver configuration.
+/// Supported types are A, AAAA, CNAME, SRV and TXT; SRV lookups take the
+/// service name, e.g. `_sip._tcp.example.com`.
+#[tauri::command]
+pub async fn network_dns_lookup(
+    target: String,
+    record_types: Vec<String>,
+) -> Result<DnsLookupResult, String> {
+    let (host, _) = parse_target(&target)?;
+    let types = record_types
+        .iter()
+        .map(|name| match name.to_ascii_uppercase().as_str() {
+            "A" => Ok(RecordType::A),
+            "AAAA" => Ok(RecordType::AAAA),
+            "CNAME" => Ok(RecordType::CNAME),
+            "SRV" => Ok(RecordType::SRV),
+            "TXT" => Ok(RecordType::TXT),
+            _ => Err(format!("Unsupported DNS record type: {}", name)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|e| format!("Failed to read the system DNS configuration: {}", e))?;
+
+    let started = Instant::now();
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for record_type in types {
+        match resolver.lookup(host.as_str(), record_type).await {
+            Ok(lookup) => {
+                // Answers can include the CNAME chain ahead of the records asked for
+                records.extend(lookup.record_iter().filter_map(|record| {
+                    Some(DnsRecord {
+                        record_type: record.record_type().to_string(),
+                        name: record.name().to_string(),
+                        ttl: record.ttl(),
+                        value: record.data()?.to_string(),
+                    })
+                }));
+            }
+            Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => {}
+            Err(e) => errors.push(DnsLookupError {
+                record_type: record_type.to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(DnsLookupResult {
+        host,
+        records,
+        errors,
+        duration_ms: round_ms(started.elapsed()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_targets_and_tool_output() {
+        assert_eq!(
+            parse_target("https://api.example.com/v1/users").unwrap(),
+            ("api.example.com".to_string(), Some(443))
+        );
+        assert_eq!(
+            parse_target("localhost:8080").unwrap(),
+            ("localhost".to_string(), Some(8080))
+        );
+        assert_eq!(
+            parse_target("[::1]:3000").unwrap(),
+            ("::1".to_string(), Some(3000))
+        );
+        assert_eq!(parse_target("::1").unwrap(), ("::1".to_string(), None));
+        assert!(parse_target("  ").is_err());
+
+        let linux = "PING 10.0.0.1 (10.0.0.1) 56(84) bytes of data.\n\
+            64 bytes from 10.0.0.1: icmp_seq=1 ttl=64 time=0.412 ms\n\
+            64 bytes from 10.0.0.1: icmp_seq=3 ttl=64 time=1.05 ms\n";
+        let replies = parse_ping_output(linux);
+        assert_eq!(replies.len(), 2);
+        assert_eq!((replies[1].seq, replies[1].time_ms), (3, Some(1.05)));
+
+        let windows = "Reply from 10.0.0.1: bytes=32 time<1ms TTL=64\n\
+            Reply from 10.0.0.1: bytes=32 time=12ms TTL=64\n";
+        let replies = parse_ping_output(windows);
+        assert_eq!((replies[1].seq, replies[1].time_ms), (2, Some(12.0)));
+
+        assert_eq!(
+            parse_trace_line(" 2  192.168.1.1  1.234 ms  1.102 ms *"),
+            Some(TraceHop {
+                hop: 2,
+                address: Some("192.168.1.1".to_string()),
+                times_ms: vec![1.234, 1.102],
+                lost: 1,
+            })
+        );
+        assert_eq!(
+            parse_trace_line("  3    <1 ms    12 ms     *     10.0.0.1"),
+            Some(TraceHop {
+                hop: 3,
+                address: Some("10.0.0.1".to_string()),
+                times_ms: vec![1.0, 12.0],
+                lost: 1,
+            })
+        );
+        assert_eq!(
+            parse_trace_line("traceroute to 10.0.0.1, 30 hops max"),
+            None
+        );
+    }
+}
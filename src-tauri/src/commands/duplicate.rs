@@ -0,0 +1,248 @@
+//! Deep copies of collections, folders and endpoints.
+//!
+//! Every copied endpoint and folder gets a fresh UUID. Per-endpoint data files
+//! (including scripts), collection variables and the secrets stored for the
+//! copied items come along, so a copy sends exactly what the original sends.
+
+use serde_json::Value;
+use std::path::Path;
+use tauri::AppHandle;
+
+use super::collections::{
+    collection_get, collection_get_endpoint_data, collection_get_variables,
+    collection_save_endpoint_data, collection_save_variables, persist_collection, Collection,
+    EndpointData,
+};
+use super::secrets::copy_secret_scope;
+
+fn copy_name(name: &str) -> String {
+    format!("{} (copy)", name)
+}
+
+fn id_of(value: &Value) -> Option<&str> {
+    value.get("id").and_then(Value::as_str)
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Give every endpoint in `endpoints` a new id and return `(old, new)` pairs.
+fn reassign_endpoint_ids(endpoints: &mut [Value]) -> Vec<(String, String)> {
+    let mut ids = Vec::new();
+    for endpoint in endpoints.iter_mut() {
+        let Some(old_id) = id_of(endpoint).map(str::to_string) else {
+            continue;
+        };
+        let id = new_id();
+        endpoint["id"] = Value::String(id.clone());
+        ids.push((old_id, id));
+    }
+    ids
+}
+
+/// Rename a copied folder or endpoint, falling back to its path for unnamed endpoints.
+fn rename_copy(value: &mut Value) {
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| value.get("path").and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string();
+    value["name"] = Value::String(copy_name(&name));
+}
+
+fn auth_scope(collection_id: &str, endpoint_id: &str) -> String {
+    format!("auth:{}:{}", collection_id, endpoint_id)
+}
+
+fn folder_auth_scope(collection_id: &str, folder_id: &str) -> String {
+    format!("auth:{}:__folder__:{}", collection_id, folder_id)
+}
+
+/// Copy the data file and auth secrets of each `(old, new)` endpoint pair.
+/// The new endpoints must already be part of the persisted target collection.
+async fn copy_endpoints(
+    app: &AppHandle,
+    from_collection: &str,
+    to_collection: &str,
+    ids: &[(String, String)],
+) -> Result<(), String> {
+    let empty = serde_json::to_value(EndpointData::default()).map_err(|e| e.to_string())?;
+    for (old_id, id) in ids {
+        let data =
+            collection_get_endpoint_data(app.clone(), from_collection.to_string(), old_id.clone())
+                .await?;
+        if serde_json::to_value(&data).map_err(|e| e.to_string())? != empty {
+            collection_save_endpoint_data(app.clone(), to_collection.to_string(), id.clone(), data)
+                .await?;
+        }
+        copy_secret_scope(
+            app,
+            &auth_scope(from_collection, old_id),
+            &auth_scope(to_collection, id),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Copy a whole collection next to the original.
+#[tauri::command]
+pub async fn collection_duplicate(
+    app: AppHandle,
+    collection_id: String,
+) -> Result<Collection, String> {
+    let source = collection_get(app.clone(), collection_id.clone()).await?;
+    let mut copy = source.clone();
+    copy.id = new_id();
+    copy.name = copy_name(&source.name);
+    copy.storage_parent_path = source
+        .storage_path
+        .as_deref()
+        .and_then(|path| Path::new(path).parent())
+        .map(|parent| parent.to_string_lossy().to_string());
+    copy.storage_path = None;
+
+    let mut ids = reassign_endpoint_ids(&mut copy.endpoints);
+    let mut folder_ids = Vec::new();
+    for folder in copy.folders.iter_mut() {
+        if let Some(old_id) = id_of(folder).map(str::to_string) {
+            let id = new_id();
+            folder["id"] = Value::String(id.clone());
+            folder_ids.push((old_id, id));
+        }
+        if let Some(endpoints) = folder.get_mut("endpoints").and_then(Value::as_array_mut) {
+            ids.extend(reassign_endpoint_ids(endpoints));
+        }
+    }
+
+    let copy = persist_collection(&app, copy)?;
+    copy_endpoints(&app, &collection_id, &copy.id, &ids).await?;
+
+    let variables = collection_get_variables(app.clone(), collection_id.clone()).await?;
+    if !variables.is_empty() {
+        collection_save_variables(app.clone(), copy.id.clone(), variables).await?;
+    }
+    let scopes = [
+        (
+            format!("collvar:{}", collection_id),
+            format!("collvar:{}", copy.id),
+        ),
+        (
+            auth_scope(&collection_id, "__collection__"),
+            auth_scope(&copy.id, "__collection__"),
+        ),
+    ];
+    for (from, to) in scopes {
+        copy_secret_scope(&app, &from, &to).await?;
+    }
+    for (old_id, id) in &folder_ids {
+        copy_secret_scope(
+            &app,
+            &folder_auth_scope(&collection_id, old_id),
+            &folder_auth_scope(&copy.id, id),
+        )
+        .await?;
+    }
+
+    Ok(copy)
+}
+
+/// Copy a folder and its endpoints; the copy is placed right after the original.
+#[tauri::command]
+pub async fn folder_duplicate(
+    app: AppHandle,
+    collection_id: String,
+    folder_id: String,
+) -> Result<Value, String> {
+    let mut collection = collection_get(app.clone(), collection_id.clone()).await?;
+    let pos = collection
+        .folders
+        .iter()
+        .position(|f| id_of(f) == Some(folder_id.as_str()))
+        .ok_or_else(|| format!("Folder {} not found", folder_id))?;
+
+    let mut folder = collection.folders[pos].clone();
+    let id = new_id();
+    folder["id"] = Value::String(id.clone());
+    rename_copy(&mut folder);
+    let ids = folder
+        .get_mut("endpoints")
+        .and_then(Value::as_array_mut)
+        .map(|endpoints| reassign_endpoint_ids(endpoints))
+        .unwrap_or_default();
+    collection.folders.insert(pos + 1, folder.clone());
+
+    persist_collection(&app, collection)?;
+    copy_endpoints(&app, &collection_id, &collection_id, &ids).await?;
+    copy_secret_scope(
+        &app,
+        &folder_auth_scope(&collection_id, &folder_id),
+        &folder_auth_scope(&collection_id, &id),
+    )
+    .await?;
+    Ok(folder)
+}
+
+/// Insert a copy of the endpoint after `endpoint_id` in `endpoints`, if it is there.
+fn insert_copy(endpoints: &mut Vec<Value>, endpoint_id: &str, id: &str) -> Option<Value> {
+    let pos = endpoints
+        .iter()
+        .position(|e| id_of(e) == Some(endpoint_id))?;
+    let mut copy = endpoints[pos].clone();
+    copy["id"] = Value::String(id.to_string());
+    rename_copy(&mut copy);
+    endpoints.insert(pos + 1, copy.clone());
+    Some(copy)
+}
+
+/// Copy a single endpoint within its collection, next to the original.
+#[tauri::command]
+pub async fn endpoint_duplicate(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<Value, String> {
+    let mut collection = collection_get(app.clone(), collection_id.clone()).await?;
+    let id = new_id();
+    let copy = match insert_copy(&mut collection.endpoints, &endpoint_id, &id) {
+        Some(copy) => Some(copy),
+        None => collection.folders.iter_mut().find_map(|folder| {
+            let endpoints = folder.get_mut("endpoints").and_then(Value::as_array_mut)?;
+            insert_copy(endpoints, &endpoint_id, &id)
+        }),
+    }
+    .ok_or_else(|| format!("Endpoint {} not found", endpoint_id))?;
+
+    persist_collection(&app, collection)?;
+    copy_endpoints(&app, &collection_id, &collection_id, &[(endpoint_id, id)]).await?;
+    Ok(copy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn copies_get_new_ids_and_a_copy_suffix() {
+        let mut endpoints = vec![
+            json!({"id": "e1", "name": "List users", "path": "/users"}),
+            json!({"id": "e2", "name": "", "path": "/users/{id}"}),
+        ];
+
+        let copy = insert_copy(&mut endpoints, "e2", "e3").unwrap();
+        assert_eq!(copy["name"], "/users/{id} (copy)");
+        assert_eq!(endpoints.len(), 3);
+        assert_eq!(endpoints[2]["id"], "e3");
+        assert!(insert_copy(&mut endpoints, "missing", "e4").is_none());
+
+        let ids = reassign_endpoint_ids(&mut endpoints);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0].0, "e1");
+        assert_eq!(endpoints[0]["id"], ids[0].1.as_str());
+        assert_ne!(ids[0].1, "e1");
+    }
+}
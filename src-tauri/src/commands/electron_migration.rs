@@ -0,0 +1,219 @@
+//! One-time import from the Electron build of Resonance.
+//!
+//! The Electron app kept all of its data in an electron-store `config.json`
+//! inside its userData directory. Most keys carry over unchanged, so the
+//! migration copies them into the current store (never overwriting values
+//! that are already set), reshapes the few whose format changed and then hands
+//! the imported collections to the regular collection migration.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use super::collections::collections_migrate;
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+/// Directory names the Electron app used for its userData directory.
+const LEGACY_DIRS: [&str; 2] = ["resonance", "Resonance"];
+const LEGACY_FILE: &str = "config.json";
+/// Store key recording that the migration ran or was dismissed.
+const MIGRATION_KEY: &str = "electronMigration";
+/// electron-store bookkeeping and Electron window state with no meaning here.
+const IGNORED_KEYS: [&str; 3] = ["__internal__", "windowBounds", "windowState"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyInstall {
+    pub path: String,
+    pub key_count: usize,
+    pub collection_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub source_path: String,
+    pub imported_keys: Vec<String>,
+    /// Keys left alone because the current store already has a value for them.
+    pub skipped_keys: Vec<String>,
+    pub collections: u32,
+}
+
+fn legacy_config_path(app: &AppHandle) -> Option<PathBuf> {
+    let config_dir = app.path().config_dir().ok()?;
+    LEGACY_DIRS
+        .iter()
+        .map(|dir| config_dir.join(dir).join(LEGACY_FILE))
+        .find(|path| path.is_file())
+}
+
+fn read_legacy(path: &Path) -> Result<Map<String, Value>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("{} is not a JSON object", path.display())),
+        Err(e) => Err(format!("Failed to parse {}: {}", path.display(), e)),
+    }
+}
+
+/// Map legacy values onto the current schema.
+fn normalize(mut legacy: Map<String, Value>) -> Map<String, Value> {
+    legacy.retain(|key, _| !IGNORED_KEYS.contains(&key.as_str()));
+
+    // Environments used to be a bare array with the active id in its own key.
+    if let Some(Value::Array(items)) = legacy.get("environments").cloned() {
+        let active = legacy.remove("activeEnvironmentId").unwrap_or(Value::Null);
+        legacy.insert(
+            "environments".to_string(),
+            serde_json::json!({ "items": items, "activeEnvironmentId": active }),
+        );
+    }
+    legacy
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+fn migration_recorded(app: &AppHandle) -> Result<bool, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    Ok(store.get(MIGRATION_KEY).is_some())
+}
+
+fn record_migration(app: &AppHandle, status: &str, source: Option<&str>) -> Result<(), String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    store.set(
+        MIGRATION_KEY.to_string(),
+        serde_json::json!({
+            "status": status,
+            "sourcePath": source,
+            "at": chrono::Utc::now().timestamp_millis(),
+        }),
+    );
+    save_store(app)
+}
+
+/// Look for data left behind by the Electron app. Returns `None` when there is
+/// nothing to import or the user already migrated or dismissed the offer.
+#[tauri::command]
+pub async fn electron_migration_detect(app: AppHandle) -> Result<Option<LegacyInstall>, String> {
+    if migration_recorded(&app)? {
+        return Ok(None);
+    }
+    let Some(path) = legacy_config_path(&app) else {
+        return Ok(None);
+    };
+    let legacy = normalize(read_legacy(&path)?);
+    if legacy.values().all(is_empty_value) {
+        return Ok(None);
+    }
+    let collection_count = legacy
+        .get("collections")
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len);
+
+    Ok(Some(LegacyInstall {
+        path: path.to_string_lossy().to_string(),
+        key_count: legacy.len(),
+        collection_count,
+    }))
+}
+
+/// Import the Electron app's data and report what was copied.
+#[tauri::command]
+pub async fn electron_migration_run(app: AppHandle) -> Result<MigrationReport, String> {
+    let path = legacy_config_path(&app)
+        .ok_or_else(|| "No data from the Electron app was found".to_string())?;
+    let source_path = path.to_string_lossy().to_string();
+    let legacy = normalize(read_legacy(&path)?);
+
+    let mut imported_keys = Vec::new();
+    let mut skipped_keys = Vec::new();
+    {
+        let _guard = lock_store();
+        let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+        for (key, value) in legacy {
+            if is_empty_value(&value) {
+                continue;
+            }
+            match store.get(&key) {
+                Some(existing) if !is_empty_value(&existing) => skipped_keys.push(key),
+                _ => {
+                    store.set(key.clone(), value);
+                    imported_keys.push(key);
+                }
+            }
+        }
+        record_migration(&app, "imported", Some(&source_path))?;
+    }
+
+    let collections = if imported_keys.iter().any(|key| key == "collections") {
+        collections_migrate(app.clone()).await?
+    } else {
+        0
+    };
+
+    Ok(MigrationReport {
+        source_path,
+        imported_keys,
+        skipped_keys,
+        collections,
+    })
+}
+
+/// Stop offering the migration without importing anything.
+#[tauri::command]
+pub async fn electron_migration_dismiss(app: AppHandle) -> Result<(), String> {
+    let _guard = lock_store();
+    record_migration(&app, "dismissed", None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn normalize_reshapes_environments_and_drops_electron_keys() {
+        let legacy = json!({
+            "__internal__": { "migrations": { "version": "1.0.0" } },
+            "windowBounds": { "width": 1200 },
+            "environments": [{ "id": "dev", "name": "Dev" }],
+            "activeEnvironmentId": "dev",
+            "theme": "dark"
+        });
+        let Value::Object(legacy) = legacy else {
+            unreachable!()
+        };
+
+        let normalized = normalize(legacy);
+        assert_eq!(normalized.len(), 2);
+        assert_eq!(normalized["theme"], "dark");
+        assert_eq!(normalized["environments"]["activeEnvironmentId"], "dev");
+        assert_eq!(normalized["environments"]["items"][0]["id"], "dev");
+    }
+
+    #[test]
+    fn normalize_keeps_environments_already_in_the_new_shape() {
+        let Value::Object(legacy) = json!({
+            "environments": { "items": [], "activeEnvironmentId": null },
+            "activeEnvironmentId": "dev"
+        }) else {
+            unreachable!()
+        };
+
+        let normalized = normalize(legacy);
+        assert_eq!(normalized["environments"]["items"], json!([]));
+        assert_eq!(normalized["activeEnvironmentId"], "dev");
+    }
+}
@@ -0,0 +1,227 @@
+//! Saved vs. unsaved request state.
+//!
+//! The saved copy of a request is its per-endpoint file in the collection
+//! directory. Edits that have not been saved yet are kept as a draft in the
+//! store (`endpointDrafts`), so the dirty indicator and Ctrl+S survive a reload
+//! or a crash. Saving writes the draft to the file and drops it; discarding
+//! drops it and returns the saved copy.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::collections::{
+    collection_get_endpoint_data, collection_save_endpoint_data, redact_auth_secrets, EndpointData,
+};
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const DRAFTS_KEY: &str = "endpointDrafts";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointDraft {
+    pub collection_id: String,
+    pub endpoint_id: String,
+    pub data: EndpointData,
+    /// Unix timestamp (ms) of the last draft save
+    pub updated_at: i64,
+}
+
+/// Both copies of a request as shown by the editor.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointState {
+    pub saved: EndpointData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<EndpointData>,
+    pub dirty: bool,
+}
+
+impl EndpointState {
+    fn new(saved: EndpointData, draft: Option<EndpointData>) -> Self {
+        let dirty = draft.is_some();
+        Self {
+            saved,
+            draft,
+            dirty,
+        }
+    }
+}
+
+fn read_drafts(app: &AppHandle) -> Vec<EndpointDraft> {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(DRAFTS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn write_drafts(app: &AppHandle, drafts: &[EndpointDraft]) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    store.set(
+        DRAFTS_KEY.to_string(),
+        serde_json::to_value(drafts).map_err(|e| e.to_string())?,
+    );
+    save_store(app)
+}
+
+fn is_draft_of(draft: &EndpointDraft, collection_id: &str, endpoint_id: &str) -> bool {
+    draft.collection_id == collection_id && draft.endpoint_id == endpoint_id
+}
+
+/// Remove the draft of one endpoint, if any.
+pub(crate) fn remove_draft(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> Result<(), String> {
+    let _guard = lock_store();
+    let mut drafts = read_drafts(app);
+    let before = drafts.len();
+    drafts.retain(|d| !is_draft_of(d, collection_id, endpoint_id));
+    if drafts.len() == before {
+        return Ok(());
+    }
+    write_drafts(app, &drafts)
+}
+
+/// Drop every draft of a collection. Called when a trashed collection is purged.
+pub(crate) fn purge_drafts_for_collection(app: &AppHandle, collection_id: &str) {
+    let _guard = lock_store();
+    let mut drafts = read_drafts(app);
+    let before = drafts.len();
+    drafts.retain(|d| d.collection_id != collection_id);
+    if drafts.len() != before {
+        if let Err(e) = write_drafts(app, &drafts) {
+            eprintln!("Failed to purge drafts of {}: {}", collection_id, e);
+        }
+    }
+}
+
+/// Two copies are the same request when their serialized forms match, which
+/// ignores the difference between absent and default fields.
+fn same_request(a: &EndpointData, b: &EndpointData) -> bool {
+    serde_json::to_value(a).unwrap_or(Value::Null) == serde_json::to_value(b).unwrap_or(Value::Null)
+}
+
+/// Saved copy and draft (if any) of an endpoint.
+#[tauri::command]
+pub async fn endpoint_get(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<EndpointState, String> {
+    let saved =
+        collection_get_endpoint_data(app.clone(), collection_id.clone(), endpoint_id.clone())
+            .await?;
+    let draft = read_drafts(&app)
+        .into_iter()
+        .find(|d| is_draft_of(d, &collection_id, &endpoint_id))
+        .map(|d| d.data);
+    Ok(EndpointState::new(saved, draft))
+}
+
+/// Save (Ctrl+S): write `data` as the endpoint's saved copy and drop its draft.
+#[tauri::command]
+pub async fn endpoint_update(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+    data: EndpointData,
+) -> Result<EndpointState, String> {
+    collection_save_endpoint_data(
+        app.clone(),
+        collection_id.clone(),
+        endpoint_id.clone(),
+        data,
+    )
+    .await?;
+    remove_draft(&app, &collection_id, &endpoint_id)?;
+    let saved = collection_get_endpoint_data(app, collection_id, endpoint_id).await?;
+    Ok(EndpointState::new(saved, None))
+}
+
+/// Record unsaved edits. A draft identical to the saved copy is dropped, so
+/// undoing every change clears the dirty state.
+#[tauri::command]
+pub async fn endpoint_save_draft(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+    mut data: EndpointData,
+) -> Result<EndpointState, String> {
+    // Drafts live in the plaintext store; credentials stay in the secret store
+    if let Some(auth) = data.auth_config.as_mut() {
+        redact_auth_secrets(auth);
+    }
+    let saved =
+        collection_get_endpoint_data(app.clone(), collection_id.clone(), endpoint_id.clone())
+            .await?;
+
+    let _guard = lock_store();
+    let mut drafts = read_drafts(&app);
+    drafts.retain(|d| !is_draft_of(d, &collection_id, &endpoint_id));
+    let draft = if same_request(&saved, &data) {
+        None
+    } else {
+        drafts.push(EndpointDraft {
+            collection_id,
+            endpoint_id,
+            data: data.clone(),
+            updated_at: chrono::Utc::now().timestamp_millis(),
+        });
+        Some(data)
+    };
+    write_drafts(&app, &drafts)?;
+    Ok(EndpointState::new(saved, draft))
+}
+
+/// Throw away unsaved edits and return the saved copy.
+#[tauri::command]
+pub async fn endpoint_discard_draft(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<EndpointState, String> {
+    remove_draft(&app, &collection_id, &endpoint_id)?;
+    let saved = collection_get_endpoint_data(app, collection_id, endpoint_id).await?;
+    Ok(EndpointState::new(saved, None))
+}
+
+/// Endpoints with unsaved edits, optionally limited to one collection. Drives
+/// the dirty-dot indicators in the sidebar and tabs.
+#[tauri::command]
+pub async fn endpoint_drafts_list(
+    app: AppHandle,
+    collection_id: Option<String>,
+) -> Result<Vec<EndpointDraft>, String> {
+    Ok(read_drafts(&app)
+        .into_iter()
+        .filter(|d| match &collection_id {
+            Some(id) => &d.collection_id == id,
+            None => true,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drafts_equal_to_the_saved_copy_are_not_dirty() {
+        let saved = EndpointData {
+            url: Some("{{baseUrl}}/users".to_string()),
+            ..Default::default()
+        };
+        let mut edited = saved.clone();
+        assert!(same_request(&saved, &edited));
+
+        edited.url = Some("{{baseUrl}}/users/1".to_string());
+        assert!(!same_request(&saved, &edited));
+    }
+}
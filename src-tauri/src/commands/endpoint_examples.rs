@@ -0,0 +1,236 @@
+//! Example responses saved on an endpoint.
+//!
+//! A received response can be kept as a named example of its endpoint. The
+//! examples of one endpoint are stored next to its request file, in
+//! `examples/<endpoint id>.json` of the collection directory, so they travel
+//! with the collection, are served by the mock server and are written into
+//! OpenAPI and Postman exports.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+use super::collections::{resolve_collection_dir, sanitize_file_component};
+
+pub(crate) const EXAMPLES_DIR: &str = "examples";
+
+/// Response headers never stored with an example: they belong to one session.
+const SESSION_HEADERS: [&str; 2] = ["set-cookie", "set-cookie2"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseExample {
+    pub id: String,
+    pub name: String,
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    /// Unix timestamp (ms) of the last save
+    pub saved_at: i64,
+}
+
+impl ResponseExample {
+    /// The media type of the example's `Content-Type`, without parameters.
+    pub fn content_type(&self) -> Option<String> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .and_then(|(_, value)| value.split(';').next())
+            .map(|mime| mime.trim().to_lowercase())
+            .filter(|mime| !mime.is_empty())
+    }
+}
+
+/// A response as sent by the frontend to be saved.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExampleInput {
+    pub name: String,
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+}
+
+fn examples_file(collection_dir: &Path, endpoint_id: &str) -> PathBuf {
+    collection_dir
+        .join(EXAMPLES_DIR)
+        .join(format!("{}.json", sanitize_file_component(endpoint_id)))
+}
+
+/// The examples saved on an endpoint, oldest first.
+pub(crate) fn read_examples(collection_dir: &Path, endpoint_id: &str) -> Vec<ResponseExample> {
+    fs::read_to_string(examples_file(collection_dir, endpoint_id))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_examples(
+    collection_dir: &Path,
+    endpoint_id: &str,
+    examples: &[ResponseExample],
+) -> Result<(), String> {
+    let file = examples_file(collection_dir, endpoint_id);
+    if examples.is_empty() {
+        return delete_examples(collection_dir, endpoint_id);
+    }
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create examples dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(examples)
+        .map_err(|e| format!("Failed to serialize examples: {}", e))?;
+    fs::write(&file, json).map_err(|e| format!("Failed to write examples: {}", e))
+}
+
+/// Remove the examples of a deleted endpoint.
+pub(crate) fn delete_examples(collection_dir: &Path, endpoint_id: &str) -> Result<(), String> {
+    let file = examples_file(collection_dir, endpoint_id);
+    if file.exists() {
+        fs::remove_file(&file).map_err(|e| format!("Failed to delete examples: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Add `input` to `examples`, replacing the example of the same name.
+fn upsert_example(examples: &mut Vec<ResponseExample>, input: ExampleInput) -> ResponseExample {
+    let name = input.name.trim().to_string();
+    let headers = input
+        .headers
+        .into_iter()
+        .filter(|(key, _)| {
+            !SESSION_HEADERS
+                .iter()
+                .any(|session| key.eq_ignore_ascii_case(session))
+        })
+        .collect();
+    let existing = examples
+        .iter()
+        .position(|example| example.name.eq_ignore_ascii_case(&name));
+    let example = ResponseExample {
+        id: existing
+            .map(|index| examples[index].id.clone())
+            .unwrap_or_else(|| Uuid::new_v4().to_string()),
+        name,
+        status: input.status,
+        status_text: input.status_text,
+        headers,
+        body: input.body,
+        saved_at: chrono::Utc::now().timestamp_millis(),
+    };
+    match existing {
+        Some(index) => examples[index] = example.clone(),
+        None => examples.push(example.clone()),
+    }
+    example
+}
+
+fn collection_dir(app: &AppHandle, collection_id: &str) -> Result<PathBuf, String> {
+    resolve_collection_dir(app, collection_id)?
+        .ok_or_else(|| format!("Collection {} not found", collection_id))
+}
+
+#[tauri::command]
+pub async fn endpoint_examples_list(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<Vec<ResponseExample>, String> {
+    let dir = collection_dir(&app, &collection_id)?;
+    Ok(read_examples(&dir, &endpoint_id))
+}
+
+/// Save a response as an example of the endpoint; an example with the same
+/// name is overwritten.
+#[tauri::command]
+pub async fn endpoint_example_save(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+    example: ExampleInput,
+) -> Result<ResponseExample, String> {
+    if example.name.trim().is_empty() {
+        return Err("An example needs a name".to_string());
+    }
+    let dir = collection_dir(&app, &collection_id)?;
+    let mut examples = read_examples(&dir, &endpoint_id);
+    let saved = upsert_example(&mut examples, example);
+    write_examples(&dir, &endpoint_id, &examples)?;
+    super::collection_watcher::mark_synced(&app, &collection_id, &dir);
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn endpoint_example_delete(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+    example_id: String,
+) -> Result<Vec<ResponseExample>, String> {
+    let dir = collection_dir(&app, &collection_id)?;
+    let mut examples = read_examples(&dir, &endpoint_id);
+    examples.retain(|example| example.id != example_id);
+    write_examples(&dir, &endpoint_id, &examples)?;
+    super::collection_watcher::mark_synced(&app, &collection_id, &dir);
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str, status: u16) -> ExampleInput {
+        ExampleInput {
+            name: name.to_string(),
+            status,
+            status_text: String::new(),
+            headers: HashMap::from([
+                (
+                    "Content-Type".to_string(),
+                    "application/json; charset=utf-8".to_string(),
+                ),
+                ("Set-Cookie".to_string(), "session=abc".to_string()),
+            ]),
+            body: "{\"id\":1}".to_string(),
+        }
+    }
+
+    #[test]
+    fn examples_are_saved_by_name_next_to_the_collection() {
+        let dir = std::env::temp_dir().join(format!("resonance-examples-{}", Uuid::new_v4()));
+        let mut examples = Vec::new();
+        let created = upsert_example(&mut examples, input(" Found ", 200));
+        upsert_example(&mut examples, input("Missing", 404));
+        let replaced = upsert_example(&mut examples, input("found", 201));
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(replaced.id, created.id);
+        assert_eq!(examples[0].status, 201);
+        assert_eq!(examples[0].name, "found");
+        assert!(!examples[0].headers.contains_key("Set-Cookie"));
+        assert_eq!(
+            examples[0].content_type().as_deref(),
+            Some("application/json")
+        );
+
+        write_examples(&dir, "ep/1", &examples).unwrap();
+        let read = read_examples(&dir, "ep/1");
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[1].name, "Missing");
+
+        write_examples(&dir, "ep/1", &[]).unwrap();
+        assert!(read_examples(&dir, "ep/1").is_empty());
+        assert!(!examples_file(&dir, "ep/1").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,482 @@
+//! Passphrase-encrypted environments.
+//!
+//! An encrypted environment keeps its variable names in `variables` (with empty
+//! values) and the real values in an `encrypted` blob: AES-256-GCM over the JSON
+//! variable map, keyed by PBKDF2-HMAC-SHA256 of the passphrase. The blob is part of
+//! the environment object, so exports and shared files stay encrypted.
+//!
+//! Unlocking derives the key once per session. Decrypted values are held only in
+//! [`EnvironmentVaultState`] and are never written back to the store; locking or
+//! quitting the app forgets them. Secret variables (see `secrets.rs`) are stored
+//! out of band already and are not part of the blob.
+
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+use tauri_plugin_store::StoreExt;
+
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const ENVIRONMENTS_KEY: &str = "environments";
+const FORMAT_VERSION: u32 = 1;
+const KDF_NAME: &str = "pbkdf2-sha256";
+/// OWASP's current recommendation for PBKDF2-HMAC-SHA256.
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// The `encrypted` field of an environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedVariables {
+    pub version: u32,
+    pub kdf: String,
+    pub iterations: u32,
+    /// Base64
+    pub salt: String,
+    /// Base64
+    pub nonce: String,
+    /// Base64 ciphertext followed by the GCM tag
+    pub ciphertext: String,
+}
+
+struct UnlockedEnvironment {
+    key: [u8; KEY_LEN],
+    salt: Vec<u8>,
+    iterations: u32,
+    variables: HashMap<String, String>,
+}
+
+pub struct EnvironmentVaultState {
+    unlocked: Mutex<HashMap<String, UnlockedEnvironment>>,
+}
+
+impl Default for EnvironmentVaultState {
+    fn default() -> Self {
+        Self {
+            unlocked: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+fn b64() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    getrandom::fill(&mut buf).map_err(|e| format!("Failed to generate random bytes: {}", e))?;
+    Ok(buf)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<[u8; KEY_LEN], String> {
+    let iterations =
+        NonZeroU32::new(iterations).ok_or_else(|| "Invalid KDF iteration count".to_string())?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+fn aead_key(key: &[u8; KEY_LEN]) -> Result<LessSafeKey, String> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "Invalid encryption key".to_string())
+}
+
+fn seal(
+    key: &[u8; KEY_LEN],
+    salt: &[u8],
+    iterations: u32,
+    variables: &HashMap<String, String>,
+) -> Result<EncryptedVariables, String> {
+    let nonce = random_bytes::<NONCE_LEN>()?;
+    let mut in_out = serde_json::to_vec(variables).map_err(|e| e.to_string())?;
+    aead_key(key)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| "Failed to encrypt environment".to_string())?;
+    Ok(EncryptedVariables {
+        version: FORMAT_VERSION,
+        kdf: KDF_NAME.to_string(),
+        iterations,
+        salt: b64().encode(salt),
+        nonce: b64().encode(nonce),
+        ciphertext: b64().encode(in_out),
+    })
+}
+
+fn open(
+    key: &[u8; KEY_LEN],
+    payload: &EncryptedVariables,
+) -> Result<HashMap<String, String>, String> {
+    let nonce: [u8; NONCE_LEN] = b64()
+        .decode(&payload.nonce)
+        .ok()
+        .and_then(|n| n.try_into().ok())
+        .ok_or_else(|| "Encrypted environment is corrupted".to_string())?;
+    let mut in_out = b64()
+        .decode(&payload.ciphertext)
+        .map_err(|_| "Encrypted environment is corrupted".to_string())?;
+    let plaintext = aead_key(key)?
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| "Wrong passphrase".to_string())?;
+    serde_json::from_slice(plaintext).map_err(|e| format!("Invalid environment data: {}", e))
+}
+
+/// Derive the key for an existing blob and decrypt it.
+fn unlock_payload(
+    passphrase: &str,
+    payload: &EncryptedVariables,
+) -> Result<UnlockedEnvironment, String> {
+    if payload.version != FORMAT_VERSION || payload.kdf != KDF_NAME {
+        return Err(format!(
+            "Unsupported environment encryption (version {}, {})",
+            payload.version, payload.kdf
+        ));
+    }
+    let salt = b64()
+        .decode(&payload.salt)
+        .map_err(|_| "Encrypted environment is corrupted".to_string())?;
+    let key = derive_key(passphrase, &salt, payload.iterations)?;
+    let variables = open(&key, payload)?;
+    Ok(UnlockedEnvironment {
+        key,
+        salt,
+        iterations: payload.iterations,
+        variables,
+    })
+}
+
+/// Load the `environments` store value and run `f` on the environment with `id`,
+/// saving the store when `f` succeeds.
+fn update_environment<T>(
+    app: &AppHandle,
+    environment_id: &str,
+    f: impl FnOnce(&mut Map<String, Value>) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = lock_store();
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let mut environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+    let environment = environments
+        .get_mut("items")
+        .and_then(Value::as_array_mut)
+        .and_then(|items| {
+            items
+                .iter_mut()
+                .find(|env| env.get("id").and_then(Value::as_str) == Some(environment_id))
+        })
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let result = f(environment)?;
+    store.set(ENVIRONMENTS_KEY.to_string(), environments);
+    save_store(app)?;
+    Ok(result)
+}
+
+fn read_payload(app: &AppHandle, environment_id: &str) -> Result<EncryptedVariables, String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+    let environment = environments
+        .get("items")
+        .and_then(Value::as_array)
+        .and_then(|items| {
+            items
+                .iter()
+                .find(|env| env.get("id").and_then(Value::as_str) == Some(environment_id))
+        })
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let encrypted = environment
+        .get("encrypted")
+        .cloned()
+        .ok_or_else(|| "Environment is not encrypted".to_string())?;
+    serde_json::from_value(encrypted).map_err(|e| format!("Invalid encrypted environment: {}", e))
+}
+
+/// Names of the environment's secret variables, which stay in the secret store.
+fn secret_keys(environment: &Map<String, Value>) -> Vec<String> {
+    environment
+        .get("secretKeys")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write a sealed blob and blank the plaintext values it covers.
+fn store_sealed(
+    environment: &mut Map<String, Value>,
+    payload: &EncryptedVariables,
+    variables: &HashMap<String, String>,
+) -> Result<(), String> {
+    let secrets = secret_keys(environment);
+    let mut placeholders: Map<String, Value> = environment
+        .get("variables")
+        .and_then(Value::as_object)
+        .map(|vars| {
+            vars.iter()
+                .filter(|(k, _)| secrets.contains(k))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    for name in variables.keys() {
+        placeholders.insert(name.clone(), Value::String(String::new()));
+    }
+    environment.insert("variables".to_string(), Value::Object(placeholders));
+    environment.insert(
+        "encrypted".to_string(),
+        serde_json::to_value(payload).map_err(|e| e.to_string())?,
+    );
+    Ok(())
+}
+
+/// Decrypted variables of an unlocked environment, or `None` when it is locked.
+pub(crate) fn unlocked_variables(
+    state: &EnvironmentVaultState,
+    environment_id: &str,
+) -> Option<HashMap<String, String>> {
+    state
+        .unlocked
+        .lock()
+        .unwrap()
+        .get(environment_id)
+        .map(|env| env.variables.clone())
+}
+
+/// Encrypt the (non-secret) variables of an environment with `passphrase`. The
+/// environment stays unlocked for the rest of the session.
+#[tauri::command]
+pub async fn environment_encrypt(
+    app: AppHandle,
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+    passphrase: String,
+) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        ));
+    }
+    let salt = random_bytes::<SALT_LEN>()?;
+    let key = tokio::task::spawn_blocking(move || derive_key(&passphrase, &salt, KDF_ITERATIONS))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    let variables = update_environment(&app, &environment_id, |environment| {
+        if environment.contains_key("encrypted") {
+            return Err("Environment is already encrypted".to_string());
+        }
+        let secrets = secret_keys(environment);
+        let variables: HashMap<String, String> = environment
+            .get("variables")
+            .and_then(Value::as_object)
+            .map(|vars| {
+                vars.iter()
+                    .filter(|(k, _)| !secrets.contains(k))
+                    .map(|(k, v)| match v {
+                        Value::String(s) => (k.clone(), s.clone()),
+                        other => (k.clone(), other.to_string()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let payload = seal(&key, &salt, KDF_ITERATIONS, &variables)?;
+        store_sealed(environment, &payload, &variables)?;
+        Ok(variables)
+    })?;
+
+    state.unlocked.lock().unwrap().insert(
+        environment_id,
+        UnlockedEnvironment {
+            key,
+            salt: salt.to_vec(),
+            iterations: KDF_ITERATIONS,
+            variables,
+        },
+    );
+    Ok(())
+}
+
+/// Unlock an encrypted environment for this session and return its values.
+#[tauri::command]
+pub async fn environment_unlock(
+    app: AppHandle,
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+    passphrase: String,
+) -> Result<HashMap<String, String>, String> {
+    let payload = read_payload(&app, &environment_id)?;
+    let unlocked = tokio::task::spawn_blocking(move || unlock_payload(&passphrase, &payload))
+        .await
+        .map_err(|e| e.to_string())??;
+    let variables = unlocked.variables.clone();
+    state
+        .unlocked
+        .lock()
+        .unwrap()
+        .insert(environment_id, unlocked);
+    Ok(variables)
+}
+
+/// Forget the decrypted values of an environment.
+#[tauri::command]
+pub async fn environment_lock(
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+) -> Result<(), String> {
+    state.unlocked.lock().unwrap().remove(&environment_id);
+    Ok(())
+}
+
+/// Decrypted values of an unlocked environment (`None` while locked). Used by the
+/// frontend when resolving variables for a request or script.
+#[tauri::command]
+pub async fn environment_unlocked_variables(
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+) -> Result<Option<HashMap<String, String>>, String> {
+    Ok(unlocked_variables(&state, &environment_id))
+}
+
+/// Replace the values of an unlocked encrypted environment and re-encrypt them.
+#[tauri::command]
+pub async fn environment_save_encrypted(
+    app: AppHandle,
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+    variables: HashMap<String, String>,
+) -> Result<(), String> {
+    let mut unlocked = state.unlocked.lock().unwrap();
+    let entry = unlocked
+        .get_mut(&environment_id)
+        .ok_or_else(|| "Unlock the environment before editing it".to_string())?;
+    let payload = seal(&entry.key, &entry.salt, entry.iterations, &variables)?;
+    update_environment(&app, &environment_id, |environment| {
+        store_sealed(environment, &payload, &variables)
+    })?;
+    entry.variables = variables;
+    Ok(())
+}
+
+/// Turn an encrypted environment back into a plain one.
+#[tauri::command]
+pub async fn environment_decrypt(
+    app: AppHandle,
+    state: State<'_, EnvironmentVaultState>,
+    environment_id: String,
+    passphrase: String,
+) -> Result<(), String> {
+    let payload = read_payload(&app, &environment_id)?;
+    let unlocked = tokio::task::spawn_blocking(move || unlock_payload(&passphrase, &payload))
+        .await
+        .map_err(|e| e.to_string())??;
+    update_environment(&app, &environment_id, |environment| {
+        let vars = environment
+            .entry("variables".to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Some(vars) = vars.as_object_mut() {
+            for (name, value) in &unlocked.variables {
+                vars.insert(name.clone(), Value::String(value.clone()));
+            }
+        }
+        environment.remove("encrypted");
+        Ok(())
+    })?;
+    state.unlocked.lock().unwrap().remove(&environment_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Low iteration count keeps the tests fast; the format is the same.
+    const TEST_ITERATIONS: u32 = 1_000;
+
+    fn sample() -> HashMap<String, String> {
+        HashMap::from([
+            ("baseUrl".to_string(), "https://api.test".to_string()),
+            ("apiKey".to_string(), "k-123".to_string()),
+        ])
+    }
+
+    #[test]
+    fn round_trips_with_the_right_passphrase_only() {
+        let salt = [7u8; SALT_LEN];
+        let key = derive_key("correct horse", &salt, TEST_ITERATIONS).unwrap();
+        let payload = seal(&key, &salt, TEST_ITERATIONS, &sample()).unwrap();
+        assert!(!payload.ciphertext.contains("k-123"));
+
+        let unlocked = unlock_payload("correct horse", &payload).unwrap();
+        assert_eq!(unlocked.variables, sample());
+        assert_eq!(
+            unlock_payload("wrong horse", &payload).err().as_deref(),
+            Some("Wrong passphrase")
+        );
+    }
+
+    #[test]
+    fn each_seal_uses_a_fresh_nonce() {
+        let salt = [1u8; SALT_LEN];
+        let key = derive_key("passphrase", &salt, TEST_ITERATIONS).unwrap();
+        let a = seal(&key, &salt, TEST_ITERATIONS, &sample()).unwrap();
+        let b = seal(&key, &salt, TEST_ITERATIONS, &sample()).unwrap();
+        assert_ne!(a.nonce, b.nonce);
+        assert_ne!(a.ciphertext, b.ciphertext);
+    }
+
+    #[test]
+    fn sealing_blanks_values_but_keeps_secret_placeholders() {
+        let mut environment = serde_json::json!({
+            "id": "e1",
+            "variables": { "baseUrl": "https://api.test", "token": "" },
+            "secretKeys": ["token"]
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        let payload = seal(
+            &[0u8; KEY_LEN],
+            &[0u8; SALT_LEN],
+            TEST_ITERATIONS,
+            &sample(),
+        )
+        .unwrap();
+        store_sealed(&mut environment, &payload, &sample()).unwrap();
+        assert_eq!(
+            environment["variables"],
+            serde_json::json!({ "token": "", "baseUrl": "", "apiKey": "" })
+        );
+        assert!(environment.contains_key("encrypted"));
+    }
+}
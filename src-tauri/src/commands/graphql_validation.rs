@@ -0,0 +1,1050 @@
+//! Validation of GraphQL documents and variables against the schema cached by
+//! GraphQL mode (the introspection results the frontend keeps per URL), so
+//! typos in field names, arguments, enum values or variables are reported
+//! before the request is sent.
+
+use graphql_parser::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, Type, TypeCondition, VariableDefinition,
+};
+use graphql_parser::Pos;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::workspaces::store_file;
+
+/// Store key of the introspection results, keyed by endpoint URL.
+const SCHEMA_CACHE_KEY: &str = "graphqlSchemaCache";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLValidationError {
+    pub message: String,
+    /// Position in the query document (1-based), for document errors
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Path into the variables, e.g. `$input.items[0].name`, for variable errors
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLValidation {
+    /// Whether a cached schema was found; without one only syntax is checked
+    pub schema_found: bool,
+    pub errors: Vec<GraphQLValidationError>,
+}
+
+/// A type reference as found in introspection results.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: String,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    fn named(name: &str) -> Self {
+        TypeRef {
+            kind: String::new(),
+            name: Some(name.to_string()),
+            of_type: None,
+        }
+    }
+
+    fn wrap(kind: &str, inner: TypeRef) -> Self {
+        TypeRef {
+            kind: kind.to_string(),
+            name: None,
+            of_type: Some(Box::new(inner)),
+        }
+    }
+
+    fn from_ast(ty: &Type<'_, String>) -> Self {
+        match ty {
+            Type::NamedType(name) => TypeRef::named(name),
+            Type::ListType(inner) => TypeRef::wrap("LIST", TypeRef::from_ast(inner)),
+            Type::NonNullType(inner) => TypeRef::wrap("NON_NULL", TypeRef::from_ast(inner)),
+        }
+    }
+
+    fn is_non_null(&self) -> bool {
+        self.kind == "NON_NULL"
+    }
+
+    /// Name of the innermost named type.
+    fn base_name(&self) -> &str {
+        match &self.of_type {
+            Some(inner) => inner.base_name(),
+            None => self.name.as_deref().unwrap_or(""),
+        }
+    }
+}
+
+impl std::fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.kind.as_str(), &self.of_type) {
+            ("NON_NULL", Some(inner)) => write!(f, "{}!", inner),
+            ("LIST", Some(inner)) => write!(f, "[{}]", inner),
+            _ => f.write_str(self.name.as_deref().unwrap_or("")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValue {
+    name: String,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    default_value: Option<String>,
+}
+
+impl InputValue {
+    fn is_required(&self) -> bool {
+        self.ty.is_non_null() && self.default_value.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FieldDef {
+    name: String,
+    #[serde(default)]
+    args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaType {
+    kind: String,
+    name: String,
+    fields: Option<Vec<FieldDef>>,
+    input_fields: Option<Vec<InputValue>>,
+    enum_values: Option<Vec<NamedRef>>,
+}
+
+impl SchemaType {
+    fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields
+            .iter()
+            .flatten()
+            .find(|field| field.name == name)
+    }
+
+    fn is_composite(&self) -> bool {
+        matches!(self.kind.as_str(), "OBJECT" | "INTERFACE" | "UNION")
+    }
+
+    fn is_input(&self) -> bool {
+        matches!(self.kind.as_str(), "SCALAR" | "ENUM" | "INPUT_OBJECT")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    query_type: Option<NamedRef>,
+    mutation_type: Option<NamedRef>,
+    subscription_type: Option<NamedRef>,
+    types: Vec<SchemaType>,
+}
+
+struct Schema {
+    query: Option<String>,
+    mutation: Option<String>,
+    subscription: Option<String>,
+    types: HashMap<String, SchemaType>,
+}
+
+impl Schema {
+    /// Build a schema from the `data` of an introspection query.
+    fn from_introspection(introspection: &Value) -> Result<Self, String> {
+        let schema = introspection
+            .get("__schema")
+            .ok_or_else(|| "Cached introspection result has no __schema".to_string())?;
+        let schema: IntrospectionSchema = serde_json::from_value(schema.clone())
+            .map_err(|e| format!("Invalid cached GraphQL schema: {}", e))?;
+        Ok(Schema {
+            query: schema.query_type.map(|t| t.name),
+            mutation: schema.mutation_type.map(|t| t.name),
+            subscription: schema.subscription_type.map(|t| t.name),
+            types: schema
+                .types
+                .into_iter()
+                .map(|ty| (ty.name.clone(), ty))
+                .collect(),
+        })
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(!ca.eq_ignore_ascii_case(cb));
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// " Did you mean "x"?" for the closest candidate, or "" when none is close.
+fn did_you_mean<'c>(name: &str, candidates: impl Iterator<Item = &'c str>) -> String {
+    let threshold = name.len() * 2 / 5 + 1;
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!(" Did you mean \"{}\"?", candidate))
+        .unwrap_or_default()
+}
+
+fn fits_int(n: Option<i64>) -> bool {
+    n.is_some_and(|n| i32::try_from(n).is_ok())
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a FragmentDefinition<'a, String>>,
+    errors: Vec<GraphQLValidationError>,
+    /// Variables of the operation being validated
+    defined_variables: HashSet<String>,
+    used_variables: HashSet<String>,
+    used_fragments: HashSet<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn error_at(&mut self, pos: Pos, message: String) {
+        let error = GraphQLValidationError {
+            message,
+            line: Some(pos.line),
+            column: Some(pos.column),
+            path: None,
+        };
+        // Fragments are checked at every spread; report each problem once
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    fn variable_error(&mut self, path: &str, message: String) {
+        self.errors.push(GraphQLValidationError {
+            message,
+            line: None,
+            column: None,
+            path: Some(path.to_string()),
+        });
+    }
+
+    fn operation(&mut self, operation: &'a OperationDefinition<'a, String>) {
+        let (pos, kind, name, variables, directives, selection_set) = match operation {
+            OperationDefinition::SelectionSet(set) => {
+                (set.span.0, "query", None, &[][..], &[][..], set)
+            }
+            OperationDefinition::Query(q) => (
+                q.position,
+                "query",
+                q.name.as_deref(),
+                &q.variable_definitions[..],
+                &q.directives[..],
+                &q.selection_set,
+            ),
+            OperationDefinition::Mutation(m) => (
+                m.position,
+                "mutation",
+                m.name.as_deref(),
+                &m.variable_definitions[..],
+                &m.directives[..],
+                &m.selection_set,
+            ),
+            OperationDefinition::Subscription(s) => (
+                s.position,
+                "subscription",
+                s.name.as_deref(),
+                &s.variable_definitions[..],
+                &s.directives[..],
+                &s.selection_set,
+            ),
+        };
+        let root = match kind {
+            "mutation" => self.schema.mutation.as_deref(),
+            "subscription" => self.schema.subscription.as_deref(),
+            _ => self.schema.query.as_deref(),
+        };
+        let Some(root) = root else {
+            self.error_at(pos, format!("Schema does not support {} operations", kind));
+            return;
+        };
+
+        self.defined_variables.clear();
+        self.used_variables.clear();
+        for definition in variables {
+            self.variable_definition(definition);
+        }
+        self.directives(directives);
+        self.selection_set(root, selection_set, &mut Vec::new());
+
+        let mut unused: Vec<_> = variables
+            .iter()
+            .filter(|definition| !self.used_variables.contains(&definition.name))
+            .collect();
+        unused.sort_by_key(|definition| (definition.position.line, definition.position.column));
+        for definition in unused {
+            let message = match name {
+                Some(name) => format!(
+                    "Variable \"${}\" is never used in operation \"{}\"",
+                    definition.name, name
+                ),
+                None => format!("Variable \"${}\" is never used", definition.name),
+            };
+            self.error_at(definition.position, message);
+        }
+    }
+
+    fn variable_definition(&mut self, definition: &VariableDefinition<'_, String>) {
+        let ty = TypeRef::from_ast(&definition.var_type);
+        match self.schema.types.get(ty.base_name()) {
+            None => {
+                let suggestion = did_you_mean(
+                    ty.base_name(),
+                    self.schema
+                        .types
+                        .values()
+                        .filter(|t| t.is_input())
+                        .map(|t| t.name.as_str()),
+                );
+                self.error_at(
+                    definition.position,
+                    format!("Unknown type \"{}\".{}", ty.base_name(), suggestion),
+                );
+            }
+            Some(named) if !named.is_input() => self.error_at(
+                definition.position,
+                format!(
+                    "Variable \"${}\" cannot be non-input type \"{}\"",
+                    definition.name, ty
+                ),
+            ),
+            Some(_) => {
+                if let Some(default) = &definition.default_value {
+                    self.value(definition.position, default, &ty);
+                }
+            }
+        }
+        self.defined_variables.insert(definition.name.clone());
+    }
+
+    fn directives(&mut self, directives: &[Directive<'_, String>]) {
+        let boolean = TypeRef::wrap("NON_NULL", TypeRef::named("Boolean"));
+        for directive in directives {
+            for (name, value) in &directive.arguments {
+                if matches!(directive.name.as_str(), "include" | "skip") && name == "if" {
+                    self.value(directive.position, value, &boolean);
+                } else {
+                    self.record_variables(directive.position, value);
+                }
+            }
+        }
+    }
+
+    /// Note the variables used in a value whose type isn't known.
+    fn record_variables(&mut self, pos: Pos, value: &graphql_parser::query::Value<'_, String>) {
+        use graphql_parser::query::Value as Literal;
+        match value {
+            Literal::Variable(name) => self.use_variable(pos, name),
+            Literal::List(items) => items.iter().for_each(|v| self.record_variables(pos, v)),
+            Literal::Object(fields) => fields.values().for_each(|v| self.record_variables(pos, v)),
+            _ => {}
+        }
+    }
+
+    fn use_variable(&mut self, pos: Pos, name: &str) {
+        self.used_variables.insert(name.to_string());
+        if !self.defined_variables.contains(name) {
+            self.error_at(pos, format!("Variable \"${}\" is not defined", name));
+        }
+    }
+
+    fn selection_set(
+        &mut self,
+        parent: &str,
+        set: &'a SelectionSet<'a, String>,
+        spread_stack: &mut Vec<&'a str>,
+    ) {
+        let schema = self.schema;
+        let Some(parent_type) = schema.types.get(parent) else {
+            return;
+        };
+        for selection in &set.items {
+            match selection {
+                Selection::Field(field) => {
+                    self.directives(&field.directives);
+                    if field.name == "__typename" {
+                        continue;
+                    }
+                    // Introspection fields aren't listed in the schema's types
+                    if Some(parent) == schema.query.as_deref()
+                        && matches!(field.name.as_str(), "__schema" | "__type")
+                    {
+                        for (_, value) in &field.arguments {
+                            self.record_variables(field.position, value);
+                        }
+                        continue;
+                    }
+                    if parent_type.kind == "UNION" {
+                        self.error_at(
+                            field.position,
+                            format!(
+                                "Cannot query field \"{}\" on union type \"{}\"; use a fragment such as \"... on Type\"",
+                                field.name, parent
+                            ),
+                        );
+                        continue;
+                    }
+                    let Some(definition) = parent_type.field(&field.name) else {
+                        let suggestion = did_you_mean(
+                            &field.name,
+                            parent_type.fields.iter().flatten().map(|f| f.name.as_str()),
+                        );
+                        self.error_at(
+                            field.position,
+                            format!(
+                                "Cannot query field \"{}\" on type \"{}\".{}",
+                                field.name, parent, suggestion
+                            ),
+                        );
+                        continue;
+                    };
+
+                    for (name, value) in &field.arguments {
+                        match definition.args.iter().find(|arg| arg.name == *name) {
+                            Some(arg) => self.value(field.position, value, &arg.ty),
+                            None => {
+                                let suggestion = did_you_mean(
+                                    name,
+                                    definition.args.iter().map(|arg| arg.name.as_str()),
+                                );
+                                self.error_at(
+                                    field.position,
+                                    format!(
+                                        "Unknown argument \"{}\" on field \"{}.{}\".{}",
+                                        name, parent, field.name, suggestion
+                                    ),
+                                );
+                                self.record_variables(field.position, value);
+                            }
+                        }
+                    }
+                    for arg in definition.args.iter().filter(|arg| arg.is_required()) {
+                        if !field.arguments.iter().any(|(name, _)| *name == arg.name) {
+                            self.error_at(
+                                field.position,
+                                format!(
+                                    "Field \"{}.{}\" argument \"{}\" of type \"{}\" is required, but it was not provided",
+                                    parent, field.name, arg.name, arg.ty
+                                ),
+                            );
+                        }
+                    }
+
+                    let field_type = definition.ty.base_name();
+                    let composite = schema
+                        .types
+                        .get(field_type)
+                        .is_some_and(SchemaType::is_composite);
+                    match (composite, field.selection_set.items.is_empty()) {
+                        (true, true) => self.error_at(
+                            field.position,
+                            format!(
+                                "Field \"{}\" of type \"{}\" must have a selection of subfields",
+                                field.name, definition.ty
+                            ),
+                        ),
+                        (false, false) => self.error_at(
+                            field.position,
+                            format!(
+                                "Field \"{}\" must not have a selection since type \"{}\" has no subfields",
+                                field.name, definition.ty
+                            ),
+                        ),
+                        (true, false) => {
+                            self.selection_set(field_type, &field.selection_set, spread_stack)
+                        }
+                        (false, true) => {}
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives);
+                    let name = spread.fragment_name.as_str();
+                    self.used_fragments.insert(name.to_string());
+                    let Some(fragment) = self.fragments.get(name).copied() else {
+                        let suggestion = did_you_mean(name, self.fragments.keys().copied());
+                        self.error_at(
+                            spread.position,
+                            format!("Unknown fragment \"{}\".{}", name, suggestion),
+                        );
+                        continue;
+                    };
+                    if spread_stack.contains(&name) {
+                        self.error_at(
+                            spread.position,
+                            format!("Cannot spread fragment \"{}\" within itself", name),
+                        );
+                        continue;
+                    }
+                    let TypeCondition::On(on) = &fragment.type_condition;
+                    spread_stack.push(name);
+                    self.selection_set(on, &fragment.selection_set, spread_stack);
+                    spread_stack.pop();
+                }
+                Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    let on = match &inline.type_condition {
+                        Some(TypeCondition::On(on)) => on.as_str(),
+                        None => parent,
+                    };
+                    if self.type_condition(inline.position, on) {
+                        self.selection_set(on, &inline.selection_set, spread_stack);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check a fragment type condition; returns whether the type can be selected on.
+    fn type_condition(&mut self, pos: Pos, on: &str) -> bool {
+        match self.schema.types.get(on) {
+            Some(ty) if ty.is_composite() => true,
+            Some(_) => {
+                self.error_at(
+                    pos,
+                    format!("Fragment cannot condition on non composite type \"{}\"", on),
+                );
+                false
+            }
+            None => {
+                let suggestion = did_you_mean(
+                    on,
+                    self.schema
+                        .types
+                        .values()
+                        .filter(|t| t.is_composite())
+                        .map(|t| t.name.as_str()),
+                );
+                self.error_at(pos, format!("Unknown type \"{}\".{}", on, suggestion));
+                false
+            }
+        }
+    }
+
+    /// Check a literal argument value against its type.
+    fn value(&mut self, pos: Pos, value: &graphql_parser::query::Value<'_, String>, ty: &TypeRef) {
+        use graphql_parser::query::Value as Literal;
+        if let Literal::Variable(name) = value {
+            self.use_variable(pos, name);
+            return;
+        }
+        match (ty.kind.as_str(), &ty.of_type) {
+            ("NON_NULL", Some(inner)) => {
+                if matches!(value, Literal::Null) {
+                    self.error_at(
+                        pos,
+                        format!("Expected value of type \"{}\", found null", ty),
+                    );
+                } else {
+                    self.value(pos, value, inner);
+                }
+                return;
+            }
+            ("LIST", Some(inner)) => {
+                match value {
+                    Literal::List(items) => {
+                        items.iter().for_each(|item| self.value(pos, item, inner))
+                    }
+                    Literal::Null => {}
+                    single => self.value(pos, single, inner),
+                }
+                return;
+            }
+            _ => {}
+        }
+        if matches!(value, Literal::Null) {
+            return;
+        }
+        let schema = self.schema;
+        let Some(named) = schema.types.get(ty.base_name()) else {
+            return;
+        };
+        let valid = match named.kind.as_str() {
+            "SCALAR" => match named.name.as_str() {
+                "Int" => {
+                    matches!(value, Literal::Int(n) if fits_int(n.as_i64()))
+                }
+                "Float" => matches!(value, Literal::Int(_) | Literal::Float(_)),
+                "String" => matches!(value, Literal::String(_)),
+                "Boolean" => matches!(value, Literal::Boolean(_)),
+                "ID" => matches!(value, Literal::String(_) | Literal::Int(_)),
+                _ => true,
+            },
+            "ENUM" => match value {
+                Literal::Enum(name) => {
+                    let values = named.enum_values.iter().flatten();
+                    if !values.clone().any(|v| v.name == *name) {
+                        let suggestion = did_you_mean(name, values.map(|v| v.name.as_str()));
+                        self.error_at(
+                            pos,
+                            format!(
+                                "Value \"{}\" does not exist in \"{}\" enum.{}",
+                                name, named.name, suggestion
+                            ),
+                        );
+                    }
+                    true
+                }
+                _ => false,
+            },
+            "INPUT_OBJECT" => match value {
+                Literal::Object(fields) => {
+                    let input_fields = named.input_fields.iter().flatten();
+                    for (name, field_value) in fields {
+                        match input_fields.clone().find(|f| f.name == *name) {
+                            Some(field) => self.value(pos, field_value, &field.ty),
+                            None => {
+                                let suggestion = did_you_mean(
+                                    name,
+                                    input_fields.clone().map(|f| f.name.as_str()),
+                                );
+                                self.error_at(
+                                    pos,
+                                    format!(
+                                        "Field \"{}\" is not defined by type \"{}\".{}",
+                                        name, named.name, suggestion
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    for field in input_fields.filter(|f| f.is_required()) {
+                        if !fields.contains_key(&field.name) {
+                            self.error_at(
+                                pos,
+                                format!(
+                                    "Field \"{}.{}\" of required type \"{}\" was not provided",
+                                    named.name, field.name, field.ty
+                                ),
+                            );
+                        }
+                    }
+                    true
+                }
+                _ => false,
+            },
+            _ => true,
+        };
+        if !valid {
+            self.error_at(
+                pos,
+                format!("{} cannot represent value: {}", named.name, value),
+            );
+        }
+    }
+
+    /// Check a variable value from the variables JSON against its declared type.
+    fn json_value(&mut self, path: &str, value: &Value, ty: &TypeRef) {
+        match (ty.kind.as_str(), &ty.of_type) {
+            ("NON_NULL", Some(inner)) => {
+                if value.is_null() {
+                    self.variable_error(
+                        path,
+                        format!("Expected non-nullable type \"{}\" not to be null", ty),
+                    );
+                } else {
+                    self.json_value(path, value, inner);
+                }
+                return;
+            }
+            ("LIST", Some(inner)) => {
+                match value {
+                    Value::Array(items) => {
+                        for (index, item) in items.iter().enumerate() {
+                            self.json_value(&format!("{}[{}]", path, index), item, inner);
+                        }
+                    }
+                    Value::Null => {}
+                    single => self.json_value(path, single, inner),
+                }
+                return;
+            }
+            _ => {}
+        }
+        if value.is_null() {
+            return;
+        }
+        let schema = self.schema;
+        let Some(named) = schema.types.get(ty.base_name()) else {
+            return;
+        };
+        let valid = match named.kind.as_str() {
+            "SCALAR" => match named.name.as_str() {
+                "Int" => fits_int(value.as_i64()),
+                "Float" => value.is_number(),
+                "String" => value.is_string(),
+                "Boolean" => value.is_boolean(),
+                "ID" => value.is_string() || value.is_i64() || value.is_u64(),
+                _ => true,
+            },
+            "ENUM" => match value.as_str() {
+                Some(name) => {
+                    let values = named.enum_values.iter().flatten();
+                    if !values.clone().any(|v| v.name == name) {
+                        let suggestion = did_you_mean(name, values.map(|v| v.name.as_str()));
+                        self.variable_error(
+                            path,
+                            format!(
+                                "Value \"{}\" does not exist in \"{}\" enum.{}",
+                                name, named.name, suggestion
+                            ),
+                        );
+                    }
+                    true
+                }
+                None => false,
+            },
+            "INPUT_OBJECT" => match value.as_object() {
+                Some(fields) => {
+                    let input_fields = named.input_fields.iter().flatten();
+                    for (name, field_value) in fields {
+                        let field_path = format!("{}.{}", path, name);
+                        match input_fields.clone().find(|f| f.name == *name) {
+                            Some(field) => self.json_value(&field_path, field_value, &field.ty),
+                            None => {
+                                let suggestion = did_you_mean(
+                                    name,
+                                    input_fields.clone().map(|f| f.name.as_str()),
+                                );
+                                self.variable_error(
+                                    &field_path,
+                                    format!(
+                                        "Field \"{}\" is not defined by type \"{}\".{}",
+                                        name, named.name, suggestion
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    for field in input_fields.filter(|f| f.is_required()) {
+                        if !fields.contains_key(&field.name) {
+                            self.variable_error(
+                                &format!("{}.{}", path, field.name),
+                                format!(
+                                    "Field \"{}\" of required type \"{}\" was not provided",
+                                    field.name, field.ty
+                                ),
+                            );
+                        }
+                    }
+                    true
+                }
+                None => false,
+            },
+            _ => true,
+        };
+        if !valid {
+            self.variable_error(
+                path,
+                format!("{} cannot represent value: {}", named.name, value),
+            );
+        }
+    }
+}
+
+/// Position of a syntax error, parsed from the parser's message.
+fn parse_error_position(message: &str) -> (Option<usize>, Option<usize>) {
+    let position = regex::Regex::new(r"(\d+):(\d+)")
+        .ok()
+        .and_then(|re| re.captures(message))
+        .map(|caps| (caps[1].parse().ok(), caps[2].parse().ok()));
+    position.unwrap_or((None, None))
+}
+
+fn operation_name<'d>(operation: &'d OperationDefinition<'_, String>) -> Option<&'d str> {
+    match operation {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(q) => q.name.as_deref(),
+        OperationDefinition::Mutation(m) => m.name.as_deref(),
+        OperationDefinition::Subscription(s) => s.name.as_deref(),
+    }
+}
+
+fn operation_variables<'d, 'q>(
+    operation: &'d OperationDefinition<'q, String>,
+) -> &'d [VariableDefinition<'q, String>] {
+    match operation {
+        OperationDefinition::SelectionSet(_) => &[],
+        OperationDefinition::Query(q) => &q.variable_definitions,
+        OperationDefinition::Mutation(m) => &m.variable_definitions,
+        OperationDefinition::Subscription(s) => &s.variable_definitions,
+    }
+}
+
+fn validate_document<'a>(
+    schema: &'a Schema,
+    document: &'a Document<'a, String>,
+    variables: &Value,
+    selected: Option<&str>,
+) -> Vec<GraphQLValidationError> {
+    let mut operations = Vec::new();
+    let mut fragments = HashMap::new();
+    for definition in &document.definitions {
+        match definition {
+            Definition::Operation(operation) => operations.push(operation),
+            Definition::Fragment(fragment) => {
+                fragments.insert(fragment.name.as_str(), fragment);
+            }
+        }
+    }
+
+    let mut validator = Validator {
+        schema,
+        fragments,
+        errors: Vec::new(),
+        defined_variables: HashSet::new(),
+        used_variables: HashSet::new(),
+        used_fragments: HashSet::new(),
+    };
+
+    let fragment_list: Vec<_> = validator.fragments.values().copied().collect();
+    for fragment in &fragment_list {
+        let TypeCondition::On(on) = &fragment.type_condition;
+        validator.type_condition(fragment.position, on);
+    }
+    for operation in &operations {
+        validator.operation(operation);
+    }
+    let mut unused: Vec<_> = fragment_list
+        .into_iter()
+        .filter(|fragment| !validator.used_fragments.contains(&fragment.name))
+        .collect();
+    unused.sort_by_key(|fragment| (fragment.position.line, fragment.position.column));
+    for fragment in unused {
+        validator.error_at(
+            fragment.position,
+            format!("Fragment \"{}\" is never used", fragment.name),
+        );
+    }
+
+    // Variables only apply to the operation that will run
+    let operation = match selected {
+        Some(name) => {
+            let found = operations
+                .iter()
+                .find(|operation| operation_name(operation) == Some(name));
+            if found.is_none() {
+                validator.variable_error(
+                    "operationName",
+                    format!("Unknown operation named \"{}\"", name),
+                );
+            }
+            found
+        }
+        None if operations.len() > 1 => {
+            validator.variable_error(
+                "operationName",
+                "Must provide operation name if query contains multiple operations".to_string(),
+            );
+            None
+        }
+        None => operations.first(),
+    };
+    if let Some(operation) = operation {
+        for definition in operation_variables(operation) {
+            let ty = TypeRef::from_ast(&definition.var_type);
+            let path = format!("${}", definition.name);
+            match variables.get(&definition.name) {
+                Some(value) => validator.json_value(&path, value, &ty),
+                None if ty.is_non_null() && definition.default_value.is_none() => validator
+                    .variable_error(
+                        &path,
+                        format!(
+                            "Variable \"{}\" of required type \"{}\" was not provided",
+                            path, ty
+                        ),
+                    ),
+                None => {}
+            }
+        }
+    }
+    validator.errors
+}
+
+/// Validate `query` and `variables` against an introspection result.
+fn validate(
+    introspection: Option<&Value>,
+    query: &str,
+    variables: &Value,
+    operation_name: Option<&str>,
+) -> Result<GraphQLValidation, String> {
+    let document = match graphql_parser::parse_query::<String>(query) {
+        Ok(document) => document,
+        Err(e) => {
+            let message = e.to_string();
+            let (line, column) = parse_error_position(&message);
+            return Ok(GraphQLValidation {
+                schema_found: introspection.is_some(),
+                errors: vec![GraphQLValidationError {
+                    message: format!("Syntax error: {}", message.trim()),
+                    line,
+                    column,
+                    path: None,
+                }],
+            });
+        }
+    };
+    let Some(introspection) = introspection else {
+        return Ok(GraphQLValidation {
+            schema_found: false,
+            errors: Vec::new(),
+        });
+    };
+    let schema = Schema::from_introspection(introspection)?;
+    let errors = validate_document(&schema, &document, variables, operation_name);
+    Ok(GraphQLValidation {
+        schema_found: true,
+        errors,
+    })
+}
+
+/// Validate a query document and its variables against the schema cached for
+/// `url` by GraphQL mode. Only syntax is checked when no schema is cached.
+#[tauri::command]
+pub fn graphql_validate(
+    app: AppHandle,
+    url: String,
+    query: String,
+    variables: Option<Value>,
+    operation_name: Option<String>,
+) -> Result<GraphQLValidation, String> {
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+    let cache = store.get(SCHEMA_CACHE_KEY);
+    let introspection = cache.as_ref().and_then(|cache| cache.get(url.trim()));
+    validate(
+        introspection,
+        &query,
+        &variables.unwrap_or_else(|| Value::Object(Default::default())),
+        operation_name.as_deref(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn named(kind: &str, name: &str) -> Value {
+        json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn non_null(inner: Value) -> Value {
+        json!({ "kind": "NON_NULL", "name": null, "ofType": inner })
+    }
+
+    fn introspection() -> Value {
+        json!({ "__schema": {
+            "queryType": { "name": "Query" },
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [
+                { "kind": "OBJECT", "name": "Query", "fields": [
+                    { "name": "user", "args": [
+                        { "name": "id", "type": non_null(named("SCALAR", "ID")), "defaultValue": null }
+                    ], "type": named("OBJECT", "User") },
+                    { "name": "users", "args": [
+                        { "name": "filter", "type": named("INPUT_OBJECT", "UserFilter"), "defaultValue": null }
+                    ], "type": { "kind": "LIST", "name": null, "ofType": named("OBJECT", "User") } }
+                ] },
+                { "kind": "OBJECT", "name": "User", "fields": [
+                    { "name": "id", "args": [], "type": non_null(named("SCALAR", "ID")) },
+                    { "name": "name", "args": [], "type": named("SCALAR", "String") },
+                    { "name": "role", "args": [], "type": named("ENUM", "Role") }
+                ] },
+                { "kind": "INPUT_OBJECT", "name": "UserFilter", "inputFields": [
+                    { "name": "role", "type": non_null(named("ENUM", "Role")), "defaultValue": null },
+                    { "name": "limit", "type": named("SCALAR", "Int"), "defaultValue": "10" }
+                ] },
+                { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }, { "name": "MEMBER" }] },
+                { "kind": "SCALAR", "name": "ID" },
+                { "kind": "SCALAR", "name": "String" },
+                { "kind": "SCALAR", "name": "Int" },
+                { "kind": "SCALAR", "name": "Boolean" }
+            ]
+        } })
+    }
+
+    fn messages(query: &str, variables: Value) -> Vec<String> {
+        let schema = introspection();
+        validate(Some(&schema), query, &variables, None)
+            .unwrap()
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[test]
+    fn reports_unknown_fields_arguments_and_bad_variables() {
+        assert!(messages(
+            "query Q($f: UserFilter) { users(filter: $f) { id name role } user(id: 1) { ...U } } fragment U on User { name }",
+            json!({ "f": { "role": "ADMIN", "limit": 5 } })
+        )
+        .is_empty());
+
+        let errors = messages(
+            "query Q($id: ID!, $extra: Int) { user(id: $id) { nmae } users(filter: { role: OWNER }) }",
+            json!({}),
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Cannot query field \"nmae\" on type \"User\". Did you mean \"name\"?",
+                "Value \"OWNER\" does not exist in \"Role\" enum.",
+                "Field \"users\" of type \"[User]\" must have a selection of subfields",
+                "Variable \"$extra\" is never used in operation \"Q\"",
+                "Variable \"$id\" of required type \"ID!\" was not provided",
+            ]
+        );
+
+        let errors = messages(
+            "query($f: UserFilter) { users(filter: $f) { id } user { name } }",
+            json!({ "f": { "role": "admin", "limt": "x" } }),
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Field \"Query.user\" argument \"id\" of type \"ID!\" is required, but it was not provided",
+                "Value \"admin\" does not exist in \"Role\" enum. Did you mean \"ADMIN\"?",
+                "Field \"limt\" is not defined by type \"UserFilter\". Did you mean \"limit\"?",
+            ]
+        );
+
+        let syntax = validate(None, "{ user(id: 1) { name }", &json!({}), None).unwrap();
+        assert!(!syntax.schema_found);
+        assert_eq!(syntax.errors.len(), 1);
+        assert!(syntax.errors[0].line.is_some());
+    }
+}
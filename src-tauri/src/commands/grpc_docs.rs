@@ -0,0 +1,292 @@
+//! gRPC API documentation captured from server reflection: every service and
+//! method with its comments and input/output skeletons, stored next to the
+//! collection file as [`GRPC_API_FILE`] so the API surface stays browsable
+//! after the server is gone.
+
+use prost_reflect::DescriptorPool;
+use prost_types::FileDescriptorProto;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use super::collections::resolve_collection_dir;
+use super::grpc_reflection::{
+    collect_file_descriptors, create_channel, generate_message_skeleton, grpc_route_for,
+    normalize_target_with_tls, resolve_target_variables, strip_leading_dot, GrpcTlsOptions,
+    ReflectionClient,
+};
+
+const GRPC_API_FILE: &str = "grpc-api.json";
+
+/// Reflection's own services are left out of the documentation.
+const REFLECTION_PACKAGE_PREFIX: &str = "grpc.reflection.";
+
+/// Field numbers of `FileDescriptorProto.service` and
+/// `ServiceDescriptorProto.method`, as used in source code info paths.
+const FILE_SERVICE_FIELD: i32 = 6;
+const SERVICE_METHOD_FIELD: i32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcMethodDoc {
+    pub name: String,
+    /// `/package.Service/Method`
+    pub full_method: String,
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub input_skeleton: Value,
+    pub output_skeleton: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcServiceDoc {
+    /// Fully qualified service name
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub methods: Vec<GrpcMethodDoc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcApiDocs {
+    /// Server the documentation was captured from
+    pub target: String,
+    /// RFC 3339 timestamp of the capture
+    pub captured_at: String,
+    pub services: Vec<GrpcServiceDoc>,
+}
+
+fn docs_path(app: &AppHandle, collection_id: &str) -> Result<PathBuf, String> {
+    let dir = resolve_collection_dir(app, collection_id)?
+        .ok_or_else(|| format!("Collection {} not found", collection_id))?;
+    Ok(dir.join(GRPC_API_FILE))
+}
+
+/// Comments of a file's elements keyed by source code info path. Servers that
+/// strip source info simply yield none.
+fn comments_by_path(file: &FileDescriptorProto) -> HashMap<Vec<i32>, String> {
+    file.source_code_info
+        .iter()
+        .flat_map(|info| &info.location)
+        .filter_map(|location| {
+            let comment = location
+                .leading_comments
+                .as_deref()
+                .or(location.trailing_comments.as_deref())?
+                .trim();
+            (!comment.is_empty()).then(|| (location.path.clone(), comment.to_string()))
+        })
+        .collect()
+}
+
+fn message_skeleton(pool: &DescriptorPool, type_name: &str) -> Value {
+    pool.get_message_by_name(&strip_leading_dot(type_name))
+        .map(|desc| generate_message_skeleton(&desc))
+        .unwrap_or(Value::Null)
+}
+
+/// Document the services defined in `files`, sorted by name. `pool` holds
+/// the same files and resolves the message types.
+fn document_services(files: &[FileDescriptorProto], pool: &DescriptorPool) -> Vec<GrpcServiceDoc> {
+    let mut services = Vec::new();
+    for file in files {
+        let comments = comments_by_path(file);
+        let package = file.package();
+        for (service_index, service) in file.service.iter().enumerate() {
+            let name = if package.is_empty() {
+                service.name().to_string()
+            } else {
+                format!("{}.{}", package, service.name())
+            };
+            if name.starts_with(REFLECTION_PACKAGE_PREFIX) {
+                continue;
+            }
+
+            let service_path = vec![FILE_SERVICE_FIELD, service_index as i32];
+            let methods = service
+                .method
+                .iter()
+                .enumerate()
+                .map(|(method_index, method)| {
+                    let mut method_path = service_path.clone();
+                    method_path.extend([SERVICE_METHOD_FIELD, method_index as i32]);
+                    GrpcMethodDoc {
+                        name: method.name().to_string(),
+                        full_method: format!("/{}/{}", name, method.name()),
+                        input_type: strip_leading_dot(method.input_type()),
+                        output_type: strip_leading_dot(method.output_type()),
+                        client_streaming: method.client_streaming(),
+                        server_streaming: method.server_streaming(),
+                        comment: comments.get(&method_path).cloned(),
+                        input_skeleton: message_skeleton(pool, method.input_type()),
+                        output_skeleton: message_skeleton(pool, method.output_type()),
+                    }
+                })
+                .collect();
+
+            services.push(GrpcServiceDoc {
+                comment: comments.get(&service_path).cloned(),
+                name,
+                methods,
+            });
+        }
+    }
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    services
+}
+
+/// Capture the API of a server with reflection enabled and store it with the
+/// collection, replacing any earlier capture.
+#[tauri::command]
+pub async fn grpc_docs_capture(
+    app: AppHandle,
+    collection_id: String,
+    target: String,
+    tls: Option<GrpcTlsOptions>,
+) -> Result<GrpcApiDocs, String> {
+    let path = docs_path(&app, &collection_id)?;
+    let tls = tls.unwrap_or_default();
+    let target = resolve_target_variables(&app, Some(&collection_id), &target).await?;
+    let target = normalize_target_with_tls(&target, tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
+
+    let channel = create_channel(&target, &tls, &route).await?;
+    let mut client = ReflectionClient::new(channel);
+    let services = client.list_services().await?;
+    let symbols: Vec<&str> = services
+        .iter()
+        .map(String::as_str)
+        .filter(|service| !service.starts_with(REFLECTION_PACKAGE_PREFIX))
+        .collect();
+    if symbols.is_empty() {
+        return Err("The server lists no services besides reflection".to_string());
+    }
+
+    let files = collect_file_descriptors(&mut client, &symbols).await?;
+    let pool = DescriptorPool::from_file_descriptor_set(prost_types::FileDescriptorSet {
+        file: files.clone(),
+    })
+    .map_err(|e| format!("Failed to build descriptor pool: {}", e))?;
+
+    let docs = GrpcApiDocs {
+        target,
+        captured_at: chrono::Utc::now().to_rfc3339(),
+        services: document_services(&files, &pool),
+    };
+    let content = serde_json::to_string_pretty(&docs)
+        .map_err(|e| format!("Failed to serialize gRPC API docs: {}", e))?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {}: {}", GRPC_API_FILE, e))?;
+    Ok(docs)
+}
+
+/// The stored API documentation of a collection, if one was captured.
+#[tauri::command]
+pub async fn grpc_docs_get(
+    app: AppHandle,
+    collection_id: String,
+) -> Result<Option<GrpcApiDocs>, String> {
+    let path = docs_path(&app, &collection_id)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", GRPC_API_FILE, e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", GRPC_API_FILE, e))
+}
+
+#[tauri::command]
+pub async fn grpc_docs_delete(app: AppHandle, collection_id: String) -> Result<(), String> {
+    let path = docs_path(&app, &collection_id)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove {}: {}", GRPC_API_FILE, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        source_code_info::Location,
+        DescriptorProto, FieldDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto,
+        SourceCodeInfo,
+    };
+
+    fn location(path: Vec<i32>, comment: &str) -> Location {
+        Location {
+            path,
+            leading_comments: Some(comment.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn documents_services_with_comments_and_skeletons() {
+        let file = FileDescriptorProto {
+            name: Some("greeter.proto".to_string()),
+            package: Some("demo".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Hello".to_string()),
+                field: vec![FieldDescriptorProto {
+                    name: Some("name".to_string()),
+                    json_name: Some("name".to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::String as i32),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Greeter".to_string()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("SayHello".to_string()),
+                    input_type: Some(".demo.Hello".to_string()),
+                    output_type: Some(".demo.Hello".to_string()),
+                    server_streaming: Some(true),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            source_code_info: Some(SourceCodeInfo {
+                location: vec![
+                    location(vec![6, 0], " Greets people.\n"),
+                    location(vec![6, 0, 2, 0], " Says hello back.\n"),
+                ],
+            }),
+            ..Default::default()
+        };
+        let files = vec![file];
+        let pool = DescriptorPool::from_file_descriptor_set(prost_types::FileDescriptorSet {
+            file: files.clone(),
+        })
+        .unwrap();
+
+        let services = document_services(&files, &pool);
+        assert_eq!(services.len(), 1);
+        let greeter = &services[0];
+        assert_eq!(greeter.name, "demo.Greeter");
+        assert_eq!(greeter.comment.as_deref(), Some("Greets people."));
+
+        let method = &greeter.methods[0];
+        assert_eq!(method.full_method, "/demo.Greeter/SayHello");
+        assert_eq!(method.input_type, "demo.Hello");
+        assert!(method.server_streaming && !method.client_streaming);
+        assert_eq!(method.comment.as_deref(), Some("Says hello back."));
+        assert_eq!(method.input_skeleton, serde_json::json!({ "name": "" }));
+    }
+}
@@ -0,0 +1,127 @@
+//! Default gRPC metadata of a collection (`grpcMetadata` in the collection
+//! file), the gRPC counterpart of default headers: entries for every method,
+//! one service or one method, whose `{{variable}}` values are resolved from
+//! the collection variables and the active environment. Metadata set on the
+//! request itself wins.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+use super::collections::collection_grpc_metadata;
+use super::variables::{
+    load_active_environment_variables, load_collection_variables, VariableResolver,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrpcMetadataDefaults {
+    /// Sent with every method
+    #[serde(default)]
+    pub all: BTreeMap<String, String>,
+    /// Keyed by `/package.Service` or `/package.Service/Method`; method
+    /// entries win over service entries
+    #[serde(default)]
+    pub methods: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl GrpcMetadataDefaults {
+    /// The entries that apply to `full_method`, with lowercase keys.
+    fn for_method(&self, full_method: &str) -> BTreeMap<String, String> {
+        let full_method = full_method.trim();
+        let service = full_method
+            .rsplit_once('/')
+            .map_or(full_method, |(service, _)| service);
+        let scopes = [
+            Some(&self.all),
+            self.methods.get(service),
+            self.methods.get(full_method),
+        ];
+        let mut entries = BTreeMap::new();
+        for scope in scopes.into_iter().flatten() {
+            for (key, value) in scope {
+                entries.insert(key.trim().to_ascii_lowercase(), value.clone());
+            }
+        }
+        entries.retain(|key, _| !key.is_empty());
+        entries
+    }
+}
+
+/// Add the defaults the request does not set itself.
+fn merge_defaults(
+    metadata: &mut HashMap<String, String>,
+    defaults: BTreeMap<String, String>,
+    resolver: &mut VariableResolver,
+) {
+    for (key, value) in defaults {
+        if metadata
+            .keys()
+            .any(|existing| existing.eq_ignore_ascii_case(&key))
+        {
+            continue;
+        }
+        metadata.insert(key, resolver.resolve(&value));
+    }
+}
+
+/// Merge the collection's default metadata for `full_method` into a
+/// request's metadata.
+pub(crate) async fn apply_metadata_defaults(
+    app: &AppHandle,
+    collection_id: Option<&str>,
+    full_method: &str,
+    metadata: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    let Some(collection_id) = collection_id else {
+        return Ok(());
+    };
+    let Some(defaults) = collection_grpc_metadata(app, collection_id)? else {
+        return Ok(());
+    };
+    let defaults = defaults.for_method(full_method);
+    if defaults.is_empty() {
+        return Ok(());
+    }
+
+    let mut variables = load_collection_variables(app, collection_id).await;
+    variables.extend(load_active_environment_variables(app).await);
+    merge_defaults(metadata, defaults, &mut VariableResolver::new(variables));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_scoped_defaults_under_request_metadata() {
+        let defaults: GrpcMetadataDefaults = serde_json::from_value(serde_json::json!({
+            "all": { "Authorization": "Bearer {{token}}", "x-tenant-id": "{{tenant}}" },
+            "methods": {
+                "/acme.Billing": { "x-team": "billing", "x-tenant-id": "shared" },
+                "/acme.Billing/Refund": { "x-team": "refunds" }
+            }
+        }))
+        .unwrap();
+
+        let refund = defaults.for_method("/acme.Billing/Refund");
+        assert_eq!(refund["authorization"], "Bearer {{token}}");
+        assert_eq!(refund["x-tenant-id"], "shared");
+        assert_eq!(refund["x-team"], "refunds");
+        assert!(!defaults
+            .for_method("/acme.Users/Get")
+            .contains_key("x-team"));
+
+        let mut resolver = VariableResolver::new(HashMap::from([
+            ("token".to_string(), "abc".to_string()),
+            ("tenant".to_string(), "t1".to_string()),
+        ]));
+        let mut metadata = HashMap::from([("X-Team".to_string(), "mine".to_string())]);
+        merge_defaults(&mut metadata, refund, &mut resolver);
+        assert_eq!(metadata["authorization"], "Bearer abc");
+        assert_eq!(metadata["x-tenant-id"], "shared");
+        assert_eq!(metadata["X-Team"], "mine");
+        assert!(!metadata.contains_key("x-team"));
+    }
+}
@@ -8,8 +8,12 @@ use tauri::{AppHandle, State};
 use tauri_plugin_dialog::{DialogExt, FilePath};
 use tokio::sync::oneshot;
 
+use super::api_request::RequestState;
+use super::grpc_metadata::apply_metadata_defaults;
 pub use super::grpc_reflection::GrpcUnaryRequest;
-use super::grpc_reflection::{create_channel, normalize_target_with_tls};
+use super::grpc_reflection::{
+    create_channel, grpc_route_for, normalize_target_with_tls, resolve_target_variables,
+};
 
 /// State to hold loaded proto file descriptors
 pub struct ProtoState {
@@ -77,7 +81,6 @@ pub async fn grpc_parse_proto_file(
         }
     }
 
-    // Use protox to parse the proto file
     let pool = protox_parse::parse_proto_file(&proto_path, &includes)?;
 
     // Extract services from the pool
@@ -154,10 +157,10 @@ pub async fn grpc_proto_get_input_skeleton(
 /// Invoke a gRPC unary call using a loaded proto file for type information
 #[tauri::command]
 pub async fn grpc_proto_invoke_unary(
-    _app: AppHandle,
+    app: AppHandle,
     state: State<'_, ProtoState>,
     proto_path: String,
-    request: GrpcUnaryRequest,
+    mut request: GrpcUnaryRequest,
 ) -> Result<Value, String> {
     use http::uri::PathAndQuery;
     use tonic::metadata::{MetadataKey, MetadataValue};
@@ -170,8 +173,17 @@ pub async fn grpc_proto_invoke_unary(
             .cloned()
             .ok_or_else(|| format!("Proto file not loaded: {}", proto_path))?
     };
-
-    let target = normalize_target_with_tls(&request.target, request.tls.use_tls);
+    apply_metadata_defaults(
+        &app,
+        request.collection_id.as_deref(),
+        &request.full_method,
+        &mut request.metadata,
+    )
+    .await?;
+
+    let target =
+        resolve_target_variables(&app, request.collection_id.as_deref(), &request.target).await?;
+    let target = normalize_target_with_tls(&target, request.tls.use_tls);
     let (input_type, output_type) = resolve_method_types_from_pool(&pool, &request.full_method)?;
 
     let input_desc = pool
@@ -183,7 +195,8 @@ pub async fn grpc_proto_invoke_unary(
 
     let input_msg = json_to_dynamic_message(&request.request_json, input_desc.clone())?;
 
-    let channel = create_channel(&target, &request.tls).await?;
+    let route = grpc_route_for(&app, &target)?;
+    let channel = create_channel(&target, &request.tls, &route).await?;
 
     let mut grpc = tonic::client::Grpc::new(channel);
     grpc.ready()
@@ -269,6 +282,58 @@ pub async fn grpc_unload_proto(
     Ok(())
 }
 
+/// Message types of all loaded proto files, for decoding protobuf HTTP bodies
+#[tauri::command]
+pub async fn grpc_proto_list_messages(state: State<'_, ProtoState>) -> Result<Vec<String>, String> {
+    let pools = state.pools.lock().map_err(|e| e.to_string())?;
+    let mut names: Vec<String> = pools
+        .values()
+        .flat_map(|pool| pool.all_messages())
+        .filter(|message| !message.is_map_entry())
+        .map(|message| message.full_name().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Decode the body of a tab's last HTTP response (kept under `response_key`)
+/// as protobuf message `message_type` from the loaded proto files
+#[tauri::command]
+pub async fn grpc_proto_decode_response(
+    state: State<'_, ProtoState>,
+    request_state: State<'_, RequestState>,
+    response_key: String,
+    message_type: String,
+) -> Result<Value, String> {
+    let response = request_state
+        .kept_responses
+        .get(&response_key)
+        .ok_or_else(|| {
+            "This response is no longer available; send the request again".to_string()
+        })?;
+    let message_type = strip_leading_dot(message_type.trim());
+    let desc = {
+        let pools = state.pools.lock().map_err(|e| e.to_string())?;
+        pools
+            .values()
+            .find_map(|pool| pool.get_message_by_name(&message_type))
+            .ok_or_else(|| {
+                format!(
+                    "Message type not found in the loaded protos: {}",
+                    message_type
+                )
+            })?
+    };
+    decode_message(desc, &response.body)
+}
+
+fn decode_message(desc: prost_reflect::MessageDescriptor, bytes: &[u8]) -> Result<Value, String> {
+    let message = DynamicMessage::decode(desc.clone(), bytes)
+        .map_err(|e| format!("The body is not a valid {}: {}", desc.full_name(), e))?;
+    dynamic_message_to_json(&message)
+}
+
 /// Open a file dialog to select a proto file
 #[tauri::command]
 pub async fn grpc_select_proto_file(app: AppHandle) -> Result<Option<String>, String> {
@@ -290,71 +355,36 @@ pub async fn grpc_select_proto_file(app: AppHandle) -> Result<Option<String>, St
 
 // Helper module for parsing proto files
 mod protox_parse {
+    use miette::Diagnostic;
     use prost_reflect::DescriptorPool;
     use std::path::PathBuf;
-    use std::process::Command;
 
+    /// Compile a proto file and its imports in process. Like `protoc -I`,
+    /// imports are looked up in `include_paths` and the file itself must lie
+    /// in one of them.
     pub fn parse_proto_file(
         proto_path: &str,
         include_paths: &[PathBuf],
     ) -> Result<DescriptorPool, String> {
-        // Use protoc to compile the proto file to a file descriptor set
-        let protoc = find_protoc()?;
-
-        let temp_dir = std::env::temp_dir();
-        let descriptor_path = temp_dir.join(format!("resonance_proto_{}.pb", uuid::Uuid::new_v4()));
-
-        let mut cmd = Command::new(&protoc);
-        cmd.arg("--descriptor_set_out")
-            .arg(&descriptor_path)
-            .arg("--include_imports");
-
-        for include in include_paths {
-            cmd.arg("-I").arg(include);
-        }
-
-        cmd.arg(proto_path);
-
-        let output = cmd
-            .output()
-            .map_err(|e| format!("Failed to run protoc: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // Clean up temp file if it exists
-            let _ = std::fs::remove_file(&descriptor_path);
-            return Err(format!("protoc failed: {}", stderr));
-        }
-
-        // Read the descriptor set
-        let descriptor_bytes = std::fs::read(&descriptor_path)
-            .map_err(|e| format!("Failed to read descriptor set: {}", e))?;
-
-        // Clean up temp file
-        let _ = std::fs::remove_file(&descriptor_path);
-
-        // Parse into DescriptorPool
-        DescriptorPool::decode(descriptor_bytes.as_slice())
-            .map_err(|e| format!("Failed to parse descriptor set: {}", e))
+        let mut compiler = protox::Compiler::new(include_paths).map_err(|e| describe(&e))?;
+        compiler.include_imports(true).include_source_info(true);
+        compiler.open_file(proto_path).map_err(|e| describe(&e))?;
+        Ok(compiler.descriptor_pool())
     }
 
-    fn find_protoc() -> Result<PathBuf, String> {
-        let prev_hook = std::panic::take_hook();
-        std::panic::set_hook(Box::new(|_| {}));
-        let vendored = std::panic::catch_unwind(protoc_bin_vendored::protoc_bin_path);
-        std::panic::set_hook(prev_hook);
-        if let Ok(Ok(path)) = vendored {
-            if path.exists() {
-                return Ok(path);
-            }
-        }
-
-        // Fall back to system protoc
-        if let Ok(path) = which::which("protoc") {
-            return Ok(path);
-        }
-
-        Err("protoc not found. Please install Protocol Buffers compiler.".to_string())
+    /// `file:line:column: message` where protox knows the location.
+    fn describe(error: &protox::Error) -> String {
+        let Some(file) = error.file() else {
+            return error.to_string();
+        };
+        let position = error
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .zip(error.source_code())
+            .and_then(|(label, source)| source.read_span(label.inner(), 0, 0).ok())
+            .map(|span| format!(":{}:{}", span.line() + 1, span.column() + 1))
+            .unwrap_or_default();
+        format!("{}{}: {}", file, position, error)
     }
 }
 
@@ -558,3 +588,88 @@ impl tonic::codec::Decoder for DynamicMessageDecoder {
         Ok(Some(msg))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+    use serde_json::json;
+
+    fn field(name: &str, number: i32, kind: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(kind as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn http_bodies_decode_to_json_with_a_loaded_message_type() {
+        let file = FileDescriptorProto {
+            name: Some("item.proto".to_string()),
+            package: Some("shop".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Item".to_string()),
+                field: vec![field("id", 1, Type::Int32), field("name", 2, Type::String)],
+                ..Default::default()
+            }],
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        };
+        let pool = DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] })
+            .unwrap();
+        let desc = pool.get_message_by_name("shop.Item").unwrap();
+
+        // id = 42, name = "abc"
+        let body = [0x08, 0x2a, 0x12, 0x03, b'a', b'b', b'c'];
+        assert_eq!(
+            decode_message(desc.clone(), &body).unwrap(),
+            json!({ "id": 42, "name": "abc" })
+        );
+        assert!(decode_message(desc, &[0x12, 0x09, b'a']).is_err());
+    }
+
+    #[test]
+    fn proto_files_compile_in_process_with_located_errors() {
+        let dir = std::env::temp_dir().join(format!("resonance-proto-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("common")).unwrap();
+        std::fs::write(
+            dir.join("common/id.proto"),
+            "syntax = \"proto3\";\npackage common;\nmessage Id { string value = 1; }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("users.proto"),
+            "syntax = \"proto3\";\npackage users;\nimport \"common/id.proto\";\n\
+             service Users { rpc Get(common.Id) returns (common.Id); }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("broken.proto"),
+            "syntax = \"proto3\";\nmessage Broken {\n  string = 1;\n}\n",
+        )
+        .unwrap();
+
+        let includes = vec![dir.clone()];
+        let users = dir.join("users.proto");
+        let pool = protox_parse::parse_proto_file(users.to_str().unwrap(), &includes).unwrap();
+        let service = pool.get_service_by_name("users.Users").unwrap();
+        assert_eq!(
+            service.methods().next().unwrap().input().full_name(),
+            "common.Id"
+        );
+
+        let broken = dir.join("broken.proto");
+        let error =
+            protox_parse::parse_proto_file(broken.to_str().unwrap(), &includes).unwrap_err();
+        assert!(error.starts_with("broken.proto:3:"), "{}", error);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
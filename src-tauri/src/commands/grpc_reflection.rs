@@ -1,4 +1,10 @@
 use super::api_request::ClientCertConfig;
+use super::grpc_metadata::apply_metadata_defaults;
+use super::proxy::{connect_tunnel, ProxyState, TunnelProxy};
+use super::variables::{
+    load_active_environment_variables, load_collection_variables, variable_pattern,
+    VariableResolver,
+};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use bytes::Buf;
@@ -8,7 +14,7 @@ use prost_reflect::{DescriptorPool, DynamicMessage};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
 use tonic::metadata::KeyAndValueRef;
 use tonic::metadata::{MetadataKey, MetadataValue};
@@ -54,17 +60,34 @@ pub struct GrpcUnaryRequest {
     pub deadline_ms: Option<u64>,
     #[serde(default)]
     pub tls: GrpcTlsOptions,
+    /// Collection whose default metadata is merged into `metadata`
+    #[serde(default)]
+    pub collection_id: Option<String>,
 }
 
 #[tauri::command]
 pub async fn grpc_invoke_unary(
-    _app: AppHandle,
-    request: GrpcUnaryRequest,
+    app: AppHandle,
+    mut request: GrpcUnaryRequest,
 ) -> Result<Value, String> {
-    let target = normalize_target_with_tls(&request.target, request.tls.use_tls);
-    let pool =
-        build_descriptor_pool_for_method_with_tls(&target, &request.full_method, &request.tls)
-            .await?;
+    apply_metadata_defaults(
+        &app,
+        request.collection_id.as_deref(),
+        &request.full_method,
+        &mut request.metadata,
+    )
+    .await?;
+    let target =
+        resolve_target_variables(&app, request.collection_id.as_deref(), &request.target).await?;
+    let target = normalize_target_with_tls(&target, request.tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
+    let pool = build_descriptor_pool_for_method_with_tls(
+        &target,
+        &request.full_method,
+        &request.tls,
+        &route,
+    )
+    .await?;
     let (input_type, output_type) = resolve_method_types(&pool, &request.full_method)?;
 
     let input_desc = pool
@@ -76,7 +99,7 @@ pub async fn grpc_invoke_unary(
 
     let input_msg = json_to_dynamic_message(&request.request_json, input_desc.clone())?;
 
-    let channel = create_channel(&target, &request.tls).await?;
+    let channel = create_channel(&target, &request.tls, &route).await?;
 
     let mut grpc = tonic::client::Grpc::new(channel);
     grpc.ready()
@@ -141,15 +164,17 @@ pub async fn grpc_invoke_unary(
 
 #[tauri::command]
 pub async fn grpc_reflection_list_methods(
-    _app: AppHandle,
+    app: AppHandle,
     target: String,
     service_name: String,
     tls: Option<GrpcTlsOptions>,
 ) -> Result<Value, String> {
     let tls = tls.unwrap_or_default();
+    let target = resolve_target_variables(&app, None, &target).await?;
     let target = normalize_target_with_tls(&target, tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
 
-    let channel = create_channel(&target, &tls).await?;
+    let channel = create_channel(&target, &tls, &route).await?;
     let mut client = ReflectionClient::new(channel);
 
     let files = client.file_containing_symbol(&service_name).await?;
@@ -386,14 +411,16 @@ impl ReflectionClient {
 
 #[tauri::command]
 pub async fn grpc_reflection_list_services(
-    _app: AppHandle,
+    app: AppHandle,
     target: String,
     tls: Option<GrpcTlsOptions>,
 ) -> Result<Value, String> {
     let tls = tls.unwrap_or_default();
+    let target = resolve_target_variables(&app, None, &target).await?;
     let target = normalize_target_with_tls(&target, tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
 
-    let channel = create_channel(&target, &tls).await?;
+    let channel = create_channel(&target, &tls, &route).await?;
     let mut client = ReflectionClient::new(channel);
 
     let services = client.list_services().await?;
@@ -421,15 +448,72 @@ pub(crate) fn normalize_target_with_tls(target: &str, use_tls: bool) -> String {
     }
 }
 
-pub(crate) async fn create_channel(target: &str, tls: &GrpcTlsOptions) -> Result<Channel, String> {
+/// Substitute `{{variable}}` references in a gRPC target (`{{grpcTarget}}`,
+/// `{{host}}:50051`) with the collection variables and the active
+/// environment's, so switching environments moves gRPC calls between
+/// clusters the way it does REST base URLs.
+pub(crate) async fn resolve_target_variables(
+    app: &AppHandle,
+    collection_id: Option<&str>,
+    target: &str,
+) -> Result<String, String> {
+    if !target.contains("{{") {
+        return Ok(target.to_string());
+    }
+    let mut variables = match collection_id {
+        Some(collection_id) => load_collection_variables(app, collection_id).await,
+        None => HashMap::new(),
+    };
+    variables.extend(load_active_environment_variables(app).await);
+    expand_target(target, variables)
+}
+
+/// A reference left unresolved fails the call instead of dialing a literal
+/// `{{...}}` host.
+fn expand_target(target: &str, variables: HashMap<String, String>) -> Result<String, String> {
+    let resolved = VariableResolver::new(variables).resolve(target);
+    match variable_pattern().find(&resolved) {
+        Some(unresolved) => Err(format!(
+            "gRPC target '{}' uses {}, which neither the collection nor the active environment defines",
+            target,
+            unresolved.as_str()
+        )),
+        None => Ok(resolved),
+    }
+}
+
+/// How gRPC connections to a target are made, from the network settings.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GrpcRoute {
+    /// HTTP proxy the connection is tunneled through
+    pub proxy: Option<TunnelProxy>,
+    /// Certificates trusted for the target's host despite failing verification
+    pub trusted: Vec<String>,
+}
+
+/// The route of gRPC connections to `target`: its proxy and trusted
+/// certificates. Fails while offline mode is on and `target` is not on this
+/// machine.
+pub(crate) fn grpc_route_for(app: &AppHandle, target: &str) -> Result<GrpcRoute, String> {
+    let state = app.state::<ProxyState>();
+    state.check_offline(target)?;
+    Ok(GrpcRoute {
+        proxy: state.grpc_proxy(target)?,
+        trusted: state.trusted_hosts.fingerprints_for(target),
+    })
+}
+
+pub(crate) async fn create_channel(
+    target: &str,
+    tls: &GrpcTlsOptions,
+    route: &GrpcRoute,
+) -> Result<Channel, String> {
     let endpoint =
         Endpoint::from_shared(target.to_string()).map_err(|e| format!("Invalid target: {}", e))?;
+    let proxy = route.proxy.as_ref();
 
     if !tls.use_tls {
-        return endpoint
-            .connect()
-            .await
-            .map_err(|e| format!("Connection failed: {}", e));
+        return connect(endpoint, proxy).await;
     }
 
     let (cert_path, key_path, ca_path) = match &tls.client_cert {
@@ -439,7 +523,15 @@ pub(crate) async fn create_channel(target: &str, tls: &GrpcTlsOptions) -> Result
     let identity_pems = crate::commands::tls::load_identity_pems(cert_path, key_path)?;
 
     if tls.skip_verify {
-        return connect_skip_verify(endpoint, identity_pems).await;
+        let config = crate::commands::tls::build_danger_grpc_tls_config(identity_pems)?;
+        return connect_with_rustls(endpoint, config, proxy.cloned()).await;
+    }
+    if !route.trusted.is_empty() {
+        let ca_pem = crate::commands::tls::load_ca_pem(ca_path)?;
+        let mut config =
+            crate::commands::tls::build_trusted_tls_config(&route.trusted, ca_pem, identity_pems)?;
+        config.alpn_protocols = vec![b"h2".to_vec()];
+        return connect_with_rustls(endpoint, config, proxy.cloned()).await;
     }
 
     let mut tls_config = ClientTlsConfig::new().with_native_roots();
@@ -450,36 +542,68 @@ pub(crate) async fn create_channel(target: &str, tls: &GrpcTlsOptions) -> Result
         tls_config = tls_config.identity(tonic::transport::Identity::from_pem(cert_pem, key_pem));
     }
 
-    endpoint
+    let endpoint = endpoint
         .tls_config(tls_config)
-        .map_err(|e| format!("TLS config error: {}", e))?
-        .connect()
+        .map_err(|e| format!("TLS config error: {}", e))?;
+    connect(endpoint, proxy).await
+}
+
+fn uri_host_port(uri: &http::Uri) -> std::io::Result<(String, u16)> {
+    let host = uri
+        .host()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "target has no host"))?
+        .to_string();
+    let default_port = if uri.scheme_str() == Some("https") {
+        443
+    } else {
+        80
+    };
+    Ok((host, uri.port_u16().unwrap_or(default_port)))
+}
+
+/// Connect directly, or through an HTTP CONNECT tunnel when a proxy is set.
+/// tonic applies the endpoint's TLS config on top of the tunnel.
+async fn connect(endpoint: Endpoint, proxy: Option<&TunnelProxy>) -> Result<Channel, String> {
+    let Some(proxy) = proxy.cloned() else {
+        return endpoint
+            .connect()
+            .await
+            .map_err(|e| format!("Connection failed: {}", e));
+    };
+
+    let connector = tower::service_fn(move |uri: http::Uri| {
+        let proxy = proxy.clone();
+        async move {
+            let (host, port) = uri_host_port(&uri)?;
+            let tcp = connect_tunnel(&proxy, &host, port).await?;
+            Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(tcp))
+        }
+    });
+    endpoint
+        .connect_with_connector(connector)
         .await
         .map_err(|e| format!("Connection failed: {}", e))
 }
 
-/// Connect with server-certificate verification disabled. tonic has no hook
-/// for a custom certificate verifier, so the TLS handshake happens in a custom
-/// connector and tonic receives an already-encrypted stream.
-async fn connect_skip_verify(
+/// Connect with a custom certificate verifier (verification disabled, or
+/// trust exceptions). tonic has no hook for one, so the TLS handshake happens
+/// in a custom connector and tonic receives an already-encrypted stream.
+async fn connect_with_rustls(
     endpoint: Endpoint,
-    identity_pems: Option<crate::commands::tls::IdentityPems>,
+    config: rustls::ClientConfig,
+    proxy: Option<TunnelProxy>,
 ) -> Result<Channel, String> {
-    let config = crate::commands::tls::build_danger_grpc_tls_config(identity_pems)?;
     let tls_connector = tokio_rustls::TlsConnector::from(std::sync::Arc::new(config));
 
     let connector = tower::service_fn(move |uri: http::Uri| {
         let tls_connector = tls_connector.clone();
+        let proxy = proxy.clone();
         async move {
-            let host = uri
-                .host()
-                .ok_or_else(|| {
-                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "target has no host")
-                })?
-                .to_string();
-            let port = uri.port_u16().unwrap_or(443);
-
-            let tcp = tokio::net::TcpStream::connect((host.as_str(), port)).await?;
+            let (host, port) = uri_host_port(&uri)?;
+            let tcp = match &proxy {
+                Some(proxy) => connect_tunnel(proxy, &host, port).await?,
+                None => tokio::net::TcpStream::connect((host.as_str(), port)).await?,
+            };
             let server_name =
                 rustls::pki_types::ServerName::try_from(host.clone()).map_err(|e| {
                     std::io::Error::new(
@@ -573,15 +697,18 @@ pub(crate) fn dynamic_message_to_json(msg: &DynamicMessage) -> Result<Value, Str
 
 #[tauri::command]
 pub async fn grpc_get_input_skeleton(
-    _app: AppHandle,
+    app: AppHandle,
     target: String,
     full_method: String,
     tls: Option<GrpcTlsOptions>,
 ) -> Result<Value, String> {
     let tls = tls.unwrap_or_default();
+    let target = resolve_target_variables(&app, None, &target).await?;
     let target = normalize_target_with_tls(&target, tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
 
-    let pool = build_descriptor_pool_for_method_with_tls(&target, &full_method, &tls).await?;
+    let pool =
+        build_descriptor_pool_for_method_with_tls(&target, &full_method, &tls, &route).await?;
     let (input_type, _) = resolve_method_types(&pool, &full_method)?;
 
     let input_desc = pool
@@ -592,7 +719,7 @@ pub async fn grpc_get_input_skeleton(
     Ok(skeleton)
 }
 
-fn generate_message_skeleton(desc: &prost_reflect::MessageDescriptor) -> Value {
+pub(crate) fn generate_message_skeleton(desc: &prost_reflect::MessageDescriptor) -> Value {
     let mut obj = serde_json::Map::new();
 
     for field in desc.fields() {
@@ -640,6 +767,7 @@ pub(crate) async fn build_descriptor_pool_for_method_with_tls(
     target: &str,
     full_method: &str,
     tls: &GrpcTlsOptions,
+    route: &GrpcRoute,
 ) -> Result<DescriptorPool, String> {
     let trimmed = full_method.trim();
     if !trimmed.starts_with('/') {
@@ -651,15 +779,28 @@ pub(crate) async fn build_descriptor_pool_for_method_with_tls(
     }
     let service_symbol = parts[0];
 
-    let channel = create_channel(target, tls).await?;
+    let channel = create_channel(target, tls, route).await?;
     let mut client = ReflectionClient::new(channel);
 
+    let collected = collect_file_descriptors(&mut client, &[service_symbol]).await?;
+    let fds = prost_types::FileDescriptorSet { file: collected };
+    DescriptorPool::from_file_descriptor_set(fds)
+        .map_err(|e| format!("Failed to build descriptor pool: {}", e))
+}
+
+/// The files defining `symbols` plus everything they import, each once.
+pub(crate) async fn collect_file_descriptors(
+    client: &mut ReflectionClient,
+    symbols: &[&str],
+) -> Result<Vec<prost_types::FileDescriptorProto>, String> {
     let mut collected: Vec<prost_types::FileDescriptorProto> = Vec::new();
     let mut seen: HashSet<String> = HashSet::new();
 
-    let initial = client.file_containing_symbol(service_symbol).await?;
-    for fd in initial {
-        queue_descriptor(&mut collected, &mut seen, fd);
+    for symbol in symbols {
+        let initial = client.file_containing_symbol(symbol).await?;
+        for fd in initial {
+            queue_descriptor(&mut collected, &mut seen, fd);
+        }
     }
 
     let mut idx = 0;
@@ -677,9 +818,7 @@ pub(crate) async fn build_descriptor_pool_for_method_with_tls(
         }
     }
 
-    let fds = prost_types::FileDescriptorSet { file: collected };
-    DescriptorPool::from_file_descriptor_set(fds)
-        .map_err(|e| format!("Failed to build descriptor pool: {}", e))
+    Ok(collected)
 }
 
 pub(crate) struct DynamicMessageCodec {
@@ -766,6 +905,21 @@ fn queue_descriptor(
 mod tests {
     use super::*;
 
+    #[test]
+    fn target_variables_resolve_or_fail_loudly() {
+        let variables = HashMap::from([
+            ("grpcTarget".to_string(), "{{host}}:50051".to_string()),
+            ("host".to_string(), "grpc.staging.internal".to_string()),
+        ]);
+        assert_eq!(
+            expand_target("{{grpcTarget}}", variables.clone()).unwrap(),
+            "grpc.staging.internal:50051"
+        );
+
+        let err = expand_target("https://{{region}}.{{host}}", variables).unwrap_err();
+        assert!(err.contains("{{region}}"), "{}", err);
+    }
+
     #[test]
     fn v1_and_v1alpha_reflection_messages_are_wire_compatible() {
         let v1_request = reflection::ServerReflectionRequest {
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use http::uri::PathAndQuery;
 use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
@@ -8,15 +10,18 @@ use serde_json::Value;
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::{mpsc, Mutex};
 use tokio::task::AbortHandle;
+use tokio::time::Instant;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tonic::metadata::{MetadataKey, MetadataValue};
-use tonic::Request;
+use tonic::{Request, Status};
 
+use super::grpc_metadata::apply_metadata_defaults;
 use super::grpc_proto::ProtoState;
 use super::grpc_reflection::{
     build_descriptor_pool_for_method_with_tls, create_channel, dynamic_message_to_json,
-    json_to_dynamic_message, metadata_to_json_map, normalize_target_with_tls, resolve_method_types,
-    strip_leading_dot, DynamicMessageCodec, GrpcTlsOptions,
+    grpc_route_for, json_to_dynamic_message, metadata_to_json_map, normalize_target_with_tls,
+    resolve_method_types, resolve_target_variables, strip_leading_dot, DynamicMessageCodec,
+    GrpcTlsOptions,
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -33,6 +38,13 @@ pub struct GrpcStreamRequest {
     pub tls: GrpcTlsOptions,
     #[serde(default)]
     pub proto_path: Option<String>,
+    /// Collection whose default metadata is merged into `metadata`
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    /// Time the whole call may take, from start to the last message; sent to
+    /// the server as `grpc-timeout`. No deadline when not set.
+    #[serde(default)]
+    pub deadline_ms: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -84,10 +96,47 @@ fn emit(app: &AppHandle, payload: GrpcStreamEventPayload) {
     let _ = app.emit("grpc-stream-event", payload);
 }
 
+/// Emits `error` and `close` for a call that ended with `status`.
+fn emit_failure(app: &AppHandle, tab_id: &str, full_method: &str, status: &Status) {
+    for event_type in ["error", "close"] {
+        emit(
+            app,
+            GrpcStreamEventPayload {
+                tab_id: tab_id.to_string(),
+                event_type: event_type.to_string(),
+                full_method: full_method.to_string(),
+                message: None,
+                status: Some(status.code() as i32),
+                status_message: Some(status.message().to_string()),
+                headers: None,
+                trailers: None,
+            },
+        );
+    }
+}
+
+/// Runs `call` to completion, or until `deadline` passes; `None` then.
+async fn until_deadline<F: Future>(deadline: Option<Instant>, call: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, call).await.ok(),
+        None => Some(call.await),
+    }
+}
+
+fn deadline_exceeded() -> Status {
+    Status::deadline_exceeded("deadline exceeded")
+}
+
+/// Sets the request metadata, and the `grpc-timeout` header when the call
+/// has a deadline.
 fn apply_metadata<T>(
     req: &mut Request<T>,
     metadata: HashMap<String, String>,
+    deadline: Option<Instant>,
 ) -> Result<(), String> {
+    if let Some(deadline) = deadline {
+        req.set_timeout(deadline.saturating_duration_since(Instant::now()));
+    }
     for (k, v) in metadata {
         let key = MetadataKey::from_bytes(k.as_bytes())
             .map_err(|e| format!("Invalid metadata key '{}': {}", k, e))?;
@@ -117,6 +166,35 @@ fn resolve_method_streaming(
     Ok((method.is_client_streaming(), method.is_server_streaming()))
 }
 
+/// Which kinds of streaming method a start command accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamKind {
+    /// Server, client or bidirectional streaming
+    Any,
+    /// Server streaming only: one request, a stream of responses
+    ServerOnly,
+}
+
+fn check_method_kind(
+    kind: StreamKind,
+    full_method: &str,
+    is_client_streaming: bool,
+    is_server_streaming: bool,
+) -> Result<(), String> {
+    if !is_server_streaming && !is_client_streaming {
+        return Err("Use grpc_invoke_unary for unary methods".to_string());
+    }
+    if kind == StreamKind::ServerOnly && is_client_streaming {
+        return Err(format!(
+            "{} is not a server-streaming method; use grpc_stream_start instead",
+            full_method
+        ));
+    }
+    Ok(())
+}
+
+/// Open a streaming call for a tab. Messages, errors and the end of the
+/// call are sent as `grpc-stream-event` events.
 #[tauri::command]
 pub async fn grpc_stream_start(
     app: AppHandle,
@@ -124,9 +202,43 @@ pub async fn grpc_stream_start(
     proto_state: State<'_, ProtoState>,
     request: GrpcStreamRequest,
 ) -> Result<GrpcStreamCommandResponse, String> {
+    start_stream(app, &state, &proto_state, request, StreamKind::Any).await
+}
+
+/// Call a server-streaming method, by reflection or from the loaded proto
+/// file at `protoPath`. Each response message is sent as a `message`
+/// `grpc-stream-event`; `grpc_stream_cancel` ends the call early, and
+/// `deadlineMs` bounds it, closing with `DEADLINE_EXCEEDED`.
+#[tauri::command]
+pub async fn grpc_invoke_server_stream(
+    app: AppHandle,
+    state: State<'_, GrpcStreamingState>,
+    proto_state: State<'_, ProtoState>,
+    request: GrpcStreamRequest,
+) -> Result<GrpcStreamCommandResponse, String> {
+    start_stream(app, &state, &proto_state, request, StreamKind::ServerOnly).await
+}
+
+async fn start_stream(
+    app: AppHandle,
+    state: &GrpcStreamingState,
+    proto_state: &ProtoState,
+    mut request: GrpcStreamRequest,
+    kind: StreamKind,
+) -> Result<GrpcStreamCommandResponse, String> {
+    let deadline = request
+        .deadline_ms
+        .map(|ms| Instant::now() + Duration::from_millis(ms));
     if request.tab_id.trim().is_empty() {
         return Err("Tab ID is required".to_string());
     }
+    apply_metadata_defaults(
+        &app,
+        request.collection_id.as_deref(),
+        &request.full_method,
+        &mut request.metadata,
+    )
+    .await?;
 
     // Close any existing stream for this tab before opening a new one
     {
@@ -137,7 +249,10 @@ pub async fn grpc_stream_start(
         }
     }
 
-    let target = normalize_target_with_tls(&request.target, request.tls.use_tls);
+    let target =
+        resolve_target_variables(&app, request.collection_id.as_deref(), &request.target).await?;
+    let target = normalize_target_with_tls(&target, request.tls.use_tls);
+    let route = grpc_route_for(&app, &target)?;
 
     let pool: DescriptorPool = if let Some(proto_path) = &request.proto_path {
         let pools = proto_state.pools.lock().map_err(|e| e.to_string())?;
@@ -146,17 +261,24 @@ pub async fn grpc_stream_start(
             .cloned()
             .ok_or_else(|| format!("Proto file not loaded: {}", proto_path))?
     } else {
-        build_descriptor_pool_for_method_with_tls(&target, &request.full_method, &request.tls)
-            .await?
+        build_descriptor_pool_for_method_with_tls(
+            &target,
+            &request.full_method,
+            &request.tls,
+            &route,
+        )
+        .await?
     };
 
     let (input_type, output_type) = resolve_method_types(&pool, &request.full_method)?;
     let (is_client_streaming, is_server_streaming) =
         resolve_method_streaming(&pool, &request.full_method)?;
-
-    if !is_server_streaming && !is_client_streaming {
-        return Err("Use grpc_invoke_unary for unary methods".to_string());
-    }
+    check_method_kind(
+        kind,
+        &request.full_method,
+        is_client_streaming,
+        is_server_streaming,
+    )?;
 
     let input_desc = pool
         .get_message_by_name(&strip_leading_dot(&input_type))
@@ -165,7 +287,7 @@ pub async fn grpc_stream_start(
         .get_message_by_name(&strip_leading_dot(&output_type))
         .ok_or_else(|| format!("Output message type not found: {}", output_type))?;
 
-    let channel = create_channel(&target, &request.tls).await?;
+    let channel = create_channel(&target, &request.tls, &route).await?;
     let mut grpc = tonic::client::Grpc::new(channel);
     grpc.ready()
         .await
@@ -193,7 +315,7 @@ pub async fn grpc_stream_start(
             let _ = tx.send(msg);
         }
         let mut req = Request::new(request_stream);
-        apply_metadata(&mut req, metadata)?;
+        apply_metadata(&mut req, metadata, deadline)?;
 
         emit(
             &app,
@@ -215,7 +337,10 @@ pub async fn grpc_stream_start(
         let read_full_method = full_method.clone();
 
         let join = tokio::spawn(async move {
-            match grpc.client_streaming(req, path, codec).await {
+            let result = until_deadline(deadline, grpc.client_streaming(req, path, codec))
+                .await
+                .unwrap_or_else(|| Err(deadline_exceeded()));
+            match result {
                 Ok(response) => {
                     let headers = metadata_to_json_map(response.metadata());
                     let msg = response.into_inner();
@@ -248,32 +373,7 @@ pub async fn grpc_stream_start(
                     );
                 }
                 Err(status) => {
-                    emit(
-                        &read_app,
-                        GrpcStreamEventPayload {
-                            tab_id: read_tab_id.clone(),
-                            event_type: "error".to_string(),
-                            full_method: read_full_method.clone(),
-                            message: None,
-                            status: Some(status.code() as i32),
-                            status_message: Some(status.message().to_string()),
-                            headers: None,
-                            trailers: None,
-                        },
-                    );
-                    emit(
-                        &read_app,
-                        GrpcStreamEventPayload {
-                            tab_id: read_tab_id.clone(),
-                            event_type: "close".to_string(),
-                            full_method: read_full_method.clone(),
-                            message: None,
-                            status: Some(status.code() as i32),
-                            status_message: Some(status.message().to_string()),
-                            headers: None,
-                            trailers: None,
-                        },
-                    );
+                    emit_failure(&read_app, &read_tab_id, &read_full_method, &status);
                 }
             }
 
@@ -309,10 +409,10 @@ pub async fn grpc_stream_start(
             let _ = tx.send(msg);
         }
         let mut req = Request::new(request_stream);
-        apply_metadata(&mut req, metadata)?;
-        let response = grpc
-            .streaming(req, path, codec)
+        apply_metadata(&mut req, metadata, deadline)?;
+        let response = until_deadline(deadline, grpc.streaming(req, path, codec))
             .await
+            .unwrap_or_else(|| Err(deadline_exceeded()))
             .map_err(|e| format!("Failed to start streaming call: {}", e))?;
         let headers = metadata_to_json_map(response.metadata());
         (Some(tx), response.into_inner(), headers)
@@ -324,10 +424,10 @@ pub async fn grpc_stream_start(
             .unwrap_or_else(|| Value::Object(Default::default()));
         let initial = json_to_dynamic_message(&initial_json, input_desc.clone())?;
         let mut req = Request::new(initial);
-        apply_metadata(&mut req, metadata)?;
-        let response = grpc
-            .server_streaming(req, path, codec)
+        apply_metadata(&mut req, metadata, deadline)?;
+        let response = until_deadline(deadline, grpc.server_streaming(req, path, codec))
             .await
+            .unwrap_or_else(|| Err(deadline_exceeded()))
             .map_err(|e| format!("Failed to start server streaming: {}", e))?;
         let headers = metadata_to_json_map(response.metadata());
         (None, response.into_inner(), headers)
@@ -354,7 +454,10 @@ pub async fn grpc_stream_start(
 
     let join = tokio::spawn(async move {
         loop {
-            match response_stream.message().await {
+            let next = until_deadline(deadline, response_stream.message())
+                .await
+                .unwrap_or_else(|| Err(deadline_exceeded()));
+            match next {
                 Ok(Some(msg)) => {
                     let data = dynamic_message_to_json(&msg).unwrap_or(Value::Null);
                     emit(
@@ -394,32 +497,7 @@ pub async fn grpc_stream_start(
                     break;
                 }
                 Err(status) => {
-                    emit(
-                        &read_app,
-                        GrpcStreamEventPayload {
-                            tab_id: read_tab_id.clone(),
-                            event_type: "error".to_string(),
-                            full_method: read_full_method.clone(),
-                            message: None,
-                            status: Some(status.code() as i32),
-                            status_message: Some(status.message().to_string()),
-                            headers: None,
-                            trailers: None,
-                        },
-                    );
-                    emit(
-                        &read_app,
-                        GrpcStreamEventPayload {
-                            tab_id: read_tab_id.clone(),
-                            event_type: "close".to_string(),
-                            full_method: read_full_method.clone(),
-                            message: None,
-                            status: Some(status.code() as i32),
-                            status_message: Some(status.message().to_string()),
-                            headers: None,
-                            trailers: None,
-                        },
-                    );
+                    emit_failure(&read_app, &read_tab_id, &read_full_method, &status);
                     break;
                 }
             }
@@ -519,3 +597,37 @@ pub async fn grpc_stream_cancel(
     }
     Ok(GrpcStreamCommandResponse { success: true })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_stream_accepts_server_streaming_methods_only() {
+        let method = "/pkg.Feed/Watch";
+        assert!(check_method_kind(StreamKind::ServerOnly, method, false, true).is_ok());
+        assert!(check_method_kind(StreamKind::ServerOnly, method, true, true).is_err());
+        assert!(check_method_kind(StreamKind::ServerOnly, method, true, false).is_err());
+        assert!(check_method_kind(StreamKind::Any, method, true, true).is_ok());
+        assert!(check_method_kind(StreamKind::Any, method, false, false).is_err());
+
+        let request: GrpcStreamRequest = serde_json::from_value(serde_json::json!({
+            "tabId": "t1",
+            "target": "localhost:50051",
+            "fullMethod": method,
+            "deadlineMs": 1500
+        }))
+        .unwrap();
+        assert_eq!(request.deadline_ms, Some(1500));
+    }
+
+    #[tokio::test]
+    async fn calls_end_when_the_deadline_passes() {
+        let deadline = Some(Instant::now() + Duration::from_millis(10));
+        assert_eq!(
+            until_deadline(deadline, std::future::pending::<()>()).await,
+            None
+        );
+        assert_eq!(until_deadline(None, async { 7 }).await, Some(7));
+    }
+}
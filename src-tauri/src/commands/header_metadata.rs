@@ -0,0 +1,366 @@
+//! Request header metadata for autocomplete: standard header names with a
+//! short description and common values, merged with the headers and values
+//! sent before in this workspace (from request history). Kept in the backend
+//! so the frontend does not bundle the dataset.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+/// Values remembered per header from history.
+const MAX_SEEN_VALUES: usize = 10;
+
+/// Headers whose values are credentials; their past values are never offered.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+];
+
+struct StandardHeader {
+    name: &'static str,
+    description: &'static str,
+    values: &'static [&'static str],
+}
+
+const STANDARD_HEADERS: &[StandardHeader] = &[
+    StandardHeader {
+        name: "Accept",
+        description: "Media types the client can handle in the response.",
+        values: &[
+            "application/json",
+            "*/*",
+            "text/html",
+            "application/xml",
+            "text/plain",
+            "text/event-stream",
+        ],
+    },
+    StandardHeader {
+        name: "Accept-Charset",
+        description: "Character sets the client can handle.",
+        values: &["utf-8"],
+    },
+    StandardHeader {
+        name: "Accept-Encoding",
+        description: "Content encodings (compression) the client can handle.",
+        values: &["gzip, deflate, br", "gzip", "identity"],
+    },
+    StandardHeader {
+        name: "Accept-Language",
+        description: "Natural languages preferred for the response.",
+        values: &["en-US,en;q=0.9", "en", "de", "fr"],
+    },
+    StandardHeader {
+        name: "Authorization",
+        description: "Credentials authenticating the client with the server.",
+        values: &["Bearer {{token}}", "Basic ", "Bearer "],
+    },
+    StandardHeader {
+        name: "Cache-Control",
+        description: "Caching directives for this request.",
+        values: &["no-cache", "no-store", "max-age=0", "only-if-cached"],
+    },
+    StandardHeader {
+        name: "Connection",
+        description: "Whether the connection stays open after the request.",
+        values: &["keep-alive", "close"],
+    },
+    StandardHeader {
+        name: "Content-Encoding",
+        description: "Encoding (compression) applied to the request body.",
+        values: &["gzip", "deflate", "br"],
+    },
+    StandardHeader {
+        name: "Content-Language",
+        description: "Natural language of the request body.",
+        values: &["en-US", "en"],
+    },
+    StandardHeader {
+        name: "Content-Length",
+        description: "Size of the request body in bytes; normally set automatically.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "Content-Type",
+        description: "Media type of the request body.",
+        values: &[
+            "application/json",
+            "application/x-www-form-urlencoded",
+            "multipart/form-data",
+            "text/plain",
+            "application/xml",
+            "text/xml; charset=utf-8",
+            "application/octet-stream",
+            "application/graphql",
+        ],
+    },
+    StandardHeader {
+        name: "Cookie",
+        description: "Cookies previously sent by the server.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "DNT",
+        description: "Asks the server not to track the user.",
+        values: &["1", "0"],
+    },
+    StandardHeader {
+        name: "Expect",
+        description: "Expectations the server must meet to handle the request.",
+        values: &["100-continue"],
+    },
+    StandardHeader {
+        name: "Forwarded",
+        description: "Client information added by proxies (RFC 7239).",
+        values: &["for=192.0.2.60;proto=https"],
+    },
+    StandardHeader {
+        name: "From",
+        description: "Email address of the person controlling the user agent.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "Host",
+        description: "Host and port of the server; normally taken from the URL.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "Idempotency-Key",
+        description: "Unique key that lets the server deduplicate retried requests.",
+        values: &["{{$uuid}}"],
+    },
+    StandardHeader {
+        name: "If-Match",
+        description: "Only apply the request if the resource matches one of the ETags.",
+        values: &["*"],
+    },
+    StandardHeader {
+        name: "If-Modified-Since",
+        description: "Only return the resource if it changed after the given date.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "If-None-Match",
+        description: "Only return the resource if it matches none of the ETags.",
+        values: &["*"],
+    },
+    StandardHeader {
+        name: "If-Unmodified-Since",
+        description: "Only apply the request if the resource did not change after the given date.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "Origin",
+        description: "Origin that initiated the request; used for CORS.",
+        values: &["http://localhost:3000"],
+    },
+    StandardHeader {
+        name: "Pragma",
+        description: "HTTP/1.0 caching directive.",
+        values: &["no-cache"],
+    },
+    StandardHeader {
+        name: "Prefer",
+        description: "Preferred server behaviour (RFC 7240).",
+        values: &["return=representation", "return=minimal", "respond-async"],
+    },
+    StandardHeader {
+        name: "Proxy-Authorization",
+        description: "Credentials authenticating the client with a proxy.",
+        values: &["Basic "],
+    },
+    StandardHeader {
+        name: "Range",
+        description: "Part of the resource to return.",
+        values: &["bytes=0-1023"],
+    },
+    StandardHeader {
+        name: "Referer",
+        description: "Address of the page that linked to the requested resource.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "TE",
+        description: "Transfer encodings the client accepts.",
+        values: &["trailers"],
+    },
+    StandardHeader {
+        name: "traceparent",
+        description: "W3C Trace Context: trace and parent span of the request.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "Upgrade",
+        description: "Asks the server to switch to another protocol.",
+        values: &["websocket", "h2c"],
+    },
+    StandardHeader {
+        name: "User-Agent",
+        description: "Identifies the client software.",
+        values: &["Resonance"],
+    },
+    StandardHeader {
+        name: "Via",
+        description: "Proxies the request passed through.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "X-API-Key",
+        description: "API key, a common non-standard alternative to Authorization.",
+        values: &["{{apiKey}}"],
+    },
+    StandardHeader {
+        name: "X-Correlation-ID",
+        description: "Identifier correlating the request across services.",
+        values: &["{{$uuid}}"],
+    },
+    StandardHeader {
+        name: "X-Forwarded-For",
+        description: "Originating client IP address, added by proxies.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "X-Forwarded-Host",
+        description: "Original Host requested by the client, added by proxies.",
+        values: &[],
+    },
+    StandardHeader {
+        name: "X-Forwarded-Proto",
+        description: "Original protocol used by the client, added by proxies.",
+        values: &["https", "http"],
+    },
+    StandardHeader {
+        name: "X-Request-ID",
+        description: "Unique identifier of the request, for tracing in logs.",
+        values: &["{{$uuid}}"],
+    },
+    StandardHeader {
+        name: "X-Requested-With",
+        description: "Marks AJAX requests.",
+        values: &["XMLHttpRequest"],
+    },
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderMetadata {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Common values of a standard header
+    pub values: Vec<String>,
+    /// Values sent before in this workspace, most recent first
+    pub seen_values: Vec<String>,
+    /// Part of the built-in dataset (`false` = only seen in history)
+    pub standard: bool,
+}
+
+/// Merges the request headers of `history` (newest first) into the standard
+/// dataset.
+fn merge_history(history: &Value) -> Vec<HeaderMetadata> {
+    let mut headers: Vec<HeaderMetadata> = STANDARD_HEADERS
+        .iter()
+        .map(|header| HeaderMetadata {
+            name: header.name.to_string(),
+            description: Some(header.description.to_string()),
+            values: header.values.iter().map(|v| v.to_string()).collect(),
+            seen_values: Vec::new(),
+            standard: true,
+        })
+        .collect();
+
+    let sent = history
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.pointer("/request/headers").and_then(Value::as_object))
+        .flatten();
+    for (name, value) in sent {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        let index = match headers
+            .iter()
+            .position(|header| header.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => index,
+            None => {
+                headers.push(HeaderMetadata {
+                    name: name.to_string(),
+                    description: None,
+                    values: Vec::new(),
+                    seen_values: Vec::new(),
+                    standard: false,
+                });
+                headers.len() - 1
+            }
+        };
+        let header = &mut headers[index];
+        let Some(value) = value.as_str().map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+            || header.seen_values.len() >= MAX_SEEN_VALUES
+            || header.seen_values.iter().any(|seen| seen == value)
+        {
+            continue;
+        }
+        header.seen_values.push(value.to_string());
+    }
+
+    headers.sort_by_key(|header| header.name.to_ascii_lowercase());
+    headers
+}
+
+/// Header names, descriptions and values for request header autocomplete.
+#[tauri::command]
+pub async fn headers_metadata(app: AppHandle) -> Result<Vec<HeaderMetadata>, String> {
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+    let history = store.get(HISTORY_KEY).unwrap_or(Value::Null);
+    Ok(merge_history(&history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_values_seen_in_history() {
+        let history = serde_json::json!([
+            { "request": { "headers": {
+                "content-type": "application/vnd.api+json",
+                "Authorization": "Bearer secret",
+                "X-Tenant": "acme"
+            } } },
+            { "request": { "headers": {
+                "Content-Type": "application/vnd.api+json",
+                "X-Tenant": "globex"
+            } } },
+            { "request": { "url": "https://api.test" } }
+        ]);
+        let headers = merge_history(&history);
+        let find = |name: &str| headers.iter().find(|h| h.name == name).unwrap();
+
+        let content_type = find("Content-Type");
+        assert!(content_type.standard);
+        assert!(content_type
+            .values
+            .contains(&"application/json".to_string()));
+        assert_eq!(content_type.seen_values, vec!["application/vnd.api+json"]);
+
+        assert!(find("Authorization").seen_values.is_empty());
+
+        let tenant = find("X-Tenant");
+        assert!(!tenant.standard && tenant.description.is_none());
+        assert_eq!(tenant.seen_values, vec!["acme", "globex"]);
+    }
+}
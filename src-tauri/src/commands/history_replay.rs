@@ -0,0 +1,153 @@
+//! Replaying a history entry: re-sends the request exactly as it was recorded
+//! (resolved URL, headers and body; variables are not resolved again) and
+//! returns the recorded and the new response side by side. Nothing of the
+//! collection's current setup (inherited auth, secrets, proxy, CA bundle) is
+//! applied; only offline mode and the safety policy still hold it back.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, State};
+use tauri_plugin_store::StoreExt;
+
+use super::api_request::{
+    confirmation_required, execute_api_request, safety_policy_reason, ApiResponse, RequestOptions,
+    RequestState,
+};
+use super::proxy::ProxyState;
+use super::secrets::{mask_secret_values, secret_variable_values, SECRET_MASK};
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryReplay {
+    /// When the recorded request was sent (ms since the epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<i64>,
+    /// The request as recorded, which is what was sent again
+    pub request: Value,
+    /// The recorded response
+    pub previous: Value,
+    /// The response to the replayed request
+    pub current: ApiResponse,
+    pub status_changed: bool,
+    pub body_changed: bool,
+}
+
+/// Request options for a recorded request. History masks secret variable
+/// values, and sending the mask would not be a faithful replay.
+fn replay_options(request: &Value) -> Result<RequestOptions, String> {
+    if request.to_string().contains(SECRET_MASK) {
+        return Err(
+            "This request was recorded with masked secret values and cannot be re-sent exactly"
+                .to_string(),
+        );
+    }
+    serde_json::from_value(request.clone())
+        .map_err(|e| format!("Invalid request in history entry: {}", e))
+}
+
+/// An empty body is recorded as `null` but received as an empty string.
+fn same_body(previous: Option<&Value>, current: Option<&Value>) -> bool {
+    let normalize = |value: Option<&Value>| match value {
+        None | Some(Value::Null) => Value::String(String::new()),
+        Some(value) => value.clone(),
+    };
+    normalize(previous) == normalize(current)
+}
+
+/// Re-send history entry `id` as recorded and return both responses.
+/// `confirmed` skips the collection's safety policy, like `send_api_request`.
+#[tauri::command]
+pub async fn replay_history_entry(
+    app: AppHandle,
+    state: State<'_, RequestState>,
+    proxy_state: State<'_, ProxyState>,
+    id: String,
+    confirmed: Option<bool>,
+) -> Result<HistoryReplay, String> {
+    let entry = {
+        let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+        store
+            .get(HISTORY_KEY)
+            .and_then(|history| {
+                history
+                    .as_array()?
+                    .iter()
+                    .find(|entry| entry.get("id").and_then(Value::as_str) == Some(id.as_str()))
+                    .cloned()
+            })
+            .ok_or_else(|| format!("History entry {} not found", id))?
+    };
+    let request = entry.get("request").cloned().unwrap_or(Value::Null);
+    let previous = entry.get("response").cloned().unwrap_or(Value::Null);
+
+    let mut options = replay_options(&request)?;
+    options.confirmed = confirmed.unwrap_or(false);
+    let current = match safety_policy_reason(&app, &options)? {
+        Some(reason) => confirmation_required(reason),
+        None => {
+            // Cancellable like any other send, but without re-resolving it
+            let cancelled = state.start_cancellable();
+            let response = execute_api_request(&proxy_state, options, cancelled).await;
+            state.finish_cancellable();
+            response?
+        }
+    };
+
+    // The recorded body had secrets masked; mask the new one the same way
+    // before comparing
+    let mut current_data = current.data.clone().unwrap_or(Value::Null);
+    let collection_id = request.get("collectionId").and_then(Value::as_str);
+    mask_secret_values(
+        &mut current_data,
+        &secret_variable_values(&app, collection_id).await,
+    );
+
+    Ok(HistoryReplay {
+        sent_at: entry.get("timestamp").and_then(Value::as_i64),
+        status_changed: previous.get("status").and_then(Value::as_u64)
+            != current.status.map(u64::from),
+        body_changed: !same_body(previous.get("data"), Some(&current_data)),
+        request,
+        previous,
+        current,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn replays_recorded_requests_only_when_unmasked() {
+        let recorded = json!({
+            "method": "POST",
+            "url": "https://api.test/orders?page=2",
+            "rawUrl": "{{baseUrl}}/orders",
+            "headers": { "Content-Type": "application/json" },
+            "body": { "id": 7 },
+            "bodyType": null,
+            "collectionId": "c1"
+        });
+        let options = replay_options(&recorded).unwrap();
+        assert_eq!(options.url, "https://api.test/orders?page=2");
+        assert_eq!(options.body, Some(json!({ "id": 7 })));
+        assert_eq!(options.collection_id.as_deref(), Some("c1"));
+
+        let masked = json!({
+            "method": "GET",
+            "url": "https://api.test",
+            "headers": { "Authorization": format!("Bearer {}", SECRET_MASK) }
+        });
+        assert!(replay_options(&masked).is_err());
+
+        assert!(same_body(Some(&Value::Null), Some(&json!(""))));
+        assert!(!same_body(
+            Some(&json!({ "a": 1 })),
+            Some(&json!({ "a": 2 }))
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+//! Hypermedia link extraction: follow-up requests advertised by a response
+//! (HAL `_links`, JSON:API `links`, Siren links and actions, and the `Link`
+//! header), resolved against the request URL so they can be opened directly.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Links returned at most, so a large embedded collection stays usable.
+const MAX_LINKS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HypermediaLink {
+    /// Link relation, e.g. "next", "self", "author.related"
+    pub rel: String,
+    /// Absolute URL (a URI template when `templated`)
+    pub href: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// RFC 6570 template whose variables still need filling in
+    pub templated: bool,
+    /// Where the link came from: "hal" | "jsonapi" | "siren" | "header"
+    pub source: String,
+}
+
+struct LinkCollector {
+    base: Option<url::Url>,
+    links: Vec<HypermediaLink>,
+}
+
+impl LinkCollector {
+    fn add(
+        &mut self,
+        rel: &str,
+        href: &str,
+        method: Option<&str>,
+        title: Option<&str>,
+        source: &str,
+    ) {
+        let href = href.trim();
+        if href.is_empty() || self.links.len() >= MAX_LINKS {
+            return;
+        }
+        let templated = href.contains('{');
+        let link = HypermediaLink {
+            rel: rel.to_string(),
+            href: self.resolve(href, templated),
+            method: method
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .unwrap_or("GET")
+                .to_ascii_uppercase(),
+            title: title.map(str::to_string),
+            templated,
+            source: source.to_string(),
+        };
+        if !self.links.contains(&link) {
+            self.links.push(link);
+        }
+    }
+
+    /// Resolves a relative `href` against the request URL. Template
+    /// expressions would be percent-encoded by the URL parser, so only the
+    /// part before the first one is resolved.
+    fn resolve(&self, href: &str, templated: bool) -> String {
+        let Some(base) = &self.base else {
+            return href.to_string();
+        };
+        let (fixed, template) = match href.find('{') {
+            Some(index) if templated => href.split_at(index),
+            _ => (href, ""),
+        };
+        if fixed.is_empty() {
+            return format!("{}{}", base, template);
+        }
+        match base.join(fixed) {
+            Ok(url) => format!("{}{}", url, template),
+            Err(_) => href.to_string(),
+        }
+    }
+
+    /// HAL: `_links: { rel: {href} | [{href}] }`.
+    fn add_hal(&mut self, object: &Map<String, Value>) {
+        let Some(links) = object.get("_links").and_then(Value::as_object) else {
+            return;
+        };
+        for (rel, value) in links.iter().filter(|(rel, _)| *rel != "curies") {
+            let targets = match value {
+                Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+            for target in targets {
+                let Some(href) = target.get("href").and_then(Value::as_str) else {
+                    continue;
+                };
+                let title = target
+                    .get("title")
+                    .or_else(|| target.get("name"))
+                    .and_then(Value::as_str);
+                let method = target.get("method").and_then(Value::as_str);
+                self.add(rel, href, method, title, "hal");
+            }
+        }
+    }
+
+    /// The `self` link of each HAL `_embedded` resource, named after the
+    /// embedded relation.
+    fn add_hal_embedded(&mut self, object: &Map<String, Value>) {
+        let Some(embedded) = object.get("_embedded").and_then(Value::as_object) else {
+            return;
+        };
+        for (name, value) in embedded {
+            let resources = match value {
+                Value::Array(items) => items.iter().collect(),
+                other => vec![other],
+            };
+            for resource in resources {
+                let Some(self_link) = resource
+                    .pointer("/_links/self/href")
+                    .and_then(Value::as_str)
+                else {
+                    continue;
+                };
+                let title = resource
+                    .pointer("/_links/self/title")
+                    .and_then(Value::as_str);
+                self.add(name, self_link, None, title, "hal");
+            }
+        }
+    }
+
+    /// JSON:API: top-level `links`, the primary resource's links and
+    /// relationship links, and each item's `self` link.
+    fn add_json_api(&mut self, object: &Map<String, Value>) {
+        if let Some(links) = object.get("links").and_then(Value::as_object) {
+            self.add_json_api_links(links, "");
+        }
+        match object.get("data") {
+            Some(Value::Object(resource)) => {
+                if let Some(links) = resource.get("links").and_then(Value::as_object) {
+                    self.add_json_api_links(links, "");
+                }
+                let relationships = resource
+                    .get("relationships")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten();
+                for (name, relationship) in relationships {
+                    if let Some(links) = relationship.get("links").and_then(Value::as_object) {
+                        self.add_json_api_links(links, &format!("{}.", name));
+                    }
+                }
+            }
+            Some(Value::Array(resources)) => {
+                for resource in resources {
+                    if let Some(href) = resource.pointer("/links/self") {
+                        let title = resource.get("id").and_then(Value::as_str);
+                        if let Some(href) = json_api_href(href) {
+                            self.add("item", href, None, title, "jsonapi");
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_json_api_links(&mut self, links: &Map<String, Value>, prefix: &str) {
+        for (rel, value) in links {
+            if let Some(href) = json_api_href(value) {
+                let title = value.get("title").and_then(Value::as_str);
+                self.add(&format!("{}{}", prefix, rel), href, None, title, "jsonapi");
+            }
+        }
+    }
+
+    /// Siren: `links: [{rel: [..], href}]` and `actions: [{name, method, href}]`.
+    fn add_siren(&mut self, links: &[Value], actions: &[Value]) {
+        for link in links {
+            let Some(href) = link.get("href").and_then(Value::as_str) else {
+                continue;
+            };
+            let rel = link
+                .get("rel")
+                .and_then(Value::as_array)
+                .map(|rels| {
+                    rels.iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            let title = link.get("title").and_then(Value::as_str);
+            self.add(&rel, href, None, title, "siren");
+        }
+        for action in actions {
+            let Some(href) = action.get("href").and_then(Value::as_str) else {
+                continue;
+            };
+            let name = action
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let method = action.get("method").and_then(Value::as_str);
+            let title = action.get("title").and_then(Value::as_str);
+            self.add(name, href, method, title, "siren");
+        }
+    }
+
+    /// `Link: <url>; rel="next", <url>; rel="prev"` (RFC 8288).
+    fn add_link_header(&mut self, value: &str) {
+        for link in value.split(',') {
+            let mut parts = link.split(';');
+            let Some(target) = parts
+                .next()
+                .and_then(|t| t.trim().strip_prefix('<'))
+                .and_then(|t| t.strip_suffix('>'))
+            else {
+                continue;
+            };
+            let mut rel = "";
+            let mut title = None;
+            for param in parts {
+                let Some((name, value)) = param.split_once('=') else {
+                    continue;
+                };
+                let value = value.trim().trim_matches('"');
+                match name.trim().to_ascii_lowercase().as_str() {
+                    "rel" => rel = value,
+                    "title" => title = Some(value),
+                    _ => {}
+                }
+            }
+            self.add(rel, target, None, title, "header");
+        }
+    }
+}
+
+/// JSON:API link values are either a URL string or `{href, meta}`.
+fn json_api_href(value: &Value) -> Option<&str> {
+    value
+        .as_str()
+        .or_else(|| value.get("href").and_then(Value::as_str))
+}
+
+/// Extracts the links advertised by a response body and headers.
+pub fn extract_links(
+    url: &str,
+    body: &Value,
+    headers: &HashMap<String, String>,
+) -> Vec<HypermediaLink> {
+    let mut collector = LinkCollector {
+        base: url::Url::parse(url).ok(),
+        links: Vec::new(),
+    };
+
+    let parsed;
+    let body = match body {
+        Value::String(text) => {
+            parsed = serde_json::from_str(text).unwrap_or(Value::Null);
+            &parsed
+        }
+        other => other,
+    };
+
+    if let Some(object) = body.as_object() {
+        collector.add_hal(object);
+        collector.add_hal_embedded(object);
+        match object.get("links") {
+            Some(Value::Array(links)) => {
+                let actions = object
+                    .get("actions")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                collector.add_siren(links, actions);
+            }
+            _ => collector.add_json_api(object),
+        }
+    }
+
+    for (name, value) in headers {
+        if name.eq_ignore_ascii_case("link") {
+            collector.add_link_header(value);
+        }
+    }
+
+    collector.links
+}
+
+/// Follow-up requests advertised by a response (hypermedia links).
+#[tauri::command]
+pub fn hypermedia_links(
+    url: String,
+    body: Value,
+    headers: HashMap<String, String>,
+) -> Vec<HypermediaLink> {
+    extract_links(&url, &body, &headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn extracts_hal_json_api_siren_and_header_links() {
+        let hal = json!({
+            "_links": {
+                "self": { "href": "/orders?page=2" },
+                "next": { "href": "/orders?page=3" },
+                "find": { "href": "/orders{?id}", "templated": true },
+                "curies": [{ "name": "ea", "href": "/docs/{rel}", "templated": true }],
+                "ea:cancel": { "href": "/orders/7/cancel", "method": "post", "title": "Cancel" }
+            },
+            "_embedded": { "orders": [{ "_links": { "self": { "href": "/orders/7" } } }] }
+        });
+        let links = extract_links("https://api.test/v1/orders", &hal, &HashMap::new());
+        let find = |rel: &str| links.iter().find(|l| l.rel == rel).unwrap();
+        assert_eq!(find("next").href, "https://api.test/orders?page=3");
+        assert_eq!(find("find").href, "https://api.test/orders{?id}");
+        assert!(find("find").templated);
+        assert_eq!(find("ea:cancel").method, "POST");
+        assert_eq!(find("orders").href, "https://api.test/orders/7");
+        assert!(links.iter().all(|l| l.rel != "curies"));
+
+        let json_api = json!({
+            "links": { "next": { "href": "articles?page[number]=2" } },
+            "data": {
+                "id": "1",
+                "links": { "self": "https://api.test/articles/1" },
+                "relationships": { "author": { "links": { "related": "/articles/1/author" } } }
+            }
+        });
+        let links = extract_links("https://api.test/articles", &json_api, &HashMap::new());
+        let rels: Vec<_> = links
+            .iter()
+            .map(|l| (l.rel.as_str(), l.source.as_str()))
+            .collect();
+        assert_eq!(
+            rels,
+            vec![
+                ("next", "jsonapi"),
+                ("self", "jsonapi"),
+                ("author.related", "jsonapi")
+            ]
+        );
+
+        let siren = json!({
+            "links": [{ "rel": ["self"], "href": "/orders/42" }],
+            "actions": [{ "name": "add-item", "method": "POST", "href": "/orders/42/items" }]
+        })
+        .to_string();
+        let headers = HashMap::from([(
+            "link".to_string(),
+            r#"<https://api.test/orders/43>; rel="next"; title="Next order""#.to_string(),
+        )]);
+        let links = extract_links(
+            "https://api.test/orders/42",
+            &Value::String(siren),
+            &headers,
+        );
+        assert_eq!(links.len(), 3);
+        assert_eq!(
+            (links[1].rel.as_str(), links[1].method.as_str()),
+            ("add-item", "POST")
+        );
+        assert_eq!(links[2].title.as_deref(), Some("Next order"));
+    }
+}
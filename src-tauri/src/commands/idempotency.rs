@@ -0,0 +1,177 @@
+//! Idempotency keys: an endpoint may carry an `idempotencyKey` setting in the
+//! collection file. Every send of the endpoint then gets a fresh key in the
+//! `Idempotency-Key` header (or the header the setting names), a UUID or one
+//! generated from a template such as `order-{{$timestamp}}`.
+//!
+//! The last key of each endpoint is remembered, so a request can be resent
+//! with the same key to test how a server treats the retry of a Stripe-style
+//! idempotent POST.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+use super::api_request::RequestOptions;
+use super::variables::VariableResolver;
+
+/// Field of an endpoint in the collection file holding its settings.
+const IDEMPOTENCY_FIELD: &str = "idempotencyKey";
+const DEFAULT_HEADER: &str = "Idempotency-Key";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdempotencySettings {
+    /// Header the key is sent in; `Idempotency-Key` when empty
+    #[serde(default)]
+    pub header: String,
+    /// Template the key is generated from, e.g. `{{$uuid}}`; a UUID when empty
+    #[serde(default)]
+    pub template: String,
+}
+
+impl IdempotencySettings {
+    fn header_name(&self) -> &str {
+        match self.header.trim() {
+            "" => DEFAULT_HEADER,
+            header => header,
+        }
+    }
+}
+
+/// The key a request went out with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentIdempotencyKey {
+    pub header: String,
+    pub key: String,
+    /// The endpoint's previous key was sent again
+    pub reused: bool,
+}
+
+/// The endpoint's idempotency key settings, when it sends keys.
+pub(crate) fn endpoint_idempotency(endpoint: &Value) -> Option<IdempotencySettings> {
+    serde_json::from_value(endpoint.get(IDEMPOTENCY_FIELD)?.clone()).ok()
+}
+
+/// A new key: the template resolved against `variables`, or a UUID.
+fn generate_key(template: &str, variables: HashMap<String, String>) -> String {
+    if template.trim().is_empty() {
+        return Uuid::new_v4().to_string();
+    }
+    VariableResolver::new(variables).resolve(template.trim())
+}
+
+/// The last key sent by each endpoint, keyed by collection and endpoint id.
+#[derive(Default)]
+pub struct IdempotencyKeys {
+    keys: Mutex<HashMap<(String, String), String>>,
+}
+
+impl IdempotencyKeys {
+    pub(crate) fn last(&self, collection_id: &str, endpoint_id: &str) -> Option<String> {
+        let keys = self.keys.lock().unwrap();
+        keys.get(&(collection_id.to_string(), endpoint_id.to_string()))
+            .cloned()
+    }
+
+    pub(crate) fn remember(&self, collection_id: &str, endpoint_id: &str, key: &str) {
+        let mut keys = self.keys.lock().unwrap();
+        keys.insert(
+            (collection_id.to_string(), endpoint_id.to_string()),
+            key.to_string(),
+        );
+    }
+}
+
+/// Give a request of the endpoint its key: the last one when the request
+/// asks to reuse it, a new one otherwise. The key is remembered for the next
+/// reuse.
+pub(crate) async fn assign_key(
+    app: &AppHandle,
+    keys: &IdempotencyKeys,
+    options: &mut RequestOptions,
+    settings: &IdempotencySettings,
+) -> Result<SentIdempotencyKey, String> {
+    let (Some(collection_id), Some(endpoint_id)) =
+        (options.collection_id.clone(), options.endpoint_id.clone())
+    else {
+        return Err("Idempotency keys need a request saved in a collection".to_string());
+    };
+    let key = if options.reuse_idempotency_key {
+        keys.last(&collection_id, &endpoint_id).ok_or_else(|| {
+            "No idempotency key was sent for this request yet; send it once first".to_string()
+        })?
+    } else {
+        let variables = if settings.template.trim().is_empty() {
+            HashMap::new()
+        } else {
+            super::runner::initial_run_variables(app, &collection_id, &options.variable_overrides)
+                .await
+        };
+        generate_key(&settings.template, variables)
+    };
+    keys.remember(&collection_id, &endpoint_id, &key);
+
+    let headers = options.headers.get_or_insert_with(HashMap::new);
+    Ok(SentIdempotencyKey {
+        header: inject(headers, settings, &key),
+        key,
+        reused: options.reuse_idempotency_key,
+    })
+}
+
+/// Set the key header on `headers`, replacing a header of the same name in
+/// any case, and return the name it was sent under.
+fn inject(
+    headers: &mut HashMap<String, String>,
+    settings: &IdempotencySettings,
+    key: &str,
+) -> String {
+    let name = settings.header_name().to_string();
+    headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&name));
+    headers.insert(name.clone(), key.to_string());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn generates_keys_and_remembers_the_last_one() {
+        let endpoint = json!({ "id": "e1", "idempotencyKey": { "template": "order-{{orderId}}" } });
+        let settings = endpoint_idempotency(&endpoint).unwrap();
+        assert_eq!(settings.header_name(), DEFAULT_HEADER);
+        assert!(endpoint_idempotency(&json!({ "id": "e2" })).is_none());
+
+        let variables = HashMap::from([("orderId".to_string(), "42".to_string())]);
+        assert_eq!(generate_key(&settings.template, variables), "order-42");
+        let uuid = generate_key("", HashMap::new());
+        assert!(Uuid::parse_str(&uuid).is_ok());
+        assert_ne!(uuid, generate_key("", HashMap::new()));
+
+        let mut headers = HashMap::from([("idempotency-key".to_string(), "old".to_string())]);
+        let name = inject(&mut headers, &settings, "order-42");
+        assert_eq!(name, DEFAULT_HEADER);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[DEFAULT_HEADER], "order-42");
+
+        let custom = IdempotencySettings {
+            header: "X-Request-Key".to_string(),
+            template: String::new(),
+        };
+        inject(&mut headers, &custom, "k");
+        assert_eq!(headers["X-Request-Key"], "k");
+
+        let keys = IdempotencyKeys::default();
+        assert_eq!(keys.last("c1", "e1"), None);
+        keys.remember("c1", "e1", "order-42");
+        keys.remember("c1", "e1", "order-43");
+        assert_eq!(keys.last("c1", "e1").as_deref(), Some("order-43"));
+        assert_eq!(keys.last("c1", "e2"), None);
+    }
+}
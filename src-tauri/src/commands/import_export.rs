@@ -1,4 +1,5 @@
-//! Collection import/export Tauri commands (OpenAPI & Postman) and shared models.
+//! Collection import/export Tauri commands (OpenAPI, Postman, HAR, `.http`
+//! files and curl commands, Markdown/HTML docs) and shared models.
 //!
 //! Parsing/serialization logic lives in the submodules; the command entry points
 //! stay here so their registration paths in `main.rs` remain stable.
@@ -7,18 +8,37 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::AppHandle;
+use tauri::{AppHandle, State};
 use tauri_plugin_dialog::{DialogExt, FilePath};
 use tokio::sync::oneshot;
 
+use crate::commands::endpoint_examples::ResponseExample;
+use crate::commands::proxy::ProxyState;
+
+mod curl;
+mod detect;
+mod docs;
 mod export;
+mod har;
+mod http_file;
 mod openapi;
 mod postman;
+mod postman_dump;
+mod shell_script;
 mod storage;
 
+pub(crate) use detect::detect_import_kind;
+pub(crate) use export::is_json_media_type;
+
+use curl::{parse_curl_command, CurlImport};
+use docs::{collection_to_html, collection_to_markdown};
 use export::{collection_to_openapi, collection_to_postman, load_collection_for_export};
+use har::parse_har;
+use http_file::{collection_to_http_file, parse_http_file};
 use openapi::parse_openapi_spec;
 use postman::parse_postman_collection;
+use postman_dump::{read_postman_dump, DumpEnvironment};
+use shell_script::collection_to_shell_script;
 use storage::{
     get_last_import_directory, pick_import_file_with_kind, save_collection_to_files,
     save_last_import_directory,
@@ -47,6 +67,9 @@ pub struct Collection {
 pub struct VariableEntry {
     pub key: String,
     pub value: String,
+    /// Secret variables keep their value in the secret store; exports mask them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,7 +91,14 @@ pub struct Endpoint {
     pub name: String,
     pub method: String,
     pub path: String,
+    /// Markdown documentation of the endpoint
     pub description: Option<String>,
+    /// Markdown showing how to use the endpoint (typical calls, caveats)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example_use: Option<String>,
+    /// Marked as deprecated in generated docs and OpenAPI exports
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
     /// Parameters grouped by location: { path: {...}, query: {...}, header: {...} }
     pub parameters: Option<Value>,
     pub request_body: Option<Value>,
@@ -76,6 +106,9 @@ pub struct Endpoint {
     /// Authentication configuration: { type: "bearer"|"basic"|"api-key"|"digest"|"oauth2", config: {...} }
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security: Option<Value>,
+    /// Free-form labels used to select endpoints for runs (`smoke`, `slow`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// Transient per-request scripts ({ preRequestScript, testScript }); persisted
     /// to the endpoint's data file, never serialized into collection.json.
     #[serde(skip_serializing, default)]
@@ -84,6 +117,10 @@ pub struct Endpoint {
     /// to the endpoint's data file, never serialized into collection.json.
     #[serde(skip_serializing, default)]
     pub graphql_data: Option<Value>,
+    /// Transient saved example responses; stored in the collection's
+    /// `examples/` directory, never serialized into collection.json.
+    #[serde(skip_serializing, default)]
+    pub examples: Vec<ResponseExample>,
 }
 
 #[tauri::command]
@@ -118,6 +155,38 @@ pub async fn import_openapi_file(
     Ok(Some(collection))
 }
 
+/// Import an OpenAPI spec served over HTTP(S), e.g. from a `resonance://import` link.
+#[tauri::command]
+pub async fn import_openapi_url(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    url: String,
+    storage_parent_path: Option<String>,
+) -> Result<Collection, String> {
+    proxy_state.check_offline(&url)?;
+    let response = proxy_state
+        .plain_client()?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: {}", url, response.status()));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+
+    let spec: Value = serde_yaml_ng::from_str(&content)
+        .map_err(|e| format!("Failed to parse OpenAPI spec: {}", e))?;
+    let collection = parse_openapi_spec(spec.clone())?;
+    save_collection_to_files(&app, &collection, storage_parent_path)?;
+    super::spec_sync::record_spec_source(&app, &collection.id, &url, spec)?;
+
+    Ok(collection)
+}
+
 #[tauri::command]
 pub async fn import_postman_collection(
     app: AppHandle,
@@ -150,6 +219,131 @@ pub async fn import_postman_collection(
     Ok(Some(collection))
 }
 
+/// Result of importing a Postman data dump.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostmanDumpImport {
+    /// Collections created from the dump
+    pub collections: Vec<Collection>,
+    /// Environments (and globals) for the frontend to create
+    pub environments: Vec<DumpEnvironment>,
+    /// Archive entries that were not imported, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Import every collection of a Postman data dump zip and return its
+/// environments and globals.
+#[tauri::command]
+pub async fn import_postman_dump(
+    app: AppHandle,
+    file_path: Option<String>,
+    storage_parent_path: Option<String>,
+) -> Result<Option<PostmanDumpImport>, String> {
+    let resolved_file_path = if let Some(file_path) = file_path {
+        let path = PathBuf::from(file_path);
+        save_last_import_directory(&app, &path);
+        path
+    } else {
+        let Some(path) = pick_import_file_with_kind(&app, "postman_dump").await? else {
+            return Ok(None);
+        };
+        path
+    };
+
+    let file = std::fs::File::open(&resolved_file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let dump = read_postman_dump(std::io::BufReader::new(file))?;
+
+    let mut skipped = dump.skipped;
+    let mut collections = Vec::new();
+    for (entry, document) in dump.collections {
+        match parse_postman_collection(document) {
+            Ok(collection) => {
+                save_collection_to_files(&app, &collection, storage_parent_path.clone())?;
+                collections.push(collection);
+            }
+            Err(e) => skipped.push(format!("{}: {}", entry, e)),
+        }
+    }
+
+    Ok(Some(PostmanDumpImport {
+        collections,
+        environments: dump.environments,
+        skipped,
+    }))
+}
+
+#[tauri::command]
+pub async fn import_har_file(
+    app: AppHandle,
+    file_path: Option<String>,
+    storage_parent_path: Option<String>,
+) -> Result<Option<Collection>, String> {
+    let resolved_file_path = if let Some(file_path) = file_path {
+        let path = PathBuf::from(file_path);
+        save_last_import_directory(&app, &path);
+        path
+    } else {
+        let Some(path) = pick_import_file_with_kind(&app, "har").await? else {
+            return Ok(None);
+        };
+        path
+    };
+
+    let content = std::fs::read_to_string(&resolved_file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let har: Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse HAR file: {}", e))?;
+
+    let name = resolved_file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported HAR".to_string());
+    let collection = parse_har(har, &name)?;
+
+    save_collection_to_files(&app, &collection, storage_parent_path)?;
+
+    Ok(Some(collection))
+}
+
+/// Import the requests of a VS Code REST Client / JetBrains `.http` file.
+#[tauri::command]
+pub async fn import_http_file(
+    app: AppHandle,
+    file_path: Option<String>,
+    storage_parent_path: Option<String>,
+) -> Result<Option<Collection>, String> {
+    let resolved_file_path = if let Some(file_path) = file_path {
+        let path = PathBuf::from(file_path);
+        save_last_import_directory(&app, &path);
+        path
+    } else {
+        let Some(path) = pick_import_file_with_kind(&app, "http").await? else {
+            return Ok(None);
+        };
+        path
+    };
+
+    let content = std::fs::read_to_string(&resolved_file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let name = resolved_file_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported Requests".to_string());
+    let collection = parse_http_file(&content, &name)?;
+
+    save_collection_to_files(&app, &collection, storage_parent_path)?;
+
+    Ok(Some(collection))
+}
+
+/// Parse a pasted curl command line into an endpoint, plus the form rows and
+/// proxy it uses. Nothing is saved; the caller adds it to a collection.
+#[tauri::command]
+pub fn import_curl(command: String) -> Result<CurlImport, String> {
+    parse_curl_command(&command)
+}
+
 #[tauri::command]
 pub async fn import_postman_environment(app: AppHandle) -> Result<Option<Value>, String> {
     let (tx, rx) = oneshot::channel::<Option<FilePath>>();
@@ -327,6 +521,168 @@ pub async fn export_postman(app: AppHandle, collection_id: String) -> Result<Val
     }))
 }
 
+/// Export a collection as an `.http` file for REST Client / JetBrains HTTP
+/// Client, e.g. to keep its requests next to the code.
+#[tauri::command]
+pub async fn export_http_file(app: AppHandle, collection_id: String) -> Result<Value, String> {
+    let collection = load_collection_for_export(&app, &collection_id)?;
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_file_name(format!("{}.http", collection.name))
+        .add_filter("HTTP Request Files", &["http", "rest"]);
+
+    if let Some(last_dir) = get_last_import_directory(&app) {
+        dialog = dialog.set_directory(last_dir);
+    }
+
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path);
+    });
+
+    let file_path = rx.await.map_err(|e| format!("Dialog error: {}", e))?;
+
+    let Some(path) = file_path else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+
+    let (content, skipped) = collection_to_http_file(&collection);
+
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    save_last_import_directory(&app, file_path);
+
+    std::fs::write(file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+        "skipped": {
+            "count": skipped.len(),
+            "items": skipped
+        }
+    }))
+}
+
+/// Exports a collection, or one folder of it, as a standalone bash script of
+/// `curl` calls for reproducing requests without the app.
+#[tauri::command]
+pub async fn export_shell_script(
+    app: AppHandle,
+    collection_id: String,
+    folder_id: Option<String>,
+) -> Result<Value, String> {
+    let collection = load_collection_for_export(&app, &collection_id)?;
+    let (content, skipped) = collection_to_shell_script(&collection, folder_id.as_deref())?;
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_file_name(format!("{}.sh", collection.name))
+        .add_filter("Shell Scripts", &["sh"]);
+
+    if let Some(last_dir) = get_last_import_directory(&app) {
+        dialog = dialog.set_directory(last_dir);
+    }
+
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path);
+    });
+
+    let file_path = rx.await.map_err(|e| format!("Dialog error: {}", e))?;
+
+    let Some(path) = file_path else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    save_last_import_directory(&app, file_path);
+
+    std::fs::write(file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(file_path, std::fs::Permissions::from_mode(0o755));
+    }
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+        "skipped": {
+            "count": skipped.len(),
+            "items": skipped
+        }
+    }))
+}
+
+/// Export a collection as API documentation: Markdown by default, or a
+/// self-contained static HTML page when `format` is `"html"`.
+#[tauri::command]
+pub async fn export_markdown(
+    app: AppHandle,
+    collection_id: String,
+    format: Option<String>,
+) -> Result<Value, String> {
+    let collection = load_collection_for_export(&app, &collection_id)?;
+    let html = format.as_deref() == Some("html");
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+
+    let (file_ext, filter_name) = if html {
+        ("html", "HTML Files")
+    } else {
+        ("md", "Markdown Files")
+    };
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_file_name(format!("{}.{}", collection.name, file_ext))
+        .add_filter(filter_name, &[file_ext]);
+
+    if let Some(last_dir) = get_last_import_directory(&app) {
+        dialog = dialog.set_directory(last_dir);
+    }
+
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path);
+    });
+
+    let file_path = rx.await.map_err(|e| format!("Dialog error: {}", e))?;
+
+    let Some(path) = file_path else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+
+    let (content, skipped) = if html {
+        collection_to_html(&collection)
+    } else {
+        collection_to_markdown(&collection)
+    };
+
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    save_last_import_directory(&app, file_path);
+
+    std::fs::write(file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+        "format": if html { "html" } else { "markdown" },
+        "skipped": {
+            "count": skipped.len(),
+            "items": skipped
+        }
+    }))
+}
+
 #[tauri::command]
 pub async fn save_json_export(
     app: AppHandle,
@@ -380,6 +736,7 @@ pub async fn save_documentation(
     let (filter_name, extensions): (&str, &[&str]) = match mime_type.as_str() {
         "text/html" => ("HTML Files", &["html"]),
         "text/markdown" => ("Markdown Files", &["md"]),
+        "application/xml" => ("XML Files", &["xml"]),
         _ => ("All Files", &["*"]),
     };
 
@@ -0,0 +1,761 @@
+//! Pasted `curl` command lines (terminal history, API docs, "Copy as cURL")
+//! turned into an endpoint.
+//!
+//! The command is split like a POSIX shell would (quotes, `$'...'`, escapes and
+//! line continuations). Options that shape the request are mapped onto the
+//! endpoint; transfer options (`-s`, `-o`, `--retry`, ...) are listed as ignored.
+
+use super::Endpoint;
+use crate::commands::proxy::{ProxyAuth, ProxyOverride, ProxyServer};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Result of parsing a curl command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurlImport {
+    pub endpoint: Endpoint,
+    /// Body in the request editor's `formBodyData` shape, for form fields and
+    /// file uploads (`-F`, `--data-binary @file`, `-T`) that the endpoint's
+    /// `requestBody` example cannot carry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_body_data: Option<Value>,
+    /// Proxy from `-x`/`--socks5`, as a collection proxy override
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyOverride>,
+    /// Options that have no equivalent in a saved request
+    pub ignored: Vec<String>,
+}
+
+/// Options that take a value but don't change the saved request.
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "-w",
+    "--write-out",
+    "--cacert",
+    "--capath",
+    "-E",
+    "--cert",
+    "--key",
+    "--cert-type",
+    "--key-type",
+    "--resolve",
+    "--connect-to",
+    "-c",
+    "--cookie-jar",
+    "-D",
+    "--dump-header",
+    "--limit-rate",
+    "--max-redirs",
+    "--interface",
+    "-K",
+    "--config",
+    "--trace",
+    "--trace-ascii",
+    "--stderr",
+];
+
+/// Short flags without a value, also accepted when combined (`-sSL`).
+const SHORT_FLAGS: &str = "sSLkvifNIGq#0123467";
+
+enum Body {
+    None,
+    Text(String),
+    Binary(String),
+    Form(Vec<Value>),
+}
+
+#[derive(Default)]
+struct ProxyOption {
+    url: Option<String>,
+    socks: Option<&'static str>,
+    user: Option<String>,
+}
+
+/// Splits a command line into words: single quotes, double quotes with
+/// backslash escapes, ANSI-C `$'...'` strings and `\`-newline continuations.
+/// cmd.exe's `^` continuations from "Copy as cURL (cmd)" are joined too.
+fn split_words(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '^' if matches!(chars.peek(), Some('\n') | Some('\r')) => {
+                while matches!(chars.peek(), Some('\n') | Some('\r')) {
+                    chars.next();
+                }
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                    in_word = true;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => current.push('\n'),
+                            Some('t') => current.push('\t'),
+                            Some('r') => current.push('\r'),
+                            Some(c) => current.push(c),
+                            None => return Err("Unterminated $'...' string".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated $'...' string".to_string()),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+/// Expands combined short flags (`-sSL`) and attached values (`-XPOST`,
+/// `-HAccept:x`) into separate words.
+fn expand_short_options(words: Vec<String>) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut after_value_option = false;
+    for word in words {
+        let is_short = word.len() > 2 && word.starts_with('-') && !word.starts_with("--");
+        if after_value_option || !is_short {
+            after_value_option = takes_value(&word);
+            out.push(word);
+            continue;
+        }
+        let mut rest = &word[1..];
+        while let Some(flag) = rest.chars().next() {
+            rest = &rest[flag.len_utf8()..];
+            let option = format!("-{}", flag);
+            if takes_value(&option) {
+                out.push(option);
+                if rest.is_empty() {
+                    after_value_option = true;
+                } else {
+                    out.push(rest.to_string());
+                }
+                break;
+            }
+            out.push(option);
+            if !SHORT_FLAGS.contains(flag) {
+                break;
+            }
+        }
+    }
+    out
+}
+
+fn takes_value(option: &str) -> bool {
+    matches!(
+        option,
+        "-X" | "--request"
+            | "-H"
+            | "--header"
+            | "-d"
+            | "--data"
+            | "--data-ascii"
+            | "--data-raw"
+            | "--data-binary"
+            | "--data-urlencode"
+            | "--json"
+            | "-F"
+            | "--form"
+            | "--form-string"
+            | "-u"
+            | "--user"
+            | "--oauth2-bearer"
+            | "-A"
+            | "--user-agent"
+            | "-e"
+            | "--referer"
+            | "-b"
+            | "--cookie"
+            | "-x"
+            | "--proxy"
+            | "-U"
+            | "--proxy-user"
+            | "--socks4"
+            | "--socks4a"
+            | "--socks5"
+            | "--socks5-hostname"
+            | "-T"
+            | "--upload-file"
+            | "--url"
+    ) || IGNORED_WITH_VALUE.contains(&option)
+}
+
+/// Percent-encodes a `--data-urlencode` value the way curl does.
+fn url_encode(text: &str) -> String {
+    url::form_urlencoded::byte_serialize(text.as_bytes()).collect()
+}
+
+/// `name=content`, `=content`, `content` or `name@file` per curl's
+/// `--data-urlencode` rules; files are not read at import time.
+fn data_urlencode(value: &str) -> String {
+    match value.split_once('=') {
+        Some(("", content)) => url_encode(content),
+        Some((name, content)) => format!("{}={}", name, url_encode(content)),
+        None => url_encode(value),
+    }
+}
+
+/// One `-F` part as a form row; `@file` becomes a file upload and `<file`
+/// (file content as a text field) a file part too, since the file is not read.
+fn form_row(value: &str, literal: bool) -> Result<Value, String> {
+    let (key, content) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid form field '{}': expected name=value", value))?;
+    let mut row = Map::new();
+    row.insert("key".into(), Value::String(key.to_string()));
+    row.insert("enabled".into(), Value::Bool(true));
+    let file = (!literal)
+        .then(|| {
+            content
+                .strip_prefix('@')
+                .or_else(|| content.strip_prefix('<'))
+        })
+        .flatten();
+    match file {
+        Some(spec) => {
+            // `@path;type=image/png;filename=x.png`
+            let mut parts = spec.split(';');
+            let path = parts.next().unwrap_or_default().trim_matches('"');
+            row.insert("type".into(), Value::String("file".to_string()));
+            row.insert("value".into(), Value::String(String::new()));
+            row.insert("filePath".into(), Value::String(path.to_string()));
+            for part in parts {
+                if let Some(mime) = part.trim().strip_prefix("type=") {
+                    row.insert("contentType".into(), Value::String(mime.to_string()));
+                }
+            }
+        }
+        None => {
+            row.insert("type".into(), Value::String("text".to_string()));
+            row.insert("value".into(), Value::String(content.to_string()));
+        }
+    }
+    Ok(Value::Object(row))
+}
+
+/// A collection proxy override from `-x`/`--socks5` and `--proxy-user`.
+fn proxy_override(option: ProxyOption) -> Result<Option<ProxyOverride>, String> {
+    let Some(mut raw) = option.url else {
+        return Ok(None);
+    };
+    if let Some(scheme) = option.socks {
+        raw = format!("{}://{}", scheme, raw);
+    } else if !raw.contains("://") {
+        raw = format!("http://{}", raw);
+    }
+    let url = url::Url::parse(&raw).map_err(|e| format!("Invalid proxy '{}': {}", raw, e))?;
+    let proxy_type = match url.scheme() {
+        "socks5h" | "socks5" => "socks5",
+        "socks4" | "socks4a" => "socks4",
+        "https" => "https",
+        _ => "http",
+    };
+    // curl's default proxy port, except for HTTPS proxies
+    let default_port = if proxy_type == "https" { 443 } else { 1080 };
+    let (username, password) = match option.user {
+        Some(user) => {
+            let (u, p) = user.split_once(':').unwrap_or((user.as_str(), ""));
+            (u.to_string(), p.to_string())
+        }
+        None => (
+            url.username().to_string(),
+            url.password().unwrap_or_default().to_string(),
+        ),
+    };
+    Ok(Some(ProxyOverride {
+        mode: "custom".to_string(),
+        server: Some(ProxyServer {
+            proxy_type: proxy_type.to_string(),
+            host: url.host_str().unwrap_or_default().to_string(),
+            port: url.port().unwrap_or(default_port),
+        }),
+        auth: ProxyAuth {
+            enabled: !username.is_empty(),
+            username,
+            password,
+        },
+        bypass_list: Vec::new(),
+    }))
+}
+
+/// `Get User Orders`-style name from the last path segment.
+fn request_name(method: &str, path: &str) -> String {
+    let path = path
+        .split_once("://")
+        .map(|(_, rest)| rest.find('/').map(|i| &rest[i..]).unwrap_or(""))
+        .unwrap_or(path);
+    let segment = path
+        .trim_matches('/')
+        .rsplit('/')
+        .find(|s| !s.is_empty())
+        .unwrap_or_default();
+    if segment.is_empty() {
+        return format!("{} Request", method);
+    }
+    let words: Vec<String> = segment
+        .split(['-', '_'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect();
+    format!("{} {}", method, words.join(" "))
+}
+
+fn header_param(value: &str) -> Value {
+    serde_json::json!({ "example": value })
+}
+
+fn value_of(option: &str, words: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    words
+        .next()
+        .ok_or_else(|| format!("Option {} needs a value", option))
+}
+
+/// Parses a curl command line into an endpoint.
+pub(crate) fn parse_curl_command(command: &str) -> Result<CurlImport, String> {
+    let words = expand_short_options(split_words(command.trim())?);
+    let mut words = words.into_iter();
+    match words.next() {
+        Some(first) if first == "curl" || first.ends_with("/curl") || first == "curl.exe" => {}
+        _ => return Err("Not a curl command: it must start with 'curl'".to_string()),
+    }
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut body = Body::None;
+    let mut form: Vec<Value> = Vec::new();
+    let mut security: Option<Value> = None;
+    let mut user: Option<String> = None;
+    let mut digest = false;
+    let mut get = false;
+    let mut head = false;
+    let mut proxy = ProxyOption::default();
+    let mut ignored = Vec::new();
+
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "-X" | "--request" => method = Some(value_of(&word, &mut words)?.to_uppercase()),
+            "-H" | "--header" => {
+                let header = value_of(&word, &mut words)?;
+                if let Some((name, value)) = header.split_once(':') {
+                    headers.push((name.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => {
+                let value = value_of(&word, &mut words)?;
+                match value.strip_prefix('@') {
+                    Some(path) if word == "--data-binary" || data.is_empty() => {
+                        body = Body::Binary(path.to_string())
+                    }
+                    _ => data.push(value),
+                }
+            }
+            "--data-raw" => data.push(value_of(&word, &mut words)?),
+            "--data-urlencode" => data.push(data_urlencode(&value_of(&word, &mut words)?)),
+            "--json" => {
+                data.push(value_of(&word, &mut words)?);
+                for (name, value) in [
+                    ("Content-Type", "application/json"),
+                    ("Accept", "application/json"),
+                ] {
+                    if !headers.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)) {
+                        headers.push((name.to_string(), value.to_string()));
+                    }
+                }
+            }
+            "-F" | "--form" => form.push(form_row(&value_of(&word, &mut words)?, false)?),
+            "--form-string" => form.push(form_row(&value_of(&word, &mut words)?, true)?),
+            "-T" | "--upload-file" => {
+                body = Body::Binary(value_of(&word, &mut words)?);
+                method.get_or_insert_with(|| "PUT".to_string());
+            }
+            "-u" | "--user" => user = Some(value_of(&word, &mut words)?),
+            "--digest" => digest = true,
+            "--basic" => digest = false,
+            "--oauth2-bearer" => {
+                security = Some(serde_json::json!({
+                    "type": "bearer",
+                    "config": { "token": value_of(&word, &mut words)? }
+                }))
+            }
+            "-A" | "--user-agent" => {
+                headers.push(("User-Agent".to_string(), value_of(&word, &mut words)?))
+            }
+            "-e" | "--referer" => {
+                headers.push(("Referer".to_string(), value_of(&word, &mut words)?))
+            }
+            "-b" | "--cookie" => {
+                let cookie = value_of(&word, &mut words)?;
+                if cookie.contains('=') {
+                    headers.push(("Cookie".to_string(), cookie));
+                } else {
+                    ignored.push(format!("{} {} (cookie file)", word, cookie));
+                }
+            }
+            "--compressed" => {
+                if !headers
+                    .iter()
+                    .any(|(n, _)| n.eq_ignore_ascii_case("accept-encoding"))
+                {
+                    headers.push((
+                        "Accept-Encoding".to_string(),
+                        "gzip, deflate, br".to_string(),
+                    ));
+                }
+            }
+            "-x" | "--proxy" => proxy.url = Some(value_of(&word, &mut words)?),
+            "-U" | "--proxy-user" => proxy.user = Some(value_of(&word, &mut words)?),
+            "--socks4" | "--socks4a" => {
+                proxy.url = Some(value_of(&word, &mut words)?);
+                proxy.socks = Some("socks4");
+            }
+            "--socks5" | "--socks5-hostname" => {
+                proxy.url = Some(value_of(&word, &mut words)?);
+                proxy.socks = Some("socks5");
+            }
+            "-G" | "--get" => get = true,
+            "-I" | "--head" => head = true,
+            "--url" => url = Some(value_of(&word, &mut words)?),
+            option if IGNORED_WITH_VALUE.contains(&option) => {
+                let value = value_of(&word, &mut words)?;
+                ignored.push(format!("{} {}", option, value));
+            }
+            option if option.starts_with('-') && option.len() > 1 => {
+                ignored.push(option.to_string());
+            }
+            _ => {
+                if url.is_none() {
+                    url = Some(word);
+                } else {
+                    ignored.push(word);
+                }
+            }
+        }
+    }
+
+    let url = url.ok_or("No URL found in the curl command")?;
+    // curl assumes http:// for scheme-less URLs
+    let url = if url.contains("://") || url.starts_with("{{") {
+        url
+    } else {
+        format!("http://{}", url)
+    };
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+    let mut query = query.to_string();
+
+    if get && !data.is_empty() {
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(&data.join("&"));
+        data.clear();
+    }
+    if !data.is_empty() {
+        body = Body::Text(data.join("&"));
+    } else if !form.is_empty() {
+        body = Body::Form(form);
+    }
+
+    let has_body = !matches!(body, Body::None);
+    let method = method.unwrap_or_else(|| {
+        if head {
+            "HEAD".to_string()
+        } else if has_body {
+            "POST".to_string()
+        } else {
+            "GET".to_string()
+        }
+    });
+
+    if let Some(user) = user {
+        let (username, password) = user.split_once(':').unwrap_or((user.as_str(), ""));
+        security = Some(serde_json::json!({
+            "type": if digest { "digest" } else { "basic" },
+            "config": { "username": username, "password": password }
+        }));
+    }
+
+    let content_type = headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case("content-type"))
+        .map(|(_, v)| v.clone());
+    let (request_body, form_body_data) = match body {
+        Body::None => (None, None),
+        Body::Text(text) => {
+            let is_form = content_type.is_none()
+                && text.contains('=')
+                && serde_json::from_str::<Value>(&text).is_err();
+            let content_type = content_type.unwrap_or_else(|| {
+                if is_form {
+                    "application/x-www-form-urlencoded".to_string()
+                } else {
+                    "application/json".to_string()
+                }
+            });
+            (
+                Some(serde_json::json!({ "contentType": content_type, "example": text })),
+                None,
+            )
+        }
+        Body::Binary(path) => {
+            let mut binary = Map::new();
+            binary.insert("mode".into(), Value::String("binary".to_string()));
+            binary.insert("filePath".into(), Value::String(path));
+            if let Some(content_type) = content_type {
+                binary.insert("contentType".into(), Value::String(content_type));
+            }
+            (None, Some(Value::Object(binary)))
+        }
+        Body::Form(rows) => {
+            // Text fields also go into the endpoint so exports keep them
+            let fields: Map<String, Value> = rows
+                .iter()
+                .filter(|row| row.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|row| {
+                    Some((
+                        row.get("key")?.as_str()?.to_string(),
+                        row.get("value")?.clone(),
+                    ))
+                })
+                .collect();
+            (
+                (!fields.is_empty())
+                    .then(|| serde_json::json!({ "type": "formdata", "fields": fields })),
+                Some(serde_json::json!({ "mode": "formdata", "fields": rows })),
+            )
+        }
+    };
+
+    let mut parameters = Map::new();
+    let query_params: Map<String, Value> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), serde_json::json!({ "example": value }))
+        })
+        .collect();
+    if !query_params.is_empty() {
+        parameters.insert("query".to_string(), Value::Object(query_params));
+    }
+    let header_params: Map<String, Value> = headers
+        .iter()
+        // Form bodies get their Content-Type (with boundary) when sent
+        .filter(|(name, _)| {
+            !(form_body_data.is_some() && name.eq_ignore_ascii_case("content-type"))
+        })
+        .map(|(name, value)| (name.clone(), header_param(value)))
+        .collect();
+    if !header_params.is_empty() {
+        parameters.insert("header".to_string(), Value::Object(header_params));
+    }
+
+    let endpoint = Endpoint {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: request_name(&method, path),
+        method,
+        path: path.to_string(),
+        description: Some("Imported from cURL".to_string()),
+        example_use: None,
+        deprecated: false,
+        parameters: (!parameters.is_empty()).then_some(Value::Object(parameters)),
+        request_body,
+        responses: None,
+        security,
+        tags: Vec::new(),
+        scripts: None,
+        graphql_data: None,
+        examples: Vec::new(),
+    };
+
+    Ok(CurlImport {
+        endpoint,
+        form_body_data,
+        proxy: proxy_override(proxy)?,
+        ignored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_a_copied_browser_request() {
+        let import = parse_curl_command(
+            "curl 'https://api.test/v1/user-orders?page=2&sort=desc' \\\n  -H 'Accept: application/json' \\\n  -H $'X-Note: it\\'s' \\\n  --data-raw '{\"a\":1}' \\\n  --compressed -sSL",
+        )
+        .unwrap();
+        let endpoint = import.endpoint;
+        assert_eq!(endpoint.method, "POST");
+        assert_eq!(endpoint.name, "POST User Orders");
+        assert_eq!(endpoint.path, "https://api.test/v1/user-orders");
+        let parameters = endpoint.parameters.unwrap();
+        assert_eq!(parameters["query"]["page"]["example"], "2");
+        assert_eq!(
+            parameters["header"]["Accept"]["example"],
+            "application/json"
+        );
+        assert_eq!(parameters["header"]["X-Note"]["example"], "it's");
+        assert_eq!(
+            parameters["header"]["Accept-Encoding"]["example"],
+            "gzip, deflate, br"
+        );
+        assert_eq!(
+            endpoint.request_body,
+            Some(json!({ "contentType": "application/json", "example": "{\"a\":1}" }))
+        );
+        assert_eq!(import.ignored, vec!["-s", "-S", "-L"]);
+    }
+
+    #[test]
+    fn maps_auth_cookies_and_explicit_method() {
+        let import = parse_curl_command(
+            "curl -XDELETE -u admin:s3cr3t --digest -b 'sid=1; theme=dark' \"http://localhost:8080/items/{{id}}\"",
+        )
+        .unwrap();
+        let endpoint = import.endpoint;
+        assert_eq!(endpoint.method, "DELETE");
+        assert_eq!(
+            endpoint.security,
+            Some(
+                json!({ "type": "digest", "config": { "username": "admin", "password": "s3cr3t" } })
+            )
+        );
+        assert_eq!(
+            endpoint.parameters.unwrap()["header"]["Cookie"]["example"],
+            "sid=1; theme=dark"
+        );
+    }
+
+    #[test]
+    fn keeps_multipart_files_as_form_rows() {
+        let import = parse_curl_command(
+            "curl -F 'title=Report' -F 'file=@/tmp/report.pdf;type=application/pdf' -H 'Content-Type: multipart/form-data' https://up.test/files",
+        )
+        .unwrap();
+        assert_eq!(import.endpoint.method, "POST");
+        assert_eq!(
+            import.endpoint.request_body,
+            Some(json!({ "type": "formdata", "fields": { "title": "Report" } }))
+        );
+        assert!(import.endpoint.parameters.is_none());
+        let rows = &import.form_body_data.unwrap()["fields"];
+        assert_eq!(rows[1]["type"], "file");
+        assert_eq!(rows[1]["filePath"], "/tmp/report.pdf");
+        assert_eq!(rows[1]["contentType"], "application/pdf");
+    }
+
+    #[test]
+    fn data_with_get_goes_to_the_query() {
+        let import =
+            parse_curl_command("curl -G --data-urlencode 'q=a b' -d limit=5 api.test/search")
+                .unwrap();
+        assert_eq!(import.endpoint.method, "GET");
+        assert_eq!(import.endpoint.path, "http://api.test/search");
+        assert!(import.endpoint.request_body.is_none());
+        let query = &import.endpoint.parameters.unwrap()["query"];
+        assert_eq!(query["q"]["example"], "a+b");
+        assert_eq!(query["limit"]["example"], "5");
+    }
+
+    #[test]
+    fn reads_proxy_and_uploads() {
+        let import = parse_curl_command(
+            "curl -x socks5h://proxy.local -U bob:pw -T ./big.bin https://files.test/put",
+        )
+        .unwrap();
+        assert_eq!(import.endpoint.method, "PUT");
+        assert_eq!(
+            import.form_body_data,
+            Some(json!({ "mode": "binary", "filePath": "./big.bin" }))
+        );
+        let proxy = import.proxy.unwrap();
+        assert_eq!(proxy.mode, "custom");
+        let server = proxy.server.unwrap();
+        assert_eq!(
+            (
+                server.proxy_type.as_str(),
+                server.host.as_str(),
+                server.port
+            ),
+            ("socks5", "proxy.local", 1080)
+        );
+        assert!(proxy.auth.enabled);
+        assert_eq!(proxy.auth.username, "bob");
+    }
+
+    #[test]
+    fn rejects_non_curl_and_unterminated_input() {
+        assert!(parse_curl_command("wget https://x.test").is_err());
+        assert!(parse_curl_command("curl 'https://x.test").is_err());
+        assert!(parse_curl_command("curl -s").is_err());
+    }
+}
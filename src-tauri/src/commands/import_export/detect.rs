@@ -0,0 +1,80 @@
+//! Detection of the import format of a file opened from outside the app
+//! (command line, file manager "Open with").
+
+use serde_json::Value;
+use std::path::Path;
+
+use crate::commands::request_bundle::BUNDLE_MARKER;
+
+/// Import format of a file: "openapi", "postman", "postman_dump",
+/// "request_bundle", "har", "http" or "proto".
+pub(crate) fn detect_import_kind(path: &Path) -> Result<&'static str, String> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "proto" => return Ok("proto"),
+        "http" | "rest" => return Ok("http"),
+        "zip" => return Ok("postman_dump"),
+        _ => {}
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    // YAML parsing also covers JSON
+    let document: Value = serde_yaml_ng::from_str(&content).unwrap_or(Value::Null);
+    detect_document_kind(&document).ok_or_else(|| {
+        format!(
+            "{} is not an OpenAPI spec, Postman collection, HAR, .http or proto file",
+            path.display()
+        )
+    })
+}
+
+fn detect_document_kind(document: &Value) -> Option<&'static str> {
+    if document.get(BUNDLE_MARKER).is_some() {
+        return Some("request_bundle");
+    }
+    if document.get("openapi").is_some() || document.get("swagger").is_some() {
+        return Some("openapi");
+    }
+    if document
+        .pointer("/log/entries")
+        .is_some_and(Value::is_array)
+    {
+        return Some("har");
+    }
+    let info = document.get("info")?;
+    let postman_schema = info
+        .get("schema")
+        .and_then(|v| v.as_str())
+        .is_some_and(|schema| schema.contains("getpostman.com"));
+    if postman_schema || info.get("_postman_id").is_some() {
+        return Some("postman");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_document_kinds() {
+        let kind = |text: &str| detect_document_kind(&serde_yaml_ng::from_str(text).unwrap());
+        assert_eq!(
+            kind("openapi: 3.0.0\ninfo:\n  title: Pets\n"),
+            Some("openapi")
+        );
+        assert_eq!(kind(r#"{"swagger": "2.0"}"#), Some("openapi"));
+        assert_eq!(kind(r#"{"log": {"entries": []}}"#), Some("har"));
+        assert_eq!(
+            kind(
+                r#"{"info": {"name": "API", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"}, "item": []}"#
+            ),
+            Some("postman")
+        );
+        assert_eq!(kind(r#"{"name": "package", "version": "1.0.0"}"#), None);
+    }
+}
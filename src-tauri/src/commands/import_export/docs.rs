@@ -0,0 +1,732 @@
+//! Shareable API documentation for a collection, as a Markdown file or a
+//! self-contained static HTML page.
+//!
+//! Endpoint descriptions and example use are written in Markdown; the HTML
+//! page renders the common subset (headings, paragraphs, lists, fenced code,
+//! inline code, bold and links) and escapes everything else.
+
+use super::http_file::example_text;
+use super::storage::is_http_method;
+use super::{Collection, Endpoint};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Parameter locations in the order they are documented.
+const PARAMETER_LOCATIONS: [(&str, &str); 3] = [
+    ("path", "Path parameters"),
+    ("query", "Query parameters"),
+    ("header", "Headers"),
+];
+
+struct ParameterRow {
+    name: String,
+    required: bool,
+    description: String,
+    example: String,
+}
+
+struct ResponseRow {
+    status: String,
+    description: String,
+}
+
+/// The documented parts of one endpoint, shared by both output formats.
+struct EndpointDoc<'a> {
+    endpoint: &'a Endpoint,
+    anchor: String,
+    parameters: Vec<(&'static str, Vec<ParameterRow>)>,
+    body: Option<String>,
+    responses: Vec<ResponseRow>,
+}
+
+struct Section<'a> {
+    title: Option<&'a str>,
+    endpoints: Vec<EndpointDoc<'a>>,
+}
+
+/// A GitHub-style heading anchor, made unique within the document.
+fn anchor(text: &str, used: &mut HashSet<String>) -> String {
+    let base: String = text
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            ' ' => Some('-'),
+            _ => None,
+        })
+        .collect();
+    let mut candidate = base.clone();
+    let mut n = 1;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{}-{}", base, n);
+        n += 1;
+    }
+    candidate
+}
+
+fn parameter_rows(endpoint: &Endpoint, location: &str) -> Vec<ParameterRow> {
+    endpoint
+        .parameters
+        .as_ref()
+        .and_then(|p| p.get(location))
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, param)| ParameterRow {
+            name: name.clone(),
+            // Path parameters are always required
+            required: location == "path"
+                || param.get("required").and_then(Value::as_bool) == Some(true),
+            description: param
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            example: example_text(param),
+        })
+        .collect()
+}
+
+fn response_rows(endpoint: &Endpoint) -> Vec<ResponseRow> {
+    let mut rows: Vec<ResponseRow> = endpoint
+        .responses
+        .iter()
+        .flatten()
+        .map(|(status, response)| ResponseRow {
+            status: status.clone(),
+            description: response
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect();
+    for example in &endpoint.examples {
+        let status = example.status.to_string();
+        if !rows.iter().any(|row| row.status == status) {
+            rows.push(ResponseRow {
+                status,
+                description: example.status_text.clone(),
+            });
+        }
+    }
+    rows.sort_by(|a, b| a.status.cmp(&b.status));
+    rows
+}
+
+fn endpoint_doc<'a>(endpoint: &'a Endpoint, used: &mut HashSet<String>) -> EndpointDoc<'a> {
+    let parameters = PARAMETER_LOCATIONS
+        .iter()
+        .map(|(location, title)| (*title, parameter_rows(endpoint, location)))
+        .filter(|(_, rows)| !rows.is_empty())
+        .collect();
+    let body = endpoint
+        .request_body
+        .as_ref()
+        .and_then(|body| body.get("example"))
+        .map(example_text)
+        .filter(|text| !text.trim().is_empty());
+    EndpointDoc {
+        endpoint,
+        anchor: anchor(&endpoint.name, used),
+        parameters,
+        body,
+        responses: response_rows(endpoint),
+    }
+}
+
+/// The documented HTTP endpoints of one section; other requests are added to
+/// `skipped`.
+fn section_docs<'a>(
+    title: Option<&str>,
+    endpoints: impl Iterator<Item = &'a Endpoint>,
+    used: &mut HashSet<String>,
+    skipped: &mut Vec<String>,
+) -> Vec<EndpointDoc<'a>> {
+    let mut docs = Vec::new();
+    for endpoint in endpoints {
+        if !is_http_method(&endpoint.method) {
+            skipped.push(match title {
+                Some(title) => format!("{}/{}", title, endpoint.name),
+                None => endpoint.name.clone(),
+            });
+            continue;
+        }
+        docs.push(endpoint_doc(endpoint, used));
+    }
+    docs
+}
+
+/// Groups the HTTP endpoints into the collection's root requests followed by
+/// one section per folder. Returns the sections and the skipped requests.
+fn sections(collection: &Collection) -> (Vec<Section<'_>>, Vec<String>) {
+    let folder_endpoint_ids: HashSet<&str> = collection
+        .folders
+        .iter()
+        .flat_map(|f| f.endpoints.iter().map(|e| e.id.as_str()))
+        .collect();
+    let mut used = HashSet::new();
+    let mut skipped = Vec::new();
+
+    let root = collection
+        .endpoints
+        .iter()
+        .filter(|e| !folder_endpoint_ids.contains(e.id.as_str()));
+    let mut sections = vec![Section {
+        title: None,
+        endpoints: section_docs(None, root, &mut used, &mut skipped),
+    }];
+    for folder in &collection.folders {
+        sections.push(Section {
+            title: Some(&folder.name),
+            endpoints: section_docs(
+                Some(&folder.name),
+                folder.endpoints.iter(),
+                &mut used,
+                &mut skipped,
+            ),
+        });
+    }
+    sections.retain(|section| !section.endpoints.is_empty());
+    (sections, skipped)
+}
+
+fn request_line(collection: &Collection, endpoint: &Endpoint) -> String {
+    let url = match collection.base_url.as_deref() {
+        Some(base_url) if endpoint.path.starts_with('/') => {
+            format!("{}{}", base_url.trim_end_matches('/'), endpoint.path)
+        }
+        _ => endpoint.path.clone(),
+    };
+    format!("{} {}", endpoint.method.to_ascii_uppercase(), url)
+}
+
+/// Escapes `|` and line breaks so a value fits in one Markdown table cell.
+fn table_cell(text: &str) -> String {
+    let cell = text.replace('|', "\\|").replace('\n', " ");
+    if cell.trim().is_empty() {
+        "-".to_string()
+    } else {
+        cell
+    }
+}
+
+/// A fence longer than any backtick run in the text.
+fn code_fence(text: &str) -> String {
+    let longest = text
+        .split(|c: char| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Writes the collection as a Markdown document. Returns the document and the
+/// requests that are not documented (non-HTTP endpoints).
+pub(crate) fn collection_to_markdown(collection: &Collection) -> (String, Vec<String>) {
+    let (sections, skipped) = sections(collection);
+    let mut out = format!("# {}\n\n", collection.name);
+    if let Some(description) = collection
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        out.push_str(description.trim());
+        out.push_str("\n\n");
+    }
+    if let Some(base_url) = &collection.base_url {
+        out.push_str(&format!("Base URL: `{}`\n\n", base_url));
+    }
+
+    out.push_str("## Contents\n\n");
+    for section in &sections {
+        let indent = match section.title {
+            Some(title) => {
+                out.push_str(&format!("- {}\n", title));
+                "  "
+            }
+            None => "",
+        };
+        for doc in &section.endpoints {
+            out.push_str(&format!(
+                "{}- [{}](#{}){}\n",
+                indent,
+                doc.endpoint.name,
+                doc.anchor,
+                if doc.endpoint.deprecated {
+                    " (deprecated)"
+                } else {
+                    ""
+                }
+            ));
+        }
+    }
+    out.push('\n');
+
+    for section in &sections {
+        if let Some(title) = section.title {
+            out.push_str(&format!("## {}\n\n", title));
+        }
+        for doc in &section.endpoints {
+            write_endpoint_markdown(&mut out, collection, doc);
+        }
+    }
+
+    (out.trim_end().to_string() + "\n", skipped)
+}
+
+fn write_endpoint_markdown(out: &mut String, collection: &Collection, doc: &EndpointDoc) {
+    let endpoint = doc.endpoint;
+    out.push_str(&format!("### {}\n\n", endpoint.name));
+    if endpoint.deprecated {
+        out.push_str("> **Deprecated** — this endpoint may be removed in a future version.\n\n");
+    }
+    out.push_str(&format!(
+        "```\n{}\n```\n\n",
+        request_line(collection, endpoint)
+    ));
+    if let Some(description) = endpoint
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        out.push_str(description.trim());
+        out.push_str("\n\n");
+    }
+
+    for (title, rows) in &doc.parameters {
+        out.push_str(&format!("#### {}\n\n", title));
+        out.push_str("| Name | Required | Description | Example |\n");
+        out.push_str("|------|----------|-------------|---------|\n");
+        for row in rows {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                row.name,
+                if row.required { "Yes" } else { "No" },
+                table_cell(&row.description),
+                table_cell(&row.example)
+            ));
+        }
+        out.push('\n');
+    }
+
+    if let Some(body) = &doc.body {
+        let fence = code_fence(body);
+        out.push_str("#### Request body\n\n");
+        out.push_str(&format!("{}\n{}\n{}\n\n", fence, body.trim_end(), fence));
+    }
+
+    if let Some(example_use) = endpoint
+        .example_use
+        .as_deref()
+        .filter(|e| !e.trim().is_empty())
+    {
+        out.push_str("#### Example use\n\n");
+        out.push_str(example_use.trim());
+        out.push_str("\n\n");
+    }
+
+    if !doc.responses.is_empty() {
+        out.push_str("#### Responses\n\n");
+        out.push_str("| Status | Description |\n|--------|-------------|\n");
+        for row in &doc.responses {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                row.status,
+                table_cell(&row.description)
+            ));
+        }
+        out.push('\n');
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Renders inline code, `**bold**` and `[text](http...)` links in already
+/// escaped text.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in text.split('`').enumerate() {
+        // An unmatched backtick leaves an odd part count; keep it literal
+        if i % 2 == 1 && i < text.matches('`').count() {
+            out.push_str(&format!("<code>{}</code>", part));
+            continue;
+        }
+        if i % 2 == 1 {
+            out.push('`');
+        }
+        let mut rest = part;
+        while let Some(start) = rest.find("**") {
+            let Some(len) = rest[start + 2..].find("**") else {
+                break;
+            };
+            out.push_str(&render_links(&rest[..start]));
+            out.push_str(&format!(
+                "<strong>{}</strong>",
+                render_links(&rest[start + 2..start + 2 + len])
+            ));
+            rest = &rest[start + 4 + len..];
+        }
+        out.push_str(&render_links(rest));
+    }
+    out
+}
+
+fn render_links(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find("](").map(|i| open + i) else {
+            break;
+        };
+        let Some(end) = rest[close..].find(')').map(|i| close + i) else {
+            break;
+        };
+        let label = &rest[open + 1..close];
+        let href = &rest[close + 2..end];
+        out.push_str(&rest[..open]);
+        if href.starts_with("http://") || href.starts_with("https://") || href.starts_with('#') {
+            out.push_str(&format!("<a href=\"{}\">{}</a>", href, label));
+        } else {
+            out.push_str(&rest[open..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Renders user-written Markdown. Headings are demoted below `min_level` so
+/// they nest under the endpoint's own heading.
+fn render_markdown(markdown: &str, min_level: usize) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<(&str, Vec<String>)> = None;
+    let mut code: Option<Vec<String>> = None;
+
+    fn flush(
+        out: &mut String,
+        paragraph: &mut Vec<String>,
+        list: &mut Option<(&str, Vec<String>)>,
+    ) {
+        if !paragraph.is_empty() {
+            out.push_str(&format!("<p>{}</p>\n", render_inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+        if let Some((tag, items)) = list.take() {
+            out.push_str(&format!("<{}>\n", tag));
+            for item in items {
+                out.push_str(&format!("<li>{}</li>\n", render_inline(&item)));
+            }
+            out.push_str(&format!("</{}>\n", tag));
+        }
+    }
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if let Some(lines) = code.as_mut() {
+            if trimmed.starts_with("```") {
+                out.push_str(&format!("<pre><code>{}</code></pre>\n", lines.join("\n")));
+                code = None;
+            } else {
+                lines.push(escape_html(line));
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            flush(&mut out, &mut paragraph, &mut list);
+            code = Some(Vec::new());
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut out, &mut paragraph, &mut list);
+            continue;
+        }
+        let escaped = escape_html(trimmed);
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush(&mut out, &mut paragraph, &mut list);
+            let level = (hashes + min_level - 1).min(6);
+            out.push_str(&format!(
+                "<h{0}>{1}</h{0}>\n",
+                level,
+                render_inline(escaped[hashes..].trim())
+            ));
+            continue;
+        }
+        let ordered_item = trimmed
+            .split_once(". ")
+            .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        let item = if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            Some(("ul", item))
+        } else {
+            ordered_item.map(|(_, item)| ("ol", item))
+        };
+        if let Some((tag, item)) = item {
+            if !paragraph.is_empty() || list.as_ref().is_some_and(|(t, _)| *t != tag) {
+                flush(&mut out, &mut paragraph, &mut list);
+            }
+            list.get_or_insert_with(|| (tag, Vec::new()))
+                .1
+                .push(escape_html(item));
+            continue;
+        }
+        if let Some((_, items)) = list.as_mut() {
+            // A continuation line of the last list item
+            if line.starts_with(' ') {
+                if let Some(last) = items.last_mut() {
+                    last.push(' ');
+                    last.push_str(&escaped);
+                    continue;
+                }
+            }
+            flush(&mut out, &mut paragraph, &mut list);
+        }
+        paragraph.push(escaped);
+    }
+    if let Some(lines) = code {
+        out.push_str(&format!("<pre><code>{}</code></pre>\n", lines.join("\n")));
+    }
+    flush(&mut out, &mut paragraph, &mut list);
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,-apple-system,sans-serif;max-width:960px;margin:0 auto;padding:2rem;line-height:1.5;color:#1f2328}\
+code,pre{font-family:ui-monospace,monospace;background:#f6f8fa;border-radius:4px}\
+code{padding:.1em .3em}pre{padding:.75rem;overflow-x:auto}pre code{padding:0}\
+table{border-collapse:collapse;margin:.5rem 0}th,td{border:1px solid #d0d7de;padding:.3rem .6rem;text-align:left}\
+.endpoint{border-top:1px solid #d0d7de;padding-top:1rem;margin-top:2rem}\
+.method{font-weight:700}.deprecated{background:#fff8c5;border-left:4px solid #bf8700;padding:.5rem .75rem}\
+.deprecated-name{text-decoration:line-through}";
+
+/// Writes the collection as a self-contained HTML page. Returns the page and
+/// the requests that are not documented (non-HTTP endpoints).
+pub(crate) fn collection_to_html(collection: &Collection) -> (String, Vec<String>) {
+    let (sections, skipped) = sections(collection);
+    let title = escape_html(&collection.name);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, HTML_STYLE, title
+    );
+    if let Some(description) = &collection.description {
+        out.push_str(&render_markdown(description, 2));
+    }
+    if let Some(base_url) = &collection.base_url {
+        out.push_str(&format!(
+            "<p>Base URL: <code>{}</code></p>\n",
+            escape_html(base_url)
+        ));
+    }
+
+    out.push_str("<nav>\n<h2>Contents</h2>\n<ul>\n");
+    for section in &sections {
+        if let Some(title) = section.title {
+            out.push_str(&format!("<li>{}\n<ul>\n", escape_html(title)));
+        }
+        for doc in &section.endpoints {
+            out.push_str(&format!(
+                "<li><a href=\"#{}\"{}>{}</a></li>\n",
+                doc.anchor,
+                if doc.endpoint.deprecated {
+                    " class=\"deprecated-name\""
+                } else {
+                    ""
+                },
+                escape_html(&doc.endpoint.name)
+            ));
+        }
+        if section.title.is_some() {
+            out.push_str("</ul>\n</li>\n");
+        }
+    }
+    out.push_str("</ul>\n</nav>\n");
+
+    for section in &sections {
+        if let Some(title) = section.title {
+            out.push_str(&format!("<h2>{}</h2>\n", escape_html(title)));
+        }
+        for doc in &section.endpoints {
+            write_endpoint_html(&mut out, collection, doc);
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+
+    (out, skipped)
+}
+
+fn write_endpoint_html(out: &mut String, collection: &Collection, doc: &EndpointDoc) {
+    let endpoint = doc.endpoint;
+    out.push_str(&format!(
+        "<section class=\"endpoint\" id=\"{}\">\n<h3>{}</h3>\n",
+        doc.anchor,
+        escape_html(&endpoint.name)
+    ));
+    if endpoint.deprecated {
+        out.push_str("<p class=\"deprecated\"><strong>Deprecated</strong> — this endpoint may be removed in a future version.</p>\n");
+    }
+    let line = request_line(collection, endpoint);
+    let (method, url) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+    out.push_str(&format!(
+        "<pre><code><span class=\"method\">{}</span> {}</code></pre>\n",
+        escape_html(method),
+        escape_html(url)
+    ));
+    if let Some(description) = &endpoint.description {
+        out.push_str(&render_markdown(description, 4));
+    }
+
+    for (title, rows) in &doc.parameters {
+        out.push_str(&format!(
+            "<h4>{}</h4>\n<table>\n<tr><th>Name</th><th>Required</th><th>Description</th><th>Example</th></tr>\n",
+            title
+        ));
+        for row in rows {
+            out.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&row.name),
+                if row.required { "Yes" } else { "No" },
+                escape_html(&row.description),
+                escape_html(&row.example)
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+
+    if let Some(body) = &doc.body {
+        out.push_str(&format!(
+            "<h4>Request body</h4>\n<pre><code>{}</code></pre>\n",
+            escape_html(body.trim_end())
+        ));
+    }
+
+    if let Some(example_use) = &endpoint.example_use {
+        if !example_use.trim().is_empty() {
+            out.push_str("<h4>Example use</h4>\n");
+            out.push_str(&render_markdown(example_use, 5));
+        }
+    }
+
+    if !doc.responses.is_empty() {
+        out.push_str("<h4>Responses</h4>\n<table>\n<tr><th>Status</th><th>Description</th></tr>\n");
+        for row in &doc.responses {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&row.status),
+                escape_html(&row.description)
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</section>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Folder;
+    use super::*;
+    use serde_json::json;
+
+    fn endpoint(name: &str, method: &str, path: &str) -> Endpoint {
+        Endpoint {
+            id: name.to_string(),
+            name: name.to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+            description: None,
+            example_use: None,
+            deprecated: false,
+            parameters: None,
+            request_body: None,
+            responses: None,
+            security: None,
+            tags: Vec::new(),
+            scripts: None,
+            graphql_data: None,
+            examples: Vec::new(),
+        }
+    }
+
+    fn collection() -> Collection {
+        let mut list = endpoint("List users", "GET", "/users");
+        list.description = Some("Returns **all** users.\n\n- paged\n- sorted".to_string());
+        list.parameters = Some(json!({
+            "query": { "page": { "example": "2", "description": "Page | number" } }
+        }));
+        list.responses = Some(
+            [("200".to_string(), json!({ "description": "OK" }))]
+                .into_iter()
+                .collect(),
+        );
+        let mut old = endpoint("Get user", "GET", "/users/{{id}}");
+        old.deprecated = true;
+        old.example_use = Some("Call `GET /users/1` <first>".to_string());
+        Collection {
+            id: "c1".to_string(),
+            name: "Users API".to_string(),
+            description: Some("User management.".to_string()),
+            base_url: Some("https://api.test/".to_string()),
+            endpoints: vec![list, endpoint("Feed", "WS", "wss://api.test/feed")],
+            folders: vec![Folder {
+                id: "f1".to_string(),
+                name: "Legacy".to_string(),
+                endpoints: vec![old],
+                auth_config: None,
+            }],
+            variables: None,
+            auth_config: None,
+        }
+    }
+
+    #[test]
+    fn writes_markdown_with_contents_parameters_and_deprecation() {
+        let (markdown, skipped) = collection_to_markdown(&collection());
+        assert_eq!(skipped, vec!["Feed".to_string()]);
+        assert!(markdown.starts_with("# Users API\n\nUser management.\n"));
+        assert!(markdown.contains("- [List users](#list-users)\n"));
+        assert!(markdown.contains("- Legacy\n  - [Get user](#get-user) (deprecated)\n"));
+        assert!(markdown.contains("```\nGET https://api.test/users\n```"));
+        assert!(markdown.contains("| `page` | No | Page \\| number | 2 |"));
+        assert!(markdown.contains("## Legacy\n\n### Get user\n\n> **Deprecated**"));
+        assert!(markdown.contains("#### Example use\n\nCall `GET /users/1` <first>\n"));
+        assert!(markdown.contains("| 200 | OK |"));
+    }
+
+    #[test]
+    fn writes_html_with_rendered_and_escaped_markdown() {
+        let (html, skipped) = collection_to_html(&collection());
+        assert_eq!(skipped, vec!["Feed".to_string()]);
+        assert!(html.contains("<title>Users API</title>"));
+        assert!(html.contains("<p>Returns <strong>all</strong> users.</p>\n<ul>\n<li>paged</li>\n<li>sorted</li>\n</ul>"));
+        assert!(html.contains("<a href=\"#get-user\" class=\"deprecated-name\">Get user</a>"));
+        assert!(html.contains("<p>Call <code>GET /users/1</code> &lt;first&gt;</p>"));
+        assert!(!html.contains("<first>"));
+    }
+
+    #[test]
+    fn renders_code_blocks_headings_and_only_web_links() {
+        let html = render_markdown(
+            "## Usage\n```\nlet x = <a>;\n```\n1. [docs](https://x.test)\n2. [bad](javascript:alert(1))",
+            4,
+        );
+        assert_eq!(
+            html,
+            "<h5>Usage</h5>\n<pre><code>let x = &lt;a&gt;;</code></pre>\n<ol>\n<li><a href=\"https://x.test\">docs</a></li>\n<li>[bad](javascript:alert(1))</li>\n</ol>\n"
+        );
+    }
+
+    #[test]
+    fn anchors_are_unique() {
+        let mut used = HashSet::new();
+        assert_eq!(anchor("Get user!", &mut used), "get-user");
+        assert_eq!(anchor("Get user", &mut used), "get-user-1");
+    }
+}
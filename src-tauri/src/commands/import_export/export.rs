@@ -4,6 +4,7 @@
 use super::storage::is_http_method;
 use super::{Collection, Endpoint, Folder, VariableEntry};
 use crate::commands::collections as storage_collections;
+use crate::commands::endpoint_examples::{read_examples, ResponseExample};
 use crate::commands::scripts::ScriptData;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -18,11 +19,7 @@ pub(crate) fn load_collection_for_export(
     let collection_dir = storage_collections::resolve_collection_dir(app, collection_id)?
         .ok_or_else(|| format!("Collection {} not found", collection_id))?;
 
-    let collection_file = collection_dir.join("collection.json");
-    let content = fs::read_to_string(&collection_file)
-        .map_err(|e| format!("Failed to read collection.json: {}", e))?;
-    let raw: Value =
-        serde_json::from_str(&content).map_err(|e| format!("Failed to parse collection: {}", e))?;
+    let raw = storage_collections::read_collection_value(&collection_dir)?;
 
     let id = raw
         .get("id")
@@ -69,7 +66,8 @@ pub(crate) fn load_collection_for_export(
                             .and_then(|v| v.as_str())
                             .unwrap_or_default()
                             .to_string();
-                        Some(VariableEntry { key, value })
+                        let secret = e.get("secret").and_then(|v| v.as_bool()) == Some(true);
+                        Some(VariableEntry { key, value, secret })
                     })
                     .collect()
             })
@@ -77,11 +75,7 @@ pub(crate) fn load_collection_for_export(
         None
     };
 
-    attach_endpoint_data(
-        &collection_dir.join("requests"),
-        &mut endpoints,
-        &mut folders,
-    );
+    attach_endpoint_data(&collection_dir, &mut endpoints, &mut folders);
 
     Ok(Collection {
         id,
@@ -95,27 +89,37 @@ pub(crate) fn load_collection_for_export(
     })
 }
 
-/// Cached per-endpoint payload: (scripts, graphql_data), None when no data file exists.
-type EndpointPayload = Option<(Option<Value>, Option<Value>)>;
+/// Cached per-endpoint payload: (scripts, graphql_data, examples), None when
+/// the endpoint has neither a data file nor examples.
+type EndpointPayload = Option<(Option<Value>, Option<Value>, Vec<ResponseExample>)>;
 
-/// Populate the transient `scripts`/`graphql_data` fields from each endpoint's
-/// data file so they can be serialized on export. Data is read once per
-/// endpoint id and applied to both the flat and folder occurrences.
-fn attach_endpoint_data(requests_dir: &Path, endpoints: &mut [Endpoint], folders: &mut [Folder]) {
+/// Populate the transient `scripts`/`graphql_data`/`examples` fields from each
+/// endpoint's data and examples files so they can be serialized on export.
+/// Data is read once per endpoint id and applied to both the flat and folder
+/// occurrences.
+fn attach_endpoint_data(collection_dir: &Path, endpoints: &mut [Endpoint], folders: &mut [Folder]) {
+    let requests_dir = collection_dir.join("requests");
     let mut cache: HashMap<String, EndpointPayload> = HashMap::new();
 
     let mut fill = |endpoint: &mut Endpoint| {
         let data = cache.entry(endpoint.id.clone()).or_insert_with(|| {
-            storage_collections::find_endpoint_data_file(requests_dir, &endpoint.id)
+            let data = storage_collections::find_endpoint_data_file(&requests_dir, &endpoint.id)
                 .ok()
                 .flatten()
                 .and_then(|path| fs::read_to_string(path).ok())
-                .and_then(|s| serde_json::from_str::<storage_collections::EndpointData>(&s).ok())
-                .map(|d| (d.scripts, d.graphql_data))
+                .and_then(|s| serde_json::from_str::<storage_collections::EndpointData>(&s).ok());
+            let examples = read_examples(collection_dir, &endpoint.id);
+            if data.is_none() && examples.is_empty() {
+                return None;
+            }
+            let (scripts, graphql_data) =
+                data.map_or((None, None), |d| (d.scripts, d.graphql_data));
+            Some((scripts, graphql_data, examples))
         });
-        if let Some((scripts, graphql_data)) = data {
+        if let Some((scripts, graphql_data, examples)) = data {
             endpoint.scripts = scripts.clone();
             endpoint.graphql_data = graphql_data.clone();
+            endpoint.examples = examples.clone();
         }
     };
 
@@ -152,6 +156,14 @@ pub(crate) fn collection_to_openapi(collection: &Collection) -> (Value, Vec<Stri
             operation["description"] = Value::String(desc.clone());
         }
 
+        if endpoint.deprecated {
+            operation["deprecated"] = Value::Bool(true);
+        }
+
+        if !endpoint.tags.is_empty() {
+            operation["tags"] = serde_json::json!(endpoint.tags);
+        }
+
         if let Some(params) = &endpoint.parameters {
             operation["parameters"] = serde_json::to_value(params).unwrap_or(Value::Array(vec![]));
         }
@@ -165,6 +177,10 @@ pub(crate) fn collection_to_openapi(collection: &Collection) -> (Value, Vec<Stri
                 serde_json::to_value(responses).unwrap_or(Value::Object(serde_json::Map::new()));
         }
 
+        for example in &endpoint.examples {
+            add_openapi_example(&mut operation["responses"], example);
+        }
+
         paths
             .entry(endpoint.path.clone())
             .or_default()
@@ -191,6 +207,77 @@ pub(crate) fn collection_to_openapi(collection: &Collection) -> (Value, Vec<Stri
     (spec, skipped)
 }
 
+/// Whether a media type carries JSON.
+pub(crate) fn is_json_media_type(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Add a saved example under `responses[status].content[type].examples`,
+/// creating the response entry when the spec does not describe the status.
+fn add_openapi_example(responses: &mut Value, example: &ResponseExample) {
+    let Some(responses) = responses.as_object_mut() else {
+        return;
+    };
+    let media_type = example
+        .content_type()
+        .unwrap_or_else(|| "text/plain".to_string());
+    let value = if is_json_media_type(&media_type) {
+        serde_json::from_str(&example.body).unwrap_or_else(|_| Value::String(example.body.clone()))
+    } else {
+        Value::String(example.body.clone())
+    };
+
+    let response = responses
+        .entry(example.status.to_string())
+        .or_insert_with(|| {
+            let description = if example.status_text.is_empty() {
+                example.name.clone()
+            } else {
+                example.status_text.clone()
+            };
+            serde_json::json!({ "description": description })
+        });
+    let Some(response) = response.as_object_mut() else {
+        return;
+    };
+    let content = response
+        .entry("content")
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(content) = content.as_object_mut() {
+        let media = content
+            .entry(media_type)
+            .or_insert_with(|| serde_json::json!({}));
+        media["examples"][example.name.as_str()] = serde_json::json!({
+            "summary": example.name,
+            "value": value
+        });
+    }
+}
+
+/// A saved example as a Postman `response[]` entry of the item's request.
+fn example_to_postman_response(request: &Value, example: &ResponseExample) -> Value {
+    let header: Vec<Value> = example
+        .headers
+        .iter()
+        .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+        .collect();
+    let preview_language = match example.content_type() {
+        Some(media_type) if is_json_media_type(&media_type) => "json",
+        Some(media_type) if media_type.ends_with("xml") => "xml",
+        Some(media_type) if media_type == "text/html" => "html",
+        _ => "text",
+    };
+    serde_json::json!({
+        "name": example.name,
+        "originalRequest": request,
+        "status": example.status_text,
+        "code": example.status,
+        "_postman_previewlanguage": preview_language,
+        "header": header,
+        "body": example.body
+    })
+}
+
 fn endpoint_to_postman_item(collection: &Collection, endpoint: &Endpoint) -> Value {
     let url = if collection.base_url.is_some() {
         format!("{{{{baseUrl}}}}{}", endpoint.path)
@@ -228,10 +315,19 @@ fn endpoint_to_postman_item(collection: &Collection, endpoint: &Endpoint) -> Val
         request["auth"] = auth;
     }
 
+    let responses: Vec<Value> = endpoint
+        .examples
+        .iter()
+        .map(|example| example_to_postman_response(&request, example))
+        .collect();
+
     let mut item = serde_json::json!({
         "name": endpoint.name,
         "request": request
     });
+    if !responses.is_empty() {
+        item["response"] = Value::Array(responses);
+    }
 
     let events = endpoint_events_to_postman(endpoint);
     if !events.is_empty() {
@@ -483,6 +579,14 @@ pub(crate) fn collection_to_postman(collection: &Collection) -> (Value, Vec<Stri
             {
                 has_base_url_variable = true;
             }
+            if entry.secret {
+                variables.push(serde_json::json!({
+                    "key": entry.key,
+                    "value": "",
+                    "type": "secret"
+                }));
+                continue;
+            }
             variables.push(serde_json::json!({
                 "key": entry.key,
                 "value": entry.value,
@@ -538,12 +642,16 @@ mod tests {
             method: method.to_string(),
             path: format!("/{}", name.to_lowercase().replace(' ', "-")),
             description: None,
+            example_use: None,
+            deprecated: false,
             parameters: None,
             request_body: None,
             responses: None,
             security: None,
+            tags: Vec::new(),
             scripts: None,
             graphql_data: None,
+            examples: Vec::new(),
         }
     }
 
@@ -583,10 +691,18 @@ mod tests {
                     "config": { "username": "folder-user", "password": "" }
                 })),
             }],
-            variables: Some(vec![VariableEntry {
-                key: "apiKey".to_string(),
-                value: "secret".to_string(),
-            }]),
+            variables: Some(vec![
+                VariableEntry {
+                    key: "apiKey".to_string(),
+                    value: "secret".to_string(),
+                    secret: false,
+                },
+                VariableEntry {
+                    key: "password".to_string(),
+                    value: "hunter2".to_string(),
+                    secret: true,
+                },
+            ]),
             auth_config: Some(serde_json::json!({
                 "type": "bearer",
                 "config": { "token": "{{token}}" }
@@ -638,8 +754,11 @@ mod tests {
 
         let variables = postman["variable"].as_array().unwrap();
         assert_eq!(variables[0]["key"], "apiKey");
-        assert_eq!(variables[1]["key"], "baseUrl");
-        assert_eq!(variables[1]["value"], "https://api.example.com");
+        assert_eq!(variables[1]["key"], "password");
+        assert_eq!(variables[1]["value"], "");
+        assert_eq!(variables[1]["type"], "secret");
+        assert_eq!(variables[2]["key"], "baseUrl");
+        assert_eq!(variables[2]["value"], "https://api.example.com");
     }
 
     #[test]
@@ -759,4 +878,52 @@ mod tests {
             vec![("baseUrl", "https://api.example.com"), ("token", "abc")]
         );
     }
+
+    #[test]
+    fn saved_examples_export_as_openapi_examples_and_postman_responses() {
+        let mut found = endpoint("Get User", "GET");
+        found.examples = serde_json::from_value(serde_json::json!([
+            {
+                "id": "ex-1", "name": "Found", "status": 200, "statusText": "OK",
+                "headers": { "Content-Type": "application/json" },
+                "body": "{\"id\":1}", "savedAt": 0
+            },
+            {
+                "id": "ex-2", "name": "Gone", "status": 410, "statusText": "Gone",
+                "body": "deleted", "savedAt": 0
+            }
+        ]))
+        .unwrap();
+        let collection = Collection {
+            id: "col".to_string(),
+            name: "Test".to_string(),
+            description: None,
+            base_url: None,
+            endpoints: vec![found],
+            folders: Vec::new(),
+            variables: None,
+            auth_config: None,
+        };
+
+        let (spec, _) = collection_to_openapi(&collection);
+        let responses = &spec["paths"]["/get-user"]["get"]["responses"];
+        assert_eq!(
+            responses["200"]["content"]["application/json"]["examples"]["Found"]["value"]["id"],
+            1
+        );
+        assert_eq!(responses["200"]["description"], "Successful response");
+        assert_eq!(responses["410"]["description"], "Gone");
+        assert_eq!(
+            responses["410"]["content"]["text/plain"]["examples"]["Gone"]["value"],
+            "deleted"
+        );
+
+        let (postman, _) = collection_to_postman(&collection);
+        let saved = postman["item"][0]["response"].as_array().unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[0]["code"], 200);
+        assert_eq!(saved[0]["_postman_previewlanguage"], "json");
+        assert_eq!(saved[0]["header"][0]["key"], "Content-Type");
+        assert_eq!(saved[1]["originalRequest"]["method"], "GET");
+    }
 }
@@ -0,0 +1,305 @@
+//! HAR (HTTP Archive) parsing: turns the requests recorded by browser dev
+//! tools or proxies into a `Collection`, one endpoint per distinct request.
+
+use super::storage::is_http_method;
+use super::{Collection, Endpoint, Folder};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Headers that browsers and HTTP stacks set on their own; replaying them
+/// from a recording only gets in the way.
+const SKIPPED_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "content-length",
+    "cookie",
+    "accept-encoding",
+    "user-agent",
+    "referer",
+    "origin",
+];
+
+/// Dev-tools resource types and response MIME prefixes of page assets, which
+/// are left out so a browser recording imports as the API calls it made.
+const ASSET_RESOURCE_TYPES: &[&str] = &["image", "font", "stylesheet", "script", "media"];
+const ASSET_MIME_PREFIXES: &[&str] = &[
+    "image/",
+    "font/",
+    "text/css",
+    "text/javascript",
+    "application/javascript",
+];
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+fn is_asset(entry: &Value) -> bool {
+    let resource_type = str_field(entry, "_resourceType");
+    let mime_type = entry
+        .pointer("/response/content/mimeType")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    ASSET_RESOURCE_TYPES.contains(&resource_type)
+        || ASSET_MIME_PREFIXES
+            .iter()
+            .any(|prefix| mime_type.starts_with(prefix))
+}
+
+/// `{ name: { example } }` map of a HAR `[{ name, value }]` list.
+fn name_value_params(list: Option<&Value>, skip: impl Fn(&str) -> bool) -> Map<String, Value> {
+    list.and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|item| !str_field(item, "name").is_empty() && !skip(str_field(item, "name")))
+        .map(|item| {
+            (
+                str_field(item, "name").to_string(),
+                serde_json::json!({ "example": str_field(item, "value") }),
+            )
+        })
+        .collect()
+}
+
+fn extract_har_body(post_data: Option<&Value>) -> Option<Value> {
+    let post_data = post_data?;
+    let mime_type = str_field(post_data, "mimeType").to_ascii_lowercase();
+    let form_type = if mime_type.starts_with("application/x-www-form-urlencoded") {
+        Some("urlencoded")
+    } else if mime_type.starts_with("multipart/form-data") {
+        Some("formdata")
+    } else {
+        None
+    };
+
+    if let Some(form_type) = form_type {
+        // File parts can't be replayed from the archive, which only names them
+        let fields: Map<String, Value> = post_data
+            .get("params")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|param| param.get("fileName").is_none())
+            .map(|param| {
+                (
+                    str_field(param, "name").to_string(),
+                    Value::String(str_field(param, "value").to_string()),
+                )
+            })
+            .collect();
+        if !fields.is_empty() {
+            return Some(serde_json::json!({ "type": form_type, "fields": fields }));
+        }
+    }
+
+    let text = str_field(post_data, "text");
+    (!text.is_empty()).then(|| serde_json::json!({ "example": text }))
+}
+
+fn origin(url: &url::Url) -> String {
+    match url.port() {
+        Some(port) => format!(
+            "{}://{}:{}",
+            url.scheme(),
+            url.host_str().unwrap_or(""),
+            port
+        ),
+        None => format!("{}://{}", url.scheme(), url.host_str().unwrap_or("")),
+    }
+}
+
+pub(crate) fn parse_har(har: Value, name: &str) -> Result<Collection, String> {
+    let entries = har
+        .pointer("/log/entries")
+        .and_then(|v| v.as_array())
+        .ok_or("Missing 'log.entries' in HAR file")?;
+
+    let mut seen = HashSet::new();
+    let mut requests = Vec::new();
+    for entry in entries.iter().filter(|entry| !is_asset(entry)) {
+        let Some(request) = entry.get("request") else {
+            continue;
+        };
+        let method = str_field(request, "method").to_ascii_uppercase();
+        let Ok(mut url) = url::Url::parse(str_field(request, "url")) else {
+            continue;
+        };
+        if !is_http_method(&method) || !matches!(url.scheme(), "http" | "https") {
+            continue;
+        }
+        url.set_query(None);
+        url.set_fragment(None);
+        // Recordings repeat calls (polling, retries); the first one stands for all
+        if seen.insert(format!("{} {}", method, url)) {
+            requests.push((method, url, request));
+        }
+    }
+    if requests.is_empty() {
+        return Err("The HAR file contains no HTTP requests to import".to_string());
+    }
+
+    let origins: Vec<String> = requests.iter().map(|(_, url, _)| origin(url)).collect();
+    let single_origin = origins.iter().all(|o| *o == origins[0]);
+
+    let mut endpoints = Vec::new();
+    let mut by_origin: HashMap<String, Vec<Endpoint>> = HashMap::new();
+    for ((method, url, request), request_origin) in requests.into_iter().zip(origins.iter()) {
+        // With one origin it becomes {{baseUrl}} and paths stay relative
+        let path = if single_origin {
+            url.path().to_string()
+        } else {
+            url.to_string()
+        };
+
+        let mut parameters = Map::new();
+        let query = name_value_params(request.get("queryString"), |_| false);
+        if !query.is_empty() {
+            parameters.insert("query".to_string(), Value::Object(query));
+        }
+        let headers = name_value_params(request.get("headers"), |header| {
+            header.starts_with(':')
+                || header.to_ascii_lowercase().starts_with("sec-")
+                || SKIPPED_HEADERS.contains(&header.to_ascii_lowercase().as_str())
+        });
+        if !headers.is_empty() {
+            parameters.insert("header".to_string(), Value::Object(headers));
+        }
+
+        let endpoint = Endpoint {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: format!("{} {}", method, url.path()),
+            method,
+            path,
+            description: None,
+            example_use: None,
+            deprecated: false,
+            parameters: (!parameters.is_empty()).then_some(Value::Object(parameters)),
+            request_body: extract_har_body(request.get("postData")),
+            responses: None,
+            security: None,
+            tags: Vec::new(),
+            scripts: None,
+            graphql_data: None,
+            examples: Vec::new(),
+        };
+        endpoints.push(endpoint.clone());
+        by_origin
+            .entry(request_origin.clone())
+            .or_default()
+            .push(endpoint);
+    }
+
+    let mut folders: Vec<Folder> = if single_origin {
+        Vec::new()
+    } else {
+        by_origin
+            .into_iter()
+            .map(|(origin, endpoints)| {
+                let host = origin.split("://").nth(1).unwrap_or(&origin).to_string();
+                Folder {
+                    id: format!(
+                        "folder_{}",
+                        host.replace(|c: char| !c.is_alphanumeric(), "_")
+                    ),
+                    name: host,
+                    endpoints,
+                    auth_config: None,
+                }
+            })
+            .collect()
+    };
+    folders.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Collection {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        description: None,
+        base_url: single_origin.then(|| origins[0].clone()),
+        endpoints,
+        folders,
+        variables: None,
+        auth_config: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_distinct_api_requests() {
+        let har = serde_json::json!({
+            "log": {
+                "entries": [
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/users?page=1",
+                            "headers": [
+                                { "name": ":authority", "value": "api.example.com" },
+                                { "name": "Accept", "value": "application/json" },
+                                { "name": "Cookie", "value": "session=1" }
+                            ],
+                            "queryString": [{ "name": "page", "value": "1" }]
+                        },
+                        "response": { "content": { "mimeType": "application/json" } }
+                    },
+                    {
+                        "request": {
+                            "method": "GET",
+                            "url": "https://api.example.com/users?page=2",
+                            "queryString": [{ "name": "page", "value": "2" }]
+                        }
+                    },
+                    {
+                        "request": { "method": "GET", "url": "https://api.example.com/logo.png" },
+                        "response": { "content": { "mimeType": "image/png" } }
+                    },
+                    {
+                        "request": {
+                            "method": "POST",
+                            "url": "https://api.example.com/login",
+                            "postData": {
+                                "mimeType": "application/x-www-form-urlencoded",
+                                "params": [{ "name": "user", "value": "ada" }]
+                            }
+                        }
+                    }
+                ]
+            }
+        });
+
+        let collection = parse_har(har, "Recording").unwrap();
+        assert_eq!(
+            collection.base_url.as_deref(),
+            Some("https://api.example.com")
+        );
+        assert!(collection.folders.is_empty());
+        assert_eq!(collection.endpoints.len(), 2);
+
+        let list = &collection.endpoints[0];
+        assert_eq!(
+            (list.method.as_str(), list.path.as_str()),
+            ("GET", "/users")
+        );
+        let parameters = list.parameters.as_ref().unwrap();
+        assert_eq!(parameters["query"]["page"]["example"], "1");
+        assert_eq!(
+            parameters["header"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["Accept"]
+        );
+
+        let login = &collection.endpoints[1];
+        assert_eq!(
+            login.request_body,
+            Some(serde_json::json!({ "type": "urlencoded", "fields": { "user": "ada" } }))
+        );
+
+        assert!(parse_har(serde_json::json!({ "log": { "entries": [] } }), "Empty").is_err());
+    }
+}
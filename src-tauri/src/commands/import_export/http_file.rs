@@ -0,0 +1,593 @@
+//! `.http` / `.rest` request files, as used by the VS Code REST Client and
+//! JetBrains HTTP Client: parsing into a `Collection` and writing a collection
+//! back out. Requests are separated by `###` lines, file variables are
+//! declared as `@name = value` and referenced as `{{name}}`, which is the
+//! app's own variable syntax.
+//!
+//! Response handler scripts (`> {% ... %}`) and response redirects (`>>`)
+//! are tool-specific and dropped on import.
+
+use super::storage::is_http_method;
+use super::{Collection, Endpoint, Folder, VariableEntry};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Header REST Client uses to mark a GraphQL request; its body is the query,
+/// optionally followed by a blank line and the variables JSON.
+const GRAPHQL_REQUEST_TYPE_HEADER: &str = "x-request-type";
+
+#[derive(Default)]
+struct HttpRequest {
+    name: Option<String>,
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Vec<String>,
+}
+
+/// `@name = value` file variable declaration.
+fn variable_declaration(line: &str) -> Option<(String, String)> {
+    let (name, value) = line.strip_prefix('@')?.split_once('=')?;
+    let name = name.trim();
+    (!name.is_empty() && !name.contains(char::is_whitespace))
+        .then(|| (name.to_string(), value.trim().to_string()))
+}
+
+/// The request name from a `# @name login` / `// @name login` comment.
+fn name_comment(line: &str) -> Option<&str> {
+    let comment = line
+        .strip_prefix('#')
+        .or_else(|| line.strip_prefix("//"))?
+        .trim_start();
+    let name = comment.strip_prefix("@name")?;
+    // `@name=login` is accepted by JetBrains as well
+    let name = name.trim_start().trim_start_matches('=').trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Method and URL of a request line (`POST {{baseUrl}}/users HTTP/1.1`); a
+/// line without a method is a GET. `None` for non-HTTP requests such as
+/// `WEBSOCKET` or `GRPC`.
+fn request_line(line: &str) -> Option<(String, String)> {
+    let mut line = line.trim();
+    if let Some((rest, version)) = line.rsplit_once(' ') {
+        if version.starts_with("HTTP/") {
+            line = rest.trim_end();
+        }
+    }
+    let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    if is_http_method(first) {
+        return Some((first.to_ascii_uppercase(), rest.trim().to_string()));
+    }
+    if !rest.is_empty() && first.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    Some(("GET".to_string(), line.to_string()))
+}
+
+/// Lines of a request body that are handler/redirect syntax, not content.
+fn is_response_directive(line: &str) -> bool {
+    [">> ", ">>! ", "<> ", "> "]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+/// Splits the file into requests at `###` separators. Returns the requests,
+/// the file variables and the number of non-HTTP requests left out.
+fn split_requests(content: &str) -> (Vec<HttpRequest>, Vec<VariableEntry>, usize) {
+    let mut requests = Vec::new();
+    let mut variables: Vec<VariableEntry> = Vec::new();
+    let mut unsupported = 0;
+
+    let mut current: Option<HttpRequest> = None;
+    let mut pending_name: Option<String> = None;
+    let mut in_body = false;
+    let mut in_handler = false;
+    let mut skipping = false;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim_end();
+        if line.trim_start().starts_with("###") {
+            requests.extend(current.take());
+            let title = line.trim_start().trim_start_matches('#').trim();
+            pending_name = (!title.is_empty()).then(|| title.to_string());
+            in_body = false;
+            in_handler = false;
+            skipping = false;
+            continue;
+        }
+        if skipping {
+            continue;
+        }
+
+        let Some(request) = current.as_mut() else {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Some((key, value)) = variable_declaration(trimmed) {
+                variables.retain(|entry| entry.key != key);
+                variables.push(VariableEntry {
+                    key,
+                    value,
+                    secret: false,
+                });
+            } else if let Some(name) = name_comment(trimmed) {
+                pending_name = Some(name.to_string());
+            } else if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                // Plain comments and other `# @directive`s
+            } else if let Some((method, url)) = request_line(trimmed) {
+                current = Some(HttpRequest {
+                    name: pending_name.take(),
+                    method,
+                    url,
+                    ..Default::default()
+                });
+            } else {
+                unsupported += 1;
+                skipping = true;
+            }
+            continue;
+        };
+
+        if in_body {
+            if in_handler {
+                in_handler = !line.contains("%}");
+            } else if line.starts_with("> {%") {
+                in_handler = !line.contains("%}");
+            } else if !is_response_directive(line) {
+                request.body.push(raw_line.to_string());
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if request.headers.is_empty() && (trimmed.starts_with('?') || trimmed.starts_with('&')) {
+            // Query string continued on the following lines
+            request.url.push_str(trimmed);
+        } else if trimmed.is_empty() {
+            in_body = true;
+        } else if trimmed.starts_with('#') || trimmed.starts_with("//") {
+            // Comments between headers
+        } else if let Some((name, value)) = trimmed.split_once(':') {
+            request
+                .headers
+                .push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    requests.extend(current);
+
+    for request in &mut requests {
+        while request
+            .body
+            .last()
+            .is_some_and(|line| line.trim().is_empty())
+        {
+            request.body.pop();
+        }
+    }
+    (requests, variables, unsupported)
+}
+
+fn query_params(query: &str) -> Map<String, Value> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), serde_json::json!({ "example": value }))
+        })
+        .collect()
+}
+
+fn request_to_endpoint(request: HttpRequest) -> Endpoint {
+    let (path, query) = request
+        .url
+        .split_once('?')
+        .unwrap_or((request.url.as_str(), ""));
+
+    let is_graphql = request.headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case(GRAPHQL_REQUEST_TYPE_HEADER)
+            && value.eq_ignore_ascii_case("graphql")
+    });
+    let headers: Map<String, Value> = request
+        .headers
+        .iter()
+        .filter(|(name, _)| !(is_graphql && name.eq_ignore_ascii_case(GRAPHQL_REQUEST_TYPE_HEADER)))
+        .map(|(name, value)| (name.clone(), serde_json::json!({ "example": value })))
+        .collect();
+
+    let mut parameters = Map::new();
+    let query = query_params(query);
+    if !query.is_empty() {
+        parameters.insert("query".to_string(), Value::Object(query));
+    }
+    if !headers.is_empty() {
+        parameters.insert("header".to_string(), Value::Object(headers));
+    }
+
+    let body = request.body.join("\n");
+    let (request_body, graphql_data) = if body.trim().is_empty() {
+        (None, None)
+    } else if is_graphql {
+        let (query, variables) = body.split_once("\n\n").unwrap_or((body.as_str(), ""));
+        let graphql = serde_json::json!({
+            "mode": "graphql",
+            "query": query.trim(),
+            "variables": variables.trim()
+        });
+        (None, Some(graphql))
+    } else {
+        (Some(serde_json::json!({ "example": body })), None)
+    };
+
+    Endpoint {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: request
+            .name
+            .unwrap_or_else(|| format!("{} {}", request.method, path)),
+        method: request.method,
+        path: path.to_string(),
+        description: None,
+        example_use: None,
+        deprecated: false,
+        parameters: (!parameters.is_empty()).then_some(Value::Object(parameters)),
+        request_body,
+        responses: None,
+        security: None,
+        tags: Vec::new(),
+        scripts: None,
+        graphql_data,
+        examples: Vec::new(),
+    }
+}
+
+pub(crate) fn parse_http_file(content: &str, name: &str) -> Result<Collection, String> {
+    let (requests, variables, unsupported) = split_requests(content);
+    if requests.is_empty() {
+        return Err(if unsupported > 0 {
+            "The file contains only non-HTTP requests, which cannot be imported".to_string()
+        } else {
+            "The file contains no requests to import".to_string()
+        });
+    }
+
+    let base_url = variables
+        .iter()
+        .find(|entry| {
+            entry.key.eq_ignore_ascii_case("baseurl") || entry.key.eq_ignore_ascii_case("base_url")
+        })
+        .map(|entry| entry.value.clone())
+        .filter(|value| !value.is_empty());
+
+    Ok(Collection {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        description: None,
+        base_url,
+        endpoints: requests.into_iter().map(request_to_endpoint).collect(),
+        folders: Vec::new(),
+        variables: (!variables.is_empty()).then_some(variables),
+        auth_config: None,
+    })
+}
+
+pub(super) fn example_text(value: &Value) -> String {
+    match value.get("example").unwrap_or(value) {
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+pub(super) fn parameter_examples(endpoint: &Endpoint, location: &str) -> Vec<(String, String)> {
+    endpoint
+        .parameters
+        .as_ref()
+        .and_then(|p| p.get(location))
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, param)| (name.clone(), example_text(param)))
+        .collect()
+}
+
+/// The `Authorization`-style header for the endpoint's own auth, when it has
+/// a direct `.http` equivalent.
+pub(super) fn auth_header(security: Option<&Value>) -> Option<(String, String)> {
+    let security = security?;
+    let config = security.get("config");
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    };
+    match security.get("type").and_then(|v| v.as_str())? {
+        "bearer" => Some((
+            "Authorization".to_string(),
+            format!("Bearer {}", get("token")),
+        )),
+        "basic" => Some((
+            "Authorization".to_string(),
+            format!("Basic {} {}", get("username"), get("password")),
+        )),
+        "api-key" if get("location") != "query" => {
+            let name = Some(get("keyName"))
+                .filter(|s| !s.is_empty())
+                .unwrap_or(get("key"));
+            let value = Some(get("keyValue"))
+                .filter(|s| !s.is_empty())
+                .unwrap_or(get("value"));
+            (!name.is_empty()).then(|| (name.to_string(), value.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn form_body(body: &Value) -> Option<(String, String)> {
+    let fields = body.get("fields").and_then(Value::as_object)?;
+    let value_text = |value: &Value| {
+        value
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string())
+    };
+    match body.get("type").and_then(|v| v.as_str())? {
+        "urlencoded" => {
+            let encoded = fields
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value_text(value)))
+                .collect::<Vec<_>>()
+                .join("&");
+            Some(("application/x-www-form-urlencoded".to_string(), encoded))
+        }
+        "formdata" => {
+            let boundary = "ResonanceFormBoundary";
+            let mut text = String::new();
+            for (name, value) in fields {
+                text.push_str(&format!(
+                    "--{}\nContent-Disposition: form-data; name=\"{}\"\n\n{}\n",
+                    boundary,
+                    name,
+                    value_text(value)
+                ));
+            }
+            text.push_str(&format!("--{}--", boundary));
+            Some((format!("multipart/form-data; boundary={}", boundary), text))
+        }
+        _ => None,
+    }
+}
+
+fn write_request(out: &mut String, collection: &Collection, endpoint: &Endpoint) {
+    let mut url = if endpoint.path.starts_with("http://")
+        || endpoint.path.starts_with("https://")
+        || endpoint.path.starts_with("{{")
+        || collection.base_url.is_none()
+    {
+        endpoint.path.clone()
+    } else {
+        format!("{{{{baseUrl}}}}{}", endpoint.path)
+    };
+    let query = parameter_examples(endpoint, "query");
+    if !query.is_empty() {
+        let joined = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&joined);
+    }
+
+    let mut headers = parameter_examples(endpoint, "header");
+    if let Some(header) = auth_header(endpoint.security.as_ref()) {
+        headers.push(header);
+    }
+    let has_content_type = |headers: &[(String, String)]| {
+        headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+    };
+
+    let graphql = endpoint
+        .graphql_data
+        .as_ref()
+        .filter(|g| g.get("mode").and_then(|m| m.as_str()) == Some("graphql"));
+    let body = if let Some(graphql) = graphql {
+        headers.push(("X-Request-Type".to_string(), "GraphQL".to_string()));
+        let query = graphql.get("query").and_then(|q| q.as_str()).unwrap_or("");
+        let variables = graphql
+            .get("variables")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim();
+        Some(if variables.is_empty() {
+            query.trim().to_string()
+        } else {
+            format!("{}\n\n{}", query.trim(), variables)
+        })
+    } else if let Some(body) = &endpoint.request_body {
+        match form_body(body) {
+            Some((content_type, text)) => {
+                if !has_content_type(&headers) {
+                    headers.push(("Content-Type".to_string(), content_type));
+                }
+                Some(text)
+            }
+            None => body.get("example").map(example_text),
+        }
+    } else {
+        None
+    };
+
+    out.push_str(&format!("### {}\n", endpoint.name));
+    out.push_str(&format!(
+        "{} {}\n",
+        endpoint.method.to_ascii_uppercase(),
+        url
+    ));
+    for (name, value) in &headers {
+        out.push_str(&format!("{}: {}\n", name, value));
+    }
+    if let Some(body) = body.filter(|b| !b.trim().is_empty()) {
+        out.push('\n');
+        out.push_str(body.trim_end());
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+/// Writes the collection as an `.http` file. Returns the file content and the
+/// requests that have no `.http` form (non-HTTP endpoints).
+pub(crate) fn collection_to_http_file(collection: &Collection) -> (String, Vec<String>) {
+    let mut out = String::new();
+    let mut skipped = Vec::new();
+
+    let mut has_base_url_variable = false;
+    for entry in collection.variables.iter().flatten() {
+        if entry.key.eq_ignore_ascii_case("baseurl") || entry.key.eq_ignore_ascii_case("base_url") {
+            has_base_url_variable = true;
+        }
+        // Secret values stay in the secret store
+        let value = if entry.secret {
+            ""
+        } else {
+            entry.value.as_str()
+        };
+        out.push_str(&format!("@{} = {}\n", entry.key, value));
+    }
+    if !has_base_url_variable {
+        if let Some(base_url) = collection.base_url.as_ref().filter(|s| !s.is_empty()) {
+            out.push_str(&format!("@baseUrl = {}\n", base_url));
+        }
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    // Foldered endpoints also appear in the flat list; those are written
+    // under their folder's comment only
+    let folder_endpoint_ids: HashSet<&str> = collection
+        .folders
+        .iter()
+        .flat_map(|f| f.endpoints.iter().map(|e| e.id.as_str()))
+        .collect();
+    let top_level = collection
+        .endpoints
+        .iter()
+        .filter(|e| !folder_endpoint_ids.contains(e.id.as_str()))
+        .map(|endpoint| (None, endpoint));
+    let foldered = collection.folders.iter().flat_map(|folder: &Folder| {
+        folder
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(move |(index, endpoint)| (Some((folder.name.as_str(), index == 0)), endpoint))
+    });
+
+    for (folder, endpoint) in top_level.chain(foldered) {
+        if let Some((folder_name, true)) = folder {
+            out.push_str(&format!("# {}\n\n", folder_name));
+        }
+        if !is_http_method(&endpoint.method) {
+            skipped.push(match folder {
+                Some((folder_name, _)) => format!("{}/{}", folder_name, endpoint.name),
+                None => endpoint.name.clone(),
+            });
+            continue;
+        }
+        write_request(&mut out, collection, endpoint);
+    }
+
+    (out.trim_end().to_string() + "\n", skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_requests_variables_and_bodies() {
+        let content = r#"@baseUrl = https://api.example.com
+@token = abc
+
+### List users
+GET {{baseUrl}}/users
+    ?page=2
+    &limit=10
+Accept: application/json
+
+###
+# @name createUser
+POST {{baseUrl}}/users HTTP/1.1
+Content-Type: application/json
+Authorization: Bearer {{token}}
+
+{
+  "name": "Ada"
+}
+
+> {%
+    client.global.set("id", response.body.id);
+%}
+
+### Socket
+WEBSOCKET ws://localhost:8080/ws
+
+### Query
+POST {{baseUrl}}/graphql
+X-Request-Type: GraphQL
+
+query { users { id } }
+
+{"first": 1}
+"#;
+        let collection = parse_http_file(content, "api").unwrap();
+        assert_eq!(
+            collection.base_url.as_deref(),
+            Some("https://api.example.com")
+        );
+        assert_eq!(collection.variables.as_ref().unwrap().len(), 2);
+        assert_eq!(collection.endpoints.len(), 3);
+
+        let list = &collection.endpoints[0];
+        assert_eq!(list.name, "List users");
+        assert_eq!(list.path, "{{baseUrl}}/users");
+        let parameters = list.parameters.as_ref().unwrap();
+        assert_eq!(parameters["query"]["limit"]["example"], "10");
+        assert_eq!(
+            parameters["header"]["Accept"]["example"],
+            "application/json"
+        );
+
+        let create = &collection.endpoints[1];
+        assert_eq!(
+            (create.name.as_str(), create.method.as_str()),
+            ("createUser", "POST")
+        );
+        assert_eq!(
+            create.request_body,
+            Some(serde_json::json!({ "example": "{\n  \"name\": \"Ada\"\n}" }))
+        );
+
+        let graphql = collection.endpoints[2].graphql_data.as_ref().unwrap();
+        assert_eq!(graphql["query"], "query { users { id } }");
+        assert_eq!(graphql["variables"], "{\"first\": 1}");
+
+        let (exported, skipped) = collection_to_http_file(&collection);
+        assert!(skipped.is_empty());
+        assert!(exported.starts_with("@baseUrl = https://api.example.com\n@token = abc\n"));
+        assert!(exported.contains("### List users\nGET {{baseUrl}}/users?page=2&limit=10\n"));
+
+        let reimported = parse_http_file(&exported, "api").unwrap();
+        assert_eq!(reimported.endpoints.len(), 3);
+        assert_eq!(reimported.endpoints[1].request_body, create.request_body);
+        assert_eq!(
+            reimported.endpoints[2].graphql_data,
+            collection.endpoints[2].graphql_data
+        );
+
+        assert!(parse_http_file("WEBSOCKET ws://localhost/ws\n", "ws").is_err());
+    }
+}
@@ -55,15 +55,31 @@ pub(crate) fn parse_openapi_spec(spec: Value) -> Result<Collection, String> {
                             .get("description")
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string()),
+                        example_use: None,
+                        deprecated: operation
+                            .get("deprecated")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false),
                         parameters: parse_parameters(operation.get("parameters"), &spec),
                         request_body: extract_openapi_request_body(
                             operation.get("requestBody"),
                             &spec,
                         ),
                         responses: extract_openapi_responses(operation.get("responses"), &spec),
-                        security: extract_openapi_security(operation.get("security"), &spec),
+                        security: operation_security(operation.get("security"), &spec),
+                        tags: operation
+                            .get("tags")
+                            .and_then(|v| v.as_array())
+                            .map(|tags| {
+                                tags.iter()
+                                    .filter_map(|t| t.as_str())
+                                    .map(|t| t.to_string())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
                         scripts: None,
                         graphql_data: None,
+                        examples: Vec::new(),
                     };
 
                     // Extract base path (first segment) for folder grouping
@@ -80,14 +96,17 @@ pub(crate) fn parse_openapi_spec(spec: Value) -> Result<Collection, String> {
     // Create folders from grouped endpoints
     let mut folders: Vec<Folder> = grouped_endpoints
         .into_iter()
-        .map(|(base_path, endpoints)| Folder {
-            id: format!(
-                "folder_{}",
-                base_path.replace(|c: char| !c.is_alphanumeric(), "_")
-            ),
-            name: base_path,
-            endpoints,
-            auth_config: None,
+        .map(|(base_path, mut endpoints)| {
+            let auth_config = hoist_shared_security(&mut endpoints);
+            Folder {
+                id: format!(
+                    "folder_{}",
+                    base_path.replace(|c: char| !c.is_alphanumeric(), "_")
+                ),
+                name: base_path,
+                endpoints,
+                auth_config,
+            }
         })
         .collect();
 
@@ -480,6 +499,34 @@ fn parse_parameters(params: Option<&Value>, spec: &Value) -> Option<Value> {
     Some(Value::Object(result))
 }
 
+/// The auth of an operation. Operations without their own `security`, or
+/// restating the global one, get none so they inherit the collection auth;
+/// an empty list explicitly opts the operation out of auth.
+fn operation_security(security: Option<&Value>, spec: &Value) -> Option<Value> {
+    let security = security?;
+    if spec.get("security") == Some(security) {
+        return None;
+    }
+    if security.as_array().is_some_and(Vec::is_empty) {
+        return Some(serde_json::json!({ "type": "none", "config": {} }));
+    }
+    extract_openapi_security(Some(security), spec)
+}
+
+/// When every endpoint of a folder has the same auth of its own, move it to
+/// the folder so it is configured once and the endpoints inherit it.
+fn hoist_shared_security(endpoints: &mut [Endpoint]) -> Option<Value> {
+    let (first, rest) = endpoints.split_first()?;
+    let shared = first.security.clone()?;
+    if rest.iter().any(|e| e.security.as_ref() != Some(&shared)) {
+        return None;
+    }
+    for endpoint in endpoints.iter_mut() {
+        endpoint.security = None;
+    }
+    Some(shared)
+}
+
 /// Extract security configuration from OpenAPI operation
 /// Converts OpenAPI security requirements to { type, config } format expected by frontend
 fn extract_openapi_security(security: Option<&Value>, spec: &Value) -> Option<Value> {
@@ -548,3 +595,49 @@ fn extract_openapi_security(security: Option<&Value>, spec: &Value) -> Option<Va
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn endpoints_inherit_the_default_security() {
+        let spec = json!({
+            "info": { "title": "Shop" },
+            "security": [{ "bearerAuth": [] }],
+            "components": { "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer" },
+                "adminKey": { "type": "apiKey", "name": "X-Admin-Key", "in": "header" }
+            } },
+            "paths": {
+                "/orders": {
+                    "get": { "summary": "List orders" },
+                    "post": { "summary": "Create order", "security": [{ "bearerAuth": [] }] }
+                },
+                "/health": { "get": { "summary": "Health", "security": [] } },
+                "/admin/users": {
+                    "get": { "summary": "Users", "security": [{ "adminKey": [] }] },
+                    "delete": { "summary": "Delete user", "security": [{ "adminKey": [] }] }
+                }
+            }
+        });
+
+        let collection = parse_openapi_spec(spec).unwrap();
+        assert_eq!(collection.auth_config.as_ref().unwrap()["type"], "bearer");
+        let folder = |name: &str| collection.folders.iter().find(|f| f.name == name).unwrap();
+
+        let orders = folder("orders");
+        assert!(orders.auth_config.is_none());
+        assert!(orders.endpoints.iter().all(|e| e.security.is_none()));
+
+        let health = folder("health");
+        assert_eq!(health.auth_config.as_ref().unwrap()["type"], "none");
+
+        let admin = folder("admin");
+        let admin_auth = admin.auth_config.as_ref().unwrap();
+        assert_eq!(admin_auth["type"], "api-key");
+        assert_eq!(admin_auth["config"]["keyName"], "X-Admin-Key");
+        assert!(admin.endpoints.iter().all(|e| e.security.is_none()));
+    }
+}
@@ -237,6 +237,7 @@ fn extract_postman_variables(
             entries.push(VariableEntry {
                 key: key.to_string(),
                 value,
+                secret: false,
             });
         }
     }
@@ -249,6 +250,7 @@ fn extract_postman_variables(
             entries.push(VariableEntry {
                 key: "baseUrl".to_string(),
                 value: base_url.to_string(),
+                secret: false,
             });
         }
     }
@@ -372,12 +374,16 @@ fn parse_postman_request(
             .get("description")
             .and_then(|d| d.as_str())
             .map(|s| s.to_string()),
+        example_use: None,
+        deprecated: false,
         parameters,
         request_body,
         responses: None,
         security,
+        tags: Vec::new(),
         scripts,
         graphql_data,
+        examples: Vec::new(),
     })
 }
 
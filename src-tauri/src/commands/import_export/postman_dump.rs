@@ -0,0 +1,188 @@
+//! Postman data dumps ("Export data" in Postman's settings): a zip holding
+//! every collection and environment of an account plus its globals. Entries
+//! are recognised by their content rather than their folder, since the layout
+//! differs between Postman versions.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::{Read, Seek};
+
+/// Larger entries are skipped instead of read into memory.
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Name given to the environment holding the dump's globals.
+const GLOBALS_ENVIRONMENT_NAME: &str = "Postman Globals";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpEnvironment {
+    pub name: String,
+    /// Enabled variables only
+    pub variables: Map<String, Value>,
+}
+
+/// The importable contents of a dump.
+#[derive(Debug, Default)]
+pub(super) struct PostmanDump {
+    /// Postman collection documents with the entry they came from
+    pub collections: Vec<(String, Value)>,
+    pub environments: Vec<DumpEnvironment>,
+    /// Entries that were not imported, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// An environment or globals file: `{ name, values: [{ key, value, enabled }] }`.
+fn parse_environment(document: &Value) -> Option<DumpEnvironment> {
+    let values = document.get("values")?.as_array()?;
+    let scope = document
+        .get("_postman_variable_scope")
+        .and_then(Value::as_str);
+    let name = match scope {
+        Some("globals") => GLOBALS_ENVIRONMENT_NAME.to_string(),
+        _ => document
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.trim().is_empty())?
+            .to_string(),
+    };
+    let variables = values
+        .iter()
+        .filter(|value| value.get("enabled").and_then(Value::as_bool) != Some(false))
+        .filter_map(|value| {
+            let key = value.get("key")?.as_str()?;
+            let text = match value.get("value") {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            Some((key.to_string(), Value::String(text)))
+        })
+        .collect();
+    Some(DumpEnvironment { name, variables })
+}
+
+fn is_postman_collection(document: &Value) -> bool {
+    document.get("info").is_some() && document.get("item").is_some_and(Value::is_array)
+}
+
+/// Read the collections and environments of a Postman data dump.
+pub(super) fn read_postman_dump<R: Read + Seek>(reader: R) -> Result<PostmanDump, String> {
+    let mut archive = zip::ZipArchive::new(reader)
+        .map_err(|e| format!("Not a Postman data dump (zip archive): {}", e))?;
+    let mut dump = PostmanDump::default();
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive: {}", e))?;
+        let name = entry.name().to_string();
+        // archive.json only indexes the other entries
+        if entry.is_dir()
+            || name.starts_with("__MACOSX/")
+            || !name.ends_with(".json")
+            || name.rsplit('/').next() == Some("archive.json")
+        {
+            continue;
+        }
+        if entry.size() > MAX_ENTRY_BYTES {
+            dump.skipped.push(format!("{}: too large", name));
+            continue;
+        }
+
+        let mut content = String::new();
+        if let Err(e) = entry.read_to_string(&mut content) {
+            dump.skipped.push(format!("{}: {}", name, e));
+            continue;
+        }
+        let document: Value = match serde_json::from_str(&content) {
+            Ok(document) => document,
+            Err(e) => {
+                dump.skipped.push(format!("{}: invalid JSON ({})", name, e));
+                continue;
+            }
+        };
+
+        if is_postman_collection(&document) {
+            dump.collections.push((name, document));
+        } else if let Some(environment) = parse_environment(&document) {
+            dump.environments.push(environment);
+        } else {
+            dump.skipped
+                .push(format!("{}: not a collection or environment", name));
+        }
+    }
+
+    if dump.collections.is_empty() && dump.environments.is_empty() {
+        return Err("The archive contains no Postman collections or environments".to_string());
+    }
+    Ok(dump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn reads_collections_environments_and_globals() {
+        let entries = [
+            ("archive.json", json!({ "collection": { "c1": true } })),
+            (
+                "collection/c1.json",
+                json!({
+                    "info": { "name": "Pets", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+                    "item": []
+                }),
+            ),
+            (
+                "environment/e1.json",
+                json!({
+                    "name": "Staging",
+                    "values": [
+                        { "key": "baseUrl", "value": "https://staging.example.com", "enabled": true },
+                        { "key": "old", "value": "x", "enabled": false },
+                        { "key": "retries", "value": 3 }
+                    ],
+                    "_postman_variable_scope": "environment"
+                }),
+            ),
+            (
+                "globals/g.json",
+                json!({
+                    "values": [{ "key": "token", "value": "abc", "enabled": true }],
+                    "_postman_variable_scope": "globals"
+                }),
+            ),
+            ("notes/readme.json", json!({ "hello": "world" })),
+        ];
+
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, document) in &entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(document.to_string().as_bytes()).unwrap();
+        }
+        let archive = writer.finish().unwrap();
+
+        let dump = read_postman_dump(Cursor::new(archive.into_inner())).unwrap();
+        assert_eq!(dump.collections.len(), 1);
+        assert_eq!(dump.collections[0].0, "collection/c1.json");
+
+        assert_eq!(dump.environments.len(), 2);
+        let staging = &dump.environments[0];
+        assert_eq!(staging.name, "Staging");
+        assert_eq!(
+            Value::Object(staging.variables.clone()),
+            json!({ "baseUrl": "https://staging.example.com", "retries": "3" })
+        );
+        assert_eq!(dump.environments[1].name, GLOBALS_ENVIRONMENT_NAME);
+
+        assert_eq!(dump.skipped.len(), 1);
+        assert!(dump.skipped[0].starts_with("notes/readme.json"));
+
+        assert!(read_postman_dump(Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+}
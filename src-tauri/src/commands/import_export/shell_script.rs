@@ -0,0 +1,510 @@
+//! Standalone bash scripts: a collection or folder written out as a sequence
+//! of `curl` calls, so a reproduction can be handed to someone without the
+//! app. `{{variables}}` become shell variables declared at the top of the
+//! script (and overridable from the environment), and values a test script
+//! stores with `environment.set('name', response.body.path)` are extracted
+//! from the previous response with `jq`, chaining the steps like a run.
+//!
+//! Other script logic has no shell equivalent and is noted in a comment.
+
+use super::http_file::{auth_header, example_text, parameter_examples};
+use super::storage::is_http_method;
+use super::{Collection, Endpoint, Folder};
+use crate::commands::scripts::ScriptData;
+use crate::commands::variables::variable_pattern;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// `environment.set('name', response.body...)`, with the accessor chain
+/// after `body` (or `json()`) captured.
+fn extraction_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r#"(?:pm\.)?environment\.set\(\s*['"]([^'"]+)['"]\s*,\s*(?:pm\.)?response\.(?:body|json\(\))((?:\.[A-Za-z_$][\w$]*|\[\s*(?:\d+|'[^']*'|"[^"]*")\s*\])*)\s*\)"#,
+        )
+        .unwrap()
+    })
+}
+
+fn accessor_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r#"\.([A-Za-z_$][\w$]*)|\[\s*(\d+)\s*\]|\[\s*'([^']*)'\s*\]|\[\s*"([^"]*)"\s*\]"#,
+        )
+        .unwrap()
+    })
+}
+
+/// Variables referenced by the script, in first-use order.
+#[derive(Default)]
+struct ShellVariables {
+    names: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl ShellVariables {
+    fn use_name(&mut self, name: &str) -> String {
+        if self.seen.insert(name.to_string()) {
+            self.names.push(name.to_string());
+        }
+        shell_name(name)
+    }
+}
+
+/// A valid bash identifier for an app variable name (`base-url` -> `base_url`).
+fn shell_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Appends `literal` escaped for use inside bash double quotes.
+fn escape(out: &mut String, literal: &str) {
+    for c in literal.chars() {
+        if matches!(c, '\\' | '"' | '$' | '`') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Double-quotes `text` for bash as-is, without substituting variables.
+fn quote_literal(text: &str) -> String {
+    let mut out = String::from("\"");
+    escape(&mut out, text);
+    out.push('"');
+    out
+}
+
+/// Double-quotes `text` for bash, turning each `{{name}}` into `${name}`.
+fn quote(text: &str, variables: &mut ShellVariables) -> String {
+    let mut out = String::from("\"");
+    let mut last = 0;
+    for captures in variable_pattern().captures_iter(text) {
+        let whole = captures.get(0).unwrap();
+        escape(&mut out, &text[last..whole.start()]);
+        out.push_str(&format!("${{{}}}", variables.use_name(&captures[1])));
+        last = whole.end();
+    }
+    escape(&mut out, &text[last..]);
+    out.push('"');
+    out
+}
+
+/// The jq filter for a JavaScript accessor chain (`.data.items[0]['id']`).
+fn jq_filter(accessors: &str) -> String {
+    let mut filter = String::new();
+    for captures in accessor_pattern().captures_iter(accessors) {
+        if let Some(index) = captures.get(2) {
+            filter.push_str(&format!("[{}]", index.as_str()));
+            continue;
+        }
+        let key = captures
+            .get(1)
+            .or_else(|| captures.get(3))
+            .or_else(|| captures.get(4))
+            .map(|m| m.as_str())
+            .unwrap_or_default();
+        if !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            filter.push_str(&format!(".{}", key));
+        } else {
+            filter.push_str(&format!(".{}", serde_json::json!(key)));
+        }
+    }
+    if filter.is_empty() {
+        ".".to_string()
+    } else {
+        filter
+    }
+}
+
+/// The auth that applies to an endpoint, following "inherit" to its folder
+/// and then the collection.
+fn effective_auth<'a>(
+    collection: &'a Collection,
+    folder: Option<&'a Folder>,
+    endpoint: &'a Endpoint,
+) -> Option<&'a Value> {
+    let inherits = |auth: &&Value| auth.get("type").and_then(Value::as_str) == Some("inherit");
+    match endpoint.security.as_ref() {
+        Some(auth) if inherits(&auth) => folder
+            .and_then(|f| f.auth_config.as_ref())
+            .filter(|auth| !inherits(auth))
+            .or(collection.auth_config.as_ref())
+            .filter(|auth| !inherits(auth)),
+        security => security,
+    }
+}
+
+fn write_step(
+    out: &mut String,
+    collection: &Collection,
+    folder: Option<&Folder>,
+    endpoint: &Endpoint,
+    variables: &mut ShellVariables,
+) {
+    let mut url = if endpoint.path.starts_with("http://")
+        || endpoint.path.starts_with("https://")
+        || endpoint.path.starts_with("{{")
+        || collection.base_url.is_none()
+    {
+        endpoint.path.clone()
+    } else {
+        format!("{{{{baseUrl}}}}{}", endpoint.path)
+    };
+    let query = parameter_examples(endpoint, "query");
+    if !query.is_empty() {
+        let joined = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&joined);
+    }
+
+    let mut args = Vec::new();
+    let mut headers = parameter_examples(endpoint, "header");
+    let auth = effective_auth(collection, folder, endpoint);
+    if auth.and_then(|a| a.get("type")).and_then(Value::as_str) == Some("basic") {
+        let config = auth.and_then(|a| a.get("config"));
+        let get = |key: &str| {
+            config
+                .and_then(|c| c.get(key))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        };
+        let credentials = format!("{}:{}", get("username"), get("password"));
+        args.push(format!("--user {}", quote(&credentials, variables)));
+    } else if let Some(header) = auth_header(auth) {
+        headers.push(header);
+    }
+    let has_content_type = |headers: &[(String, String)]| {
+        headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+    };
+
+    let graphql = endpoint
+        .graphql_data
+        .as_ref()
+        .filter(|g| g.get("mode").and_then(|m| m.as_str()) == Some("graphql"));
+    let body = endpoint.request_body.as_ref();
+    let form_fields = body
+        .and_then(|b| b.get("fields"))
+        .and_then(Value::as_object);
+    let form_type = body.and_then(|b| b.get("type")).and_then(Value::as_str);
+    if let Some(graphql) = graphql {
+        let query = graphql.get("query").and_then(|q| q.as_str()).unwrap_or("");
+        let mut payload = serde_json::json!({ "query": query.trim() });
+        if let Some(variables) = graphql
+            .get("variables")
+            .and_then(|v| v.as_str())
+            .and_then(|v| serde_json::from_str::<Value>(v).ok())
+        {
+            payload["variables"] = variables;
+        }
+        if !has_content_type(&headers) {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+        }
+        args.push(format!(
+            "--data-binary {}",
+            quote(&payload.to_string(), variables)
+        ));
+    } else if let (Some(fields), Some("urlencoded" | "formdata")) = (form_fields, form_type) {
+        let flag = if form_type == Some("formdata") {
+            "--form-string"
+        } else {
+            "--data-urlencode"
+        };
+        for (name, value) in fields {
+            let value = value
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string());
+            args.push(format!(
+                "{} {}",
+                flag,
+                quote(&format!("{}={}", name, value), variables)
+            ));
+        }
+    } else if let Some(example) = body
+        .and_then(|b| b.get("example"))
+        .map(example_text)
+        .filter(|text| !text.trim().is_empty())
+    {
+        if !has_content_type(&headers) && serde_json::from_str::<Value>(&example).is_ok() {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+        }
+        args.push(format!(
+            "--data-binary {}",
+            quote(example.trim_end(), variables)
+        ));
+    }
+
+    out.push_str(&format!(
+        "echo '==> {}'\n",
+        endpoint.name.replace('\'', "'\\''")
+    ));
+    if endpoint
+        .scripts
+        .as_ref()
+        .and_then(|s| s.get("preRequestScript"))
+        .and_then(Value::as_str)
+        .is_some_and(|s| !s.trim().is_empty())
+    {
+        out.push_str("# The pre-request script of this request is not exported\n");
+    }
+    out.push_str(
+        "curl --silent --show-error --output \"$response\" --write-out 'HTTP %{http_code}\\n' \\\n",
+    );
+    out.push_str(&format!(
+        "  --request {} {}",
+        endpoint.method.to_ascii_uppercase(),
+        quote(&url, variables)
+    ));
+    let mut lines: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "--header {}",
+                quote(&format!("{}: {}", name, value), variables)
+            )
+        })
+        .collect();
+    lines.extend(args);
+    for arg in lines {
+        out.push_str(&format!(" \\\n  {}", arg));
+    }
+    out.push_str("\ncat \"$response\"; echo\n");
+
+    let test_script = endpoint
+        .scripts
+        .clone()
+        .and_then(|s| serde_json::from_value::<ScriptData>(s).ok())
+        .map(|s| s.test_script)
+        .unwrap_or_default();
+    for line in test_script.lines().map(str::trim) {
+        let mut extracted = false;
+        for captures in extraction_pattern().captures_iter(line) {
+            out.push_str(&format!(
+                "{}=$(jq -r {} \"$response\")\n",
+                variables.use_name(&captures[1]),
+                quote_literal(&jq_filter(&captures[2]))
+            ));
+            extracted = true;
+        }
+        if !extracted && line.contains("environment.set(") {
+            out.push_str(&format!("# Not exported: {}\n", line));
+        }
+    }
+    out.push('\n');
+}
+
+/// Writes the collection, or one of its folders, as a bash script. Returns the
+/// script and the requests that have no `curl` form (non-HTTP endpoints).
+pub(crate) fn collection_to_shell_script(
+    collection: &Collection,
+    folder_id: Option<&str>,
+) -> Result<(String, Vec<String>), String> {
+    let folder_endpoint_ids: HashSet<&str> = collection
+        .folders
+        .iter()
+        .flat_map(|f| f.endpoints.iter().map(|e| e.id.as_str()))
+        .collect();
+    let steps: Vec<(Option<&Folder>, &Endpoint)> = match folder_id {
+        Some(folder_id) => {
+            let folder = collection
+                .folders
+                .iter()
+                .find(|f| f.id == folder_id)
+                .ok_or_else(|| format!("Folder {} not found", folder_id))?;
+            folder.endpoints.iter().map(|e| (Some(folder), e)).collect()
+        }
+        None => collection
+            .endpoints
+            .iter()
+            .filter(|e| !folder_endpoint_ids.contains(e.id.as_str()))
+            .map(|e| (None, e))
+            .chain(
+                collection
+                    .folders
+                    .iter()
+                    .flat_map(|f| f.endpoints.iter().map(move |e| (Some(f), e))),
+            )
+            .collect(),
+    };
+
+    let mut variables = ShellVariables::default();
+    let mut skipped = Vec::new();
+    let mut body = String::new();
+    let mut defaults: HashMap<String, String> = HashMap::new();
+    for (folder, endpoint) in steps {
+        if !is_http_method(&endpoint.method) {
+            skipped.push(match folder {
+                Some(folder) => format!("{}/{}", folder.name, endpoint.name),
+                None => endpoint.name.clone(),
+            });
+            continue;
+        }
+        for (name, example) in parameter_examples(endpoint, "path") {
+            defaults.entry(name).or_insert(example);
+        }
+        write_step(&mut body, collection, folder, endpoint, &mut variables);
+    }
+
+    // Collection values win over path parameter examples; secret values stay
+    // in the secret store and must come from the environment
+    for entry in collection.variables.iter().flatten() {
+        let value = if entry.secret { "" } else { &entry.value };
+        defaults.insert(entry.key.clone(), value.to_string());
+    }
+    if let Some(base_url) = collection.base_url.as_ref().filter(|s| !s.is_empty()) {
+        defaults
+            .entry("baseUrl".to_string())
+            .or_insert_with(|| base_url.clone());
+    }
+
+    let mut out = String::from("#!/usr/bin/env bash\n");
+    let title = match folder_id.and_then(|id| collection.folders.iter().find(|f| f.id == id)) {
+        Some(folder) => format!("{} / {}", collection.name, folder.name),
+        None => collection.name.clone(),
+    };
+    // A line break in a name would end the comment and run the rest
+    let title = title.replace(['\r', '\n'], " ");
+    out.push_str(&format!("# {}\n# Requires curl and jq.\n", title));
+    out.push_str("set -euo pipefail\n\n");
+    if !variables.names.is_empty() {
+        out.push_str("# Variables; any of them can be overridden from the environment\n");
+        for name in &variables.names {
+            let value = defaults.get(name).map(String::as_str).unwrap_or_default();
+            // A default is not a step: its own references are not declared
+            let quoted = quote(value, &mut ShellVariables::default());
+            out.push_str(&format!(
+                "[[ -v {0} ]] || {0}={1}\n",
+                shell_name(name),
+                quoted
+            ));
+        }
+        out.push('\n');
+    }
+    out.push_str("response=\"$(mktemp)\"\ntrap 'rm -f \"$response\"' EXIT\n\n");
+    out.push_str(&body);
+
+    Ok((out.trim_end().to_string() + "\n", skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::VariableEntry;
+    use super::*;
+    use serde_json::json;
+
+    fn endpoint(name: &str, method: &str, path: &str) -> Endpoint {
+        Endpoint {
+            id: name.to_string(),
+            name: name.to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+            description: None,
+            example_use: None,
+            deprecated: false,
+            parameters: None,
+            request_body: None,
+            responses: None,
+            security: None,
+            tags: Vec::new(),
+            scripts: None,
+            graphql_data: None,
+            examples: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn writes_a_chained_curl_script() {
+        let mut login = endpoint("Login", "POST", "/login");
+        login.request_body = Some(json!({ "example": "{\"user\": \"{{user}}\"}" }));
+        login.scripts = Some(json!({
+            "preRequestScript": "",
+            "testScript": "pm.environment.set('token', response.body.data['access-token']);\nenvironment.set('n', String(response.status));"
+        }));
+        let mut order = endpoint("Get order", "GET", "/orders/{{orderId}}");
+        order.parameters = Some(json!({ "path": { "orderId": { "example": "42" } } }));
+        order.security = Some(json!({ "type": "inherit" }));
+        let collection = Collection {
+            id: "c1".to_string(),
+            name: "Shop".to_string(),
+            description: None,
+            base_url: Some("https://api.test".to_string()),
+            endpoints: vec![login, endpoint("Feed", "WS", "wss://api.test/feed")],
+            folders: vec![Folder {
+                id: "f1".to_string(),
+                name: "Orders".to_string(),
+                endpoints: vec![order],
+                auth_config: None,
+            }],
+            variables: Some(vec![VariableEntry {
+                key: "user".to_string(),
+                value: "ann $HOME".to_string(),
+                secret: false,
+            }]),
+            auth_config: Some(json!({ "type": "bearer", "config": { "token": "{{token}}" } })),
+        };
+
+        let (script, skipped) = collection_to_shell_script(&collection, None).unwrap();
+        assert_eq!(skipped, vec!["Feed".to_string()]);
+        assert!(script.starts_with("#!/usr/bin/env bash\n"));
+        assert!(script.contains("[[ -v baseUrl ]] || baseUrl=\"https://api.test\""));
+        assert!(script.contains("[[ -v user ]] || user=\"ann \\$HOME\""));
+        assert!(script.contains("[[ -v orderId ]] || orderId=\"42\""));
+        assert!(script.contains("--request POST \"${baseUrl}/login\""));
+        assert!(script.contains("--data-binary \"{\\\"user\\\": \\\"${user}\\\"}\""));
+        assert!(script.contains("token=$(jq -r \".data.\\\"access-token\\\"\" \"$response\")"));
+        assert!(script.contains("# Not exported: environment.set('n', String(response.status));"));
+        assert!(script.contains("--header \"Authorization: Bearer ${token}\""));
+        assert!(script.find("token=$(jq").unwrap() < script.find("Bearer ${token}").unwrap());
+
+        let (folder_only, _) = collection_to_shell_script(&collection, Some("f1")).unwrap();
+        assert!(folder_only.contains("# Shop / Orders"));
+        assert!(!folder_only.contains("/login"));
+        assert!(collection_to_shell_script(&collection, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn names_and_response_keys_stay_inert() {
+        let mut login = endpoint("Login", "POST", "/login");
+        login.scripts = Some(json!({
+            "preRequestScript": "",
+            "testScript": "pm.environment.set('id', response.body[\"a' ; touch pwned ; '\"]);\npm.environment.set('v', response.body['{{x}}']);"
+        }));
+        let collection = Collection {
+            id: "c1".to_string(),
+            name: "Shop\nrm -rf ~\r".to_string(),
+            description: None,
+            base_url: Some("https://api.test".to_string()),
+            endpoints: vec![login],
+            folders: Vec::new(),
+            variables: None,
+            auth_config: None,
+        };
+
+        let (script, _) = collection_to_shell_script(&collection, None).unwrap();
+        assert!(script.contains("# Shop rm -rf ~ \n"));
+        assert!(!script.contains("\nrm -rf"));
+        assert!(script.contains("id=$(jq -r \".\\\"a' ; touch pwned ; '\\\"\" \"$response\")"));
+        assert!(script.contains("v=$(jq -r \".\\\"{{x}}\\\"\" \"$response\")"));
+        assert!(!script.contains("${x}"));
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::{Collection, VariableEntry};
 use crate::commands::collections as storage_collections;
+use crate::commands::workspaces::store_file;
 use serde::Serialize;
 use std::fs;
 use std::path::PathBuf;
@@ -12,7 +13,6 @@ use tauri_plugin_dialog::{DialogExt, FilePath};
 use tauri_plugin_store::StoreExt;
 use tokio::sync::oneshot;
 
-const STORE_FILE: &str = "resonance-store.json";
 const LAST_IMPORT_DIR_KEY: &str = "lastImportDirectory";
 const COLLECTIONS_DIR: &str = "collections";
 
@@ -25,7 +25,7 @@ pub(crate) fn is_http_method(method: &str) -> bool {
 
 /// Get the last used import directory from the store
 pub(crate) fn get_last_import_directory(app: &AppHandle) -> Option<std::path::PathBuf> {
-    let store = app.store(STORE_FILE).ok()?;
+    let store = app.store(store_file(app)).ok()?;
     let dir_str = store.get(LAST_IMPORT_DIR_KEY)?.as_str()?.to_string();
     if dir_str.is_empty() {
         return None;
@@ -41,12 +41,12 @@ pub(crate) fn get_last_import_directory(app: &AppHandle) -> Option<std::path::Pa
 /// Save the directory of a selected file to the store for next time
 pub(crate) fn save_last_import_directory(app: &AppHandle, file_path: &std::path::Path) {
     if let Some(parent) = file_path.parent() {
-        if let Ok(store) = app.store(STORE_FILE) {
+        if let Ok(store) = app.store(store_file(app)) {
             store.set(
                 LAST_IMPORT_DIR_KEY.to_string(),
                 serde_json::Value::String(parent.to_string_lossy().to_string()),
             );
-            let _ = store.save();
+            let _ = crate::commands::store::save_store(app);
         }
     }
 }
@@ -103,6 +103,11 @@ pub(crate) fn save_collection_to_files(
             open_api_spec: None,
             storage_path: None,
             storage_parent_path,
+            storage_format: None,
+            proxy: None,
+            login_flow: None,
+            safety_policy: None,
+            grpc_metadata: None,
         },
     )?;
 
@@ -132,6 +137,7 @@ fn save_collection_variables(
             variables.push(VariableEntry {
                 key: "baseUrl".to_string(),
                 value: base_url.clone(),
+                secret: false,
             });
         }
     }
@@ -194,9 +200,18 @@ pub(crate) async fn pick_import_file_with_kind(
         "postman" => {
             dialog = dialog.add_filter("Postman Collection", &["json"]);
         }
+        "postman_dump" => {
+            dialog = dialog.add_filter("Postman Data Dump", &["zip"]);
+        }
         "postman_environment" => {
             dialog = dialog.add_filter("Postman Environment", &["json"]);
         }
+        "har" => {
+            dialog = dialog.add_filter("HAR Files", &["har", "json"]);
+        }
+        "http" => {
+            dialog = dialog.add_filter("HTTP Request Files", &["http", "rest"]);
+        }
         _ => {}
     }
 
@@ -0,0 +1,158 @@
+//! IP family control for HTTP requests: force IPv4 or IPv6, or prefer one of
+//! them, when resolving the request's host. Forcing drops the other family's
+//! addresses; preferring only orders them, so the connector still races the
+//! other family (happy eyeballs) when the preferred one is slow to connect.
+//! Responses report the address the request actually went to.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::SocketAddr;
+
+/// Field of an endpoint in the collection file overriding the global setting.
+const IP_FAMILY_FIELD: &str = "ipFamily";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IpFamily {
+    /// The system's order, with happy eyeballs between families
+    #[default]
+    Auto,
+    Ipv4,
+    Ipv6,
+    PreferIpv4,
+    PreferIpv6,
+}
+
+impl IpFamily {
+    fn label(self) -> &'static str {
+        match self {
+            IpFamily::Ipv4 | IpFamily::PreferIpv4 => "IPv4",
+            IpFamily::Ipv6 | IpFamily::PreferIpv6 => "IPv6",
+            IpFamily::Auto => "IP",
+        }
+    }
+}
+
+/// The endpoint's own IP family, when it overrides the global setting.
+pub(crate) fn endpoint_ip_family(endpoint: &Value) -> Option<IpFamily> {
+    serde_json::from_value(endpoint.get(IP_FAMILY_FIELD)?.clone()).ok()
+}
+
+/// Resolved addresses filtered and ordered for `family`. The connector tries
+/// the first address's family first and falls back to the other one.
+pub(crate) fn order_addresses(mut addrs: Vec<SocketAddr>, family: IpFamily) -> Vec<SocketAddr> {
+    match family {
+        IpFamily::Auto => {}
+        IpFamily::Ipv4 => addrs.retain(SocketAddr::is_ipv4),
+        IpFamily::Ipv6 => addrs.retain(SocketAddr::is_ipv6),
+        IpFamily::PreferIpv4 => addrs.sort_by_key(SocketAddr::is_ipv6),
+        IpFamily::PreferIpv6 => addrs.sort_by_key(SocketAddr::is_ipv4),
+    }
+    addrs
+}
+
+/// The host resolved, but to no address of the forced family.
+#[derive(Debug)]
+pub(crate) struct NoAddressForFamily {
+    host: String,
+    family: IpFamily,
+}
+
+impl std::fmt::Display for NoAddressForFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} has no {} address", self.host, self.family.label())
+    }
+}
+
+impl std::error::Error for NoAddressForFamily {}
+
+/// The forced family a request failed on, if any.
+pub(crate) fn family_mismatch(error: &reqwest::Error) -> Option<String> {
+    let mut current: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = current {
+        if let Some(mismatch) = e.downcast_ref::<NoAddressForFamily>() {
+            return Some(mismatch.to_string());
+        }
+        current = e.source();
+    }
+    None
+}
+
+/// System resolver whose answers are filtered and ordered by [`IpFamily`].
+pub(crate) struct FamilyResolver(pub IpFamily);
+
+impl Resolve for FamilyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let family = self.0;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            let ordered = order_addresses(addrs, family);
+            if ordered.is_empty() {
+                let error: Box<dyn std::error::Error + Send + Sync> =
+                    Box::new(NoAddressForFamily { host, family });
+                return Err(error);
+            }
+            let addrs: Addrs = Box::new(ordered.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// The peer address of a response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteAddress {
+    pub ip: String,
+    pub port: u16,
+    /// "ipv4" or "ipv6"
+    pub family: String,
+}
+
+impl From<SocketAddr> for RemoteAddress {
+    fn from(addr: SocketAddr) -> Self {
+        // An IPv4 peer reached over a dual-stack socket shows up v4-mapped
+        let ip = addr.ip().to_canonical();
+        Self {
+            ip: ip.to_string(),
+            port: addr.port(),
+            family: if ip.is_ipv4() { "ipv4" } else { "ipv6" }.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_and_orders_addresses_by_family() {
+        let v4: SocketAddr = "93.184.216.34:443".parse().unwrap();
+        let v6: SocketAddr = "[2606:2800:220:1::1]:443".parse().unwrap();
+        let both = vec![v4, v6];
+
+        assert_eq!(order_addresses(both.clone(), IpFamily::Auto), both);
+        assert_eq!(order_addresses(both.clone(), IpFamily::Ipv4), vec![v4]);
+        assert_eq!(order_addresses(both.clone(), IpFamily::Ipv6), vec![v6]);
+        assert_eq!(
+            order_addresses(both.clone(), IpFamily::PreferIpv6),
+            vec![v6, v4]
+        );
+        assert_eq!(
+            order_addresses(vec![v6, v4], IpFamily::PreferIpv4),
+            vec![v4, v6]
+        );
+
+        let endpoint = serde_json::json!({ "id": "e1", "ipFamily": "prefer-ipv6" });
+        assert_eq!(endpoint_ip_family(&endpoint), Some(IpFamily::PreferIpv6));
+        assert_eq!(endpoint_ip_family(&serde_json::json!({ "id": "e2" })), None);
+
+        let mapped: SocketAddr = "[::ffff:10.0.0.1]:80".parse().unwrap();
+        let remote = RemoteAddress::from(mapped);
+        assert_eq!(remote.ip, "10.0.0.1");
+        assert_eq!(remote.family, "ipv4");
+        assert_eq!(RemoteAddress::from(v6).family, "ipv6");
+    }
+}
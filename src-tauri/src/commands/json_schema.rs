@@ -0,0 +1,640 @@
+//! Minimal JSON Schema validator used by script assertions.
+//!
+//! Covers the draft-07 keywords that matter for API contract checks (types,
+//! object/array/string/number constraints, combinators and local `$ref`s).
+//! Unknown keywords are ignored, as the specification requires, so schemas
+//! copied from OpenAPI documents validate without preprocessing.
+
+use serde_json::{Map, Value};
+
+/// Upper bound on `$ref` indirections followed while validating one value,
+/// so self-referencing schemas fail with an error instead of overflowing.
+const MAX_REF_DEPTH: usize = 64;
+
+/// A single validation failure, located by a JSON pointer into the instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Validate `instance` against `schema`, returning every failure found.
+/// An empty vector means the instance is valid.
+pub fn validate(instance: &Value, schema: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    validate_at(instance, schema, schema, "", 0, &mut errors);
+    errors
+}
+
+fn push(errors: &mut Vec<SchemaError>, path: &str, message: String) {
+    errors.push(SchemaError {
+        path: path.to_string(),
+        message,
+    });
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if is_integer(n) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_integer(n: &serde_json::Number) -> bool {
+    n.is_i64() || n.is_u64() || n.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false)
+}
+
+fn matches_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => matches!(value, Value::Number(n) if is_integer(n)),
+        other => type_name(value) == other,
+    }
+}
+
+/// JSON equality with numeric comparison by value (`1` equals `1.0`).
+fn json_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        (Value::Array(x), Value::Array(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(a, b)| json_equal(a, b))
+        }
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).map(|w| json_equal(v, w)).unwrap_or(false))
+        }
+        _ => a == b,
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Resolve a local reference (`#`, `#/definitions/x`, `#/$defs/x`, ...)
+/// against the root schema.
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    if pointer.is_empty() {
+        return Some(root);
+    }
+    root.pointer(pointer)
+}
+
+fn is_valid(instance: &Value, schema: &Value, root: &Value, depth: usize) -> bool {
+    let mut errors = Vec::new();
+    validate_at(instance, schema, root, "", depth, &mut errors);
+    errors.is_empty()
+}
+
+fn validate_at(
+    instance: &Value,
+    schema: &Value,
+    root: &Value,
+    path: &str,
+    depth: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            push(errors, path, "no value is allowed here".to_string());
+            return;
+        }
+        Value::Object(map) => map,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if depth >= MAX_REF_DEPTH {
+            push(
+                errors,
+                path,
+                format!("$ref nesting too deep at {}", reference),
+            );
+            return;
+        }
+        match resolve_ref(root, reference) {
+            Some(target) => validate_at(instance, target, root, path, depth + 1, errors),
+            None => push(errors, path, format!("unresolvable $ref {}", reference)),
+        }
+        // Draft-07: siblings of $ref are ignored.
+        return;
+    }
+
+    validate_generic(instance, schema, path, errors);
+    validate_combinators(instance, schema, root, path, depth, errors);
+
+    match instance {
+        Value::Object(map) => validate_object(instance, map, schema, root, path, depth, errors),
+        Value::Array(items) => validate_array(items, schema, root, path, depth, errors),
+        Value::String(s) => validate_string(s, schema, path, errors),
+        Value::Number(n) => validate_number(n, schema, path, errors),
+        _ => {}
+    }
+}
+
+fn validate_generic(
+    instance: &Value,
+    schema: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(list) => list.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+        if !allowed.is_empty()
+            && !allowed.iter().any(|t| matches_type(instance, t))
+            && !(nullable && instance.is_null())
+        {
+            push(
+                errors,
+                path,
+                format!(
+                    "expected type {} but got {}",
+                    allowed.join(" | "),
+                    type_name(instance)
+                ),
+            );
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.iter().any(|o| json_equal(o, instance)) {
+            push(
+                errors,
+                path,
+                format!(
+                    "value {} is not one of {}",
+                    instance,
+                    Value::Array(options.clone())
+                ),
+            );
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if !json_equal(expected, instance) {
+            push(
+                errors,
+                path,
+                format!("expected constant {} but got {}", expected, instance),
+            );
+        }
+    }
+}
+
+fn validate_combinators(
+    instance: &Value,
+    schema: &Map<String, Value>,
+    root: &Value,
+    path: &str,
+    depth: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for sub in all {
+            validate_at(instance, sub, root, path, depth, errors);
+        }
+    }
+
+    if let Some(Value::Array(any)) = schema.get("anyOf") {
+        if !any.iter().any(|sub| is_valid(instance, sub, root, depth)) {
+            push(
+                errors,
+                path,
+                "value does not match any schema in anyOf".to_string(),
+            );
+        }
+    }
+
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        let matching = one
+            .iter()
+            .filter(|sub| is_valid(instance, sub, root, depth))
+            .count();
+        if matching != 1 {
+            push(
+                errors,
+                path,
+                format!(
+                    "value matches {} schemas in oneOf, expected exactly 1",
+                    matching
+                ),
+            );
+        }
+    }
+
+    if let Some(not) = schema.get("not") {
+        if is_valid(instance, not, root, depth) {
+            push(
+                errors,
+                path,
+                "value must not match the schema in not".to_string(),
+            );
+        }
+    }
+
+    if let Some(condition) = schema.get("if") {
+        let branch = if is_valid(instance, condition, root, depth) {
+            schema.get("then")
+        } else {
+            schema.get("else")
+        };
+        if let Some(branch) = branch {
+            validate_at(instance, branch, root, path, depth, errors);
+        }
+    }
+}
+
+fn validate_object(
+    instance: &Value,
+    map: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    root: &Value,
+    path: &str,
+    depth: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !map.contains_key(key) {
+                push(errors, path, format!("missing required property '{}'", key));
+            }
+        }
+    }
+
+    if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+        if (map.len() as u64) < min {
+            push(
+                errors,
+                path,
+                format!("expected at least {} properties", min),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+        if (map.len() as u64) > max {
+            push(errors, path, format!("expected at most {} properties", max));
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let patterns: Vec<(regex::Regex, &Value)> = schema
+        .get("patternProperties")
+        .and_then(Value::as_object)
+        .map(|p| {
+            p.iter()
+                .filter_map(|(k, v)| regex::Regex::new(k).ok().map(|re| (re, v)))
+                .collect()
+        })
+        .unwrap_or_default();
+    let additional = schema.get("additionalProperties");
+
+    for (key, value) in map {
+        let child_path = format!("{}/{}", path, escape_pointer_token(key));
+        let mut matched = false;
+
+        if let Some(sub) = properties.and_then(|p| p.get(key)) {
+            matched = true;
+            validate_at(value, sub, root, &child_path, depth, errors);
+        }
+        for (re, sub) in &patterns {
+            if re.is_match(key) {
+                matched = true;
+                validate_at(value, sub, root, &child_path, depth, errors);
+            }
+        }
+
+        if !matched {
+            match additional {
+                Some(Value::Bool(false)) => {
+                    push(errors, path, format!("unexpected property '{}'", key));
+                }
+                Some(sub @ Value::Object(_)) => {
+                    validate_at(value, sub, root, &child_path, depth, errors);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(names) = schema.get("propertyNames") {
+        for key in map.keys() {
+            let key_value = Value::String(key.clone());
+            if !is_valid(&key_value, names, root, depth) {
+                push(
+                    errors,
+                    path,
+                    format!("property name '{}' is not allowed", key),
+                );
+            }
+        }
+    }
+
+    if let Some(Value::Object(dependencies)) = schema.get("dependencies") {
+        for (key, dependency) in dependencies {
+            if !map.contains_key(key) {
+                continue;
+            }
+            match dependency {
+                Value::Array(needed) => {
+                    for other in needed.iter().filter_map(Value::as_str) {
+                        if !map.contains_key(other) {
+                            push(
+                                errors,
+                                path,
+                                format!("property '{}' requires property '{}'", key, other),
+                            );
+                        }
+                    }
+                }
+                sub => validate_at(instance, sub, root, path, depth, errors),
+            }
+        }
+    }
+}
+
+fn validate_array(
+    items: &[Value],
+    schema: &Map<String, Value>,
+    root: &Value,
+    path: &str,
+    depth: usize,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            push(
+                errors,
+                path,
+                format!("expected at least {} items but got {}", min, items.len()),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if (items.len() as u64) > max {
+            push(
+                errors,
+                path,
+                format!("expected at most {} items but got {}", max, items.len()),
+            );
+        }
+    }
+
+    if schema.get("uniqueItems").and_then(Value::as_bool) == Some(true) {
+        for (i, a) in items.iter().enumerate() {
+            if items[..i].iter().any(|b| json_equal(a, b)) {
+                push(errors, path, format!("item {} is a duplicate", i));
+                break;
+            }
+        }
+    }
+
+    match schema.get("items") {
+        Some(Value::Array(tuple)) => {
+            for (i, (item, sub)) in items.iter().zip(tuple).enumerate() {
+                validate_at(item, sub, root, &format!("{}/{}", path, i), depth, errors);
+            }
+            if let Some(extra) = schema.get("additionalItems") {
+                for (i, item) in items.iter().enumerate().skip(tuple.len()) {
+                    validate_at(item, extra, root, &format!("{}/{}", path, i), depth, errors);
+                }
+            }
+        }
+        Some(sub) => {
+            for (i, item) in items.iter().enumerate() {
+                validate_at(item, sub, root, &format!("{}/{}", path, i), depth, errors);
+            }
+        }
+        None => {}
+    }
+
+    if let Some(contains) = schema.get("contains") {
+        if !items
+            .iter()
+            .any(|item| is_valid(item, contains, root, depth))
+        {
+            push(
+                errors,
+                path,
+                "no item matches the schema in contains".to_string(),
+            );
+        }
+    }
+}
+
+fn validate_string(
+    s: &str,
+    schema: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    let length = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            push(
+                errors,
+                path,
+                format!("expected at least {} characters", min),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            push(errors, path, format!("expected at most {} characters", max));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match regex::Regex::new(pattern) {
+            Ok(re) if !re.is_match(s) => {
+                push(
+                    errors,
+                    path,
+                    format!("'{}' does not match pattern {}", s, pattern),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => push(errors, path, format!("invalid pattern {}: {}", pattern, e)),
+        }
+    }
+    if let Some(format) = schema.get("format").and_then(Value::as_str) {
+        if !matches_format(s, format) {
+            push(errors, path, format!("'{}' is not a valid {}", s, format));
+        }
+    }
+}
+
+/// Check the common string formats; unknown formats are accepted.
+fn matches_format(s: &str, format: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(s).is_ok(),
+        "date" => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok(),
+        "email" => {
+            let mut parts = s.splitn(2, '@');
+            let local = parts.next().unwrap_or("");
+            let domain = parts.next().unwrap_or("");
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+        }
+        "uri" | "url" => url::Url::parse(s).is_ok(),
+        "uuid" => uuid::Uuid::parse_str(s).is_ok(),
+        "ipv4" => s.parse::<std::net::Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<std::net::Ipv6Addr>().is_ok(),
+        _ => true,
+    }
+}
+
+fn validate_number(
+    n: &serde_json::Number,
+    schema: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    let Some(value) = n.as_f64() else {
+        return;
+    };
+
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        // OpenAPI 3.0 spells exclusive bounds as booleans next to minimum/maximum.
+        let exclusive = schema.get("exclusiveMinimum").and_then(Value::as_bool) == Some(true);
+        if value < min || (exclusive && value == min) {
+            push(
+                errors,
+                path,
+                format!("{} is less than the minimum {}", value, min),
+            );
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+        let exclusive = schema.get("exclusiveMaximum").and_then(Value::as_bool) == Some(true);
+        if value > max || (exclusive && value == max) {
+            push(
+                errors,
+                path,
+                format!("{} is greater than the maximum {}", value, max),
+            );
+        }
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64) {
+        if value <= min {
+            push(
+                errors,
+                path,
+                format!("{} must be greater than {}", value, min),
+            );
+        }
+    }
+    if let Some(max) = schema.get("exclusiveMaximum").and_then(Value::as_f64) {
+        if value >= max {
+            push(errors, path, format!("{} must be less than {}", value, max));
+        }
+    }
+    if let Some(divisor) = schema.get("multipleOf").and_then(Value::as_f64) {
+        if divisor > 0.0 {
+            let quotient = value / divisor;
+            if (quotient - quotient.round()).abs() > 1e-9 {
+                push(
+                    errors,
+                    path,
+                    format!("{} is not a multiple of {}", value, divisor),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "email"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "email": { "type": "string", "format": "email" },
+                "tags": { "type": "array", "items": { "type": "string" }, "uniqueItems": true },
+                "role": { "enum": ["admin", "user"] }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn valid_instance_has_no_errors() {
+        let instance = json!({ "id": 3, "email": "a@b.io", "tags": ["x", "y"], "role": "user" });
+        assert!(validate(&instance, &user_schema()).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_required_and_wrong_types_with_paths() {
+        let instance = json!({ "id": "3", "tags": ["x", 1], "extra": true });
+        let errors = validate(&instance, &user_schema());
+        let rendered: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(rendered.contains(&"/: missing required property 'email'".to_string()));
+        assert!(rendered.contains(&"/id: expected type integer but got string".to_string()));
+        assert!(rendered.contains(&"/tags/1: expected type string but got integer".to_string()));
+        assert!(rendered.contains(&"/: unexpected property 'extra'".to_string()));
+    }
+
+    #[test]
+    fn resolves_local_refs_and_combinators() {
+        let schema = json!({
+            "definitions": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$ref": "#/definitions/node" } }
+                    },
+                    "oneOf": [{ "required": ["leaf"] }, { "required": ["children"] }]
+                }
+            },
+            "$ref": "#/definitions/node"
+        });
+        let ok = json!({ "children": [{ "leaf": 1 }, { "children": [] }] });
+        assert!(validate(&ok, &schema).is_empty());
+
+        let bad = json!({ "children": [{ "other": 1 }] });
+        let errors = validate(&bad, &schema);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "/children/0");
+    }
+
+    #[test]
+    fn integer_accepts_whole_floats_and_numbers_compare_by_value() {
+        assert!(validate(&json!(2.0), &json!({ "type": "integer" })).is_empty());
+        assert!(validate(&json!(1), &json!({ "const": 1.0 })).is_empty());
+        assert!(!validate(&json!(2.5), &json!({ "type": "integer" })).is_empty());
+    }
+
+    #[test]
+    fn self_referencing_schema_fails_instead_of_overflowing() {
+        let schema = json!({ "$ref": "#" });
+        let errors = validate(&json!(1), &schema);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("too deep"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Formatting and browsing of large JSON responses.
+//!
+//! Pretty-printing and building a tree of a multi-megabyte response in the
+//! webview blocks the UI, so large responses are handed to the backend
+//! instead: `json_document_open` keeps the parsed document (per response tab)
+//! and returns its formatted text, and `json_document_page` returns one page of
+//! the children of a node at a time, so the tree view only ever renders what
+//! is expanded.
+
+use serde::Serialize;
+use serde_json::ser::{PrettyFormatter, Serializer};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+/// Documents kept at once; the oldest is dropped when another is opened.
+const MAX_DOCUMENTS: usize = 8;
+const DEFAULT_PAGE_SIZE: usize = 200;
+const MAX_PREVIEW_CHARS: usize = 200;
+
+#[derive(Default)]
+pub struct JsonDocumentState {
+    documents: Mutex<VecDeque<(String, Arc<Value>)>>,
+}
+
+impl JsonDocumentState {
+    fn insert(&self, id: String, document: Arc<Value>) {
+        let mut documents = self.documents.lock().unwrap();
+        documents.retain(|(existing, _)| *existing != id);
+        if documents.len() >= MAX_DOCUMENTS {
+            documents.pop_front();
+        }
+        documents.push_back((id, document));
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<Value>> {
+        let documents = self.documents.lock().unwrap();
+        documents
+            .iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, document)| document.clone())
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonNode {
+    /// Object key, or `None` for array elements
+    pub key: Option<String>,
+    /// JSON Pointer of the node, used to page its children
+    pub pointer: String,
+    /// "object", "array", "string", "number", "boolean" or "null"
+    pub kind: &'static str,
+    /// The value of a scalar (as JSON, shortened) or a summary of a container
+    pub preview: String,
+    pub child_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonNodePage {
+    pub pointer: String,
+    pub kind: &'static str,
+    /// Number of children of the node
+    pub total: usize,
+    pub offset: usize,
+    pub children: Vec<JsonNode>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDocument {
+    pub formatted: String,
+    pub root: JsonNodePage,
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    }
+}
+
+fn child_count(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => map.len(),
+        Value::Array(items) => items.len(),
+        _ => 0,
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
+
+fn preview(value: &Value) -> String {
+    match value {
+        Value::Object(map) => format!("{{{}}}", count(map.len(), "key")),
+        Value::Array(items) => format!("[{}]", count(items.len(), "item")),
+        scalar => {
+            let text = scalar.to_string();
+            if text.chars().count() > MAX_PREVIEW_CHARS {
+                let shortened: String = text.chars().take(MAX_PREVIEW_CHARS).collect();
+                format!("{}…", shortened)
+            } else {
+                text
+            }
+        }
+    }
+}
+
+/// Escape a key for use as a JSON Pointer segment (RFC 6901).
+fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn node(key: Option<String>, pointer: String, value: &Value) -> JsonNode {
+    JsonNode {
+        key,
+        pointer,
+        kind: kind_of(value),
+        preview: preview(value),
+        child_count: child_count(value),
+    }
+}
+
+/// One page of the children of the node at `pointer` ("" is the root).
+fn page(
+    document: &Value,
+    pointer: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<JsonNodePage, String> {
+    let value = document
+        .pointer(pointer)
+        .ok_or_else(|| format!("No JSON value at {}", pointer))?;
+    let children = match value {
+        Value::Object(map) => map
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(key, child)| {
+                let pointer = format!("{}/{}", pointer, pointer_segment(key));
+                node(Some(key.clone()), pointer, child)
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(limit)
+            .map(|(index, child)| node(None, format!("{}/{}", pointer, index), child))
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(JsonNodePage {
+        pointer: pointer.to_string(),
+        kind: kind_of(value),
+        total: child_count(value),
+        offset,
+        children,
+    })
+}
+
+fn format_json(value: &Value, indent: usize) -> Result<String, String> {
+    let indent = " ".repeat(indent);
+    let mut out = Vec::new();
+    let mut serializer =
+        Serializer::with_formatter(&mut out, PrettyFormatter::with_indent(indent.as_bytes()));
+    serde::Serialize::serialize(value, &mut serializer)
+        .map_err(|e| format!("Failed to format JSON: {}", e))?;
+    String::from_utf8(out).map_err(|e| format!("Failed to format JSON: {}", e))
+}
+
+/// Parse and pretty-print a response body and keep it for paging under `id`.
+/// `data` is either the parsed body or its raw text.
+#[tauri::command]
+pub async fn json_document_open(
+    state: State<'_, JsonDocumentState>,
+    id: String,
+    data: Value,
+    indent: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<JsonDocument, String> {
+    let (formatted, root, document) = tokio::task::spawn_blocking(move || {
+        let document = match data {
+            Value::String(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("Response is not valid JSON: {}", e))?,
+            value => value,
+        };
+        let formatted = format_json(&document, indent.unwrap_or(2))?;
+        let root = page(&document, "", 0, page_size.unwrap_or(DEFAULT_PAGE_SIZE))?;
+        Ok::<_, String>((formatted, root, document))
+    })
+    .await
+    .map_err(|e| format!("Failed to format JSON: {}", e))??;
+    state.insert(id, Arc::new(document));
+    Ok(JsonDocument { formatted, root })
+}
+
+/// A page of the children of the node at `pointer` in an open document.
+#[tauri::command]
+pub fn json_document_page(
+    state: State<'_, JsonDocumentState>,
+    id: String,
+    pointer: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<JsonNodePage, String> {
+    let document = state
+        .get(&id)
+        .ok_or_else(|| "This response is no longer open".to_string())?;
+    page(
+        &document,
+        &pointer,
+        offset.unwrap_or(0),
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pages_list_children_with_pointers_and_summaries() {
+        let document = json!({
+            "items": [{ "id": 1 }, { "id": 2 }, { "id": 3 }],
+            "a/b": "x".repeat(500),
+            "ok": true
+        });
+
+        let root = page(&document, "", 0, 10).unwrap();
+        assert_eq!(root.kind, "object");
+        assert_eq!(root.total, 3);
+        let items = root
+            .children
+            .iter()
+            .find(|n| n.key.as_deref() == Some("items"))
+            .unwrap();
+        assert_eq!((items.kind, items.child_count), ("array", 3));
+        assert_eq!(items.preview, "[3 items]");
+        let escaped = root
+            .children
+            .iter()
+            .find(|n| n.key.as_deref() == Some("a/b"))
+            .unwrap();
+        assert_eq!(escaped.pointer, "/a~1b");
+        assert!(escaped.preview.ends_with('…'));
+
+        let second = page(&document, "/items", 1, 1).unwrap();
+        assert_eq!(second.total, 3);
+        assert_eq!(second.children.len(), 1);
+        assert_eq!(second.children[0].pointer, "/items/1");
+        assert_eq!(second.children[0].preview, "{1 key}");
+
+        assert!(page(&document, "/missing", 0, 10).is_err());
+        assert_eq!(
+            format_json(&json!({ "a": [1] }), 4).unwrap(),
+            "{\n    \"a\": [\n        1\n    ]\n}"
+        );
+    }
+}
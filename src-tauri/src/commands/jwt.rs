@@ -0,0 +1,556 @@
+//! JWT helpers: decode received tokens, verify their signature against a
+//! secret, a public key or a JWKS endpoint, and mint signed test tokens.
+
+use std::io::Cursor;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use ring::hmac;
+use ring::rand::SystemRandom;
+use ring::signature::{self, RsaKeyPair, RsaParameters, RsaPublicKeyComponents, UnparsedPublicKey};
+use rustls::pki_types::PrivateKeyDer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::State;
+
+use super::oauth::token_client;
+use super::proxy::ProxyState;
+use super::tls::der_element;
+
+/// DER content of the rsaEncryption OID (1.2.840.113549.1.1.1).
+const RSA_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+/// DER content of the id-ecPublicKey OID (1.2.840.10045.2.1).
+const EC_OID: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// Key material to verify a token with. Every non-empty field is tried.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtKey {
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub secret_base64: bool,
+    /// PEM public key, a single JWK or a JWKS document.
+    #[serde(default)]
+    pub public_key: Option<String>,
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtVerification {
+    pub valid: bool,
+    /// `kid` of the key that verified the signature.
+    pub key_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedJwt {
+    pub header: Value,
+    pub claims: Value,
+    pub signature: String,
+    pub algorithm: Option<String>,
+    /// `iat`, `exp` and `nbf` in seconds since the epoch.
+    pub issued_at: Option<i64>,
+    pub expires_at: Option<i64>,
+    pub not_before: Option<i64>,
+    pub expired: bool,
+    /// Present when a key was given.
+    pub verification: Option<JwtVerification>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtMintOptions {
+    /// HS256/HS384/HS512 or RS256/RS384/RS512.
+    pub algorithm: String,
+    /// Extra header fields such as `kid`; `alg` and `typ` are filled in.
+    #[serde(default)]
+    pub header: Map<String, Value>,
+    pub claims: Value,
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub secret_base64: bool,
+    /// PEM RSA private key (PKCS#1 or PKCS#8).
+    #[serde(default)]
+    pub private_key: Option<String>,
+    /// Sets `exp` this many seconds ahead, and `iat` unless the claims have one.
+    #[serde(default)]
+    pub expires_in: Option<i64>,
+}
+
+enum VerifyKey {
+    Secret(Vec<u8>),
+    Rsa {
+        n: Vec<u8>,
+        e: Vec<u8>,
+    },
+    /// Uncompressed EC point (`0x04 || x || y`).
+    Ec(Vec<u8>),
+}
+
+struct CandidateKey {
+    kid: Option<String>,
+    key: VerifyKey,
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, String> {
+    URL_SAFE_NO_PAD
+        .decode(segment.trim_end_matches('='))
+        .map_err(|e| format!("Invalid base64url: {}", e))
+}
+
+fn decode_json(segment: &str, part: &str) -> Result<Value, String> {
+    let bytes = decode_segment(segment).map_err(|e| format!("Invalid JWT {}: {}", part, e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Invalid JWT {}: {}", part, e))
+}
+
+fn encode_json(value: &Map<String, Value>) -> Result<String, String> {
+    let json = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+/// Split a token (optionally prefixed with `Bearer `) into its three parts.
+fn split_token(token: &str) -> Result<(&str, &str, &str), String> {
+    let token = token.trim();
+    let token = token.strip_prefix("Bearer ").unwrap_or(token).trim();
+    let parts: Vec<&str> = token.split('.').collect();
+    match parts.as_slice() {
+        [header, claims, signature] => Ok((*header, *claims, *signature)),
+        _ => Err("Not a signed JWT: expected three dot-separated parts".to_string()),
+    }
+}
+
+fn secret_bytes(secret: &str, base64: bool) -> Result<Vec<u8>, String> {
+    if !base64 {
+        return Ok(secret.as_bytes().to_vec());
+    }
+    STANDARD
+        .decode(secret.trim())
+        .or_else(|_| decode_segment(secret.trim()))
+        .map_err(|_| "The secret is not valid base64".to_string())
+}
+
+fn hmac_algorithm(alg: &str) -> Option<hmac::Algorithm> {
+    match alg {
+        "HS256" => Some(hmac::HMAC_SHA256),
+        "HS384" => Some(hmac::HMAC_SHA384),
+        "HS512" => Some(hmac::HMAC_SHA512),
+        _ => None,
+    }
+}
+
+fn rsa_parameters(alg: &str) -> Option<&'static RsaParameters> {
+    match alg {
+        "RS256" => Some(&signature::RSA_PKCS1_2048_8192_SHA256),
+        "RS384" => Some(&signature::RSA_PKCS1_2048_8192_SHA384),
+        "RS512" => Some(&signature::RSA_PKCS1_2048_8192_SHA512),
+        "PS256" => Some(&signature::RSA_PSS_2048_8192_SHA256),
+        "PS384" => Some(&signature::RSA_PSS_2048_8192_SHA384),
+        "PS512" => Some(&signature::RSA_PSS_2048_8192_SHA512),
+        _ => None,
+    }
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+/// Parse a PKCS#1 `RSAPublicKey` into its modulus and exponent.
+fn parse_rsa_public_key(der: &[u8]) -> Option<VerifyKey> {
+    let (0x30, sequence, _) = der_element(der)? else {
+        return None;
+    };
+    let (0x02, n, rest) = der_element(sequence)? else {
+        return None;
+    };
+    let (0x02, e, _) = der_element(rest)? else {
+        return None;
+    };
+    Some(VerifyKey::Rsa {
+        n: strip_leading_zeros(n),
+        e: strip_leading_zeros(e),
+    })
+}
+
+/// Parse a DER `SubjectPublicKeyInfo` holding an RSA or EC key.
+fn parse_spki(der: &[u8]) -> Result<VerifyKey, String> {
+    let invalid = || "Invalid public key".to_string();
+    let Some((0x30, spki, _)) = der_element(der) else {
+        return Err(invalid());
+    };
+    let Some((0x30, algorithm, rest)) = der_element(spki) else {
+        return Err(invalid());
+    };
+    let Some((0x06, oid, _)) = der_element(algorithm) else {
+        return Err(invalid());
+    };
+    // The BIT STRING starts with its unused-bits count.
+    let Some((0x03, bits, _)) = der_element(rest) else {
+        return Err(invalid());
+    };
+    let key = bits.get(1..).ok_or_else(invalid)?;
+    match oid {
+        RSA_OID => parse_rsa_public_key(key).ok_or_else(invalid),
+        EC_OID => Ok(VerifyKey::Ec(key.to_vec())),
+        _ => Err("Unsupported public key type; expected RSA or EC".to_string()),
+    }
+}
+
+fn parse_jwk(jwk: &Value) -> Result<CandidateKey, String> {
+    let field = |name: &str| {
+        jwk.get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("The JWK has no \"{}\"", name))
+            .and_then(decode_segment)
+    };
+    let key = match jwk.get("kty").and_then(Value::as_str) {
+        Some("RSA") => VerifyKey::Rsa {
+            n: strip_leading_zeros(&field("n")?),
+            e: strip_leading_zeros(&field("e")?),
+        },
+        Some("EC") => {
+            let mut point = vec![0x04];
+            point.extend(field("x")?);
+            point.extend(field("y")?);
+            VerifyKey::Ec(point)
+        }
+        Some("oct") => VerifyKey::Secret(field("k")?),
+        other => {
+            return Err(format!(
+                "Unsupported JWK key type: {}",
+                other.unwrap_or("none")
+            ))
+        }
+    };
+    Ok(CandidateKey {
+        kid: jwk.get("kid").and_then(Value::as_str).map(String::from),
+        key,
+    })
+}
+
+/// The keys of a JWKS document (`{"keys": [...]}`) or of a single JWK.
+fn parse_jwk_set(value: &Value) -> Result<Vec<CandidateKey>, String> {
+    match value.get("keys").and_then(Value::as_array) {
+        Some(keys) => Ok(keys.iter().filter_map(|jwk| parse_jwk(jwk).ok()).collect()),
+        None => Ok(vec![parse_jwk(value)?]),
+    }
+}
+
+fn parse_public_keys(text: &str) -> Result<Vec<CandidateKey>, String> {
+    let text = text.trim();
+    if text.starts_with("-----BEGIN") {
+        let keys = rustls_pemfile::public_keys(&mut Cursor::new(text.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid PEM: {}", e))?;
+        if keys.is_empty() {
+            return Err("No PUBLIC KEY block found in the PEM".to_string());
+        }
+        return keys
+            .iter()
+            .map(|key| {
+                Ok(CandidateKey {
+                    kid: None,
+                    key: parse_spki(key.as_ref())?,
+                })
+            })
+            .collect();
+    }
+    let value: Value = serde_json::from_str(text)
+        .map_err(|e| format!("The public key is neither PEM nor JWK JSON: {}", e))?;
+    parse_jwk_set(&value)
+}
+
+async fn fetch_jwks(proxy_state: &ProxyState, url: &str) -> Result<Vec<CandidateKey>, String> {
+    proxy_state.check_offline(url)?;
+    let response = token_client(proxy_state)?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch JWKS: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "JWKS request failed with status {}",
+            response.status()
+        ));
+    }
+    let value: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid JWKS document: {}", e))?;
+    parse_jwk_set(&value)
+}
+
+async fn candidate_keys(
+    proxy_state: &ProxyState,
+    key: &JwtKey,
+) -> Result<Vec<CandidateKey>, String> {
+    let mut keys = Vec::new();
+    if let Some(secret) = key.secret.as_deref().filter(|s| !s.is_empty()) {
+        keys.push(CandidateKey {
+            kid: None,
+            key: VerifyKey::Secret(secret_bytes(secret, key.secret_base64)?),
+        });
+    }
+    if let Some(public_key) = key.public_key.as_deref().filter(|s| !s.trim().is_empty()) {
+        keys.extend(parse_public_keys(public_key)?);
+    }
+    if let Some(url) = key.jwks_url.as_deref().filter(|s| !s.trim().is_empty()) {
+        keys.extend(fetch_jwks(proxy_state, url.trim()).await?);
+    }
+    Ok(keys)
+}
+
+fn verify_signature(
+    alg: &str,
+    key: &VerifyKey,
+    signing_input: &[u8],
+    signature: &[u8],
+) -> Result<(), String> {
+    let mismatch = |_| "Signature does not match".to_string();
+    match key {
+        VerifyKey::Secret(secret) => {
+            let algorithm = hmac_algorithm(alg)
+                .ok_or_else(|| format!("A {} token cannot be verified with a secret", alg))?;
+            hmac::verify(&hmac::Key::new(algorithm, secret), signing_input, signature)
+                .map_err(mismatch)
+        }
+        VerifyKey::Rsa { n, e } => {
+            let parameters = rsa_parameters(alg)
+                .ok_or_else(|| format!("A {} token cannot be verified with an RSA key", alg))?;
+            RsaPublicKeyComponents { n, e }
+                .verify(parameters, signing_input, signature)
+                .map_err(mismatch)
+        }
+        VerifyKey::Ec(point) => {
+            let algorithm = match alg {
+                "ES256" => &signature::ECDSA_P256_SHA256_FIXED,
+                "ES384" => &signature::ECDSA_P384_SHA384_FIXED,
+                _ => return Err(format!("A {} token cannot be verified with an EC key", alg)),
+            };
+            UnparsedPublicKey::new(algorithm, point)
+                .verify(signing_input, signature)
+                .map_err(mismatch)
+        }
+    }
+}
+
+/// Try every candidate whose `kid` matches the token's.
+fn verify_with_keys(
+    alg: &str,
+    kid: Option<&str>,
+    keys: &[CandidateKey],
+    signing_input: &[u8],
+    signature: &[u8],
+) -> JwtVerification {
+    let failed = |error: String| JwtVerification {
+        valid: false,
+        key_id: None,
+        error: Some(error),
+    };
+    if alg == "none" {
+        return failed("The token is unsigned (alg \"none\")".to_string());
+    }
+    let mut last_error = match kid {
+        Some(kid) => format!("No key matches kid \"{}\"", kid),
+        None => "No key to verify with".to_string(),
+    };
+    for candidate in keys {
+        if kid.is_some() && candidate.kid.is_some() && candidate.kid.as_deref() != kid {
+            continue;
+        }
+        match verify_signature(alg, &candidate.key, signing_input, signature) {
+            Ok(()) => {
+                return JwtVerification {
+                    valid: true,
+                    key_id: candidate.kid.clone(),
+                    error: None,
+                }
+            }
+            Err(e) => last_error = e,
+        }
+    }
+    failed(last_error)
+}
+
+fn numeric_claim(claims: &Value, name: &str) -> Option<i64> {
+    let value = claims.get(name)?;
+    value.as_i64().or_else(|| value.as_f64().map(|v| v as i64))
+}
+
+fn sign(options: &JwtMintOptions, signing_input: &[u8]) -> Result<Vec<u8>, String> {
+    let alg = options.algorithm.as_str();
+    if let Some(algorithm) = hmac_algorithm(alg) {
+        let secret = options
+            .secret
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| format!("{} signing needs a secret", alg))?;
+        let key = hmac::Key::new(algorithm, &secret_bytes(secret, options.secret_base64)?);
+        return Ok(hmac::sign(&key, signing_input).as_ref().to_vec());
+    }
+
+    let encoding: &'static dyn signature::RsaEncoding = match alg {
+        "RS256" => &signature::RSA_PKCS1_SHA256,
+        "RS384" => &signature::RSA_PKCS1_SHA384,
+        "RS512" => &signature::RSA_PKCS1_SHA512,
+        _ => return Err(format!("Unsupported signing algorithm: {}", alg)),
+    };
+    let pem = options
+        .private_key
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| format!("{} signing needs a PEM private key", alg))?;
+    let key = rustls_pemfile::private_key(&mut Cursor::new(pem.as_bytes()))
+        .map_err(|e| format!("Invalid PEM: {}", e))?
+        .ok_or_else(|| "No private key found in the PEM".to_string())?;
+    let key_pair = match &key {
+        PrivateKeyDer::Pkcs1(key) => RsaKeyPair::from_der(key.secret_pkcs1_der()),
+        PrivateKeyDer::Pkcs8(key) => RsaKeyPair::from_pkcs8(key.secret_pkcs8_der()),
+        _ => return Err(format!("{} signing needs an RSA private key", alg)),
+    }
+    .map_err(|e| format!("Invalid RSA private key: {}", e))?;
+
+    let mut signature = vec![0; key_pair.public().modulus_len()];
+    key_pair
+        .sign(
+            encoding,
+            &SystemRandom::new(),
+            signing_input,
+            &mut signature,
+        )
+        .map_err(|_| "Failed to sign the token".to_string())?;
+    Ok(signature)
+}
+
+fn mint(options: &JwtMintOptions, now: i64) -> Result<String, String> {
+    let Value::Object(mut claims) = options.claims.clone() else {
+        return Err("JWT claims must be a JSON object".to_string());
+    };
+    if let Some(expires_in) = options.expires_in {
+        claims.entry("iat").or_insert(Value::from(now));
+        claims.insert("exp".to_string(), Value::from(now + expires_in));
+    }
+    let mut header = options.header.clone();
+    header.insert("alg".to_string(), Value::from(options.algorithm.as_str()));
+    header.entry("typ").or_insert(Value::from("JWT"));
+
+    let signing_input = format!("{}.{}", encode_json(&header)?, encode_json(&claims)?);
+    let signature = sign(options, signing_input.as_bytes())?;
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Decode a JWT and, when `key` is given, verify its signature.
+#[tauri::command]
+pub async fn jwt_decode(
+    proxy_state: State<'_, ProxyState>,
+    token: String,
+    key: Option<JwtKey>,
+) -> Result<DecodedJwt, String> {
+    let (header_part, claims_part, signature_part) = split_token(&token)?;
+    let header = decode_json(header_part, "header")?;
+    let claims = decode_json(claims_part, "claims")?;
+    let signature =
+        decode_segment(signature_part).map_err(|e| format!("Invalid JWT signature: {}", e))?;
+    let algorithm = header.get("alg").and_then(Value::as_str).map(String::from);
+
+    let verification = match key {
+        Some(key) => {
+            let signing_input = format!("{}.{}", header_part, claims_part);
+            let kid = header.get("kid").and_then(Value::as_str);
+            Some(match candidate_keys(&proxy_state, &key).await {
+                Ok(keys) => verify_with_keys(
+                    algorithm.as_deref().unwrap_or("none"),
+                    kid,
+                    &keys,
+                    signing_input.as_bytes(),
+                    &signature,
+                ),
+                Err(e) => JwtVerification {
+                    valid: false,
+                    key_id: None,
+                    error: Some(e),
+                },
+            })
+        }
+        None => None,
+    };
+
+    let expires_at = numeric_claim(&claims, "exp");
+    let expired = match expires_at {
+        Some(exp) => exp <= chrono::Utc::now().timestamp(),
+        None => false,
+    };
+    Ok(DecodedJwt {
+        issued_at: numeric_claim(&claims, "iat"),
+        not_before: numeric_claim(&claims, "nbf"),
+        expires_at,
+        expired,
+        header,
+        claims,
+        signature: signature_part.to_string(),
+        algorithm,
+        verification,
+    })
+}
+
+/// Sign `options.claims` into a test token.
+#[tauri::command]
+pub async fn jwt_generate(options: JwtMintOptions) -> Result<String, String> {
+    mint(&options, chrono::Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minted_hs256_tokens_verify_only_with_their_secret() {
+        let options: JwtMintOptions = serde_json::from_value(serde_json::json!({
+            "algorithm": "HS256",
+            "header": { "kid": "k1" },
+            "claims": { "sub": "user-1" },
+            "secret": "s3cret",
+            "expiresIn": 60
+        }))
+        .unwrap();
+        let token = mint(&options, 1_000).unwrap();
+
+        let (header_part, claims_part, signature_part) = split_token(&token).unwrap();
+        let header = decode_json(header_part, "header").unwrap();
+        let claims = decode_json(claims_part, "claims").unwrap();
+        assert_eq!(header["alg"], "HS256");
+        assert_eq!(header["kid"], "k1");
+        assert_eq!(claims["iat"], 1_000);
+        assert_eq!(claims["exp"], 1_060);
+
+        let signing_input = format!("{}.{}", header_part, claims_part);
+        let signature = decode_segment(signature_part).unwrap();
+        let key = |secret: &str| CandidateKey {
+            kid: None,
+            key: VerifyKey::Secret(secret.as_bytes().to_vec()),
+        };
+        let verify = |secret: &str| {
+            verify_with_keys(
+                "HS256",
+                Some("k1"),
+                &[key(secret)],
+                signing_input.as_bytes(),
+                &signature,
+            )
+        };
+        assert!(verify("s3cret").valid);
+        let wrong = verify("other");
+        assert!(!wrong.valid);
+        assert_eq!(wrong.error.as_deref(), Some("Signature does not match"));
+    }
+}
@@ -0,0 +1,309 @@
+//! Collection login flows ("auth chains").
+//!
+//! A collection may name one of its requests as its login request, together
+//! with where the token is in that request's response: a JSONPath into the
+//! JSON body or a response header. Running the flow sends the login request,
+//! stores the extracted value in a variable of the active environment and
+//! emits `environment-changed` so the frontend reloads it. With
+//! `retryOnUnauthorized`, a request of the collection answered with 401 runs
+//! the flow and is sent once more with the new token.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_store::StoreExt;
+
+use super::api_request::{ApiResponse, RequestOptions};
+use super::proxy::ProxyState;
+use super::store::{lock_store, save_store};
+use super::variables::{active_environment_id, load_active_environment_variables};
+use super::workspaces::store_file;
+
+const ENVIRONMENTS_KEY: &str = "environments";
+
+fn default_source() -> String {
+    "body".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginFlow {
+    /// Request of the collection sent to log in
+    pub endpoint_id: String,
+    /// Where the token is: "body" (JSONPath) or "header"
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// JSONPath into the response body (`$.data.token`) or a header name
+    pub expression: String,
+    /// Active-environment variable that receives the token
+    pub variable: String,
+    /// Run the flow and resend once when a request of the collection gets 401
+    #[serde(default)]
+    pub retry_on_unauthorized: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginFlowResult {
+    pub environment_id: String,
+    pub variable: String,
+    pub value: String,
+    /// Value the variable held before, used to patch a request being retried
+    #[serde(skip)]
+    pub previous: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnvironmentChangedPayload {
+    environment_id: String,
+    variable: String,
+}
+
+fn path_segment_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"^(?:\.([^.\[\]]+)|\[(\d+)\]|\[\s*['"]([^'"]*)['"]\s*\])"#).unwrap()
+    })
+}
+
+/// Follow a simple JSONPath (`$.a.b[0]['c d']`) into `value`. The leading
+/// `$` is optional, so `data.token` works as well.
+pub(crate) fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let normalized = match path.chars().next() {
+        Some('.') | Some('[') | None => path.to_string(),
+        Some(_) => format!(".{}", path),
+    };
+    let mut rest = normalized.as_str();
+    let mut current = value;
+    while !rest.is_empty() {
+        let caps = path_segment_pattern().captures(rest)?;
+        current = match (caps.get(1), caps.get(2), caps.get(3)) {
+            (Some(key), _, _) | (_, _, Some(key)) => current.get(key.as_str())?,
+            (_, Some(index), _) => current.get(index.as_str().parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+        rest = &rest[caps[0].len()..];
+    }
+    Some(current)
+}
+
+/// The token the flow points at in the login response.
+fn extract_token(flow: &LoginFlow, response: &ApiResponse) -> Result<String, String> {
+    if !response.success {
+        return Err(match response.status {
+            Some(status) => format!("The login request returned {}", status),
+            None => response
+                .message
+                .clone()
+                .unwrap_or_else(|| "The login request failed".to_string()),
+        });
+    }
+    let token = if flow.source == "header" {
+        response
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(flow.expression.trim()))
+            .map(|(_, value)| value.clone())
+    } else {
+        let body = match &response.data {
+            Some(Value::String(text)) => serde_json::from_str(text).unwrap_or(Value::Null),
+            Some(data) => data.clone(),
+            None => Value::Null,
+        };
+        json_path(&body, &flow.expression).and_then(|value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        })
+    };
+    token
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| format!("The login response has no value at {}", flow.expression))
+}
+
+/// Write `value` into variable `name` of the active environment. Variables
+/// flagged as secret go to the keychain, like the frontend stores them.
+async fn store_variable(app: &AppHandle, name: &str, value: &str) -> Result<String, String> {
+    let environment_id = active_environment_id(app)
+        .ok_or_else(|| "No active environment to store the token in".to_string())?;
+    let secret = {
+        let _guard = lock_store();
+        let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+        let mut environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+        let environment = environments
+            .get_mut("items")
+            .and_then(Value::as_array_mut)
+            .and_then(|items| {
+                items.iter_mut().find(|environment| {
+                    environment.get("id").and_then(Value::as_str) == Some(environment_id.as_str())
+                })
+            })
+            .ok_or_else(|| "The active environment no longer exists".to_string())?;
+        if environment.get("encrypted").is_some() {
+            return Err(
+                "The active environment is encrypted; login flows cannot write to it".to_string(),
+            );
+        }
+        let secret = environment
+            .get("secretKeys")
+            .and_then(Value::as_array)
+            .is_some_and(|keys| keys.iter().any(|key| key.as_str() == Some(name)));
+        if !secret {
+            if !environment.get("variables").is_some_and(Value::is_object) {
+                environment["variables"] = Value::Object(serde_json::Map::new());
+            }
+            environment["variables"][name] = Value::String(value.to_string());
+            store.set(ENVIRONMENTS_KEY.to_string(), environments);
+            save_store(app)?;
+        }
+        secret
+    };
+    if secret {
+        super::secrets::secret_set(
+            format!("env:{}|{}", environment_id, name),
+            value.to_string(),
+        )
+        .await?;
+    }
+    Ok(environment_id)
+}
+
+/// Send the login request of `flow` and store the token it returns.
+pub(crate) async fn run_login_flow(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    collection_id: &str,
+    flow: &LoginFlow,
+) -> Result<LoginFlowResult, String> {
+    if flow.variable.trim().is_empty() {
+        return Err("The login flow has no variable to store the token in".to_string());
+    }
+    let response =
+        super::runner::send_endpoint(app, proxy_state, collection_id, &flow.endpoint_id).await?;
+    let value = extract_token(flow, &response)?;
+    let previous = load_active_environment_variables(app)
+        .await
+        .remove(&flow.variable);
+    let environment_id = store_variable(app, &flow.variable, &value).await?;
+    let _ = app.emit(
+        "environment-changed",
+        EnvironmentChangedPayload {
+            environment_id: environment_id.clone(),
+            variable: flow.variable.clone(),
+        },
+    );
+    Ok(LoginFlowResult {
+        environment_id,
+        variable: flow.variable.clone(),
+        value,
+        previous,
+    })
+}
+
+fn replace_in_value(value: &mut Value, from: &[String], to: &str) {
+    match value {
+        Value::String(s) => {
+            for pattern in from {
+                *s = s.replace(pattern.as_str(), to);
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| replace_in_value(item, from, to)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| replace_in_value(item, from, to)),
+        _ => {}
+    }
+}
+
+/// Put the new token into a request that was sent with the old one (or with
+/// the variable still unresolved).
+pub(crate) fn apply_token(options: &mut RequestOptions, login: &LoginFlowResult) {
+    let mut from = vec![format!("{{{{{}}}}}", login.variable)];
+    if let Some(previous) = login.previous.as_ref().filter(|p| !p.is_empty()) {
+        from.push(previous.clone());
+    }
+    for pattern in &from {
+        options.url = options.url.replace(pattern.as_str(), &login.value);
+    }
+    if let Some(headers) = options.headers.as_mut() {
+        for header in headers.values_mut() {
+            for pattern in &from {
+                *header = header.replace(pattern.as_str(), &login.value);
+            }
+        }
+    }
+    if let Some(body) = options.body.as_mut() {
+        replace_in_value(body, &from, &login.value);
+    }
+}
+
+/// Run a collection's login flow now and store the token it yields.
+#[tauri::command]
+pub async fn login_flow_run(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    collection_id: String,
+) -> Result<LoginFlowResult, String> {
+    let flow = super::collections::collection_login_flow(&app, &collection_id)?
+        .ok_or_else(|| "This collection has no login flow".to_string())?;
+    run_login_flow(&app, &proxy_state, &collection_id, &flow).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn response(data: Value, headers: &[(&str, &str)]) -> ApiResponse {
+        serde_json::from_value(json!({
+            "success": true,
+            "data": data,
+            "status": 200,
+            "statusText": "OK",
+            "headers": headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+            "setCookies": [],
+            "timings": {
+                "startTime": 0, "dnsLookup": 0, "tcpConnection": 0, "tlsHandshake": 0,
+                "firstByte": 0, "download": 0, "total": 0
+            }
+        }))
+        .unwrap()
+    }
+
+    fn flow(source: &str, expression: &str) -> LoginFlow {
+        LoginFlow {
+            endpoint_id: "login".to_string(),
+            source: source.to_string(),
+            expression: expression.to_string(),
+            variable: "token".to_string(),
+            retry_on_unauthorized: true,
+        }
+    }
+
+    #[test]
+    fn tokens_are_extracted_from_the_body_or_a_header() {
+        let login = response(
+            json!({ "data": { "sessions": [{ "access token": "abc" }] }, "ttl": 60 }),
+            &[("x-auth-token", "from-header")],
+        );
+        let body = |path| extract_token(&flow("body", path), &login);
+        assert_eq!(body("$.data.sessions[0]['access token']").unwrap(), "abc");
+        assert_eq!(body("ttl").unwrap(), "60");
+        assert!(body("$.data.missing").is_err());
+        assert_eq!(
+            extract_token(&flow("header", "X-Auth-Token"), &login).unwrap(),
+            "from-header"
+        );
+    }
+}
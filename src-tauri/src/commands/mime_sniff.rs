@@ -0,0 +1,219 @@
+//! Response content sniffing: detects what a body actually is (JSON, XML,
+//! HTML, image, binary or plain text) and compares it with the declared
+//! `Content-Type`, so the response view isn't chosen from missing or wrong
+//! metadata.
+
+use serde::{Deserialize, Serialize};
+
+/// Bytes looked at to tell text from binary.
+const SNIFF_LEN: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSniff {
+    /// Kind detected from the body: "json" | "xml" | "html" | "image" |
+    /// "binary" | "text"
+    pub kind: String,
+    /// MIME type detected from the body
+    pub mime_type: String,
+    /// The `Content-Type` header, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared: Option<String>,
+    /// The header is missing or names a different kind of content
+    pub mismatch: bool,
+    /// Content type the body should be rendered as
+    pub render_content_type: String,
+}
+
+/// Kind and MIME type of a body from its magic bytes or leading text.
+fn sniff_body(body: &[u8]) -> (&'static str, &'static str) {
+    const SIGNATURES: &[(&[u8], &str, &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image", "image/png"),
+        (b"\xff\xd8\xff", "image", "image/jpeg"),
+        (b"GIF87a", "image", "image/gif"),
+        (b"GIF89a", "image", "image/gif"),
+        (b"\x00\x00\x01\x00", "image", "image/x-icon"),
+        (b"%PDF-", "binary", "application/pdf"),
+        (b"PK\x03\x04", "binary", "application/zip"),
+        (b"\x1f\x8b", "binary", "application/gzip"),
+    ];
+    if let Some((_, kind, mime)) = SIGNATURES
+        .iter()
+        .find(|(magic, _, _)| body.starts_with(magic))
+    {
+        return (kind, mime);
+    }
+    if body.len() >= 12 && &body[..4] == b"RIFF" && &body[8..12] == b"WEBP" {
+        return ("image", "image/webp");
+    }
+
+    let head = &body[..body.len().min(SNIFF_LEN)];
+    let is_text = match std::str::from_utf8(head) {
+        Ok(text) => !text.contains('\0'),
+        // A multi-byte character cut off at the sniff boundary is still text
+        Err(e) => e.error_len().is_none() && !head[..e.valid_up_to()].contains(&0),
+    };
+    if !is_text {
+        return ("binary", "application/octet-stream");
+    }
+
+    let text = String::from_utf8_lossy(head);
+    let start = text.trim_start_matches('\u{feff}').trim_start();
+    let lower = start
+        .chars()
+        .take(256)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if (start.starts_with('{') || start.starts_with('['))
+        && serde_json::from_slice::<serde_json::Value>(body).is_ok()
+    {
+        return ("json", "application/json");
+    }
+    if lower.starts_with("<!doctype html")
+        || lower.starts_with("<html")
+        || lower.starts_with("<head")
+        || lower.starts_with("<body")
+    {
+        return ("html", "text/html");
+    }
+    if lower.starts_with("<svg") || (lower.starts_with("<?xml") && lower.contains("<svg")) {
+        return ("image", "image/svg+xml");
+    }
+    let element_start = lower
+        .strip_prefix('<')
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '!'));
+    if lower.starts_with("<?xml") || element_start {
+        return ("xml", "application/xml");
+    }
+    ("text", "text/plain")
+}
+
+/// Kind of content a `Content-Type` value announces, `None` when it says
+/// too little to compare (e.g. an unknown `application/*` type).
+fn declared_kind(content_type: &str) -> Option<&'static str> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let (top, sub) = essence.split_once('/')?;
+    let kind = if sub == "json" || sub.ends_with("+json") {
+        "json"
+    } else if sub == "svg+xml" || top == "image" {
+        "image"
+    } else if sub == "html" || sub == "xhtml+xml" {
+        "html"
+    } else if sub == "xml" || sub.ends_with("+xml") {
+        "xml"
+    } else if top == "text"
+        || matches!(
+            sub,
+            "javascript" | "ecmascript" | "x-www-form-urlencoded" | "graphql" | "yaml" | "x-yaml"
+        )
+    {
+        "text"
+    } else if top == "audio"
+        || top == "video"
+        || top == "font"
+        || matches!(
+            sub,
+            "octet-stream"
+                | "pdf"
+                | "zip"
+                | "gzip"
+                | "x-protobuf"
+                | "protobuf"
+                | "grpc"
+                | "msgpack"
+                | "x-msgpack"
+        )
+    {
+        "binary"
+    } else {
+        return None;
+    };
+    Some(kind)
+}
+
+/// Sniffs a non-empty response body against its declared content type.
+pub fn sniff_content(body: &[u8], declared: Option<&str>) -> Option<ContentSniff> {
+    if body.is_empty() {
+        return None;
+    }
+    let (kind, mime_type) = sniff_body(body);
+    let declared = declared
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    let declared_kind = declared.as_deref().map(declared_kind);
+    let mismatch = match declared_kind {
+        None => true,
+        Some(None) => false,
+        Some(Some(declared_kind)) => declared_kind != kind,
+    };
+
+    let render_content_type = match &declared {
+        Some(declared) if !mismatch => declared.clone(),
+        // Never switch to rendering HTML on sniffing alone: a body the server
+        // didn't label as HTML is shown as source
+        _ if kind == "html" => "text/plain".to_string(),
+        _ => mime_type.to_string(),
+    };
+
+    Some(ContentSniff {
+        kind: kind.to_string(),
+        mime_type: mime_type.to_string(),
+        declared,
+        mismatch,
+        render_content_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_body_kinds_and_header_mismatches() {
+        let json = sniff_content(br#" {"id": 1}"#, Some("text/html; charset=utf-8")).unwrap();
+        assert_eq!(json.kind, "json");
+        assert!(json.mismatch);
+        assert_eq!(json.render_content_type, "application/json");
+
+        let matching = sniff_content(br#"[1, 2]"#, Some("application/problem+json")).unwrap();
+        assert!(!matching.mismatch);
+        assert_eq!(matching.render_content_type, "application/problem+json");
+
+        let html = sniff_content(b"<!DOCTYPE html><html></html>", None).unwrap();
+        assert_eq!(html.kind, "html");
+        assert!(html.mismatch);
+        assert_eq!(html.render_content_type, "text/plain");
+
+        let png =
+            sniff_content(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", Some("application/json")).unwrap();
+        assert_eq!(
+            (png.kind.as_str(), png.render_content_type.as_str()),
+            ("image", "image/png")
+        );
+
+        let xml = sniff_content(b"<?xml version=\"1.0\"?><a/>", Some("text/xml")).unwrap();
+        assert!(!xml.mismatch);
+        assert_eq!(
+            sniff_content(b"\x00\x01\x02binary", None).unwrap().kind,
+            "binary"
+        );
+        assert_eq!(
+            sniff_content(b"{broken", Some("application/json"))
+                .unwrap()
+                .kind,
+            "text"
+        );
+        assert!(
+            !sniff_content(b"%PDF-1.7", Some("application/vnd.custom"))
+                .unwrap()
+                .mismatch
+        );
+        assert!(sniff_content(b"", Some("application/json")).is_none());
+    }
+}
@@ -1,30 +1,122 @@
 use axum::{
     extract::{Path, Query, State as AxumState},
-    http::{Method, StatusCode},
-    response::Json,
+    http::{header, Method, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::any,
     Router,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
+use tauri::AppHandle;
 use tokio::sync::oneshot;
 use tower_http::cors::CorsLayer;
 use uuid::Uuid;
 
+use super::collections::resolve_collection_dir;
+use super::endpoint_examples::{read_examples, ResponseExample};
+
+/// Request logs kept by default; the oldest are dropped first.
+const DEFAULT_LOG_SIZE: usize = 1000;
+const MAX_LOG_SIZE: usize = 100_000;
+const DEFAULT_LOG_PAGE: usize = 20;
+
+fn default_log_size() -> usize {
+    DEFAULT_LOG_SIZE
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MockServerSettings {
     pub port: u16,
     #[serde(default)]
     pub endpoint_delays: HashMap<String, u64>,
+    /// Default delay of every endpoint in a collection, keyed by collection ID;
+    /// an endpoint delay overrides it
+    #[serde(default)]
+    pub collection_delays: HashMap<String, u64>,
     #[serde(default)]
     pub custom_responses: HashMap<String, Value>,
     #[serde(default)]
     pub custom_status_codes: HashMap<String, u16>,
+    /// Endpoints switched off answer 404 while the server runs; missing
+    /// entries are enabled
+    #[serde(default)]
+    pub endpoint_enabled: HashMap<String, bool>,
+    /// Saved example served by each endpoint, by example ID; endpoints without
+    /// an entry serve their first successful example
+    #[serde(default)]
+    pub selected_examples: HashMap<String, String>,
+    /// Request logs kept while the server runs
+    #[serde(default = "default_log_size")]
+    pub log_size: usize,
+}
+
+impl MockServerSettings {
+    fn is_endpoint_enabled(&self, key: &str) -> bool {
+        self.endpoint_enabled.get(key).copied().unwrap_or(true)
+    }
+
+    fn delay_for(&self, collection_id: &str, key: &str) -> Option<u64> {
+        self.endpoint_delays
+            .get(key)
+            .or_else(|| self.collection_delays.get(collection_id))
+            .copied()
+            .filter(|delay| *delay > 0)
+    }
+}
+
+/// The saved example an endpoint answers with when it has no custom
+/// response: the selected one, else the first 2xx, else the first.
+fn pick_example(
+    mut examples: Vec<ResponseExample>,
+    selected: Option<&str>,
+) -> Option<ResponseExample> {
+    let index = selected
+        .and_then(|id| examples.iter().position(|example| example.id == id))
+        .or_else(|| {
+            examples
+                .iter()
+                .position(|example| (200..300).contains(&example.status))
+        })
+        .or((!examples.is_empty()).then_some(0))?;
+    Some(examples.swap_remove(index))
+}
+
+/// A mock response body: JSON, or a saved example's body as it was received.
+enum MockBody {
+    Json(Value),
+    Text { content_type: String, body: String },
+}
+
+impl MockBody {
+    fn from_example(example: ResponseExample) -> Self {
+        let content_type = example.content_type();
+        let json_like = content_type
+            .as_deref()
+            .map(|media_type| media_type.contains("json"))
+            .unwrap_or(true);
+        match serde_json::from_str(&example.body) {
+            Ok(value) if json_like => MockBody::Json(value),
+            _ => MockBody::Text {
+                content_type: content_type.unwrap_or_else(|| "text/plain".to_string()),
+                body: example.body,
+            },
+        }
+    }
+
+    fn into_response(self, status: StatusCode) -> Response {
+        match self {
+            MockBody::Json(value) => (status, Json(value)).into_response(),
+            MockBody::Text { content_type, body } => {
+                (status, [(header::CONTENT_TYPE, content_type)], body).into_response()
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -33,7 +125,6 @@ pub struct MockEndpoint {
     pub path_regex: Regex,
     #[allow(dead_code)] // Stored for debugging/future use
     pub path_pattern: String,
-    #[allow(dead_code)] // Stored for path parameter extraction
     pub param_names: Vec<String>,
     pub endpoint: Value,
     pub collection_id: String,
@@ -51,6 +142,80 @@ pub struct RequestLog {
     pub response_status: u16,
     pub response_time: u64,
     pub matched_endpoint: Option<MatchedEndpointInfo>,
+    /// Path parameters of the matched endpoint, e.g. `{ "id": "42" }`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub path_params: HashMap<String, String>,
+}
+
+/// The most recent request logs, oldest first. Pushing into a full buffer
+/// drops the oldest entry.
+pub struct RequestLogBuffer {
+    entries: VecDeque<RequestLog>,
+    capacity: usize,
+    /// Requests logged since the server started or the log was cleared,
+    /// including dropped ones
+    received: u64,
+}
+
+impl RequestLogBuffer {
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, MAX_LOG_SIZE);
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(DEFAULT_LOG_SIZE)),
+            capacity,
+            received: 0,
+        }
+    }
+
+    fn push(&mut self, log: RequestLog) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(log);
+        self.received += 1;
+    }
+
+    /// Change the size, dropping the oldest entries that no longer fit.
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.clamp(1, MAX_LOG_SIZE);
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.received = 0;
+    }
+
+    /// `limit` logs, newest first, after skipping the `offset` newest.
+    fn page(&self, offset: usize, limit: usize) -> RequestLogPage {
+        RequestLogPage {
+            logs: self
+                .entries
+                .iter()
+                .rev()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            total: self.entries.len(),
+            received: self.received,
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// One page of the request log, newest first.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLogPage {
+    pub logs: Vec<RequestLog>,
+    /// Logs kept
+    pub total: usize,
+    /// Requests logged, including those dropped from the buffer
+    pub received: u64,
+    pub capacity: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,8 +230,11 @@ pub struct MatchedEndpointInfo {
 #[derive(Clone)]
 pub struct MockServerState {
     pub endpoints: Arc<RwLock<Vec<MockEndpoint>>>,
+    /// Directory of each mocked collection, where its saved examples are read
+    /// from on every request so newly saved ones are served right away
+    pub collection_dirs: Arc<HashMap<String, PathBuf>>,
     pub settings: Arc<RwLock<MockServerSettings>>,
-    pub logs: Arc<RwLock<Vec<RequestLog>>>,
+    pub logs: Arc<RwLock<RequestLogBuffer>>,
 }
 
 struct ServerHandle {
@@ -84,6 +252,7 @@ fn get_server_handle() -> &'static RwLock<Option<ServerHandle>> {
 
 #[tauri::command]
 pub async fn mock_server_start(
+    app: AppHandle,
     settings: MockServerSettings,
     collections: Vec<Value>,
 ) -> Result<Value, String> {
@@ -107,10 +276,19 @@ pub async fn mock_server_start(
         }));
     }
 
+    let collection_dirs = endpoints
+        .iter()
+        .filter_map(|endpoint| {
+            let dir = resolve_collection_dir(&app, &endpoint.collection_id).ok()??;
+            Some((endpoint.collection_id.clone(), dir))
+        })
+        .collect();
+
     let state = MockServerState {
         endpoints: Arc::new(RwLock::new(endpoints)),
+        collection_dirs: Arc::new(collection_dirs),
         settings: Arc::new(RwLock::new(settings.clone())),
-        logs: Arc::new(RwLock::new(Vec::new())),
+        logs: Arc::new(RwLock::new(RequestLogBuffer::new(settings.log_size))),
     };
 
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
@@ -185,7 +363,7 @@ pub async fn mock_server_status() -> Result<Value, String> {
     let handle = get_server_handle().read().unwrap();
 
     if let Some(server) = handle.as_ref() {
-        let log_count = server.state.logs.read().unwrap().len();
+        let log_count = server.state.logs.read().unwrap().received;
         Ok(serde_json::json!({
             "running": true,
             "port": server.port,
@@ -200,17 +378,20 @@ pub async fn mock_server_status() -> Result<Value, String> {
     }
 }
 
+/// A page of the request log, newest first: `limit` logs after skipping the
+/// `offset` newest.
 #[tauri::command]
-pub async fn mock_server_logs(limit: Option<usize>) -> Result<Vec<RequestLog>, String> {
+pub async fn mock_server_logs(
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<RequestLogPage, String> {
     let handle = get_server_handle().read().unwrap();
 
     if let Some(server) = handle.as_ref() {
         let logs = server.state.logs.read().unwrap();
-        let limit = limit.unwrap_or(20);
-        let result: Vec<RequestLog> = logs.iter().rev().take(limit).cloned().collect();
-        Ok(result)
+        Ok(logs.page(offset.unwrap_or(0), limit.unwrap_or(DEFAULT_LOG_PAGE)))
     } else {
-        Ok(Vec::new())
+        Ok(RequestLogPage::default())
     }
 }
 
@@ -226,16 +407,105 @@ pub async fn mock_server_clear_logs() -> Result<Value, String> {
     }
 }
 
+/// Apply changed delays, responses, status codes and log size to the running
+/// server. The port only changes on restart.
 #[tauri::command]
-pub async fn mock_server_reload_settings() -> Result<Value, String> {
-    // Settings are stored in the state, would need to reload from store
-    // For now, just return success
+pub async fn mock_server_reload_settings(
+    settings: Option<MockServerSettings>,
+) -> Result<Value, String> {
+    let handle = get_server_handle().read().unwrap();
+
+    if let (Some(server), Some(mut settings)) = (handle.as_ref(), settings) {
+        server
+            .state
+            .logs
+            .write()
+            .unwrap()
+            .set_capacity(settings.log_size);
+        let mut current = server.state.settings.write().unwrap();
+        settings.port = current.port;
+        *current = settings;
+    }
     Ok(serde_json::json!({
         "success": true,
         "message": "Settings reloaded successfully"
     }))
 }
 
+/// Switch a route of the running server on or off. The setting itself is
+/// persisted by the frontend and passed in on the next start.
+#[tauri::command]
+pub async fn mock_server_set_endpoint_enabled(
+    collection_id: String,
+    endpoint_id: String,
+    enabled: bool,
+) -> Result<Value, String> {
+    let handle = get_server_handle().read().unwrap();
+
+    if let Some(server) = handle.as_ref() {
+        let key = format!("{}_{}", collection_id, endpoint_id);
+        server
+            .state
+            .settings
+            .write()
+            .unwrap()
+            .endpoint_enabled
+            .insert(key, enabled);
+        Ok(serde_json::json!({ "success": true }))
+    } else {
+        Ok(serde_json::json!({ "success": false, "message": "Server is not running" }))
+    }
+}
+
+/// Values of the endpoint's `{name}` path segments in a matching path.
+fn extract_path_params(endpoint: &MockEndpoint, path: &str) -> HashMap<String, String> {
+    let Some(captures) = endpoint.path_regex.captures(path) else {
+        return HashMap::new();
+    };
+    endpoint
+        .param_names
+        .iter()
+        .zip(captures.iter().skip(1))
+        .filter_map(|(name, value)| Some((name.clone(), value?.as_str().to_string())))
+        .collect()
+}
+
+/// Replace `{name}` placeholders in the response's strings with path
+/// parameters. A string that is only a placeholder takes the value as a
+/// number when it is a plain integer, so `"id": "{id}"` echoes `"id": 42`.
+fn fill_path_params(value: &mut Value, params: &HashMap<String, String>) {
+    match value {
+        Value::String(text) => {
+            let whole = text
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .and_then(|name| params.get(name));
+            if let Some(param) = whole {
+                *value = param
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|number| number.to_string() == *param)
+                    .map(Value::from)
+                    .unwrap_or_else(|| Value::String(param.clone()));
+                return;
+            }
+            for (name, param) in params {
+                let placeholder = format!("{{{}}}", name);
+                if text.contains(&placeholder) {
+                    *text = text.replace(&placeholder, param);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| fill_path_params(item, params)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|field| fill_path_params(field, params)),
+        _ => {}
+    }
+}
+
 fn build_routing_table(collections: &[Value]) -> Vec<MockEndpoint> {
     let mut endpoints = Vec::new();
     let param_regex = regex::Regex::new(r"\{([^}]+)\}").unwrap();
@@ -300,16 +570,18 @@ async fn handle_mock_request(
     Path(path): Path<String>,
     Query(query): Query<HashMap<String, String>>,
     AxumState(state): AxumState<MockServerState>,
-) -> (StatusCode, Json<Value>) {
+) -> Response {
     let start = std::time::Instant::now();
     let path = format!("/{}", path);
 
     // First pass: find matching endpoint and extract needed data
-    let match_result = {
+    let (match_result, disabled) = {
         let endpoints = state.endpoints.read().unwrap();
         let settings = state.settings.read().unwrap();
 
         let mut found = None;
+        // A disabled route that matched, reported when nothing else does
+        let mut disabled = None;
         for endpoint in endpoints.iter() {
             if endpoint.method != method.as_str().to_uppercase() {
                 continue;
@@ -325,11 +597,6 @@ async fn handle_mock_request(
                         .and_then(|v| v.as_str())
                         .unwrap_or("")
                 );
-
-                let delay = settings.endpoint_delays.get(&delay_key).copied();
-                let custom_response = settings.custom_responses.get(&delay_key).cloned();
-                let custom_status = settings.custom_status_codes.get(&delay_key).copied();
-                let endpoint_data = endpoint.endpoint.clone();
                 let matched_info = MatchedEndpointInfo {
                     collection_id: endpoint.collection_id.clone(),
                     collection_name: endpoint.collection_name.clone(),
@@ -347,31 +614,69 @@ async fn handle_mock_request(
                         .to_string(),
                 };
 
+                if !settings.is_endpoint_enabled(&delay_key) {
+                    disabled.get_or_insert(matched_info);
+                    continue;
+                }
+
+                let delay = settings.delay_for(&endpoint.collection_id, &delay_key);
+                let custom_response = settings.custom_responses.get(&delay_key).cloned();
+                let custom_status = settings.custom_status_codes.get(&delay_key).copied();
+                let selected_example = settings.selected_examples.get(&delay_key).cloned();
+                let endpoint_data = endpoint.endpoint.clone();
+                let path_params = extract_path_params(endpoint, &path);
+
                 found = Some((
-                    delay_key,
+                    path_params,
                     delay,
                     custom_response,
                     custom_status,
+                    selected_example,
                     endpoint_data,
                     matched_info,
                 ));
                 break;
             }
         }
-        found
+        (found, disabled)
     };
 
     // Process the match
-    if let Some((_delay_key, delay, custom_response, custom_status, endpoint_data, matched_info)) =
-        match_result
+    if let Some((
+        path_params,
+        delay,
+        custom_response,
+        custom_status,
+        selected_example,
+        endpoint_data,
+        matched_info,
+    )) = match_result
     {
         // Apply delay if configured
         if let Some(delay_ms) = delay {
             tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
         }
 
-        let response = custom_response.unwrap_or_else(|| generate_mock_response(&endpoint_data));
-        let status_code = custom_status.unwrap_or(200);
+        // A custom response wins over saved examples, which win over the spec
+        let example = match custom_response {
+            Some(_) => None,
+            None => state
+                .collection_dirs
+                .get(&matched_info.collection_id)
+                .map(|dir| read_examples(dir, &matched_info.endpoint_id))
+                .and_then(|examples| pick_example(examples, selected_example.as_deref())),
+        };
+        let status_code = custom_status
+            .or(example.as_ref().map(|example| example.status))
+            .unwrap_or(200);
+        let mut body = match (custom_response, example) {
+            (Some(response), _) => MockBody::Json(response),
+            (None, Some(example)) => MockBody::from_example(example),
+            (None, None) => MockBody::Json(generate_mock_response(&endpoint_data)),
+        };
+        if let MockBody::Json(response) = &mut body {
+            fill_path_params(response, &path_params);
+        }
 
         // Log request
         let log = RequestLog {
@@ -383,21 +688,20 @@ async fn handle_mock_request(
             response_status: status_code,
             response_time: start.elapsed().as_millis() as u64,
             matched_endpoint: Some(matched_info),
+            path_params,
         };
 
-        let mut logs = state.logs.write().unwrap();
-        logs.push(log);
-        if logs.len() > 100 {
-            logs.remove(0);
-        }
+        state.logs.write().unwrap().push(log);
 
-        return (
-            StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK),
-            Json(response),
-        );
+        return body.into_response(StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK));
     }
 
-    // 404 - Not found
+    // 404 - Not found, or the matching route is switched off
+    let error = if disabled.is_some() {
+        "Endpoint disabled"
+    } else {
+        "Endpoint not found"
+    };
     let log = RequestLog {
         id: Uuid::new_v4().to_string(),
         timestamp: chrono::Utc::now().timestamp_millis(),
@@ -406,7 +710,8 @@ async fn handle_mock_request(
         query,
         response_status: 404,
         response_time: start.elapsed().as_millis() as u64,
-        matched_endpoint: None,
+        matched_endpoint: disabled,
+        path_params: HashMap::new(),
     };
 
     state.logs.write().unwrap().push(log);
@@ -414,11 +719,12 @@ async fn handle_mock_request(
     (
         StatusCode::NOT_FOUND,
         Json(serde_json::json!({
-            "error": "Endpoint not found",
+            "error": error,
             "path": path,
             "method": method.to_string()
         })),
     )
+        .into_response()
 }
 
 fn generate_mock_response(endpoint: &Value) -> Value {
@@ -483,3 +789,135 @@ fn generate_from_schema(schema: &Value) -> Value {
         _ => Value::Null,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_are_enabled_unless_switched_off() {
+        let mut settings: MockServerSettings =
+            serde_json::from_value(serde_json::json!({ "port": 3000 })).unwrap();
+        assert!(settings.is_endpoint_enabled("c1_e1"));
+
+        settings.endpoint_enabled.insert("c1_e1".to_string(), false);
+        settings.endpoint_enabled.insert("c1_e2".to_string(), true);
+        assert!(!settings.is_endpoint_enabled("c1_e1"));
+        assert!(settings.is_endpoint_enabled("c1_e2"));
+    }
+
+    #[test]
+    fn echoes_path_params_into_responses() {
+        let collections = vec![serde_json::json!({
+            "id": "c1",
+            "name": "Pets",
+            "endpoints": [
+                { "id": "e1", "method": "get", "path": "/owners/{ownerId}/pets/{id}" }
+            ]
+        })];
+        let endpoints = build_routing_table(&collections);
+        let params = extract_path_params(&endpoints[0], "/owners/ann/pets/42");
+        assert_eq!(params.get("ownerId").map(String::as_str), Some("ann"));
+        assert_eq!(params.get("id").map(String::as_str), Some("42"));
+        assert!(extract_path_params(&endpoints[0], "/owners/ann").is_empty());
+
+        let mut response = serde_json::json!({
+            "id": "{id}",
+            "owner": "{ownerId}",
+            "href": "/pets/{id}?owner={ownerId}",
+            "tags": ["{missing}"]
+        });
+        fill_path_params(&mut response, &params);
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "id": 42,
+                "owner": "ann",
+                "href": "/pets/42?owner=ann",
+                "tags": ["{missing}"]
+            })
+        );
+    }
+
+    #[test]
+    fn endpoint_delays_override_the_collection_delay() {
+        let mut settings: MockServerSettings =
+            serde_json::from_value(serde_json::json!({ "port": 3000 })).unwrap();
+        assert_eq!(settings.delay_for("c1", "c1_e1"), None);
+
+        settings.collection_delays.insert("c1".to_string(), 1500);
+        settings.endpoint_delays.insert("c1_e2".to_string(), 200);
+        assert_eq!(settings.delay_for("c1", "c1_e1"), Some(1500));
+        assert_eq!(settings.delay_for("c1", "c1_e2"), Some(200));
+        assert_eq!(settings.delay_for("c2", "c2_e1"), None);
+    }
+
+    #[test]
+    fn serves_the_selected_or_first_successful_example() {
+        let examples: Vec<ResponseExample> = serde_json::from_value(serde_json::json!([
+            { "id": "a", "name": "Missing", "status": 404, "body": "not here",
+              "headers": { "Content-Type": "text/plain" }, "savedAt": 0 },
+            { "id": "b", "name": "Found", "status": 200, "body": "{\"id\":\"{id}\"}",
+              "headers": { "Content-Type": "application/json" }, "savedAt": 0 }
+        ]))
+        .unwrap();
+
+        assert_eq!(pick_example(examples.clone(), None).unwrap().id, "b");
+        assert_eq!(pick_example(examples.clone(), Some("a")).unwrap().id, "a");
+        assert_eq!(
+            pick_example(examples.clone(), Some("gone")).unwrap().id,
+            "b"
+        );
+        assert!(pick_example(Vec::new(), None).is_none());
+
+        let found = pick_example(examples.clone(), None).unwrap();
+        assert!(matches!(MockBody::from_example(found), MockBody::Json(_)));
+        match MockBody::from_example(examples[0].clone()) {
+            MockBody::Text { content_type, body } => {
+                assert_eq!(content_type, "text/plain");
+                assert_eq!(body, "not here");
+            }
+            MockBody::Json(_) => panic!("text example served as JSON"),
+        }
+    }
+
+    #[test]
+    fn request_log_keeps_the_newest_entries_and_pages_newest_first() {
+        let log = |n: u64| RequestLog {
+            id: n.to_string(),
+            timestamp: n as i64,
+            method: "GET".to_string(),
+            path: format!("/items/{}", n),
+            query: HashMap::new(),
+            response_status: 200,
+            response_time: n,
+            matched_endpoint: None,
+            path_params: HashMap::new(),
+        };
+        let ids = |page: &RequestLogPage| -> Vec<String> {
+            page.logs.iter().map(|log| log.id.clone()).collect()
+        };
+
+        let mut buffer = RequestLogBuffer::new(3);
+        for n in 1..=5 {
+            buffer.push(log(n));
+        }
+        let page = buffer.page(0, 2);
+        assert_eq!(ids(&page), ["5", "4"]);
+        assert_eq!((page.total, page.received, page.capacity), (3, 5, 3));
+        assert_eq!(ids(&buffer.page(2, 2)), ["3"]);
+        assert!(buffer.page(3, 2).logs.is_empty());
+
+        buffer.set_capacity(2);
+        assert_eq!(ids(&buffer.page(0, 10)), ["5", "4"]);
+        buffer.set_capacity(0);
+        assert_eq!(buffer.page(0, 10).capacity, 1);
+
+        buffer.clear();
+        assert_eq!(buffer.page(0, 10).received, 0);
+
+        let settings: MockServerSettings =
+            serde_json::from_value(serde_json::json!({ "port": 3000 })).unwrap();
+        assert_eq!(settings.log_size, DEFAULT_LOG_SIZE);
+    }
+}
@@ -1,20 +1,80 @@
 pub mod api_request;
 pub mod app;
+pub mod bulk_edit;
 pub mod certificates;
+pub mod client_pool;
+pub mod codegen;
+pub mod collection_history;
+pub mod collection_watcher;
 pub mod collections;
+pub mod command_auth;
+pub mod command_palette;
+pub mod cookie_jar;
+pub mod crash_reports;
+pub mod deep_link;
+pub mod diagnostics;
+pub mod duplicate;
+pub mod electron_migration;
+pub mod endpoint_drafts;
+pub mod endpoint_examples;
+pub mod environment_vault;
 pub mod graphql_subscription;
+pub mod graphql_validation;
+pub mod grpc_docs;
+pub mod grpc_metadata;
 pub mod grpc_proto;
 pub mod grpc_reflection;
 pub mod grpc_streaming;
+pub mod header_metadata;
+pub mod history_replay;
+pub mod hypermedia;
+pub mod idempotency;
 pub mod import_export;
+pub mod ip_family;
+pub mod json_schema;
+pub mod json_view;
+pub mod jwt;
+pub mod login_flow;
+pub mod mime_sniff;
 pub mod mock_server;
+pub mod monitors;
 pub mod mqtt;
+pub mod negotiate;
 pub mod oauth;
+pub mod oauth_redirect;
+pub mod otlp_export;
+pub mod pagination;
 pub mod proxy;
+pub mod remote_sync;
+pub mod request_bundle;
+pub mod request_timeline;
+pub mod response_file;
+pub mod response_search;
+pub mod response_transform;
+pub mod runner;
+pub mod safety_policy;
+pub mod schema_inference;
 pub mod scripts;
+pub mod secret_managers;
 pub mod secrets;
+pub mod security_headers;
+pub mod settings_transfer;
+pub mod soap;
+pub mod spec_sync;
 pub mod sse;
 pub mod store;
+pub mod test_generation;
+pub mod time_budget;
 pub mod tls;
+pub mod tls_probe;
+pub mod token_manager;
+pub mod trace_headers;
+pub mod trash;
+pub mod trusted_hosts;
 pub mod updater;
+pub mod usage_stats;
+pub mod variable_usage;
+pub mod variables;
+pub mod webhook_receiver;
 pub mod websocket;
+pub mod workspaces;
@@ -0,0 +1,413 @@
+//! Scheduled collection runs ("monitors").
+//!
+//! A monitor runs a collection (or one of its folders) on a fixed interval
+//! for as long as the app is open. Each run is summarised and stored, and a
+//! `monitor-event` is emitted on every run plus whenever a monitor starts
+//! failing or recovers, so the frontend can raise a notification.
+
+use super::proxy::ProxyState;
+use super::runner::{execute_collection_run, CollectionRunResult, RunCollectionOptions};
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::Mutex;
+
+const MONITORS_KEY: &str = "monitors";
+const MONITOR_RESULTS_KEY: &str = "monitorResults";
+
+/// Shortest interval accepted, to keep a misconfigured monitor from
+/// hammering the target API.
+const MIN_INTERVAL_SECS: u64 = 60;
+/// Number of run summaries kept per monitor.
+const MAX_RESULTS_PER_MONITOR: usize = 50;
+
+pub struct MonitorState {
+    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl Default for MonitorState {
+    fn default() -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl MonitorState {
+    /// Cancel every scheduled monitor, e.g. before switching workspaces.
+    pub async fn stop_all(&self) {
+        for (_, task) in self.tasks.lock().await.drain() {
+            task.abort();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorConfig {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub collection_id: String,
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    pub interval_secs: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Stored summary of one monitor run; the full per-request results are kept
+/// only for failing requests to bound the store size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorRunSummary {
+    pub monitor_id: String,
+    pub run_id: String,
+    pub started_at: i64,
+    pub duration: u64,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errored: usize,
+    /// "passed" or "failed"; a run that could not start at all is "error".
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Requests that did not pass, including ones held back by the safety
+    /// policy (monitors never confirm guarded requests)
+    #[serde(default)]
+    pub failures: Vec<super::runner::RequestRunResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MonitorEventPayload {
+    monitor_id: String,
+    monitor_name: String,
+    event_type: String,
+    summary: MonitorRunSummary,
+}
+
+fn summarize(monitor_id: &str, result: &CollectionRunResult) -> MonitorRunSummary {
+    let ok = result.failed == 0 && result.errored == 0;
+    MonitorRunSummary {
+        monitor_id: monitor_id.to_string(),
+        run_id: result.run_id.clone(),
+        started_at: result.started_at,
+        duration: result.duration,
+        total: result.total,
+        passed: result.passed,
+        failed: result.failed,
+        errored: result.errored,
+        status: if ok { "passed" } else { "failed" }.to_string(),
+        error: None,
+        failures: result
+            .results
+            .iter()
+            .filter(|r| r.status != "passed")
+            .cloned()
+            .collect(),
+    }
+}
+
+fn error_summary(monitor_id: &str, error: String) -> MonitorRunSummary {
+    MonitorRunSummary {
+        monitor_id: monitor_id.to_string(),
+        run_id: uuid::Uuid::new_v4().to_string(),
+        started_at: chrono::Utc::now().timestamp_millis(),
+        duration: 0,
+        total: 0,
+        passed: 0,
+        failed: 0,
+        errored: 0,
+        status: "error".to_string(),
+        error: Some(error),
+        failures: Vec::new(),
+    }
+}
+
+/// Event type for a finished run given the previous run's status, if any.
+/// Transitions into and out of a failing state get their own event so the
+/// frontend only notifies once per outage.
+fn transition_event(previous: Option<&str>, current: &str) -> &'static str {
+    let was_ok = previous.map(|s| s == "passed");
+    let is_ok = current == "passed";
+    match (was_ok, is_ok) {
+        (Some(true) | None, false) => "failing",
+        (Some(false), true) => "recovered",
+        _ => "run-completed",
+    }
+}
+
+fn read_monitors(app: &AppHandle) -> Result<Vec<MonitorConfig>, String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    Ok(store
+        .get(MONITORS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn write_monitors(app: &AppHandle, monitors: &[MonitorConfig]) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let value = serde_json::to_value(monitors)
+        .map_err(|e| format!("Failed to serialize monitors: {}", e))?;
+    store.set(MONITORS_KEY.to_string(), value);
+    save_store(app)
+}
+
+fn read_results(app: &AppHandle) -> HashMap<String, Vec<MonitorRunSummary>> {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(MONITOR_RESULTS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn write_results(
+    app: &AppHandle,
+    results: &HashMap<String, Vec<MonitorRunSummary>>,
+) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let value = serde_json::to_value(results)
+        .map_err(|e| format!("Failed to serialize monitor results: {}", e))?;
+    store.set(MONITOR_RESULTS_KEY.to_string(), value);
+    save_store(app)
+}
+
+/// Newest first, capped at [`MAX_RESULTS_PER_MONITOR`].
+fn push_result(history: &mut Vec<MonitorRunSummary>, summary: MonitorRunSummary) {
+    history.insert(0, summary);
+    history.truncate(MAX_RESULTS_PER_MONITOR);
+}
+
+/// Run a monitor once, record the summary and emit the matching event.
+async fn run_monitor_once(app: &AppHandle, monitor: &MonitorConfig) -> MonitorRunSummary {
+    let proxy_state = app.state::<ProxyState>();
+    let options = RunCollectionOptions {
+        collection_id: monitor.collection_id.clone(),
+        folder_id: monitor.folder_id.clone(),
+        variables: monitor.variables.clone(),
+        ..Default::default()
+    };
+    let summary = match execute_collection_run(app, &proxy_state, options).await {
+        Ok(result) => summarize(&monitor.id, &result),
+        Err(e) => error_summary(&monitor.id, e),
+    };
+
+    let previous = {
+        let _guard = lock_store();
+        let mut results = read_results(app);
+        let history = results.entry(monitor.id.clone()).or_default();
+        let previous = history.first().map(|s| s.status.clone());
+        push_result(history, summary.clone());
+        if let Err(e) = write_results(app, &results) {
+            eprintln!("Failed to store monitor result: {}", e);
+        }
+        previous
+    };
+
+    let _ = app.emit(
+        "monitor-event",
+        MonitorEventPayload {
+            monitor_id: monitor.id.clone(),
+            monitor_name: monitor.name.clone(),
+            event_type: transition_event(previous.as_deref(), &summary.status).to_string(),
+            summary: summary.clone(),
+        },
+    );
+    summary
+}
+
+fn spawn_monitor(app: AppHandle, monitor: MonitorConfig) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let period = Duration::from_secs(monitor.interval_secs.max(MIN_INTERVAL_SECS));
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            run_monitor_once(&app, &monitor).await;
+        }
+    })
+}
+
+/// (Re)start the task for `monitor`, or just stop it when disabled.
+async fn schedule(
+    app: &AppHandle,
+    tasks: &Mutex<HashMap<String, JoinHandle<()>>>,
+    monitor: &MonitorConfig,
+) {
+    let mut tasks = tasks.lock().await;
+    if let Some(handle) = tasks.remove(&monitor.id) {
+        handle.abort();
+    }
+    if monitor.enabled {
+        tasks.insert(
+            monitor.id.clone(),
+            spawn_monitor(app.clone(), monitor.clone()),
+        );
+    }
+}
+
+/// Start every enabled monitor saved in the store. Called once at startup.
+pub fn start_saved_monitors(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let monitors = match read_monitors(&app) {
+            Ok(monitors) => monitors,
+            Err(e) => {
+                eprintln!("Failed to load monitors: {}", e);
+                return;
+            }
+        };
+        let state = app.state::<MonitorState>();
+        for monitor in monitors.iter().filter(|m| m.enabled) {
+            schedule(&app, &state.tasks, monitor).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn monitors_list(app: AppHandle) -> Result<Vec<MonitorConfig>, String> {
+    read_monitors(&app)
+}
+
+/// Create or update a monitor and restart its schedule.
+#[tauri::command]
+pub async fn monitor_save(
+    app: AppHandle,
+    state: State<'_, MonitorState>,
+    mut monitor: MonitorConfig,
+) -> Result<MonitorConfig, String> {
+    if monitor.collection_id.is_empty() {
+        return Err("Monitor requires a collection".to_string());
+    }
+    if monitor.interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!(
+            "Monitor interval must be at least {} seconds",
+            MIN_INTERVAL_SECS
+        ));
+    }
+    if monitor.id.is_empty() {
+        monitor.id = uuid::Uuid::new_v4().to_string();
+    }
+
+    {
+        let _guard = lock_store();
+        let mut monitors = read_monitors(&app)?;
+        match monitors.iter_mut().find(|m| m.id == monitor.id) {
+            Some(existing) => *existing = monitor.clone(),
+            None => monitors.push(monitor.clone()),
+        }
+        write_monitors(&app, &monitors)?;
+    }
+
+    schedule(&app, &state.tasks, &monitor).await;
+    Ok(monitor)
+}
+
+#[tauri::command]
+pub async fn monitor_delete(
+    app: AppHandle,
+    state: State<'_, MonitorState>,
+    monitor_id: String,
+) -> Result<(), String> {
+    if let Some(handle) = state.tasks.lock().await.remove(&monitor_id) {
+        handle.abort();
+    }
+
+    let _guard = lock_store();
+    let mut monitors = read_monitors(&app)?;
+    monitors.retain(|m| m.id != monitor_id);
+    write_monitors(&app, &monitors)?;
+
+    let mut results = read_results(&app);
+    if results.remove(&monitor_id).is_some() {
+        write_results(&app, &results)?;
+    }
+    Ok(())
+}
+
+/// Stored run summaries for a monitor, newest first.
+#[tauri::command]
+pub async fn monitor_results(
+    app: AppHandle,
+    monitor_id: String,
+) -> Result<Vec<MonitorRunSummary>, String> {
+    Ok(read_results(&app).remove(&monitor_id).unwrap_or_default())
+}
+
+/// Run a monitor immediately, outside its schedule.
+#[tauri::command]
+pub async fn monitor_run_now(
+    app: AppHandle,
+    monitor_id: String,
+) -> Result<MonitorRunSummary, String> {
+    let monitor = read_monitors(&app)?
+        .into_iter()
+        .find(|m| m.id == monitor_id)
+        .ok_or_else(|| format!("Monitor not found: {}", monitor_id))?;
+    Ok(run_monitor_once(&app, &monitor).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(status: &str) -> MonitorRunSummary {
+        let mut s = error_summary("m1", String::new());
+        s.status = status.to_string();
+        s
+    }
+
+    #[test]
+    fn transition_events_fire_once_per_state_change() {
+        assert_eq!(transition_event(None, "passed"), "run-completed");
+        assert_eq!(transition_event(None, "failed"), "failing");
+        assert_eq!(transition_event(Some("passed"), "error"), "failing");
+        assert_eq!(transition_event(Some("failed"), "failed"), "run-completed");
+        assert_eq!(transition_event(Some("error"), "passed"), "recovered");
+        assert_eq!(transition_event(Some("passed"), "passed"), "run-completed");
+    }
+
+    #[test]
+    fn history_is_newest_first_and_capped() {
+        let mut history = Vec::new();
+        for i in 0..(MAX_RESULTS_PER_MONITOR + 5) {
+            let mut s = summary("passed");
+            s.total = i;
+            push_result(&mut history, s);
+        }
+        assert_eq!(history.len(), MAX_RESULTS_PER_MONITOR);
+        assert_eq!(history[0].total, MAX_RESULTS_PER_MONITOR + 4);
+    }
+
+    #[test]
+    fn monitor_config_defaults_to_enabled() {
+        let monitor: MonitorConfig = serde_json::from_value(serde_json::json!({
+            "name": "Smoke",
+            "collectionId": "c1",
+            "intervalSecs": 300
+        }))
+        .unwrap();
+        assert!(monitor.enabled);
+        assert!(monitor.id.is_empty());
+        assert!(monitor.folder_id.is_none());
+    }
+}
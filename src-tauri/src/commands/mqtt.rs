@@ -1,10 +1,11 @@
 use super::api_request::ClientCertConfig;
+use super::proxy::ProxyState;
 use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 
@@ -143,6 +144,16 @@ fn parse_broker(broker: &str) -> Result<(String, u16, bool), String> {
     Ok((host, port, use_tls))
 }
 
+/// URL form of the broker address, for the offline check. An `http` URL,
+/// since only special schemes have their IPv4 hosts parsed as addresses.
+fn broker_origin(host: &str, port: u16) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("http://[{}]:{}", host, port)
+    } else {
+        format!("http://{}:{}", host, port)
+    }
+}
+
 /// Build the rumqttc TLS transport from the request's TLS options. Always
 /// builds an explicit rustls config (webpki roots) rather than rumqttc's
 /// default, so skip-verify, custom CA, and mTLS all flow through the shared
@@ -208,6 +219,8 @@ async fn establish_connection(
     use_tls: bool,
     config_key: String,
 ) -> Result<AsyncClient, String> {
+    app.state::<ProxyState>()
+        .check_offline(&broker_origin(&host, port))?;
     let client_id = request
         .client_id
         .clone()
@@ -489,6 +502,22 @@ pub async fn mqtt_close(
 mod tests {
     use super::*;
 
+    #[test]
+    fn offline_check_sees_loopback_brokers() {
+        let offline = std::sync::atomic::AtomicBool::new(true);
+        for (host, local) in [
+            ("localhost", true),
+            ("127.0.0.1", true),
+            ("::1", true),
+            ("[::1]", true),
+            ("broker.example.com", false),
+        ] {
+            let allowed =
+                super::super::proxy::check_offline(&offline, &broker_origin(host, 1883)).is_ok();
+            assert_eq!(allowed, local, "{}", host);
+        }
+    }
+
     #[test]
     fn parses_plain_mqtt_with_explicit_port() {
         assert_eq!(
@@ -0,0 +1,340 @@
+//! Kerberos/SPNEGO (`Negotiate`) authentication.
+//!
+//! Tokens come from the platform's security libraries, using the credentials
+//! of the signed-in user (a `kinit` ticket on Linux/macOS, the logon session
+//! on Windows):
+//!
+//! * **Linux/macOS:** GSSAPI, loaded at runtime (MIT or Heimdal `libgssapi`,
+//!   `GSS.framework` on macOS) so the app still starts where no Kerberos
+//!   libraries are installed; Negotiate requests then fail with an explanation.
+//! * **Windows:** SSPI's `Negotiate` package.
+//!
+//! Only the first leg is performed: the request is retried once with the
+//! client token after the server answered 401 with `WWW-Authenticate:
+//! Negotiate`, which is all HTTP services need in practice.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// True when one of the `WWW-Authenticate` values offers `Negotiate`.
+pub(crate) fn offers_negotiate<'a>(challenges: impl IntoIterator<Item = &'a str>) -> bool {
+    challenges.into_iter().any(|challenge| {
+        challenge.split(',').any(|scheme| {
+            scheme
+                .split_whitespace()
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case("negotiate"))
+        })
+    })
+}
+
+/// The `Authorization` header value for `host`'s `HTTP` service. Blocks on
+/// the KDC, so call it from a blocking thread.
+pub(crate) fn authorization_header(host: &str) -> Result<String, String> {
+    let token = platform::initial_token(host)?;
+    Ok(format!("Negotiate {}", STANDARD.encode(token)))
+}
+
+#[cfg(unix)]
+mod platform {
+    use libloading::Library;
+    use std::ffi::c_void;
+    use std::ptr;
+
+    #[cfg(target_os = "macos")]
+    const LIBRARIES: &[&str] = &["/System/Library/Frameworks/GSS.framework/GSS"];
+    #[cfg(not(target_os = "macos"))]
+    const LIBRARIES: &[&str] = &["libgssapi_krb5.so.2", "libgssapi.so.3", "libgssapi.so"];
+
+    /// GSS_C_NT_HOSTBASED_SERVICE (1.2.840.113554.1.2.1.4).
+    const HOSTBASED_SERVICE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x12, 0x01, 0x02, 0x01, 0x04];
+    /// SPNEGO (1.3.6.1.5.5.2).
+    const SPNEGO: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x02];
+    const GSS_C_MUTUAL_FLAG: u32 = 2;
+    const GSS_C_GSS_CODE: i32 = 1;
+    const GSS_C_MECH_CODE: i32 = 2;
+
+    // GSS.framework declares its structs with 2-byte packing.
+    #[cfg_attr(target_os = "macos", repr(C, packed(2)))]
+    #[cfg_attr(not(target_os = "macos"), repr(C))]
+    struct GssOid {
+        length: u32,
+        elements: *const c_void,
+    }
+
+    #[cfg_attr(target_os = "macos", repr(C, packed(2)))]
+    #[cfg_attr(not(target_os = "macos"), repr(C))]
+    struct GssBuffer {
+        length: usize,
+        value: *mut c_void,
+    }
+
+    impl GssBuffer {
+        fn empty() -> Self {
+            Self {
+                length: 0,
+                value: ptr::null_mut(),
+            }
+        }
+    }
+
+    fn oid(bytes: &'static [u8]) -> GssOid {
+        GssOid {
+            length: bytes.len() as u32,
+            elements: bytes.as_ptr().cast(),
+        }
+    }
+
+    type ImportName =
+        unsafe extern "C" fn(*mut u32, *mut GssBuffer, *mut GssOid, *mut *mut c_void) -> u32;
+    type InitSecContext = unsafe extern "C" fn(
+        *mut u32,
+        *mut c_void,
+        *mut *mut c_void,
+        *mut c_void,
+        *mut GssOid,
+        u32,
+        u32,
+        *mut c_void,
+        *mut GssBuffer,
+        *mut *mut GssOid,
+        *mut GssBuffer,
+        *mut u32,
+        *mut u32,
+    ) -> u32;
+    type ReleaseBuffer = unsafe extern "C" fn(*mut u32, *mut GssBuffer) -> u32;
+    type ReleaseName = unsafe extern "C" fn(*mut u32, *mut *mut c_void) -> u32;
+    type DeleteSecContext = unsafe extern "C" fn(*mut u32, *mut *mut c_void, *mut GssBuffer) -> u32;
+    type DisplayStatus =
+        unsafe extern "C" fn(*mut u32, u32, i32, *mut GssOid, *mut u32, *mut GssBuffer) -> u32;
+
+    fn is_error(major: u32) -> bool {
+        major & 0xffff_0000 != 0
+    }
+
+    fn load() -> Result<Library, String> {
+        LIBRARIES
+            .iter()
+            // SAFETY: the GSSAPI libraries have no initialisers with preconditions.
+            .find_map(|name| unsafe { Library::new(name) }.ok())
+            .ok_or_else(|| {
+                "Negotiate authentication needs the Kerberos GSSAPI library (libgssapi_krb5)"
+                    .to_string()
+            })
+    }
+
+    /// Text of one GSSAPI status code, e.g. "No Kerberos credentials available".
+    unsafe fn status_text(library: &Library, status: u32, status_type: i32) -> Option<String> {
+        let display: libloading::Symbol<DisplayStatus> =
+            library.get(b"gss_display_status\0").ok()?;
+        let release: libloading::Symbol<ReleaseBuffer> =
+            library.get(b"gss_release_buffer\0").ok()?;
+        let (mut minor, mut context) = (0u32, 0u32);
+        let mut buffer = GssBuffer::empty();
+        let major = display(
+            &mut minor,
+            status,
+            status_type,
+            ptr::null_mut(),
+            &mut context,
+            &mut buffer,
+        );
+        if is_error(major) || buffer.value.is_null() {
+            return None;
+        }
+        let bytes = std::slice::from_raw_parts(buffer.value.cast::<u8>(), buffer.length);
+        let text = String::from_utf8_lossy(bytes).trim().to_string();
+        release(&mut minor, &mut buffer);
+        Some(text).filter(|text| !text.is_empty())
+    }
+
+    unsafe fn failure(library: &Library, step: &str, major: u32, minor: u32) -> String {
+        let detail = status_text(library, minor, GSS_C_MECH_CODE)
+            .or_else(|| status_text(library, major, GSS_C_GSS_CODE))
+            .unwrap_or_else(|| format!("status {:#x}/{}", major, minor));
+        format!("Kerberos {} failed: {}", step, detail)
+    }
+
+    pub(super) fn initial_token(host: &str) -> Result<Vec<u8>, String> {
+        let library = load()?;
+        // SAFETY: the signatures match the GSSAPI C bindings (RFC 2744), every
+        // out-pointer is valid for the call, and each handle or buffer GSSAPI
+        // allocates is released below.
+        unsafe {
+            let symbol_error = |e: libloading::Error| format!("Incomplete GSSAPI library: {}", e);
+            let import_name: libloading::Symbol<ImportName> =
+                library.get(b"gss_import_name\0").map_err(symbol_error)?;
+            let init_sec_context: libloading::Symbol<InitSecContext> = library
+                .get(b"gss_init_sec_context\0")
+                .map_err(symbol_error)?;
+            let release_buffer: libloading::Symbol<ReleaseBuffer> =
+                library.get(b"gss_release_buffer\0").map_err(symbol_error)?;
+            let release_name: libloading::Symbol<ReleaseName> =
+                library.get(b"gss_release_name\0").map_err(symbol_error)?;
+            let delete_sec_context: libloading::Symbol<DeleteSecContext> = library
+                .get(b"gss_delete_sec_context\0")
+                .map_err(symbol_error)?;
+
+            let mut minor = 0u32;
+            let mut service = format!("HTTP@{}", host).into_bytes();
+            let mut service_buffer = GssBuffer {
+                length: service.len(),
+                value: service.as_mut_ptr().cast(),
+            };
+            let mut name_type = oid(HOSTBASED_SERVICE);
+            let mut name: *mut c_void = ptr::null_mut();
+            let major = import_name(&mut minor, &mut service_buffer, &mut name_type, &mut name);
+            if is_error(major) {
+                return Err(failure(&library, "name lookup", major, minor));
+            }
+
+            let mut mechanism = oid(SPNEGO);
+            let mut context: *mut c_void = ptr::null_mut();
+            let mut output = GssBuffer::empty();
+            let major = init_sec_context(
+                &mut minor,
+                ptr::null_mut(),
+                &mut context,
+                name,
+                &mut mechanism,
+                GSS_C_MUTUAL_FLAG,
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut output,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            let result = if is_error(major) {
+                Err(failure(&library, "authentication", major, minor))
+            } else if output.value.is_null() {
+                Err("Kerberos authentication produced no token".to_string())
+            } else {
+                Ok(std::slice::from_raw_parts(output.value.cast::<u8>(), output.length).to_vec())
+            };
+
+            let mut ignored = 0u32;
+            release_buffer(&mut ignored, &mut output);
+            if !context.is_null() {
+                delete_sec_context(&mut ignored, &mut context, ptr::null_mut());
+            }
+            release_name(&mut ignored, &mut name);
+            result
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::ptr;
+    use windows_sys::Win32::Foundation::{SEC_E_OK, SEC_I_CONTINUE_NEEDED};
+    use windows_sys::Win32::Security::Authentication::Identity::{
+        AcquireCredentialsHandleW, DeleteSecurityContext, FreeContextBuffer, FreeCredentialsHandle,
+        InitializeSecurityContextW, SecBuffer, SecBufferDesc, ISC_REQ_ALLOCATE_MEMORY,
+        ISC_REQ_MUTUAL_AUTH, SECBUFFER_TOKEN, SECBUFFER_VERSION, SECPKG_CRED_OUTBOUND,
+        SECURITY_NATIVE_DREP,
+    };
+    use windows_sys::Win32::Security::Credentials::SecHandle;
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    pub(super) fn initial_token(host: &str) -> Result<Vec<u8>, String> {
+        let package = wide("Negotiate");
+        let target = wide(&format!("HTTP/{}", host));
+        // SAFETY: every pointer passed is valid for the call; the credentials,
+        // context and SSPI-allocated output buffer are released below.
+        unsafe {
+            let mut credentials = SecHandle {
+                dwLower: 0,
+                dwUpper: 0,
+            };
+            let status = AcquireCredentialsHandleW(
+                ptr::null(),
+                package.as_ptr(),
+                SECPKG_CRED_OUTBOUND,
+                ptr::null(),
+                ptr::null(),
+                None,
+                ptr::null(),
+                &mut credentials,
+                ptr::null_mut(),
+            );
+            if status != SEC_E_OK {
+                return Err(format!(
+                    "Acquiring Windows credentials failed: {:#010x}",
+                    status
+                ));
+            }
+
+            let mut context = SecHandle {
+                dwLower: 0,
+                dwUpper: 0,
+            };
+            let mut buffer = SecBuffer {
+                cbBuffer: 0,
+                BufferType: SECBUFFER_TOKEN,
+                pvBuffer: ptr::null_mut(),
+            };
+            let mut output = SecBufferDesc {
+                ulVersion: SECBUFFER_VERSION,
+                cBuffers: 1,
+                pBuffers: &mut buffer,
+            };
+            let mut attributes = 0u32;
+            let status = InitializeSecurityContextW(
+                &credentials,
+                ptr::null(),
+                target.as_ptr(),
+                ISC_REQ_MUTUAL_AUTH | ISC_REQ_ALLOCATE_MEMORY,
+                0,
+                SECURITY_NATIVE_DREP,
+                ptr::null(),
+                0,
+                &mut context,
+                &mut output,
+                &mut attributes,
+                ptr::null_mut(),
+            );
+            let result = if status != SEC_E_OK && status != SEC_I_CONTINUE_NEEDED {
+                Err(format!(
+                    "Windows Negotiate authentication failed: {:#010x}",
+                    status
+                ))
+            } else if buffer.pvBuffer.is_null() {
+                Err("Windows Negotiate authentication produced no token".to_string())
+            } else {
+                Ok(std::slice::from_raw_parts(
+                    buffer.pvBuffer.cast::<u8>(),
+                    buffer.cbBuffer as usize,
+                )
+                .to_vec())
+            };
+
+            if !buffer.pvBuffer.is_null() {
+                FreeContextBuffer(buffer.pvBuffer);
+            }
+            if status == SEC_E_OK || status == SEC_I_CONTINUE_NEEDED {
+                DeleteSecurityContext(&context);
+            }
+            FreeCredentialsHandle(&credentials);
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_is_found_among_the_offered_schemes() {
+        assert!(offers_negotiate(["Negotiate"]));
+        assert!(offers_negotiate(["Basic realm=\"x\"", "negotiate abc=="]));
+        assert!(offers_negotiate(["NTLM, Negotiate"]));
+        assert!(!offers_negotiate(["NegotiateX"]));
+        assert!(!offers_negotiate(["Digest realm=\"x\", nonce=\"n\""]));
+    }
+}
@@ -2,9 +2,19 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use tauri::State;
 
+use super::proxy::ProxyState;
+
+/// Grant type for polling the token endpoint in the device flow (RFC 8628)
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Polling interval when the server does not specify one (RFC 8628 section 3.2)
+const DEFAULT_DEVICE_INTERVAL: u64 = 5;
+/// Lifetime of a device code when the server does not specify one
+const DEFAULT_DEVICE_EXPIRES_IN: u64 = 600;
+
 /// OAuth 2.0 Configuration for token requests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -30,6 +40,11 @@ pub struct OAuth2Config {
     pub refresh_token: Option<String>,
     #[serde(default)]
     pub audience: Option<String>,
+    /// Device authorization endpoint for the device code flow
+    #[serde(default)]
+    pub device_authorization_url: Option<String>,
+    #[serde(default)]
+    pub device_code: Option<String>,
     /// Additional custom parameters to include in the token request
     #[serde(default)]
     pub extra_params: Option<HashMap<String, String>>,
@@ -61,6 +76,69 @@ pub struct OAuth2TokenResponse {
     pub error_description: Option<String>,
 }
 
+impl OAuth2TokenResponse {
+    fn failure(error: &str, description: String) -> Self {
+        Self {
+            success: false,
+            access_token: None,
+            token_type: None,
+            expires_in: None,
+            refresh_token: None,
+            scope: None,
+            id_token: None,
+            error: Some(error.to_string()),
+            error_description: Some(description),
+        }
+    }
+}
+
+/// Device authorization shown to the user while the device code flow polls
+/// for a token
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceAuthorization {
+    pub flow_id: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// A device code flow waiting for the user to approve it
+pub struct DeviceFlow {
+    config: OAuth2Config,
+    interval: u64,
+    expires_at: Instant,
+}
+
+/// Endpoints and capabilities an OpenID Connect provider publishes at
+/// `/.well-known/openid-configuration`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcConfiguration {
+    pub issuer: String,
+    #[serde(default, alias = "authorization_endpoint")]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default, alias = "token_endpoint")]
+    pub token_endpoint: Option<String>,
+    #[serde(default, alias = "device_authorization_endpoint")]
+    pub device_authorization_endpoint: Option<String>,
+    #[serde(default, alias = "userinfo_endpoint")]
+    pub userinfo_endpoint: Option<String>,
+    #[serde(default, alias = "jwks_uri")]
+    pub jwks_uri: Option<String>,
+    #[serde(default, alias = "scopes_supported")]
+    pub scopes_supported: Vec<String>,
+    #[serde(default, alias = "grant_types_supported")]
+    pub grant_types_supported: Vec<String>,
+    #[serde(default, alias = "code_challenge_methods_supported")]
+    pub code_challenge_methods_supported: Vec<String>,
+    #[serde(default, alias = "token_endpoint_auth_methods_supported")]
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+}
+
 /// PKCE (Proof Key for Code Exchange) parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -95,16 +173,27 @@ pub struct AuthorizationUrlParams {
 pub struct OAuth2State {
     /// Maps state parameter to code_verifier for PKCE
     pub pkce_verifiers: Mutex<HashMap<String, String>>,
+    /// Pending device code flows by flow id
+    pub device_flows: Mutex<HashMap<String, DeviceFlow>>,
 }
 
 impl Default for OAuth2State {
     fn default() -> Self {
         Self {
             pkce_verifiers: Mutex::new(HashMap::new()),
+            device_flows: Mutex::new(HashMap::new()),
         }
     }
 }
 
+impl OAuth2State {
+    fn device_flows(&self) -> Result<MutexGuard<'_, HashMap<String, DeviceFlow>>, String> {
+        self.device_flows
+            .lock()
+            .map_err(|e| format!("Failed to lock state: {}", e))
+    }
+}
+
 /// Generate PKCE code verifier and challenge
 /// Returns (code_verifier, code_challenge, code_challenge_method)
 #[tauri::command]
@@ -208,12 +297,237 @@ pub fn oauth2_build_authorization_url(params: AuthorizationUrlParams) -> Result<
 
 /// Exchange credentials for an OAuth 2.0 access token
 #[tauri::command]
-pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenResponse, String> {
-    let client = Client::builder()
-        .user_agent(format!("resonance/{}", env!("CARGO_PKG_VERSION")))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+pub async fn oauth2_get_token(
+    proxy_state: State<'_, ProxyState>,
+    config: OAuth2Config,
+) -> Result<OAuth2TokenResponse, String> {
+    request_token(&proxy_state, &token_client(&proxy_state)?, &config).await
+}
+
+/// Start a device code flow: request a device and user code for the user to
+/// approve on another device. Poll for the token with `oauth2_device_poll`.
+#[tauri::command]
+pub async fn oauth2_device_start(
+    state: State<'_, OAuth2State>,
+    proxy_state: State<'_, ProxyState>,
+    config: OAuth2Config,
+) -> Result<DeviceAuthorization, String> {
+    let url = match config.device_authorization_url.as_deref() {
+        Some(url) if !url.is_empty() => url,
+        _ => return Err("Device authorization URL is required".to_string()),
+    };
+    proxy_state.check_offline(url)?;
+
+    let mut form_params: HashMap<String, String> = HashMap::new();
+    if let Some(scope) = config.scope.as_ref().filter(|scope| !scope.is_empty()) {
+        form_params.insert("scope".to_string(), scope.clone());
+    }
+    if let Some(audience) = config
+        .audience
+        .as_ref()
+        .filter(|audience| !audience.is_empty())
+    {
+        form_params.insert("audience".to_string(), audience.clone());
+    }
+    if let Some(extra) = &config.extra_params {
+        for (key, value) in extra {
+            form_params.insert(key.clone(), value.clone());
+        }
+    }
+
+    let client = token_client(&proxy_state)?;
+    let response = with_client_auth(client.post(url), &config, &mut form_params)
+        .header("Accept", "application/json")
+        .form(&form_params)
+        .send()
+        .await
+        .map_err(|e| format!("Device authorization request failed: {}", e))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    let value: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+
+    if !status.is_success() {
+        let reason = value
+            .get("error_description")
+            .or_else(|| value.get("error"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| format!("HTTP {}", status.as_u16()));
+        return Err(format!("Device authorization failed: {}", reason));
+    }
+
+    let flow_id = uuid::Uuid::new_v4().to_string();
+    let (device_code, authorization) = parse_device_authorization(&value, flow_id.clone())?;
+    let flow = DeviceFlow {
+        config: OAuth2Config {
+            grant_type: DEVICE_CODE_GRANT.to_string(),
+            device_code: Some(device_code),
+            ..config
+        },
+        interval: authorization.interval,
+        expires_at: Instant::now() + Duration::from_secs(authorization.expires_in),
+    };
+    state.device_flows()?.insert(flow_id, flow);
+
+    Ok(authorization)
+}
+
+/// Poll the token endpoint until the user approves or denies a device code
+/// flow, it expires or it is cancelled, honouring the server's `slow_down`.
+#[tauri::command]
+pub async fn oauth2_device_poll(
+    state: State<'_, OAuth2State>,
+    proxy_state: State<'_, ProxyState>,
+    flow_id: String,
+) -> Result<OAuth2TokenResponse, String> {
+    let client = token_client(&proxy_state)?;
+    loop {
+        let interval = match state.device_flows()?.get(&flow_id) {
+            Some(flow) => flow.interval,
+            None => return Err("Unknown or cancelled device code flow".to_string()),
+        };
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        let config = {
+            let mut flows = state.device_flows()?;
+            match flows.get(&flow_id) {
+                Some(flow) if Instant::now() >= flow.expires_at => {
+                    flows.remove(&flow_id);
+                    return Ok(OAuth2TokenResponse::failure(
+                        "expired_token",
+                        "The device code expired before it was approved".to_string(),
+                    ));
+                }
+                Some(flow) => flow.config.clone(),
+                None => {
+                    return Ok(OAuth2TokenResponse::failure(
+                        "access_denied",
+                        "The device code flow was cancelled".to_string(),
+                    ))
+                }
+            }
+        };
+
+        let result = request_token(&proxy_state, &client, &config).await;
+        let error = result
+            .as_ref()
+            .ok()
+            .and_then(|response| response.error.clone());
+        let mut flows = state.device_flows()?;
+        match error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => {
+                if let Some(flow) = flows.get_mut(&flow_id) {
+                    flow.interval += DEFAULT_DEVICE_INTERVAL;
+                }
+            }
+            _ => {
+                flows.remove(&flow_id);
+                return result;
+            }
+        }
+    }
+}
+
+/// Stop polling a device code flow
+#[tauri::command]
+pub fn oauth2_device_cancel(state: State<'_, OAuth2State>, flow_id: String) -> Result<(), String> {
+    state.device_flows()?.remove(&flow_id);
+    Ok(())
+}
 
+/// The discovery document URL of an issuer. An issuer given with the
+/// well-known path already appended is used as is.
+fn discovery_url(issuer: &str) -> Result<String, String> {
+    let issuer = issuer.trim();
+    if !issuer.starts_with("https://") && !issuer.starts_with("http://") {
+        return Err("The issuer must be an http(s) URL".to_string());
+    }
+    if issuer.contains("/.well-known/") {
+        return Ok(issuer.to_string());
+    }
+    Ok(format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    ))
+}
+
+/// Fetch an issuer's OpenID Connect discovery document, so the OAuth 2.0 auth
+/// types can fill in its endpoints instead of having them typed by hand
+#[tauri::command]
+pub async fn oauth2_discover(
+    proxy_state: State<'_, ProxyState>,
+    issuer: String,
+) -> Result<OidcConfiguration, String> {
+    let url = discovery_url(&issuer)?;
+    proxy_state.check_offline(&url)?;
+    let response = token_client(&proxy_state)?
+        .get(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "{} returned HTTP {}",
+            url,
+            response.status().as_u16()
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid OpenID configuration at {}: {}", url, e))
+}
+
+/// Split a device authorization response into the device code (kept in the
+/// backend) and what the user needs to see
+fn parse_device_authorization(
+    value: &serde_json::Value,
+    flow_id: String,
+) -> Result<(String, DeviceAuthorization), String> {
+    let field = |name: &str| value.get(name).and_then(|v| v.as_str()).map(str::to_string);
+    let device_code = field("device_code").ok_or("Response has no device_code")?;
+    let user_code = field("user_code").ok_or("Response has no user_code")?;
+    // Some providers (e.g. Google) still use the draft's `verification_url`.
+    let verification_uri = field("verification_uri")
+        .or_else(|| field("verification_url"))
+        .ok_or("Response has no verification_uri")?;
+
+    Ok((
+        device_code,
+        DeviceAuthorization {
+            flow_id,
+            user_code,
+            verification_uri,
+            verification_uri_complete: field("verification_uri_complete"),
+            expires_in: value
+                .get("expires_in")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_DEVICE_EXPIRES_IN),
+            interval: value
+                .get("interval")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(DEFAULT_DEVICE_INTERVAL),
+        },
+    ))
+}
+
+/// Client for auth server and secret store calls. Redirects are checked
+/// against offline mode; callers check the URL they start from.
+pub(crate) fn token_client(proxy_state: &ProxyState) -> Result<Client, String> {
+    proxy_state.plain_client()
+}
+
+pub(crate) async fn request_token(
+    proxy_state: &ProxyState,
+    client: &Client,
+    config: &OAuth2Config,
+) -> Result<OAuth2TokenResponse, String> {
+    proxy_state.check_offline(&config.token_url)?;
     let mut form_params: HashMap<String, String> = HashMap::new();
 
     // Set grant type
@@ -225,17 +539,10 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
             if let Some(code) = &config.authorization_code {
                 form_params.insert("code".to_string(), code.clone());
             } else {
-                return Ok(OAuth2TokenResponse {
-                    success: false,
-                    access_token: None,
-                    token_type: None,
-                    expires_in: None,
-                    refresh_token: None,
-                    scope: None,
-                    id_token: None,
-                    error: Some("invalid_request".to_string()),
-                    error_description: Some("Authorization code is required".to_string()),
-                });
+                return Ok(OAuth2TokenResponse::failure(
+                    "invalid_request",
+                    "Authorization code is required".to_string(),
+                ));
             }
 
             if let Some(redirect_uri) = &config.redirect_uri {
@@ -262,31 +569,27 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
             if let Some(refresh_token) = &config.refresh_token {
                 form_params.insert("refresh_token".to_string(), refresh_token.clone());
             } else {
-                return Ok(OAuth2TokenResponse {
-                    success: false,
-                    access_token: None,
-                    token_type: None,
-                    expires_in: None,
-                    refresh_token: None,
-                    scope: None,
-                    id_token: None,
-                    error: Some("invalid_request".to_string()),
-                    error_description: Some("Refresh token is required".to_string()),
-                });
+                return Ok(OAuth2TokenResponse::failure(
+                    "invalid_request",
+                    "Refresh token is required".to_string(),
+                ));
+            }
+        }
+        DEVICE_CODE_GRANT => {
+            if let Some(device_code) = &config.device_code {
+                form_params.insert("device_code".to_string(), device_code.clone());
+            } else {
+                return Ok(OAuth2TokenResponse::failure(
+                    "invalid_request",
+                    "Device code is required".to_string(),
+                ));
             }
         }
         _ => {
-            return Ok(OAuth2TokenResponse {
-                success: false,
-                access_token: None,
-                token_type: None,
-                expires_in: None,
-                refresh_token: None,
-                scope: None,
-                id_token: None,
-                error: Some("unsupported_grant_type".to_string()),
-                error_description: Some(format!("Unsupported grant type: {}", config.grant_type)),
-            });
+            return Ok(OAuth2TokenResponse::failure(
+                "unsupported_grant_type",
+                format!("Unsupported grant type: {}", config.grant_type),
+            ));
         }
     }
 
@@ -311,9 +614,33 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
         }
     }
 
-    // Build the request
-    let mut request = client.post(&config.token_url);
+    let request = with_client_auth(client.post(&config.token_url), config, &mut form_params);
+
+    // Send the request
+    let response = request
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .form(&form_params)
+        .send()
+        .await
+        .map_err(|e| format!("Token request failed: {}", e))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    Ok(parse_token_response(status, &body))
+}
 
+/// Authenticate the client to the authorization server as configured: Basic
+/// auth header or `client_id`/`client_secret` form parameters.
+fn with_client_auth(
+    mut request: reqwest::RequestBuilder,
+    config: &OAuth2Config,
+    form_params: &mut HashMap<String, String>,
+) -> reqwest::RequestBuilder {
     // Handle client authentication
     let client_auth_method = config.client_auth_method.as_deref().unwrap_or("body");
 
@@ -342,23 +669,12 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
         }
     }
 
-    // Send the request
-    let response = request
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .header("Accept", "application/json")
-        .form(&form_params)
-        .send()
-        .await
-        .map_err(|e| format!("Token request failed: {}", e))?;
-
-    let status = response.status();
-    let body = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+    request
+}
 
+fn parse_token_response(status: reqwest::StatusCode, body: &str) -> OAuth2TokenResponse {
     // Parse the response
-    let token_response: serde_json::Value = serde_json::from_str(&body).unwrap_or_else(|_| {
+    let token_response: serde_json::Value = serde_json::from_str(body).unwrap_or_else(|_| {
         serde_json::json!({
             "error": "invalid_response",
             "error_description": body
@@ -366,7 +682,7 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
     });
 
     if status.is_success() {
-        Ok(OAuth2TokenResponse {
+        OAuth2TokenResponse {
             success: true,
             access_token: token_response
                 .get("access_token")
@@ -391,9 +707,9 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
                 .map(|s| s.to_string()),
             error: None,
             error_description: None,
-        })
+        }
     } else {
-        Ok(OAuth2TokenResponse {
+        OAuth2TokenResponse {
             success: false,
             access_token: None,
             token_type: None,
@@ -410,7 +726,7 @@ pub async fn oauth2_get_token(config: OAuth2Config) -> Result<OAuth2TokenRespons
                 .get("error_description")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
-        })
+        }
     }
 }
 
@@ -427,3 +743,54 @@ fn rand_bytes<const N: usize>() -> [u8; N] {
 fn base64_url_encode(data: &[u8]) -> String {
     base64::Engine::encode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn device_authorization_accepts_verification_url_and_defaults() {
+        let value = json!({
+            "device_code": "dev-123",
+            "user_code": "ABCD-EFGH",
+            "verification_url": "https://example.com/device"
+        });
+        let (device_code, authorization) =
+            parse_device_authorization(&value, "flow".to_string()).unwrap();
+        assert_eq!(device_code, "dev-123");
+        assert_eq!(authorization.user_code, "ABCD-EFGH");
+        assert_eq!(authorization.verification_uri, "https://example.com/device");
+        assert_eq!(authorization.interval, DEFAULT_DEVICE_INTERVAL);
+        assert_eq!(authorization.expires_in, DEFAULT_DEVICE_EXPIRES_IN);
+
+        let missing = json!({ "user_code": "ABCD-EFGH" });
+        assert!(parse_device_authorization(&missing, "flow".to_string()).is_err());
+    }
+
+    #[test]
+    fn discovery_documents_are_read_from_the_well_known_path() {
+        assert_eq!(
+            discovery_url("https://login.example.com/realms/dev/").unwrap(),
+            "https://login.example.com/realms/dev/.well-known/openid-configuration"
+        );
+        assert_eq!(
+            discovery_url("https://tenant.auth0.com/.well-known/openid-configuration").unwrap(),
+            "https://tenant.auth0.com/.well-known/openid-configuration"
+        );
+        assert!(discovery_url("login.example.com").is_err());
+
+        let config: OidcConfiguration = serde_json::from_value(json!({
+            "issuer": "https://login.example.com",
+            "token_endpoint": "https://login.example.com/token",
+            "scopes_supported": ["openid", "email"]
+        }))
+        .unwrap();
+        assert_eq!(
+            config.token_endpoint.as_deref(),
+            Some("https://login.example.com/token")
+        );
+        assert_eq!(config.scopes_supported, vec!["openid", "email"]);
+        assert!(config.device_authorization_endpoint.is_none());
+    }
+}
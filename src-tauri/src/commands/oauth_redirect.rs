@@ -0,0 +1,233 @@
+//! Loopback redirect listener for the OAuth 2.0 authorization code flow
+//! (RFC 8252 section 7.3). While the user signs in in the browser, a one-shot
+//! HTTP listener on the redirect URI's localhost port catches the redirect
+//! and hands the authorization code back, so it does not have to be copied
+//! out of the address bar.
+
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::State;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use url::Url;
+
+/// How long the listener waits for the browser to come back.
+const REDIRECT_TIMEOUT: Duration = Duration::from_secs(300);
+/// Longest request head read from the browser.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+const SUCCESS_PAGE: &str =
+    "Authorization complete. You can close this window and return to Resonance.";
+const FAILURE_PAGE: &str = "Authorization failed. Return to Resonance for details.";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationRedirect {
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+/// The listener waiting for a redirect, cancelled by dropping its sender.
+#[derive(Default)]
+pub struct OAuthRedirectState {
+    cancel: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+/// Address to listen on and path the redirect URI points at. Only `http`
+/// URIs on the loopback interface can be listened on.
+fn loopback_target(redirect_uri: &str) -> Result<(SocketAddr, String), String> {
+    let url = Url::parse(redirect_uri).map_err(|e| format!("Invalid redirect URI: {}", e))?;
+    let ip = match url.host_str() {
+        Some("localhost") | Some("127.0.0.1") => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        Some("[::1]") => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        _ => {
+            return Err(
+                "Only http://localhost redirect URIs can be caught; paste the code instead"
+                    .to_string(),
+            )
+        }
+    };
+    if url.scheme() != "http" {
+        return Err("Only http redirect URIs can be caught; paste the code instead".to_string());
+    }
+    let port = url.port().unwrap_or(80);
+    Ok((SocketAddr::new(ip, port), url.path().to_string()))
+}
+
+/// The outcome of a request to `target`, or `None` when it is not for the
+/// redirect path (a favicon, say) and the listener keeps waiting.
+fn parse_callback(
+    target: &str,
+    path: &str,
+    expected_state: Option<&str>,
+) -> Option<Result<AuthorizationRedirect, String>> {
+    let url = Url::parse(&format!("http://localhost{}", target)).ok()?;
+    if url.path() != path {
+        return None;
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or(error);
+        return Some(Err(format!("Authorization failed: {}", description)));
+    }
+    let state = param("state");
+    if expected_state.is_some() && state.as_deref() != expected_state {
+        return Some(Err(
+            "The redirect's state does not match the authorization request".to_string(),
+        ));
+    }
+    Some(match param("code") {
+        Some(code) if !code.is_empty() => Ok(AuthorizationRedirect { code, state }),
+        _ => Err("The redirect has no authorization code".to_string()),
+    })
+}
+
+/// The request target (path and query) of the request on `stream`.
+async fn read_request_target(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut buf).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next()?.split_whitespace();
+    request_line.next()?;
+    request_line.next().map(str::to_string)
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\"><title>Resonance</title></head>\
+         <body style=\"font-family: sans-serif\"><p>{}</p></body></html>",
+        message
+    );
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Listen on the redirect URI's localhost port until the browser comes back
+/// from the authorization server, and return the authorization code. The
+/// redirect's `state` must match `expected_state` when given. A newer call
+/// or `oauth2_cancel_redirect` ends the wait.
+#[tauri::command]
+pub async fn oauth2_await_redirect(
+    state: State<'_, OAuthRedirectState>,
+    redirect_uri: String,
+    expected_state: Option<String>,
+) -> Result<AuthorizationRedirect, String> {
+    let (addr, path) = loopback_target(&redirect_uri)?;
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    // Replacing the sender cancels a listener still waiting from an earlier flow
+    *state.cancel.lock().unwrap() = Some(cancel_tx);
+
+    let listener = TcpListener::bind(addr).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::AddrInUse {
+            format!(
+                "Port {} is already in use; paste the code instead",
+                addr.port()
+            )
+        } else {
+            format!("Failed to listen on {}: {}", addr, e)
+        }
+    })?;
+
+    let wait = async {
+        loop {
+            let (mut stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| format!("Failed to accept the redirect: {}", e))?;
+            let Some(target) = read_request_target(&mut stream).await else {
+                continue;
+            };
+            match parse_callback(&target, &path, expected_state.as_deref()) {
+                Some(result) => {
+                    let page = if result.is_ok() {
+                        SUCCESS_PAGE
+                    } else {
+                        FAILURE_PAGE
+                    };
+                    respond(&mut stream, "200 OK", page).await;
+                    return result;
+                }
+                None => respond(&mut stream, "404 Not Found", FAILURE_PAGE).await,
+            }
+        }
+    };
+
+    tokio::select! {
+        result = wait => result,
+        _ = cancel_rx => Err("Cancelled".to_string()),
+        _ = tokio::time::sleep(REDIRECT_TIMEOUT) => {
+            Err("Timed out waiting for the authorization redirect".to_string())
+        }
+    }
+}
+
+/// Stop waiting for an authorization redirect.
+#[tauri::command]
+pub fn oauth2_cancel_redirect(state: State<'_, OAuthRedirectState>) -> Result<(), String> {
+    state.cancel.lock().unwrap().take();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catches_the_code_on_the_redirect_path() {
+        let (addr, path) = loopback_target("http://localhost:8080/callback").unwrap();
+        assert_eq!(addr, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(path, "/callback");
+        assert_eq!(
+            loopback_target("http://[::1]/cb").unwrap().0,
+            "[::1]:80".parse().unwrap()
+        );
+        assert!(loopback_target("https://localhost:8080/callback").is_err());
+        assert!(loopback_target("https://app.example.com/callback").is_err());
+
+        assert_eq!(
+            parse_callback("/callback?code=abc%2F1&state=s1", "/callback", Some("s1")),
+            Some(Ok(AuthorizationRedirect {
+                code: "abc/1".to_string(),
+                state: Some("s1".to_string()),
+            }))
+        );
+        assert_eq!(parse_callback("/favicon.ico", "/callback", None), None);
+        assert!(matches!(
+            parse_callback("/callback?code=abc&state=other", "/callback", Some("s1")),
+            Some(Err(_))
+        ));
+        assert_eq!(
+            parse_callback(
+                "/callback?error=access_denied&error_description=User+declined",
+                "/callback",
+                None
+            ),
+            Some(Err("Authorization failed: User declined".to_string()))
+        );
+        assert!(matches!(
+            parse_callback("/callback", "/callback", None),
+            Some(Err(_))
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+//! OpenTelemetry export of sent requests: when the `otlpEndpoint` setting
+//! names a collector, each request becomes a client span posted there as
+//! OTLP/HTTP JSON, with its timing phases as span events. A request sent with
+//! a `traceparent` header is exported as the span that header names, so the
+//! server's spans nest under it.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use uuid::Uuid;
+
+use super::api_request::ApiResponse;
+use super::proxy::ProxyState;
+use super::workspaces::store_file;
+
+const SETTINGS_KEY: &str = "settings";
+const TRACES_PATH: &str = "/v1/traces";
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `SPAN_KIND_CLIENT` and the `STATUS_CODE_*` values of the OTLP protobuf.
+const SPAN_KIND_CLIENT: u8 = 3;
+const STATUS_CODE_OK: u8 = 1;
+const STATUS_CODE_ERROR: u8 = 2;
+
+/// The collector's traces URL, if export is on. A bare collector address
+/// (`http://localhost:4318`) gets the standard traces path.
+fn traces_url(app: &AppHandle) -> Option<String> {
+    let settings = app.store(store_file(app)).ok()?.get(SETTINGS_KEY)?;
+    let endpoint = settings.get("otlpEndpoint")?.as_str()?.trim();
+    if endpoint.is_empty() {
+        return None;
+    }
+    let endpoint = endpoint.trim_end_matches('/');
+    Some(if endpoint.ends_with(TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{}{}", endpoint, TRACES_PATH)
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn int_attribute(key: &str, value: u64) -> Value {
+    // OTLP JSON carries 64-bit integers as strings
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+/// Trace and span id of the request's `traceparent`, or fresh ones.
+fn span_ids(response: &ApiResponse) -> (String, String) {
+    response
+        .trace_ids
+        .as_ref()
+        .and_then(|ids| {
+            let mut parts = ids.traceparent.split('-').skip(1);
+            let trace_id = parts.next()?;
+            let span_id = parts.next()?;
+            (trace_id.len() == 32 && span_id.len() == 16)
+                .then(|| (trace_id.to_string(), span_id.to_string()))
+        })
+        .unwrap_or_else(|| {
+            let span_id = Uuid::new_v4().simple().to_string()[..16].to_string();
+            (Uuid::new_v4().simple().to_string(), span_id)
+        })
+}
+
+/// The OTLP `ExportTraceServiceRequest` for one request.
+fn request_span(method: &str, url: &str, response: &ApiResponse) -> Value {
+    let timings = &response.timings;
+    let millis_to_nanos = |ms: u64| (ms * 1_000_000).to_string();
+    let start = timings.start_time;
+    let (trace_id, span_id) = span_ids(response);
+
+    let mut attributes = vec![
+        string_attribute("http.request.method", method),
+        string_attribute("url.full", url),
+    ];
+    if let Some(host) = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+    {
+        attributes.push(string_attribute("server.address", &host));
+    }
+    if let Some(status) = response.status {
+        attributes.push(int_attribute(
+            "http.response.status_code",
+            u64::from(status),
+        ));
+    }
+    if let Some(size) = response.size {
+        attributes.push(int_attribute("http.response.body.size", size as u64));
+    }
+    if let Some(ids) = &response.trace_ids {
+        attributes.push(string_attribute(
+            "http.request.header.x-request-id",
+            &ids.request_id,
+        ));
+    }
+
+    // Connection phases follow each other from the start; the first byte is
+    // measured from the start itself.
+    let mut events = Vec::new();
+    let mut offset = 0;
+    for (name, duration) in [
+        ("dns.lookup", timings.dns_lookup),
+        ("tcp.connect", timings.tcp_connection),
+        ("tls.handshake", timings.tls_handshake),
+    ] {
+        if duration > 0 {
+            offset += duration;
+            events.push(json!({
+                "timeUnixNano": millis_to_nanos(start + offset),
+                "name": name,
+                "attributes": [int_attribute("duration_ms", duration)],
+            }));
+        }
+    }
+    if timings.first_byte > 0 {
+        events.push(json!({
+            "timeUnixNano": millis_to_nanos(start + timings.first_byte),
+            "name": "first_byte",
+        }));
+    }
+
+    // Client spans count 4xx responses as errors too
+    let failed = !matches!(response.status, Some(status) if status < 400);
+    let status = if failed {
+        json!({
+            "code": STATUS_CODE_ERROR,
+            "message": response.message.clone().unwrap_or_default(),
+        })
+    } else {
+        json!({ "code": STATUS_CODE_OK })
+    };
+
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [string_attribute("service.name", "resonance")],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "resonance", "version": env!("CARGO_PKG_VERSION") },
+                "spans": [{
+                    "traceId": trace_id,
+                    "spanId": span_id,
+                    "name": method,
+                    "kind": SPAN_KIND_CLIENT,
+                    "startTimeUnixNano": millis_to_nanos(start),
+                    "endTimeUnixNano": millis_to_nanos(start + timings.total),
+                    "attributes": attributes,
+                    "events": events,
+                    "status": status,
+                }],
+            }],
+        }],
+    })
+}
+
+/// Post the span of a sent request to the configured collector in the
+/// background. Export failures never affect the request.
+pub(crate) fn export_request_span(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    method: &str,
+    url: &str,
+    response: &ApiResponse,
+) {
+    let Some(traces_url) = traces_url(app) else {
+        return;
+    };
+    if response.cancelled == Some(true) || proxy_state.check_offline(&traces_url).is_err() {
+        return;
+    }
+    let body = request_span(method, url, response);
+    let redirect = proxy_state.redirect_policy();
+    tauri::async_runtime::spawn(async move {
+        let Ok(client) = reqwest::Client::builder()
+            .timeout(EXPORT_TIMEOUT)
+            .redirect(redirect)
+            .build()
+        else {
+            return;
+        };
+        if let Err(e) = client.post(&traces_url).json(&body).send().await {
+            eprintln!("OTLP export to {} failed: {}", traces_url, e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(value: Value) -> ApiResponse {
+        let mut response = json!({
+            "success": true,
+            "status": 200,
+            "headers": {},
+            "setCookies": [],
+            "size": 12,
+            "timings": {
+                "startTime": 1_000, "dnsLookup": 2, "tcpConnection": 3, "tlsHandshake": 4,
+                "firstByte": 40, "download": 5, "total": 45
+            }
+        });
+        response
+            .as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(response).unwrap()
+    }
+
+    #[test]
+    fn builds_client_spans_that_join_the_sent_traceparent() {
+        let traced = response(json!({
+            "traceIds": {
+                "requestId": "r-1",
+                "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+            }
+        }));
+        let request = request_span("GET", "https://api.test/items", &traced);
+        let span = &request["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(span["spanId"], "b7ad6b7169203331");
+        assert_eq!(span["kind"], SPAN_KIND_CLIENT);
+        assert_eq!(span["startTimeUnixNano"], "1000000000");
+        assert_eq!(span["endTimeUnixNano"], "1045000000");
+        assert_eq!(span["status"]["code"], STATUS_CODE_OK);
+        let events: Vec<&str> = span["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            ["dns.lookup", "tcp.connect", "tls.handshake", "first_byte"]
+        );
+        assert_eq!(span["events"][2]["timeUnixNano"], "1009000000");
+
+        let failed = response(json!({ "status": null, "message": "Connection failed." }));
+        let request = request_span("POST", "https://api.test/items", &failed);
+        let span = &request["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["status"]["code"], STATUS_CODE_ERROR);
+        assert_eq!(span["status"]["message"], "Connection failed.");
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+    }
+}
@@ -0,0 +1,278 @@
+//! Pagination follower: sends a request, then keeps requesting the next page
+//! (from a `Link` header, a cursor in the body or a page number) until the
+//! last page or a page limit, and returns every page's items with per-page
+//! timings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::Instant;
+use tauri::{AppHandle, State};
+
+use super::api_request::{execute_api_request, ApiResponse, RequestOptions, RequestTimings};
+use super::login_flow::json_path;
+use super::proxy::ProxyState;
+
+const DEFAULT_MAX_PAGES: usize = 20;
+/// Upper bound for [`PaginationOptions::max_pages`].
+const MAX_PAGES: usize = 200;
+
+fn default_first_page() -> u64 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PaginationStrategy {
+    /// `Link: <url>; rel="next"` response header (RFC 8288)
+    LinkHeader,
+    /// Cursor read from each page's body and sent as a query parameter
+    #[serde(rename_all = "camelCase")]
+    Cursor {
+        /// JSONPath of the next cursor, e.g. `$.meta.nextCursor`
+        cursor_path: String,
+        param: String,
+    },
+    /// Page number query parameter, counted up from `start` until a page has
+    /// no items
+    #[serde(rename_all = "camelCase")]
+    Page {
+        param: String,
+        #[serde(default = "default_first_page")]
+        start: u64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationOptions {
+    pub strategy: PaginationStrategy,
+    /// JSONPath of the item array in each page (the body itself when unset)
+    #[serde(default)]
+    pub items_path: Option<String>,
+    /// Pages to fetch at most (defaults to 20, capped at [`MAX_PAGES`])
+    #[serde(default)]
+    pub max_pages: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageResult {
+    pub url: String,
+    pub status: Option<u16>,
+    pub item_count: usize,
+    pub size: Option<usize>,
+    pub timings: RequestTimings,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginationResult {
+    pub pages: Vec<PageResult>,
+    /// Items of every page, in page order
+    pub items: Vec<Value>,
+    /// Why following stopped: "lastPage" | "limit" | "loop" | "error"
+    pub stop_reason: String,
+    /// Why following stopped early ("error" or "loop")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Wall-clock duration of all pages in ms
+    pub duration: u64,
+}
+
+fn response_body(response: &ApiResponse) -> Value {
+    match &response.data {
+        Some(Value::String(text)) => serde_json::from_str(text).unwrap_or(Value::Null),
+        Some(data) => data.clone(),
+        None => Value::Null,
+    }
+}
+
+/// The items of one page; a page without an array at `items_path` has none.
+fn page_items(body: &Value, items_path: Option<&str>) -> Vec<Value> {
+    let items = match items_path.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => json_path(body, path),
+        None => Some(body),
+    };
+    items.and_then(Value::as_array).cloned().unwrap_or_default()
+}
+
+/// The `rel="next"` target of a `Link` header value.
+fn next_link(link_header: &str) -> Option<&str> {
+    link_header.split(',').find_map(|link| {
+        let (target, params) = link.split_once(';')?;
+        let is_next = params.split(';').any(|param| {
+            param
+                .split_once('=')
+                .filter(|(name, _)| name.trim().eq_ignore_ascii_case("rel"))
+                .is_some_and(|(_, value)| {
+                    value
+                        .trim()
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+        });
+        let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        is_next.then_some(target)
+    })
+}
+
+fn with_query_param(url: &str, name: &str, value: &str) -> Result<String, String> {
+    let mut parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| key != name)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(name, value);
+    Ok(parsed.to_string())
+}
+
+/// URL of the page after `page` (0-based), or `None` on the last page.
+fn next_page_url(
+    strategy: &PaginationStrategy,
+    url: &str,
+    page: u64,
+    response: &ApiResponse,
+    body: &Value,
+    item_count: usize,
+) -> Result<Option<String>, String> {
+    match strategy {
+        PaginationStrategy::LinkHeader => {
+            let Some(target) = response
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("link"))
+                .and_then(|(_, value)| next_link(value))
+            else {
+                return Ok(None);
+            };
+            let base = url::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+            base.join(target)
+                .map(|next| Some(next.to_string()))
+                .map_err(|e| format!("Invalid next link {}: {}", target, e))
+        }
+        PaginationStrategy::Cursor { cursor_path, param } => {
+            let cursor = match json_path(body, cursor_path) {
+                Some(Value::String(cursor)) => cursor.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            if cursor.is_empty() {
+                return Ok(None);
+            }
+            with_query_param(url, param, &cursor).map(Some)
+        }
+        PaginationStrategy::Page { param, start } => {
+            if item_count == 0 {
+                return Ok(None);
+            }
+            with_query_param(url, param, &(start + page + 1).to_string()).map(Some)
+        }
+    }
+}
+
+/// Sends `request_options` and follows its pages as `options` describes.
+#[tauri::command]
+pub async fn pagination_follow(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    request_options: RequestOptions,
+    options: PaginationOptions,
+) -> Result<PaginationResult, String> {
+    let mut request_options =
+        super::secrets::resolve_secret_references(&app, request_options).await?;
+    if let Some(collection_id) = request_options.collection_id.as_deref() {
+        request_options.proxy = super::collections::collection_proxy(&app, collection_id)?;
+    }
+    if let PaginationStrategy::Page { param, start } = &options.strategy {
+        request_options.url = with_query_param(&request_options.url, param, &start.to_string())?;
+    }
+    let max_pages = options
+        .max_pages
+        .unwrap_or(DEFAULT_MAX_PAGES)
+        .clamp(1, MAX_PAGES);
+
+    let started = Instant::now();
+    let mut result = PaginationResult {
+        pages: Vec::new(),
+        items: Vec::new(),
+        stop_reason: "limit".to_string(),
+        message: None,
+        duration: 0,
+    };
+    let mut seen = HashSet::new();
+    let mut url = request_options.url.clone();
+    for page in 0..max_pages as u64 {
+        seen.insert(url.clone());
+        let mut page_options = request_options.clone();
+        page_options.url = url.clone();
+        let response =
+            execute_api_request(&proxy_state, page_options, std::future::pending()).await?;
+        let body = response_body(&response);
+        let items = page_items(&body, options.items_path.as_deref());
+        result.pages.push(PageResult {
+            url: url.clone(),
+            status: response.status,
+            item_count: items.len(),
+            size: response.size,
+            timings: response.timings.clone(),
+        });
+
+        if !response.success {
+            result.stop_reason = "error".to_string();
+            result.message = Some(match response.status {
+                Some(status) => format!("Page {} returned {}", page + 1, status),
+                None => response
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| format!("Page {} failed", page + 1)),
+            });
+            break;
+        }
+        let item_count = items.len();
+        result.items.extend(items);
+
+        match next_page_url(&options.strategy, &url, page, &response, &body, item_count)? {
+            None => {
+                result.stop_reason = "lastPage".to_string();
+                break;
+            }
+            Some(next) if seen.contains(&next) => {
+                result.stop_reason = "loop".to_string();
+                result.message = Some(format!("Page {} links back to {}", page + 1, next));
+                break;
+            }
+            Some(next) => url = next,
+        }
+    }
+    result.duration = started.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_next_pages_for_each_strategy() {
+        let header =
+            r#"<https://api.test/items?page=1>; rel="prev", </items?page=3>; rel="next last""#;
+        assert_eq!(next_link(header), Some("/items?page=3"));
+        assert_eq!(next_link(r#"<https://api.test/a>; rel="prev""#), None);
+
+        assert_eq!(
+            with_query_param("https://api.test/items?cursor=a&limit=5", "cursor", "b c").unwrap(),
+            "https://api.test/items?limit=5&cursor=b+c"
+        );
+
+        let body = serde_json::json!({ "data": [1, 2], "meta": { "next": null } });
+        assert_eq!(page_items(&body, Some("$.data")).len(), 2);
+        assert!(page_items(&body, None).is_empty());
+    }
+}
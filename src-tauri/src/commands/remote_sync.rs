@@ -0,0 +1,760 @@
+//! Opt-in sync of collections and environments with a shared remote.
+//!
+//! The workspace is exchanged as a single bundle file (`resonance-sync.json`)
+//! stored in a git repository, on a WebDAV share or in an S3 bucket. Conflicts
+//! are detected by remembering the hash of the remote bundle and of the local
+//! data at the last successful sync: pushing over a remote that changed since,
+//! or pulling over local changes that were never pushed, is refused unless
+//! forced.
+//!
+//! Secret variables are blanked in the bundle; their values stay in the local
+//! secret store.
+//!
+//! Git uses the system `git` binary and the user's own credentials (SSH keys,
+//! credential helpers); the WebDAV password and S3 secret key are kept in the
+//! OS keychain.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use super::api_request::{build_aws_v4_headers, sha256_hex, AwsAuthConfig};
+use super::collections::{
+    collection_get_endpoint_data, collection_get_variables, collection_save_endpoint_data,
+    collection_save_variables, collections_get_all, persist_collection, Collection, EndpointData,
+};
+use super::proxy::ProxyState;
+use super::secrets::{secret_get, secret_set};
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const SETTINGS_KEY: &str = "syncSettings";
+const STATE_KEY: &str = "syncState";
+const ENVIRONMENTS_KEY: &str = "environments";
+const SYNC_FILE: &str = "resonance-sync.json";
+const BUNDLE_FORMAT: &str = "resonance-sync";
+const BUNDLE_VERSION: u32 = 1;
+const WEBDAV_PASSWORD_ACCOUNT: &str = "sync|webdavPassword";
+const S3_SECRET_ACCOUNT: &str = "sync|s3SecretAccessKey";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRemote {
+    pub url: String,
+    #[serde(default)]
+    pub branch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WebDavRemote {
+    /// Folder URL; the bundle is stored inside it
+    pub url: String,
+    #[serde(default)]
+    pub username: String,
+    /// Write-only: kept in the keychain, always returned empty
+    #[serde(default)]
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct S3Remote {
+    /// Custom endpoint for S3-compatible stores; AWS when empty
+    #[serde(default)]
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    #[serde(default)]
+    pub prefix: String,
+    pub access_key_id: String,
+    /// Write-only: kept in the keychain, always returned empty
+    #[serde(default)]
+    pub secret_access_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSettings {
+    pub enabled: bool,
+    /// "git", "webdav" or "s3"
+    #[serde(default)]
+    pub backend: String,
+    #[serde(default)]
+    pub git: GitRemote,
+    #[serde(default)]
+    pub webdav: WebDavRemote,
+    #[serde(default)]
+    pub s3: S3Remote,
+}
+
+/// Hashes recorded at the last successful push or pull.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SyncState {
+    remote_hash: Option<String>,
+    local_hash: Option<String>,
+    synced_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundledCollection {
+    collection: Collection,
+    #[serde(default)]
+    endpoints: HashMap<String, EndpointData>,
+    #[serde(default)]
+    variables: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncBundle {
+    format: String,
+    version: u32,
+    collections: Vec<BundledCollection>,
+    #[serde(default)]
+    environments: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResult {
+    /// "pushed", "pulled", "upToDate" or "conflict"
+    pub status: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<i64>,
+}
+
+impl SyncResult {
+    fn new(status: &str, message: impl Into<String>, synced_at: Option<i64>) -> Self {
+        Self {
+            status: status.to_string(),
+            message: message.into(),
+            synced_at,
+        }
+    }
+}
+
+fn read_settings(app: &AppHandle) -> SyncSettings {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(SETTINGS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn read_state(app: &AppHandle) -> SyncState {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(STATE_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn write_key<T: Serialize>(app: &AppHandle, key: &str, value: &T) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    store.set(
+        key.to_string(),
+        serde_json::to_value(value).map_err(|e| e.to_string())?,
+    );
+    save_store(app)
+}
+
+fn record_sync(app: &AppHandle, remote_hash: String, local_hash: String) -> Result<i64, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    write_key(
+        app,
+        STATE_KEY,
+        &SyncState {
+            remote_hash: Some(remote_hash),
+            local_hash: Some(local_hash),
+            synced_at: Some(now),
+        },
+    )?;
+    Ok(now)
+}
+
+// ---------------------------------------------------------------------------
+// Bundle
+// ---------------------------------------------------------------------------
+
+fn endpoint_ids(collection: &Collection) -> Vec<String> {
+    let folder_endpoints = collection
+        .folders
+        .iter()
+        .filter_map(|folder| folder.get("endpoints").and_then(Value::as_array))
+        .flatten();
+    collection
+        .endpoints
+        .iter()
+        .chain(folder_endpoints)
+        .filter_map(|e| e.get("id").and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+fn read_environments(app: &AppHandle) -> Vec<Value> {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(ENVIRONMENTS_KEY))
+        .and_then(|v| v.get("items").and_then(Value::as_array).cloned())
+        .unwrap_or_default()
+}
+
+/// Blank the values of an environment's secret variables. They live in the
+/// local secret store and never leave the machine with the bundle.
+fn blank_environment_secrets(environment: &mut Value) {
+    let secret_keys: Vec<String> = environment
+        .get("secretKeys")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|k| k.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let Some(variables) = environment
+        .get_mut("variables")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+    for key in secret_keys {
+        if let Some(value) = variables.get_mut(&key) {
+            *value = Value::String(String::new());
+        }
+    }
+}
+
+/// Blank the values of collection variables flagged `secret`.
+fn blank_variable_secrets(variables: &mut [Value]) {
+    for entry in variables.iter_mut() {
+        if entry.get("secret").and_then(Value::as_bool) == Some(true) {
+            entry["value"] = Value::String(String::new());
+        }
+    }
+}
+
+async fn build_bundle(app: &AppHandle) -> Result<SyncBundle, String> {
+    let mut collections = Vec::new();
+    for mut collection in collections_get_all(app.clone()).await? {
+        let mut endpoints = HashMap::new();
+        for id in endpoint_ids(&collection) {
+            let data = collection_get_endpoint_data(app.clone(), collection.id.clone(), id.clone())
+                .await?;
+            endpoints.insert(id, data);
+        }
+        let mut variables = collection_get_variables(app.clone(), collection.id.clone()).await?;
+        blank_variable_secrets(&mut variables);
+        // Where a collection lives is a local detail
+        collection.storage_path = None;
+        collection.storage_format = None;
+        collections.push(BundledCollection {
+            collection,
+            endpoints,
+            variables,
+        });
+    }
+    collections.sort_by(|a, b| a.collection.id.cmp(&b.collection.id));
+
+    let mut environments = read_environments(app);
+    environments.iter_mut().for_each(blank_environment_secrets);
+
+    Ok(SyncBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        collections,
+        environments,
+    })
+}
+
+/// Hash of the bundle's content. Endpoint maps are serialized in key order so
+/// equal data always hashes the same.
+fn bundle_hash(bundle: &SyncBundle) -> Result<String, String> {
+    let mut value = serde_json::to_value(bundle).map_err(|e| e.to_string())?;
+    if let Some(collections) = value.get_mut("collections").and_then(Value::as_array_mut) {
+        for entry in collections {
+            if let Some(endpoints) = entry.get_mut("endpoints").and_then(Value::as_object_mut) {
+                endpoints.sort_keys();
+            }
+        }
+    }
+    let bytes = serde_json::to_vec(&value).map_err(|e| e.to_string())?;
+    Ok(sha256_hex(&bytes))
+}
+
+fn parse_bundle(bytes: &[u8]) -> Result<SyncBundle, String> {
+    let bundle: SyncBundle = serde_json::from_slice(bytes)
+        .map_err(|e| format!("Remote sync file is not valid: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT || bundle.version > BUNDLE_VERSION {
+        return Err("Remote sync file was written by an unsupported version".to_string());
+    }
+    Ok(bundle)
+}
+
+/// Merge remote environments into the local list by id, keeping local-only
+/// environments and the active selection.
+fn merge_environments(local: &mut Vec<Value>, remote: Vec<Value>) {
+    for environment in remote {
+        let id = environment.get("id").cloned();
+        match local.iter_mut().find(|e| e.get("id") == id.as_ref()) {
+            Some(existing) => *existing = environment,
+            None => local.push(environment),
+        }
+    }
+}
+
+async fn apply_bundle(app: &AppHandle, bundle: SyncBundle) -> Result<(), String> {
+    for entry in bundle.collections {
+        let persisted = persist_collection(app, entry.collection)?;
+        for (id, data) in entry.endpoints {
+            collection_save_endpoint_data(app.clone(), persisted.id.clone(), id, data).await?;
+        }
+        collection_save_variables(app.clone(), persisted.id.clone(), entry.variables).await?;
+    }
+
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let _guard = lock_store();
+    let mut environments = store
+        .get(ENVIRONMENTS_KEY)
+        .filter(Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({ "items": [] }));
+    let mut items = environments
+        .get("items")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    merge_environments(&mut items, bundle.environments);
+    environments["items"] = Value::Array(items);
+    store.set(ENVIRONMENTS_KEY.to_string(), environments);
+    save_store(app)
+}
+
+// ---------------------------------------------------------------------------
+// Remotes
+// ---------------------------------------------------------------------------
+
+fn git_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(super::workspaces::data_dir(app)?.join("sync").join("git"))
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn git_branch(remote: &GitRemote) -> &str {
+    if remote.branch.trim().is_empty() {
+        "main"
+    } else {
+        remote.branch.trim()
+    }
+}
+
+/// Bring the local clone to the remote branch's latest commit.
+fn git_checkout(dir: &Path, remote: &GitRemote) -> Result<(), String> {
+    if !dir.join(".git").exists() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        run_git(dir, &["init", "--quiet"])?;
+        run_git(dir, &["remote", "add", "origin", &remote.url])?;
+    } else {
+        run_git(dir, &["remote", "set-url", "origin", &remote.url])?;
+    }
+    run_git(dir, &["fetch", "--quiet", "origin"])?;
+
+    let branch = git_branch(remote);
+    let tracking = format!("origin/{}", branch);
+    if run_git(dir, &["rev-parse", "--verify", "--quiet", &tracking]).is_ok() {
+        run_git(dir, &["checkout", "--quiet", "-B", branch, &tracking])?;
+    } else {
+        // Empty remote or new branch: start from nothing
+        run_git(
+            dir,
+            &["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)],
+        )?;
+    }
+    Ok(())
+}
+
+fn git_push(dir: &Path, remote: &GitRemote, body: &[u8]) -> Result<(), String> {
+    std::fs::write(dir.join(SYNC_FILE), body)
+        .map_err(|e| format!("Failed to write sync file: {}", e))?;
+    run_git(dir, &["add", SYNC_FILE])?;
+    run_git(
+        dir,
+        &["commit", "--quiet", "-m", "Update Resonance workspace"],
+    )?;
+    run_git(dir, &["push", "--quiet", "origin", git_branch(remote)])
+        .map_err(|e| format!("{} (the remote may have changed, pull first)", e))?;
+    Ok(())
+}
+
+fn s3_object_url(remote: &S3Remote) -> String {
+    let prefix = remote.prefix.trim_matches('/');
+    let key = if prefix.is_empty() {
+        SYNC_FILE.to_string()
+    } else {
+        format!("{}/{}", prefix, SYNC_FILE)
+    };
+    if remote.endpoint.trim().is_empty() {
+        format!(
+            "https://{}.s3.{}.amazonaws.com/{}",
+            remote.bucket, remote.region, key
+        )
+    } else {
+        format!(
+            "{}/{}/{}",
+            remote.endpoint.trim_end_matches('/'),
+            remote.bucket,
+            key
+        )
+    }
+}
+
+async fn s3_request(
+    proxy_state: &ProxyState,
+    remote: &S3Remote,
+    method: reqwest::Method,
+    body: Vec<u8>,
+) -> Result<reqwest::Response, String> {
+    let secret = secret_get(S3_SECRET_ACCOUNT.to_string())
+        .await?
+        .ok_or_else(|| "S3 secret access key is not set".to_string())?;
+    let aws = AwsAuthConfig {
+        access_key_id: remote.access_key_id.clone(),
+        secret_access_key: secret,
+        region: remote.region.clone(),
+        service: "s3".to_string(),
+        session_token: None,
+    };
+    let url = s3_object_url(remote);
+    proxy_state.check_offline(&url)?;
+    let headers = build_aws_v4_headers(&aws, method.as_str(), &url, &HashMap::new(), &body)?;
+
+    let mut request = proxy_state.plain_client()?.request(method, &url).body(body);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    request
+        .send()
+        .await
+        .map_err(|e| format!("S3 request failed: {}", e))
+}
+
+fn webdav_file_url(remote: &WebDavRemote) -> String {
+    format!("{}/{}", remote.url.trim_end_matches('/'), SYNC_FILE)
+}
+
+async fn webdav_request(
+    proxy_state: &ProxyState,
+    remote: &WebDavRemote,
+    method: reqwest::Method,
+    body: Vec<u8>,
+) -> Result<reqwest::Response, String> {
+    let url = webdav_file_url(remote);
+    proxy_state.check_offline(&url)?;
+    let mut request = proxy_state.plain_client()?.request(method, &url).body(body);
+    if !remote.username.is_empty() {
+        let password = secret_get(WEBDAV_PASSWORD_ACCOUNT.to_string()).await?;
+        request = request.basic_auth(&remote.username, password);
+    }
+    request
+        .send()
+        .await
+        .map_err(|e| format!("WebDAV request failed: {}", e))
+}
+
+/// Turn a GET response into the file's bytes, or `None` when it does not exist yet.
+async fn read_response(response: reqwest::Response) -> Result<Option<Vec<u8>>, String> {
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("Remote returned HTTP {}", status.as_u16()));
+    }
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    Ok(Some(bytes.to_vec()))
+}
+
+fn check_upload(response: reqwest::Response) -> Result<(), String> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "Remote rejected the upload: HTTP {}",
+            status.as_u16()
+        ));
+    }
+    Ok(())
+}
+
+async fn fetch_remote(app: &AppHandle, settings: &SyncSettings) -> Result<Option<Vec<u8>>, String> {
+    match settings.backend.as_str() {
+        "git" => {
+            let dir = git_dir(app)?;
+            let remote = settings.git.clone();
+            let file = dir.join(SYNC_FILE);
+            tokio::task::spawn_blocking(move || {
+                git_checkout(&dir, &remote).map(|_| std::fs::read(&file).ok())
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        }
+        "webdav" => {
+            let proxy_state = app.state::<ProxyState>();
+            let response = webdav_request(
+                &proxy_state,
+                &settings.webdav,
+                reqwest::Method::GET,
+                Vec::new(),
+            )
+            .await?;
+            read_response(response).await
+        }
+        "s3" => {
+            let proxy_state = app.state::<ProxyState>();
+            let response =
+                s3_request(&proxy_state, &settings.s3, reqwest::Method::GET, Vec::new()).await?;
+            read_response(response).await
+        }
+        other => Err(format!("Unknown sync backend: {}", other)),
+    }
+}
+
+/// Upload the bundle. For git this commits on top of the checkout made by
+/// `fetch_remote`.
+async fn upload_remote(
+    app: &AppHandle,
+    settings: &SyncSettings,
+    body: Vec<u8>,
+) -> Result<(), String> {
+    match settings.backend.as_str() {
+        "git" => {
+            let dir = git_dir(app)?;
+            let remote = settings.git.clone();
+            tokio::task::spawn_blocking(move || git_push(&dir, &remote, &body))
+                .await
+                .map_err(|e| e.to_string())?
+        }
+        "webdav" => {
+            let proxy_state = app.state::<ProxyState>();
+            check_upload(
+                webdav_request(&proxy_state, &settings.webdav, reqwest::Method::PUT, body).await?,
+            )
+        }
+        "s3" => {
+            let proxy_state = app.state::<ProxyState>();
+            check_upload(s3_request(&proxy_state, &settings.s3, reqwest::Method::PUT, body).await?)
+        }
+        other => Err(format!("Unknown sync backend: {}", other)),
+    }
+}
+
+fn enabled_settings(app: &AppHandle) -> Result<SyncSettings, String> {
+    let settings = read_settings(app);
+    if !settings.enabled {
+        return Err("Sync is not enabled".to_string());
+    }
+    Ok(settings)
+}
+
+// ---------------------------------------------------------------------------
+// Commands
+// ---------------------------------------------------------------------------
+
+/// Sync settings, with the keychain-held credentials left empty.
+#[tauri::command]
+pub async fn sync_settings_get(app: AppHandle) -> Result<SyncSettings, String> {
+    Ok(read_settings(&app))
+}
+
+/// Save sync settings. Non-empty credentials go to the keychain; empty ones
+/// keep the stored value.
+#[tauri::command]
+pub async fn sync_settings_set(app: AppHandle, mut settings: SyncSettings) -> Result<(), String> {
+    let password = std::mem::take(&mut settings.webdav.password);
+    if !password.is_empty() {
+        secret_set(WEBDAV_PASSWORD_ACCOUNT.to_string(), password).await?;
+    }
+    let secret_key = std::mem::take(&mut settings.s3.secret_access_key);
+    if !secret_key.is_empty() {
+        secret_set(S3_SECRET_ACCOUNT.to_string(), secret_key).await?;
+    }
+
+    // A different remote starts over: nothing is known about its content yet
+    let previous = read_settings(&app);
+    if serde_json::to_value(&previous).ok() != serde_json::to_value(&settings).ok() {
+        write_key(&app, STATE_KEY, &SyncState::default())?;
+    }
+    write_key(&app, SETTINGS_KEY, &settings)
+}
+
+/// Upload local collections and environments. Refused when the remote changed
+/// since the last sync, unless `force` is set.
+#[tauri::command]
+pub async fn sync_push(app: AppHandle, force: Option<bool>) -> Result<SyncResult, String> {
+    let settings = enabled_settings(&app)?;
+    let state = read_state(&app);
+    let bundle = build_bundle(&app).await?;
+    let local_hash = bundle_hash(&bundle)?;
+
+    let remote_hash = match fetch_remote(&app, &settings).await? {
+        Some(bytes) => Some(bundle_hash(&parse_bundle(&bytes)?)?),
+        None => None,
+    };
+    if remote_hash.as_deref() == Some(local_hash.as_str()) {
+        let synced_at = record_sync(&app, local_hash.clone(), local_hash)?;
+        return Ok(SyncResult::new(
+            "upToDate",
+            "Remote is up to date",
+            Some(synced_at),
+        ));
+    }
+    if remote_hash.is_some() && remote_hash != state.remote_hash && !force.unwrap_or(false) {
+        return Ok(SyncResult::new(
+            "conflict",
+            "The remote changed since the last sync. Pull first or force the push.",
+            state.synced_at,
+        ));
+    }
+
+    let body = serde_json::to_vec_pretty(&bundle).map_err(|e| e.to_string())?;
+    upload_remote(&app, &settings, body).await?;
+    let synced_at = record_sync(&app, local_hash.clone(), local_hash)?;
+    Ok(SyncResult::new(
+        "pushed",
+        "Pushed to remote",
+        Some(synced_at),
+    ))
+}
+
+/// Download the remote bundle and apply it. Refused when there are local
+/// changes that were never pushed, unless `force` is set.
+#[tauri::command]
+pub async fn sync_pull(app: AppHandle, force: Option<bool>) -> Result<SyncResult, String> {
+    let settings = enabled_settings(&app)?;
+    let state = read_state(&app);
+    let Some(bytes) = fetch_remote(&app, &settings).await? else {
+        return Ok(SyncResult::new(
+            "upToDate",
+            "Nothing has been pushed to the remote yet",
+            state.synced_at,
+        ));
+    };
+    let remote = parse_bundle(&bytes)?;
+    let remote_hash = bundle_hash(&remote)?;
+    let local_hash = bundle_hash(&build_bundle(&app).await?)?;
+
+    if remote_hash == local_hash || state.remote_hash.as_deref() == Some(remote_hash.as_str()) {
+        return Ok(SyncResult::new(
+            "upToDate",
+            "Already up to date",
+            state.synced_at,
+        ));
+    }
+    let local_changed = state.local_hash.as_deref() != Some(local_hash.as_str());
+    if local_changed && !force.unwrap_or(false) {
+        return Ok(SyncResult::new(
+            "conflict",
+            "There are local changes that were not pushed. Push first or force the pull.",
+            state.synced_at,
+        ));
+    }
+
+    apply_bundle(&app, remote).await?;
+    let local_hash = bundle_hash(&build_bundle(&app).await?)?;
+    let synced_at = record_sync(&app, remote_hash, local_hash)?;
+    Ok(SyncResult::new(
+        "pulled",
+        "Pulled from remote",
+        Some(synced_at),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn secret_values_are_blanked_in_the_bundle() {
+        let mut environment = json!({
+            "id": "dev",
+            "variables": {"host": "api.test", "token": "t-1"},
+            "secretKeys": ["token"]
+        });
+        blank_environment_secrets(&mut environment);
+        assert_eq!(
+            environment["variables"],
+            json!({"host": "api.test", "token": ""})
+        );
+
+        let mut variables = vec![
+            json!({"key": "apiKey", "value": "k-1", "secret": true}),
+            json!({"key": "host", "value": "api.test"}),
+        ];
+        blank_variable_secrets(&mut variables);
+        assert_eq!(variables[0]["value"], "");
+        assert_eq!(variables[1]["value"], "api.test");
+    }
+
+    #[test]
+    fn remote_environments_replace_by_id_and_keep_local_ones() {
+        let mut local = vec![
+            json!({"id": "dev", "name": "Dev", "variables": {"host": "old"}}),
+            json!({"id": "mine", "name": "Local only"}),
+        ];
+        merge_environments(
+            &mut local,
+            vec![
+                json!({"id": "dev", "name": "Dev", "variables": {"host": "new"}}),
+                json!({"id": "prod", "name": "Prod"}),
+            ],
+        );
+        assert_eq!(local.len(), 3);
+        assert_eq!(local[0]["variables"]["host"], "new");
+        assert_eq!(local[1]["id"], "mine");
+        assert_eq!(local[2]["id"], "prod");
+    }
+
+    #[test]
+    fn s3_urls_use_virtual_hosts_on_aws_and_paths_elsewhere() {
+        let mut remote = S3Remote {
+            region: "eu-west-1".to_string(),
+            bucket: "team".to_string(),
+            prefix: "/api/".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            s3_object_url(&remote),
+            "https://team.s3.eu-west-1.amazonaws.com/api/resonance-sync.json"
+        );
+        remote.endpoint = "http://localhost:9000/".to_string();
+        remote.prefix.clear();
+        assert_eq!(
+            s3_object_url(&remote),
+            "http://localhost:9000/team/resonance-sync.json"
+        );
+    }
+}
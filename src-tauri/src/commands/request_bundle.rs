@@ -0,0 +1,451 @@
+//! Request bundles: one request packed into a single small file (or a
+//! `resonance://bundle?data=...` link) for sending to a colleague. A bundle
+//! carries the endpoint, its data file (body, headers, auth, scripts) and the
+//! values of the variables it references. Secret and encrypted variables are
+//! listed without their values; stored auth is already redacted on disk.
+//!
+//! Importing adds the request to the collection of the same name, or to a new
+//! collection, and adds the variables that collection does not define yet.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use tokio::sync::oneshot;
+
+use super::collections::{
+    collection_get, collection_get_endpoint_data, collection_get_variables,
+    collection_save_endpoint_data, collection_save_variables, collections_get_all,
+    persist_collection, Collection, EndpointData,
+};
+use super::deep_link::SCHEME;
+use super::variables::{active_environment, variable_pattern};
+
+/// Version of the bundle format, stored under [`BUNDLE_MARKER`].
+const BUNDLE_VERSION: u32 = 1;
+/// Top-level field identifying a bundle document.
+pub(crate) const BUNDLE_MARKER: &str = "resonanceRequestBundle";
+const BUNDLE_EXTENSION: &str = "resonance-request.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBundle {
+    /// Format version; also marks the document as a bundle
+    pub resonance_request_bundle: u32,
+    /// Unix timestamp (ms)
+    pub exported_at: i64,
+    pub collection_name: String,
+    #[serde(default)]
+    pub base_url: String,
+    /// The endpoint as stored in its collection file
+    pub endpoint: Value,
+    #[serde(default)]
+    pub data: EndpointData,
+    /// Referenced variables and their values; secret ones are empty
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// Variables whose values were left out
+    #[serde(default)]
+    pub stripped_secrets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImport {
+    pub collection_id: String,
+    pub endpoint_id: String,
+    /// The bundle's request was added to a collection created for it
+    pub created_collection: bool,
+    /// Variables the target collection now has but without a value
+    pub needs_values: Vec<String>,
+}
+
+fn references_in(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => {
+            for caps in variable_pattern().captures_iter(text) {
+                names.insert(caps[1].to_string());
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| references_in(item, names)),
+        Value::Object(map) => map.values().for_each(|item| references_in(item, names)),
+        _ => {}
+    }
+}
+
+/// Values for `referenced` and for whatever those values reference in turn,
+/// environment values winning over collection ones as when sending. Returns
+/// the variables and the names of the secret ones, whose values are dropped.
+fn bundle_variables(
+    referenced: BTreeSet<String>,
+    collection_variables: &[Value],
+    environment: Option<&Value>,
+) -> (BTreeMap<String, String>, Vec<String>) {
+    // name -> (value, secret)
+    let mut known: BTreeMap<String, (String, bool)> = BTreeMap::new();
+    for entry in collection_variables {
+        let Some(key) = entry.get("key").and_then(Value::as_str) else {
+            continue;
+        };
+        if key.is_empty() || entry.get("enabled").and_then(Value::as_bool) == Some(false) {
+            continue;
+        }
+        let value = entry
+            .get("value")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let secret = entry.get("secret").and_then(Value::as_bool) == Some(true);
+        known.insert(key.to_string(), (value.to_string(), secret));
+    }
+    if let Some(environment) = environment {
+        let secret_keys: BTreeSet<&str> = environment
+            .get("secretKeys")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let encrypted = environment.get("encrypted").is_some();
+        let variables = environment.get("variables").and_then(Value::as_object);
+        for (key, value) in variables.into_iter().flatten() {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            let secret = encrypted || secret_keys.contains(key.as_str());
+            known.insert(key.clone(), (text, secret));
+        }
+    }
+
+    let mut variables = BTreeMap::new();
+    let mut stripped = Vec::new();
+    let mut pending: Vec<String> = referenced.into_iter().collect();
+    while let Some(name) = pending.pop() {
+        if variables.contains_key(&name) {
+            continue;
+        }
+        let Some((value, secret)) = known.get(&name) else {
+            continue;
+        };
+        if *secret {
+            stripped.push(name.clone());
+            variables.insert(name, String::new());
+        } else {
+            let mut nested = BTreeSet::new();
+            references_in(&Value::String(value.clone()), &mut nested);
+            pending.extend(nested);
+            variables.insert(name, value.clone());
+        }
+    }
+    stripped.sort();
+    (variables, stripped)
+}
+
+/// The endpoint `endpoint_id` at the root of the collection or in a folder.
+fn find_endpoint<'a>(collection: &'a Collection, endpoint_id: &str) -> Option<&'a Value> {
+    let nested = collection
+        .folders
+        .iter()
+        .filter_map(|folder| folder.get("endpoints").and_then(Value::as_array))
+        .flatten();
+    collection
+        .endpoints
+        .iter()
+        .chain(nested)
+        .find(|endpoint| endpoint.get("id").and_then(Value::as_str) == Some(endpoint_id))
+}
+
+async fn build_bundle(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> Result<RequestBundle, String> {
+    let collection = collection_get(app.clone(), collection_id.to_string()).await?;
+    let endpoint = find_endpoint(&collection, endpoint_id)
+        .cloned()
+        .ok_or_else(|| format!("Endpoint {} not found", endpoint_id))?;
+    let data = collection_get_endpoint_data(
+        app.clone(),
+        collection_id.to_string(),
+        endpoint_id.to_string(),
+    )
+    .await?;
+
+    let mut referenced = BTreeSet::new();
+    references_in(&endpoint, &mut referenced);
+    references_in(
+        &serde_json::to_value(&data).map_err(|e| e.to_string())?,
+        &mut referenced,
+    );
+    references_in(&Value::String(collection.base_url.clone()), &mut referenced);
+    let collection_variables =
+        collection_get_variables(app.clone(), collection_id.to_string()).await?;
+    let environment = active_environment(app);
+    let (variables, stripped_secrets) =
+        bundle_variables(referenced, &collection_variables, environment.as_ref());
+
+    Ok(RequestBundle {
+        resonance_request_bundle: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        collection_name: collection.name,
+        base_url: collection.base_url,
+        endpoint,
+        data,
+        variables,
+        stripped_secrets,
+    })
+}
+
+/// Parse a bundle from a file's content or from a `resonance://bundle` link.
+pub(crate) fn parse_bundle(text: &str) -> Result<RequestBundle, String> {
+    let text = text.trim();
+    let json = if text.starts_with(&format!("{}:", SCHEME)) {
+        let url = url::Url::parse(text).map_err(|e| format!("Invalid link: {}", e))?;
+        let data = url
+            .query_pairs()
+            .find(|(key, _)| key == "data")
+            .map(|(_, value)| value.into_owned())
+            .ok_or("Bundle links need a data parameter")?;
+        let bytes = URL_SAFE_NO_PAD
+            .decode(data.trim_end_matches('='))
+            .map_err(|e| format!("Invalid bundle link: {}", e))?;
+        String::from_utf8(bytes).map_err(|e| format!("Invalid bundle link: {}", e))?
+    } else {
+        text.to_string()
+    };
+    let bundle: RequestBundle =
+        serde_json::from_str(&json).map_err(|e| format!("Not a request bundle: {}", e))?;
+    if bundle.resonance_request_bundle > BUNDLE_VERSION {
+        return Err(format!(
+            "The bundle was made by a newer version of Resonance (format {})",
+            bundle.resonance_request_bundle
+        ));
+    }
+    Ok(bundle)
+}
+
+/// Save one request as a bundle file.
+#[tauri::command]
+pub async fn export_request_bundle(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<Value, String> {
+    let bundle = build_bundle(&app, &collection_id, &endpoint_id).await?;
+    let name = bundle
+        .endpoint
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or("request");
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+    app.dialog()
+        .file()
+        .set_file_name(format!(
+            "{}.{}",
+            super::collections::sanitize_file_component(name),
+            BUNDLE_EXTENSION
+        ))
+        .add_filter("Resonance Request", &["json"])
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+    let file_path = rx.await.map_err(|e| format!("Dialog error: {}", e))?;
+    let Some(path) = file_path else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+    std::fs::write(file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+        "strippedSecrets": bundle.stripped_secrets,
+    }))
+}
+
+/// One request as a `resonance://bundle?data=...` link.
+#[tauri::command]
+pub async fn request_bundle_link(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<String, String> {
+    let bundle = build_bundle(&app, &collection_id, &endpoint_id).await?;
+    let json =
+        serde_json::to_string(&bundle).map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+    Ok(format!(
+        "{}://bundle?data={}",
+        SCHEME,
+        URL_SAFE_NO_PAD.encode(json)
+    ))
+}
+
+/// Read a bundle from a `resonance://bundle` link, or from a file (picked
+/// when no path is given).
+#[tauri::command]
+pub async fn request_bundle_open(
+    app: AppHandle,
+    file_path: Option<String>,
+    link: Option<String>,
+) -> Result<Option<RequestBundle>, String> {
+    if let Some(link) = link {
+        return parse_bundle(&link).map(Some);
+    }
+    let path = match file_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+            app.dialog()
+                .file()
+                .add_filter("Resonance Request", &["json"])
+                .pick_file(move |file_path| {
+                    let _ = tx.send(file_path);
+                });
+            let Some(path) = rx.await.map_err(|e| format!("Dialog error: {}", e))? else {
+                return Ok(None);
+            };
+            path.as_path().ok_or("Invalid file path")?.to_path_buf()
+        }
+    };
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+    parse_bundle(&content).map(Some)
+}
+
+/// Add a bundle's request to the collection it came from (matched by name),
+/// or to a new collection, along with the variables the collection lacks.
+#[tauri::command]
+pub async fn request_bundle_import(
+    app: AppHandle,
+    bundle: RequestBundle,
+) -> Result<BundleImport, String> {
+    let existing = collections_get_all(app.clone())
+        .await?
+        .into_iter()
+        .find(|collection| collection.name == bundle.collection_name);
+    let created_collection = existing.is_none();
+    let mut collection = match existing {
+        Some(collection) => collection,
+        None => serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "name": bundle.collection_name,
+            "baseUrl": bundle.base_url,
+        }))
+        .map_err(|e| e.to_string())?,
+    };
+
+    let endpoint_id = uuid::Uuid::new_v4().to_string();
+    let mut endpoint = bundle.endpoint.clone();
+    endpoint["id"] = Value::String(endpoint_id.clone());
+    collection.endpoints.push(endpoint);
+    let collection = persist_collection(&app, collection)?;
+    collection_save_endpoint_data(
+        app.clone(),
+        collection.id.clone(),
+        endpoint_id.clone(),
+        bundle.data,
+    )
+    .await?;
+
+    let mut variables = collection_get_variables(app.clone(), collection.id.clone()).await?;
+    let defined: BTreeSet<String> = variables
+        .iter()
+        .filter_map(|entry| entry.get("key").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect();
+    let mut needs_values = Vec::new();
+    for (key, value) in bundle.variables {
+        if defined.contains(&key) {
+            continue;
+        }
+        let secret = bundle.stripped_secrets.contains(&key);
+        if secret {
+            needs_values.push(key.clone());
+        }
+        variables.push(serde_json::json!({ "key": key, "value": value, "secret": secret }));
+    }
+    if variables.len() > defined.len() {
+        collection_save_variables(app.clone(), collection.id.clone(), variables).await?;
+    }
+
+    Ok(BundleImport {
+        collection_id: collection.id,
+        endpoint_id,
+        created_collection,
+        needs_values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn collects_referenced_variables_and_strips_secrets() {
+        let endpoint = json!({
+            "id": "e1",
+            "name": "Get user",
+            "method": "GET",
+            "path": "{{baseUrl}}/users/{{userId}}"
+        });
+        let mut referenced = BTreeSet::new();
+        references_in(&endpoint, &mut referenced);
+        references_in(
+            &json!({ "headers": [{ "key": "Authorization", "value": "Bearer {{token}}" }] }),
+            &mut referenced,
+        );
+
+        let collection_variables = vec![
+            json!({ "key": "baseUrl", "value": "{{scheme}}://api.example.com" }),
+            json!({ "key": "scheme", "value": "http" }),
+            json!({ "key": "userId", "value": "7" }),
+            json!({ "key": "unused", "value": "x" }),
+        ];
+        let environment = json!({
+            "id": "env1",
+            "variables": { "scheme": "https", "token": "" },
+            "secretKeys": ["token"]
+        });
+        let (variables, stripped) =
+            bundle_variables(referenced, &collection_variables, Some(&environment));
+        assert_eq!(
+            variables,
+            BTreeMap::from([
+                (
+                    "baseUrl".to_string(),
+                    "{{scheme}}://api.example.com".to_string()
+                ),
+                ("scheme".to_string(), "https".to_string()),
+                ("token".to_string(), String::new()),
+                ("userId".to_string(), "7".to_string()),
+            ])
+        );
+        assert_eq!(stripped, ["token"]);
+
+        let bundle = RequestBundle {
+            resonance_request_bundle: BUNDLE_VERSION,
+            exported_at: 0,
+            collection_name: "Users".to_string(),
+            base_url: String::new(),
+            endpoint,
+            data: EndpointData::default(),
+            variables,
+            stripped_secrets: stripped,
+        };
+        let json = serde_json::to_string(&bundle).unwrap();
+        let link = format!("{}://bundle?data={}", SCHEME, URL_SAFE_NO_PAD.encode(&json));
+        assert_eq!(parse_bundle(&link).unwrap().collection_name, "Users");
+        assert_eq!(parse_bundle(&json).unwrap().variables.len(), 4);
+        assert!(parse_bundle("{\"hello\": 1}").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Live lifecycle events for a single HTTP request, emitted as
+//! `request-timeline` while it runs so the response panel can draw the
+//! waterfall as phases complete instead of only after the response arrives.
+//!
+//! Phases, in order: `queued`, `dns`, `connected`, `tls`, `requestSent`,
+//! `firstByte`, any number of `chunk`s, then `complete`; or `failed` /
+//! `cancelled` at any point.
+//! `connected` and `tls` come from the timing probe and are skipped when the
+//! probe is (behind a proxy or with client certificates).
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+/// Minimum gap between two `chunk` events, so large downloads don't flood the
+/// webview with one event per network read.
+const CHUNK_INTERVAL_MS: u64 = 50;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimelineEventPayload {
+    tab_id: String,
+    phase: &'static str,
+    /// Milliseconds since the request was queued
+    elapsed_ms: u64,
+    /// Body bytes received so far (`chunk` and `complete` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<u64>,
+}
+
+/// Rate limit for `chunk` events, keyed on elapsed milliseconds.
+struct ChunkThrottle {
+    /// Elapsed ms of the last emitted chunk (`u64::MAX` = none yet)
+    last: AtomicU64,
+}
+
+impl ChunkThrottle {
+    fn new() -> Self {
+        Self {
+            last: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    fn ready(&self, elapsed_ms: u64) -> bool {
+        let last = self.last.load(Ordering::Relaxed);
+        if last != u64::MAX && elapsed_ms < last + CHUNK_INTERVAL_MS {
+            return false;
+        }
+        self.last.store(elapsed_ms, Ordering::Relaxed);
+        true
+    }
+}
+
+struct Sink {
+    app: AppHandle,
+    tab_id: String,
+    start: Instant,
+    chunks: ChunkThrottle,
+}
+
+/// Event sink for one request, tied to the workspace tab that sent it.
+/// Requests without a tab (runner, login flows) use [`Timeline::none`].
+pub(crate) struct Timeline(Option<Sink>);
+
+impl Timeline {
+    /// Starts the clock and emits `queued`.
+    pub(crate) fn start(app: &AppHandle, tab_id: &str) -> Self {
+        let timeline = Self(Some(Sink {
+            app: app.clone(),
+            tab_id: tab_id.to_string(),
+            start: Instant::now(),
+            chunks: ChunkThrottle::new(),
+        }));
+        timeline.phase("queued");
+        timeline
+    }
+
+    pub(crate) fn none() -> Self {
+        Self(None)
+    }
+
+    fn emit(&self, phase: &'static str, bytes: Option<u64>, is_chunk: bool) {
+        let Some(sink) = &self.0 else {
+            return;
+        };
+        let elapsed_ms = sink.start.elapsed().as_millis() as u64;
+        if is_chunk && !sink.chunks.ready(elapsed_ms) {
+            return;
+        }
+        let _ = sink.app.emit(
+            "request-timeline",
+            TimelineEventPayload {
+                tab_id: sink.tab_id.clone(),
+                phase,
+                elapsed_ms,
+                bytes,
+            },
+        );
+    }
+
+    pub(crate) fn phase(&self, phase: &'static str) {
+        self.emit(phase, None, false);
+    }
+
+    /// Reports body progress, at most once per [`CHUNK_INTERVAL_MS`].
+    pub(crate) fn chunk(&self, received: usize) {
+        self.emit("chunk", Some(received as u64), true);
+    }
+
+    pub(crate) fn complete(&self, received: usize) {
+        self.emit("complete", Some(received as u64), false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttles_chunk_events() {
+        let throttle = ChunkThrottle::new();
+        assert!(throttle.ready(0));
+        assert!(!throttle.ready(10));
+        assert!(!throttle.ready(49));
+        assert!(throttle.ready(50));
+        assert!(!throttle.ready(99));
+        assert!(throttle.ready(120));
+    }
+}
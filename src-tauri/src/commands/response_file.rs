@@ -0,0 +1,295 @@
+//! Saving a response body to a file.
+//!
+//! `send_api_request` keeps the body of the last response of each workspace
+//! tab exactly as it was received, so binary downloads (images, archives,
+//! protobuf) are written byte for byte. History entries only keep the decoded
+//! body, so they are written as text, with structured JSON bodies
+//! pretty-printed.
+
+use bytes::Bytes;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::oneshot;
+
+use super::api_request::RequestState;
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+/// Responses kept at once; the oldest is dropped when another comes in.
+const MAX_KEPT_RESPONSES: usize = 8;
+
+/// A response body kept for saving, with what is needed to name the file.
+#[derive(Clone)]
+pub(crate) struct KeptResponse {
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Bytes,
+}
+
+/// The last response of each workspace tab, keyed by tab id.
+#[derive(Default)]
+pub struct KeptResponses {
+    responses: Mutex<VecDeque<(String, KeptResponse)>>,
+}
+
+impl KeptResponses {
+    pub(crate) fn keep(&self, key: String, response: KeptResponse) {
+        let mut responses = self.responses.lock().unwrap();
+        responses.retain(|(existing, _)| *existing != key);
+        if responses.len() >= MAX_KEPT_RESPONSES {
+            responses.pop_front();
+        }
+        responses.push_back((key, response));
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<KeptResponse> {
+        let responses = self.responses.lock().unwrap();
+        responses
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, response)| response.clone())
+    }
+}
+
+fn disposition_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"(?i)filename(\*)?\s*=\s*(?:[\w-]+'[\w-]*')?"?([^";]+)"?"#).unwrap()
+    })
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = if bytes[i] == b'%' {
+            text.get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn extension_for(content_type: &str) -> &'static str {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    match mime.as_str() {
+        "application/json" | "application/problem+json" | "application/ld+json" => "json",
+        m if m.ends_with("+json") => "json",
+        "application/xml" | "text/xml" => "xml",
+        m if m.ends_with("+xml") => "xml",
+        "text/html" => "html",
+        "text/csv" => "csv",
+        "text/plain" => "txt",
+        "application/pdf" => "pdf",
+        "application/zip" => "zip",
+        "application/gzip" => "gz",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "application/x-protobuf" | "application/protobuf" => "pb",
+        _ => "bin",
+    }
+}
+
+/// File name offered in the save dialog: the `Content-Disposition` file name
+/// when there is one, else the last segment of the URL path, with an
+/// extension matching the content type when it has none.
+fn suggested_file_name(url: &str, headers: &HashMap<String, String>) -> String {
+    let from_disposition = header(headers, "content-disposition").and_then(|disposition| {
+        // Prefer the RFC 5987 `filename*` form when both are present
+        let mut names: Vec<_> = disposition_pattern().captures_iter(disposition).collect();
+        names.sort_by_key(|caps| caps.get(1).is_none());
+        names.first().map(|caps| {
+            let name = caps[2].trim();
+            if caps.get(1).is_some() {
+                percent_decode(name)
+            } else {
+                name.to_string()
+            }
+        })
+    });
+    let name = from_disposition
+        .or_else(|| {
+            url::Url::parse(url)
+                .ok()?
+                .path_segments()?
+                .rev()
+                .find(|segment| !segment.is_empty())
+                .map(percent_decode)
+        })
+        .map(|name| {
+            name.rsplit(['/', '\\'])
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        })
+        .filter(|name| !name.is_empty() && name != "." && name != "..")
+        .unwrap_or_else(|| "response".to_string());
+    if name.contains('.') {
+        name
+    } else {
+        let content_type = header(headers, "content-type").unwrap_or("");
+        format!("{}.{}", name, extension_for(content_type))
+    }
+}
+
+/// URL, headers and body of a history entry.
+fn history_response(app: &AppHandle, id: &str) -> Result<KeptResponse, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let history = store.get(HISTORY_KEY).unwrap_or(Value::Null);
+    let entry = history
+        .as_array()
+        .and_then(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.get("id").and_then(Value::as_str) == Some(id))
+        })
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+    let response = entry
+        .get("response")
+        .ok_or_else(|| "This history entry has no response".to_string())?;
+    let body = match response.get("data") {
+        Some(Value::String(text)) => text.clone().into_bytes(),
+        Some(Value::Null) | None => {
+            return Err("This history entry has no response body".to_string())
+        }
+        Some(data) => serde_json::to_vec_pretty(data).map_err(|e| e.to_string())?,
+    };
+    let url = entry
+        .pointer("/request/url")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+    let headers = response
+        .get("headers")
+        .and_then(|headers| serde_json::from_value(headers.clone()).ok())
+        .unwrap_or_default();
+    Ok(KeptResponse {
+        url,
+        headers,
+        body: Bytes::from(body),
+    })
+}
+
+/// Write a response body to a file chosen in a save dialog: the one of
+/// history entry `history_id` when given, else the last response kept under
+/// `response_key`.
+#[tauri::command]
+pub async fn save_response_to_file(
+    app: AppHandle,
+    state: State<'_, RequestState>,
+    response_key: Option<String>,
+    history_id: Option<String>,
+) -> Result<Value, String> {
+    let KeptResponse { url, headers, body } = match history_id.as_deref() {
+        Some(id) => history_response(&app, id)?,
+        None => response_key
+            .as_deref()
+            .and_then(|key| state.kept_responses.get(key))
+            .ok_or_else(|| {
+                "This response is no longer available; send the request again to save it"
+                    .to_string()
+            })?,
+    };
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+    app.dialog()
+        .file()
+        .set_file_name(suggested_file_name(&url, &headers))
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+    let Some(path) = rx.await.map_err(|e| format!("Dialog error: {}", e))? else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    tokio::fs::write(file_path, &body)
+        .await
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+        "size": body.len()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn file_names_come_from_the_disposition_or_the_url() {
+        let disposition = headers(&[(
+            "Content-Disposition",
+            "attachment; filename=\"report.csv\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf",
+        )]);
+        assert_eq!(
+            suggested_file_name("https://x.test/dl", &disposition),
+            "résumé.pdf"
+        );
+        let plain = headers(&[(
+            "content-disposition",
+            "attachment; filename=../../etc/passwd",
+        )]);
+        assert_eq!(suggested_file_name("https://x.test/", &plain), "passwd.bin");
+
+        let png = headers(&[("content-type", "image/png")]);
+        assert_eq!(
+            suggested_file_name("https://x.test/avatars/42/", &png),
+            "42.png"
+        );
+        let json = headers(&[("Content-Type", "application/json; charset=utf-8")]);
+        assert_eq!(
+            suggested_file_name("https://x.test", &json),
+            "response.json"
+        );
+        assert_eq!(
+            suggested_file_name("https://x.test/files/a%20b.tar.gz", &json),
+            "a b.tar.gz"
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Searching the response bodies kept in history and in saved endpoint
+//! examples, by plain text or regular expression. Each match comes with
+//! snippets of the surrounding text.
+
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::collections::{collections_get_all, resolve_collection_dir};
+use super::endpoint_examples::read_examples;
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+const DEFAULT_LIMIT: usize = 100;
+const SNIPPETS_PER_MATCH: usize = 3;
+/// Characters of context shown on each side of a match.
+const SNIPPET_CONTEXT: usize = 60;
+/// Compiled size limit, so a pathological pattern fails instead of hanging.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MatchSource {
+    History,
+    Example,
+}
+
+/// A matched piece of a body, split so the frontend can highlight it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseMatch {
+    pub source: MatchSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_name: Option<String>,
+    pub method: String,
+    /// The request URL for history entries, the endpoint path for examples
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// When the response was received or the example saved (ms since the epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    pub match_count: usize,
+    pub snippets: Vec<Snippet>,
+}
+
+fn build_matcher(query: &str, regex: bool, case_sensitive: bool) -> Result<Regex, String> {
+    let pattern = if regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("Invalid regular expression: {}", e))
+}
+
+/// A body as shown in the response viewer: JSON pretty-printed.
+fn body_text(data: &Value) -> String {
+    match data {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+/// Up to `max` characters of `text` ending at its end, on a char boundary.
+fn tail(text: &str, max: usize) -> &str {
+    let start = text
+        .char_indices()
+        .rev()
+        .nth(max.saturating_sub(1))
+        .map_or(0, |(index, _)| index);
+    &text[start..]
+}
+
+/// Up to `max` characters of `text` from its start, on a char boundary.
+fn head(text: &str, max: usize) -> &str {
+    let end = text
+        .char_indices()
+        .nth(max)
+        .map_or(text.len(), |(index, _)| index);
+    &text[..end]
+}
+
+/// Snippets are shown on one line: whitespace runs become one space.
+fn one_line(text: &str) -> String {
+    let mut line = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                line.push(' ');
+            }
+            in_space = true;
+        } else {
+            line.push(c);
+            in_space = false;
+        }
+    }
+    line
+}
+
+/// The number of matches in `text` and snippets of the first ones.
+fn search_text(matcher: &Regex, text: &str) -> Option<(usize, Vec<Snippet>)> {
+    let mut count = 0;
+    let mut snippets = Vec::new();
+    for found in matcher.find_iter(text).filter(|found| !found.is_empty()) {
+        count += 1;
+        if snippets.len() < SNIPPETS_PER_MATCH {
+            let before = tail(&text[..found.start()], SNIPPET_CONTEXT);
+            let after = head(&text[found.end()..], SNIPPET_CONTEXT);
+            snippets.push(Snippet {
+                before: format!(
+                    "{}{}",
+                    if before.len() < found.start() {
+                        "…"
+                    } else {
+                        ""
+                    },
+                    one_line(before).trim_start()
+                ),
+                matched: one_line(found.as_str()),
+                after: format!(
+                    "{}{}",
+                    one_line(after).trim_end(),
+                    if after.len() < text.len() - found.end() {
+                        "…"
+                    } else {
+                        ""
+                    }
+                ),
+            });
+        }
+    }
+    (count > 0).then_some((count, snippets))
+}
+
+/// Matches in the recorded responses of `history`, newest entry first.
+fn history_matches(history: &Value, matcher: &Regex, limit: usize) -> Vec<ResponseMatch> {
+    let Some(entries) = history.as_array() else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let response = entry.get("response")?;
+            let (match_count, snippets) = search_text(matcher, &body_text(response.get("data")?))?;
+            let request = entry.get("request");
+            let text = |key: &str| {
+                request
+                    .and_then(|request| request.get(key))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            };
+            Some(ResponseMatch {
+                source: MatchSource::History,
+                history_id: entry.get("id").and_then(Value::as_str).map(str::to_string),
+                collection_id: text("collectionId"),
+                endpoint_id: text("endpointId"),
+                example_name: None,
+                method: text("method").unwrap_or_default(),
+                url: text("url").unwrap_or_default(),
+                status: response
+                    .get("status")
+                    .and_then(Value::as_u64)
+                    .and_then(|status| u16::try_from(status).ok()),
+                timestamp: entry.get("timestamp").and_then(Value::as_i64),
+                match_count,
+                snippets,
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Matches in the examples saved on the endpoints of all collections.
+async fn example_matches(app: &AppHandle, matcher: &Regex, limit: usize) -> Vec<ResponseMatch> {
+    let mut matches = Vec::new();
+    let Ok(collections) = collections_get_all(app.clone()).await else {
+        return matches;
+    };
+    for collection in collections {
+        let Ok(Some(dir)) = resolve_collection_dir(app, &collection.id) else {
+            continue;
+        };
+        for endpoint in &collection.endpoints {
+            let Some(endpoint_id) = endpoint.get("id").and_then(Value::as_str) else {
+                continue;
+            };
+            let field = |key: &str| {
+                endpoint
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            for example in read_examples(&dir, endpoint_id) {
+                let Some((match_count, snippets)) = search_text(matcher, &example.body) else {
+                    continue;
+                };
+                matches.push(ResponseMatch {
+                    source: MatchSource::Example,
+                    history_id: None,
+                    collection_id: Some(collection.id.clone()),
+                    endpoint_id: Some(endpoint_id.to_string()),
+                    example_name: Some(example.name),
+                    method: field("method"),
+                    url: field("path"),
+                    status: Some(example.status),
+                    timestamp: Some(example.saved_at),
+                    match_count,
+                    snippets,
+                });
+                if matches.len() >= limit {
+                    return matches;
+                }
+            }
+        }
+    }
+    matches
+}
+
+/// Search the response bodies of history entries and, unless
+/// `include_examples` is false, of saved examples. `query` is plain text
+/// unless `regex` is set; matching ignores case unless `case_sensitive`.
+#[tauri::command]
+pub async fn search_response_bodies(
+    app: AppHandle,
+    query: String,
+    regex: Option<bool>,
+    case_sensitive: Option<bool>,
+    include_examples: Option<bool>,
+    limit: Option<usize>,
+) -> Result<Vec<ResponseMatch>, String> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let matcher = build_matcher(
+        &query,
+        regex.unwrap_or(false),
+        case_sensitive.unwrap_or(false),
+    )?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+
+    let history = {
+        let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+        store.get(HISTORY_KEY).unwrap_or(Value::Null)
+    };
+    let mut matches = history_matches(&history, &matcher, limit);
+    if include_examples.unwrap_or(true) && matches.len() < limit {
+        let remaining = limit - matches.len();
+        matches.extend(example_matches(&app, &matcher, remaining).await);
+    }
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn finds_matches_with_snippets_in_recorded_responses() {
+        let history = json!([
+            {
+                "id": "h2",
+                "timestamp": 2,
+                "request": { "method": "GET", "url": "https://api.test/orders", "collectionId": "c1" },
+                "response": { "status": 502, "data": { "error": { "code": "E_UPSTREAM_42" } } }
+            },
+            {
+                "id": "h1",
+                "timestamp": 1,
+                "request": { "method": "POST", "url": "https://api.test/pay" },
+                "response": { "status": 500, "data": "Fatal: e_upstream_7 after retry\nthen E_UPSTREAM_8" }
+            },
+            { "id": "h0", "request": { "method": "GET", "url": "https://api.test/" } }
+        ]);
+
+        let plain = build_matcher("e_upstream", false, false).unwrap();
+        let matches = history_matches(&history, &plain, 10);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].history_id.as_deref(), Some("h2"));
+        assert_eq!(matches[0].collection_id.as_deref(), Some("c1"));
+        assert_eq!(matches[0].status, Some(502));
+        assert_eq!(matches[1].match_count, 2);
+        let snippet = &matches[1].snippets[1];
+        assert_eq!(snippet.before, "Fatal: e_upstream_7 after retry then ");
+        assert_eq!(snippet.matched, "E_UPSTREAM");
+        assert_eq!(snippet.after, "_8");
+        assert_eq!(history_matches(&history, &plain, 1).len(), 1);
+
+        let regex = build_matcher(r"E_UPSTREAM_\d{2}\b", true, true).unwrap();
+        let matches = history_matches(&history, &regex, 10);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].snippets[0].matched, "E_UPSTREAM_42");
+
+        assert!(build_matcher("(unclosed", true, false).is_err());
+        assert!(build_matcher("(unclosed", false, false).is_ok());
+        assert_eq!(tail("añb", 2), "ñb");
+        assert_eq!(head("añb", 2), "añ");
+    }
+}
@@ -0,0 +1,207 @@
+//! Per-endpoint response transforms: a `responseTransform` in the collection
+//! file trims a JSON response before it reaches the UI and history, by
+//! projecting JSONPaths, masking fields and sorting keys, in that order.
+//! Only the displayed data changes; "Save Response" still writes the body as
+//! received.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::api_request::ApiResponse;
+use super::login_flow::json_path;
+use super::secrets::SECRET_MASK;
+
+/// Field of an endpoint in the collection file holding its transform.
+const RESPONSE_TRANSFORM_FIELD: &str = "responseTransform";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTransform {
+    /// JSONPaths to keep. One path keeps just that value; several keep an
+    /// object keyed by path.
+    #[serde(default)]
+    pub project: Vec<String>,
+    /// Field names whose values are masked at any depth (case-insensitive)
+    #[serde(default)]
+    pub mask: Vec<String>,
+    #[serde(default)]
+    pub sort_keys: bool,
+}
+
+impl ResponseTransform {
+    fn is_empty(&self) -> bool {
+        self.project.is_empty() && self.mask.is_empty() && !self.sort_keys
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformReport {
+    /// Steps that ran: "project", "mask", "sortKeys"
+    pub applied: Vec<String>,
+    /// Size of the transformed data in bytes; `size` stays the size received
+    pub size: usize,
+    /// Projection paths that matched nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+/// The endpoint's transform; unset and empty both mean none.
+pub(crate) fn endpoint_response_transform(endpoint: &Value) -> Option<ResponseTransform> {
+    let transform: ResponseTransform =
+        serde_json::from_value(endpoint.get(RESPONSE_TRANSFORM_FIELD)?.clone()).ok()?;
+    (!transform.is_empty()).then_some(transform)
+}
+
+fn mask_fields(value: &mut Value, names: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if names.iter().any(|name| name.eq_ignore_ascii_case(key)) {
+                    *field = Value::String(SECRET_MASK.to_string());
+                } else {
+                    mask_fields(field, names);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| mask_fields(item, names)),
+        _ => {}
+    }
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (key, mut field) in entries {
+                sort_keys(&mut field);
+                map.insert(key, field);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+/// Runs the transform over a JSON body.
+fn transform_body(data: &Value, transform: &ResponseTransform) -> (Value, TransformReport) {
+    let mut applied = Vec::new();
+    let mut missing = Vec::new();
+    let paths: Vec<&str> = transform
+        .project
+        .iter()
+        .map(|path| path.trim())
+        .filter(|path| !path.is_empty())
+        .collect();
+
+    let mut value = match paths.as_slice() {
+        [] => data.clone(),
+        [path] => json_path(data, path).cloned().unwrap_or_else(|| {
+            missing.push(path.to_string());
+            Value::Null
+        }),
+        _ => {
+            let mut projected = Map::new();
+            for path in &paths {
+                match json_path(data, path) {
+                    Some(found) => {
+                        projected.insert(path.to_string(), found.clone());
+                    }
+                    None => missing.push(path.to_string()),
+                }
+            }
+            Value::Object(projected)
+        }
+    };
+    if !paths.is_empty() {
+        applied.push("project".to_string());
+    }
+
+    let names: Vec<String> = transform
+        .mask
+        .iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if !names.is_empty() {
+        mask_fields(&mut value, &names);
+        applied.push("mask".to_string());
+    }
+
+    if transform.sort_keys {
+        sort_keys(&mut value);
+        applied.push("sortKeys".to_string());
+    }
+
+    let size = serde_json::to_vec(&value).map(|v| v.len()).unwrap_or(0);
+    (
+        value,
+        TransformReport {
+            applied,
+            size,
+            missing,
+        },
+    )
+}
+
+/// Transforms the response's data in place. Text bodies are left alone.
+pub(crate) fn apply_response_transform(response: &mut ApiResponse, transform: &ResponseTransform) {
+    let Some(data) = response.data.as_ref().filter(|data| !data.is_string()) else {
+        return;
+    };
+    let (data, report) = transform_body(data, transform);
+    response.data = Some(data);
+    response.response_transform = Some(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn projects_masks_and_sorts_json_bodies() {
+        let body = json!({
+            "meta": { "page": 1 },
+            "data": {
+                "users": [
+                    { "name": "ann", "Password": "hunter2", "id": 1 },
+                    { "name": "bob", "password": "swordfish", "id": 2 }
+                ]
+            }
+        });
+
+        let endpoint = json!({
+            "id": "e1",
+            "responseTransform": {
+                "project": ["$.data.users"],
+                "mask": ["password"],
+                "sortKeys": true
+            }
+        });
+        let transform = endpoint_response_transform(&endpoint).unwrap();
+        let (value, report) = transform_body(&body, &transform);
+        assert_eq!(
+            value,
+            json!([
+                { "id": 1, "name": "ann", "Password": SECRET_MASK },
+                { "id": 2, "name": "bob", "password": SECRET_MASK }
+            ])
+        );
+        let keys: Vec<&String> = value[0].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["Password", "id", "name"]);
+        assert_eq!(report.applied, ["project", "mask", "sortKeys"]);
+        assert_eq!(report.size, serde_json::to_vec(&value).unwrap().len());
+
+        let several = ResponseTransform {
+            project: vec!["$.meta.page".to_string(), "$.nope".to_string()],
+            ..Default::default()
+        };
+        let (value, report) = transform_body(&body, &several);
+        assert_eq!(value, json!({ "$.meta.page": 1 }));
+        assert_eq!(report.missing, ["$.nope"]);
+
+        assert!(endpoint_response_transform(&json!({ "responseTransform": {} })).is_none());
+    }
+}
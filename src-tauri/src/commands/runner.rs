@@ -0,0 +1,1906 @@
+//! Backend collection runner.
+//!
+//! Executes every HTTP request of a collection (or of one folder) in order:
+//! variables are resolved in Rust, pre-request scripts run in the Boa
+//! sandbox, the request goes through the same send path as the UI
+//! ([`execute_api_request`]) and test scripts run against the response.
+//! Environment changes made by scripts carry over to later requests and are
+//! returned so the frontend can persist them.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::watch;
+
+use super::api_request::{
+    execute_api_request, safety_policy_reason, ApiResponse, AuthConfig, AwsAuthConfig, BodyType,
+    ClientCertConfig, RequestOptions,
+};
+use super::collections::{Collection, EndpointData};
+use super::ip_family::{endpoint_ip_family, IpFamily};
+use super::otlp_export::export_request_span;
+use super::proxy::ProxyState;
+use super::scripts::{LogEntry, ScriptData, ScriptExecutionData, ScriptResult, TestResult};
+use super::time_budget::{endpoint_time_budget, BudgetCheck};
+use super::tls::endpoint_ignores_tls_errors;
+use super::trace_headers::{new_trace_id, TraceIds};
+use super::variables::{endpoint_variable_overrides, VariableResolver};
+use super::workspaces::store_file;
+
+mod batch;
+mod health;
+mod history;
+mod report;
+mod snapshot;
+mod tags;
+
+use batch::FolderSendResult;
+use health::{HealthCheckOptions, HealthCheckResult};
+use history::{RunDiff, RunHistoryEntry};
+use snapshot::SnapshotOptions;
+use tags::TagExpr;
+
+const SETTINGS_KEY: &str = "settings";
+
+/// Default request timeout, matching the UI when no setting is stored.
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound for [`RunCollectionOptions::retries`].
+const MAX_RETRIES: u32 = 10;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunCollectionOptions {
+    pub collection_id: String,
+    /// Only run the endpoints of this folder (all endpoints when unset)
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    /// Variables layered over the collection and active-environment values,
+    /// e.g. secrets already resolved by the frontend
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Stop the run after the first request that does not pass
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Extra attempts for a request that does not pass (capped at [`MAX_RETRIES`])
+    #[serde(default)]
+    pub retries: u32,
+    /// Pause between requests (and before each retry) in ms
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Id for the run, so the caller can cancel it while it is in progress
+    /// (generated when unset)
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// Only run these endpoints, in collection order (all endpoints when empty)
+    #[serde(default)]
+    pub endpoint_ids: Vec<String>,
+    /// Tag expression selecting endpoints, e.g. `smoke && !slow`
+    #[serde(default)]
+    pub tags: Option<String>,
+    /// Compare responses against stored snapshots (recording missing ones)
+    #[serde(default)]
+    pub snapshot: Option<SnapshotOptions>,
+    /// Send requests the safety policy guards instead of holding them back
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestRunResult {
+    pub endpoint_id: String,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    /// "passed" | "failed" (assertion or script failure) | "error" (request not completed)
+    /// | "held" (not sent: the safety policy requires confirmation)
+    pub status: String,
+    pub status_code: Option<u16>,
+    pub response_time: u64,
+    pub response_size: Option<usize>,
+    pub test_results: Vec<TestResult>,
+    pub errors: Vec<String>,
+    pub logs: Vec<LogEntry>,
+    /// Number of times the request was sent (more than 1 after retries)
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// Response time against the endpoint's time budget. Reported next to
+    /// `status`: a slow response still passes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_budget: Option<BudgetCheck>,
+    /// The `X-Request-Id` and `traceparent` the request was sent with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_ids: Option<TraceIds>,
+}
+
+fn default_attempts() -> u32 {
+    1
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionRunResult {
+    pub run_id: String,
+    pub collection_id: String,
+    pub collection_name: String,
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    /// Unix timestamp (ms) when the run started
+    pub started_at: i64,
+    /// Wall-clock duration of the whole run in ms
+    pub duration: u64,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errored: usize,
+    /// Requests not executed because the run stopped on a failure or was cancelled
+    #[serde(default)]
+    pub skipped: usize,
+    /// Requests held back because the safety policy requires confirmation
+    #[serde(default)]
+    pub held: usize,
+    /// Completed requests slower than their endpoint's time budget, counted
+    /// apart from `failed`
+    #[serde(default)]
+    pub over_budget: usize,
+    #[serde(default)]
+    pub cancelled: bool,
+    pub results: Vec<RequestRunResult>,
+    /// Environment changes made by scripts during the run (`None` = unset)
+    pub environment_changes: HashMap<String, Option<String>>,
+}
+
+/// An endpoint scheduled for execution, with the folder it lives in (used for
+/// auth inheritance).
+#[derive(Debug, Clone)]
+pub(crate) struct RunItem {
+    pub endpoint: Value,
+    pub folder: Option<Value>,
+}
+
+impl RunItem {
+    pub fn id(&self) -> String {
+        str_field(&self.endpoint, "id").to_string()
+    }
+
+    pub fn name(&self) -> String {
+        let name = str_field(&self.endpoint, "name");
+        if name.is_empty() {
+            str_field(&self.endpoint, "path").to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    pub fn method(&self) -> String {
+        let method = str_field(&self.endpoint, "method");
+        if method.is_empty() {
+            "GET".to_string()
+        } else {
+            method.to_uppercase()
+        }
+    }
+
+    pub fn tags(&self) -> Vec<String> {
+        self.endpoint
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Request settings shared by every request of a run.
+#[derive(Debug, Clone)]
+pub(crate) struct RunnerSettings {
+    pub http_version: Option<String>,
+    pub timeout: Option<u64>,
+    pub verify_ssl: bool,
+    pub follow_redirects: bool,
+    pub ip_family: Option<IpFamily>,
+    /// Extra CA bundle trusted for hosts without one of their own
+    pub ca_bundle_path: Option<String>,
+    /// Set when trace headers are on: the trace every request of the run
+    /// joins
+    pub trace_id: Option<String>,
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+fn value_as_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Key/value rows (`[{key, value, enabled?}]`) as persisted by the editor.
+fn enabled_rows(rows: &[Value]) -> Vec<(String, String)> {
+    rows.iter()
+        .filter(|row| row.get("enabled").and_then(Value::as_bool) != Some(false))
+        .filter_map(|row| {
+            let key = str_field(row, "key");
+            if key.is_empty() {
+                return None;
+            }
+            Some((
+                key.to_string(),
+                row.get("value").map(value_as_string).unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// HTTP and GraphQL endpoints are runnable; gRPC and WebSocket are not.
+fn is_http_endpoint(endpoint: &Value) -> bool {
+    matches!(str_field(endpoint, "protocol"), "" | "http" | "graphql")
+}
+
+/// Select the endpoints to run: root endpoints first, then each folder in
+/// order. With `folder_id` only that folder is run. Streaming endpoints (gRPC,
+/// WebSocket, ...) are skipped.
+pub(crate) fn collect_run_items(
+    collection: &Collection,
+    folder_id: Option<&str>,
+) -> Result<Vec<RunItem>, String> {
+    fn push_endpoints(items: &mut Vec<RunItem>, endpoints: &[Value], folder: Option<&Value>) {
+        for endpoint in endpoints.iter().filter(|e| is_http_endpoint(e)) {
+            items.push(RunItem {
+                endpoint: endpoint.clone(),
+                folder: folder.cloned(),
+            });
+        }
+    }
+
+    let mut items = Vec::new();
+    match folder_id {
+        Some(folder_id) => {
+            let folder = collection
+                .folders
+                .iter()
+                .find(|f| str_field(f, "id") == folder_id)
+                .ok_or_else(|| format!("Folder {} not found", folder_id))?;
+            let endpoints = folder
+                .get("endpoints")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            push_endpoints(&mut items, &endpoints, Some(folder));
+        }
+        None => {
+            push_endpoints(&mut items, &collection.endpoints, None);
+            for folder in &collection.folders {
+                let endpoints = folder
+                    .get("endpoints")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                push_endpoints(&mut items, &endpoints, Some(folder));
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Keep the items whose endpoint is among `endpoint_ids` (all items when it
+/// is empty). Fails when an id matches no endpoint, so a stale selection is
+/// not run as a silently shorter one.
+pub(crate) fn select_endpoints(
+    items: Vec<RunItem>,
+    endpoint_ids: &[String],
+) -> Result<Vec<RunItem>, String> {
+    if endpoint_ids.is_empty() {
+        return Ok(items);
+    }
+    if let Some(missing) = endpoint_ids
+        .iter()
+        .find(|id| !items.iter().any(|item| item.id() == **id))
+    {
+        return Err(format!("Endpoint {} not found in the collection", missing));
+    }
+    Ok(items
+        .into_iter()
+        .filter(|item| endpoint_ids.contains(&item.id()))
+        .collect())
+}
+
+/// Keep the items whose endpoint tags satisfy `expression` (all items when
+/// the expression is unset or blank).
+pub(crate) fn select_by_tags(
+    items: Vec<RunItem>,
+    expression: Option<&str>,
+) -> Result<Vec<RunItem>, String> {
+    let Some(expression) = expression.filter(|e| !e.trim().is_empty()) else {
+        return Ok(items);
+    };
+    let expr = TagExpr::parse(expression)?;
+    Ok(items
+        .into_iter()
+        .filter(|item| expr.matches(&item.tags()))
+        .collect())
+}
+
+/// Request settings from the `settings` store key, mirroring the UI defaults.
+pub(crate) fn load_runner_settings(app: &AppHandle) -> RunnerSettings {
+    let store = app.store(store_file(app)).ok();
+    saved_runner_settings(|key| store.as_ref().and_then(|store| store.get(key)))
+}
+
+/// [`load_runner_settings`] from the values `saved` returns by store key.
+pub(crate) fn saved_runner_settings(saved: impl Fn(&str) -> Option<Value>) -> RunnerSettings {
+    let settings = saved(SETTINGS_KEY).unwrap_or(Value::Null);
+
+    let timeout = match settings
+        .get("requestTimeout")
+        .or_else(|| settings.get("timeout"))
+    {
+        Some(Value::Number(n)) if n.as_u64() == Some(0) => None,
+        Some(Value::Number(n)) => n.as_u64().or(Some(DEFAULT_TIMEOUT_MS)),
+        _ => Some(DEFAULT_TIMEOUT_MS),
+    };
+
+    RunnerSettings {
+        http_version: settings
+            .get("httpVersion")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        timeout,
+        verify_ssl: settings.get("verifySsl").and_then(Value::as_bool) != Some(false),
+        follow_redirects: settings.get("followRedirects").and_then(Value::as_bool) != Some(false),
+        ip_family: settings
+            .get("ipFamily")
+            .cloned()
+            .and_then(|family| serde_json::from_value(family).ok()),
+        ca_bundle_path: settings
+            .get("caBundlePath")
+            .and_then(Value::as_str)
+            .filter(|path| !path.is_empty())
+            .map(str::to_string),
+        trace_id: (settings.get("traceHeaders").and_then(Value::as_bool) == Some(true))
+            .then(new_trace_id),
+    }
+}
+
+/// Effective auth for an endpoint: its own persisted config, then the
+/// imported `security`, then (for `inherit`) the folder and collection.
+fn effective_auth(collection: &Collection, item: &RunItem, data: &EndpointData) -> Option<Value> {
+    let own = data
+        .auth_config
+        .clone()
+        .or_else(|| item.endpoint.get("security").cloned())
+        .filter(|a| a.is_object());
+    match own {
+        Some(auth) if str_field(&auth, "type") != "inherit" => Some(auth),
+        _ => inherited_auth(collection, item.folder.as_ref()),
+    }
+}
+
+/// The auth an `inherit` endpoint gets: its folder's when the folder sets
+/// one (an explicit `none` opts out), otherwise the collection's.
+fn inherited_auth(collection: &Collection, folder: Option<&Value>) -> Option<Value> {
+    let folder_auth = folder
+        .and_then(|f| f.get("authConfig"))
+        .filter(|a| {
+            let t = str_field(a, "type");
+            !t.is_empty() && t != "inherit"
+        })
+        .cloned();
+    folder_auth.or_else(|| collection.auth_config.clone())
+}
+
+/// Apply the folder or collection auth to a request sent from the UI whose
+/// own auth is `inherit`, resolving it the way a run does: secrets and
+/// managed tokens filled in, variables of the collection and active
+/// environment substituted with the request's overrides on top.
+pub(crate) async fn apply_inherited_auth(
+    app: &AppHandle,
+    options: &mut RequestOptions,
+) -> Result<(), String> {
+    let Some(collection_id) = options.collection_id.clone() else {
+        return Ok(());
+    };
+    let mut collection = super::collections::collection_get(app.clone(), collection_id).await?;
+    hydrate_inherited_auth(app, &mut collection).await?;
+    let folder = match options.endpoint_id.as_deref() {
+        Some(endpoint_id) => collect_run_items(&collection, None)?
+            .into_iter()
+            .filter(|item| item.id() == endpoint_id)
+            .find_map(|item| item.folder),
+        None => None,
+    };
+    let auth = inherited_auth(&collection, folder.as_ref());
+
+    let variables = initial_run_variables(app, &collection.id, &options.variable_overrides).await;
+    let auth_data = generate_auth_data(auth.as_ref(), &mut VariableResolver::new(variables));
+    let headers = options.headers.get_or_insert_with(HashMap::new);
+    for (k, v) in auth_data.headers {
+        headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&k));
+        headers.insert(k, v);
+    }
+    append_query(&mut options.url, &auth_data.query_params);
+    if auth_data.challenge.is_some() {
+        options.auth = auth_data.challenge;
+    }
+    if auth_data.aws.is_some() {
+        options.aws_auth = auth_data.aws;
+    }
+    Ok(())
+}
+
+/// Headers, query parameters and reqwest-level auth derived from an auth
+/// config. Port of the frontend runner's `_generateAuthData`.
+#[derive(Debug, Default)]
+pub(crate) struct AuthData {
+    pub headers: Vec<(String, String)>,
+    pub query_params: Vec<(String, String)>,
+    /// Credentials answering a 401 challenge (digest or negotiate)
+    pub challenge: Option<AuthConfig>,
+    pub aws: Option<AwsAuthConfig>,
+}
+
+pub(crate) fn generate_auth_data(
+    auth: Option<&Value>,
+    resolver: &mut VariableResolver,
+) -> AuthData {
+    let mut data = AuthData::default();
+    let Some(auth) = auth else {
+        return data;
+    };
+    let config = auth.get("config").cloned().unwrap_or(Value::Null);
+    let mut field = |key: &str| resolver.resolve(str_field(&config, key));
+
+    match str_field(auth, "type") {
+        "bearer" => {
+            let token = field("token");
+            if !token.is_empty() {
+                data.headers
+                    .push(("Authorization".to_string(), format!("Bearer {}", token)));
+            }
+        }
+        "basic" => {
+            let (username, password) = (field("username"), field("password"));
+            if !username.is_empty() || !password.is_empty() {
+                let credentials = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", username, password));
+                data.headers.push((
+                    "Authorization".to_string(),
+                    format!("Basic {}", credentials),
+                ));
+            }
+        }
+        "api-key" => {
+            let (name, value) = (field("keyName"), field("keyValue"));
+            if !name.is_empty() && !value.is_empty() {
+                match str_field(&config, "location") {
+                    "header" => data.headers.push((name, value)),
+                    "query" => data.query_params.push((name, value)),
+                    _ => {}
+                }
+            }
+        }
+        "oauth2" => {
+            let token = field("token");
+            if !token.is_empty() {
+                let prefix = field("headerPrefix");
+                let prefix = if prefix.is_empty() {
+                    "Bearer".to_string()
+                } else {
+                    prefix
+                };
+                data.headers
+                    .push(("Authorization".to_string(), format!("{} {}", prefix, token)));
+            }
+        }
+        "command" => {
+            // The token was filled in from the command's output beforehand;
+            // an explicitly empty prefix sends the bare token
+            let token = field("token");
+            if !token.is_empty() {
+                let name = match field("headerName") {
+                    name if name.is_empty() => "Authorization".to_string(),
+                    name => name,
+                };
+                let value = match config.get("headerPrefix") {
+                    None => format!("Bearer {}", token),
+                    Some(_) => match field("headerPrefix") {
+                        prefix if prefix.is_empty() => token,
+                        prefix => format!("{} {}", prefix, token),
+                    },
+                };
+                data.headers.push((name, value));
+            }
+        }
+        "digest" => {
+            let (username, password) = (field("username"), field("password"));
+            if !username.is_empty() || !password.is_empty() {
+                data.challenge = Some(AuthConfig {
+                    username,
+                    password,
+                    auth_type: Some("digest".to_string()),
+                });
+            }
+        }
+        "negotiate" => {
+            data.challenge = Some(AuthConfig {
+                username: String::new(),
+                password: String::new(),
+                auth_type: Some("negotiate".to_string()),
+            });
+        }
+        "aws-v4" => {
+            let (access_key_id, secret_access_key) =
+                (field("accessKeyId"), field("secretAccessKey"));
+            if !access_key_id.is_empty() && !secret_access_key.is_empty() {
+                let region = field("region");
+                let session_token = field("sessionToken");
+                data.aws = Some(AwsAuthConfig {
+                    access_key_id,
+                    secret_access_key,
+                    region: if region.is_empty() {
+                        "us-east-1".to_string()
+                    } else {
+                        region
+                    },
+                    service: field("service"),
+                    session_token: (!session_token.is_empty()).then_some(session_token),
+                });
+            }
+        }
+        _ => {}
+    }
+    data
+}
+
+/// Percent-encode like JavaScript's `encodeURIComponent`, so runner URLs
+/// match the ones the UI sends.
+fn encode_component(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn append_query(url: &mut String, pairs: &[(String, String)]) {
+    let pairs: Vec<&(String, String)> = pairs
+        .iter()
+        .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+        .collect();
+    if pairs.is_empty() {
+        return;
+    }
+    let query = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", encode_component(k), encode_component(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str(&query);
+}
+
+/// Replace OpenAPI-style `{name}` placeholders with `{{name}}` so the
+/// resolver fills them from the path-parameter variables.
+fn brace_path_params(path: &str, names: &[String]) -> String {
+    let mut result = path.to_string();
+    for name in names {
+        let single = format!("{{{}}}", name);
+        let double = format!("{{{{{}}}}}", name);
+        if !result.contains(&double) {
+            result = result.replace(&single, &double);
+        }
+    }
+    result
+}
+
+/// Build the request for one endpoint. Mirrors the frontend runner's
+/// `_buildRequestConfig`: persisted editor state wins over imported examples.
+pub(crate) fn build_request_options(
+    collection: &Collection,
+    item: &RunItem,
+    data: &EndpointData,
+    resolver: &mut VariableResolver,
+    settings: &RunnerSettings,
+) -> RequestOptions {
+    let endpoint = &item.endpoint;
+    let method = item.method();
+
+    match resolver.variables().get("baseUrl") {
+        Some(base_url) if !base_url.is_empty() => {}
+        _ => resolver.set("baseUrl", collection.base_url.clone()),
+    }
+
+    // Path parameters become variables for this request.
+    let mut path_param_names: Vec<String> = Vec::new();
+    let persisted_path = enabled_rows(&data.path_params);
+    if !persisted_path.is_empty() {
+        for (key, value) in persisted_path {
+            path_param_names.push(key.clone());
+            if !value.is_empty() {
+                resolver.set(key, value);
+            }
+        }
+    } else if let Some(params) = endpoint
+        .get("parameters")
+        .and_then(|p| p.get("path"))
+        .and_then(Value::as_object)
+    {
+        for (key, param) in params {
+            path_param_names.push(key.clone());
+            let example = param
+                .get("example")
+                .map(value_as_string)
+                .unwrap_or_default();
+            if !example.is_empty() && !resolver.variables().contains_key(key) {
+                resolver.set(key.clone(), example);
+            }
+        }
+    }
+
+    let explicit_url = data
+        .url
+        .as_deref()
+        .or_else(|| endpoint.get("url").and_then(Value::as_str))
+        .filter(|u| !u.trim().is_empty());
+    let template = match explicit_url {
+        Some(url) => url.to_string(),
+        None => {
+            let path = str_field(endpoint, "path");
+            if path.contains("{{baseUrl}}") {
+                path.to_string()
+            } else {
+                format!("{{{{baseUrl}}}}{}", path)
+            }
+        }
+    };
+    let mut url = resolver.resolve(&brace_path_params(&template, &path_param_names));
+
+    // Query parameters
+    let persisted_query = enabled_rows(&data.query_params);
+    let query: Vec<(String, String)> = if !persisted_query.is_empty() {
+        persisted_query
+            .into_iter()
+            .map(|(k, v)| (resolver.resolve(&k), resolver.resolve(&v)))
+            .collect()
+    } else {
+        endpoint
+            .get("parameters")
+            .and_then(|p| p.get("query"))
+            .and_then(Value::as_object)
+            .map(|params| {
+                params
+                    .iter()
+                    .filter_map(|(k, p)| {
+                        let example = p.get("example").map(value_as_string)?;
+                        (!example.is_empty()).then(|| (k.clone(), resolver.resolve(&example)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    append_query(&mut url, &query);
+
+    if !url.is_empty() && !url.contains("://") {
+        url = format!("https://{}", url);
+    }
+
+    // Headers: collection defaults < imported endpoint headers < persisted rows
+    let mut header_pairs: Vec<(String, String)> = Vec::new();
+    for source in [
+        &collection.default_headers,
+        endpoint.get("headers").unwrap_or(&Value::Null),
+    ] {
+        if let Some(map) = source.as_object() {
+            for (k, v) in map {
+                header_pairs.push((k.clone(), value_as_string(v)));
+            }
+        }
+    }
+    header_pairs.extend(enabled_rows(&data.headers));
+    let mut headers: HashMap<String, String> = HashMap::new();
+    for (k, v) in header_pairs {
+        let key = resolver.resolve(&k);
+        // Later sources win regardless of header-name case.
+        headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&key));
+        headers.insert(key, resolver.resolve(&v));
+    }
+
+    // Body
+    let (body, body_type) = build_body(endpoint, data, &method, resolver);
+
+    // Auth
+    let auth = effective_auth(collection, item, data);
+    let auth_data = generate_auth_data(auth.as_ref(), resolver);
+    for (k, v) in auth_data.headers {
+        headers.retain(|existing, _| !existing.eq_ignore_ascii_case(&k));
+        headers.insert(k, v);
+    }
+    append_query(&mut url, &auth_data.query_params);
+
+    RequestOptions {
+        method,
+        url,
+        headers: Some(headers),
+        body,
+        auth: auth_data.challenge,
+        http_version: settings.http_version.clone(),
+        timeout: settings.timeout,
+        verify_ssl: Some(settings.verify_ssl && !endpoint_ignores_tls_errors(&item.endpoint)),
+        follow_redirects: Some(settings.follow_redirects),
+        ip_family: endpoint_ip_family(&item.endpoint).or(settings.ip_family),
+        body_type,
+        aws_auth: auth_data.aws,
+        client_cert: None,
+        ca_bundle_path: settings.ca_bundle_path.clone(),
+        collection_id: Some(collection.id.clone()),
+        proxy: collection.proxy.clone(),
+        response_key: None,
+        confirmed: false,
+        endpoint_id: Some(item.id()),
+        inherit_auth: false,
+        variable_overrides: HashMap::new(),
+        trace_headers: settings.trace_id.is_some(),
+        trace_id: settings.trace_id.clone(),
+        reuse_idempotency_key: false,
+    }
+}
+
+fn build_body(
+    endpoint: &Value,
+    data: &EndpointData,
+    method: &str,
+    resolver: &mut VariableResolver,
+) -> (Option<Value>, Option<BodyType>) {
+    if let Some(graphql) = data
+        .graphql_data
+        .as_ref()
+        .filter(|g| str_field(g, "mode") == "graphql")
+    {
+        let variables = resolver.resolve(str_field(graphql, "variables"));
+        let variables = serde_json::from_str::<Value>(&variables)
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        let mut body = Map::new();
+        body.insert(
+            "query".into(),
+            Value::String(resolver.resolve(str_field(graphql, "query"))),
+        );
+        body.insert("variables".into(), variables);
+        return (Some(Value::Object(body)), Some(BodyType::Json));
+    }
+
+    let form = data.form_body_data.as_ref();
+    let mode = form.map(|f| str_field(f, "mode")).unwrap_or("");
+
+    match mode {
+        "formdata" | "urlencoded" => {
+            let fields = form
+                .and_then(|f| f.get("fields"))
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let rows: Vec<Value> = fields
+                .iter()
+                .filter(|row| row.get("enabled").and_then(Value::as_bool) != Some(false))
+                .map(|row| {
+                    let row_type = match str_field(row, "type") {
+                        "" => "text",
+                        other => other,
+                    };
+                    let mut out = Map::new();
+                    out.insert(
+                        "key".into(),
+                        Value::String(resolver.resolve(str_field(row, "key"))),
+                    );
+                    let value = if row_type == "file" {
+                        String::new()
+                    } else {
+                        resolver.resolve(str_field(row, "value"))
+                    };
+                    out.insert("value".into(), Value::String(value));
+                    out.insert("type".into(), Value::String(row_type.to_string()));
+                    let file_path = str_field(row, "filePath");
+                    if !file_path.is_empty() {
+                        out.insert(
+                            "filePath".into(),
+                            Value::String(resolver.resolve(file_path)),
+                        );
+                    }
+                    let content_type = str_field(row, "contentType");
+                    if !content_type.is_empty() {
+                        out.insert(
+                            "contentType".into(),
+                            Value::String(content_type.to_string()),
+                        );
+                    }
+                    Value::Object(out)
+                })
+                .collect();
+            let body = (!rows.is_empty()).then_some(Value::Array(rows));
+            let body_type = if mode == "formdata" {
+                BodyType::Formdata
+            } else {
+                BodyType::Urlencoded
+            };
+            (body, Some(body_type))
+        }
+        "binary" => {
+            let file_path = form.map(|f| str_field(f, "filePath")).unwrap_or("");
+            if file_path.is_empty() {
+                return (None, None);
+            }
+            let mut out = Map::new();
+            out.insert(
+                "filePath".into(),
+                Value::String(resolver.resolve(file_path)),
+            );
+            let content_type = form.map(|f| str_field(f, "contentType")).unwrap_or("");
+            if !content_type.is_empty() {
+                out.insert(
+                    "contentType".into(),
+                    Value::String(content_type.to_string()),
+                );
+            }
+            (Some(Value::Object(out)), Some(BodyType::Binary))
+        }
+        _ => {
+            let mut content = data.modified_body.clone().unwrap_or_default();
+            if content.is_empty() && matches!(method, "POST" | "PUT" | "PATCH") {
+                if let Some(request_body) = endpoint.get("requestBody") {
+                    content = match request_body.get("example") {
+                        Some(Value::String(s)) if s != "null" => s.clone(),
+                        Some(v) if !v.is_null() && !v.is_string() => v.to_string(),
+                        _ => request_body
+                            .get("schema")
+                            .and_then(|s| s.get("example"))
+                            .map(|e| serde_json::to_string_pretty(e).unwrap_or_default())
+                            .unwrap_or_default(),
+                    };
+                }
+            }
+            if content.trim().is_empty() {
+                return (None, None);
+            }
+            let processed = resolver.resolve(&content);
+            match serde_json::from_str::<Value>(&processed) {
+                Ok(json) => (Some(json), Some(BodyType::Json)),
+                Err(_) => (Some(Value::String(processed)), Some(BodyType::Text)),
+            }
+        }
+    }
+}
+
+/// The `request` object handed to scripts.
+fn script_request(options: &RequestOptions) -> Value {
+    serde_json::json!({
+        "url": options.url,
+        "method": options.method,
+        "headers": options.headers.clone().unwrap_or_default(),
+        "body": options.body,
+        "queryParams": {},
+        "pathParams": {},
+    })
+}
+
+/// Apply the `request` object returned by a pre-request script.
+fn apply_script_request(options: &mut RequestOptions, modified: &Value) {
+    if let Some(url) = modified.get("url").and_then(Value::as_str) {
+        options.url = url.to_string();
+    }
+    if let Some(method) = modified.get("method").and_then(Value::as_str) {
+        if !method.is_empty() {
+            options.method = method.to_uppercase();
+        }
+    }
+    if let Some(headers) = modified.get("headers").and_then(Value::as_object) {
+        options.headers = Some(
+            headers
+                .iter()
+                .map(|(k, v)| (k.clone(), value_as_string(v)))
+                .collect(),
+        );
+    }
+    if let Some(body) = modified.get("body") {
+        if body != &options.body.clone().unwrap_or(Value::Null) {
+            options.body = (!body.is_null()).then(|| body.clone());
+            if body.is_string() && options.body_type == Some(BodyType::Json) {
+                options.body_type = Some(BodyType::Text);
+            }
+        }
+    }
+}
+
+/// The `response` object handed to test scripts; same shape as the UI sends.
+fn script_response(response: &ApiResponse) -> Value {
+    serde_json::json!({
+        "status": response.status,
+        "statusText": response.status_text.clone().unwrap_or_default(),
+        "headers": response.headers,
+        "body": response.data,
+        "timings": response.timings,
+        "cookies": response.set_cookies,
+    })
+}
+
+/// Fold a script result into the request result and the run variables.
+fn absorb_script_result(
+    result: ScriptResult,
+    request_result: &mut RequestRunResult,
+    variables: &mut HashMap<String, String>,
+    environment_changes: &mut HashMap<String, Option<String>>,
+) {
+    for (key, value) in &result.modified_environment {
+        match value {
+            Some(v) => variables.insert(key.clone(), v.clone()),
+            None => variables.remove(key),
+        };
+    }
+    environment_changes.extend(result.modified_environment);
+    request_result.logs.extend(result.logs);
+    request_result.errors.extend(result.errors);
+    request_result.test_results.extend(result.test_results);
+}
+
+/// Load an endpoint's persisted data and scripts; missing data is not an error.
+async fn load_endpoint_state(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> (EndpointData, ScriptData) {
+    let data = super::collections::collection_get_endpoint_data(
+        app.clone(),
+        collection_id.to_string(),
+        endpoint_id.to_string(),
+    )
+    .await
+    .unwrap_or_default();
+    let scripts = super::scripts::script_get(
+        app.clone(),
+        collection_id.to_string(),
+        endpoint_id.to_string(),
+    )
+    .await
+    .unwrap_or(ScriptData {
+        pre_request_script: String::new(),
+        test_script: String::new(),
+    });
+    (data, scripts)
+}
+
+/// Restore the secret fields of an endpoint's own auth config and fill in its
+/// managed OAuth 2.0 or command token.
+async fn hydrate_endpoint_auth(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+    data: &mut EndpointData,
+) -> Result<(), String> {
+    if let Some(auth) = data.auth_config.as_mut() {
+        let scope = format!("auth:{}:{}", collection_id, endpoint_id);
+        super::secrets::hydrate_auth_config(app, auth, &scope).await;
+        super::token_manager::apply_managed_token(app, auth).await;
+        super::command_auth::apply_command_token(app, collection_id, auth).await?;
+    }
+    Ok(())
+}
+
+/// The client certificate configured for the host (and port) of `url`.
+fn client_cert_for_url(app: &AppHandle, url: &str) -> Option<ClientCertConfig> {
+    super::certificates::client_cert_for_host(app, &super::certificates::url_host_port(url)?)
+}
+
+/// Everything a run shares across its requests.
+pub(crate) struct RunContext<'a> {
+    pub app: &'a AppHandle,
+    pub proxy_state: &'a ProxyState,
+    pub collection: &'a Collection,
+    pub settings: &'a RunnerSettings,
+    /// Flips to `true` when the run is cancelled
+    pub cancel: watch::Receiver<bool>,
+    pub snapshot: Option<&'a SnapshotOptions>,
+    /// Send requests the safety policy guards
+    pub confirmed: bool,
+}
+
+impl RunContext<'_> {
+    fn is_cancelled(&self) -> bool {
+        *self.cancel.borrow()
+    }
+
+    /// Resolves once the run is cancelled; never resolves otherwise.
+    async fn cancelled(&self) {
+        let mut cancel = self.cancel.clone();
+        if cancel.wait_for(|c| *c).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Sleep for `delay` unless the run is cancelled first.
+    async fn pause(&self, delay: Duration) {
+        if delay.is_zero() {
+            return;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = self.cancelled() => {}
+        }
+    }
+}
+
+/// Execute one endpoint: build, pre-request scripts, send, test scripts.
+/// `variables` carries run-scoped values and is updated by script changes.
+pub(crate) async fn run_item(
+    ctx: &RunContext<'_>,
+    item: &RunItem,
+    variables: &mut HashMap<String, String>,
+    environment_changes: &mut HashMap<String, Option<String>>,
+) -> RequestRunResult {
+    let RunContext {
+        app,
+        collection,
+        settings,
+        ..
+    } = *ctx;
+    let endpoint_id = item.id();
+    let (mut data, scripts) = load_endpoint_state(app, &collection.id, &endpoint_id).await;
+    let auth_error = hydrate_endpoint_auth(app, &collection.id, &endpoint_id, &mut data)
+        .await
+        .err();
+    let global = super::scripts::read_global_scripts(app);
+
+    let mut resolver = request_resolver(variables.clone(), item);
+    let mut options = build_request_options(collection, item, &data, &mut resolver, settings);
+
+    let mut result = RequestRunResult {
+        endpoint_id,
+        name: item.name(),
+        method: options.method.clone(),
+        url: options.url.clone(),
+        status: "passed".to_string(),
+        status_code: None,
+        response_time: 0,
+        response_size: None,
+        test_results: Vec::new(),
+        errors: Vec::new(),
+        logs: Vec::new(),
+        attempts: 1,
+        time_budget: None,
+        trace_ids: None,
+    };
+    if let Some(e) = auth_error {
+        result.status = "error".to_string();
+        result.errors.push(e);
+        return result;
+    }
+
+    // Pre-request scripts (global first, then the endpoint's own)
+    let pre_scripts = vec![global.pre_request_script, scripts.pre_request_script];
+    if pre_scripts.iter().any(|s| !s.trim().is_empty()) {
+        let script_data = ScriptExecutionData {
+            script: String::new(),
+            request: script_request(&options),
+            response: None,
+            environment: variables.clone(),
+        };
+        match super::scripts::execute_script_chain(
+            pre_scripts,
+            script_data,
+            true,
+            ctx.proxy_state.offline.clone(),
+        )
+        .await
+        {
+            Ok(script_result) => {
+                if let Some(modified) = &script_result.modified_request {
+                    apply_script_request(&mut options, modified);
+                }
+                absorb_script_result(script_result, &mut result, variables, environment_changes);
+            }
+            Err(e) => result.errors.push(e),
+        }
+    }
+
+    options.client_cert = client_cert_for_url(app, &options.url);
+    options.confirmed = ctx.confirmed;
+    result.method = options.method.clone();
+    result.url = options.url.clone();
+    match safety_policy_reason(app, &options) {
+        Ok(None) => {}
+        Ok(Some(reason)) => {
+            result.status = "held".to_string();
+            result.errors.push(reason);
+            return result;
+        }
+        Err(e) => {
+            result.status = "error".to_string();
+            result.errors.push(e);
+            return result;
+        }
+    }
+    let sent_request = script_request(&options);
+    let options = match super::secrets::resolve_secret_references(app, options).await {
+        Ok(options) => options,
+        Err(e) => {
+            result.status = "error".to_string();
+            result.errors.push(e);
+            return result;
+        }
+    };
+
+    let started = Instant::now();
+    let (method, url) = (options.method.clone(), options.url.clone());
+    let response = match execute_api_request(ctx.proxy_state, options, ctx.cancelled()).await {
+        Ok(response) => response,
+        Err(e) => {
+            result.response_time = started.elapsed().as_millis() as u64;
+            result.status = "error".to_string();
+            result.errors.push(e);
+            return result;
+        }
+    };
+    result.response_time = response.timings.total;
+    result.status_code = response.status;
+    result.response_size = response.size;
+    result.trace_ids = response.trace_ids.clone();
+    export_request_span(app, ctx.proxy_state, &method, &url, &response);
+
+    if response.status.is_none() {
+        result.status = "error".to_string();
+        result.errors.push(
+            response
+                .message
+                .unwrap_or_else(|| "Request failed".to_string()),
+        );
+        return result;
+    }
+    result.time_budget = endpoint_time_budget(&item.endpoint)
+        .map(|budget| BudgetCheck::new(budget, result.response_time));
+
+    // Test scripts (the endpoint's own first, then the global one)
+    let test_scripts = vec![scripts.test_script, global.test_script];
+    let has_tests = test_scripts.iter().any(|s| !s.trim().is_empty());
+    if has_tests {
+        let script_data = ScriptExecutionData {
+            script: String::new(),
+            request: sent_request,
+            response: Some(script_response(&response)),
+            environment: variables.clone(),
+        };
+        match super::scripts::execute_script_chain(
+            test_scripts,
+            script_data,
+            false,
+            ctx.proxy_state.offline.clone(),
+        )
+        .await
+        {
+            Ok(script_result) => {
+                absorb_script_result(script_result, &mut result, variables, environment_changes)
+            }
+            Err(e) => result.errors.push(e),
+        }
+    }
+
+    if let Some(options) = ctx.snapshot {
+        match snapshot::check_snapshot(app, &collection.id, &result.endpoint_id, &response, options)
+        {
+            Ok(tests) => result.test_results.extend(tests),
+            Err(e) => result.errors.push(e),
+        }
+    }
+
+    let tests_failed = result.test_results.iter().any(|t| !t.passed);
+    // Without assertions, a non-2xx status is the only failure signal.
+    if tests_failed
+        || !result.errors.is_empty()
+        || (result.test_results.is_empty() && !response.success)
+    {
+        result.status = "failed".to_string();
+    }
+    result
+}
+
+/// Variables for a run: collection < active environment < caller overrides.
+pub(crate) async fn initial_run_variables(
+    app: &AppHandle,
+    collection_id: &str,
+    overrides: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut variables = super::variables::load_collection_variables(app, collection_id).await;
+    variables.extend(super::variables::load_active_environment_variables(app).await);
+    variables.extend(overrides.clone());
+    variables
+}
+
+/// Tracks in-progress runs so they can be cancelled by id.
+pub struct RunnerState {
+    runs: Mutex<HashMap<String, watch::Sender<bool>>>,
+}
+
+impl Default for RunnerState {
+    fn default() -> Self {
+        Self {
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunnerEventPayload {
+    run_id: String,
+    collection_id: String,
+    /// "started" | "request-started" | "request-completed" | "completed" | "cancelled"
+    event_type: String,
+    total: usize,
+    /// Zero-based position of the current request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attempt: Option<u32>,
+    passed: usize,
+    failed: usize,
+    errored: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<RequestRunResult>,
+}
+
+/// Run every HTTP request of a collection (or one folder) sequentially.
+#[tauri::command]
+pub async fn run_collection(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    options: RunCollectionOptions,
+) -> Result<CollectionRunResult, String> {
+    execute_collection_run(&app, &proxy_state, options).await
+}
+
+/// Send every HTTP request of a folder (or the `endpoint_ids` among them)
+/// once, one after another or `parallel`, and return all statuses and timings
+/// together. Requests the safety policy guards are only sent when `confirmed`.
+#[tauri::command]
+pub async fn send_folder(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    collection_id: String,
+    folder_id: String,
+    parallel: Option<bool>,
+    confirmed: Option<bool>,
+    endpoint_ids: Option<Vec<String>>,
+) -> Result<FolderSendResult, String> {
+    batch::send_folder(
+        &app,
+        &proxy_state,
+        &collection_id,
+        &folder_id,
+        parallel.unwrap_or(false),
+        confirmed.unwrap_or(false),
+        &endpoint_ids.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Check that a list of URLs and/or a collection's endpoints respond, with
+/// HEAD (or GET) requests sent concurrently.
+#[tauri::command]
+pub async fn health_check(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    options: HealthCheckOptions,
+) -> Result<HealthCheckResult, String> {
+    health::health_check(&app, &proxy_state, options).await
+}
+
+/// Cancel an in-progress run. The in-flight request is abandoned and the
+/// run returns with the results collected so far.
+#[tauri::command]
+pub async fn run_cancel(state: State<'_, RunnerState>, run_id: String) -> Result<Value, String> {
+    let runs = state.runs.lock().unwrap();
+    if let Some(tx) = runs.get(&run_id) {
+        let _ = tx.send(true);
+        Ok(serde_json::json!({ "success": true, "message": "Run cancelled" }))
+    } else {
+        Ok(serde_json::json!({ "success": false, "message": "No active run with this id" }))
+    }
+}
+
+/// Restore the secret fields of the collection and folder auth configs, which
+/// are blanked in the collection file, and fill in their managed OAuth 2.0
+/// and command tokens.
+async fn hydrate_inherited_auth(
+    app: &AppHandle,
+    collection: &mut Collection,
+) -> Result<(), String> {
+    if let Some(auth) = collection.auth_config.as_mut() {
+        let scope = format!("auth:{}:__collection__", collection.id);
+        super::secrets::hydrate_auth_config(app, auth, &scope).await;
+        super::token_manager::apply_managed_token(app, auth).await;
+        super::command_auth::apply_command_token(app, &collection.id, auth).await?;
+    }
+    for folder in collection.folders.iter_mut() {
+        let scope = format!(
+            "auth:{}:__folder__:{}",
+            collection.id,
+            str_field(folder, "id")
+        );
+        if let Some(auth) = folder.get_mut("authConfig") {
+            super::secrets::hydrate_auth_config(app, auth, &scope).await;
+            super::token_manager::apply_managed_token(app, auth).await;
+            super::command_auth::apply_command_token(app, &collection.id, auth).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Build and send one endpoint of a collection the way a run would, but
+/// without its scripts, for backend callers that only need the response.
+pub(crate) async fn send_endpoint(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> Result<ApiResponse, String> {
+    let mut collection =
+        super::collections::collection_get(app.clone(), collection_id.to_string()).await?;
+    hydrate_inherited_auth(app, &mut collection).await?;
+    let item = collect_run_items(&collection, None)?
+        .into_iter()
+        .find(|item| item.id() == endpoint_id)
+        .ok_or_else(|| format!("Request {} not found in the collection", endpoint_id))?;
+
+    let settings = load_runner_settings(app);
+    let variables = initial_run_variables(app, collection_id, &HashMap::new()).await;
+    let options = build_unscripted_request(app, &collection, &item, &settings, variables).await?;
+    let options = super::secrets::resolve_secret_references(app, options).await?;
+    execute_api_request(proxy_state, options, std::future::pending()).await
+}
+
+/// Resolver for one request: the run's variables with the endpoint's own
+/// overrides on top. Overrides apply to that request only and never reach
+/// the scripts' environment.
+fn request_resolver(variables: HashMap<String, String>, item: &RunItem) -> VariableResolver {
+    let mut resolver = VariableResolver::new(variables);
+    for (name, value) in endpoint_variable_overrides(&item.endpoint) {
+        resolver.set(name, value);
+    }
+    resolver
+}
+
+/// The request for one endpoint as a run would build it, minus its scripts;
+/// secret references are still unresolved.
+async fn build_unscripted_request(
+    app: &AppHandle,
+    collection: &Collection,
+    item: &RunItem,
+    settings: &RunnerSettings,
+    variables: HashMap<String, String>,
+) -> Result<RequestOptions, String> {
+    let endpoint_id = item.id();
+    let (mut data, _) = load_endpoint_state(app, &collection.id, &endpoint_id).await;
+    hydrate_endpoint_auth(app, &collection.id, &endpoint_id, &mut data).await?;
+
+    let mut resolver = request_resolver(variables, item);
+    let mut options = build_request_options(collection, item, &data, &mut resolver, settings);
+    options.client_cert = client_cert_for_url(app, &options.url);
+    Ok(options)
+}
+
+/// Core of [`run_collection`], shared with backend callers such as monitors.
+pub(crate) async fn execute_collection_run(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    options: RunCollectionOptions,
+) -> Result<CollectionRunResult, String> {
+    let mut collection =
+        super::collections::collection_get(app.clone(), options.collection_id.clone()).await?;
+    hydrate_inherited_auth(app, &mut collection).await?;
+    let items = select_by_tags(
+        select_endpoints(
+            collect_run_items(&collection, options.folder_id.as_deref())?,
+            &options.endpoint_ids,
+        )?,
+        options.tags.as_deref(),
+    )?;
+    let settings = load_runner_settings(app);
+    let mut variables = initial_run_variables(app, &collection.id, &options.variables).await;
+    let mut environment_changes = HashMap::new();
+
+    let run_id = options
+        .run_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let runner_state = app.state::<RunnerState>();
+    runner_state
+        .runs
+        .lock()
+        .unwrap()
+        .insert(run_id.clone(), cancel_tx);
+
+    let ctx = RunContext {
+        app,
+        proxy_state,
+        collection: &collection,
+        settings: &settings,
+        cancel: cancel_rx,
+        snapshot: options.snapshot.as_ref(),
+        confirmed: options.confirmed,
+    };
+    let event = |event_type: &str, results: &[RequestRunResult]| {
+        let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+        RunnerEventPayload {
+            run_id: run_id.clone(),
+            collection_id: collection.id.clone(),
+            event_type: event_type.to_string(),
+            total: items.len(),
+            index: None,
+            name: None,
+            attempt: None,
+            passed: count("passed"),
+            failed: count("failed"),
+            errored: count("error"),
+            result: None,
+        }
+    };
+
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let started = Instant::now();
+    let mut results: Vec<RequestRunResult> = Vec::with_capacity(items.len());
+    let retries = options.retries.min(MAX_RETRIES);
+    let delay = Duration::from_millis(options.delay_ms);
+    let mut stopped_at = None;
+
+    let _ = app.emit("runner-event", event("started", &results));
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            ctx.pause(delay).await;
+        }
+        if ctx.is_cancelled() {
+            stopped_at = Some(index);
+            break;
+        }
+
+        let mut attempt = 0;
+        let result = loop {
+            attempt += 1;
+            let _ = app.emit(
+                "runner-event",
+                RunnerEventPayload {
+                    index: Some(index),
+                    name: Some(item.name()),
+                    attempt: Some(attempt),
+                    ..event("request-started", &results)
+                },
+            );
+
+            // A retry starts from the variables the first attempt saw, so
+            // script side effects of failed attempts are discarded.
+            let variables_before = variables.clone();
+            let changes_before = environment_changes.clone();
+            let mut result = run_item(&ctx, item, &mut variables, &mut environment_changes).await;
+            result.attempts = attempt;
+            // A held request is not sent, so retrying it cannot change the outcome.
+            let done = matches!(result.status.as_str(), "passed" | "held");
+            if done || attempt > retries || ctx.is_cancelled() {
+                break result;
+            }
+            variables = variables_before;
+            environment_changes = changes_before;
+            ctx.pause(delay).await;
+        };
+
+        // Held requests are reported but do not stop the run.
+        let passed = matches!(result.status.as_str(), "passed" | "held");
+        results.push(result);
+        let _ = app.emit(
+            "runner-event",
+            RunnerEventPayload {
+                index: Some(index),
+                name: Some(item.name()),
+                attempt: Some(attempt),
+                result: results.last().cloned(),
+                ..event("request-completed", &results)
+            },
+        );
+        if ctx.is_cancelled() || (!passed && options.stop_on_failure) {
+            stopped_at = Some(index + 1);
+            break;
+        }
+    }
+
+    runner_state.runs.lock().unwrap().remove(&run_id);
+    let cancelled = ctx.is_cancelled();
+    let _ = app.emit(
+        "runner-event",
+        event(if cancelled { "cancelled" } else { "completed" }, &results),
+    );
+
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+    let run = CollectionRunResult {
+        run_id: run_id.clone(),
+        collection_id: collection.id.clone(),
+        collection_name: collection.name.clone(),
+        folder_id: options.folder_id,
+        started_at,
+        duration: started.elapsed().as_millis() as u64,
+        total: results.len(),
+        passed: count("passed"),
+        failed: count("failed"),
+        errored: count("error"),
+        skipped: stopped_at.map_or(0, |at| items.len() - at),
+        held: count("held"),
+        over_budget: results
+            .iter()
+            .filter(|r| r.time_budget.as_ref().is_some_and(|b| b.exceeded))
+            .count(),
+        cancelled,
+        results,
+        environment_changes,
+    };
+    let secrets = super::secrets::secret_variable_values(app, Some(&run.collection_id)).await;
+    if let Err(e) = history::save_run(app, &run, &secrets) {
+        eprintln!("Failed to save run history: {}", e);
+    }
+    Ok(run)
+}
+
+/// Save a finished run as a JUnit XML (`format: "junit"`) or standalone
+/// HTML (`format: "html"`) report through a save dialog.
+#[tauri::command]
+pub async fn runner_export_report(
+    app: AppHandle,
+    result: CollectionRunResult,
+    format: String,
+) -> Result<Value, String> {
+    let (content, extension, mime_type) = match format.as_str() {
+        "junit" => (report::to_junit_xml(&result), "xml", "application/xml"),
+        "html" => (report::to_html(&result), "html", "text/html"),
+        other => return Err(format!("Unsupported report format: {}", other)),
+    };
+    let file_name = format!(
+        "{}-run-report.{}",
+        result.collection_name.replace(['/', '\\'], "-"),
+        extension
+    );
+    super::import_export::save_documentation(app, file_name, content, mime_type.to_string()).await
+}
+
+/// Stored runs, newest first, optionally limited to one collection.
+#[tauri::command]
+pub async fn runner_history_list(
+    app: AppHandle,
+    collection_id: Option<String>,
+) -> Result<Vec<RunHistoryEntry>, String> {
+    Ok(history::read_all_runs(&app)?
+        .iter()
+        .filter(|run| match collection_id.as_deref() {
+            Some(id) => run.collection_id == id,
+            None => true,
+        })
+        .map(RunHistoryEntry::from)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn runner_history_get(
+    app: AppHandle,
+    run_id: String,
+) -> Result<CollectionRunResult, String> {
+    history::load_run(&app, &run_id)
+}
+
+#[tauri::command]
+pub async fn runner_history_delete(app: AppHandle, run_id: String) -> Result<(), String> {
+    let path = history::run_path(&app, &run_id)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete run: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Diff two stored runs; `base_run_id` is the older/reference run.
+#[tauri::command]
+pub async fn runner_history_diff(
+    app: AppHandle,
+    base_run_id: String,
+    compare_run_id: String,
+) -> Result<RunDiff, String> {
+    let base = history::load_run(&app, &base_run_id)?;
+    let compare = history::load_run(&app, &compare_run_id)?;
+    Ok(history::diff_runs(&base, &compare))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn collection(endpoints: Value, folders: Value) -> Collection {
+        serde_json::from_value(json!({
+            "id": "c1",
+            "name": "Demo",
+            "baseUrl": "https://api.test",
+            "endpoints": endpoints,
+            "folders": folders,
+            "defaultHeaders": { "Accept": "application/json" },
+            "authConfig": { "type": "bearer", "config": { "token": "{{token}}" } },
+            "_openApiSpec": null
+        }))
+        .unwrap()
+    }
+
+    fn settings() -> RunnerSettings {
+        RunnerSettings {
+            http_version: None,
+            timeout: Some(DEFAULT_TIMEOUT_MS),
+            verify_ssl: true,
+            follow_redirects: true,
+            ip_family: None,
+            ca_bundle_path: None,
+            trace_id: None,
+        }
+    }
+
+    fn resolver(pairs: &[(&str, &str)]) -> VariableResolver {
+        VariableResolver::new(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn collects_root_then_folder_endpoints_and_skips_non_http() {
+        let c = collection(
+            json!([{ "id": "e1", "method": "GET", "path": "/a" },
+                   { "id": "g1", "protocol": "grpc", "path": "svc/Method" }]),
+            json!([{ "id": "f1", "name": "F", "endpoints": [{ "id": "e2", "method": "POST", "path": "/b" }] }]),
+        );
+        let ids: Vec<String> = collect_run_items(&c, None)
+            .unwrap()
+            .iter()
+            .map(RunItem::id)
+            .collect();
+        assert_eq!(ids, vec!["e1", "e2"]);
+
+        let folder_only = collect_run_items(&c, Some("f1")).unwrap();
+        assert_eq!(folder_only.len(), 1);
+        assert!(folder_only[0].folder.is_some());
+        assert!(collect_run_items(&c, Some("nope")).is_err());
+    }
+
+    #[test]
+    fn builds_url_headers_and_inherited_auth() {
+        let c = collection(json!([]), json!([]));
+        let item = RunItem {
+            endpoint: json!({
+                "id": "e1",
+                "method": "get",
+                "path": "/users/{id}",
+                "parameters": { "path": { "id": { "example": "7" } } },
+                "security": { "type": "inherit" }
+            }),
+            folder: None,
+        };
+        let data = EndpointData {
+            query_params: vec![
+                json!({ "key": "q", "value": "{{term}}" }),
+                json!({ "key": "off", "value": "x", "enabled": false }),
+            ],
+            headers: vec![json!({ "key": "accept", "value": "text/plain" })],
+            ..Default::default()
+        };
+        let mut r = resolver(&[("term", "a b"), ("token", "t0k")]);
+        let options = build_request_options(&c, &item, &data, &mut r, &settings());
+
+        assert_eq!(options.method, "GET");
+        assert_eq!(options.url, "https://api.test/users/7?q=a%20b");
+        let headers = options.headers.unwrap();
+        assert_eq!(
+            headers.get("accept").map(String::as_str),
+            Some("text/plain")
+        );
+        assert!(!headers.contains_key("Accept"));
+        assert_eq!(
+            headers.get("Authorization").map(String::as_str),
+            Some("Bearer t0k")
+        );
+        assert!(options.body.is_none());
+    }
+
+    #[test]
+    fn folder_auth_overrides_collection_auth() {
+        let c = collection(json!([]), json!([]));
+        let item = RunItem {
+            endpoint: json!({ "id": "e1", "method": "GET", "path": "/x" }),
+            folder: Some(json!({
+                "id": "f1",
+                "authConfig": { "type": "api-key", "config": { "keyName": "k", "keyValue": "v", "location": "query" } }
+            })),
+        };
+        let mut r = resolver(&[]);
+        let options =
+            build_request_options(&c, &item, &EndpointData::default(), &mut r, &settings());
+        assert_eq!(options.url, "https://api.test/x?k=v");
+        assert!(!options.headers.unwrap().contains_key("Authorization"));
+    }
+
+    #[test]
+    fn endpoint_can_ignore_tls_errors() {
+        let c = collection(json!([]), json!([]));
+        let mut item = RunItem {
+            endpoint: json!({ "id": "e1", "method": "GET", "path": "/x" }),
+            folder: None,
+        };
+        let mut r = resolver(&[]);
+        let options =
+            build_request_options(&c, &item, &EndpointData::default(), &mut r, &settings());
+        assert_eq!(options.verify_ssl, Some(true));
+
+        item.endpoint["ignoreTlsErrors"] = json!(true);
+        let options =
+            build_request_options(&c, &item, &EndpointData::default(), &mut r, &settings());
+        assert_eq!(options.verify_ssl, Some(false));
+    }
+
+    #[test]
+    fn command_auth_sends_the_fetched_token() {
+        let mut r = resolver(&[]);
+        let auth = json!({ "type": "command", "config": { "command": "gcloud", "token": "t1" } });
+        assert_eq!(
+            generate_auth_data(Some(&auth), &mut r).headers,
+            vec![("Authorization".to_string(), "Bearer t1".to_string())]
+        );
+        let auth = json!({ "type": "command", "config": {
+            "command": "internal-cli", "token": "t2", "headerName": "X-Auth", "headerPrefix": ""
+        } });
+        assert_eq!(
+            generate_auth_data(Some(&auth), &mut r).headers,
+            vec![("X-Auth".to_string(), "t2".to_string())]
+        );
+        let auth = json!({ "type": "command", "config": { "command": "failing" } });
+        assert!(generate_auth_data(Some(&auth), &mut r).headers.is_empty());
+    }
+
+    #[test]
+    fn body_prefers_persisted_content_and_falls_back_to_text() {
+        let c = collection(json!([]), json!([]));
+        let item = RunItem {
+            endpoint: json!({
+                "id": "e1", "method": "POST", "path": "/x",
+                "requestBody": { "example": "{\"from\":\"example\"}" }
+            }),
+            folder: None,
+        };
+        let mut r = resolver(&[("name", "Ada")]);
+        let options =
+            build_request_options(&c, &item, &EndpointData::default(), &mut r, &settings());
+        assert_eq!(options.body, Some(json!({ "from": "example" })));
+        assert_eq!(options.body_type, Some(BodyType::Json));
+
+        let data = EndpointData {
+            modified_body: Some("hello {{name}}".to_string()),
+            ..Default::default()
+        };
+        let options = build_request_options(&c, &item, &data, &mut r, &settings());
+        assert_eq!(options.body, Some(json!("hello Ada")));
+        assert_eq!(options.body_type, Some(BodyType::Text));
+    }
+
+    #[test]
+    fn script_request_mutations_are_applied() {
+        let c = collection(json!([]), json!([]));
+        let item = RunItem {
+            endpoint: json!({ "id": "e1", "method": "GET", "path": "/x" }),
+            folder: None,
+        };
+        let mut r = resolver(&[]);
+        let mut options =
+            build_request_options(&c, &item, &EndpointData::default(), &mut r, &settings());
+        let mut modified = script_request(&options);
+        modified["url"] = json!("https://other.test/y");
+        modified["headers"]["X-Trace"] = json!(42);
+        modified["body"] = json!("raw");
+        apply_script_request(&mut options, &modified);
+        assert_eq!(options.url, "https://other.test/y");
+        assert_eq!(
+            options.headers.unwrap().get("X-Trace").map(String::as_str),
+            Some("42")
+        );
+        assert_eq!(options.body, Some(json!("raw")));
+    }
+
+    #[test]
+    fn run_options_default_to_a_plain_sequential_run() {
+        let options: RunCollectionOptions =
+            serde_json::from_value(json!({ "collectionId": "c1" })).unwrap();
+        assert!(!options.stop_on_failure);
+        assert_eq!(options.retries, 0);
+        assert_eq!(options.delay_ms, 0);
+
+        let options: RunCollectionOptions = serde_json::from_value(json!({
+            "collectionId": "c1",
+            "stopOnFailure": true,
+            "retries": 3,
+            "delayMs": 250
+        }))
+        .unwrap();
+        assert!(options.stop_on_failure);
+        assert_eq!((options.retries, options.delay_ms), (3, 250));
+    }
+
+    #[test]
+    fn tag_expression_selects_endpoints() {
+        let c = collection(
+            json!([
+                { "id": "a", "method": "GET", "path": "/a", "tags": ["smoke"] },
+                { "id": "b", "method": "GET", "path": "/b", "tags": ["smoke", "slow"] },
+                { "id": "c", "method": "GET", "path": "/c" }
+            ]),
+            json!([]),
+        );
+        let ids = |expr: Option<&str>| -> Vec<String> {
+            select_by_tags(collect_run_items(&c, None).unwrap(), expr)
+                .unwrap()
+                .iter()
+                .map(RunItem::id)
+                .collect()
+        };
+        assert_eq!(ids(Some("smoke && !slow")), vec!["a"]);
+        assert_eq!(ids(Some("!smoke")), vec!["c"]);
+        assert_eq!(ids(Some("  ")).len(), 3);
+        assert_eq!(ids(None).len(), 3);
+        assert!(select_by_tags(Vec::new(), Some("smoke &&")).is_err());
+    }
+
+    #[test]
+    fn selected_endpoints_run_in_collection_order() {
+        let c = collection(
+            json!([
+                { "id": "a", "method": "GET", "path": "/a" },
+                { "id": "b", "method": "GET", "path": "/b" }
+            ]),
+            json!([{ "id": "f1", "name": "F", "endpoints": [{ "id": "c", "method": "GET", "path": "/c" }] }]),
+        );
+        let select = |ids: &[&str]| {
+            let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            select_endpoints(collect_run_items(&c, None).unwrap(), &ids)
+                .map(|items| items.iter().map(RunItem::id).collect::<Vec<_>>())
+        };
+        assert_eq!(select(&["c", "a"]).unwrap(), vec!["a", "c"]);
+        assert_eq!(select(&[]).unwrap().len(), 3);
+        assert!(select(&["a", "gone"]).unwrap_err().contains("gone"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Batch send of a folder: a lighter sibling of the full run for smoke
+//! checks after a deploy. Every HTTP request is sent once, without scripts,
+//! retries or run history, and the statuses and timings come back in one
+//! payload. Requests the collection's safety policy guards are held back
+//! unless the batch is sent confirmed.
+
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use std::time::Instant;
+use tauri::AppHandle;
+
+use super::{
+    build_unscripted_request, collect_run_items, hydrate_inherited_auth, initial_run_variables,
+    load_runner_settings, select_endpoints,
+};
+use crate::commands::api_request::{
+    execute_api_request, safety_policy_reason, ApiResponse, RequestTimings,
+};
+use crate::commands::proxy::ProxyState;
+
+/// Requests in flight at once when sending in parallel.
+const MAX_PARALLEL: usize = 6;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSendItem {
+    pub endpoint_id: String,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub status_code: Option<u16>,
+    /// A 2xx response was received
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<RequestTimings>,
+    pub size: Option<usize>,
+    /// Not sent: the safety policy wants it confirmed (`error` says why)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_required: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSendResult {
+    pub collection_id: String,
+    pub folder_id: String,
+    pub parallel: bool,
+    /// Wall-clock duration of the whole batch in ms
+    pub duration: u64,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Requests held back for confirmation, counted in neither of the above
+    pub confirmation_required: usize,
+    /// In folder order, also when sent in parallel
+    pub results: Vec<FolderSendItem>,
+}
+
+/// Fills in the outcome of sending `item` (already carrying its request).
+fn record_response(
+    mut item: FolderSendItem,
+    response: Result<ApiResponse, String>,
+) -> FolderSendItem {
+    match response {
+        Ok(response) => {
+            item.status_code = response.status;
+            item.success = response.success;
+            item.size = response.size;
+            item.timings = Some(response.timings);
+            if response.status.is_none() {
+                item.error = Some(
+                    response
+                        .message
+                        .unwrap_or_else(|| "Request failed".to_string()),
+                );
+            }
+        }
+        Err(e) => item.error = Some(e),
+    }
+    item
+}
+
+/// Marks `item` as held back by the safety policy for `reason`, unsent.
+fn record_held(mut item: FolderSendItem, reason: String) -> FolderSendItem {
+    item.confirmation_required = Some(true);
+    item.error = Some(reason);
+    item
+}
+
+/// Succeeded, failed and held-back counts of a batch.
+fn tally(results: &[FolderSendItem]) -> (usize, usize, usize) {
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let held = results
+        .iter()
+        .filter(|r| r.confirmation_required == Some(true))
+        .count();
+    (succeeded, results.len() - succeeded - held, held)
+}
+
+/// Sends the folder's requests (only `endpoint_ids` when given). `confirmed`
+/// sends the ones the safety policy guards too.
+pub(super) async fn send_folder(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    collection_id: &str,
+    folder_id: &str,
+    parallel: bool,
+    confirmed: bool,
+    endpoint_ids: &[String],
+) -> Result<FolderSendResult, String> {
+    let mut collection =
+        crate::commands::collections::collection_get(app.clone(), collection_id.to_string())
+            .await?;
+    hydrate_inherited_auth(app, &mut collection).await?;
+    let items = select_endpoints(
+        collect_run_items(&collection, Some(folder_id))?,
+        endpoint_ids,
+    )?;
+    let settings = load_runner_settings(app);
+    let variables = initial_run_variables(app, collection_id, &Default::default()).await;
+
+    let started = Instant::now();
+    let send = |run_item| {
+        let collection = &collection;
+        let settings = &settings;
+        let variables = variables.clone();
+        async move {
+            let mut item = FolderSendItem {
+                endpoint_id: run_item.id(),
+                name: run_item.name(),
+                method: run_item.method(),
+                url: String::new(),
+                status_code: None,
+                success: false,
+                error: None,
+                timings: None,
+                size: None,
+                confirmation_required: None,
+            };
+            let mut options =
+                match build_unscripted_request(app, collection, &run_item, settings, variables)
+                    .await
+                {
+                    Ok(options) => options,
+                    Err(e) => return record_response(item, Err(e)),
+                };
+            options.confirmed = confirmed;
+            item.method = options.method.clone();
+            item.url = options.url.clone();
+            match safety_policy_reason(app, &options) {
+                Ok(None) => {}
+                Ok(Some(reason)) => return record_held(item, reason),
+                Err(e) => {
+                    item.error = Some(e);
+                    return item;
+                }
+            }
+            let response =
+                match crate::commands::secrets::resolve_secret_references(app, options).await {
+                    Ok(options) => {
+                        execute_api_request(proxy_state, options, std::future::pending()).await
+                    }
+                    Err(e) => Err(e),
+                };
+            record_response(item, response)
+        }
+    };
+    let in_flight = if parallel { MAX_PARALLEL } else { 1 };
+    let results: Vec<FolderSendItem> = stream::iter(items)
+        .map(send)
+        .buffered(in_flight)
+        .collect()
+        .await;
+
+    let (succeeded, failed, held) = tally(&results);
+    Ok(FolderSendResult {
+        collection_id: collection_id.to_string(),
+        folder_id: folder_id.to_string(),
+        parallel,
+        duration: started.elapsed().as_millis() as u64,
+        total: results.len(),
+        succeeded,
+        failed,
+        confirmation_required: held,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> FolderSendItem {
+        FolderSendItem {
+            endpoint_id: "e1".to_string(),
+            name: "Health".to_string(),
+            method: "GET".to_string(),
+            url: "https://api.test/health".to_string(),
+            status_code: None,
+            success: false,
+            error: None,
+            timings: None,
+            size: None,
+            confirmation_required: None,
+        }
+    }
+
+    fn response(status: Option<u16>, message: Option<&str>) -> ApiResponse {
+        serde_json::from_value(serde_json::json!({
+            "success": status.is_some_and(|s| (200..300).contains(&s)),
+            "status": status,
+            "headers": {},
+            "setCookies": [],
+            "message": message,
+            "size": 12,
+            "timings": {
+                "startTime": 0, "dnsLookup": 1, "tcpConnection": 2, "tlsHandshake": 3,
+                "firstByte": 40, "download": 5, "total": 45
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn records_statuses_timings_and_errors() {
+        let ok = record_response(item(), Ok(response(Some(200), None)));
+        assert!(ok.success);
+        assert_eq!(ok.status_code, Some(200));
+        assert_eq!(ok.timings.unwrap().total, 45);
+        assert!(ok.error.is_none());
+
+        let server_error = record_response(item(), Ok(response(Some(503), None)));
+        assert!(!server_error.success);
+        assert!(server_error.error.is_none());
+
+        let refused = record_response(item(), Ok(response(None, Some("Connection failed."))));
+        assert_eq!(refused.error.as_deref(), Some("Connection failed."));
+
+        let unresolved = record_response(item(), Err("Secret not found".to_string()));
+        assert_eq!(unresolved.error.as_deref(), Some("Secret not found"));
+        assert!(unresolved.timings.is_none());
+    }
+
+    #[test]
+    fn held_requests_count_as_neither_success_nor_failure() {
+        let held = record_held(item(), "DELETE needs confirmation".to_string());
+        assert_eq!(held.confirmation_required, Some(true));
+        assert_eq!(held.error.as_deref(), Some("DELETE needs confirmation"));
+        assert!(held.status_code.is_none());
+        let json = serde_json::to_value(&held).unwrap();
+        assert_eq!(json["confirmationRequired"], true);
+        assert!(serde_json::to_value(item())
+            .unwrap()
+            .get("confirmationRequired")
+            .is_none());
+
+        let results = vec![
+            record_response(item(), Ok(response(Some(200), None))),
+            record_response(item(), Ok(response(Some(500), None))),
+            held,
+        ];
+        assert_eq!(tally(&results), (1, 1, 1));
+    }
+}
@@ -0,0 +1,279 @@
+//! Bulk health check: a HEAD (or GET) against a list of URLs and/or every
+//! HTTP endpoint of a collection, sent concurrently, reporting up/down,
+//! status and latency. Endpoints are checked with the lightweight method
+//! rather than their own, so a check never creates or deletes anything.
+
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use tauri::AppHandle;
+
+use super::{
+    build_unscripted_request, client_cert_for_url, collect_run_items, hydrate_inherited_auth,
+    initial_run_variables, load_runner_settings,
+};
+use crate::commands::api_request::{execute_api_request, ApiResponse, RequestOptions};
+use crate::commands::proxy::ProxyState;
+use crate::commands::variables::VariableResolver;
+
+/// Checks in flight at once.
+const MAX_PARALLEL: usize = 8;
+/// Per-check timeout when none is given; a health check should fail fast.
+const CHECK_TIMEOUT_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckOptions {
+    /// URLs to check; `{{variables}}` resolve from the active environment
+    /// (and the collection, when one is given)
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Also check every HTTP endpoint of this collection
+    #[serde(default)]
+    pub collection_id: Option<String>,
+    /// Only the endpoints of this folder of `collection_id`
+    #[serde(default)]
+    pub folder_id: Option<String>,
+    /// "HEAD" (default) or "GET"
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Per-check timeout in ms (defaults to 10000)
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckItem {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_id: Option<String>,
+    pub url: String,
+    /// Method of the check that produced the result
+    pub method: String,
+    /// The server answered without a 5xx
+    pub up: bool,
+    pub status_code: Option<u16>,
+    /// Total time of the check in ms
+    pub latency: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckResult {
+    /// Wall-clock duration of all checks in ms
+    pub duration: u64,
+    pub total: usize,
+    pub up: usize,
+    pub down: usize,
+    /// In the order the targets were given
+    pub results: Vec<HealthCheckItem>,
+}
+
+struct Target {
+    name: String,
+    endpoint_id: Option<String>,
+    options: RequestOptions,
+}
+
+/// Fills in the outcome of a check. A 4xx still means the server is up.
+fn record_check(
+    mut item: HealthCheckItem,
+    response: Result<ApiResponse, String>,
+) -> HealthCheckItem {
+    match response {
+        Ok(response) => {
+            item.status_code = response.status;
+            item.up = response.status.is_some_and(|status| status < 500);
+            if response.status.is_some() {
+                item.latency = Some(response.timings.total);
+            } else {
+                item.error = Some(
+                    response
+                        .message
+                        .unwrap_or_else(|| "Request failed".to_string()),
+                );
+            }
+        }
+        Err(e) => item.error = Some(e),
+    }
+    item
+}
+
+/// Sends one check, retrying a HEAD the server does not support as a GET.
+async fn check(app: &AppHandle, proxy_state: &ProxyState, target: Target) -> HealthCheckItem {
+    let Target {
+        name,
+        endpoint_id,
+        options,
+    } = target;
+    let mut item = HealthCheckItem {
+        name,
+        endpoint_id,
+        url: options.url.clone(),
+        method: options.method.clone(),
+        up: false,
+        status_code: None,
+        latency: None,
+        error: None,
+    };
+    let options = match crate::commands::secrets::resolve_secret_references(app, options).await {
+        Ok(options) => options,
+        Err(e) => return record_check(item, Err(e)),
+    };
+    let fallback = (options.method == "HEAD").then(|| RequestOptions {
+        method: "GET".to_string(),
+        ..options.clone()
+    });
+    let mut response = execute_api_request(proxy_state, options, std::future::pending()).await;
+    if let (Ok(head), Some(get)) = (&response, fallback) {
+        if matches!(head.status, Some(405) | Some(501)) {
+            item.method = get.method.clone();
+            response = execute_api_request(proxy_state, get, std::future::pending()).await;
+        }
+    }
+    record_check(item, response)
+}
+
+pub(super) async fn health_check(
+    app: &AppHandle,
+    proxy_state: &ProxyState,
+    options: HealthCheckOptions,
+) -> Result<HealthCheckResult, String> {
+    let method = options
+        .method
+        .as_deref()
+        .unwrap_or("HEAD")
+        .to_ascii_uppercase();
+    if method != "HEAD" && method != "GET" {
+        return Err(format!("Health checks use HEAD or GET, not {}", method));
+    }
+    let timeout = Some(options.timeout.unwrap_or(CHECK_TIMEOUT_MS));
+    let settings = load_runner_settings(app);
+    let variables = initial_run_variables(
+        app,
+        options.collection_id.as_deref().unwrap_or_default(),
+        &HashMap::new(),
+    )
+    .await;
+
+    let mut targets = Vec::new();
+    let mut resolver = VariableResolver::new(variables.clone());
+    for url in options.urls.iter().map(|url| url.trim()) {
+        if url.is_empty() {
+            continue;
+        }
+        let resolved = resolver.resolve(url);
+        let mut request: RequestOptions = serde_json::from_value(serde_json::json!({
+            "method": method,
+            "url": resolved,
+            "httpVersion": settings.http_version,
+            "verifySsl": settings.verify_ssl,
+            "followRedirects": settings.follow_redirects,
+            "ipFamily": settings.ip_family,
+        }))
+        .map_err(|e| e.to_string())?;
+        request.timeout = timeout;
+        request.client_cert = client_cert_for_url(app, &request.url);
+        targets.push(Target {
+            name: url.to_string(),
+            endpoint_id: None,
+            options: request,
+        });
+    }
+
+    if let Some(collection_id) = options.collection_id.as_deref() {
+        let mut collection =
+            crate::commands::collections::collection_get(app.clone(), collection_id.to_string())
+                .await?;
+        hydrate_inherited_auth(app, &mut collection).await?;
+        for item in collect_run_items(&collection, options.folder_id.as_deref())? {
+            let mut request =
+                build_unscripted_request(app, &collection, &item, &settings, variables.clone())
+                    .await?;
+            request.method = method.clone();
+            request.body = None;
+            request.body_type = None;
+            request.timeout = timeout;
+            targets.push(Target {
+                name: item.name(),
+                endpoint_id: Some(item.id()),
+                options: request,
+            });
+        }
+    }
+    if targets.is_empty() {
+        return Err("Nothing to check: give URLs or a collection".to_string());
+    }
+
+    let started = Instant::now();
+    let results: Vec<HealthCheckItem> = stream::iter(targets)
+        .map(|target| check(app, proxy_state, target))
+        .buffered(MAX_PARALLEL)
+        .collect()
+        .await;
+
+    let up = results.iter().filter(|r| r.up).count();
+    Ok(HealthCheckResult {
+        duration: started.elapsed().as_millis() as u64,
+        total: results.len(),
+        up,
+        down: results.len() - up,
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> HealthCheckItem {
+        HealthCheckItem {
+            name: "Health".to_string(),
+            endpoint_id: None,
+            url: "https://api.test/health".to_string(),
+            method: "HEAD".to_string(),
+            up: false,
+            status_code: None,
+            latency: None,
+            error: None,
+        }
+    }
+
+    fn response(status: Option<u16>, message: Option<&str>) -> ApiResponse {
+        serde_json::from_value(serde_json::json!({
+            "success": status.is_some_and(|s| (200..300).contains(&s)),
+            "status": status,
+            "headers": {},
+            "setCookies": [],
+            "message": message,
+            "timings": {
+                "startTime": 0, "dnsLookup": 1, "tcpConnection": 2, "tlsHandshake": 3,
+                "firstByte": 20, "download": 2, "total": 22
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn classifies_checks_as_up_or_down() {
+        let ok = record_check(item(), Ok(response(Some(204), None)));
+        assert!(ok.up);
+        assert_eq!(ok.latency, Some(22));
+
+        let unauthorized = record_check(item(), Ok(response(Some(401), None)));
+        assert!(unauthorized.up);
+
+        let unavailable = record_check(item(), Ok(response(Some(503), None)));
+        assert!(!unavailable.up);
+        assert_eq!(unavailable.status_code, Some(503));
+
+        let refused = record_check(item(), Ok(response(None, Some("Connection failed."))));
+        assert!(!refused.up);
+        assert_eq!(refused.error.as_deref(), Some("Connection failed."));
+        assert!(refused.latency.is_none());
+    }
+}
@@ -0,0 +1,397 @@
+//! Persisted run history and run-to-run diffs.
+//!
+//! Every finished collection run is written to `runs/<runId>.json` in the
+//! active workspace's data directory (the full [`CollectionRunResult`],
+//! including assertions and timings) with secret variable values masked. The
+//! oldest runs are pruned once [`MAX_STORED_RUNS`] is exceeded.
+
+use super::{CollectionRunResult, RequestRunResult};
+use crate::commands::secrets::mask_secret_values;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const RUNS_DIR: &str = "runs";
+const MAX_STORED_RUNS: usize = 200;
+
+/// Run metadata without per-request results, for listing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunHistoryEntry {
+    pub run_id: String,
+    pub collection_id: String,
+    pub collection_name: String,
+    pub folder_id: Option<String>,
+    pub started_at: i64,
+    pub duration: u64,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub errored: usize,
+}
+
+impl From<&CollectionRunResult> for RunHistoryEntry {
+    fn from(run: &CollectionRunResult) -> Self {
+        Self {
+            run_id: run.run_id.clone(),
+            collection_id: run.collection_id.clone(),
+            collection_name: run.collection_name.clone(),
+            folder_id: run.folder_id.clone(),
+            started_at: run.started_at,
+            duration: run.duration,
+            total: run.total,
+            passed: run.passed,
+            failed: run.failed,
+            errored: run.errored,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointDiff {
+    pub endpoint_id: String,
+    pub name: String,
+    pub method: String,
+    /// "regressed" | "fixed" | "changed" | "unchanged" | "added" | "removed"
+    pub change: String,
+    pub base_status: Option<String>,
+    pub compare_status: Option<String>,
+    pub base_status_code: Option<u16>,
+    pub compare_status_code: Option<u16>,
+    pub base_response_time: Option<u64>,
+    pub compare_response_time: Option<u64>,
+    /// Assertions failing in the compared run that passed (or did not exist)
+    /// in the base run
+    pub newly_failing_tests: Vec<String>,
+    /// Assertions passing in the compared run that failed in the base run
+    pub newly_passing_tests: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunDiff {
+    pub base: RunHistoryEntry,
+    pub compare: RunHistoryEntry,
+    pub regressed: usize,
+    pub fixed: usize,
+    pub added: usize,
+    pub removed: usize,
+    pub endpoints: Vec<EndpointDiff>,
+}
+
+fn runs_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::commands::workspaces::data_dir(app)?.join(RUNS_DIR))
+}
+
+/// Run ids become file names, so only accept the characters a UUID uses.
+pub(super) fn run_path(app: &AppHandle, run_id: &str) -> Result<PathBuf, String> {
+    if run_id.is_empty()
+        || !run_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid run id: {}", run_id));
+    }
+    Ok(runs_dir(app)?.join(format!("{}.json", run_id)))
+}
+
+pub(super) fn read_all_runs(app: &AppHandle) -> Result<Vec<CollectionRunResult>, String> {
+    let dir = runs_dir(app)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read run history directory: {}", e))?;
+
+    let mut runs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let parsed = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CollectionRunResult>(&content).ok());
+        match parsed {
+            Some(run) => runs.push(run),
+            None => eprintln!("Skipping unreadable run file: {}", path.display()),
+        }
+    }
+    runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(runs)
+}
+
+/// Store a finished run, with the values of `secrets` masked.
+pub(super) fn save_run(
+    app: &AppHandle,
+    run: &CollectionRunResult,
+    secrets: &[String],
+) -> Result<(), String> {
+    let dir = runs_dir(app)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create run history directory: {}", e))?;
+    let mut value =
+        serde_json::to_value(run).map_err(|e| format!("Failed to serialize run: {}", e))?;
+    mask_secret_values(&mut value, secrets);
+    let content =
+        serde_json::to_string(&value).map_err(|e| format!("Failed to serialize run: {}", e))?;
+    std::fs::write(run_path(app, &run.run_id)?, content)
+        .map_err(|e| format!("Failed to write run: {}", e))?;
+
+    let runs = read_all_runs(app)?;
+    for old in runs.iter().skip(MAX_STORED_RUNS) {
+        let _ = std::fs::remove_file(run_path(app, &old.run_id)?);
+    }
+    Ok(())
+}
+
+pub(super) fn load_run(app: &AppHandle, run_id: &str) -> Result<CollectionRunResult, String> {
+    let path = run_path(app, run_id)?;
+    let content =
+        std::fs::read_to_string(&path).map_err(|_| format!("Run not found: {}", run_id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse run {}: {}", run_id, e))
+}
+
+fn classify(base: Option<&RequestRunResult>, compare: Option<&RequestRunResult>) -> &'static str {
+    match (base, compare) {
+        (None, _) => "added",
+        (_, None) => "removed",
+        (Some(b), Some(c)) => {
+            let was_ok = b.status == "passed";
+            let is_ok = c.status == "passed";
+            match (was_ok, is_ok) {
+                (true, false) => "regressed",
+                (false, true) => "fixed",
+                _ if b.status != c.status || b.status_code != c.status_code => "changed",
+                _ => "unchanged",
+            }
+        }
+    }
+}
+
+fn endpoint_diff(
+    base: Option<&RequestRunResult>,
+    compare: Option<&RequestRunResult>,
+) -> EndpointDiff {
+    let change = classify(base, compare);
+    let latest = compare
+        .or(base)
+        .expect("endpoint diff needs at least one side");
+
+    let failing = |r: Option<&RequestRunResult>| -> HashSet<String> {
+        r.map(|r| {
+            r.test_results
+                .iter()
+                .filter(|t| !t.passed)
+                .map(|t| t.message.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    let passing = |r: Option<&RequestRunResult>| -> HashSet<String> {
+        r.map(|r| {
+            r.test_results
+                .iter()
+                .filter(|t| t.passed)
+                .map(|t| t.message.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    let (base_failing, compare_failing) = (failing(base), failing(compare));
+    let compare_passing = passing(compare);
+
+    // Keep assertion order as reported by the compared run
+    let ordered = |set: &HashSet<String>| -> Vec<String> {
+        let mut seen = HashSet::new();
+        compare
+            .map(|c| c.test_results.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|t| set.contains(&t.message) && seen.insert(t.message.clone()))
+            .map(|t| t.message.clone())
+            .collect()
+    };
+    let newly_failing: HashSet<String> =
+        compare_failing.difference(&base_failing).cloned().collect();
+    let newly_passing: HashSet<String> = base_failing
+        .intersection(&compare_passing)
+        .cloned()
+        .collect();
+
+    EndpointDiff {
+        endpoint_id: latest.endpoint_id.clone(),
+        name: latest.name.clone(),
+        method: latest.method.clone(),
+        change: change.to_string(),
+        base_status: base.map(|r| r.status.clone()),
+        compare_status: compare.map(|r| r.status.clone()),
+        base_status_code: base.and_then(|r| r.status_code),
+        compare_status_code: compare.and_then(|r| r.status_code),
+        base_response_time: base.map(|r| r.response_time),
+        compare_response_time: compare.map(|r| r.response_time),
+        newly_failing_tests: ordered(&newly_failing),
+        newly_passing_tests: ordered(&newly_passing),
+    }
+}
+
+/// Compare two runs endpoint by endpoint, in the order of the compared run
+/// followed by endpoints that only exist in the base run.
+pub(super) fn diff_runs(base: &CollectionRunResult, compare: &CollectionRunResult) -> RunDiff {
+    let find = |run: &CollectionRunResult, id: &str| -> Option<usize> {
+        run.results.iter().position(|r| r.endpoint_id == id)
+    };
+
+    let mut endpoints: Vec<EndpointDiff> = compare
+        .results
+        .iter()
+        .map(|c| {
+            let b = find(base, &c.endpoint_id).map(|i| &base.results[i]);
+            endpoint_diff(b, Some(c))
+        })
+        .collect();
+    endpoints.extend(
+        base.results
+            .iter()
+            .filter(|b| find(compare, &b.endpoint_id).is_none())
+            .map(|b| endpoint_diff(Some(b), None)),
+    );
+
+    let count = |change: &str| endpoints.iter().filter(|e| e.change == change).count();
+    RunDiff {
+        base: RunHistoryEntry::from(base),
+        compare: RunHistoryEntry::from(compare),
+        regressed: count("regressed"),
+        fixed: count("fixed"),
+        added: count("added"),
+        removed: count("removed"),
+        endpoints,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::scripts::TestResult;
+    use std::collections::HashMap;
+
+    fn request(id: &str, status: &str, code: u16, tests: &[(bool, &str)]) -> RequestRunResult {
+        RequestRunResult {
+            endpoint_id: id.to_string(),
+            name: id.to_string(),
+            method: "GET".to_string(),
+            url: format!("https://api.test/{}", id),
+            status: status.to_string(),
+            status_code: Some(code),
+            response_time: 100,
+            response_size: None,
+            test_results: tests
+                .iter()
+                .map(|(passed, message)| TestResult {
+                    passed: *passed,
+                    message: message.to_string(),
+                })
+                .collect(),
+            errors: Vec::new(),
+            logs: Vec::new(),
+            attempts: 1,
+            time_budget: None,
+            trace_ids: None,
+        }
+    }
+
+    fn run(id: &str, results: Vec<RequestRunResult>) -> CollectionRunResult {
+        CollectionRunResult {
+            run_id: id.to_string(),
+            collection_id: "c1".to_string(),
+            collection_name: "API".to_string(),
+            folder_id: None,
+            started_at: 0,
+            duration: 0,
+            total: results.len(),
+            passed: 0,
+            failed: 0,
+            errored: 0,
+            skipped: 0,
+            held: 0,
+            over_budget: 0,
+            cancelled: false,
+            results,
+            environment_changes: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn diff_classifies_regressions_fixes_and_membership_changes() {
+        let base = run(
+            "a",
+            vec![
+                request(
+                    "users",
+                    "passed",
+                    200,
+                    &[(true, "status 200"), (true, "has id")],
+                ),
+                request("orders", "failed", 500, &[(false, "status 200")]),
+                request("legacy", "passed", 200, &[]),
+                request("health", "passed", 200, &[]),
+            ],
+        );
+        let compare = run(
+            "b",
+            vec![
+                request(
+                    "users",
+                    "failed",
+                    200,
+                    &[(true, "status 200"), (false, "has id")],
+                ),
+                request("orders", "passed", 200, &[(true, "status 200")]),
+                request("health", "passed", 200, &[]),
+                request("search", "passed", 200, &[]),
+            ],
+        );
+
+        let diff = diff_runs(&base, &compare);
+        let change = |id: &str| {
+            diff.endpoints
+                .iter()
+                .find(|e| e.endpoint_id == id)
+                .unwrap()
+                .change
+                .clone()
+        };
+        assert_eq!(change("users"), "regressed");
+        assert_eq!(change("orders"), "fixed");
+        assert_eq!(change("health"), "unchanged");
+        assert_eq!(change("search"), "added");
+        assert_eq!(change("legacy"), "removed");
+        assert_eq!(
+            (diff.regressed, diff.fixed, diff.added, diff.removed),
+            (1, 1, 1, 1)
+        );
+
+        let users = diff
+            .endpoints
+            .iter()
+            .find(|e| e.endpoint_id == "users")
+            .unwrap();
+        assert_eq!(users.newly_failing_tests, vec!["has id"]);
+        let orders = diff
+            .endpoints
+            .iter()
+            .find(|e| e.endpoint_id == "orders")
+            .unwrap();
+        assert_eq!(orders.newly_passing_tests, vec!["status 200"]);
+        assert_eq!(diff.endpoints.last().unwrap().endpoint_id, "legacy");
+    }
+
+    #[test]
+    fn still_failing_with_new_status_is_changed() {
+        let base = run("a", vec![request("x", "failed", 500, &[])]);
+        let compare = run("b", vec![request("x", "failed", 404, &[])]);
+        assert_eq!(diff_runs(&base, &compare).endpoints[0].change, "changed");
+    }
+}
@@ -0,0 +1,330 @@
+//! Report renderers for collection runs: JUnit XML for CI systems and a
+//! self-contained HTML page for sharing.
+
+use super::{CollectionRunResult, RequestRunResult};
+
+/// Escape text for XML/HTML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            // Control characters other than tab/newline are not valid XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+fn iso_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string()
+}
+
+fn case_name(result: &RequestRunResult) -> String {
+    format!("{} {}", result.method, result.name)
+}
+
+/// One-line explanation of why a request did not pass.
+fn failure_message(result: &RequestRunResult) -> String {
+    let failed_tests: Vec<&str> = result
+        .test_results
+        .iter()
+        .filter(|t| !t.passed)
+        .map(|t| t.message.as_str())
+        .collect();
+    if let Some(first) = failed_tests.first() {
+        if failed_tests.len() == 1 {
+            return first.to_string();
+        }
+        return format!("{} (and {} more)", first, failed_tests.len() - 1);
+    }
+    if let Some(error) = result.errors.first() {
+        return error.clone();
+    }
+    match result.status_code {
+        Some(code) => format!("Unexpected status {}", code),
+        None => "Request failed".to_string(),
+    }
+}
+
+fn failure_details(result: &RequestRunResult) -> String {
+    let mut lines = Vec::new();
+    for test in result.test_results.iter().filter(|t| !t.passed) {
+        lines.push(format!("FAIL {}", test.message));
+    }
+    lines.extend(result.errors.iter().cloned());
+    if let Some(code) = result.status_code {
+        lines.push(format!("{} {} -> {}", result.method, result.url, code));
+    } else {
+        lines.push(format!("{} {}", result.method, result.url));
+    }
+    lines.join("\n")
+}
+
+/// Render a run as a JUnit XML document: one `<testsuite>` per run and one
+/// `<testcase>` per request, with script assertions folded into the case.
+pub(crate) fn to_junit_xml(run: &CollectionRunResult) -> String {
+    let suite_name = escape(&run.collection_name);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+        suite_name,
+        run.total,
+        run.failed,
+        run.errored,
+        seconds(run.duration)
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" id=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\" timestamp=\"{}\">\n",
+        suite_name,
+        escape(&run.run_id),
+        run.total,
+        run.failed,
+        run.errored,
+        seconds(run.duration),
+        iso_timestamp(run.started_at)
+    ));
+
+    for result in &run.results {
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\">\n",
+            escape(&case_name(result)),
+            suite_name,
+            seconds(result.response_time)
+        ));
+        let tag = match result.status.as_str() {
+            "passed" => None,
+            "error" => Some("error"),
+            "held" => Some("skipped"),
+            _ => Some("failure"),
+        };
+        if let Some(tag) = tag {
+            xml.push_str(&format!(
+                "      <{tag} message=\"{}\">{}</{tag}>\n",
+                escape(&failure_message(result)),
+                escape(&failure_details(result)),
+            ));
+        }
+        if !result.logs.is_empty() {
+            let output: Vec<String> = result
+                .logs
+                .iter()
+                .map(|log| format!("[{}] {}", log.level, log.message))
+                .collect();
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape(&output.join("\n"))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;margin:2rem;color:#1f2328}\
+h1{margin-bottom:.25rem}.meta{color:#59636e;margin-bottom:1.5rem}\
+.summary{display:flex;gap:1rem;margin-bottom:1.5rem}\
+.card{border:1px solid #d1d9e0;border-radius:6px;padding:.75rem 1.25rem;min-width:6rem}\
+.card b{display:block;font-size:1.5rem}\
+table{border-collapse:collapse;width:100%}th,td{text-align:left;padding:.5rem;border-bottom:1px solid #d1d9e0;vertical-align:top}\
+.passed{color:#1a7f37}.failed{color:#cf222e}.error{color:#9a6700}.held{color:#59636e}\
+.method{font-family:monospace;font-weight:600}.url{font-family:monospace;color:#59636e;word-break:break-all}\
+ul{margin:.25rem 0;padding-left:1.25rem}";
+
+/// Render a run as a standalone HTML page with inline styles and no
+/// external assets, so it can be mailed or attached as-is.
+pub(crate) fn to_html(run: &CollectionRunResult) -> String {
+    let title = format!("{} — Run Report", run.collection_name);
+    let started = chrono::DateTime::from_timestamp_millis(run.started_at)
+        .unwrap_or_default()
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(&title)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", HTML_STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&run.collection_name)));
+    html.push_str(&format!(
+        "<div class=\"meta\">Started {} &middot; {} ms</div>\n",
+        started, run.duration
+    ));
+
+    html.push_str("<div class=\"summary\">\n");
+    for (label, value, class) in [
+        ("Total", run.total, ""),
+        ("Passed", run.passed, "passed"),
+        ("Failed", run.failed, "failed"),
+        ("Errors", run.errored, "error"),
+        ("Held back", run.held, "held"),
+        ("Over budget", run.over_budget, "error"),
+    ] {
+        html.push_str(&format!(
+            "<div class=\"card {}\"><b>{}</b>{}</div>\n",
+            class, value, label
+        ));
+    }
+    html.push_str("</div>\n");
+
+    html.push_str(
+        "<table>\n<thead><tr><th>Result</th><th>Request</th><th>Status</th><th>Time</th><th>Details</th></tr></thead>\n<tbody>\n",
+    );
+    for result in &run.results {
+        let status_code = result
+            .status_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "—".to_string());
+
+        let mut details = String::new();
+        if !result.test_results.is_empty() {
+            details.push_str("<ul>");
+            for test in &result.test_results {
+                let (class, mark) = if test.passed {
+                    ("passed", "✓")
+                } else {
+                    ("failed", "✗")
+                };
+                details.push_str(&format!(
+                    "<li class=\"{}\">{} {}</li>",
+                    class,
+                    mark,
+                    escape(&test.message)
+                ));
+            }
+            details.push_str("</ul>");
+        }
+        for error in &result.errors {
+            details.push_str(&format!("<div class=\"error\">{}</div>", escape(error)));
+        }
+        let time = match &result.time_budget {
+            Some(budget) if budget.exceeded => format!(
+                "{} ms<div class=\"error\">over {} ms budget</div>",
+                result.response_time, budget.budget_ms
+            ),
+            _ => format!("{} ms", result.response_time),
+        };
+
+        html.push_str(&format!(
+            "<tr><td class=\"{status}\">{status}</td><td><span class=\"method\">{}</span> {}<div class=\"url\">{}</div></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&result.method),
+            escape(&result.name),
+            escape(&result.url),
+            status_code,
+            time,
+            details,
+            status = escape(&result.status),
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::scripts::TestResult;
+    use std::collections::HashMap;
+
+    fn request(name: &str, status: &str, tests: &[(bool, &str)]) -> RequestRunResult {
+        RequestRunResult {
+            endpoint_id: name.to_string(),
+            name: name.to_string(),
+            method: "GET".to_string(),
+            url: format!("https://api.test/{}", name),
+            status: status.to_string(),
+            status_code: if status == "error" { None } else { Some(200) },
+            response_time: 1234,
+            response_size: Some(10),
+            test_results: tests
+                .iter()
+                .map(|(passed, message)| TestResult {
+                    passed: *passed,
+                    message: message.to_string(),
+                })
+                .collect(),
+            errors: if status == "error" {
+                vec!["connection refused".to_string()]
+            } else {
+                Vec::new()
+            },
+            logs: Vec::new(),
+            attempts: 1,
+            time_budget: None,
+            trace_ids: None,
+        }
+    }
+
+    fn run() -> CollectionRunResult {
+        CollectionRunResult {
+            run_id: "run-1".to_string(),
+            collection_id: "c1".to_string(),
+            collection_name: "Pets & <Owners>".to_string(),
+            folder_id: None,
+            started_at: 0,
+            duration: 2500,
+            total: 3,
+            passed: 1,
+            failed: 1,
+            errored: 1,
+            skipped: 0,
+            held: 0,
+            over_budget: 0,
+            cancelled: false,
+            results: vec![
+                request("list", "passed", &[(true, "status is 200")]),
+                request("create", "failed", &[(false, "expected \"id\"")]),
+                request("delete", "error", &[]),
+            ],
+            environment_changes: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn junit_report_has_counts_and_escaped_failures() {
+        let xml = to_junit_xml(&run());
+        assert!(xml.contains(
+            "<testsuites name=\"Pets &amp; &lt;Owners&gt;\" tests=\"3\" failures=\"1\" errors=\"1\" time=\"2.500\">"
+        ));
+        assert!(xml.contains("timestamp=\"1970-01-01T00:00:00\""));
+        assert!(xml.contains("<testcase name=\"GET list\""));
+        assert!(xml.contains("<failure message=\"expected &quot;id&quot;\">"));
+        assert!(xml.contains("<error message=\"connection refused\">"));
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+    }
+
+    #[test]
+    fn junit_report_marks_held_requests_skipped() {
+        let mut held = request("purge", "held", &[]);
+        held.status_code = None;
+        held.errors = vec!["DELETE requires confirmation".to_string()];
+        let mut run = run();
+        run.results.push(held);
+        let xml = to_junit_xml(&run);
+        assert!(xml.contains("<skipped message=\"DELETE requires confirmation\">"));
+        assert!(!xml.contains("<failure message=\"DELETE requires confirmation\">"));
+    }
+
+    #[test]
+    fn html_report_is_escaped_and_lists_every_request() {
+        let html = to_html(&run());
+        assert!(html.contains("<h1>Pets &amp; &lt;Owners&gt;</h1>"));
+        assert!(!html.contains("<Owners>"));
+        assert!(html.contains("✗ expected &quot;id&quot;"));
+        assert!(html.contains("connection refused"));
+        assert_eq!(html.matches("<tr><td class=").count(), 3);
+    }
+}
@@ -0,0 +1,298 @@
+//! Response snapshot ("golden file") testing for the runner.
+//!
+//! The first run stores each endpoint's status and body under
+//! `<collection>/snapshots/<endpointId>.json`; later runs compare against the
+//! stored file and report every difference as a failed assertion. Volatile
+//! fields (timestamps, generated ids) are replaced with [`MASKED`] on both
+//! sides before comparing.
+//!
+//! Mask rules are either a bare key name (`createdAt`), which masks that key
+//! at any depth, or a JSON pointer (`/data/items/*/id`) where `*` matches any
+//! array index or object key.
+
+use crate::commands::api_request::ApiResponse;
+use crate::commands::scripts::TestResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+const MASKED: &str = "<masked>";
+/// Differences reported per endpoint before the rest are summarised.
+const MAX_REPORTED_DIFFERENCES: usize = 10;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotOptions {
+    /// Overwrite stored snapshots with the current responses
+    #[serde(default)]
+    pub update: bool,
+    /// Key names or JSON pointers whose values are ignored
+    #[serde(default)]
+    pub mask: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Snapshot {
+    status: Option<u16>,
+    body: Value,
+}
+
+fn mask_pointer(value: &mut Value, segments: &[String]) {
+    let Some((first, rest)) = segments.split_first() else {
+        *value = Value::String(MASKED.to_string());
+        return;
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if first == "*" || first == key {
+                    mask_pointer(child, rest);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                if first == "*" || *first == index.to_string() {
+                    mask_pointer(child, rest);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn mask_key(value: &mut Value, key: &str) {
+    match value {
+        Value::Object(map) => {
+            for (k, child) in map.iter_mut() {
+                if k == key {
+                    *child = Value::String(MASKED.to_string());
+                } else {
+                    mask_key(child, key);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|child| mask_key(child, key)),
+        _ => {}
+    }
+}
+
+/// Apply every mask rule to `value` in place.
+pub(super) fn apply_masks(value: &mut Value, rules: &[String]) {
+    for rule in rules.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        if let Some(pointer) = rule.strip_prefix('/') {
+            let segments: Vec<String> = pointer
+                .split('/')
+                .map(|s| s.replace("~1", "/").replace("~0", "~"))
+                .collect();
+            mask_pointer(value, &segments);
+        } else {
+            mask_key(value, rule);
+        }
+    }
+}
+
+fn preview(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() > 80 {
+        format!("{}…", text.chars().take(80).collect::<String>())
+    } else {
+        text
+    }
+}
+
+/// Differences between `expected` and `actual` as human-readable lines,
+/// each prefixed with the JSON pointer of the differing value.
+pub(super) fn diff_values(expected: &Value, actual: &Value) -> Vec<String> {
+    fn walk(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+        match (expected, actual) {
+            (Value::Object(e), Value::Object(a)) => {
+                for (key, ev) in e {
+                    let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                    match a.get(key) {
+                        Some(av) => walk(&child, ev, av, out),
+                        None => out.push(format!("{}: missing (expected {})", child, preview(ev))),
+                    }
+                }
+                for (key, av) in a {
+                    if !e.contains_key(key) {
+                        let child =
+                            format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                        out.push(format!("{}: unexpected {}", child, preview(av)));
+                    }
+                }
+            }
+            (Value::Array(e), Value::Array(a)) => {
+                for (index, ev) in e.iter().enumerate() {
+                    let child = format!("{}/{}", path, index);
+                    match a.get(index) {
+                        Some(av) => walk(&child, ev, av, out),
+                        None => out.push(format!("{}: missing (expected {})", child, preview(ev))),
+                    }
+                }
+                for (index, av) in a.iter().enumerate().skip(e.len()) {
+                    out.push(format!("{}/{}: unexpected {}", path, index, preview(av)));
+                }
+            }
+            _ if expected != actual => out.push(format!(
+                "{}: expected {}, got {}",
+                if path.is_empty() { "/" } else { path },
+                preview(expected),
+                preview(actual)
+            )),
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk("", expected, actual, &mut out);
+    out
+}
+
+fn snapshot_path(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+) -> Result<PathBuf, String> {
+    let dir = crate::commands::collections::resolve_collection_dir(app, collection_id)?
+        .ok_or_else(|| format!("Collection {} not found", collection_id))?;
+    Ok(dir.join(SNAPSHOTS_DIR).join(format!(
+        "{}.json",
+        crate::commands::collections::sanitize_file_component(endpoint_id)
+    )))
+}
+
+/// Compare (or record) the snapshot for one response and return the
+/// assertions to add to the request result.
+pub(super) fn check_snapshot(
+    app: &AppHandle,
+    collection_id: &str,
+    endpoint_id: &str,
+    response: &ApiResponse,
+    options: &SnapshotOptions,
+) -> Result<Vec<TestResult>, String> {
+    let mut current = Snapshot {
+        status: response.status,
+        body: response.data.clone().unwrap_or(Value::Null),
+    };
+    apply_masks(&mut current.body, &options.mask);
+
+    let path = snapshot_path(app, collection_id, endpoint_id)?;
+    let stored = if options.update || !path.exists() {
+        None
+    } else {
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read snapshot: {}", e))?;
+        Some(
+            serde_json::from_str::<Snapshot>(&content)
+                .map_err(|e| format!("Failed to parse snapshot: {}", e))?,
+        )
+    };
+
+    let Some(mut expected) = stored else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(&current)
+            .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+        return Ok(vec![TestResult {
+            passed: true,
+            message: "Snapshot recorded".to_string(),
+        }]);
+    };
+    // Masks may have been added since the snapshot was recorded
+    apply_masks(&mut expected.body, &options.mask);
+
+    let mut differences = Vec::new();
+    if expected.status != current.status {
+        differences.push(format!(
+            "status: expected {}, got {}",
+            expected
+                .status
+                .map_or("none".to_string(), |s| s.to_string()),
+            current.status.map_or("none".to_string(), |s| s.to_string())
+        ));
+    }
+    differences.extend(diff_values(&expected.body, &current.body));
+
+    if differences.is_empty() {
+        return Ok(vec![TestResult {
+            passed: true,
+            message: "Response matches snapshot".to_string(),
+        }]);
+    }
+
+    let extra = differences.len().saturating_sub(MAX_REPORTED_DIFFERENCES);
+    let mut results: Vec<TestResult> = differences
+        .into_iter()
+        .take(MAX_REPORTED_DIFFERENCES)
+        .map(|d| TestResult {
+            passed: false,
+            message: format!("Snapshot mismatch at {}", d),
+        })
+        .collect();
+    if extra > 0 {
+        results.push(TestResult {
+            passed: false,
+            message: format!("Snapshot mismatch: {} more difference(s)", extra),
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masks_keys_at_any_depth_and_pointer_wildcards() {
+        let mut body = json!({
+            "id": 1,
+            "createdAt": "2024-01-01",
+            "items": [
+                { "id": 10, "name": "a", "meta": { "createdAt": "x" } },
+                { "id": 11, "name": "b" }
+            ]
+        });
+        apply_masks(
+            &mut body,
+            &["createdAt".to_string(), "/items/*/id".to_string()],
+        );
+        assert_eq!(
+            body,
+            json!({
+                "id": 1,
+                "createdAt": MASKED,
+                "items": [
+                    { "id": MASKED, "name": "a", "meta": { "createdAt": MASKED } },
+                    { "id": MASKED, "name": "b" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn diff_reports_changed_missing_and_unexpected_values_by_pointer() {
+        let expected = json!({ "name": "Ada", "tags": ["a", "b"], "a/b": 1 });
+        let actual = json!({ "name": "Grace", "tags": ["a"], "a/b": 1, "extra": true });
+        let diff = diff_values(&expected, &actual);
+        assert_eq!(
+            diff,
+            vec![
+                "/name: expected \"Ada\", got \"Grace\"",
+                "/tags/1: missing (expected \"b\")",
+                "/extra: unexpected true",
+            ]
+        );
+        assert!(diff_values(&expected, &expected).is_empty());
+        assert_eq!(
+            diff_values(&json!("x"), &json!("y")),
+            vec!["/: expected \"x\", got \"y\""]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Tag expressions for selecting endpoints in a run, e.g. `smoke && !slow`
+//! or `(users || orders) && !flaky`.
+//!
+//! Operators are `!`, `&&` and `||` (in decreasing precedence) plus
+//! parentheses; tags are matched case-insensitively.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum TagExpr {
+    Tag(String),
+    Not(Box<TagExpr>),
+    And(Box<TagExpr>, Box<TagExpr>),
+    Or(Box<TagExpr>, Box<TagExpr>),
+}
+
+impl TagExpr {
+    pub(super) fn parse(input: &str) -> Result<TagExpr, String> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("Tag expression is empty".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(format!("Unexpected '{}' in tag expression", token));
+        }
+        Ok(expr)
+    }
+
+    pub(super) fn matches(&self, tags: &[String]) -> bool {
+        match self {
+            TagExpr::Tag(tag) => tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            TagExpr::Not(inner) => !inner.matches(tags),
+            TagExpr::And(a, b) => a.matches(tags) && b.matches(tags),
+            TagExpr::Or(a, b) => a.matches(tags) || b.matches(tags),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Tag(String),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Tag(tag) => write!(f, "{}", tag),
+            Token::Not => write!(f, "!"),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return Err(format!("Expected '{c}{c}' in tag expression"));
+                }
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+            }
+            c if is_tag_char(c) => {
+                let mut tag = String::new();
+                while let Some(&c) = chars.peek().filter(|c| is_tag_char(**c)) {
+                    tag.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Tag(tag));
+            }
+            other => return Err(format!("Invalid character '{}' in tag expression", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<TagExpr, String> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = TagExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<TagExpr, String> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = TagExpr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<TagExpr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(TagExpr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("Missing ')' in tag expression".to_string()),
+                }
+            }
+            Some(Token::Tag(tag)) => Ok(TagExpr::Tag(tag)),
+            Some(token) => Err(format!("Unexpected '{}' in tag expression", token)),
+            None => Err("Unexpected end of tag expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn evaluates_with_precedence_and_parentheses() {
+        let expr = TagExpr::parse("smoke && !slow").unwrap();
+        assert!(expr.matches(&tags(&["smoke"])));
+        assert!(expr.matches(&tags(&["SMOKE", "users"])));
+        assert!(!expr.matches(&tags(&["smoke", "slow"])));
+        assert!(!expr.matches(&tags(&[])));
+
+        // && binds tighter than ||
+        let expr = TagExpr::parse("a || b && c").unwrap();
+        assert!(expr.matches(&tags(&["a"])));
+        assert!(!expr.matches(&tags(&["b"])));
+
+        let expr = TagExpr::parse("(a || b) && c").unwrap();
+        assert!(!expr.matches(&tags(&["a"])));
+        assert!(expr.matches(&tags(&["b", "c"])));
+
+        let expr = TagExpr::parse("!!team:payments").unwrap();
+        assert!(expr.matches(&tags(&["team:payments"])));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for input in ["", "a &&", "a & b", "(a || b", "a b", "a || )", "a $ b"] {
+            assert!(TagExpr::parse(input).is_err(), "accepted {:?}", input);
+        }
+    }
+}
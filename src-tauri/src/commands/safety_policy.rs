@@ -0,0 +1,100 @@
+//! Per-collection safety policy: requests using one of the listed methods
+//! against a matching host are held back until the user confirms them, so a
+//! DELETE meant for staging cannot silently hit production.
+
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SafetyPolicy {
+    /// HTTP methods that need confirmation, e.g. `["DELETE", "PUT"]`
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// Host patterns where `*` matches any run of characters (`prod-*`,
+    /// `*.example.com`). An empty list matches every host.
+    #[serde(default)]
+    pub host_patterns: Vec<String>,
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return false;
+    }
+    let source = format!("^{}$", regex::escape(pattern).replace(r"\*", ".*"));
+    RegexBuilder::new(&source)
+        .case_insensitive(true)
+        .build()
+        .is_ok_and(|re| re.is_match(host))
+}
+
+impl SafetyPolicy {
+    /// Why a `method` request to `url` must be confirmed first, or `None` when
+    /// it can be sent right away.
+    pub fn confirmation_reason(&self, method: &str, url: &str) -> Option<String> {
+        let method = method.to_ascii_uppercase();
+        if !self
+            .methods
+            .iter()
+            .any(|m| m.trim().eq_ignore_ascii_case(&method))
+        {
+            return None;
+        }
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_string))
+            .unwrap_or_default();
+        if self.host_patterns.is_empty() {
+            return Some(format!(
+                "This collection asks for confirmation before every {} request.",
+                method
+            ));
+        }
+        let pattern = self
+            .host_patterns
+            .iter()
+            .find(|pattern| host_matches(pattern, &host))?;
+        Some(format!(
+            "{} to {} needs confirmation: the collection's safety policy covers {}.",
+            method,
+            host,
+            pattern.trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_listed_methods_on_matching_hosts() {
+        let policy = SafetyPolicy {
+            methods: vec!["DELETE".to_string(), "put".to_string()],
+            host_patterns: vec!["prod-*".to_string(), "*.example.com".to_string()],
+        };
+
+        let reason = policy
+            .confirmation_reason("delete", "https://prod-eu.internal/users/1")
+            .unwrap();
+        assert!(reason.contains("prod-eu.internal"));
+        assert!(policy
+            .confirmation_reason("PUT", "https://API.example.com/orders")
+            .is_some());
+        assert!(policy
+            .confirmation_reason("GET", "https://prod-eu.internal/users")
+            .is_none());
+        assert!(policy
+            .confirmation_reason("DELETE", "https://staging-prod.internal/users/1")
+            .is_none());
+
+        let every_host = SafetyPolicy {
+            methods: vec!["DELETE".to_string()],
+            host_patterns: vec![],
+        };
+        assert!(every_host
+            .confirmation_reason("DELETE", "http://localhost:3000/x")
+            .is_some());
+    }
+}
@@ -0,0 +1,465 @@
+//! Response schema inference: a JSON Schema drawn from the responses an
+//! endpoint actually returned (its recorded history and saved examples) and
+//! written into the endpoint's `responses`, the OpenAPI response objects a
+//! collection keeps per endpoint.
+//!
+//! OpenAPI exports then describe the response bodies, and collections created
+//! by hand get schemas the mock server and contract checks can work from. The
+//! schemas use OpenAPI 3.0 conventions: `nullable` for values that were null
+//! in some responses, `anyOf` for values seen with several types.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::collections::{collection_get, persist_collection};
+use super::endpoint_examples::read_examples;
+use super::import_export::is_json_media_type;
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+/// Recorded responses of the endpoint looked at, newest first.
+const MAX_HISTORY_SAMPLES: usize = 50;
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+/// A JSON response body of the endpoint.
+#[derive(Debug)]
+struct Sample {
+    status: u16,
+    media_type: String,
+    body: Value,
+}
+
+/// The schema written for one status and media type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferredResponse {
+    pub status: u16,
+    pub media_type: String,
+    /// Number of responses the schema was drawn from
+    pub samples: usize,
+    pub schema: Value,
+}
+
+/// What the values seen at one place of the bodies looked like.
+#[derive(Debug, Default)]
+struct Shape {
+    nullable: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    /// Set once a string was seen: the format all strings so far share
+    string: Option<Option<&'static str>>,
+    /// Shape of the items, once an array was seen
+    items: Option<Box<Shape>>,
+    object: Option<ObjectShape>,
+}
+
+#[derive(Debug, Default)]
+struct ObjectShape {
+    /// Number of objects seen
+    count: usize,
+    /// Properties in the order first seen, with the number of objects that
+    /// had them
+    properties: Vec<(String, Shape, usize)>,
+}
+
+/// The format of a string, for the formats that are unambiguous enough to
+/// hold across responses.
+fn string_format(s: &str) -> Option<&'static str> {
+    if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+        Some("date-time")
+    } else if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        Some("date")
+    } else if s.len() == 36 && uuid::Uuid::parse_str(s).is_ok() {
+        Some("uuid")
+    } else if (s.starts_with("http://") || s.starts_with("https://")) && url::Url::parse(s).is_ok()
+    {
+        Some("uri")
+    } else {
+        None
+    }
+}
+
+fn typed(name: &str) -> Map<String, Value> {
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), json!(name));
+    schema
+}
+
+impl Shape {
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.nullable = true,
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(n) if n.is_i64() || n.is_u64() => self.integer = true,
+            Value::Number(_) => self.number = true,
+            Value::String(s) => {
+                let format = string_format(s);
+                self.string = Some(match self.string {
+                    None => format,
+                    Some(seen) => seen.filter(|seen| Some(*seen) == format),
+                });
+            }
+            Value::Array(items) => {
+                let shape = self.items.get_or_insert_with(Box::default);
+                for item in items {
+                    shape.add(item);
+                }
+            }
+            Value::Object(map) => self.object.get_or_insert_with(Default::default).add(map),
+        }
+    }
+
+    fn schema(&self) -> Value {
+        let mut variants = Vec::new();
+        if self.boolean {
+            variants.push(typed("boolean"));
+        }
+        // Integers are numbers too, so a mix is a number
+        if self.number {
+            variants.push(typed("number"));
+        } else if self.integer {
+            variants.push(typed("integer"));
+        }
+        if let Some(format) = self.string {
+            let mut schema = typed("string");
+            if let Some(format) = format {
+                schema.insert("format".to_string(), json!(format));
+            }
+            variants.push(schema);
+        }
+        if let Some(items) = &self.items {
+            let mut schema = typed("array");
+            schema.insert("items".to_string(), items.schema());
+            variants.push(schema);
+        }
+        if let Some(object) = &self.object {
+            variants.push(object.schema());
+        }
+
+        if self.nullable {
+            if variants.is_empty() {
+                return json!({ "nullable": true });
+            }
+            for schema in variants.iter_mut() {
+                schema.insert("nullable".to_string(), Value::Bool(true));
+            }
+        }
+        match variants.len() {
+            0 => json!({}),
+            1 => Value::Object(variants.remove(0)),
+            _ => json!({ "anyOf": variants }),
+        }
+    }
+}
+
+impl ObjectShape {
+    fn add(&mut self, map: &Map<String, Value>) {
+        self.count += 1;
+        for (key, value) in map {
+            let index = match self.properties.iter().position(|(name, ..)| name == key) {
+                Some(index) => index,
+                None => {
+                    self.properties.push((key.clone(), Shape::default(), 0));
+                    self.properties.len() - 1
+                }
+            };
+            let (_, shape, seen) = &mut self.properties[index];
+            shape.add(value);
+            *seen += 1;
+        }
+    }
+
+    /// Properties present in every object seen are required.
+    fn schema(&self) -> Map<String, Value> {
+        let mut schema = typed("object");
+        let properties: Map<String, Value> = self
+            .properties
+            .iter()
+            .map(|(name, shape, _)| (name.clone(), shape.schema()))
+            .collect();
+        let required: Vec<&str> = self
+            .properties
+            .iter()
+            .filter(|(_, _, seen)| *seen == self.count)
+            .map(|(name, ..)| name.as_str())
+            .collect();
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), json!(required));
+        }
+        schema
+    }
+}
+
+/// A schema every one of `bodies` matches.
+fn infer_schema<'a>(bodies: impl IntoIterator<Item = &'a Value>) -> Value {
+    let mut shape = Shape::default();
+    for body in bodies {
+        shape.add(body);
+    }
+    shape.schema()
+}
+
+/// One schema per status and media type, in the order first seen.
+fn infer_responses(samples: &[Sample]) -> Vec<InferredResponse> {
+    let mut groups: Vec<(u16, &str, Vec<&Value>)> = Vec::new();
+    for sample in samples {
+        match groups.iter_mut().find(|(status, media_type, _)| {
+            *status == sample.status && *media_type == sample.media_type
+        }) {
+            Some((_, _, bodies)) => bodies.push(&sample.body),
+            None => groups.push((
+                sample.status,
+                sample.media_type.as_str(),
+                vec![&sample.body],
+            )),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(status, media_type, bodies)| InferredResponse {
+            status,
+            media_type: media_type.to_string(),
+            samples: bodies.len(),
+            schema: infer_schema(bodies),
+        })
+        .collect()
+}
+
+/// The media type of a `Content-Type` value, without parameters.
+fn media_type_of(content_type: Option<&str>) -> String {
+    content_type
+        .and_then(|value| value.split(';').next())
+        .map(|mime| mime.trim().to_lowercase())
+        .filter(|mime| !mime.is_empty())
+        .unwrap_or_else(|| DEFAULT_MEDIA_TYPE.to_string())
+}
+
+/// JSON bodies of the endpoint's recorded responses (`{ status, headers,
+/// data }` entries of the history, which is kept newest first).
+fn history_samples(history: &Value, collection_id: &str, endpoint_id: &str) -> Vec<Sample> {
+    history
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .pointer("/request/collectionId")
+                .and_then(Value::as_str)
+                == Some(collection_id)
+                && entry.pointer("/request/endpointId").and_then(Value::as_str) == Some(endpoint_id)
+        })
+        .filter_map(|entry| {
+            let response = entry.get("response")?;
+            let status = u16::try_from(response.get("status")?.as_u64()?).ok()?;
+            let content_type = response
+                .get("headers")
+                .and_then(Value::as_object)
+                .and_then(|headers| {
+                    headers
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                })
+                .and_then(|(_, value)| value.as_str());
+            let media_type = media_type_of(content_type);
+            if !is_json_media_type(&media_type) {
+                return None;
+            }
+            // History keeps JSON bodies parsed, but text that happens to be JSON too
+            let body = match response.get("data")? {
+                Value::String(text) => serde_json::from_str(text).ok()?,
+                data => data.clone(),
+            };
+            Some(Sample {
+                status,
+                media_type,
+                body,
+            })
+        })
+        .take(MAX_HISTORY_SAMPLES)
+        .collect()
+}
+
+/// JSON bodies of the examples saved on the endpoint.
+fn example_samples(collection_dir: &Path, endpoint_id: &str) -> Vec<Sample> {
+    read_examples(collection_dir, endpoint_id)
+        .into_iter()
+        .filter_map(|example| {
+            let media_type = media_type_of(example.content_type().as_deref());
+            if !is_json_media_type(&media_type) {
+                return None;
+            }
+            Some(Sample {
+                status: example.status,
+                body: serde_json::from_str(&example.body).ok()?,
+                media_type,
+            })
+        })
+        .collect()
+}
+
+/// Set the inferred schemas on `endpoint`, keeping the descriptions, examples
+/// and other media types its responses already have.
+fn write_schemas(endpoint: &mut Value, inferred: &[InferredResponse]) {
+    if !endpoint["responses"].is_object() {
+        endpoint["responses"] = json!({});
+    }
+    for response in inferred {
+        let entry = &mut endpoint["responses"][response.status.to_string()];
+        if !entry.is_object() {
+            *entry = json!({});
+        }
+        if entry.get("description").is_none() {
+            let description = reqwest::StatusCode::from_u16(response.status)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or("Response");
+            entry["description"] = json!(description);
+        }
+        if !entry["content"].is_object() {
+            entry["content"] = json!({});
+        }
+        let media = &mut entry["content"][response.media_type.as_str()];
+        if !media.is_object() {
+            *media = json!({});
+        }
+        media["schema"] = response.schema.clone();
+    }
+}
+
+/// Infer response schemas from the endpoint's recorded responses and saved
+/// examples and write them into its `responses`, replacing the schemas it had
+/// for the same statuses and media types. Returns what was written.
+#[tauri::command]
+pub async fn endpoint_infer_response_schema(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<Vec<InferredResponse>, String> {
+    let mut collection = collection_get(app.clone(), collection_id.clone()).await?;
+
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+    let history = store.get(HISTORY_KEY).unwrap_or(Value::Null);
+    let mut samples = history_samples(&history, &collection_id, &endpoint_id);
+    if let Some(dir) = collection.storage_path.as_deref() {
+        samples.extend(example_samples(Path::new(dir), &endpoint_id));
+    }
+    if samples.is_empty() {
+        return Err(
+            "No JSON response recorded for this endpoint yet. Send the request or save an example first."
+                .to_string(),
+        );
+    }
+    let inferred = infer_responses(&samples);
+
+    // Folder endpoints are also listed at the collection root; update every copy
+    let folder_endpoints = collection
+        .folders
+        .iter_mut()
+        .filter_map(|folder| folder.get_mut("endpoints").and_then(Value::as_array_mut))
+        .flatten();
+    let mut found = false;
+    for endpoint in collection.endpoints.iter_mut().chain(folder_endpoints) {
+        if endpoint.get("id").and_then(Value::as_str) == Some(endpoint_id.as_str()) {
+            write_schemas(endpoint, &inferred);
+            found = true;
+        }
+    }
+    if !found {
+        return Err(format!("Endpoint {} not found", endpoint_id));
+    }
+
+    persist_collection(&app, collection)?;
+    Ok(inferred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::json_schema::validate;
+
+    #[test]
+    fn infers_a_schema_all_samples_match_and_writes_it_into_responses() {
+        let history = json!([
+            { "request": { "collectionId": "c1", "endpointId": "e1" }, "response": {
+                "status": 200,
+                "headers": { "Content-Type": "application/json; charset=utf-8" },
+                "data": { "id": 1, "name": "Rex", "born": "2020-01-02T03:04:05Z",
+                          "tags": ["a"], "owner": null, "score": 1 }
+            }},
+            { "request": { "collectionId": "c1", "endpointId": "e1" }, "response": {
+                "status": 200,
+                "headers": {},
+                "data": "{\"id\": 2, \"name\": \"Tom\", \"born\": \"yesterday\", \"tags\": [], \"owner\": {\"id\": 9}, \"score\": 2.5}"
+            }},
+            { "request": { "collectionId": "c1", "endpointId": "e1" }, "response": {
+                "status": 404,
+                "headers": { "content-type": "application/problem+json" },
+                "data": { "title": "Not found" }
+            }},
+            { "request": { "collectionId": "c1", "endpointId": "e1" }, "response": {
+                "status": 200, "headers": { "content-type": "text/html" }, "data": "<p>hi</p>"
+            }},
+            { "request": { "collectionId": "c1", "endpointId": "e2" }, "response": {
+                "status": 500, "headers": {}, "data": { "oops": true }
+            }}
+        ]);
+
+        let samples = history_samples(&history, "c1", "e1");
+        assert_eq!(samples.len(), 3);
+        let inferred = infer_responses(&samples);
+        assert_eq!(inferred.len(), 2);
+        assert_eq!(inferred[0].status, 200);
+        assert_eq!(inferred[0].samples, 2);
+        assert_eq!(inferred[1].media_type, "application/problem+json");
+
+        let schema = &inferred[0].schema;
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["required"],
+            json!(["id", "name", "born", "tags", "owner", "score"])
+        );
+        assert_eq!(schema["properties"]["id"], json!({ "type": "integer" }));
+        assert_eq!(schema["properties"]["score"], json!({ "type": "number" }));
+        assert_eq!(schema["properties"]["born"], json!({ "type": "string" }));
+        assert_eq!(
+            schema["properties"]["tags"]["items"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(schema["properties"]["owner"]["nullable"], true);
+        assert_eq!(schema["properties"]["owner"]["required"], json!(["id"]));
+        for sample in samples.iter().filter(|sample| sample.status == 200) {
+            assert!(validate(&sample.body, schema).is_empty());
+        }
+        assert!(!validate(&json!({ "id": "x" }), schema).is_empty());
+
+        let mixed = infer_schema(&[json!("2024-01-01"), json!(1), json!("2024-02-01")]);
+        assert_eq!(
+            mixed,
+            json!({ "anyOf": [{ "type": "integer" }, { "type": "string", "format": "date" }] })
+        );
+
+        let mut endpoint = json!({
+            "id": "e1",
+            "responses": { "200": {
+                "description": "The pet",
+                "content": { "application/json": { "examples": { "Rex": {} } } }
+            }}
+        });
+        write_schemas(&mut endpoint, &inferred);
+        let ok = &endpoint["responses"]["200"];
+        assert_eq!(ok["description"], "The pet");
+        assert!(ok["content"]["application/json"]["examples"].is_object());
+        assert_eq!(ok["content"]["application/json"]["schema"], *schema);
+        assert_eq!(endpoint["responses"]["404"]["description"], "Not Found");
+        assert_eq!(
+            endpoint["responses"]["404"]["content"]["application/problem+json"]["schema"]
+                ["required"],
+            json!(["title"])
+        );
+    }
+}
@@ -1,3 +1,8 @@
+use super::json_schema;
+use super::proxy::{check_offline, offline_redirect_policy, ProxyState};
+use super::secrets::{mask_secret_text, secret_variable_values};
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
 use boa_engine::object::ObjectInitializer;
 use boa_engine::property::Attribute;
 use boa_engine::{js_string, Context, JsNativeError, JsResult, JsValue, NativeFunction, Source};
@@ -6,12 +11,14 @@ use serde_json::Value;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::time::Duration;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_store::StoreExt;
 
-const STORE_FILE: &str = "resonance-store.json";
 const SCRIPTS_KEY: &str = "persistedScripts";
+const GLOBAL_SCRIPTS_KEY: &str = "globalScripts";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -62,7 +69,7 @@ fn scripts_are_empty(scripts: &ScriptData) -> bool {
 
 /// Read a one-off script entry from the legacy global store.
 fn read_legacy_store_script(app: &AppHandle, key: &str) -> Option<ScriptData> {
-    let store = app.store(STORE_FILE).ok()?;
+    let store = app.store(store_file(app)).ok()?;
     let map: HashMap<String, ScriptData> = store
         .get(SCRIPTS_KEY)
         .and_then(|v| serde_json::from_value(v).ok())
@@ -74,7 +81,8 @@ fn read_legacy_store_script(app: &AppHandle, key: &str) -> Option<ScriptData> {
 /// store. Best-effort: errors are swallowed because the authoritative copy
 /// already lives in the per-endpoint file.
 fn remove_store_script_entry(app: &AppHandle, collection_id: &str, endpoint_id: &str) {
-    let Ok(store) = app.store(STORE_FILE) else {
+    let _guard = lock_store();
+    let Ok(store) = app.store(store_file(app)) else {
         return;
     };
     let mut map: HashMap<String, Value> = store
@@ -84,14 +92,16 @@ fn remove_store_script_entry(app: &AppHandle, collection_id: &str, endpoint_id:
     let key = format!("{}_{}", collection_id, endpoint_id);
     if map.remove(&key).is_some() {
         store.set(SCRIPTS_KEY.to_string(), serde_json::to_value(map).unwrap());
-        let _ = store.save();
+        let _ = save_store(app);
     }
 }
 
 /// Drop every script entry keyed by the given collection from the legacy store.
-/// Called by `collection_delete` to keep the store from accumulating orphans.
+/// Called when a trashed collection is purged, to keep the store from
+/// accumulating orphans.
 pub(crate) fn purge_store_scripts_for_collection(app: &AppHandle, collection_id: &str) {
-    let Ok(store) = app.store(STORE_FILE) else {
+    let _guard = lock_store();
+    let Ok(store) = app.store(store_file(app)) else {
         return;
     };
     let mut map: HashMap<String, Value> = store
@@ -103,7 +113,7 @@ pub(crate) fn purge_store_scripts_for_collection(app: &AppHandle, collection_id:
     map.retain(|k, _| !k.starts_with(&prefix));
     if map.len() != before {
         store.set(SCRIPTS_KEY.to_string(), serde_json::to_value(map).unwrap());
-        let _ = store.save();
+        let _ = save_store(app);
     }
 }
 
@@ -199,6 +209,8 @@ struct ScriptContext {
     request: Value,
     response: Option<Value>,
     environment: HashMap<String, String>,
+    /// Offline mode, which `sendRequest` respects like every other request
+    offline: Arc<AtomicBool>,
 }
 
 /// Execute a JavaScript script in a sandboxed environment.
@@ -224,7 +236,15 @@ fn execute_script(
     setup_pm(&mut context, pm_ctx)?;
 
     // Setup sendRequest (must come after pm so the glue can attach pm.sendRequest)
-    setup_send_request(&mut context)?;
+    let offline = ctx.borrow().offline.clone();
+    setup_send_request(&mut context, offline)?;
+
+    // Setup JSON schema validation (must come after pm for pm.response.to.matchSchema)
+    setup_schema_validation(&mut context)?;
+
+    // Setup header lookup and response time (must come after pm, which
+    // replaces the response global)
+    setup_response_helpers(&mut context)?;
 
     let baseline = if capture_request {
         stringify_request_global(&mut context).ok().flatten()
@@ -530,6 +550,34 @@ fn setup_jest(context: &mut Context, ctx: Rc<RefCell<ScriptContext>>) -> Result<
                         if (this._not) pass = !pass;
                         var msg = "Expected object" + (this._not ? " not " : " ") + "to have property " + key + (arguments.length > 1 ? " with value " + JSON.stringify(value) : "");
                         recordResult(pass, msg);
+                    },
+                    toHaveHeader: function(name, value) {
+                        var headers = this._actual && typeof this._actual.headers === 'object' ? this._actual.headers : this._actual;
+                        var actualValue;
+                        for (var key in headers || {}) {
+                            if (key.toLowerCase() === String(name).toLowerCase()) { actualValue = headers[key]; break; }
+                        }
+                        var pass = actualValue !== undefined;
+                        if (pass && arguments.length > 1) {
+                            pass = value instanceof RegExp ? value.test(actualValue) : String(actualValue) === String(value);
+                        }
+                        if (this._not) pass = !pass;
+                        var msg = "Expected response" + (this._not ? " not " : " ") + "to have header " + name + (arguments.length > 1 ? " with value " + (value instanceof RegExp ? value : JSON.stringify(value)) : "") + (actualValue !== undefined ? " (got " + JSON.stringify(actualValue) + ")" : "");
+                        recordResult(pass, msg);
+                    },
+                    toRespondWithin: function(ms) {
+                        var time = typeof this._actual === 'number' ? this._actual : (this._actual ? this._actual.responseTime : undefined);
+                        var pass = typeof time === 'number' && time <= ms;
+                        if (this._not) pass = !pass;
+                        var msg = "Expected response time " + time + "ms" + (this._not ? " not " : " ") + "to be within " + ms + "ms";
+                        recordResult(pass, msg);
+                    },
+                    toMatchSchema: function(schema) {
+                        var errors = JSON.parse(__validateSchema__(JSON.stringify(this._actual === undefined ? null : this._actual), JSON.stringify(schema)));
+                        var pass = errors.length === 0;
+                        if (this._not) pass = !pass;
+                        var msg = "Expected value" + (this._not ? " not " : " ") + "to match schema" + (errors.length > 0 ? ": " + errors.slice(0, 5).join("; ") : "");
+                        recordResult(pass, msg);
                     }
                 };
             }
@@ -833,7 +881,11 @@ struct SendRequestResponse {
 /// thread): the future is driven with `Handle::block_on`, which panics on
 /// async worker threads. Outside any tokio runtime (unit tests) a one-off
 /// current-thread runtime is created instead.
-fn perform_send_request(options: SendRequestOptions) -> Result<SendRequestResponse, String> {
+fn perform_send_request(
+    options: SendRequestOptions,
+    offline: Arc<AtomicBool>,
+) -> Result<SendRequestResponse, String> {
+    check_offline(&offline, &options.url).map_err(|e| format!("sendRequest: {}", e))?;
     let timeout_ms = options.timeout.unwrap_or(10_000).min(60_000);
     let method_str = options
         .method
@@ -846,6 +898,7 @@ fn perform_send_request(options: SendRequestOptions) -> Result<SendRequestRespon
     let fut = async move {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_millis(timeout_ms))
+            .redirect(offline_redirect_policy(offline))
             .build()
             .map_err(|e| format!("sendRequest: {}", e))?;
 
@@ -908,11 +961,7 @@ fn perform_send_request(options: SendRequestOptions) -> Result<SendRequestRespon
 /// Native backend for the JS `sendRequest` global. Takes an options JSON
 /// string and returns a response JSON string; failures become catchable JS
 /// errors so scripts can try/catch them.
-fn send_request_raw_native(
-    _this: &JsValue,
-    args: &[JsValue],
-    _context: &mut Context,
-) -> JsResult<JsValue> {
+fn send_request_raw_native(args: &[JsValue], offline: Arc<AtomicBool>) -> JsResult<JsValue> {
     let options_json = args
         .first()
         .and_then(|v| v.as_string())
@@ -925,8 +974,8 @@ fn send_request_raw_native(
         JsNativeError::typ().with_message(format!("sendRequest: invalid options: {}", e))
     })?;
 
-    let response =
-        perform_send_request(options).map_err(|e| JsNativeError::error().with_message(e))?;
+    let response = perform_send_request(options, offline)
+        .map_err(|e| JsNativeError::error().with_message(e))?;
 
     let json = serde_json::to_string(&response).map_err(|e| {
         JsNativeError::error()
@@ -939,13 +988,14 @@ fn send_request_raw_native(
 /// Register the native HTTP bridge plus the `sendRequest` / `pm.sendRequest`
 /// JS wrapper. Accepts a URL string or an options object; a Postman-style
 /// callback is supported and invoked synchronously.
-fn setup_send_request(context: &mut Context) -> Result<(), String> {
+fn setup_send_request(context: &mut Context, offline: Arc<AtomicBool>) -> Result<(), String> {
+    let send_fn = unsafe {
+        NativeFunction::from_closure(move |_, args, _| {
+            send_request_raw_native(args, offline.clone())
+        })
+    };
     context
-        .register_global_callable(
-            js_string!("__sendRequestRaw__"),
-            1,
-            NativeFunction::from_fn_ptr(send_request_raw_native),
-        )
+        .register_global_callable(js_string!("__sendRequestRaw__"), 1, send_fn)
         .map_err(|e| e.to_string())?;
 
     let glue_code = r#"
@@ -1013,11 +1063,125 @@ fn setup_send_request(context: &mut Context) -> Result<(), String> {
     Ok(())
 }
 
+/// Native backend for `toMatchSchema` / `pm.response.to.matchSchema`. Takes
+/// the instance and schema as JSON strings and returns a JSON array of error
+/// messages (empty when the instance is valid).
+fn validate_schema_native(
+    _this: &JsValue,
+    args: &[JsValue],
+    _context: &mut Context,
+) -> JsResult<JsValue> {
+    let parse_arg = |index: usize, name: &str| -> JsResult<Value> {
+        let raw = args
+            .get(index)
+            .and_then(|v| v.as_string())
+            .map(|s| s.to_std_string_escaped())
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(format!("matchSchema: expected {} JSON", name))
+            })?;
+        serde_json::from_str(&raw).map_err(|e| {
+            JsNativeError::typ().with_message(format!("matchSchema: invalid {}: {}", name, e))
+        })
+    };
+
+    let instance = parse_arg(0, "value")?;
+    let schema = parse_arg(1, "schema")?;
+    if !schema.is_object() && !schema.is_boolean() {
+        return Err(JsNativeError::typ()
+            .with_message("matchSchema: schema must be an object")
+            .into());
+    }
+
+    let errors: Vec<String> = json_schema::validate(&instance, &schema)
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    let json = serde_json::to_string(&errors).unwrap_or_else(|_| "[]".to_string());
+
+    Ok(JsValue::from(js_string!(json)))
+}
+
+/// Register the native schema validator and attach `pm.response.to.matchSchema`,
+/// which validates the (JSON-decoded) response body.
+fn setup_schema_validation(context: &mut Context) -> Result<(), String> {
+    context
+        .register_global_callable(
+            js_string!("__validateSchema__"),
+            2,
+            NativeFunction::from_fn_ptr(validate_schema_native),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let glue_code = r#"
+        (function() {
+            if (typeof pm !== 'object' || pm === null || typeof pm.response !== 'object' || pm.response === null) {
+                return;
+            }
+            function responseJson() {
+                var body = pm.response.body;
+                if (typeof body === 'string') {
+                    try { return JSON.parse(body); } catch (e) { return body; }
+                }
+                return body === undefined ? null : body;
+            }
+            pm.response.json = pm.response.json || responseJson;
+            pm.response.to = pm.response.to || {};
+            pm.response.to.matchSchema = function(schema) {
+                expect(responseJson()).toMatchSchema(schema);
+            };
+        })()
+    "#;
+
+    context
+        .eval(Source::from_bytes(glue_code.as_bytes()))
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Give the `response` global a case-insensitive `headers.get(name)` and a
+/// `responseTime` in milliseconds, taken from the total timing.
+fn setup_response_helpers(context: &mut Context) -> Result<(), String> {
+    let glue_code = r#"
+        (function() {
+            if (typeof response !== 'object' || response === null) {
+                return;
+            }
+            if (typeof response.headers !== 'object' || response.headers === null) {
+                response.headers = {};
+            }
+            var headers = response.headers;
+            Object.defineProperty(headers, 'get', {
+                enumerable: false,
+                value: function(name) {
+                    for (var key in headers) {
+                        if (key.toLowerCase() === String(name).toLowerCase()) { return headers[key]; }
+                    }
+                    return undefined;
+                }
+            });
+            if (typeof response.responseTime !== 'number' && response.timings && typeof response.timings.total === 'number') {
+                response.responseTime = response.timings.total;
+            }
+        })()
+    "#;
+
+    context
+        .eval(Source::from_bytes(glue_code.as_bytes()))
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 /// Build the script context, execute the script, and assemble the result.
 /// Runs synchronously; callers must invoke it from a blocking thread because
 /// `sendRequest` drives its HTTP future with `Handle::block_on`, which panics
 /// on async worker threads.
-fn run_script_sync(script_data: ScriptExecutionData, capture_request: bool) -> ScriptResult {
+fn run_script_sync(
+    script_data: ScriptExecutionData,
+    capture_request: bool,
+    offline: Arc<AtomicBool>,
+) -> ScriptResult {
     let ctx = Rc::new(RefCell::new(ScriptContext {
         logs: Vec::new(),
         test_results: Vec::new(),
@@ -1025,6 +1189,7 @@ fn run_script_sync(script_data: ScriptExecutionData, capture_request: bool) -> S
         request: script_data.request,
         response: script_data.response,
         environment: script_data.environment,
+        offline,
     }));
 
     let result = execute_script(&script_data.script, ctx.clone(), capture_request);
@@ -1046,42 +1211,150 @@ fn run_script_sync(script_data: ScriptExecutionData, capture_request: bool) -> S
     }
 }
 
+/// Read the workspace-level scripts that wrap every request's own scripts.
+pub(crate) fn read_global_scripts(app: &AppHandle) -> ScriptData {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(GLOBAL_SCRIPTS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or(ScriptData {
+            pre_request_script: String::new(),
+            test_script: String::new(),
+        })
+}
+
 #[tauri::command]
-pub async fn script_execute_pre_request(
+pub async fn global_scripts_get(app: AppHandle) -> Result<ScriptData, String> {
+    Ok(read_global_scripts(&app))
+}
+
+#[tauri::command]
+pub async fn global_scripts_set(app: AppHandle, scripts: ScriptData) -> Result<(), String> {
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
+    store.set(
+        GLOBAL_SCRIPTS_KEY.to_string(),
+        serde_json::to_value(&scripts).map_err(|e| e.to_string())?,
+    );
+    save_store(&app)
+}
+
+/// Run several scripts back to back against the same request. Each script
+/// sees the request and environment as left by the previous one; logs,
+/// errors and test results are concatenated. A failing script does not stop
+/// the chain, so a broken global hook never hides the endpoint's own results.
+fn run_script_chain_sync(
+    scripts: Vec<String>,
     script_data: ScriptExecutionData,
-) -> Result<ScriptResult, String> {
-    if script_data.script.trim().is_empty() {
-        return Ok(ScriptResult {
-            success: true,
-            logs: Vec::new(),
-            errors: Vec::new(),
-            test_results: Vec::new(),
-            modified_request: Some(script_data.request),
-            modified_environment: HashMap::new(),
-        });
+    capture_request: bool,
+    offline: Arc<AtomicBool>,
+) -> ScriptResult {
+    let mut request = script_data.request;
+    let mut environment = script_data.environment;
+    let mut combined = ScriptResult {
+        success: true,
+        logs: Vec::new(),
+        errors: Vec::new(),
+        test_results: Vec::new(),
+        modified_request: capture_request.then(|| request.clone()),
+        modified_environment: HashMap::new(),
+    };
+
+    for script in scripts.into_iter().filter(|s| !s.trim().is_empty()) {
+        let result = run_script_sync(
+            ScriptExecutionData {
+                script,
+                request: request.clone(),
+                response: script_data.response.clone(),
+                environment: environment.clone(),
+            },
+            capture_request,
+            offline.clone(),
+        );
+
+        for (key, value) in &result.modified_environment {
+            match value {
+                Some(v) => environment.insert(key.clone(), v.clone()),
+                None => environment.remove(key),
+            };
+        }
+        if let Some(modified) = &result.modified_request {
+            request = modified.clone();
+        }
+
+        combined.success &= result.success;
+        combined.logs.extend(result.logs);
+        combined.errors.extend(result.errors);
+        combined.test_results.extend(result.test_results);
+        combined.modified_request = result.modified_request.or(combined.modified_request);
+        combined
+            .modified_environment
+            .extend(result.modified_environment);
     }
 
-    tokio::task::spawn_blocking(move || run_script_sync(script_data, true))
-        .await
-        .map_err(|e| format!("Script execution failed: {}", e))
+    combined
 }
 
-#[tauri::command]
-pub async fn script_execute_test(script_data: ScriptExecutionData) -> Result<ScriptResult, String> {
-    if script_data.script.trim().is_empty() {
-        return Ok(ScriptResult {
-            success: true,
-            logs: Vec::new(),
-            errors: Vec::new(),
-            test_results: Vec::new(),
-            modified_request: None,
-            modified_environment: HashMap::new(),
-        });
+/// Execute a script chain on a blocking thread (see `run_script_sync`).
+/// `offline` is the offline mode flag `sendRequest` checks.
+pub(crate) async fn execute_script_chain(
+    scripts: Vec<String>,
+    script_data: ScriptExecutionData,
+    capture_request: bool,
+    offline: Arc<AtomicBool>,
+) -> Result<ScriptResult, String> {
+    tokio::task::spawn_blocking(move || {
+        run_script_chain_sync(scripts, script_data, capture_request, offline)
+    })
+    .await
+    .map_err(|e| format!("Script execution failed: {}", e))
+}
+
+/// Mask secret variable values in the console output and errors shown in the
+/// script console.
+async fn mask_output_secrets(
+    app: &AppHandle,
+    collection_id: Option<&str>,
+    mut result: ScriptResult,
+) -> ScriptResult {
+    if result.logs.is_empty() && result.errors.is_empty() {
+        return result;
+    }
+    let secrets = secret_variable_values(app, collection_id).await;
+    for log in result.logs.iter_mut() {
+        log.message = mask_secret_text(&log.message, &secrets);
+    }
+    for error in result.errors.iter_mut() {
+        *error = mask_secret_text(error, &secrets);
     }
+    result
+}
 
-    tokio::task::spawn_blocking(move || run_script_sync(script_data, false))
-        .await
-        .map_err(|e| format!("Script execution failed: {}", e))
+/// Run the workspace pre-request script, then the endpoint's own script.
+#[tauri::command]
+pub async fn script_execute_pre_request(
+    app: AppHandle,
+    script_data: ScriptExecutionData,
+    collection_id: Option<String>,
+) -> Result<ScriptResult, String> {
+    let global = read_global_scripts(&app);
+    let scripts = vec![global.pre_request_script, script_data.script.clone()];
+    let offline = app.state::<ProxyState>().offline.clone();
+    let result = execute_script_chain(scripts, script_data, true, offline).await?;
+    Ok(mask_output_secrets(&app, collection_id.as_deref(), result).await)
+}
+
+/// Run the endpoint's test script, then the workspace post-response script.
+#[tauri::command]
+pub async fn script_execute_test(
+    app: AppHandle,
+    script_data: ScriptExecutionData,
+    collection_id: Option<String>,
+) -> Result<ScriptResult, String> {
+    let global = read_global_scripts(&app);
+    let scripts = vec![script_data.script.clone(), global.test_script];
+    let offline = app.state::<ProxyState>().offline.clone();
+    let result = execute_script_chain(scripts, script_data, false, offline).await?;
+    Ok(mask_output_secrets(&app, collection_id.as_deref(), result).await)
 }
 
 #[cfg(test)]
@@ -1374,6 +1647,26 @@ mod tests {
         assert_eq!(env_value(&env, "outcome"), "caught");
     }
 
+    #[test]
+    fn send_request_respects_offline_mode() {
+        let ctx = Rc::new(RefCell::new(ScriptContext {
+            request: default_request(),
+            offline: Arc::new(AtomicBool::new(true)),
+            ..Default::default()
+        }));
+        let script = r#"
+            try {
+                sendRequest("https://api.example.com/orders");
+                environment.set('outcome', 'sent');
+            } catch (e) {
+                environment.set('outcome', e.message);
+            }
+        "#;
+        execute_script(script, ctx.clone(), false).expect("script should execute");
+        let env = ctx.borrow().environment_changes.clone();
+        assert!(env_value(&env, "outcome").contains("Offline mode is on"));
+    }
+
     #[test]
     fn send_request_callback_style_receives_response_and_error() {
         let (url, _handle) = spawn_test_server(
@@ -1487,12 +1780,17 @@ mod tests {
             environment.set('status', String(res.status));
         "#
         );
-        let result = script_execute_test(ScriptExecutionData {
-            script,
-            request: default_request(),
-            response: None,
-            environment: HashMap::new(),
-        })
+        let result = execute_script_chain(
+            vec![script],
+            ScriptExecutionData {
+                script: String::new(),
+                request: default_request(),
+                response: None,
+                environment: HashMap::new(),
+            },
+            false,
+            Arc::default(),
+        )
         .await
         .expect("command should succeed");
         assert!(result.success, "errors: {:?}", result.errors);
@@ -1501,4 +1799,150 @@ mod tests {
             Some(&Some("200".to_string()))
         );
     }
+
+    fn run_test_script(script: &str, response: Value) -> ScriptResult {
+        run_script_sync(
+            ScriptExecutionData {
+                script: script.to_string(),
+                request: default_request(),
+                response: Some(response),
+                environment: HashMap::new(),
+            },
+            false,
+            Arc::default(),
+        )
+    }
+
+    #[test]
+    fn to_match_schema_passes_and_fails_with_error_paths() {
+        let result = run_test_script(
+            r#"
+            var schema = { type: 'object', required: ['id'], properties: { id: { type: 'integer' } } };
+            test('valid', function() { expect({ id: 1 }).toMatchSchema(schema); });
+            test('invalid', function() { expect({ id: 'x' }).toMatchSchema(schema); });
+            test('negated', function() { expect({}).not.toMatchSchema(schema); });
+        "#,
+            json!({}),
+        );
+        assert!(result.success, "errors: {:?}", result.errors);
+        let results = &result.test_results;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+        assert!(
+            results[1]
+                .message
+                .contains("/id: expected type integer but got string"),
+            "message: {}",
+            results[1].message
+        );
+        assert!(results[2].passed);
+    }
+
+    #[test]
+    fn header_and_timing_matchers_read_the_response() {
+        let response = json!({
+            "status": 200,
+            "headers": { "Content-Type": "application/json", "X-Request-Id": "r-1" },
+            "body": {},
+            "timings": { "total": 120 }
+        });
+        let result = run_test_script(
+            r#"
+            test('getter', function() { expect(response.headers.get('content-type')).toBe('application/json'); });
+            test('header', function() { expect(response).toHaveHeader('x-request-id', 'r-1'); });
+            test('pattern', function() { expect(pm.response).toHaveHeader('Content-Type', /json/); });
+            test('missing', function() { expect(response).toHaveHeader('etag'); });
+            test('fast', function() { expect(response).toRespondWithin(200); });
+            test('slow', function() { expect(response.responseTime).toRespondWithin(100); });
+        "#,
+            response,
+        );
+        assert!(result.success, "errors: {:?}", result.errors);
+        let passed: Vec<bool> = result.test_results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, [true, true, true, false, true, false]);
+        assert!(result.test_results[5].message.contains("120ms"));
+    }
+
+    #[test]
+    fn pm_response_to_match_schema_validates_string_body() {
+        let response = json!({
+            "status": 200,
+            "headers": {},
+            "body": "{\"items\":[{\"name\":\"a\"},{\"name\":2}]}"
+        });
+        let result = run_test_script(
+            r#"
+            pm.test('contract', function() {
+                pm.response.to.matchSchema({
+                    type: 'object',
+                    properties: { items: { type: 'array', items: { type: 'object', properties: { name: { type: 'string' } } } } }
+                });
+            });
+        "#,
+            response,
+        );
+        assert!(result.success, "errors: {:?}", result.errors);
+        assert_eq!(result.test_results.len(), 2);
+        assert!(result
+            .test_results
+            .iter()
+            .any(|r| !r.passed && r.message.contains("/items/1/name")));
+        assert!(result
+            .test_results
+            .iter()
+            .any(|r| !r.passed && r.message == "contract"));
+    }
+
+    #[test]
+    fn script_chain_threads_request_and_environment_between_scripts() {
+        let result = run_script_chain_sync(
+            vec![
+                "request.headers['X-Trace'] = 'abc'; environment.set('seen', 'global');"
+                    .to_string(),
+                String::new(),
+                "request.url = request.url + '/' + request.headers['X-Trace']; \
+                 environment.set('endpoint', environment.get('seen'));"
+                    .to_string(),
+            ],
+            ScriptExecutionData {
+                script: String::new(),
+                request: default_request(),
+                response: None,
+                environment: HashMap::new(),
+            },
+            true,
+            Arc::default(),
+        );
+        assert!(result.success, "errors: {:?}", result.errors);
+        let request = result.modified_request.expect("request should be captured");
+        assert_eq!(request["url"], "https://example.com/abc");
+        assert_eq!(
+            result.modified_environment.get("endpoint"),
+            Some(&Some("global".to_string()))
+        );
+    }
+
+    #[test]
+    fn script_chain_keeps_running_after_a_failing_script() {
+        let result = run_script_chain_sync(
+            vec![
+                "throw new Error('global hook broke');".to_string(),
+                "test('still runs', function() { expect(1).toBe(1); });".to_string(),
+            ],
+            ScriptExecutionData {
+                script: String::new(),
+                request: default_request(),
+                response: Some(json!({})),
+                environment: HashMap::new(),
+            },
+            false,
+            Arc::default(),
+        );
+        assert!(!result.success);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.test_results.len(), 1);
+        assert!(result.test_results[0].passed);
+        assert!(result.modified_request.is_none());
+    }
 }
@@ -0,0 +1,236 @@
+//! External secret-manager references.
+//!
+//! A variable may hold `vault://<path>#<key>` (HashiCorp Vault) or
+//! `op://<vault>/<item>/<field>` (1Password) instead of the secret itself. Only
+//! the reference is stored and exported; the value is fetched here, right
+//! before the request is sent, together with the keychain `{{$secret:...}}`
+//! references.
+//!
+//! Vault is read over its HTTP API at `VAULT_ADDR` (default
+//! `http://127.0.0.1:8200`) with `VAULT_TOKEN`, or the token `vault login` left
+//! in `~/.vault-token`; `VAULT_NAMESPACE` is honoured. `<path>` is the API path
+//! below `/v1/`, so a KV v2 secret is `vault://secret/data/app#password`.
+//! 1Password references are read with the `op` CLI, which must be installed and
+//! signed in.
+
+use regex::Regex;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+use super::oauth::token_client;
+use super::proxy::ProxyState;
+
+const DEFAULT_VAULT_ADDR: &str = "http://127.0.0.1:8200";
+
+fn reference_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"vault://[\w.\-/]+#[\w.\-]+|op://[\w.\-]+/[\w.\-]+(?:/[\w.\-]+){1,2}(?:\?attribute=[\w\-]+)?",
+        )
+        .unwrap()
+    })
+}
+
+struct VaultConfig {
+    address: String,
+    token: String,
+    namespace: Option<String>,
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn vault_config(app: &AppHandle) -> Result<VaultConfig, String> {
+    let token = env_var("VAULT_TOKEN")
+        .or_else(|| {
+            let home = app.path().home_dir().ok()?;
+            let token = std::fs::read_to_string(home.join(".vault-token")).ok()?;
+            Some(token.trim().to_string()).filter(|token| !token.is_empty())
+        })
+        .ok_or_else(|| "No Vault token: set VAULT_TOKEN or run `vault login`".to_string())?;
+    Ok(VaultConfig {
+        address: env_var("VAULT_ADDR").unwrap_or_else(|| DEFAULT_VAULT_ADDR.to_string()),
+        token,
+        namespace: env_var("VAULT_NAMESPACE"),
+    })
+}
+
+async fn fetch_vault_secret(
+    client: &Client,
+    config: &VaultConfig,
+    path: &str,
+) -> Result<Value, String> {
+    let url = format!("{}/v1/{}", config.address.trim_end_matches('/'), path);
+    let mut request = client.get(&url).header("X-Vault-Token", &config.token);
+    if let Some(namespace) = &config.namespace {
+        request = request.header("X-Vault-Namespace", namespace);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Vault at {}: {}", config.address, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Vault returned {} for {}", response.status(), path));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Vault response for {}: {}", path, e))
+}
+
+/// A field of a Vault read response. KV v2 nests the fields under
+/// `data.data` (next to `data.metadata`), KV v1 and most other engines put
+/// them directly under `data`.
+fn vault_field(response: &Value, key: &str) -> Option<String> {
+    let data = response.get("data")?;
+    let fields = match data.get("data") {
+        Some(nested @ Value::Object(_)) if data.get("metadata").is_some() => nested,
+        _ => data,
+    };
+    match fields.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+async fn op_read(reference: &str) -> Result<String, String> {
+    let output = tokio::process::Command::new("op")
+        .args(["read", "--no-newline", reference])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run the 1Password CLI (op): {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "op read {} failed: {}",
+            reference,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn collect_references(value: &Value, out: &mut HashSet<String>) {
+    match value {
+        Value::String(s) => {
+            for found in reference_pattern().find_iter(s) {
+                out.insert(found.as_str().to_string());
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_references(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_references(v, out)),
+        _ => {}
+    }
+}
+
+fn substitute_references(value: &mut Value, secrets: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            let replaced = reference_pattern().replace_all(s, |caps: &regex::Captures| {
+                secrets.get(&caps[0]).cloned().unwrap_or_default()
+            });
+            *s = replaced.into_owned();
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| substitute_references(v, secrets)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|v| substitute_references(v, secrets)),
+        _ => {}
+    }
+}
+
+/// True when the strings of `value` hold a `vault://` or `op://` reference.
+pub(crate) fn has_external_references(value: &Value) -> bool {
+    let mut references = HashSet::new();
+    collect_references(value, &mut references);
+    !references.is_empty()
+}
+
+/// Replace every `vault://` and `op://` reference in the strings of `value`
+/// with the secret it points to. Fails when one cannot be read, rather than
+/// sending the reference.
+pub(crate) async fn resolve_external_references(
+    app: &AppHandle,
+    value: &mut Value,
+) -> Result<(), String> {
+    let mut references = HashSet::new();
+    collect_references(value, &mut references);
+    if references.is_empty() {
+        return Ok(());
+    }
+
+    let (vault_references, op_references): (Vec<String>, Vec<String>) = references
+        .into_iter()
+        .partition(|reference| reference.starts_with("vault://"));
+    let mut secrets = HashMap::new();
+    if !vault_references.is_empty() {
+        let proxy_state = app.state::<ProxyState>();
+        let client = token_client(&proxy_state)?;
+        let config = vault_config(app)?;
+        proxy_state.check_offline(&config.address)?;
+        let mut responses: HashMap<String, Value> = HashMap::new();
+        for reference in vault_references {
+            let Some((path, key)) = reference["vault://".len()..].split_once('#') else {
+                continue;
+            };
+            if !responses.contains_key(path) {
+                let response = fetch_vault_secret(&client, &config, path).await?;
+                responses.insert(path.to_string(), response);
+            }
+            let secret = vault_field(&responses[path], key)
+                .ok_or_else(|| format!("Vault secret {} has no field \"{}\"", path, key))?;
+            secrets.insert(reference, secret);
+        }
+    }
+    for reference in op_references {
+        let secret = op_read(&reference).await?;
+        secrets.insert(reference, secret);
+    }
+
+    substitute_references(value, &secrets);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn references_are_found_inside_strings() {
+        let mut references = HashSet::new();
+        collect_references(
+            &json!({
+                "headers": { "Authorization": "Bearer vault://secret/data/app#token" },
+                "body": "{\"pin\": \"op://Dev/Card/pin\"}"
+            }),
+            &mut references,
+        );
+        let mut found: Vec<_> = references.into_iter().collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec!["op://Dev/Card/pin", "vault://secret/data/app#token"]
+        );
+    }
+
+    #[test]
+    fn vault_fields_are_read_from_kv_v1_and_v2_responses() {
+        let v2 = json!({ "data": { "data": { "token": "t-2" }, "metadata": { "version": 3 } } });
+        let v1 = json!({ "data": { "token": "t-1", "ttl": 60 } });
+        assert_eq!(vault_field(&v2, "token").as_deref(), Some("t-2"));
+        assert_eq!(vault_field(&v1, "token").as_deref(), Some("t-1"));
+        assert_eq!(vault_field(&v1, "ttl").as_deref(), Some("60"));
+        assert_eq!(vault_field(&v1, "missing"), None);
+    }
+}
@@ -16,10 +16,31 @@
 //! Each secret is addressed by an opaque `account` string built by the frontend
 //! (`<scope>|<key>`). The keyring calls are synchronous, so they run on a blocking thread
 //! to avoid stalling the async runtime; the portal calls are natively async.
+//!
+//! Requests may reference secrets as `{{$secret:name}}` (a secret variable of the active
+//! environment) or `{{$secret:<scope>|<key>}}`. The frontend leaves these references in
+//! place and they are substituted here, right before the request is sent, so the value
+//! never passes through the renderer.
 
 use keyring::{Entry, Error as KeyringError};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::api_request::RequestOptions;
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
 
 const KEYRING_SERVICE: &str = "resonance";
+/// Non-sensitive `{ scope: { key: true } }` index of keychain entries (see `SecretStore.js`).
+const SECRET_INDEX_KEY: &str = "secretIndex";
+/// Plaintext `{ scope: { key: value } }` fallback used when no keychain is available.
+const SECRET_VALUES_KEY: &str = "secretValues";
+/// Shown in place of secret values in history, logs and exports.
+pub(crate) const SECRET_MASK: &str = "********";
 
 fn make_entry(account: &str) -> Result<Entry, String> {
     Entry::new(KEYRING_SERVICE, account).map_err(|e| e.to_string())
@@ -182,3 +203,306 @@ pub async fn secret_keychain_available() -> bool {
     .await
     .unwrap_or(false)
 }
+
+fn secret_reference_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\{\{\s*\$secret:([^}]+?)\s*\}\}").unwrap())
+}
+
+/// Split a reference into `(scope, key)`. A bare name refers to the active environment.
+fn parse_reference(reference: &str, active_environment: Option<&str>) -> Option<(String, String)> {
+    match reference.split_once('|') {
+        Some((scope, key)) if !scope.is_empty() && !key.is_empty() => {
+            Some((scope.to_string(), key.to_string()))
+        }
+        Some(_) => None,
+        None => active_environment.map(|id| (format!("env:{}", id), reference.to_string())),
+    }
+}
+
+fn collect_references(value: &Value, out: &mut HashSet<String>) {
+    match value {
+        Value::String(s) => {
+            for caps in secret_reference_pattern().captures_iter(s) {
+                out.insert(caps[1].to_string());
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_references(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_references(v, out)),
+        _ => {}
+    }
+}
+
+fn substitute_references(value: &mut Value, secrets: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            let replaced = secret_reference_pattern().replace_all(s, |caps: &regex::Captures| {
+                secrets.get(&caps[1]).cloned().unwrap_or_default()
+            });
+            *s = replaced.into_owned();
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| substitute_references(v, secrets)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|v| substitute_references(v, secrets)),
+        _ => {}
+    }
+}
+
+/// Read every secret of `scope` from the same place the frontend `SecretStore` keeps
+/// it: keychain entries listed in the index, plus any plaintext fallback values.
+pub(crate) async fn read_secret_scope(app: &AppHandle, scope: &str) -> HashMap<String, String> {
+    let Ok(store) = app.store(store_file(app)) else {
+        return HashMap::new();
+    };
+    let mut secrets: HashMap<String, String> = store
+        .get(SECRET_VALUES_KEY)
+        .and_then(|values| values.get(scope).and_then(Value::as_object).cloned())
+        .map(|entries| {
+            entries
+                .into_iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k, v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let indexed: Vec<String> = store
+        .get(SECRET_INDEX_KEY)
+        .and_then(|index| index.get(scope).and_then(Value::as_object).cloned())
+        .map(|entries| entries.into_iter().map(|(k, _)| k).collect())
+        .unwrap_or_default();
+    for key in indexed {
+        match secret_get(format!("{}|{}", scope, key)).await {
+            Ok(Some(value)) => {
+                secrets.insert(key, value);
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to read secret {}|{}: {}", scope, key, e),
+        }
+    }
+    secrets
+}
+
+/// Values of the secret variables that can reach a request: those of the
+/// active environment and, when given, of the collection.
+pub(crate) async fn secret_variable_values(
+    app: &AppHandle,
+    collection_id: Option<&str>,
+) -> Vec<String> {
+    let mut scopes = Vec::new();
+    if let Some(id) = super::variables::active_environment_id(app) {
+        scopes.push(format!("env:{}", id));
+    }
+    if let Some(id) = collection_id {
+        scopes.push(format!("collvar:{}", id));
+    }
+
+    let mut values = Vec::new();
+    for scope in scopes {
+        values.extend(read_secret_scope(app, &scope).await.into_values());
+    }
+    values.retain(|value| !value.is_empty());
+    // Longest first, so a secret containing another is masked as a whole
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    values.dedup();
+    values
+}
+
+/// Replace every occurrence of a secret value in `text`.
+pub(crate) fn mask_secret_text(text: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(text.to_string(), |text, secret| {
+        text.replace(secret.as_str(), SECRET_MASK)
+    })
+}
+
+/// Replace every occurrence of a secret value in the strings of `value`.
+pub(crate) fn mask_secret_values(value: &mut Value, secrets: &[String]) {
+    match value {
+        Value::String(s) => *s = mask_secret_text(s, secrets),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| mask_secret_values(v, secrets)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|v| mask_secret_values(v, secrets)),
+        _ => {}
+    }
+}
+
+/// Mask secret variable values in `value` before it is kept somewhere
+/// readable, such as request history.
+#[tauri::command]
+pub async fn secret_mask(
+    app: AppHandle,
+    collection_id: Option<String>,
+    mut value: Value,
+) -> Result<Value, String> {
+    let secrets = secret_variable_values(&app, collection_id.as_deref()).await;
+    mask_secret_values(&mut value, &secrets);
+    Ok(value)
+}
+
+/// Copy every secret of `from` into `to`, keeping each one in the backend it was in.
+/// Used when duplicating the item a scope belongs to.
+pub(crate) async fn copy_secret_scope(app: &AppHandle, from: &str, to: &str) -> Result<(), String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+
+    // Keychain entries first: the store lock cannot be held across the awaits
+    let indexed = store
+        .get(SECRET_INDEX_KEY)
+        .and_then(|index| index.get(from).and_then(Value::as_object).cloned());
+    if let Some(keys) = indexed.as_ref() {
+        for key in keys.keys() {
+            if let Some(value) = secret_get(format!("{}|{}", from, key)).await? {
+                secret_set(format!("{}|{}", to, key), value).await?;
+            }
+        }
+    }
+
+    let _guard = lock_store();
+    let mut values = store.get(SECRET_VALUES_KEY).unwrap_or(Value::Null);
+    let plaintext = values.get(from).cloned();
+    if let (Some(entries), Some(map)) = (plaintext, values.as_object_mut()) {
+        map.insert(to.to_string(), entries);
+        store.set(SECRET_VALUES_KEY.to_string(), values);
+    }
+    let mut index = store.get(SECRET_INDEX_KEY).unwrap_or(Value::Null);
+    if let (Some(keys), Some(map)) = (indexed, index.as_object_mut()) {
+        map.insert(to.to_string(), Value::Object(keys));
+        store.set(SECRET_INDEX_KEY.to_string(), index);
+    }
+    save_store(app)
+}
+
+/// Fill blank secret fields of a persisted auth config (`{type, config}`) from `scope`,
+/// the counterpart of the frontend's `mergeAuthSecrets`.
+pub(crate) async fn hydrate_auth_config(app: &AppHandle, auth: &mut Value, scope: &str) {
+    let Some(config) = auth.get_mut("config").and_then(Value::as_object_mut) else {
+        return;
+    };
+    let blank = config
+        .values()
+        .any(|v| v.as_str().is_some_and(str::is_empty));
+    if !blank {
+        return;
+    }
+    for (field, value) in read_secret_scope(app, scope).await {
+        if config
+            .get(&field)
+            .and_then(Value::as_str)
+            .is_some_and(str::is_empty)
+        {
+            config.insert(field, Value::String(value));
+        }
+    }
+}
+
+/// Replace every `{{$secret:...}}` reference in a request with the stored value,
+/// then every external secret-manager reference (see `secret_managers`).
+/// Fails when a referenced secret does not exist, rather than sending the literal.
+pub(crate) async fn resolve_secret_references(
+    app: &AppHandle,
+    options: RequestOptions,
+) -> Result<RequestOptions, String> {
+    let mut value = serde_json::to_value(&options).map_err(|e| e.to_string())?;
+    let mut references = HashSet::new();
+    collect_references(&value, &mut references);
+    if references.is_empty() && !super::secret_managers::has_external_references(&value) {
+        return Ok(options);
+    }
+
+    let active_environment = super::variables::active_environment_id(app);
+    let mut scopes: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut secrets = HashMap::new();
+    for reference in references {
+        let (scope, key) = parse_reference(&reference, active_environment.as_deref())
+            .ok_or_else(|| format!("Invalid secret reference: {{{{$secret:{}}}}}", reference))?;
+        if !scopes.contains_key(&scope) {
+            let values = read_secret_scope(app, &scope).await;
+            scopes.insert(scope.clone(), values);
+        }
+        let secret = scopes[&scope]
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| format!("Secret not found: {}", reference))?;
+        secrets.insert(reference, secret);
+    }
+
+    substitute_references(&mut value, &secrets);
+    super::secret_managers::resolve_external_references(app, &mut value).await?;
+    let mut resolved: RequestOptions =
+        serde_json::from_value(value).map_err(|e| format!("Failed to apply secrets: {}", e))?;
+    // Not serialized, so it does not survive the round trip
+    resolved.proxy = options.proxy;
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn bare_references_use_the_active_environment() {
+        assert_eq!(
+            parse_reference("apiKey", Some("e1")),
+            Some(("env:e1".to_string(), "apiKey".to_string()))
+        );
+        assert_eq!(parse_reference("apiKey", None), None);
+        assert_eq!(
+            parse_reference("auth:c1:ep|token", Some("e1")),
+            Some(("auth:c1:ep".to_string(), "token".to_string()))
+        );
+        assert_eq!(parse_reference("|token", Some("e1")), None);
+    }
+
+    #[test]
+    fn secret_values_are_masked_in_nested_strings() {
+        let mut value = json!({
+            "url": "https://api.test/?key=k-1",
+            "headers": { "Authorization": "Bearer t-2-long" },
+            "status": 200
+        });
+        let secrets = vec!["t-2-long".to_string(), "k-1".to_string()];
+        mask_secret_values(&mut value, &secrets);
+        assert_eq!(
+            value,
+            json!({
+                "url": "https://api.test/?key=********",
+                "headers": { "Authorization": "Bearer ********" },
+                "status": 200
+            })
+        );
+    }
+
+    #[test]
+    fn references_are_found_and_substituted_in_nested_values() {
+        let mut value = json!({
+            "url": "https://api.test/?key={{$secret:apiKey}}",
+            "headers": { "Authorization": "Bearer {{ $secret:env:e1|token }}" },
+            "body": [{ "value": "{{$uuid}}" }]
+        });
+        let mut references = HashSet::new();
+        collect_references(&value, &mut references);
+        assert_eq!(
+            references,
+            HashSet::from(["apiKey".to_string(), "env:e1|token".to_string()])
+        );
+
+        let secrets = HashMap::from([
+            ("apiKey".to_string(), "k-1".to_string()),
+            ("env:e1|token".to_string(), "t-2".to_string()),
+        ]);
+        substitute_references(&mut value, &secrets);
+        assert_eq!(
+            value,
+            json!({
+                "url": "https://api.test/?key=k-1",
+                "headers": { "Authorization": "Bearer t-2" },
+                "body": [{ "value": "{{$uuid}}" }]
+            })
+        );
+    }
+}
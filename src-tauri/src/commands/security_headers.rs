@@ -0,0 +1,600 @@
+//! Security audit of response headers: transport security (HSTS), content
+//! security policy, framing, MIME sniffing, referrer and permissions policies,
+//! cookie flags, CORS and headers that disclose server software. Each check
+//! yields a finding with a severity hint, for quick app-sec reviews.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Six months, the minimum `max-age` accepted for HSTS preloading.
+const HSTS_MIN_MAX_AGE: u64 = 15_552_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    High,
+    Medium,
+    Low,
+    Info,
+    Pass,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityFinding {
+    /// Check the finding comes from, e.g. "hsts", "csp" or "cookie"
+    pub check: &'static str,
+    /// Header the finding is about, as sent by the server
+    pub header: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityAudit {
+    pub https: bool,
+    /// Findings ordered from the most to the least severe
+    pub findings: Vec<SecurityFinding>,
+}
+
+struct Audit {
+    findings: Vec<SecurityFinding>,
+}
+
+impl Audit {
+    fn add(&mut self, check: &'static str, header: &str, severity: Severity, message: String) {
+        self.findings.push(SecurityFinding {
+            check,
+            header: header.to_string(),
+            severity,
+            message,
+        });
+    }
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+/// Directives of a `;`-separated header, with lowercased names.
+fn directives(value: &str) -> Vec<(String, String)> {
+    value
+        .split(';')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            let (name, rest) = part.split_once(['=', ' ']).unwrap_or((part, ""));
+            Some((name.trim().to_lowercase(), rest.trim().to_string()))
+        })
+        .collect()
+}
+
+fn check_hsts(audit: &mut Audit, headers: &HashMap<String, String>, https: bool) {
+    const NAME: &str = "Strict-Transport-Security";
+    if !https {
+        audit.add(
+            "hsts",
+            NAME,
+            Severity::High,
+            "The response was served over plain HTTP; traffic can be read and modified in transit"
+                .to_string(),
+        );
+        return;
+    }
+    let Some(value) = header(headers, NAME) else {
+        audit.add(
+            "hsts",
+            NAME,
+            Severity::High,
+            "Missing; browsers may be downgraded to HTTP on later visits".to_string(),
+        );
+        return;
+    };
+    let directives = directives(value);
+    let max_age = directives
+        .iter()
+        .find(|(name, _)| name == "max-age")
+        .and_then(|(_, v)| v.trim_matches('"').parse::<u64>().ok());
+    let include_subdomains = directives
+        .iter()
+        .any(|(name, _)| name == "includesubdomains");
+    match max_age {
+        None => audit.add(
+            "hsts",
+            NAME,
+            Severity::High,
+            "Has no valid max-age, so browsers ignore it".to_string(),
+        ),
+        Some(0) => audit.add(
+            "hsts",
+            NAME,
+            Severity::High,
+            "max-age=0 tells browsers to forget the HSTS policy".to_string(),
+        ),
+        Some(age) if age < HSTS_MIN_MAX_AGE => audit.add(
+            "hsts",
+            NAME,
+            Severity::Medium,
+            format!(
+                "max-age={} is shorter than six months ({})",
+                age, HSTS_MIN_MAX_AGE
+            ),
+        ),
+        Some(_) if !include_subdomains => audit.add(
+            "hsts",
+            NAME,
+            Severity::Low,
+            "Does not cover subdomains (includeSubDomains)".to_string(),
+        ),
+        Some(_) => audit.add("hsts", NAME, Severity::Pass, format!("Set: {}", value)),
+    }
+}
+
+fn check_csp(audit: &mut Audit, headers: &HashMap<String, String>, is_html: bool) {
+    const NAME: &str = "Content-Security-Policy";
+    let Some(value) = header(headers, NAME) else {
+        let (severity, message) =
+            if header(headers, "Content-Security-Policy-Report-Only").is_some() {
+                (
+                    Severity::Low,
+                    "Only a report-only policy is set; nothing is enforced".to_string(),
+                )
+            } else if is_html {
+                (
+                    Severity::Medium,
+                    "Missing; injected scripts run without restriction".to_string(),
+                )
+            } else {
+                (
+                    Severity::Info,
+                    "Missing; it matters mostly for HTML documents".to_string(),
+                )
+            };
+        audit.add("csp", NAME, severity, message);
+        return;
+    };
+    let policy = directives(value);
+    let sources = |name: &str| {
+        policy
+            .iter()
+            .find(|(directive, _)| directive == name)
+            .map(|(_, sources)| sources.to_lowercase())
+    };
+    let mut weak = false;
+    if let Some(script) = sources("script-src").or_else(|| sources("default-src")) {
+        for (token, message) in [
+            ("'unsafe-inline'", "allows inline scripts ('unsafe-inline')"),
+            ("'unsafe-eval'", "allows eval() ('unsafe-eval')"),
+        ] {
+            if script.split_whitespace().any(|source| source == token) {
+                weak = true;
+                audit.add(
+                    "csp",
+                    NAME,
+                    Severity::Medium,
+                    format!("Script policy {}", message),
+                );
+            }
+        }
+        if script
+            .split_whitespace()
+            .any(|source| matches!(source, "*" | "http:" | "https:" | "data:"))
+        {
+            weak = true;
+            audit.add(
+                "csp",
+                NAME,
+                Severity::Medium,
+                "Script policy allows scripts from any host".to_string(),
+            );
+        }
+    } else {
+        weak = true;
+        audit.add(
+            "csp",
+            NAME,
+            Severity::Medium,
+            "Has neither script-src nor default-src, so scripts are not restricted".to_string(),
+        );
+    }
+    if sources("object-src").is_none() && sources("default-src").is_none() {
+        weak = true;
+        audit.add(
+            "csp",
+            NAME,
+            Severity::Low,
+            "Does not restrict plugins (object-src)".to_string(),
+        );
+    }
+    if !weak {
+        audit.add("csp", NAME, Severity::Pass, format!("Set: {}", value));
+    }
+}
+
+fn check_framing(audit: &mut Audit, headers: &HashMap<String, String>, is_html: bool) {
+    const NAME: &str = "X-Frame-Options";
+    let frame_ancestors = header(headers, "Content-Security-Policy")
+        .map(directives)
+        .and_then(|policy| {
+            policy
+                .into_iter()
+                .find(|(name, _)| name == "frame-ancestors")
+        });
+    if let Some((_, sources)) = frame_ancestors {
+        audit.add(
+            "framing",
+            "Content-Security-Policy",
+            Severity::Pass,
+            format!("Framing restricted by frame-ancestors {}", sources),
+        );
+        return;
+    }
+    match header(headers, NAME).map(str::to_uppercase) {
+        Some(value) if value == "DENY" || value == "SAMEORIGIN" => {
+            audit.add("framing", NAME, Severity::Pass, format!("Set: {}", value))
+        }
+        Some(value) => audit.add(
+            "framing",
+            NAME,
+            Severity::Medium,
+            format!(
+                "{} is not supported by current browsers; use DENY, SAMEORIGIN or CSP frame-ancestors",
+                value
+            ),
+        ),
+        None if is_html => audit.add(
+            "framing",
+            NAME,
+            Severity::Medium,
+            "Missing, and no CSP frame-ancestors; the page can be framed (clickjacking)"
+                .to_string(),
+        ),
+        None => audit.add(
+            "framing",
+            NAME,
+            Severity::Info,
+            "Missing; it matters mostly for HTML documents".to_string(),
+        ),
+    }
+}
+
+fn check_nosniff(audit: &mut Audit, headers: &HashMap<String, String>) {
+    const NAME: &str = "X-Content-Type-Options";
+    match header(headers, NAME) {
+        Some(value) if value.eq_ignore_ascii_case("nosniff") => {
+            audit.add("nosniff", NAME, Severity::Pass, "Set: nosniff".to_string())
+        }
+        Some(value) => audit.add(
+            "nosniff",
+            NAME,
+            Severity::Low,
+            format!("{} has no effect; the only valid value is nosniff", value),
+        ),
+        None => audit.add(
+            "nosniff",
+            NAME,
+            Severity::Low,
+            "Missing; browsers may sniff the body as another content type".to_string(),
+        ),
+    }
+}
+
+fn check_referrer_policy(audit: &mut Audit, headers: &HashMap<String, String>) {
+    const NAME: &str = "Referrer-Policy";
+    let Some(value) = header(headers, NAME) else {
+        audit.add(
+            "referrer",
+            NAME,
+            Severity::Info,
+            "Missing; browsers default to strict-origin-when-cross-origin".to_string(),
+        );
+        return;
+    };
+    // With a list of policies, the last one wins
+    let policy = value
+        .split(',')
+        .map(|p| p.trim().to_lowercase())
+        .next_back()
+        .unwrap_or_default();
+    if policy == "unsafe-url" || policy == "no-referrer-when-downgrade" {
+        audit.add(
+            "referrer",
+            NAME,
+            Severity::Medium,
+            format!("{} leaks full URLs to other sites", policy),
+        );
+    } else {
+        audit.add("referrer", NAME, Severity::Pass, format!("Set: {}", value));
+    }
+}
+
+fn check_permissions_policy(audit: &mut Audit, headers: &HashMap<String, String>, is_html: bool) {
+    const NAME: &str = "Permissions-Policy";
+    if header(headers, NAME).is_some() {
+        audit.add("permissions", NAME, Severity::Pass, "Set".to_string());
+    } else if is_html {
+        audit.add(
+            "permissions",
+            NAME,
+            Severity::Info,
+            "Missing; browser features such as camera or geolocation are not restricted"
+                .to_string(),
+        );
+    }
+}
+
+fn check_cookies(audit: &mut Audit, set_cookies: &[String], https: bool) {
+    const NAME: &str = "Set-Cookie";
+    for cookie in set_cookies {
+        let mut parts = cookie.split(';');
+        let name = parts
+            .next()
+            .and_then(|pair| pair.split('=').next())
+            .unwrap_or("")
+            .trim();
+        let attributes = directives(&parts.collect::<Vec<_>>().join(";"));
+        let has = |attribute: &str| attributes.iter().any(|(name, _)| name == attribute);
+        let same_site = attributes
+            .iter()
+            .find(|(name, _)| name == "samesite")
+            .map(|(_, value)| value.to_lowercase());
+
+        let mut problems = Vec::new();
+        if !has("secure") {
+            let severity = if same_site.as_deref() == Some("none") {
+                Severity::High
+            } else if https {
+                Severity::Medium
+            } else {
+                Severity::Low
+            };
+            problems.push((severity, "is sent over plain HTTP too (no Secure flag)"));
+        }
+        if !has("httponly") {
+            problems.push((Severity::Low, "is readable by scripts (no HttpOnly flag)"));
+        }
+        match same_site.as_deref() {
+            None => problems.push((
+                Severity::Low,
+                "has no SameSite attribute; browsers default to Lax",
+            )),
+            Some("none") => problems.push((
+                Severity::Info,
+                "is sent on cross-site requests (SameSite=None)",
+            )),
+            _ => {}
+        }
+        if name.starts_with("__Host-") && has("domain") {
+            problems.push((
+                Severity::Medium,
+                "uses the __Host- prefix but sets a Domain, so browsers reject it",
+            ));
+        }
+
+        if problems.is_empty() {
+            audit.add(
+                "cookie",
+                NAME,
+                Severity::Pass,
+                format!("Cookie {} has Secure, HttpOnly and SameSite set", name),
+            );
+        }
+        for (severity, problem) in problems {
+            audit.add(
+                "cookie",
+                NAME,
+                severity,
+                format!("Cookie {} {}", name, problem),
+            );
+        }
+    }
+}
+
+fn check_cors(audit: &mut Audit, headers: &HashMap<String, String>) {
+    const NAME: &str = "Access-Control-Allow-Origin";
+    let Some(origin) = header(headers, NAME) else {
+        return;
+    };
+    let credentials = header(headers, "Access-Control-Allow-Credentials")
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    if origin == "*" {
+        if credentials {
+            audit.add(
+                "cors",
+                NAME,
+                Severity::High,
+                "Any origin with credentials allowed; browsers refuse it, but it suggests the origin is reflected for real requests"
+                    .to_string(),
+            );
+        } else {
+            audit.add(
+                "cors",
+                NAME,
+                Severity::Low,
+                "Any origin may read responses; fine for public data only".to_string(),
+            );
+        }
+    } else if origin.eq_ignore_ascii_case("null") {
+        audit.add(
+            "cors",
+            NAME,
+            Severity::High,
+            "Allows the null origin, which sandboxed iframes and local files can claim".to_string(),
+        );
+    } else if origin.starts_with("http://") {
+        audit.add(
+            "cors",
+            NAME,
+            Severity::Medium,
+            format!("Trusts {}, an origin served over plain HTTP", origin),
+        );
+    } else {
+        let vary_origin = header(headers, "Vary").is_some_and(|vary| {
+            vary.split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("origin"))
+        });
+        if !vary_origin {
+            audit.add(
+                "cors",
+                NAME,
+                Severity::Low,
+                format!(
+                    "Allows {} without Vary: Origin; caches may serve it to other origins",
+                    origin
+                ),
+            );
+        } else if credentials {
+            audit.add(
+                "cors",
+                NAME,
+                Severity::Info,
+                format!("Allows {} with credentials", origin),
+            );
+        } else {
+            audit.add("cors", NAME, Severity::Pass, format!("Allows {}", origin));
+        }
+    }
+}
+
+fn check_disclosure(audit: &mut Audit, headers: &HashMap<String, String>) {
+    if let Some(server) = header(headers, "Server") {
+        if server.chars().any(|c| c.is_ascii_digit()) {
+            audit.add(
+                "disclosure",
+                "Server",
+                Severity::Low,
+                format!("Discloses the server version: {}", server),
+            );
+        }
+    }
+    for name in ["X-Powered-By", "X-AspNet-Version", "X-AspNetMvc-Version"] {
+        if let Some(value) = header(headers, name) {
+            audit.add(
+                "disclosure",
+                name,
+                Severity::Low,
+                format!("Discloses the server stack: {}", value),
+            );
+        }
+    }
+}
+
+fn audit_headers(
+    url: &str,
+    headers: &HashMap<String, String>,
+    set_cookies: &[String],
+) -> SecurityAudit {
+    let https = url.trim_start().to_lowercase().starts_with("https://");
+    let is_html =
+        header(headers, "Content-Type").is_some_and(|ct| ct.to_lowercase().contains("html"));
+    let mut audit = Audit {
+        findings: Vec::new(),
+    };
+    check_hsts(&mut audit, headers, https);
+    check_csp(&mut audit, headers, is_html);
+    check_framing(&mut audit, headers, is_html);
+    check_nosniff(&mut audit, headers);
+    check_referrer_policy(&mut audit, headers);
+    check_permissions_policy(&mut audit, headers, is_html);
+    check_cookies(&mut audit, set_cookies, https);
+    check_cors(&mut audit, headers);
+    check_disclosure(&mut audit, headers);
+
+    let mut findings = audit.findings;
+    findings.sort_by_key(|finding| finding.severity);
+    SecurityAudit { https, findings }
+}
+
+/// Audit the security headers of a response to `url`. `set_cookies` holds
+/// every Set-Cookie value, since the headers map keeps only one.
+#[tauri::command]
+pub fn audit_security_headers(
+    url: String,
+    headers: HashMap<String, String>,
+    set_cookies: Option<Vec<String>>,
+) -> SecurityAudit {
+    audit_headers(&url, &headers, &set_cookies.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn severity_of(audit: &SecurityAudit, check: &str) -> Vec<Severity> {
+        audit
+            .findings
+            .iter()
+            .filter(|f| f.check == check)
+            .map(|f| f.severity)
+            .collect()
+    }
+
+    #[test]
+    fn audits_weak_and_strong_headers() {
+        let weak = audit_headers(
+            "https://api.test/login",
+            &headers(&[
+                ("content-type", "text/html"),
+                ("strict-transport-security", "max-age=3600"),
+                (
+                    "content-security-policy",
+                    "default-src 'self'; script-src 'self' 'unsafe-inline'",
+                ),
+                ("access-control-allow-origin", "*"),
+                ("access-control-allow-credentials", "true"),
+                ("server", "nginx/1.18.0"),
+            ]),
+            &["sid=abc; Path=/; SameSite=None".to_string()],
+        );
+        assert_eq!(severity_of(&weak, "hsts"), vec![Severity::Medium]);
+        assert_eq!(severity_of(&weak, "csp"), vec![Severity::Medium]);
+        assert_eq!(severity_of(&weak, "framing"), vec![Severity::Medium]);
+        assert_eq!(severity_of(&weak, "cors"), vec![Severity::High]);
+        assert_eq!(severity_of(&weak, "disclosure"), vec![Severity::Low]);
+        assert_eq!(
+            severity_of(&weak, "cookie"),
+            vec![Severity::High, Severity::Low, Severity::Info]
+        );
+        assert_eq!(weak.findings[0].severity, Severity::High);
+
+        let strong = audit_headers(
+            "https://api.test/",
+            &headers(&[
+                (
+                    "Strict-Transport-Security",
+                    "max-age=31536000; includeSubDomains",
+                ),
+                (
+                    "Content-Security-Policy",
+                    "default-src 'none'; frame-ancestors 'none'",
+                ),
+                ("X-Content-Type-Options", "nosniff"),
+                ("Referrer-Policy", "no-referrer"),
+                ("Access-Control-Allow-Origin", "https://app.test"),
+                ("Vary", "Accept-Encoding, Origin"),
+            ]),
+            &["__Host-sid=abc; Path=/; Secure; HttpOnly; SameSite=Strict".to_string()],
+        );
+        assert!(strong
+            .findings
+            .iter()
+            .all(|f| f.severity == Severity::Pass || f.severity == Severity::Info));
+
+        let plain = audit_headers("http://api.test/", &HashMap::new(), &[]);
+        assert!(!plain.https);
+        assert_eq!(severity_of(&plain, "hsts"), vec![Severity::High]);
+    }
+}
@@ -0,0 +1,276 @@
+//! Export and import of the application settings alone, so a new machine can
+//! be set up like an existing one without copying its collections.
+//!
+//! Only preferences travel: the settings, theme, proxy, offline mode, mock
+//! server port and log size, and layout widths of the active workspace.
+//! Collections, environments, history, certificates and secrets stay behind,
+//! and the proxy password is never written to the file.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::oneshot;
+
+use super::proxy::ProxyState;
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const FORMAT_VERSION: u32 = 1;
+const PROXY_KEY: &str = "proxySettings";
+const MOCK_SERVER_KEY: &str = "mockServer";
+
+/// Store keys copied as they are.
+const PLAIN_KEYS: [&str; 6] = [
+    "settings",
+    "theme",
+    "accent",
+    "offlineMode",
+    "sidebarWidth",
+    "graphqlExplorerWidth",
+];
+
+/// Mock server settings that do not name collections or endpoints.
+const MOCK_SERVER_DEFAULTS: [&str; 2] = ["port", "logSize"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsFile {
+    /// Format version; also marks the file as a settings export.
+    pub resonance_settings: u32,
+    #[serde(default)]
+    pub exported_at: i64,
+    pub values: Map<String, Value>,
+}
+
+/// The exportable part of the store entries.
+fn exported_values(entries: &Map<String, Value>) -> Map<String, Value> {
+    let mut values: Map<String, Value> = PLAIN_KEYS
+        .iter()
+        .filter_map(|key| {
+            let value = entries.get(*key).filter(|value| !value.is_null())?;
+            Some((key.to_string(), value.clone()))
+        })
+        .collect();
+
+    if let Some(mut proxy) = entries.get(PROXY_KEY).cloned() {
+        if let Some(auth) = proxy.get_mut("auth").and_then(Value::as_object_mut) {
+            auth.insert("password".to_string(), Value::String(String::new()));
+        }
+        values.insert(PROXY_KEY.to_string(), proxy);
+    }
+
+    if let Some(mock) = entries.get(MOCK_SERVER_KEY).and_then(Value::as_object) {
+        let defaults: Map<String, Value> = MOCK_SERVER_DEFAULTS
+            .iter()
+            .filter_map(|key| Some((key.to_string(), mock.get(*key)?.clone())))
+            .collect();
+        values.insert(MOCK_SERVER_KEY.to_string(), Value::Object(defaults));
+    }
+    values
+}
+
+/// The store entries to write for imported `values`, merged into the
+/// `current` ones so nothing outside the exported settings is lost.
+fn imported_entries(
+    values: &Map<String, Value>,
+    current: &Map<String, Value>,
+) -> Map<String, Value> {
+    let mut entries: Map<String, Value> = PLAIN_KEYS
+        .iter()
+        .filter_map(|key| Some((key.to_string(), values.get(*key)?.clone())))
+        .collect();
+
+    if let Some(mut proxy) = values.get(PROXY_KEY).cloned() {
+        // The file never carries the password: keep the one of this machine
+        let local_password = current
+            .get(PROXY_KEY)
+            .and_then(|proxy| proxy.pointer("/auth/password"))
+            .cloned();
+        if let (Some(auth), Some(password)) = (
+            proxy.get_mut("auth").and_then(Value::as_object_mut),
+            local_password,
+        ) {
+            let has_password = matches!(
+                auth.get("password").and_then(Value::as_str),
+                Some(password) if !password.is_empty()
+            );
+            if !has_password {
+                auth.insert("password".to_string(), password);
+            }
+        }
+        entries.insert(PROXY_KEY.to_string(), proxy);
+    }
+
+    if let Some(imported) = values.get(MOCK_SERVER_KEY).and_then(Value::as_object) {
+        let mut mock = current
+            .get(MOCK_SERVER_KEY)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        for key in MOCK_SERVER_DEFAULTS {
+            if let Some(value) = imported.get(key) {
+                mock.insert(key.to_string(), value.clone());
+            }
+        }
+        entries.insert(MOCK_SERVER_KEY.to_string(), Value::Object(mock));
+    }
+    entries
+}
+
+fn parse_settings_file(text: &str) -> Result<SettingsFile, String> {
+    let file: SettingsFile = serde_json::from_str(text)
+        .map_err(|_| "The file is not a Resonance settings export".to_string())?;
+    if file.resonance_settings > FORMAT_VERSION {
+        return Err(format!(
+            "Settings export version {} is not supported by this version of Resonance",
+            file.resonance_settings
+        ));
+    }
+    Ok(file)
+}
+
+/// Save the active workspace's settings to a file.
+#[tauri::command]
+pub async fn settings_export(app: AppHandle) -> Result<Value, String> {
+    let store = app
+        .store(store_file(&app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let entries: Map<String, Value> = store.entries().into_iter().collect();
+    let file = SettingsFile {
+        resonance_settings: FORMAT_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        values: exported_values(&entries),
+    };
+
+    let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+    app.dialog()
+        .file()
+        .set_file_name("resonance-settings.json")
+        .add_filter("Resonance Settings", &["json"])
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+    let file_path = rx.await.map_err(|e| format!("Dialog error: {}", e))?;
+    let Some(path) = file_path else {
+        return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+    };
+    let file_path = path.as_path().ok_or("Invalid file path")?;
+
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    std::fs::write(file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": file_path.to_string_lossy(),
+    }))
+}
+
+/// Apply a settings export (picked when no path is given) to the active
+/// workspace.
+#[tauri::command]
+pub async fn settings_import(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    file_path: Option<String>,
+) -> Result<Value, String> {
+    let path = match file_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let (tx, rx) = oneshot::channel::<Option<FilePath>>();
+            app.dialog()
+                .file()
+                .add_filter("Resonance Settings", &["json"])
+                .pick_file(move |file_path| {
+                    let _ = tx.send(file_path);
+                });
+            let Some(path) = rx.await.map_err(|e| format!("Dialog error: {}", e))? else {
+                return Ok(serde_json::json!({ "success": false, "cancelled": true }));
+            };
+            path.as_path().ok_or("Invalid file path")?.to_path_buf()
+        }
+    };
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let file = parse_settings_file(&content)?;
+
+    let store = app
+        .store(store_file(&app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let imported = {
+        let _guard = lock_store();
+        let current: Map<String, Value> = store.entries().into_iter().collect();
+        let entries = imported_entries(&file.values, &current);
+        let keys: Vec<String> = entries.keys().cloned().collect();
+        for (key, value) in entries {
+            store.set(key, value);
+        }
+        save_store(&app)?;
+        keys
+    };
+    proxy_state.reload_from_store(&app);
+
+    Ok(serde_json::json!({
+        "success": true,
+        "filePath": path.to_string_lossy(),
+        "imported": imported,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn exports_preferences_only_and_keeps_the_local_proxy_password() {
+        let entries: Map<String, Value> = serde_json::from_value(json!({
+            "settings": { "timeout": 5000, "language": "de" },
+            "theme": "dark",
+            "environments": [{ "id": "env-1" }],
+            "clientCertificates": { "items": [{ "host": "api.test" }] },
+            "proxySettings": {
+                "enabled": true,
+                "host": "proxy.corp",
+                "auth": { "enabled": true, "username": "me", "password": "hunter2" }
+            },
+            "mockServer": { "port": 4000, "enabledCollections": ["c1"] }
+        }))
+        .unwrap();
+
+        let values = exported_values(&entries);
+        let mut keys: Vec<&str> = values.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["mockServer", "proxySettings", "settings", "theme"]);
+        assert_eq!(values["proxySettings"]["auth"]["password"], "");
+        assert_eq!(values["mockServer"], json!({ "port": 4000 }));
+
+        let text = serde_json::to_string(&SettingsFile {
+            resonance_settings: FORMAT_VERSION,
+            exported_at: 0,
+            values,
+        })
+        .unwrap();
+        let file = parse_settings_file(&text).unwrap();
+
+        let current: Map<String, Value> = serde_json::from_value(json!({
+            "proxySettings": { "auth": { "password": "local" } },
+            "mockServer": { "port": 3000, "enabledCollections": ["c9"] }
+        }))
+        .unwrap();
+        let imported = imported_entries(&file.values, &current);
+        assert_eq!(imported["proxySettings"]["auth"]["password"], "local");
+        assert_eq!(imported["proxySettings"]["host"], "proxy.corp");
+        assert_eq!(
+            imported["mockServer"],
+            json!({ "port": 4000, "enabledCollections": ["c9"] })
+        );
+        assert_eq!(imported["settings"]["language"], "de");
+        assert!(!imported.contains_key("environments"));
+
+        assert!(parse_settings_file("{\"collections\": []}").is_err());
+        assert!(parse_settings_file("{\"resonanceSettings\": 99, \"values\": {}}").is_err());
+    }
+}
@@ -0,0 +1,425 @@
+//! SOAP body mode: build a SOAP 1.1/1.2 envelope for an operation from JSON
+//! parameters, and unwrap the Body (or Fault) of SOAP responses into JSON.
+//!
+//! Parameters map to XML the way most SOAP tools read it back: object keys
+//! become child elements, arrays repeat their element, `@name` keys are
+//! attributes, `#text` is text content and `null` is `xsi:nil`.
+
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const SOAP_11_NS: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+const SOAP_12_NS: &str = "http://www.w3.org/2003/05/soap-envelope";
+const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoapEnvelopeRequest {
+    /// Operation (the Body's child element), e.g. `GetWeather`
+    pub operation: String,
+    /// Target namespace of the operation; its elements are qualified with it
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub soap_action: Option<String>,
+    /// "1.1" (default) or "1.2"
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Parameters of the operation
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoapEnvelope {
+    pub body: String,
+    /// Content-Type and, for SOAP 1.1, SOAPAction
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoapFault {
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub actor: Option<String>,
+    pub detail: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoapResponse {
+    pub version: &'static str,
+    pub header: Option<Value>,
+    pub body: Option<Value>,
+    pub fault: Option<SoapFault>,
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => escape_xml(s),
+        other => escape_xml(&other.to_string()),
+    }
+}
+
+/// Append `<name ...>value</name>` for a parameter value.
+fn write_element(out: &mut String, name: &str, value: &Value, depth: usize) -> Result<(), String> {
+    if !is_xml_name(name) {
+        return Err(format!("\"{}\" is not a valid XML element name", name));
+    }
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                write_element(out, name, item, depth)?;
+            }
+        }
+        Value::Null => out.push_str(&format!("{}<{} xsi:nil=\"true\"/>\n", indent, name)),
+        Value::Object(fields) => {
+            let mut attributes = String::new();
+            let mut text = None;
+            let mut children = Vec::new();
+            for (key, child) in fields {
+                if let Some(attribute) = key.strip_prefix('@') {
+                    if !is_xml_name(attribute) {
+                        return Err(format!(
+                            "\"{}\" is not a valid XML attribute name",
+                            attribute
+                        ));
+                    }
+                    attributes.push_str(&format!(" {}=\"{}\"", attribute, scalar_text(child)));
+                } else if key == "#text" {
+                    text = Some(scalar_text(child));
+                } else {
+                    children.push((key, child));
+                }
+            }
+            if children.is_empty() {
+                match text {
+                    Some(text) => out.push_str(&format!(
+                        "{}<{}{}>{}</{}>\n",
+                        indent, name, attributes, text, name
+                    )),
+                    None => out.push_str(&format!("{}<{}{}/>\n", indent, name, attributes)),
+                }
+            } else {
+                out.push_str(&format!("{}<{}{}>\n", indent, name, attributes));
+                if let Some(text) = text {
+                    out.push_str(&format!("{}  {}\n", indent, text));
+                }
+                for (key, child) in children {
+                    write_element(out, key, child, depth + 1)?;
+                }
+                out.push_str(&format!("{}</{}>\n", indent, name));
+            }
+        }
+        scalar => out.push_str(&format!(
+            "{}<{}>{}</{}>\n",
+            indent,
+            name,
+            scalar_text(scalar),
+            name
+        )),
+    }
+    Ok(())
+}
+
+fn build_envelope(request: &SoapEnvelopeRequest) -> Result<SoapEnvelope, String> {
+    let operation = request.operation.trim();
+    if operation.is_empty() {
+        return Err("SOAP operation is required".to_string());
+    }
+    if !is_xml_name(operation) {
+        return Err(format!("\"{}\" is not a valid XML element name", operation));
+    }
+    let soap_12 = request.version.as_deref() == Some("1.2");
+    let soap_ns = if soap_12 { SOAP_12_NS } else { SOAP_11_NS };
+
+    // The operation declares the target namespace as its default, so its
+    // parameters are qualified with it as well
+    let namespace = request
+        .namespace
+        .as_deref()
+        .map(str::trim)
+        .filter(|ns| !ns.is_empty())
+        .map(|ns| format!(" xmlns=\"{}\"", escape_xml(ns)))
+        .unwrap_or_default();
+    let mut body = String::new();
+    match &request.params {
+        Value::Object(fields) if !fields.is_empty() => {
+            body.push_str(&format!("    <{}{}>\n", operation, namespace));
+            for (key, value) in fields {
+                write_element(&mut body, key, value, 3)?;
+            }
+            body.push_str(&format!("    </{}>\n", operation));
+        }
+        Value::Object(_) | Value::Null => {
+            body.push_str(&format!("    <{}{}/>\n", operation, namespace))
+        }
+        _ => return Err("SOAP parameters must be a JSON object".to_string()),
+    }
+    let envelope = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<soap:Envelope xmlns:soap=\"{}\" xmlns:xsi=\"{}\">\n  <soap:Body>\n{}  </soap:Body>\n</soap:Envelope>\n",
+        soap_ns, XSI_NS, body
+    );
+
+    let action = request
+        .soap_action
+        .as_deref()
+        .map(str::trim)
+        .filter(|action| !action.is_empty());
+    let mut headers = HashMap::new();
+    if soap_12 {
+        let content_type = match action {
+            Some(action) => format!("application/soap+xml; charset=utf-8; action=\"{}\"", action),
+            None => "application/soap+xml; charset=utf-8".to_string(),
+        };
+        headers.insert("Content-Type".to_string(), content_type);
+    } else {
+        headers.insert(
+            "Content-Type".to_string(),
+            "text/xml; charset=utf-8".to_string(),
+        );
+        headers.insert(
+            "SOAPAction".to_string(),
+            format!("\"{}\"", action.unwrap_or("")),
+        );
+    }
+    Ok(SoapEnvelope {
+        body: envelope,
+        headers,
+    })
+}
+
+/// JSON form of an element: its text when it has neither attributes nor
+/// child elements, else an object of attributes (`@name`), children (repeated
+/// ones as arrays) and text (`#text`). Namespace prefixes are dropped.
+fn element_to_json(node: Node) -> Value {
+    let children: Vec<Node> = node.children().filter(Node::is_element).collect();
+    let text: String = node
+        .children()
+        .filter(Node::is_text)
+        .filter_map(|child| child.text())
+        .collect::<String>()
+        .trim()
+        .to_string();
+    let attributes: Vec<_> = node
+        .attributes()
+        .filter(|attribute| attribute.namespace() != Some(XSI_NS))
+        .collect();
+
+    let nil = node
+        .attribute((XSI_NS, "nil"))
+        .is_some_and(|nil| nil == "true" || nil == "1");
+    if nil && children.is_empty() {
+        return Value::Null;
+    }
+    if children.is_empty() && attributes.is_empty() {
+        return Value::String(text);
+    }
+
+    let mut object = Map::new();
+    for attribute in attributes {
+        object.insert(
+            format!("@{}", attribute.name()),
+            Value::String(attribute.value().to_string()),
+        );
+    }
+    for child in children {
+        let name = child.tag_name().name().to_string();
+        let value = element_to_json(child);
+        match object.get_mut(&name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                object.insert(name, value);
+            }
+        }
+    }
+    if !text.is_empty() {
+        object.insert("#text".to_string(), Value::String(text));
+    }
+    Value::Object(object)
+}
+
+/// JSON of the child elements of `node`, keyed by local name.
+fn children_to_json(node: Node) -> Option<Value> {
+    let mut wrapper = Map::new();
+    for child in node.children().filter(Node::is_element) {
+        let name = child.tag_name().name().to_string();
+        let value = element_to_json(child);
+        match wrapper.get_mut(&name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                wrapper.insert(name, value);
+            }
+        }
+    }
+    (!wrapper.is_empty()).then_some(Value::Object(wrapper))
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, name: &str) -> Option<Node<'a, 'i>> {
+    node.children()
+        .find(|child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn text_of(node: Option<Node>) -> Option<String> {
+    node.map(|node| {
+        node.descendants()
+            .filter(Node::is_text)
+            .filter_map(|text| text.text())
+            .collect::<String>()
+            .trim()
+            .to_string()
+    })
+    .filter(|text| !text.is_empty())
+}
+
+fn fault(node: Node, soap_12: bool) -> SoapFault {
+    if soap_12 {
+        SoapFault {
+            code: text_of(child(node, "Code").and_then(|code| child(code, "Value"))),
+            message: text_of(child(node, "Reason").and_then(|reason| child(reason, "Text"))),
+            actor: text_of(child(node, "Role")),
+            detail: child(node, "Detail").and_then(children_to_json),
+        }
+    } else {
+        SoapFault {
+            code: text_of(child(node, "faultcode")),
+            message: text_of(child(node, "faultstring")),
+            actor: text_of(child(node, "faultactor")),
+            detail: child(node, "detail").and_then(children_to_json),
+        }
+    }
+}
+
+fn unwrap_response(xml: &str) -> Result<SoapResponse, String> {
+    let document = Document::parse(xml.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("Invalid XML: {}", e))?;
+    let envelope = document.root_element();
+    let soap_12 = match envelope.tag_name().namespace() {
+        Some(SOAP_11_NS) => false,
+        Some(SOAP_12_NS) => true,
+        _ => return Err("Response is not a SOAP envelope".to_string()),
+    };
+    if envelope.tag_name().name() != "Envelope" {
+        return Err("Response is not a SOAP envelope".to_string());
+    }
+    let body = child(envelope, "Body").ok_or("SOAP envelope has no Body")?;
+    let fault = child(body, "Fault").map(|node| fault(node, soap_12));
+    Ok(SoapResponse {
+        version: if soap_12 { "1.2" } else { "1.1" },
+        header: child(envelope, "Header").and_then(children_to_json),
+        body: if fault.is_some() {
+            None
+        } else {
+            children_to_json(body)
+        },
+        fault,
+    })
+}
+
+/// Build the envelope and headers of a SOAP request.
+#[tauri::command]
+pub fn soap_build_envelope(request: SoapEnvelopeRequest) -> Result<SoapEnvelope, String> {
+    build_envelope(&request)
+}
+
+/// Unwrap the Body or Fault of a SOAP response into JSON.
+#[tauri::command]
+pub fn soap_unwrap_response(body: String) -> Result<SoapResponse, String> {
+    unwrap_response(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn builds_envelopes_and_unwraps_bodies_and_faults() {
+        let envelope = build_envelope(&SoapEnvelopeRequest {
+            operation: "GetWeather".to_string(),
+            namespace: Some("http://example.com/weather".to_string()),
+            soap_action: Some("http://example.com/weather/GetWeather".to_string()),
+            version: None,
+            params: json!({
+                "City": "Köln & Bonn",
+                "Days": [1, 2],
+                "Units": { "@system": "metric", "#text": "C" },
+                "Note": null
+            }),
+        })
+        .unwrap();
+        assert_eq!(
+            envelope.headers["SOAPAction"],
+            "\"http://example.com/weather/GetWeather\""
+        );
+        assert!(envelope.body.contains(
+            "<GetWeather xmlns=\"http://example.com/weather\">\n      <City>Köln &amp; Bonn</City>\n      <Days>1</Days>\n      <Days>2</Days>\n      <Units system=\"metric\">C</Units>\n      <Note xsi:nil=\"true\"/>\n    </GetWeather>"
+        ));
+
+        // What was built unwraps back to the same parameters
+        let unwrapped = unwrap_response(&envelope.body).unwrap();
+        assert_eq!(unwrapped.version, "1.1");
+        assert_eq!(
+            unwrapped.body.unwrap(),
+            json!({ "GetWeather": {
+                "City": "Köln & Bonn",
+                "Days": ["1", "2"],
+                "Units": { "@system": "metric", "#text": "C" },
+                "Note": null
+            } })
+        );
+
+        let fault = unwrap_response(
+            r#"<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+                 <env:Body><env:Fault>
+                   <env:Code><env:Value>env:Sender</env:Value></env:Code>
+                   <env:Reason><env:Text xml:lang="en">Unknown city</env:Text></env:Reason>
+                   <env:Detail><e:Error xmlns:e="urn:e"><e:Id>42</e:Id></e:Error></env:Detail>
+                 </env:Fault></env:Body>
+               </env:Envelope>"#,
+        )
+        .unwrap();
+        assert_eq!(fault.version, "1.2");
+        assert!(fault.body.is_none());
+        let fault = fault.fault.unwrap();
+        assert_eq!(fault.code.as_deref(), Some("env:Sender"));
+        assert_eq!(fault.message.as_deref(), Some("Unknown city"));
+        assert_eq!(fault.detail.unwrap(), json!({ "Error": { "Id": "42" } }));
+
+        assert!(unwrap_response("<html/>").is_err());
+    }
+}
@@ -0,0 +1,534 @@
+//! Upstream change detection for collections imported from an OpenAPI URL.
+//!
+//! The spec a collection was imported from is kept next to the collection
+//! file as [`SPEC_SOURCE_FILE`]. Checks re-download it, diff it against that
+//! baseline and classify each change as breaking (removed operations, new or
+//! newly required parameters and bodies) or not. Changes are announced with a
+//! `spec-sync-event`; breaking ones leave the baseline untouched until the user
+//! accepts the new version, so they keep being reported instead of being
+//! silently absorbed.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::collections::{get_collection_index, resolve_collection_dir};
+use super::proxy::ProxyState;
+
+const SPEC_SOURCE_FILE: &str = "openapi-source.json";
+/// How often imported specs are checked in the background.
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const HTTP_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpecSource {
+    url: String,
+    imported_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_checked_at: Option<i64>,
+    spec: Value,
+    /// Newer version holding breaking changes, waiting to be accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending_spec: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SpecChangeKind {
+    EndpointAdded,
+    EndpointRemoved,
+    EndpointDeprecated,
+    ParameterAdded,
+    ParameterRemoved,
+    ParameterRequired,
+    ParameterOptional,
+    RequestBodyRequired,
+    RequestBodyOptional,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecChange {
+    pub kind: SpecChangeKind,
+    pub breaking: bool,
+    pub method: String,
+    pub path: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecSyncReport {
+    pub collection_id: String,
+    pub url: String,
+    pub checked_at: i64,
+    pub breaking: usize,
+    pub changes: Vec<SpecChange>,
+}
+
+fn source_path(dir: &Path) -> PathBuf {
+    dir.join(SPEC_SOURCE_FILE)
+}
+
+fn read_source(dir: &Path) -> Result<Option<SpecSource>, String> {
+    let path = source_path(dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", SPEC_SOURCE_FILE, e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", SPEC_SOURCE_FILE, e))
+}
+
+fn write_source(dir: &Path, source: &SpecSource) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(source)
+        .map_err(|e| format!("Failed to serialize {}: {}", SPEC_SOURCE_FILE, e))?;
+    std::fs::write(source_path(dir), content)
+        .map_err(|e| format!("Failed to write {}: {}", SPEC_SOURCE_FILE, e))
+}
+
+/// Remember the spec a collection was imported from so later checks can
+/// diff against it.
+pub(crate) fn record_spec_source(
+    app: &AppHandle,
+    collection_id: &str,
+    url: &str,
+    spec: Value,
+) -> Result<(), String> {
+    let Some(dir) = resolve_collection_dir(app, collection_id)? else {
+        return Ok(());
+    };
+    write_source(
+        &dir,
+        &SpecSource {
+            url: url.to_string(),
+            imported_at: chrono::Utc::now().timestamp_millis(),
+            last_checked_at: None,
+            spec,
+            pending_spec: None,
+        },
+    )
+}
+
+async fn fetch_spec(proxy_state: &ProxyState, url: &str) -> Result<Value, String> {
+    proxy_state.check_offline(url)?;
+    let response = proxy_state
+        .plain_client()?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: {}", url, response.status()));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+    serde_yaml_ng::from_str(&content).map_err(|e| format!("Failed to parse OpenAPI spec: {}", e))
+}
+
+/// Follow a local `$ref` (`#/components/parameters/Id`) within the spec.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    value
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'))
+        .and_then(|pointer| spec.pointer(pointer))
+        .unwrap_or(value)
+}
+
+/// Path with template variable names erased, so renaming `{id}` to
+/// `{userId}` is not reported as a removed and an added endpoint.
+fn path_shape(path: &str) -> String {
+    let mut shape = String::with_capacity(path.len());
+    let mut in_variable = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_variable = true;
+                shape.push_str("{}");
+            }
+            '}' => in_variable = false,
+            _ if !in_variable => shape.push(c),
+            _ => {}
+        }
+    }
+    shape
+}
+
+struct Operation<'a> {
+    method: String,
+    path: &'a str,
+    deprecated: bool,
+    /// `(location, name)` → required, path variables excluded
+    parameters: BTreeMap<(String, String), bool>,
+    /// `None` when the operation takes no body
+    body_required: Option<bool>,
+}
+
+fn operations(spec: &Value) -> BTreeMap<(String, String), Operation<'_>> {
+    let mut operations = BTreeMap::new();
+    let Some(paths) = spec.get("paths").and_then(Value::as_object) else {
+        return operations;
+    };
+
+    for (path, item) in paths {
+        let item = resolve(spec, item);
+        let shared = item.get("parameters").and_then(Value::as_array);
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            let mut parameters = BTreeMap::new();
+            let own = operation.get("parameters").and_then(Value::as_array);
+            // Operation-level parameters override path-level ones
+            for parameter in shared.into_iter().chain(own).flatten() {
+                let parameter = resolve(spec, parameter);
+                let (Some(location), Some(name)) = (
+                    parameter.get("in").and_then(Value::as_str),
+                    parameter.get("name").and_then(Value::as_str),
+                ) else {
+                    continue;
+                };
+                // Path variables are covered by the path itself
+                if location == "path" {
+                    continue;
+                }
+                let required = parameter
+                    .get("required")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                parameters.insert((location.to_string(), name.to_string()), required);
+            }
+
+            let body_required = operation.get("requestBody").map(|body| {
+                resolve(spec, body)
+                    .get("required")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            });
+
+            operations.insert(
+                (method.to_string(), path_shape(path)),
+                Operation {
+                    method: method.to_uppercase(),
+                    path,
+                    deprecated: operation
+                        .get("deprecated")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                    parameters,
+                    body_required,
+                },
+            );
+        }
+    }
+    operations
+}
+
+/// Classify every operation-level difference between two versions of a spec.
+pub(crate) fn diff_specs(old: &Value, new: &Value) -> Vec<SpecChange> {
+    let before = operations(old);
+    let after = operations(new);
+    let mut changes = Vec::new();
+
+    let change = |kind, breaking, operation: &Operation, detail: String| SpecChange {
+        kind,
+        breaking,
+        method: operation.method.clone(),
+        path: operation.path.to_string(),
+        detail,
+    };
+
+    for (key, old_op) in &before {
+        let Some(new_op) = after.get(key) else {
+            changes.push(change(
+                SpecChangeKind::EndpointRemoved,
+                true,
+                old_op,
+                "Endpoint was removed".to_string(),
+            ));
+            continue;
+        };
+
+        if new_op.deprecated && !old_op.deprecated {
+            changes.push(change(
+                SpecChangeKind::EndpointDeprecated,
+                false,
+                new_op,
+                "Endpoint is now deprecated".to_string(),
+            ));
+        }
+
+        for ((location, name), required) in &new_op.parameters {
+            let label = format!("{} parameter '{}'", location, name);
+            match old_op.parameters.get(&(location.clone(), name.clone())) {
+                None => changes.push(change(
+                    SpecChangeKind::ParameterAdded,
+                    *required,
+                    new_op,
+                    if *required {
+                        format!("New required {}", label)
+                    } else {
+                        format!("New optional {}", label)
+                    },
+                )),
+                Some(false) if *required => changes.push(change(
+                    SpecChangeKind::ParameterRequired,
+                    true,
+                    new_op,
+                    format!("The {} is now required", label),
+                )),
+                Some(true) if !*required => changes.push(change(
+                    SpecChangeKind::ParameterOptional,
+                    false,
+                    new_op,
+                    format!("The {} is now optional", label),
+                )),
+                _ => {}
+            }
+        }
+        for (location, name) in old_op.parameters.keys() {
+            if !new_op
+                .parameters
+                .contains_key(&(location.clone(), name.clone()))
+            {
+                changes.push(change(
+                    SpecChangeKind::ParameterRemoved,
+                    false,
+                    new_op,
+                    format!("The {} parameter '{}' was removed", location, name),
+                ));
+            }
+        }
+
+        match (old_op.body_required, new_op.body_required) {
+            (None | Some(false), Some(true)) => changes.push(change(
+                SpecChangeKind::RequestBodyRequired,
+                true,
+                new_op,
+                "A request body is now required".to_string(),
+            )),
+            (Some(true), None | Some(false)) => changes.push(change(
+                SpecChangeKind::RequestBodyOptional,
+                false,
+                new_op,
+                "The request body is no longer required".to_string(),
+            )),
+            _ => {}
+        }
+    }
+
+    for (key, new_op) in &after {
+        if !before.contains_key(key) {
+            changes.push(change(
+                SpecChangeKind::EndpointAdded,
+                false,
+                new_op,
+                "New endpoint".to_string(),
+            ));
+        }
+    }
+
+    changes
+}
+
+async fn check_collection(
+    app: &AppHandle,
+    collection_id: &str,
+    dir: &Path,
+) -> Result<Option<SpecSyncReport>, String> {
+    let Some(mut source) = read_source(dir)? else {
+        return Ok(None);
+    };
+
+    let latest = fetch_spec(&app.state::<ProxyState>(), &source.url).await?;
+    let changes = diff_specs(&source.spec, &latest);
+    let breaking = changes.iter().filter(|change| change.breaking).count();
+    let checked_at = chrono::Utc::now().timestamp_millis();
+
+    source.last_checked_at = Some(checked_at);
+    if breaking > 0 {
+        source.pending_spec = Some(latest);
+    } else {
+        // Nothing a client could trip over: follow upstream directly
+        source.spec = latest;
+        source.pending_spec = None;
+    }
+    write_source(dir, &source)?;
+
+    let report = SpecSyncReport {
+        collection_id: collection_id.to_string(),
+        url: source.url,
+        checked_at,
+        breaking,
+        changes,
+    };
+    if !report.changes.is_empty() {
+        let _ = app.emit("spec-sync-event", &report);
+    }
+    Ok(Some(report))
+}
+
+/// Re-download the spec a collection was imported from and report what
+/// changed upstream. `None` when the collection was not imported from a URL.
+#[tauri::command]
+pub async fn spec_sync_check(
+    app: AppHandle,
+    collection_id: String,
+) -> Result<Option<SpecSyncReport>, String> {
+    let Some(dir) = resolve_collection_dir(&app, &collection_id)? else {
+        return Err(format!("Collection {} not found", collection_id));
+    };
+    check_collection(&app, &collection_id, &dir).await
+}
+
+/// Adopt the pending upstream version as the new baseline once its breaking
+/// changes have been reviewed.
+#[tauri::command]
+pub async fn spec_sync_accept(app: AppHandle, collection_id: String) -> Result<bool, String> {
+    let Some(dir) = resolve_collection_dir(&app, &collection_id)? else {
+        return Err(format!("Collection {} not found", collection_id));
+    };
+    let Some(mut source) = read_source(&dir)? else {
+        return Ok(false);
+    };
+    let Some(pending) = source.pending_spec.take() else {
+        return Ok(false);
+    };
+    source.spec = pending;
+    write_source(&dir, &source)?;
+    Ok(true)
+}
+
+/// Check every URL-imported collection in the background for the lifetime
+/// of the app. Failures (offline, spec moved) are skipped until the next round.
+pub fn start_spec_sync(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let index = get_collection_index(&app).unwrap_or_default();
+            for (collection_id, dir) in index {
+                let _ = check_collection(&app, &collection_id, Path::new(&dir)).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(paths: Value) -> Value {
+        json!({
+            "openapi": "3.0.0",
+            "components": {
+                "parameters": {
+                    "Limit": { "name": "limit", "in": "query", "required": false }
+                }
+            },
+            "paths": paths
+        })
+    }
+
+    fn kinds(changes: &[SpecChange]) -> Vec<(SpecChangeKind, bool)> {
+        changes
+            .iter()
+            .map(|change| (change.kind, change.breaking))
+            .collect()
+    }
+
+    #[test]
+    fn removed_endpoint_is_breaking_and_added_is_not() {
+        let old = spec(json!({ "/users": { "get": {}, "delete": {} } }));
+        let new = spec(json!({ "/users": { "get": {}, "post": {} } }));
+
+        let changes = diff_specs(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![
+                (SpecChangeKind::EndpointRemoved, true),
+                (SpecChangeKind::EndpointAdded, false),
+            ]
+        );
+        assert_eq!(changes[0].method, "DELETE");
+        assert_eq!(changes[1].method, "POST");
+    }
+
+    #[test]
+    fn parameter_requirement_changes_are_classified() {
+        let old = spec(json!({ "/users": { "get": { "parameters": [
+            { "$ref": "#/components/parameters/Limit" },
+            { "name": "sort", "in": "query", "required": true },
+            { "name": "page", "in": "query" }
+        ] } } }));
+        let new = spec(json!({ "/users": { "get": { "parameters": [
+            { "name": "limit", "in": "query", "required": true },
+            { "name": "sort", "in": "query" },
+            { "name": "tenant", "in": "header", "required": true },
+            { "name": "fields", "in": "query" }
+        ] } } }));
+
+        let changes = diff_specs(&old, &new);
+        assert_eq!(
+            kinds(&changes),
+            vec![
+                (SpecChangeKind::ParameterAdded, true),
+                (SpecChangeKind::ParameterAdded, false),
+                (SpecChangeKind::ParameterRequired, true),
+                (SpecChangeKind::ParameterOptional, false),
+                (SpecChangeKind::ParameterRemoved, false),
+            ]
+        );
+        assert_eq!(changes[0].detail, "New required header parameter 'tenant'");
+    }
+
+    #[test]
+    fn renamed_path_variables_and_bodies() {
+        let old = spec(json!({ "/users/{id}": { "put": {
+            "parameters": [{ "name": "id", "in": "path" }],
+            "requestBody": { "content": {} }
+        } } }));
+        let new = spec(json!({ "/users/{id}": { "put": {
+            "parameters": [{ "name": "id", "in": "path" }],
+            "requestBody": { "required": true, "content": {} },
+            "deprecated": true
+        } } }));
+
+        assert_eq!(
+            kinds(&diff_specs(&old, &new)),
+            vec![
+                (SpecChangeKind::EndpointDeprecated, false),
+                (SpecChangeKind::RequestBodyRequired, true),
+            ]
+        );
+
+        let renamed = spec(json!({ "/users/{userId}": { "put": {
+            "parameters": [{ "name": "userId", "in": "path" }],
+            "requestBody": { "content": {} }
+        } } }));
+        assert!(diff_specs(&old, &renamed).is_empty());
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        let doc = spec(json!({ "/users": { "get": { "parameters": [
+            { "$ref": "#/components/parameters/Limit" }
+        ] } } }));
+        assert!(diff_specs(&doc, &doc).is_empty());
+    }
+}
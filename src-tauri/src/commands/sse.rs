@@ -3,10 +3,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 
+use super::proxy::ProxyState;
+
 pub struct SseState {
     connections: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
 }
@@ -147,8 +149,10 @@ async fn run_stream(
     headers: HashMap<String, String>,
     initial_last_event_id: Option<String>,
 ) {
+    let redirect = app.state::<ProxyState>().redirect_policy();
     let client = match reqwest::Client::builder()
         .pool_idle_timeout(Duration::from_secs(0))
+        .redirect(redirect)
         .build()
     {
         Ok(c) => c,
@@ -417,6 +421,7 @@ pub async fn sse_connect(
     if request.url.trim().is_empty() {
         return Err("SSE URL is required".to_string());
     }
+    app.state::<ProxyState>().check_offline(&request.url)?;
 
     // Close any existing connection on this tab.
     {
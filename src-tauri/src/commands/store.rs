@@ -1,8 +1,89 @@
 use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
-const STORE_FILE: &str = "resonance-store.json";
+use super::workspaces::store_file;
+
+/// Held across read-modify-write cycles on store values so two commands (or
+/// two windows) cannot interleave them and drop each other's changes.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Serializes writes of store files to disk.
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A store file read straight from disk, for the headless CLI, which runs
+/// without the app and so without the store plugin. A missing or unreadable
+/// file reads as empty.
+pub(crate) struct SavedStore(serde_json::Map<String, Value>);
+
+impl SavedStore {
+    pub(crate) fn read(path: &Path) -> Self {
+        let entries = fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self(entries)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<Value> {
+        self.0.get(key).cloned()
+    }
+}
+
+/// Take the store write lock. Must not be held across an `.await`.
+pub(crate) fn lock_store() -> MutexGuard<'static, ()> {
+    WRITE_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Load a store file with auto-save disabled, so it only reaches disk through
+/// [`save_store_file`]. Later `app.store(file)` calls return this instance.
+pub(crate) fn open_store(app: &AppHandle, file: &str) -> Result<(), String> {
+    app.store_builder(file)
+        .disable_auto_save()
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open store {}: {}", file, e))
+}
+
+/// Write `bytes` to a temporary file next to `path` and rename it over
+/// `path`, so readers and crashes only ever see a complete file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp_name = path.as_os_str().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = Path::new(&tmp_name);
+    {
+        let mut file = fs::File::create(tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    fs::rename(tmp, path)
+}
+
+/// Save a store file atomically.
+pub(crate) fn save_store_file(app: &AppHandle, file: &str) -> Result<(), String> {
+    let store = app
+        .store(file)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let path = tauri_plugin_store::resolve_store_path(app, file)
+        .map_err(|e| format!("Failed to resolve store path: {}", e))?;
+
+    let _guard = SAVE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let entries: serde_json::Map<String, Value> = store.entries().into_iter().collect();
+    let bytes = serde_json::to_vec_pretty(&entries).map_err(|e| e.to_string())?;
+    write_atomic(&path, &bytes).map_err(|e| format!("Failed to save store: {}", e))
+}
+
+/// Save the active workspace's store atomically.
+pub(crate) fn save_store(app: &AppHandle) -> Result<(), String> {
+    save_store_file(app, &store_file(app))
+}
 
 fn get_default_for_key(key: &str) -> Value {
     match key {
@@ -10,19 +91,22 @@ fn get_default_for_key(key: &str) -> Value {
         "environments" => serde_json::json!([]),
         "activeEnvironmentId" => Value::Null,
         "requestHistory" => serde_json::json!([]),
-        "cookieJar" => serde_json::json!([]),
+        "cookieJar" => serde_json::json!({}),
         "workspaceTabs" => serde_json::json!([]),
         "activeWorkspaceTabId" => Value::Null,
         "theme" => serde_json::json!("system"),
         "accentColor" => serde_json::json!("blue"),
         "proxySettings" => serde_json::json!({
             "enabled": false,
-            "mode": "manual",
-            "manualConfig": {
-                "httpProxy": "",
-                "httpsProxy": "",
-                "noProxy": ""
-            }
+            "useSystemProxy": false,
+            "type": "http",
+            "host": "",
+            "port": 8080,
+            "httpsProxy": null,
+            "grpcProxy": null,
+            "auth": { "enabled": false, "username": "", "password": "" },
+            "bypassList": [],
+            "timeout": 10000
         }),
         "mockServerSettings" => serde_json::json!({
             "port": 3001,
@@ -47,7 +131,7 @@ fn get_default_for_key(key: &str) -> Value {
 
 #[tauri::command]
 pub async fn store_get(app: AppHandle, key: String) -> Result<Value, String> {
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
 
     let value = store.get(&key).unwrap_or(Value::Null);
 
@@ -60,12 +144,11 @@ pub async fn store_get(app: AppHandle, key: String) -> Result<Value, String> {
 
 #[tauri::command]
 pub async fn store_set(app: AppHandle, key: String, value: Value) -> Result<(), String> {
-    let store = app.store(STORE_FILE).map_err(|e| e.to_string())?;
+    let store = app.store(store_file(&app)).map_err(|e| e.to_string())?;
 
+    let _guard = lock_store();
     store.set(key, value);
-    store.save().map_err(|e| e.to_string())?;
-
-    Ok(())
+    save_store(&app)
 }
 
 #[tauri::command]
@@ -89,3 +172,21 @@ pub async fn settings_get(app: AppHandle) -> Result<Value, String> {
 pub async fn settings_set(app: AppHandle, settings: Value) -> Result<(), String> {
     store_set(app, "settings".to_string(), settings).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atomic_write_replaces_the_file_and_leaves_no_temp_file() {
+        let dir = std::env::temp_dir().join(format!("resonance-store-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("store.json");
+
+        write_atomic(&path, b"{\"a\":1}").unwrap();
+        write_atomic(&path, b"{\"a\":2}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\":2}");
+        assert!(!dir.join("store.json.tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+//! Starter test scripts generated from an endpoint's last recorded response
+//! (the newest history entry sent from it): the status, the content type and
+//! the top-level JSON properties with their types.
+
+use serde_json::Value;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::scripts::{script_get, script_save, ScriptData};
+use super::workspaces::store_file;
+
+const HISTORY_KEY: &str = "requestHistory";
+/// Properties beyond this many are left out of the generated assertions.
+const MAX_PROPERTIES: usize = 20;
+
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn type_assertion(accessor: &str, value: &Value) -> String {
+    match value {
+        Value::Null => format!("expect({}).toBeNull();", accessor),
+        Value::Array(_) => format!("expect(Array.isArray({})).toBe(true);", accessor),
+        Value::Bool(_) => format!("expect(typeof {}).toBe('boolean');", accessor),
+        Value::Number(_) => format!("expect(typeof {}).toBe('number');", accessor),
+        Value::String(_) => format!("expect(typeof {}).toBe('string');", accessor),
+        Value::Object(_) => format!("expect(typeof {}).toBe('object');", accessor),
+    }
+}
+
+/// Property and type assertions for the fields of `object`, read through
+/// `accessor` (`response.body` or `response.body[0]`).
+fn property_assertions(accessor: &str, object: &serde_json::Map<String, Value>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (key, value) in object.iter().take(MAX_PROPERTIES) {
+        let key = js_string(key);
+        lines.push(format!("expect({}).toHaveProperty({});", accessor, key));
+        lines.push(type_assertion(&format!("{}[{}]", accessor, key), value));
+    }
+    lines
+}
+
+fn test_block(name: &str, lines: &[String]) -> String {
+    let body: Vec<String> = lines.iter().map(|line| format!("    {}", line)).collect();
+    format!(
+        "test({}, function() {{\n{}\n}});",
+        js_string(name),
+        body.join("\n")
+    )
+}
+
+/// Test script for a recorded response (`{ status, headers, data }` as kept in
+/// history).
+fn generate_test_script(response: &Value) -> Result<String, String> {
+    let status = response
+        .get("status")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "The last response has no status code".to_string())?;
+    let mut tests = vec![test_block(
+        &format!("Status is {}", status),
+        &[format!("expect(response.status).toBe({});", status)],
+    )];
+
+    let content_type = response
+        .get("headers")
+        .and_then(Value::as_object)
+        .and_then(|headers| {
+            headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        })
+        .and_then(|(name, value)| Some((name.clone(), value.as_str()?.to_string())));
+    if let Some((header, value)) = content_type {
+        let mime = value
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if !mime.is_empty() {
+            tests.push(test_block(
+                &format!("Content-Type is {}", mime),
+                &[format!(
+                    "expect(response.headers[{}]).toContain({});",
+                    js_string(&header),
+                    js_string(&mime)
+                )],
+            ));
+        }
+    }
+
+    // History keeps JSON bodies parsed, but text that happens to be JSON too
+    let body = match response.get("data") {
+        Some(Value::String(text)) => serde_json::from_str(text).unwrap_or(Value::Null),
+        Some(data) => data.clone(),
+        None => Value::Null,
+    };
+    match &body {
+        Value::Object(object) if !object.is_empty() => {
+            tests.push(test_block(
+                "Body has the expected properties",
+                &property_assertions("response.body", object),
+            ));
+        }
+        Value::Array(items) => {
+            let mut lines = vec!["expect(Array.isArray(response.body)).toBe(true);".to_string()];
+            if let Some(Value::Object(first)) = items.first() {
+                lines.push("expect(response.body.length).toBeGreaterThan(0);".to_string());
+                lines.extend(property_assertions("response.body[0]", first));
+            }
+            tests.push(test_block("Body is a list of the expected items", &lines));
+        }
+        _ => {}
+    }
+
+    Ok(tests.join("\n\n"))
+}
+
+fn last_response(app: &AppHandle, collection_id: &str, endpoint_id: &str) -> Result<Value, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let history = store.get(HISTORY_KEY).unwrap_or(Value::Null);
+    // History is kept newest first
+    history
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .pointer("/request/collectionId")
+                .and_then(Value::as_str)
+                == Some(collection_id)
+                && entry.pointer("/request/endpointId").and_then(Value::as_str) == Some(endpoint_id)
+        })
+        .find_map(|entry| entry.get("response").filter(|r| r.is_object()).cloned())
+        .ok_or_else(|| {
+            "No recorded response for this endpoint yet. Send the request first.".to_string()
+        })
+}
+
+/// Generates tests from the endpoint's last response and saves them after
+/// any test script it already has. Returns the saved scripts.
+#[tauri::command]
+pub async fn script_generate_tests(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<ScriptData, String> {
+    let response = last_response(&app, &collection_id, &endpoint_id)?;
+    let generated = generate_test_script(&response)?;
+
+    let mut scripts = script_get(app.clone(), collection_id.clone(), endpoint_id.clone()).await?;
+    scripts.test_script = if scripts.test_script.trim().is_empty() {
+        format!("{}\n", generated)
+    } else {
+        format!("{}\n\n{}\n", scripts.test_script.trim_end(), generated)
+    };
+    script_save(app, collection_id, endpoint_id, scripts.clone()).await?;
+    Ok(scripts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_status_content_type_and_property_tests() {
+        let response = serde_json::json!({
+            "status": 201,
+            "headers": { "Content-Type": "application/json; charset=utf-8" },
+            "data": { "id": 7, "name": "Rex", "tags": [], "owner": null }
+        });
+
+        let script = generate_test_script(&response).unwrap();
+        assert!(script.starts_with(
+            "test(\"Status is 201\", function() {\n    expect(response.status).toBe(201);\n});"
+        ));
+        assert!(script.contains(
+            "expect(response.headers[\"Content-Type\"]).toContain(\"application/json\");"
+        ));
+        assert!(script.contains("expect(response.body).toHaveProperty(\"id\");"));
+        assert!(script.contains("expect(typeof response.body[\"id\"]).toBe('number');"));
+        assert!(script.contains("expect(Array.isArray(response.body[\"tags\"])).toBe(true);"));
+        assert!(script.contains("expect(response.body[\"owner\"]).toBeNull();"));
+
+        let list = serde_json::json!({ "status": 200, "data": "[{\"id\": 1}]" });
+        let script = generate_test_script(&list).unwrap();
+        assert!(script.contains("expect(response.body[0]).toHaveProperty(\"id\");"));
+
+        assert!(generate_test_script(&serde_json::json!({ "status": null })).is_err());
+    }
+}
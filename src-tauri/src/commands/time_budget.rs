@@ -0,0 +1,59 @@
+//! Response time budgets: an endpoint may carry a `timeBudgetMs` SLO in the
+//! collection file. Responses slower than the budget are flagged, both on
+//! single sends and in runner results, without changing whether the request
+//! passed.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Field of an endpoint in the collection file holding its budget in ms.
+const TIME_BUDGET_FIELD: &str = "timeBudgetMs";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCheck {
+    pub budget_ms: u64,
+    pub elapsed_ms: u64,
+    /// The response took longer than the budget
+    pub exceeded: bool,
+}
+
+impl BudgetCheck {
+    pub fn new(budget_ms: u64, elapsed_ms: u64) -> Self {
+        Self {
+            budget_ms,
+            elapsed_ms,
+            exceeded: elapsed_ms > budget_ms,
+        }
+    }
+}
+
+/// The endpoint's time budget; unset and zero both mean none.
+pub(crate) fn endpoint_time_budget(endpoint: &Value) -> Option<u64> {
+    endpoint
+        .get(TIME_BUDGET_FIELD)
+        .and_then(Value::as_u64)
+        .filter(|budget| *budget > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_budget_and_flags_slow_responses() {
+        let endpoint = serde_json::json!({ "id": "e1", "timeBudgetMs": 300 });
+        assert_eq!(endpoint_time_budget(&endpoint), Some(300));
+        assert_eq!(
+            endpoint_time_budget(&serde_json::json!({ "timeBudgetMs": 0 })),
+            None
+        );
+        assert_eq!(
+            endpoint_time_budget(&serde_json::json!({ "id": "e2" })),
+            None
+        );
+
+        assert!(!BudgetCheck::new(300, 300).exceeded);
+        assert!(BudgetCheck::new(300, 301).exceeded);
+    }
+}
@@ -1,11 +1,23 @@
 //! Shared TLS building blocks used by the HTTP timing probe, the gRPC
 //! channel builder, and the MQTT transport: the danger accept-all
 //! certificate verifier, PEM loading/parsing helpers for client identities
-//! and CA bundles, and rustls client config builders.
+//! and CA bundles, rustls client config builders, certificate pinning, and
+//! per-host trust exceptions.
 
-use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use base64::Engine;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{WantsClientCert, WebPkiServerVerifier};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
+/// Endpoint field turning certificate verification off for that request only.
+const IGNORE_TLS_ERRORS_FIELD: &str = "ignoreTlsErrors";
+
+/// Start of the handshake error raised for a pin mismatch, so the request
+/// error can be recognised and reported as such.
+pub(crate) const PIN_MISMATCH: &str = "Certificate pin mismatch";
+
 /// PEM bytes of a client identity: (certificate chain, private key).
 pub(crate) type IdentityPems = (Vec<u8>, Vec<u8>);
 
@@ -140,16 +152,7 @@ pub(crate) fn build_danger_tls_config(
         .map_err(|e| format!("TLS protocol configuration error: {}", e))?
         .dangerous()
         .with_custom_certificate_verifier(Arc::new(NoCertVerifier));
-
-    match identity {
-        Some((cert_pem, key_pem)) => {
-            let (certs, key) = parse_identity(&cert_pem, &key_pem)?;
-            builder
-                .with_client_auth_cert(certs, key)
-                .map_err(|e| format!("Client certificate could not be loaded: {}", e))
-        }
-        None => Ok(builder.with_no_client_auth()),
-    }
+    with_identity(builder, identity)
 }
 
 /// Skip-verify config for gRPC channels: ALPN pinned to h2.
@@ -161,13 +164,24 @@ pub(crate) fn build_danger_grpc_tls_config(
     Ok(config)
 }
 
-/// Build a verifying rustls client config: webpki roots plus an optional
-/// custom CA bundle appended, and an optional client identity (mTLS).
-/// No ALPN is set.
-pub(crate) fn build_verifying_tls_config(
-    ca_pem: Option<Vec<u8>>,
+/// Present the client identity (mTLS), if any, and finish the config.
+fn with_identity(
+    builder: rustls::ConfigBuilder<rustls::ClientConfig, WantsClientCert>,
     identity: Option<IdentityPems>,
 ) -> Result<rustls::ClientConfig, String> {
+    match identity {
+        Some((cert_pem, key_pem)) => {
+            let (certs, key) = parse_identity(&cert_pem, &key_pem)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| format!("Client certificate could not be loaded: {}", e))
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Webpki roots plus the CAs of an optional custom bundle.
+fn root_store(ca_pem: Option<Vec<u8>>) -> Result<rustls::RootCertStore, String> {
     let mut root_store = rustls::RootCertStore::empty();
     root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
 
@@ -188,24 +202,534 @@ pub(crate) fn build_verifying_tls_config(
             })?;
         }
     }
+    Ok(root_store)
+}
 
+/// Build a verifying rustls client config: webpki roots plus an optional
+/// custom CA bundle appended, and an optional client identity (mTLS).
+/// No ALPN is set.
+pub(crate) fn build_verifying_tls_config(
+    ca_pem: Option<Vec<u8>>,
+    identity: Option<IdentityPems>,
+) -> Result<rustls::ClientConfig, String> {
+    let root_store = root_store(ca_pem)?;
     let provider = Arc::new(rustls::crypto::ring::default_provider());
     let builder = rustls::ClientConfig::builder_with_provider(provider)
         .with_safe_default_protocol_versions()
         .map_err(|e| format!("TLS protocol configuration error: {}", e))?
         .with_root_certificates(root_store);
+    with_identity(builder, identity)
+}
 
-    match identity {
-        Some((cert_pem, key_pem)) => {
-            let (certs, key) = parse_identity(&cert_pem, &key_pem)?;
-            builder
-                .with_client_auth_cert(certs, key)
-                .map_err(|e| format!("Client certificate could not be loaded: {}", e))
+/// Read one DER element: `(tag, content, rest)`.
+pub(crate) fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
         }
-        None => Ok(builder.with_no_client_auth()),
+        let len = rest[..count]
+            .iter()
+            .fold(0usize, |len, &byte| (len << 8) | byte as usize);
+        (len, &rest[count..])
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+/// The DER-encoded `SubjectPublicKeyInfo` of an X.509 certificate.
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    let (0x30, certificate, _) = der_element(certificate)? else {
+        return None;
+    };
+    let (0x30, tbs, _) = der_element(certificate)? else {
+        return None;
+    };
+    // Skip the optional [0] version, then serial, signature, issuer, validity
+    // and subject.
+    let mut rest = tbs;
+    if der_element(rest)?.0 == 0xa0 {
+        rest = der_element(rest)?.2;
+    }
+    for _ in 0..5 {
+        rest = der_element(rest)?.2;
+    }
+    let (0x30, _, after) = der_element(rest)? else {
+        return None;
+    };
+    Some(&rest[..rest.len() - after.len()])
+}
+
+/// Names and validity period of an X.509 certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CertificateSummary {
+    /// Common name of the subject, else the whole name as `OID=value` pairs
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: chrono::DateTime<chrono::Utc>,
+    pub not_after: chrono::DateTime<chrono::Utc>,
+}
+
+/// The readable value of an X.501 name: its common name when it has one,
+/// else its attribute values joined with commas.
+fn der_name(name: &[u8]) -> String {
+    let mut values = Vec::new();
+    let mut common_name = None;
+    let mut sets = name;
+    while let Some((_, set, rest)) = der_element(sets) {
+        sets = rest;
+        let Some((0x30, attribute, _)) = der_element(set) else {
+            continue;
+        };
+        let Some((0x06, oid, value)) = der_element(attribute) else {
+            continue;
+        };
+        let Some((tag, value, _)) = der_element(value) else {
+            continue;
+        };
+        let value = match tag {
+            // BMPString is UTF-16
+            0x1e => String::from_utf16_lossy(
+                &value
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => String::from_utf8_lossy(value).to_string(),
+        };
+        if oid == [0x55, 0x04, 0x03] {
+            common_name = Some(value.clone());
+        }
+        values.push(value);
+    }
+    common_name.unwrap_or_else(|| values.join(", "))
+}
+
+/// A DER UTCTime (`YYMMDDHHMMSSZ`) or GeneralizedTime (`YYYYMMDDHHMMSSZ`).
+fn der_time(tag: u8, value: &[u8]) -> Option<chrono::DateTime<chrono::Utc>> {
+    let value = std::str::from_utf8(value).ok()?;
+    let value = match tag {
+        // Two-digit years 50-99 are 19xx (RFC 5280)
+        0x17 => match value.get(..2)?.parse::<u32>().ok()? {
+            year if year >= 50 => format!("19{}", value),
+            _ => format!("20{}", value),
+        },
+        0x18 => value.to_string(),
+        _ => return None,
+    };
+    chrono::NaiveDateTime::parse_from_str(&value, "%Y%m%d%H%M%SZ")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+/// Subject, issuer and validity of a DER certificate.
+pub(crate) fn certificate_summary(certificate: &[u8]) -> Option<CertificateSummary> {
+    let (0x30, certificate, _) = der_element(certificate)? else {
+        return None;
+    };
+    let (0x30, tbs, _) = der_element(certificate)? else {
+        return None;
+    };
+    // Skip the optional [0] version, serial and signature algorithm
+    let mut rest = tbs;
+    if der_element(rest)?.0 == 0xa0 {
+        rest = der_element(rest)?.2;
+    }
+    rest = der_element(rest)?.2;
+    rest = der_element(rest)?.2;
+    let (0x30, issuer, rest) = der_element(rest)? else {
+        return None;
+    };
+    let (0x30, validity, rest) = der_element(rest)? else {
+        return None;
+    };
+    let (0x30, subject, _) = der_element(rest)? else {
+        return None;
+    };
+    let (before_tag, not_before, validity) = der_element(validity)?;
+    let (after_tag, not_after, _) = der_element(validity)?;
+    Some(CertificateSummary {
+        subject: der_name(subject),
+        issuer: der_name(issuer),
+        not_before: der_time(before_tag, not_before)?,
+        not_after: der_time(after_tag, not_after)?,
+    })
+}
+
+/// A pinned server certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CertificatePin {
+    /// SHA-256 of the certificate's public key (`sha256/<base64>`, the
+    /// format of OkHttp and HPKP), which survives certificate renewals.
+    PublicKey(Vec<u8>),
+    /// SHA-256 fingerprint of the whole certificate, in hex
+    /// (`openssl x509 -noout -fingerprint -sha256`).
+    Certificate(Vec<u8>),
+}
+
+impl CertificatePin {
+    pub(crate) fn parse(pin: &str) -> Result<Self, String> {
+        let pin = pin.trim();
+        let (digest, public_key) = match pin.strip_prefix("sha256/") {
+            Some(encoded) => (
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .ok(),
+                true,
+            ),
+            None => (hex::decode(pin.replace([':', ' '], "")).ok(), false),
+        };
+        match digest {
+            Some(digest) if digest.len() == 32 && public_key => Ok(CertificatePin::PublicKey(digest)),
+            Some(digest) if digest.len() == 32 => Ok(CertificatePin::Certificate(digest)),
+            _ => Err(format!(
+                "Invalid pin {}: expected sha256/<base64 public key hash> or a hex SHA-256 fingerprint",
+                pin
+            )),
+        }
+    }
+
+    pub(crate) fn matches(&self, certificate: &[u8]) -> bool {
+        match self {
+            CertificatePin::PublicKey(digest) => subject_public_key_info(certificate)
+                .is_some_and(|spki| Sha256::digest(spki).as_slice() == digest.as_slice()),
+            CertificatePin::Certificate(digest) => {
+                Sha256::digest(certificate).as_slice() == digest.as_slice()
+            }
+        }
+    }
+}
+
+/// Whether the endpoint skips certificate verification, whatever the global
+/// setting says.
+pub(crate) fn endpoint_ignores_tls_errors(endpoint: &serde_json::Value) -> bool {
+    endpoint
+        .get(IGNORE_TLS_ERRORS_FIELD)
+        .and_then(serde_json::Value::as_bool)
+        == Some(true)
+}
+
+/// The `sha256/<base64>` pin of a certificate's public key.
+fn public_key_pin(certificate: &[u8]) -> Option<String> {
+    let spki = subject_public_key_info(certificate)?;
+    Some(format!(
+        "sha256/{}",
+        base64::engine::general_purpose::STANDARD.encode(Sha256::digest(spki))
+    ))
+}
+
+/// Verifier that runs the usual verification (or none, with SSL verification
+/// off) and then requires the server's own certificate to match one of the
+/// pins. Intermediates are not considered: without verification nothing ties
+/// them to the server, and with it any extra certificate could be appended.
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    pins: Vec<CertificatePin>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        if self.pins.iter().any(|pin| pin.matches(end_entity)) {
+            return Ok(verified);
+        }
+        Err(rustls::Error::General(format!(
+            "{} for {}: the server presented {}, which matches none of the pinned certificates",
+            PIN_MISMATCH,
+            server_name.to_str(),
+            public_key_pin(end_entity).unwrap_or_else(|| "an unreadable certificate".to_string())
+        )))
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.inner.supported_verify_schemes()
     }
 }
 
+/// Build a rustls client config that only accepts servers presenting a
+/// pinned certificate, on top of the usual verification when `verify` is
+/// set. Pinning also works against self-signed certificates with
+/// verification off. No ALPN is set.
+pub(crate) fn build_pinned_tls_config(
+    pins: &[String],
+    verify: bool,
+    ca_pem: Option<Vec<u8>>,
+    identity: Option<IdentityPems>,
+) -> Result<rustls::ClientConfig, String> {
+    let pins = pins
+        .iter()
+        .map(|pin| CertificatePin::parse(pin))
+        .collect::<Result<Vec<_>, _>>()?;
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let inner: Arc<dyn ServerCertVerifier> = if verify {
+        webpki_verifier(provider.clone(), ca_pem)?
+    } else {
+        Arc::new(NoCertVerifier)
+    };
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS protocol configuration error: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier { inner, pins }));
+    with_identity(builder, identity)
+}
+
+/// Display name of a TLS protocol version, e.g. `TLS 1.3`.
+pub(crate) fn protocol_version_name(version: rustls::ProtocolVersion) -> String {
+    match version {
+        rustls::ProtocolVersion::TLSv1_3 => "TLS 1.3".to_string(),
+        rustls::ProtocolVersion::TLSv1_2 => "TLS 1.2".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Colon-separated hex SHA-256 fingerprint of a DER certificate, as printed
+/// by `openssl x509 -noout -fingerprint -sha256`.
+pub(crate) fn certificate_fingerprint(certificate: &[u8]) -> String {
+    Sha256::digest(certificate)
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn webpki_verifier(
+    provider: Arc<rustls::crypto::CryptoProvider>,
+    ca_pem: Option<Vec<u8>>,
+) -> Result<Arc<WebPkiServerVerifier>, String> {
+    WebPkiServerVerifier::builder_with_provider(Arc::new(root_store(ca_pem)?), provider)
+        .build()
+        .map_err(|e| format!("TLS verifier could not be built: {}", e))
+}
+
+/// Verifier that accepts what the usual verification accepts, plus server
+/// certificates the user chose to trust for the host although they fail it
+/// (self-signed, expired, wrong name).
+#[derive(Debug)]
+struct TrustedCertVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    trusted: Vec<CertificatePin>,
+}
+
+impl ServerCertVerifier for TrustedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        ) {
+            Err(_) if self.trusted.iter().any(|pin| pin.matches(end_entity)) => {
+                Ok(ServerCertVerified::assertion())
+            }
+            verified => verified,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Build a verifying rustls client config that also accepts the `trusted`
+/// certificates (hex SHA-256 fingerprints or `sha256/` public key pins) when
+/// verification fails. No ALPN is set.
+pub(crate) fn build_trusted_tls_config(
+    trusted: &[String],
+    ca_pem: Option<Vec<u8>>,
+    identity: Option<IdentityPems>,
+) -> Result<rustls::ClientConfig, String> {
+    let trusted = trusted
+        .iter()
+        .map(|fingerprint| CertificatePin::parse(fingerprint))
+        .collect::<Result<Vec<_>, _>>()?;
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let inner = webpki_verifier(provider.clone(), ca_pem)?;
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS protocol configuration error: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(TrustedCertVerifier { inner, trusted }));
+    with_identity(builder, identity)
+}
+
+/// What [`InspectingVerifier`] saw: the server certificate and why the usual
+/// verification rejected it (`None` when it passed).
+pub(crate) type InspectedCertificate = (Vec<u8>, Option<String>);
+
+/// The rest of what the server presented: its intermediate certificates and
+/// the stapled OCSP response (empty when none was stapled).
+pub(crate) type PresentedChain = (Vec<Vec<u8>>, Vec<u8>);
+
+/// Verifier that lets every handshake through and records the server
+/// certificate with the outcome of the usual verification, so the user can
+/// see what they are about to trust.
+#[derive(Debug)]
+pub(crate) struct InspectingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    seen: std::sync::Mutex<Option<InspectedCertificate>>,
+    chain: std::sync::Mutex<Option<PresentedChain>>,
+}
+
+impl InspectingVerifier {
+    pub(crate) fn take(&self) -> Option<InspectedCertificate> {
+        self.seen.lock().unwrap().take()
+    }
+
+    pub(crate) fn take_chain(&self) -> Option<PresentedChain> {
+        self.chain.lock().unwrap().take()
+    }
+}
+
+impl ServerCertVerifier for InspectingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let problem = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .err()
+            .map(|e| e.to_string());
+        *self.seen.lock().unwrap() = Some((end_entity.to_vec(), problem));
+        *self.chain.lock().unwrap() = Some((
+            intermediates.iter().map(|cert| cert.to_vec()).collect(),
+            ocsp_response.to_vec(),
+        ));
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// A client config whose verifier records the server certificate instead of
+/// checking it; read the result from the returned verifier after connecting.
+pub(crate) fn build_inspecting_tls_config(
+) -> Result<(rustls::ClientConfig, Arc<InspectingVerifier>), String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = Arc::new(InspectingVerifier {
+        inner: webpki_verifier(provider.clone(), None)?,
+        seen: std::sync::Mutex::new(None),
+        chain: std::sync::Mutex::new(None),
+    });
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS protocol configuration error: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    Ok((config, verifier))
+}
+
+/// A client config offering only `versions` and, when given, only
+/// `cipher_suites`, for finding out what a server accepts. Certificates are not
+/// verified, so a bad certificate does not hide the answer.
+pub(crate) fn build_restricted_tls_config(
+    versions: &[&'static rustls::SupportedProtocolVersion],
+    cipher_suites: Option<Vec<rustls::SupportedCipherSuite>>,
+) -> Result<rustls::ClientConfig, String> {
+    let mut provider = rustls::crypto::ring::default_provider();
+    if let Some(cipher_suites) = cipher_suites {
+        provider.cipher_suites = cipher_suites;
+    }
+    Ok(
+        rustls::ClientConfig::builder_with_provider(Arc::new(provider))
+            .with_protocol_versions(versions)
+            .map_err(|e| format!("TLS protocol configuration error: {}", e))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoCertVerifier))
+            .with_no_client_auth(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +785,107 @@ mod tests {
         assert!(config.alpn_protocols.is_empty());
     }
 
+    #[test]
+    fn pins_match_the_public_key_or_the_whole_certificate() {
+        let spki: &[u8] = &[
+            0x30, 0x0b, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x02, 0x00, 0x01,
+        ];
+        let mut tbs = vec![0x30, 0x1d, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01];
+        tbs.extend_from_slice(&[0x30, 0x00, 0x30, 0x00, 0x30, 0x00, 0x30, 0x00]);
+        tbs.extend_from_slice(spki);
+        let mut certificate = vec![0x30, 0x24];
+        certificate.extend_from_slice(&tbs);
+        certificate.extend_from_slice(&[0x30, 0x00, 0x03, 0x01, 0x00]);
+        assert_eq!(subject_public_key_info(&certificate), Some(spki));
+
+        let key_pin = public_key_pin(&certificate).unwrap();
+        assert!(CertificatePin::parse(&key_pin)
+            .unwrap()
+            .matches(&certificate));
+        let fingerprint = certificate_fingerprint(&certificate);
+        let certificate_pin = CertificatePin::parse(&fingerprint).unwrap();
+        assert!(certificate_pin.matches(&certificate));
+        assert!(!certificate_pin.matches(spki));
+
+        assert!(CertificatePin::parse("sha256/dG9vIHNob3J0").is_err());
+        assert!(build_pinned_tls_config(&[key_pin.clone()], true, None, None).is_ok());
+        assert!(build_trusted_tls_config(&[fingerprint, key_pin], None, None).is_ok());
+        assert!(build_trusted_tls_config(&["not-a-fingerprint".to_string()], None, None).is_err());
+    }
+
+    #[test]
+    fn pins_only_match_the_server_certificate() {
+        let pinned = CertificateDer::from(vec![0x30, 0x03, 0x02, 0x01, 0x01]);
+        let other = CertificateDer::from(vec![0x30, 0x03, 0x02, 0x01, 0x02]);
+        let verifier = PinnedCertVerifier {
+            inner: Arc::new(NoCertVerifier),
+            pins: vec![CertificatePin::parse(&certificate_fingerprint(&pinned)).unwrap()],
+        };
+        let server_name = ServerName::try_from("api.example.com").unwrap();
+        let verify = |end_entity: &CertificateDer<'_>, intermediates: &[CertificateDer<'_>]| {
+            verifier.verify_server_cert(
+                end_entity,
+                intermediates,
+                &server_name,
+                &[],
+                UnixTime::now(),
+            )
+        };
+
+        assert!(verify(&pinned, &[]).is_ok());
+        // A pinned certificate tacked onto someone else's chain does not count
+        let err = verify(&other, std::slice::from_ref(&pinned)).unwrap_err();
+        assert!(err.to_string().contains(PIN_MISMATCH));
+    }
+
+    #[test]
+    fn summarizes_certificate_names_and_validity() {
+        fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+            let mut element = vec![tag, content.len() as u8];
+            element.extend_from_slice(content);
+            element
+        }
+        fn name(attributes: &[(&[u8], u8, &str)]) -> Vec<u8> {
+            let sets: Vec<u8> = attributes
+                .iter()
+                .flat_map(|(oid, tag, value)| {
+                    let attribute = [der(0x06, oid), der(*tag, value.as_bytes())].concat();
+                    der(0x31, &der(0x30, &attribute))
+                })
+                .collect();
+            der(0x30, &sets)
+        }
+        let issuer = name(&[(&[0x55, 0x04, 0x0a], 0x13, "Example Org")]);
+        let subject = name(&[
+            (&[0x55, 0x04, 0x0a], 0x13, "Example Org"),
+            (&[0x55, 0x04, 0x03], 0x0c, "api.example.com"),
+        ]);
+        let validity = der(
+            0x30,
+            &[der(0x17, b"991231235959Z"), der(0x18, b"20300101000000Z")].concat(),
+        );
+        let tbs = der(
+            0x30,
+            &[
+                der(0xa0, &der(0x02, &[2])),
+                der(0x02, &[1]),
+                der(0x30, &[]),
+                issuer,
+                validity,
+                subject,
+            ]
+            .concat(),
+        );
+        let certificate = der(0x30, &[tbs, der(0x30, &[]), der(0x03, &[0])].concat());
+
+        let summary = certificate_summary(&certificate).unwrap();
+        assert_eq!(summary.subject, "api.example.com");
+        assert_eq!(summary.issuer, "Example Org");
+        assert_eq!(summary.not_before.to_rfc3339(), "1999-12-31T23:59:59+00:00");
+        assert_eq!(summary.not_after.to_rfc3339(), "2030-01-01T00:00:00+00:00");
+        assert_eq!(certificate_summary(&certificate[..20]), None);
+    }
+
     #[test]
     fn verifying_config_rejects_garbage_ca() {
         let err = build_verifying_tls_config(Some(b"not a pem".to_vec()), None).unwrap_err();
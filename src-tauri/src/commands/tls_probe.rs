@@ -0,0 +1,240 @@
+//! TLS checks of a server in the spirit of testssl.sh: which protocol
+//! versions and cipher suites it accepts, the certificate chain it presents,
+//! whether it staples an OCSP response and how long until its certificate
+//! expires.
+//!
+//! Every probe is a real handshake through rustls, so only what rustls speaks
+//! can be tested: TLS 1.2 and 1.3 with its AEAD cipher suites. A server that
+//! still accepts TLS 1.0/1.1 or CBC suites is not reported as doing so.
+
+use futures_util::future::join_all;
+use rustls::pki_types::ServerName;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::State;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use super::diagnostics::{parse_target, resolve};
+use super::proxy::ProxyState;
+use super::tls::{
+    build_inspecting_tls_config, build_restricted_tls_config, certificate_fingerprint,
+    certificate_summary, protocol_version_name,
+};
+
+const DEFAULT_TLS_PORT: u16 = 443;
+/// Time allowed for each handshake, connecting included.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsVersionSupport {
+    pub version: String,
+    pub supported: bool,
+    /// Why the handshake failed, usually the server's protocol_version alert
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CipherSuiteSupport {
+    pub name: String,
+    pub version: String,
+    pub supported: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbedCertificate {
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    /// RFC 3339 timestamps
+    pub not_before: Option<String>,
+    pub not_after: Option<String>,
+    /// Negative once the certificate has expired
+    pub days_to_expiry: Option<i64>,
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsProbeResult {
+    pub host: String,
+    pub port: u16,
+    pub address: String,
+    pub negotiated_version: Option<String>,
+    pub negotiated_cipher_suite: Option<String>,
+    pub versions: Vec<TlsVersionSupport>,
+    pub cipher_suites: Vec<CipherSuiteSupport>,
+    /// The server certificate first, then the intermediates it sent
+    pub certificates: Vec<ProbedCertificate>,
+    /// Why the usual verification rejects the chain, None when it passes
+    pub verification_problem: Option<String>,
+    pub ocsp_stapled: bool,
+    /// Days until the server certificate expires
+    pub days_to_expiry: Option<i64>,
+    pub duration_ms: u64,
+}
+
+/// URL form of `host:port`, for the offline check.
+fn probe_origin(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("https://[{}]:{}", host, port)
+    } else {
+        format!("https://{}:{}", host, port)
+    }
+}
+
+fn probed_certificate(certificate: &[u8], now: chrono::DateTime<chrono::Utc>) -> ProbedCertificate {
+    let summary = certificate_summary(certificate);
+    ProbedCertificate {
+        subject: summary.as_ref().map(|s| s.subject.clone()),
+        issuer: summary.as_ref().map(|s| s.issuer.clone()),
+        not_before: summary.as_ref().map(|s| s.not_before.to_rfc3339()),
+        not_after: summary.as_ref().map(|s| s.not_after.to_rfc3339()),
+        days_to_expiry: summary.as_ref().map(|s| (s.not_after - now).num_days()),
+        fingerprint: certificate_fingerprint(certificate),
+    }
+}
+
+fn suite_name(suite: rustls::SupportedCipherSuite) -> String {
+    format!("{:?}", suite.suite())
+}
+
+async fn handshake(
+    addr: SocketAddr,
+    server_name: ServerName<'static>,
+    config: rustls::ClientConfig,
+) -> Result<tokio_rustls::client::TlsStream<TcpStream>, String> {
+    let connect = async {
+        let tcp = TcpStream::connect(addr)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", addr, e))?;
+        tokio_rustls::TlsConnector::from(Arc::new(config))
+            .connect(server_name, tcp)
+            .await
+            .map_err(|e| e.to_string())
+    };
+    timeout(HANDSHAKE_TIMEOUT, connect)
+        .await
+        .map_err(|_| "Handshake timed out".to_string())?
+}
+
+/// Whether a handshake offering only what `config` allows succeeds.
+async fn accepts(
+    addr: SocketAddr,
+    server_name: ServerName<'static>,
+    config: Result<rustls::ClientConfig, String>,
+) -> Result<(), String> {
+    handshake(addr, server_name, config?).await.map(|_| ())
+}
+
+/// Probes the TLS setup of a host: one regular handshake for the negotiated
+/// parameters, certificate chain and OCSP staple, then one handshake per
+/// protocol version and per cipher suite to see which the server accepts.
+/// The port defaults to the target URL's, else 443.
+#[tauri::command]
+pub async fn network_tls_probe(
+    state: State<'_, ProxyState>,
+    target: String,
+    port: Option<u16>,
+) -> Result<TlsProbeResult, String> {
+    let (host, target_port) = parse_target(&target)?;
+    let port = port.or(target_port).unwrap_or(DEFAULT_TLS_PORT);
+    state.check_offline(&probe_origin(&host, port))?;
+    let addr = resolve(&host, port).await?;
+    let server_name = ServerName::try_from(host.clone())
+        .map_err(|e| format!("Invalid TLS server name {}: {}", host, e))?;
+    let started = Instant::now();
+
+    let (config, verifier) = build_inspecting_tls_config()?;
+    let tls = handshake(addr, server_name.clone(), config)
+        .await
+        .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+    let (_, session) = tls.get_ref();
+    let negotiated_version = session.protocol_version().map(protocol_version_name);
+    let negotiated_cipher_suite = session.negotiated_cipher_suite().map(suite_name);
+    drop(tls);
+    let (leaf, verification_problem) = verifier
+        .take()
+        .ok_or_else(|| format!("{} presented no certificate", host))?;
+    let (intermediates, ocsp_response) = verifier.take_chain().unwrap_or_default();
+
+    // Each probe is an independent connection, so they all run at once
+    let version_probes = rustls::ALL_VERSIONS.iter().map(|&version| {
+        let config = build_restricted_tls_config(&[version], None);
+        accepts(addr, server_name.clone(), config)
+    });
+    let suites = rustls::crypto::ring::default_provider().cipher_suites;
+    let suite_probes = suites.iter().map(|&suite| {
+        let config = build_restricted_tls_config(&[suite.version()], Some(vec![suite]));
+        accepts(addr, server_name.clone(), config)
+    });
+    let (version_results, suite_results) =
+        futures_util::join!(join_all(version_probes), join_all(suite_probes));
+
+    let versions = rustls::ALL_VERSIONS
+        .iter()
+        .zip(version_results)
+        .map(|(version, result)| TlsVersionSupport {
+            version: protocol_version_name(version.version),
+            supported: result.is_ok(),
+            error: result.err(),
+        })
+        .collect();
+    let cipher_suites = suites
+        .iter()
+        .zip(suite_results)
+        .map(|(&suite, result)| CipherSuiteSupport {
+            name: suite_name(suite),
+            version: protocol_version_name(suite.version().version),
+            supported: result.is_ok(),
+        })
+        .collect();
+
+    let now = chrono::Utc::now();
+    let certificates: Vec<ProbedCertificate> = std::iter::once(&leaf)
+        .chain(intermediates.iter())
+        .map(|certificate| probed_certificate(certificate, now))
+        .collect();
+    let days_to_expiry = certificates[0].days_to_expiry;
+
+    Ok(TlsProbeResult {
+        host,
+        port,
+        address: addr.ip().to_string(),
+        negotiated_version,
+        negotiated_cipher_suite,
+        versions,
+        cipher_suites,
+        certificates,
+        verification_problem,
+        ocsp_stapled: !ocsp_response.is_empty(),
+        days_to_expiry,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_check_origin_brackets_ipv6_hosts() {
+        assert_eq!(
+            probe_origin("example.com", 8443),
+            "https://example.com:8443"
+        );
+        assert_eq!(probe_origin("::1", 443), "https://[::1]:443");
+    }
+
+    #[test]
+    fn unparsable_certificates_keep_their_fingerprint() {
+        let certificate = probed_certificate(&[0x30, 0x00], chrono::Utc::now());
+        assert_eq!(certificate.subject, None);
+        assert_eq!(certificate.days_to_expiry, None);
+        assert_eq!(certificate.fingerprint.len(), 32 * 3 - 1);
+    }
+}
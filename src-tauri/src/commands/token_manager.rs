@@ -0,0 +1,285 @@
+//! OAuth 2.0 token manager.
+//!
+//! Tokens obtained through an OAuth 2.0 auth config are recorded per active
+//! environment and auth profile (token URL, client id and scope), so every
+//! request using the same profile shares one token. When a recorded token has
+//! expired it is refreshed before the request is sent: with its refresh token
+//! when it has one, otherwise by repeating a non-interactive grant.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use super::oauth::{request_token, token_client, OAuth2Config, OAuth2TokenResponse};
+use super::proxy::ProxyState;
+use super::store::{lock_store, save_store};
+use super::variables::active_environment_id;
+use super::workspaces::store_file;
+
+const TOKENS_KEY: &str = "oauthTokens";
+/// Tokens this close to expiry are refreshed, so they do not lapse in flight.
+const EXPIRY_MARGIN_MS: i64 = 30_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredToken {
+    pub profile: String,
+    #[serde(default)]
+    pub environment_id: Option<String>,
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub scope: Option<String>,
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    /// Expiry in milliseconds since the epoch; `None` when the server gave none.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    pub updated_at: i64,
+}
+
+impl StoredToken {
+    fn is_expired(&self, now: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now + EXPIRY_MARGIN_MS >= expires_at,
+            None => false,
+        }
+    }
+}
+
+fn profile_key(environment_id: Option<&str>, config: &OAuth2Config) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        environment_id.unwrap_or_default(),
+        config.token_url,
+        config.client_id,
+        config.scope.as_deref().unwrap_or_default()
+    )
+}
+
+fn record(
+    environment_id: Option<String>,
+    config: &OAuth2Config,
+    response: OAuth2TokenResponse,
+    previous_refresh_token: Option<String>,
+) -> Result<StoredToken, String> {
+    let access_token = match response.access_token {
+        Some(token) if response.success && !token.is_empty() => token,
+        _ => {
+            return Err(response
+                .error_description
+                .or(response.error)
+                .unwrap_or_else(|| "The token response has no access token".to_string()))
+        }
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    Ok(StoredToken {
+        profile: profile_key(environment_id.as_deref(), config),
+        environment_id,
+        token_url: config.token_url.clone(),
+        client_id: config.client_id.clone(),
+        scope: config.scope.clone(),
+        access_token,
+        refresh_token: response.refresh_token.or(previous_refresh_token),
+        token_type: response.token_type,
+        expires_at: response
+            .expires_in
+            .map(|seconds| now + seconds.saturating_mul(1000)),
+        updated_at: now,
+    })
+}
+
+fn read_tokens(app: &AppHandle) -> Result<Vec<StoredToken>, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let Some(Value::Object(tokens)) = store.get(TOKENS_KEY) else {
+        return Ok(Vec::new());
+    };
+    Ok(tokens
+        .into_iter()
+        .filter_map(|(_, token)| serde_json::from_value(token).ok())
+        .collect())
+}
+
+/// Insert (`Some`) or remove (`None`) the token of `profile`.
+fn write_token(app: &AppHandle, profile: &str, token: Option<&StoredToken>) -> Result<(), String> {
+    let _guard = lock_store();
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let mut tokens = match store.get(TOKENS_KEY) {
+        Some(Value::Object(tokens)) => tokens,
+        _ => serde_json::Map::new(),
+    };
+    match token {
+        Some(token) => {
+            let value = serde_json::to_value(token).map_err(|e| e.to_string())?;
+            tokens.insert(profile.to_string(), value);
+        }
+        None => {
+            tokens.remove(profile);
+        }
+    }
+    store.set(TOKENS_KEY.to_string(), Value::Object(tokens));
+    save_store(app)
+}
+
+/// Obtain a new token for an expired one without user interaction.
+async fn renew(
+    proxy_state: &ProxyState,
+    config: &OAuth2Config,
+    token: &StoredToken,
+) -> Result<OAuth2TokenResponse, String> {
+    let request = match &token.refresh_token {
+        Some(refresh_token) => OAuth2Config {
+            grant_type: "refresh_token".to_string(),
+            refresh_token: Some(refresh_token.clone()),
+            ..config.clone()
+        },
+        None if matches!(
+            config.grant_type.as_str(),
+            "client_credentials" | "password"
+        ) =>
+        {
+            config.clone()
+        }
+        None => {
+            return Err(
+                "The token expired and has no refresh token; get a new one from the auth tab"
+                    .to_string(),
+            )
+        }
+    };
+    request_token(proxy_state, &token_client(proxy_state)?, &request).await
+}
+
+/// The recorded token of `config`'s profile in the active environment,
+/// refreshed first when it has expired.
+pub(crate) async fn managed_token(
+    app: &AppHandle,
+    config: &OAuth2Config,
+) -> Result<Option<StoredToken>, String> {
+    if config.grant_type == "manual" || config.token_url.is_empty() {
+        return Ok(None);
+    }
+    let environment_id = active_environment_id(app);
+    let profile = profile_key(environment_id.as_deref(), config);
+    let Some(token) = read_tokens(app)?
+        .into_iter()
+        .find(|token| token.profile == profile)
+    else {
+        return Ok(None);
+    };
+    if !token.is_expired(chrono::Utc::now().timestamp_millis()) {
+        return Ok(Some(token));
+    }
+
+    let response = renew(&app.state::<ProxyState>(), config, &token).await?;
+    let renewed = record(environment_id, config, response, token.refresh_token)?;
+    write_token(app, &profile, Some(&renewed))?;
+    Ok(Some(renewed))
+}
+
+/// Put the managed token of an `oauth2` auth config (`{type, config}`) into its
+/// `token` field, for backend callers that send requests themselves.
+pub(crate) async fn apply_managed_token(app: &AppHandle, auth: &mut Value) {
+    if auth.get("type").and_then(Value::as_str) != Some("oauth2") {
+        return;
+    }
+    let Some(config) = auth
+        .get("config")
+        .cloned()
+        .and_then(|config| serde_json::from_value::<OAuth2Config>(config).ok())
+    else {
+        return;
+    };
+    match managed_token(app, &config).await {
+        Ok(Some(token)) => auth["config"]["token"] = Value::String(token.access_token),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to refresh OAuth 2.0 token: {}", e),
+    }
+}
+
+/// Record a token obtained for `config` in the active environment.
+#[tauri::command]
+pub async fn oauth2_token_save(
+    app: AppHandle,
+    config: OAuth2Config,
+    token: OAuth2TokenResponse,
+) -> Result<StoredToken, String> {
+    let stored = record(active_environment_id(&app), &config, token, None)?;
+    write_token(&app, &stored.profile, Some(&stored))?;
+    Ok(stored)
+}
+
+/// The token to send for `config`, refreshed when expired. `None` when no
+/// token was recorded for its profile.
+#[tauri::command]
+pub async fn oauth2_token_get(
+    app: AppHandle,
+    config: OAuth2Config,
+) -> Result<Option<StoredToken>, String> {
+    managed_token(&app, &config).await
+}
+
+/// Every recorded token, across environments.
+#[tauri::command]
+pub async fn oauth2_tokens_list(app: AppHandle) -> Result<Vec<StoredToken>, String> {
+    let mut tokens = read_tokens(&app)?;
+    tokens.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    Ok(tokens)
+}
+
+/// Forget a recorded token, so requests of its profile stop using it.
+#[tauri::command]
+pub async fn oauth2_token_revoke(app: AppHandle, profile: String) -> Result<(), String> {
+    write_token(&app, &profile, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OAuth2Config {
+        serde_json::from_value(serde_json::json!({
+            "grantType": "client_credentials",
+            "tokenUrl": "https://auth.test/token",
+            "clientId": "cli",
+            "scope": "read"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn records_keep_the_previous_refresh_token_and_expire_early() {
+        let response: OAuth2TokenResponse = serde_json::from_value(serde_json::json!({
+            "success": true,
+            "accessToken": "abc",
+            "expiresIn": 60
+        }))
+        .unwrap();
+        let token = record(
+            Some("dev".to_string()),
+            &config(),
+            response,
+            Some("refresh".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(token.profile, "dev|https://auth.test/token|cli|read");
+        assert_eq!(token.refresh_token.as_deref(), Some("refresh"));
+        assert!(!token.is_expired(token.updated_at));
+        assert!(token.is_expired(token.updated_at + 60_000 - EXPIRY_MARGIN_MS));
+
+        let failed: OAuth2TokenResponse = serde_json::from_value(serde_json::json!({
+            "success": false,
+            "error": "invalid_client"
+        }))
+        .unwrap();
+        assert_eq!(
+            record(None, &config(), failed, None).unwrap_err(),
+            "invalid_client"
+        );
+    }
+}
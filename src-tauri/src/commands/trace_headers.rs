@@ -0,0 +1,89 @@
+//! Generated `X-Request-Id` and W3C `traceparent` headers, so a response can
+//! be found again in the server's logs and traces. Requests of one runner
+//! iteration share a trace id and differ by span id.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The correlation ids a request was sent with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceIds {
+    pub request_id: String,
+    pub traceparent: String,
+}
+
+/// A fresh 32 hex digit trace id.
+pub fn new_trace_id() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+fn header_value<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Add the headers the request does not set itself, joining `trace_id` when
+/// given, and report the ids it goes out with.
+pub fn inject(headers: &mut HashMap<String, String>, trace_id: Option<&str>) -> TraceIds {
+    let request_id = match header_value(headers, REQUEST_ID_HEADER) {
+        Some(existing) => existing.to_string(),
+        None => {
+            let id = Uuid::new_v4().to_string();
+            headers.insert(REQUEST_ID_HEADER.to_string(), id.clone());
+            id
+        }
+    };
+    let traceparent = match header_value(headers, TRACEPARENT_HEADER) {
+        Some(existing) => existing.to_string(),
+        None => {
+            let trace_id = trace_id.map_or_else(new_trace_id, str::to_string);
+            let span_id = &Uuid::new_v4().simple().to_string()[..16];
+            let value = format!("00-{}-{}-01", trace_id, span_id);
+            headers.insert(TRACEPARENT_HEADER.to_string(), value.clone());
+            value
+        }
+    };
+    TraceIds {
+        request_id,
+        traceparent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injects_missing_ids_and_keeps_the_requests_own() {
+        let trace_id = new_trace_id();
+        let mut first = HashMap::new();
+        let mut second = HashMap::new();
+        let a = inject(&mut first, Some(&trace_id));
+        let b = inject(&mut second, Some(&trace_id));
+
+        assert_eq!(first[REQUEST_ID_HEADER], a.request_id);
+        assert_ne!(a.request_id, b.request_id);
+        let parts: Vec<&str> = a.traceparent.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(
+            (parts[0], parts[1], parts[3]),
+            ("00", trace_id.as_str(), "01")
+        );
+        assert_eq!(parts[2].len(), 16);
+        assert_ne!(a.traceparent, b.traceparent);
+        assert!(b.traceparent.contains(&trace_id));
+
+        let mut own = HashMap::from([("x-request-id".to_string(), "mine".to_string())]);
+        let ids = inject(&mut own, None);
+        assert_eq!(ids.request_id, "mine");
+        assert_eq!(own.len(), 2);
+        assert!(!own.contains_key(REQUEST_ID_HEADER));
+    }
+}
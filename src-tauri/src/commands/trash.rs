@@ -0,0 +1,415 @@
+//! Soft delete for collections and endpoints.
+//!
+//! Deleting moves the collection directory (or the endpoint's data file) into
+//! `<data dir>/trash/<entry id>` and records an entry in the store (`trash`).
+//! Entries can be restored to where they came from until they are purged,
+//! either explicitly or once they are older than the retention period.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::collections::{
+    collection_get, find_available_dir, find_endpoint_data_file, persist_collection,
+    register_collection_path, resolve_collection_dir, unregister_collection_path,
+};
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const TRASH_KEY: &str = "trash";
+const TRASH_DIR: &str = "trash";
+
+/// How long deleted items are kept before they are purged automatically.
+const RETENTION_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrashKind {
+    Collection,
+    Endpoint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub kind: TrashKind,
+    pub collection_id: String,
+    pub name: String,
+    /// Unix timestamp (ms) of the deletion
+    pub deleted_at: i64,
+    /// Unix timestamp (ms) after which the entry is purged
+    pub expires_at: i64,
+    /// Where the deleted files live now; `None` for an endpoint without a data file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_path: Option<String>,
+    /// Collection directory the entry was removed from
+    pub original_path: String,
+    /// Folder the endpoint was in, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder_id: Option<String>,
+    /// The endpoint as it appeared in the collection file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<Value>,
+}
+
+fn trash_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(super::workspaces::data_dir(app)?.join(TRASH_DIR))
+}
+
+fn read_entries(app: &AppHandle) -> Vec<TrashEntry> {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(TRASH_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn write_entries(app: &AppHandle, entries: &[TrashEntry]) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    store.set(
+        TRASH_KEY.to_string(),
+        serde_json::to_value(entries).map_err(|e| e.to_string())?,
+    );
+    save_store(app)
+}
+
+fn push_entry(app: &AppHandle, entry: TrashEntry) -> Result<(), String> {
+    let _guard = lock_store();
+    let mut entries = read_entries(app);
+    entries.push(entry);
+    write_entries(app, &entries)
+}
+
+/// Remove the entries matching `pred` from the index and return them.
+fn take_entries(
+    app: &AppHandle,
+    pred: impl Fn(&TrashEntry) -> bool,
+) -> Result<Vec<TrashEntry>, String> {
+    let _guard = lock_store();
+    let (taken, kept): (Vec<_>, Vec<_>) = read_entries(app).into_iter().partition(pred);
+    if !taken.is_empty() {
+        write_entries(app, &kept)?;
+    }
+    Ok(taken)
+}
+
+fn is_expired(entry: &TrashEntry, now_ms: i64) -> bool {
+    entry.expires_at <= now_ms
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+/// Rename `from` to `to`, falling back to copy and delete when they are on
+/// different file systems (collections can live anywhere on disk).
+fn move_path(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursively(from, to).map_err(|e| format!("Failed to move {:?}: {}", from, e))?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+    .map_err(|e| format!("Failed to remove {:?}: {}", from, e))
+}
+
+fn new_entry(kind: TrashKind, collection_id: &str, name: String, original: &Path) -> TrashEntry {
+    let now = chrono::Utc::now().timestamp_millis();
+    TrashEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        collection_id: collection_id.to_string(),
+        name,
+        deleted_at: now,
+        expires_at: now + RETENTION_DAYS * 24 * 60 * 60 * 1000,
+        trash_path: None,
+        original_path: original.to_string_lossy().to_string(),
+        folder_id: None,
+        endpoint: None,
+    }
+}
+
+/// Move a collection to the trash. Called by `collection_delete`.
+pub(crate) fn trash_collection(app: &AppHandle, collection_id: &str) -> Result<(), String> {
+    let Some(collection_dir) = resolve_collection_dir(app, collection_id)? else {
+        return unregister_collection_path(app, collection_id);
+    };
+
+    let name = super::collections::read_collection_value(&collection_dir)
+        .ok()
+        .and_then(|v| v.get("name").and_then(Value::as_str).map(str::to_string))
+        .unwrap_or_else(|| collection_id.to_string());
+    let mut entry = new_entry(TrashKind::Collection, collection_id, name, &collection_dir);
+    let target = trash_dir(app)?.join(&entry.id);
+    move_path(&collection_dir, &target)?;
+    entry.trash_path = Some(target.to_string_lossy().to_string());
+
+    push_entry(app, entry)?;
+    unregister_collection_path(app, collection_id)
+}
+
+/// Take an endpoint out of its collection (top level or folder) and return it
+/// with the id of the folder it was in.
+fn take_endpoint(
+    endpoints: &mut Vec<Value>,
+    folders: &mut [Value],
+    endpoint_id: &str,
+) -> Option<(Value, Option<String>)> {
+    let matches = |e: &Value| e.get("id").and_then(Value::as_str) == Some(endpoint_id);
+    if let Some(pos) = endpoints.iter().position(matches) {
+        return Some((endpoints.remove(pos), None));
+    }
+    for folder in folders.iter_mut() {
+        let folder_id = folder.get("id").and_then(Value::as_str).map(str::to_string);
+        let Some(list) = folder.get_mut("endpoints").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        if let Some(pos) = list.iter().position(matches) {
+            return Some((list.remove(pos), folder_id));
+        }
+    }
+    None
+}
+
+/// Remove an endpoint from its collection and move its data file to the trash.
+#[tauri::command]
+pub async fn trash_endpoint(
+    app: AppHandle,
+    collection_id: String,
+    endpoint_id: String,
+) -> Result<TrashEntry, String> {
+    let mut collection = collection_get(app.clone(), collection_id.clone()).await?;
+    let (endpoint, folder_id) = take_endpoint(
+        &mut collection.endpoints,
+        &mut collection.folders,
+        &endpoint_id,
+    )
+    .ok_or_else(|| format!("Endpoint {} not found", endpoint_id))?;
+    let collection_dir = PathBuf::from(collection.storage_path.clone().unwrap_or_default());
+
+    let name = endpoint
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(&endpoint_id)
+        .to_string();
+    let mut entry = new_entry(TrashKind::Endpoint, &collection_id, name, &collection_dir);
+    if let Some(file) = find_endpoint_data_file(&collection_dir.join("requests"), &endpoint_id)? {
+        let file_name = file.file_name().unwrap_or_default().to_os_string();
+        let target = trash_dir(&app)?.join(&entry.id).join(file_name);
+        move_path(&file, &target)?;
+        entry.trash_path = Some(target.to_string_lossy().to_string());
+    }
+    entry.folder_id = folder_id;
+    entry.endpoint = Some(endpoint);
+
+    persist_collection(&app, collection)?;
+    push_entry(&app, entry.clone())?;
+    Ok(entry)
+}
+
+/// Delete the files of an entry for good, along with store data that only
+/// mattered while it could come back.
+fn purge_entry(app: &AppHandle, entry: &TrashEntry) {
+    if let Ok(dir) = trash_dir(app) {
+        let entry_dir = dir.join(&entry.id);
+        if entry_dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&entry_dir) {
+                eprintln!("Failed to purge trash entry {}: {}", entry.id, e);
+            }
+        }
+    }
+    match entry.kind {
+        TrashKind::Collection => {
+            super::scripts::purge_store_scripts_for_collection(app, &entry.collection_id);
+            super::endpoint_drafts::purge_drafts_for_collection(app, &entry.collection_id);
+            super::collection_history::remove_history(app, &entry.collection_id);
+        }
+        TrashKind::Endpoint => {
+            let endpoint_id = entry
+                .endpoint
+                .as_ref()
+                .and_then(|e| e.get("id"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Err(e) =
+                super::endpoint_drafts::remove_draft(app, &entry.collection_id, endpoint_id)
+            {
+                eprintln!("Failed to drop draft of {}: {}", endpoint_id, e);
+            }
+            // Saved examples stay in the collection while the endpoint can come back
+            if let Ok(Some(dir)) = resolve_collection_dir(app, &entry.collection_id) {
+                if let Err(e) = super::endpoint_examples::delete_examples(&dir, endpoint_id) {
+                    eprintln!("Failed to drop examples of {}: {}", endpoint_id, e);
+                }
+            }
+        }
+    }
+}
+
+/// Purge entries past their retention period. Runs at startup and whenever
+/// the trash is listed.
+pub(crate) fn purge_expired(app: &AppHandle) {
+    let now = chrono::Utc::now().timestamp_millis();
+    match take_entries(app, |entry| is_expired(entry, now)) {
+        Ok(expired) => expired.iter().for_each(|entry| purge_entry(app, entry)),
+        Err(e) => eprintln!("Failed to update trash: {}", e),
+    }
+}
+
+/// Trashed items, newest first.
+#[tauri::command]
+pub async fn trash_list(app: AppHandle) -> Result<Vec<TrashEntry>, String> {
+    purge_expired(&app);
+    let mut entries = read_entries(&app);
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+async fn restore_endpoint(app: &AppHandle, entry: &TrashEntry) -> Result<(), String> {
+    let endpoint = entry
+        .endpoint
+        .clone()
+        .ok_or_else(|| "Trash entry has no endpoint".to_string())?;
+    let mut collection = collection_get(app.clone(), entry.collection_id.clone())
+        .await
+        .map_err(|_| "Restore the endpoint's collection first".to_string())?;
+
+    let folder = entry.folder_id.as_deref().and_then(|folder_id| {
+        collection
+            .folders
+            .iter_mut()
+            .find(|f| f.get("id").and_then(Value::as_str) == Some(folder_id))
+    });
+    match folder.and_then(|f| f.get_mut("endpoints").and_then(Value::as_array_mut)) {
+        Some(list) => list.push(endpoint),
+        None => collection.endpoints.push(endpoint),
+    }
+    let collection = persist_collection(app, collection)?;
+
+    if let Some(trash_path) = entry.trash_path.as_ref().map(PathBuf::from) {
+        let collection_dir = PathBuf::from(collection.storage_path.unwrap_or_default());
+        let file_name = trash_path.file_name().unwrap_or_default();
+        move_path(
+            &trash_path,
+            &collection_dir.join("requests").join(file_name),
+        )?;
+        super::collection_watcher::mark_synced(app, &entry.collection_id, &collection_dir);
+    }
+    Ok(())
+}
+
+fn restore_collection(app: &AppHandle, entry: &TrashEntry) -> Result<(), String> {
+    if resolve_collection_dir(app, &entry.collection_id)?.is_some() {
+        return Err(format!(
+            "A collection with id {} already exists",
+            entry.collection_id
+        ));
+    }
+    let trash_path = entry
+        .trash_path
+        .as_ref()
+        .map(PathBuf::from)
+        .ok_or_else(|| "Trash entry has no files".to_string())?;
+
+    let original = PathBuf::from(&entry.original_path);
+    let parent = original.parent().unwrap_or(Path::new("."));
+    let base_name = original
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&entry.collection_id);
+    let target = find_available_dir(parent, base_name, None);
+    move_path(&trash_path, &target)?;
+    register_collection_path(app, &entry.collection_id, &target)
+}
+
+/// Put a trashed collection or endpoint back where it was.
+#[tauri::command]
+pub async fn trash_restore(app: AppHandle, entry_id: String) -> Result<(), String> {
+    let entry = read_entries(&app)
+        .into_iter()
+        .find(|e| e.id == entry_id)
+        .ok_or_else(|| format!("Trash entry {} not found", entry_id))?;
+
+    match entry.kind {
+        TrashKind::Collection => restore_collection(&app, &entry)?,
+        TrashKind::Endpoint => restore_endpoint(&app, &entry).await?,
+    }
+
+    take_entries(&app, |e| e.id == entry_id)?;
+    if let Ok(dir) = trash_dir(&app) {
+        let _ = fs::remove_dir_all(dir.join(&entry.id));
+    }
+    Ok(())
+}
+
+/// Permanently delete one entry, or the whole trash when `entry_id` is `None`.
+#[tauri::command]
+pub async fn trash_purge(app: AppHandle, entry_id: Option<String>) -> Result<(), String> {
+    let purged = take_entries(&app, |e| match &entry_id {
+        Some(id) => &e.id == id,
+        None => true,
+    })?;
+    if let Some(id) = entry_id.as_ref().filter(|_| purged.is_empty()) {
+        return Err(format!("Trash entry {} not found", id));
+    }
+    for entry in &purged {
+        purge_entry(&app, entry);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn entries_expire_after_the_retention_period() {
+        let entry = new_entry(
+            TrashKind::Collection,
+            "c1",
+            "Users API".to_string(),
+            Path::new("/tmp/users-api"),
+        );
+        let day = 24 * 60 * 60 * 1000;
+        assert!(!is_expired(&entry, entry.deleted_at + day));
+        assert!(is_expired(&entry, entry.deleted_at + RETENTION_DAYS * day));
+    }
+
+    #[test]
+    fn takes_endpoints_from_top_level_and_folders() {
+        let mut endpoints = vec![json!({"id": "e1"})];
+        let mut folders = vec![json!({"id": "f1", "endpoints": [{"id": "e2"}]})];
+
+        let (_, folder) = take_endpoint(&mut endpoints, &mut folders, "e1").unwrap();
+        assert_eq!(folder, None);
+        assert!(endpoints.is_empty());
+
+        let (endpoint, folder) = take_endpoint(&mut endpoints, &mut folders, "e2").unwrap();
+        assert_eq!(endpoint["id"], "e2");
+        assert_eq!(folder.as_deref(), Some("f1"));
+        assert!(take_endpoint(&mut endpoints, &mut folders, "e3").is_none());
+    }
+}
@@ -0,0 +1,268 @@
+//! Per-host TLS exceptions: instead of turning certificate verification off
+//! everywhere, the user trusts the certificate one host presents (by its
+//! SHA-256 fingerprint), either for this session or always. HTTP requests and
+//! gRPC channels to that host then accept exactly that certificate when the
+//! usual verification rejects it.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, State};
+use tauri_plugin_store::StoreExt;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use super::proxy::ProxyState;
+use super::store::{lock_store, save_store};
+use super::tls::{build_inspecting_tls_config, certificate_fingerprint, CertificatePin};
+use super::workspaces::store_file;
+
+const TRUSTED_HOSTS_KEY: &str = "trustedHosts";
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedHost {
+    /// Host name or IP, lowercase, without port
+    pub host: String,
+    /// Hex SHA-256 fingerprint of the trusted certificate
+    pub fingerprint: String,
+    /// Unix timestamp (ms) when the exception was added
+    pub added_at: i64,
+    /// Kept in the workspace (`true`) or only until the app quits
+    #[serde(default)]
+    pub remembered: bool,
+}
+
+/// The trust exceptions in effect: the remembered ones of the active
+/// workspace plus the ones added for this session.
+#[derive(Default)]
+pub struct TrustedHosts {
+    entries: RwLock<Vec<TrustedHost>>,
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase()
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    Some(normalize_host(parsed.host_str()?))
+}
+
+impl TrustedHosts {
+    /// Swap the remembered exceptions for the ones `saved` returns from the
+    /// active workspace's store; session exceptions are kept.
+    pub(crate) fn load_saved(&self, saved: impl Fn(&str) -> Option<serde_json::Value>) {
+        let saved: Vec<TrustedHost> = saved(TRUSTED_HOSTS_KEY)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|entry| !entry.remembered);
+        entries.extend(saved.into_iter().map(|entry| TrustedHost {
+            remembered: true,
+            ..entry
+        }));
+    }
+
+    /// Fingerprints trusted for the host of `url`.
+    pub(crate) fn fingerprints_for(&self, url: &str) -> Vec<String> {
+        let Some(host) = url_host(url) else {
+            return Vec::new();
+        };
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.host == host)
+            .map(|entry| entry.fingerprint.clone())
+            .collect()
+    }
+
+    fn list(&self) -> Vec<TrustedHost> {
+        self.entries.read().unwrap().clone()
+    }
+
+    fn remembered(&self) -> Vec<TrustedHost> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.remembered)
+            .cloned()
+            .collect()
+    }
+
+    /// Add (or replace) the exception for `host` and `fingerprint`.
+    fn add(&self, entry: TrustedHost) {
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|e| !(e.host == entry.host && e.fingerprint == entry.fingerprint));
+        entries.push(entry);
+    }
+
+    fn remove(&self, host: &str, fingerprint: &str) {
+        self.entries
+            .write()
+            .unwrap()
+            .retain(|e| !(e.host == host && e.fingerprint == fingerprint));
+    }
+}
+
+fn save_remembered(app: &AppHandle, trusted_hosts: &TrustedHosts) -> Result<(), String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let _guard = lock_store();
+    store.set(
+        TRUSTED_HOSTS_KEY.to_string(),
+        serde_json::to_value(trusted_hosts.remembered()).map_err(|e| e.to_string())?,
+    );
+    save_store(app)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentedCertificate {
+    pub host: String,
+    pub fingerprint: String,
+    /// Why the usual verification rejects the certificate (`None` = it is
+    /// trusted without an exception)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+    /// An exception for this certificate already exists
+    pub trusted: bool,
+}
+
+/// Connects to the host of an `https://` URL and reports the certificate it
+/// presents, so the UI can ask the user whether to trust it. Connects
+/// directly, without the configured proxy.
+#[tauri::command]
+pub async fn trusted_hosts_inspect(
+    state: State<'_, ProxyState>,
+    url: String,
+) -> Result<PresentedCertificate, String> {
+    state.check_offline(&url)?;
+    let parsed = url::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "The URL has no host".to_string())?
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let (config, verifier) = build_inspecting_tls_config()?;
+    let server_name = rustls::pki_types::ServerName::try_from(normalize_host(&host))
+        .map_err(|e| format!("Invalid TLS server name {}: {}", host, e))?;
+    let handshake = async {
+        let tcp = TcpStream::connect((normalize_host(&host).as_str(), port))
+            .await
+            .map_err(|e| format!("Connection to {}:{} failed: {}", host, port, e))?;
+        tokio_rustls::TlsConnector::from(Arc::new(config))
+            .connect(server_name, tcp)
+            .await
+            .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))
+    };
+    timeout(INSPECT_TIMEOUT, handshake)
+        .await
+        .map_err(|_| format!("TLS handshake with {} timed out", host))??;
+
+    let (certificate, problem) = verifier
+        .take()
+        .ok_or_else(|| format!("{} presented no certificate", host))?;
+    let trusted = state
+        .trusted_hosts
+        .fingerprints_for(&url)
+        .iter()
+        .filter_map(|fingerprint| CertificatePin::parse(fingerprint).ok())
+        .any(|pin| pin.matches(&certificate));
+    Ok(PresentedCertificate {
+        host: normalize_host(&host),
+        fingerprint: certificate_fingerprint(&certificate),
+        problem,
+        trusted,
+    })
+}
+
+#[tauri::command]
+pub async fn trusted_hosts_list(state: State<'_, ProxyState>) -> Result<Vec<TrustedHost>, String> {
+    Ok(state.trusted_hosts.list())
+}
+
+/// Trusts the certificate with `fingerprint` for `host`: for this session, or
+/// saved in the workspace with `remember`.
+#[tauri::command]
+pub async fn trusted_hosts_add(
+    app: AppHandle,
+    state: State<'_, ProxyState>,
+    host: String,
+    fingerprint: String,
+    remember: bool,
+) -> Result<Vec<TrustedHost>, String> {
+    let host = normalize_host(&host);
+    if host.is_empty() {
+        return Err("Host is required".to_string());
+    }
+    CertificatePin::parse(&fingerprint)?;
+    state.trusted_hosts.add(TrustedHost {
+        host,
+        fingerprint: fingerprint.trim().to_string(),
+        added_at: chrono::Utc::now().timestamp_millis(),
+        remembered: remember,
+    });
+    // Also drops a remembered exception that is now kept for this session only
+    save_remembered(&app, &state.trusted_hosts)?;
+    Ok(state.trusted_hosts.list())
+}
+
+#[tauri::command]
+pub async fn trusted_hosts_remove(
+    app: AppHandle,
+    state: State<'_, ProxyState>,
+    host: String,
+    fingerprint: String,
+) -> Result<Vec<TrustedHost>, String> {
+    state
+        .trusted_hosts
+        .remove(&normalize_host(&host), fingerprint.trim());
+    save_remembered(&app, &state.trusted_hosts)?;
+    Ok(state.trusted_hosts.list())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(host: &str, fingerprint: &str, remembered: bool) -> TrustedHost {
+        TrustedHost {
+            host: host.to_string(),
+            fingerprint: fingerprint.to_string(),
+            added_at: 0,
+            remembered,
+        }
+    }
+
+    #[test]
+    fn exceptions_apply_to_their_host_only() {
+        let trusted = TrustedHosts::default();
+        trusted.add(entry("dev.internal", "AA", true));
+        trusted.add(entry("dev.internal", "BB", false));
+        trusted.add(entry("dev.internal", "AA", false));
+        trusted.add(entry("::1", "CC", false));
+
+        assert_eq!(
+            trusted.fingerprints_for("https://DEV.internal:8443/users"),
+            vec!["BB", "AA"]
+        );
+        assert_eq!(trusted.fingerprints_for("https://[::1]/"), vec!["CC"]);
+        assert!(trusted
+            .fingerprints_for("https://prod.internal/")
+            .is_empty());
+        assert!(trusted.remembered().is_empty());
+
+        trusted.remove("dev.internal", "BB");
+        assert_eq!(
+            trusted.fingerprints_for("https://dev.internal/"),
+            vec!["AA"]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! Usage statistics for the dashboard: every request sent from the request
+//! editor is counted per host and per collection in daily buckets (requests,
+//! errors, latency), kept in the workspace store for [`RETENTION_DAYS`] days.
+//! `usage_stats_query` aggregates the buckets over a number of days.
+
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::api_request::ApiResponse;
+use super::store::{lock_store, save_store};
+use super::workspaces::store_file;
+
+const USAGE_STATS_KEY: &str = "usageStats";
+const RETENTION_DAYS: i64 = 90;
+const DEFAULT_QUERY_DAYS: u32 = 30;
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+/// Counters of one host, within one collection, on one UTC day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageBucket {
+    /// "YYYY-MM-DD"
+    day: String,
+    host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    collection_id: Option<String>,
+    count: u64,
+    /// Requests without a response or with a 4xx/5xx status
+    errors: u64,
+    /// Requests that got a response; the latency average divides by these
+    responded: u64,
+    total_ms: u64,
+}
+
+/// One sent request.
+struct UsageSample<'a> {
+    host: &'a str,
+    collection_id: Option<&'a str>,
+    error: bool,
+    /// Total time, when a response came back
+    latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UsageGrouping {
+    Host,
+    Collection,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub count: u64,
+    pub errors: u64,
+    /// `errors / count`, 0 when nothing was sent
+    pub error_rate: f64,
+    pub avg_latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsagePoint {
+    pub day: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageGroup {
+    /// Host name, or collection ID
+    pub key: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+    /// One point per day of the queried range, oldest first
+    pub series: Vec<UsagePoint>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    pub days: u32,
+    pub totals: UsageTotals,
+    /// Busiest first
+    pub groups: Vec<UsageGroup>,
+}
+
+/// Sums of buckets, turned into [`UsageTotals`] once complete.
+#[derive(Default, Clone, Copy)]
+struct Tally {
+    count: u64,
+    errors: u64,
+    responded: u64,
+    total_ms: u64,
+}
+
+impl Tally {
+    fn add(&mut self, bucket: &UsageBucket) {
+        self.count += bucket.count;
+        self.errors += bucket.errors;
+        self.responded += bucket.responded;
+        self.total_ms += bucket.total_ms;
+    }
+
+    fn merge(&mut self, other: Tally) {
+        self.count += other.count;
+        self.errors += other.errors;
+        self.responded += other.responded;
+        self.total_ms += other.total_ms;
+    }
+
+    fn totals(self) -> UsageTotals {
+        UsageTotals {
+            count: self.count,
+            errors: self.errors,
+            error_rate: if self.count == 0 {
+                0.0
+            } else {
+                self.errors as f64 / self.count as f64
+            },
+            avg_latency_ms: (self.responded > 0).then(|| self.total_ms / self.responded),
+        }
+    }
+}
+
+fn format_day(day: NaiveDate) -> String {
+    day.format(DAY_FORMAT).to_string()
+}
+
+fn add_sample(buckets: &mut Vec<UsageBucket>, day: &str, sample: &UsageSample) {
+    let collection_id = sample.collection_id.map(str::to_string);
+    let index = buckets.iter().position(|bucket| {
+        bucket.day == day && bucket.host == sample.host && bucket.collection_id == collection_id
+    });
+    let bucket = match index {
+        Some(index) => &mut buckets[index],
+        None => {
+            buckets.push(UsageBucket {
+                day: day.to_string(),
+                host: sample.host.to_string(),
+                collection_id,
+                count: 0,
+                errors: 0,
+                responded: 0,
+                total_ms: 0,
+            });
+            buckets.last_mut().unwrap()
+        }
+    };
+    bucket.count += 1;
+    if sample.error {
+        bucket.errors += 1;
+    }
+    if let Some(latency) = sample.latency_ms {
+        bucket.responded += 1;
+        bucket.total_ms += latency;
+    }
+}
+
+/// Aggregate the buckets from `first_day` through `last_day`.
+fn aggregate(
+    buckets: &[UsageBucket],
+    grouping: UsageGrouping,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+) -> (UsageTotals, Vec<UsageGroup>) {
+    let (first, last) = (format_day(first_day), format_day(last_day));
+    let mut overall = Tally::default();
+    let mut groups: BTreeMap<&str, BTreeMap<&str, Tally>> = BTreeMap::new();
+    for bucket in buckets {
+        if bucket.day < first || bucket.day > last {
+            continue;
+        }
+        overall.add(bucket);
+        let key = match grouping {
+            UsageGrouping::Host => Some(bucket.host.as_str()),
+            UsageGrouping::Collection => bucket.collection_id.as_deref(),
+        };
+        // Requests sent outside a collection only count towards the totals
+        let Some(key) = key else {
+            continue;
+        };
+        groups
+            .entry(key)
+            .or_default()
+            .entry(bucket.day.as_str())
+            .or_default()
+            .add(bucket);
+    }
+
+    let days: Vec<String> = first_day
+        .iter_days()
+        .take_while(|day| *day <= last_day)
+        .map(format_day)
+        .collect();
+    let mut groups: Vec<UsageGroup> = groups
+        .into_iter()
+        .map(|(key, by_day)| {
+            let mut total = Tally::default();
+            let series = days
+                .iter()
+                .map(|day| {
+                    let tally = by_day.get(day.as_str()).copied().unwrap_or_default();
+                    total.merge(tally);
+                    UsagePoint {
+                        day: day.clone(),
+                        totals: tally.totals(),
+                    }
+                })
+                .collect();
+            UsageGroup {
+                key: key.to_string(),
+                totals: total.totals(),
+                series,
+            }
+        })
+        .collect();
+    groups.sort_by_key(|group| Reverse(group.totals.count));
+    (overall.totals(), groups)
+}
+
+fn read_buckets(app: &AppHandle) -> Vec<UsageBucket> {
+    app.store(store_file(app))
+        .ok()
+        .and_then(|store| store.get(USAGE_STATS_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn write_buckets(app: &AppHandle, buckets: &[UsageBucket]) -> Result<(), String> {
+    let store = app
+        .store(store_file(app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let value = serde_json::to_value(buckets)
+        .map_err(|e| format!("Failed to serialize usage stats: {}", e))?;
+    store.set(USAGE_STATS_KEY.to_string(), value);
+    save_store(app)
+}
+
+/// Count a sent request. Cancelled requests and ones held back for
+/// confirmation never reached the server and are not counted.
+pub(crate) fn record_usage(
+    app: &AppHandle,
+    url: &str,
+    collection_id: Option<&str>,
+    response: &ApiResponse,
+) {
+    if response.cancelled == Some(true) || response.confirmation_required == Some(true) {
+        return;
+    }
+    let Some(host) = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_ascii_lowercase))
+    else {
+        return;
+    };
+    let sample = UsageSample {
+        host: &host,
+        collection_id,
+        error: !matches!(response.status, Some(status) if status < 400),
+        latency_ms: response.status.map(|_| response.timings.total),
+    };
+
+    let today = Utc::now().date_naive();
+    let cutoff = format_day(today - Duration::days(RETENTION_DAYS));
+    let _guard = lock_store();
+    let mut buckets = read_buckets(app);
+    buckets.retain(|bucket| bucket.day > cutoff);
+    add_sample(&mut buckets, &format_day(today), &sample);
+    if let Err(e) = write_buckets(app, &buckets) {
+        eprintln!("Failed to store usage stats: {}", e);
+    }
+}
+
+/// Request counts, error rates and average latency over the last `days` days
+/// (default 30), per host or per collection, with a daily series for each.
+#[tauri::command]
+pub async fn usage_stats_query(
+    app: AppHandle,
+    group_by: UsageGrouping,
+    days: Option<u32>,
+) -> Result<UsageReport, String> {
+    let days = days
+        .unwrap_or(DEFAULT_QUERY_DAYS)
+        .clamp(1, RETENTION_DAYS as u32);
+    let today = Utc::now().date_naive();
+    let first_day = today - Duration::days(i64::from(days) - 1);
+    let (totals, groups) = aggregate(&read_buckets(&app), group_by, first_day, today);
+    Ok(UsageReport {
+        days,
+        totals,
+        groups,
+    })
+}
+
+/// Forget all usage statistics of the active workspace.
+#[tauri::command]
+pub async fn usage_stats_clear(app: AppHandle) -> Result<(), String> {
+    let _guard = lock_store();
+    write_buckets(&app, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_daily_buckets_per_host_and_collection() {
+        let mut buckets = Vec::new();
+        let samples = [
+            (
+                "2024-03-01",
+                "api.example.com",
+                Some("c1"),
+                false,
+                Some(100),
+            ),
+            ("2024-03-01", "api.example.com", Some("c1"), true, Some(300)),
+            ("2024-03-03", "api.example.com", None, true, None),
+            (
+                "2024-03-03",
+                "auth.example.com",
+                Some("c1"),
+                false,
+                Some(50),
+            ),
+            ("2024-02-01", "old.example.com", None, false, Some(10)),
+        ];
+        for (day, host, collection_id, error, latency_ms) in samples {
+            let sample = UsageSample {
+                host,
+                collection_id,
+                error,
+                latency_ms,
+            };
+            add_sample(&mut buckets, day, &sample);
+        }
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(buckets[0].count, 2);
+
+        let first = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let last = NaiveDate::from_ymd_opt(2024, 3, 3).unwrap();
+        let (totals, groups) = aggregate(&buckets, UsageGrouping::Host, first, last);
+        assert_eq!(totals.count, 4);
+        assert_eq!(totals.errors, 2);
+        assert_eq!(totals.error_rate, 0.5);
+        assert_eq!(totals.avg_latency_ms, Some(150));
+
+        assert_eq!(groups.len(), 2);
+        let api = &groups[0];
+        assert_eq!(api.key, "api.example.com");
+        assert_eq!(api.totals.count, 3);
+        assert_eq!(api.totals.avg_latency_ms, Some(200));
+        let series: Vec<(&str, u64)> = api
+            .series
+            .iter()
+            .map(|point| (point.day.as_str(), point.totals.count))
+            .collect();
+        assert_eq!(
+            series,
+            [("2024-03-01", 2), ("2024-03-02", 0), ("2024-03-03", 1)]
+        );
+        assert_eq!(api.series[1].totals.avg_latency_ms, None);
+
+        let (totals, groups) = aggregate(&buckets, UsageGrouping::Collection, first, last);
+        assert_eq!(totals.count, 4);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].key, "c1");
+        assert_eq!(groups[0].totals.count, 3);
+    }
+}
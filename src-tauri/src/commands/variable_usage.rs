@@ -0,0 +1,403 @@
+//! Variable usage report: finds every `{{variable}}` reference in collections,
+//! endpoints and scripts and checks it against the environments and
+//! collection variables, listing references nothing defines and variables
+//! nothing uses. Meant for cleaning up large imported workspaces.
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use super::collections::{
+    collection_get_endpoint_data, collection_get_variables, collections_get_all,
+};
+use super::variables::variable_pattern;
+use super::workspaces::store_file;
+
+const ENVIRONMENTS_KEY: &str = "environments";
+
+/// `environment.get("name")` / `pm.environment.set('name', ...)` in scripts.
+fn script_access_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"environment\.(get|set|unset)\(\s*['"`]([^'"`]+)['"`]"#).unwrap()
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableUsage {
+    pub name: String,
+    /// Where the variable is referenced, e.g. "Pets / List pets"
+    pub referenced_in: Vec<String>,
+    /// Environments and collections defining the variable
+    pub defined_in: Vec<String>,
+    /// Environments that lack the variable
+    pub missing_in: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedVariable {
+    pub name: String,
+    /// "environment" | "collection"
+    pub scope: String,
+    /// Name of the environment or collection defining the variable
+    pub owner: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableUsageReport {
+    /// Referenced variables no environment or collection defines
+    pub undefined: Vec<VariableUsage>,
+    /// Referenced variables only some environments define
+    pub incomplete: Vec<VariableUsage>,
+    pub unused: Vec<UnusedVariable>,
+    /// Distinct variables referenced anywhere
+    pub referenced_count: usize,
+}
+
+/// Variables defined by one environment or collection.
+struct DefinedVariables {
+    owner: String,
+    keys: HashSet<String>,
+}
+
+#[derive(Default)]
+struct UsageScan {
+    /// Referenced name -> (location, referencing collection id; `None` for
+    /// references in environment values, which apply everywhere)
+    references: BTreeMap<String, Vec<(String, Option<String>)>>,
+    /// Names scripts set at run time
+    set_by_scripts: HashSet<String>,
+}
+
+impl UsageScan {
+    fn add_references(&mut self, value: &Value, location: &str, collection_id: Option<&str>) {
+        match value {
+            Value::String(text) => {
+                for caps in variable_pattern().captures_iter(text) {
+                    self.references
+                        .entry(caps[1].to_string())
+                        .or_default()
+                        .push((location.to_string(), collection_id.map(str::to_string)));
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.add_references(item, location, collection_id);
+                }
+            }
+            Value::Object(map) => {
+                for item in map.values() {
+                    self.add_references(item, location, collection_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Scripts reference variables both as `{{name}}` and through the
+    /// `environment` API.
+    fn add_script(&mut self, scripts: &Value, location: &str, collection_id: &str) {
+        self.add_references(scripts, location, Some(collection_id));
+        let texts = scripts.as_object().into_iter().flat_map(|map| map.values());
+        for text in texts.filter_map(Value::as_str) {
+            for caps in script_access_pattern().captures_iter(text) {
+                let name = caps[2].to_string();
+                if &caps[1] == "get" {
+                    self.references
+                        .entry(name)
+                        .or_default()
+                        .push((location.to_string(), Some(collection_id.to_string())));
+                } else {
+                    self.set_by_scripts.insert(name);
+                }
+            }
+        }
+    }
+}
+
+fn build_report(
+    scan: &UsageScan,
+    environments: &[DefinedVariables],
+    collections: &HashMap<String, DefinedVariables>,
+) -> VariableUsageReport {
+    let mut report = VariableUsageReport {
+        referenced_count: scan.references.len(),
+        ..Default::default()
+    };
+
+    for (name, references) in &scan.references {
+        // Collection variables cover the references of their own collection
+        let uncovered = references.iter().any(|(_, collection_id)| {
+            !collection_id
+                .as_ref()
+                .and_then(|id| collections.get(id))
+                .is_some_and(|collection| collection.keys.contains(name))
+        });
+        if !uncovered || scan.set_by_scripts.contains(name) {
+            continue;
+        }
+
+        let (defining, missing): (Vec<&DefinedVariables>, Vec<&DefinedVariables>) = environments
+            .iter()
+            .partition(|environment| environment.keys.contains(name));
+        let mut defined_in: Vec<String> = defining.iter().map(|e| e.owner.clone()).collect();
+        let mut defining_collections: Vec<String> = collections
+            .values()
+            .filter(|collection| collection.keys.contains(name))
+            .map(|collection| collection.owner.clone())
+            .collect();
+        defining_collections.sort();
+        defined_in.extend(defining_collections);
+
+        let usage = VariableUsage {
+            name: name.clone(),
+            referenced_in: references
+                .iter()
+                .map(|(location, _)| location.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            defined_in,
+            missing_in: missing.iter().map(|e| e.owner.clone()).collect(),
+        };
+        if defining.is_empty() {
+            report.undefined.push(usage);
+        } else if !usage.missing_in.is_empty() {
+            report.incomplete.push(usage);
+        }
+    }
+
+    for environment in environments {
+        for key in &environment.keys {
+            if !scan.references.contains_key(key) {
+                report.unused.push(UnusedVariable {
+                    name: key.clone(),
+                    scope: "environment".to_string(),
+                    owner: environment.owner.clone(),
+                });
+            }
+        }
+    }
+    for (collection_id, collection) in collections {
+        for key in &collection.keys {
+            let used = scan.references.get(key).is_some_and(|references| {
+                references
+                    .iter()
+                    .any(|(_, id)| id.is_none() || id.as_ref() == Some(collection_id))
+            });
+            if !used {
+                report.unused.push(UnusedVariable {
+                    name: key.clone(),
+                    scope: "collection".to_string(),
+                    owner: collection.owner.clone(),
+                });
+            }
+        }
+    }
+    report.unused.sort_by(|a, b| {
+        (a.scope.as_str(), a.owner.as_str(), a.name.as_str()).cmp(&(
+            b.scope.as_str(),
+            b.owner.as_str(),
+            b.name.as_str(),
+        ))
+    });
+    report
+}
+
+fn string_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Environments as stored by the frontend (`{ items: [...] }`, or a bare
+/// array in older stores).
+fn load_environments(
+    app: &AppHandle,
+    scan: &mut UsageScan,
+) -> Result<Vec<DefinedVariables>, String> {
+    let store = app.store(store_file(app)).map_err(|e| e.to_string())?;
+    let environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+    let items = environments
+        .get("items")
+        .unwrap_or(&environments)
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(items
+        .iter()
+        .map(|environment| {
+            let owner = string_field(environment, "name").to_string();
+            let variables = environment.get("variables").and_then(Value::as_object);
+            if let Some(variables) = variables {
+                // Values may reference other variables
+                let location = format!("{} (environment)", owner);
+                for value in variables.values() {
+                    scan.add_references(value, &location, None);
+                }
+            }
+            DefinedVariables {
+                keys: variables
+                    .map(|vars| vars.keys().cloned().collect())
+                    .unwrap_or_default(),
+                owner,
+            }
+        })
+        .collect())
+}
+
+/// Scans every collection, endpoint and script of the workspace for
+/// `{{variable}}` references and reports the undefined and unused ones.
+#[tauri::command]
+pub async fn variables_usage_report(app: AppHandle) -> Result<VariableUsageReport, String> {
+    let mut scan = UsageScan::default();
+    let environments = load_environments(&app, &mut scan)?;
+    let mut collections = HashMap::new();
+
+    for collection in collections_get_all(app.clone()).await? {
+        let id = collection.id.clone();
+        let name = collection.name.clone();
+
+        let variables = collection_get_variables(app.clone(), id.clone()).await?;
+        let mut keys = HashSet::new();
+        let variables_location = format!("{} (variables)", name);
+        for entry in &variables {
+            let key = string_field(entry, "key");
+            if key.is_empty() || entry.get("enabled").and_then(Value::as_bool) == Some(false) {
+                continue;
+            }
+            keys.insert(key.to_string());
+            if let Some(value) = entry.get("value") {
+                scan.add_references(value, &variables_location, Some(&id));
+            }
+        }
+
+        let folder_endpoints = collection
+            .folders
+            .iter()
+            .filter_map(|folder| folder.get("endpoints").and_then(Value::as_array))
+            .flatten();
+        let mut seen = HashSet::new();
+        let endpoints: Vec<Value> = collection
+            .endpoints
+            .iter()
+            .chain(folder_endpoints)
+            .filter(|endpoint| seen.insert(string_field(endpoint, "id").to_string()))
+            .cloned()
+            .collect();
+
+        // Collection settings: base URL, default headers, auth, proxy, login
+        let mut settings = serde_json::to_value(&collection).map_err(|e| e.to_string())?;
+        if let Some(map) = settings.as_object_mut() {
+            for key in [
+                "id",
+                "name",
+                "endpoints",
+                "folders",
+                "_openApiSpec",
+                "storagePath",
+            ] {
+                map.remove(key);
+            }
+        }
+        scan.add_references(&settings, &name, Some(&id));
+
+        for endpoint in &endpoints {
+            let endpoint_id = string_field(endpoint, "id");
+            let location = format!("{} / {}", name, string_field(endpoint, "name"));
+            scan.add_references(endpoint, &location, Some(&id));
+
+            let data =
+                collection_get_endpoint_data(app.clone(), id.clone(), endpoint_id.to_string())
+                    .await?;
+            if let Some(scripts) = &data.scripts {
+                scan.add_script(scripts, &format!("{} (script)", location), &id);
+            }
+            let request = serde_json::json!([
+                data.modified_body,
+                data.path_params,
+                data.query_params,
+                data.headers,
+                data.auth_config,
+                data.url,
+                data.graphql_data,
+                data.form_body_data,
+                data.grpc_data
+            ]);
+            scan.add_references(&request, &location, Some(&id));
+        }
+
+        collections.insert(id, DefinedVariables { owner: name, keys });
+    }
+
+    Ok(build_report(&scan, &environments, &collections))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defined(owner: &str, keys: &[&str]) -> DefinedVariables {
+        DefinedVariables {
+            owner: owner.to_string(),
+            keys: keys.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn reports_undefined_incomplete_and_unused_variables() {
+        let mut scan = UsageScan::default();
+        scan.add_references(
+            &serde_json::json!({
+                "path": "{{baseUrl}}/users/{{ userId }}",
+                "headers": [{ "key": "Authorization", "value": "Bearer {{token}}" }],
+                "body": "{{$uuid}} {{apiVersion}}"
+            }),
+            "API / Get user",
+            Some("c1"),
+        );
+        scan.add_script(
+            &serde_json::json!({
+                "preRequestScript": "const t = environment.get('tenant');",
+                "testScript": "pm.environment.set(\"userId\", response.json().id);"
+            }),
+            "API / Get user (script)",
+            "c1",
+        );
+
+        let environments = vec![
+            defined("Dev", &["token", "tenant", "legacyHost"]),
+            defined("Prod", &["token"]),
+        ];
+        let collections =
+            HashMap::from([("c1".to_string(), defined("API", &["baseUrl", "unusedFlag"]))]);
+        let report = build_report(&scan, &environments, &collections);
+
+        assert_eq!(report.referenced_count, 5);
+        let undefined: Vec<&str> = report.undefined.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(undefined, vec!["apiVersion"]);
+        assert_eq!(report.incomplete.len(), 1);
+        assert_eq!(report.incomplete[0].name, "tenant");
+        assert_eq!(report.incomplete[0].missing_in, vec!["Prod"]);
+        assert_eq!(
+            report.incomplete[0].referenced_in,
+            vec!["API / Get user (script)"]
+        );
+
+        let unused: Vec<(&str, &str)> = report
+            .unused
+            .iter()
+            .map(|u| (u.scope.as_str(), u.name.as_str()))
+            .collect();
+        assert_eq!(
+            unused,
+            vec![("collection", "unusedFlag"), ("environment", "legacyHost")]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Backend `{{variable}}` resolution for requests sent without the frontend
+//! (collection runner and friends).
+//!
+//! Mirrors `VariableProcessor` in the frontend: static `{{name}}` references
+//! and a subset of the `{{$dynamic}}` generators are substituted in repeated
+//! passes, and unknown references are left verbatim so they stay visible in
+//! the resulting request.
+
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use super::environment_vault::{unlocked_variables, EnvironmentVaultState};
+use super::workspaces::store_file;
+
+const ENVIRONMENTS_KEY: &str = "environments";
+const ACTIVE_ENVIRONMENT_KEY: &str = "activeEnvironmentId";
+/// Field of an endpoint in the collection file whose values win over the
+/// environment and collection variables for that endpoint's requests.
+const VARIABLE_OVERRIDES_FIELD: &str = "variableOverrides";
+
+/// Matches the frontend's `MAX_RESOLUTION_PASSES`.
+const MAX_RESOLUTION_PASSES: usize = 10;
+
+pub(crate) fn variable_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_][A-Za-z0-9_.-]*)\s*\}\}").unwrap())
+}
+
+fn dynamic_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\{\{\s*\$([a-zA-Z_][a-zA-Z0-9_]*)(?::([^}]*))?\s*\}\}").unwrap()
+    })
+}
+
+/// Resolves templates against a variable map. Dynamic values are cached per
+/// resolver so `{{$uuid}}` yields the same value everywhere in one request.
+#[derive(Debug, Default)]
+pub struct VariableResolver {
+    variables: HashMap<String, String>,
+    dynamic_cache: HashMap<String, String>,
+}
+
+impl VariableResolver {
+    pub fn new(variables: HashMap<String, String>) -> Self {
+        Self {
+            variables,
+            dynamic_cache: HashMap::new(),
+        }
+    }
+
+    pub fn variables(&self) -> &HashMap<String, String> {
+        &self.variables
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.variables.insert(key.into(), value.into());
+    }
+
+    /// Substitute every resolvable reference in `template`.
+    pub fn resolve(&mut self, template: &str) -> String {
+        let mut result = template.to_string();
+        for _ in 0..MAX_RESOLUTION_PASSES {
+            let next = self.resolve_pass(&result);
+            if next == result {
+                break;
+            }
+            result = next;
+        }
+        result
+    }
+
+    fn resolve_pass(&mut self, input: &str) -> String {
+        let with_dynamic = dynamic_pattern()
+            .replace_all(input, |caps: &regex::Captures| {
+                let name = &caps[1];
+                let params = caps.get(2).map(|m| m.as_str());
+                let cache_key = match params {
+                    Some(p) => format!("{}:{}", name, p),
+                    None => name.to_string(),
+                };
+                if let Some(cached) = self.dynamic_cache.get(&cache_key) {
+                    return cached.clone();
+                }
+                match generate_dynamic(name, params) {
+                    Some(value) => {
+                        self.dynamic_cache.insert(cache_key, value.clone());
+                        value
+                    }
+                    None => caps[0].to_string(),
+                }
+            })
+            .into_owned();
+
+        variable_pattern()
+            .replace_all(&with_dynamic, |caps: &regex::Captures| {
+                self.variables
+                    .get(&caps[1])
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+}
+
+fn random_u64() -> u64 {
+    let mut buf = [0u8; 8];
+    getrandom::fill(&mut buf).expect("OS random number generator unavailable");
+    u64::from_le_bytes(buf)
+}
+
+/// Generate a value for the dynamic variables that make sense outside the
+/// UI. Returns `None` for unknown generators so the reference stays verbatim.
+fn generate_dynamic(name: &str, params: Option<&str>) -> Option<String> {
+    let now = chrono::Utc::now();
+    match name {
+        "timestamp" => Some(now.timestamp().to_string()),
+        "timestampMs" => Some(now.timestamp_millis().to_string()),
+        "isoTimestamp" => Some(now.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+        "uuid" => Some(uuid::Uuid::new_v4().to_string()),
+        "randomBoolean" => Some((random_u64() % 2 == 0).to_string()),
+        "randomInt" => {
+            let (min, max) = params
+                .and_then(|p| {
+                    let mut parts = p.split(':');
+                    let min = parts.next()?.trim().parse::<i64>().ok()?;
+                    let max = parts.next()?.trim().parse::<i64>().ok()?;
+                    Some((min, max))
+                })
+                .unwrap_or((0, 1000));
+            let (min, max) = if min <= max { (min, max) } else { (max, min) };
+            let span = (max - min) as u64 + 1;
+            Some((min + (random_u64() % span) as i64).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Convert a stored value (string, number, bool) to its template form.
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        Value::Array(_) | Value::Object(_) => Some(value.to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// The endpoint's variable overrides (`{name: value}`).
+pub(crate) fn endpoint_variable_overrides(endpoint: &Value) -> HashMap<String, String> {
+    endpoint
+        .get(VARIABLE_OVERRIDES_FIELD)
+        .and_then(Value::as_object)
+        .map(|overrides| {
+            overrides
+                .iter()
+                .filter(|(name, _)| !name.is_empty())
+                .filter_map(|(name, value)| value_to_string(value).map(|v| (name.clone(), v)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collection variables from `variables.json` (`[{key, value, enabled?}]`).
+/// Secret entries are stored blanked on disk; their values come from the
+/// secret store.
+pub(crate) async fn load_collection_variables(
+    app: &AppHandle,
+    collection_id: &str,
+) -> HashMap<String, String> {
+    let entries =
+        super::collections::collection_get_variables(app.clone(), collection_id.to_string())
+            .await
+            .unwrap_or_default();
+
+    let mut variables = HashMap::new();
+    let mut has_secrets = false;
+    for entry in entries {
+        let Some(key) = entry.get("key").and_then(Value::as_str) else {
+            continue;
+        };
+        if key.is_empty() || entry.get("enabled").and_then(Value::as_bool) == Some(false) {
+            continue;
+        }
+        if entry.get("secret").and_then(Value::as_bool) == Some(true) {
+            has_secrets = true;
+            continue;
+        }
+        if let Some(value) = entry.get("value").and_then(value_to_string) {
+            variables.insert(key.to_string(), value);
+        }
+    }
+    if has_secrets {
+        let scope = format!("collvar:{}", collection_id);
+        variables.extend(super::secrets::read_secret_scope(app, &scope).await);
+    }
+    variables
+}
+
+/// The active environment as stored by the frontend
+/// (`environments: {items: [{id, variables, secretKeys}], activeEnvironmentId}`).
+pub(crate) fn active_environment(app: &AppHandle) -> Option<Value> {
+    let store = app.store(store_file(app)).ok()?;
+    let environments = store.get(ENVIRONMENTS_KEY).unwrap_or(Value::Null);
+    let active_id = environments
+        .get("activeEnvironmentId")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+            store
+                .get(ACTIVE_ENVIRONMENT_KEY)
+                .and_then(|v| v.as_str().map(str::to_string))
+        })?;
+
+    environments
+        .get("items")
+        .and_then(Value::as_array)?
+        .iter()
+        .find(|env| env.get("id").and_then(Value::as_str) == Some(active_id.as_str()))
+        .cloned()
+}
+
+pub(crate) fn active_environment_id(app: &AppHandle) -> Option<String> {
+    active_environment(app)?
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Variables of the active environment, with secret and encrypted variables
+/// filled in (the persisted map only holds empty placeholders for them).
+pub(crate) async fn load_active_environment_variables(app: &AppHandle) -> HashMap<String, String> {
+    let Some(environment) = active_environment(app) else {
+        return HashMap::new();
+    };
+    let mut variables: HashMap<String, String> = environment
+        .get("variables")
+        .and_then(Value::as_object)
+        .map(|vars| {
+            vars.iter()
+                .filter_map(|(k, v)| value_to_string(v).map(|v| (k.clone(), v)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let id = environment
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    // Encrypted environments contribute their values only while unlocked
+    if environment.get("encrypted").is_some() {
+        if let Some(state) = app.try_state::<EnvironmentVaultState>() {
+            variables.extend(unlocked_variables(&state, id).unwrap_or_default());
+        }
+    }
+    let has_secrets = environment
+        .get("secretKeys")
+        .and_then(Value::as_array)
+        .is_some_and(|keys| !keys.is_empty());
+    if has_secrets {
+        let scope = format!("env:{}", id);
+        variables.extend(super::secrets::read_secret_scope(app, &scope).await);
+    }
+    variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver(pairs: &[(&str, &str)]) -> VariableResolver {
+        VariableResolver::new(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn resolves_nested_references_and_keeps_unknown_ones() {
+        let mut r = resolver(&[("baseUrl", "{{scheme}}://api.test"), ("scheme", "https")]);
+        assert_eq!(
+            r.resolve("{{baseUrl}}/users/{{ id }}?q={{missing}}"),
+            "https://api.test/users/{{ id }}?q={{missing}}"
+        );
+    }
+
+    #[test]
+    fn self_reference_stops_after_bounded_passes() {
+        let mut r = resolver(&[("a", "x{{a}}")]);
+        let out = r.resolve("{{a}}");
+        assert!(out.starts_with("xxxxxxxxxx"));
+    }
+
+    #[test]
+    fn dynamic_values_are_cached_per_resolver() {
+        let mut r = resolver(&[]);
+        let out = r.resolve("{{$uuid}}|{{$uuid}}|{{$unknownGenerator}}");
+        let parts: Vec<&str> = out.split('|').collect();
+        assert_eq!(parts[0], parts[1]);
+        assert_eq!(parts[0].len(), 36);
+        assert_eq!(parts[2], "{{$unknownGenerator}}");
+    }
+
+    #[test]
+    fn endpoint_overrides_win_over_environment_values() {
+        let endpoint = serde_json::json!({
+            "variableOverrides": { "region": "eu-west-1", "retries": 3, "": "x", "unset": null }
+        });
+        let overrides = endpoint_variable_overrides(&endpoint);
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides["retries"], "3");
+
+        let mut r = resolver(&[("region", "us-east-1"), ("host", "{{region}}.api.test")]);
+        for (name, value) in overrides {
+            r.set(name, value);
+        }
+        assert_eq!(r.resolve("https://{{host}}"), "https://eu-west-1.api.test");
+        assert!(endpoint_variable_overrides(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn random_int_respects_bounds() {
+        for _ in 0..50 {
+            let mut r = resolver(&[]);
+            let n: i64 = r.resolve("{{$randomInt:5:7}}").parse().unwrap();
+            assert!((5..=7).contains(&n));
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! Webhook receiver: a local listener that accepts any request sent to a
+//! chosen port and path, keeps it with its headers and body, and streams it
+//! to the UI as a `webhook-request` event. Used like a local request bin to
+//! test the webhooks a service sends out.
+
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, DefaultBodyLimit, State as AxumState},
+    http::{HeaderMap, Method, StatusCode, Uri},
+    response::{IntoResponse, Json, Response},
+    routing::any,
+    Router,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+const EVENT_NAME: &str = "webhook-request";
+/// Captured requests kept; the oldest are dropped first.
+const MAX_REQUESTS: usize = 500;
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookReceiverConfig {
+    pub port: u16,
+    /// Path requests are accepted under, e.g. `/hooks`; empty accepts all
+    #[serde(default)]
+    pub path: String,
+    /// Status answered to captured requests, 200 when not set
+    #[serde(default)]
+    pub response_status: Option<u16>,
+    /// Listen on all interfaces, so services in containers or on other
+    /// machines can reach the receiver; localhost only otherwise
+    #[serde(default)]
+    pub listen_on_all_interfaces: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BodyEncoding {
+    Text,
+    Base64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedRequest {
+    pub id: String,
+    /// Unix timestamp (ms)
+    pub received_at: i64,
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    /// In the order received; repeated headers are kept
+    pub headers: Vec<CapturedHeader>,
+    /// UTF-8 bodies as text, others base64-encoded
+    pub body: String,
+    pub body_encoding: BodyEncoding,
+    pub size: usize,
+    pub remote_addr: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookReceiverStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub request_count: usize,
+}
+
+struct RunningReceiver {
+    shutdown_tx: oneshot::Sender<()>,
+    port: u16,
+    url: String,
+}
+
+type RequestLog = Arc<Mutex<VecDeque<CapturedRequest>>>;
+
+#[derive(Default)]
+pub struct WebhookReceiverState {
+    running: Mutex<Option<RunningReceiver>>,
+    requests: RequestLog,
+}
+
+impl WebhookReceiverState {
+    fn running(&self) -> MutexGuard<'_, Option<RunningReceiver>> {
+        self.running.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn status(&self) -> WebhookReceiverStatus {
+        let request_count = lock_requests(&self.requests).len();
+        match self.running().as_ref() {
+            Some(receiver) => WebhookReceiverStatus {
+                running: true,
+                port: Some(receiver.port),
+                url: Some(receiver.url.clone()),
+                request_count,
+            },
+            None => WebhookReceiverStatus {
+                request_count,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+fn lock_requests(requests: &RequestLog) -> MutexGuard<'_, VecDeque<CapturedRequest>> {
+    requests.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[derive(Clone)]
+struct ReceiverContext {
+    app: AppHandle,
+    base_path: String,
+    status: StatusCode,
+    requests: RequestLog,
+}
+
+/// `path` as a base path: leading slash, no trailing one, empty for the root.
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+fn path_matches(base_path: &str, path: &str) -> bool {
+    base_path.is_empty()
+        || path == base_path
+        || path
+            .strip_prefix(base_path)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn capture(
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+    body: &[u8],
+    remote_addr: SocketAddr,
+) -> CapturedRequest {
+    let (body_text, body_encoding) = match std::str::from_utf8(body) {
+        Ok(text) => (text.to_string(), BodyEncoding::Text),
+        Err(_) => (STANDARD.encode(body), BodyEncoding::Base64),
+    };
+    CapturedRequest {
+        id: Uuid::new_v4().to_string(),
+        received_at: chrono::Utc::now().timestamp_millis(),
+        method: method.to_string(),
+        path: uri.path().to_string(),
+        query: uri.query().unwrap_or_default().to_string(),
+        headers: headers
+            .iter()
+            .map(|(name, value)| CapturedHeader {
+                name: name.to_string(),
+                value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            })
+            .collect(),
+        body: body_text,
+        body_encoding,
+        size: body.len(),
+        remote_addr: remote_addr.to_string(),
+    }
+}
+
+fn record(requests: &RequestLog, request: CapturedRequest) {
+    let mut requests = lock_requests(requests);
+    if requests.len() >= MAX_REQUESTS {
+        requests.pop_front();
+    }
+    requests.push_back(request);
+}
+
+async fn handle_webhook(
+    AxumState(context): AxumState<ReceiverContext>,
+    ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if !path_matches(&context.base_path, uri.path()) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "Not a webhook path",
+                "path": uri.path(),
+            })),
+        )
+            .into_response();
+    }
+
+    let request = capture(&method, &uri, &headers, &body, remote_addr);
+    let id = request.id.clone();
+    let _ = context.app.emit(EVENT_NAME, &request);
+    record(&context.requests, request);
+
+    (
+        context.status,
+        Json(serde_json::json!({ "received": true, "id": id })),
+    )
+        .into_response()
+}
+
+/// Start listening for webhooks.
+#[tauri::command]
+pub async fn webhook_receiver_start(
+    app: AppHandle,
+    state: State<'_, WebhookReceiverState>,
+    config: WebhookReceiverConfig,
+) -> Result<WebhookReceiverStatus, String> {
+    if state.running().is_some() {
+        return Err("The webhook receiver is already running".to_string());
+    }
+    let status = match config.response_status {
+        Some(code) => {
+            StatusCode::from_u16(code).map_err(|_| format!("Invalid response status {}", code))?
+        }
+        None => StatusCode::OK,
+    };
+    let base_path = normalize_path(&config.path);
+    let ip = if config.listen_on_all_interfaces {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    };
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::new(ip, config.port))
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AddrInUse {
+                format!("Port {} is already in use", config.port)
+            } else {
+                format!("Failed to listen on port {}: {}", config.port, e)
+            }
+        })?;
+
+    let context = ReceiverContext {
+        app,
+        base_path: base_path.clone(),
+        status,
+        requests: state.requests.clone(),
+    };
+    let router = Router::new()
+        .route("/*path", any(handle_webhook))
+        .route("/", any(handle_webhook))
+        .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
+        .with_state(context);
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    {
+        let mut running = state.running();
+        // Another start won the race while this one was binding
+        if running.is_some() {
+            return Err("The webhook receiver is already running".to_string());
+        }
+        *running = Some(RunningReceiver {
+            shutdown_tx,
+            port: config.port,
+            url: format!(
+                "http://localhost:{}{}",
+                config.port,
+                if base_path.is_empty() {
+                    "/"
+                } else {
+                    &base_path
+                }
+            ),
+        });
+    }
+
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        })
+        .await
+        .ok();
+    });
+
+    Ok(state.status())
+}
+
+/// Stop listening. Captured requests are kept until cleared.
+#[tauri::command]
+pub async fn webhook_receiver_stop(
+    state: State<'_, WebhookReceiverState>,
+) -> Result<WebhookReceiverStatus, String> {
+    if let Some(receiver) = state.running().take() {
+        let _ = receiver.shutdown_tx.send(());
+    }
+    Ok(state.status())
+}
+
+#[tauri::command]
+pub async fn webhook_receiver_status(
+    state: State<'_, WebhookReceiverState>,
+) -> Result<WebhookReceiverStatus, String> {
+    Ok(state.status())
+}
+
+/// Captured requests, newest first.
+#[tauri::command]
+pub async fn webhook_receiver_requests(
+    state: State<'_, WebhookReceiverState>,
+) -> Result<Vec<CapturedRequest>, String> {
+    Ok(lock_requests(&state.requests)
+        .iter()
+        .rev()
+        .cloned()
+        .collect())
+}
+
+#[tauri::command]
+pub async fn webhook_receiver_clear(state: State<'_, WebhookReceiverState>) -> Result<(), String> {
+    lock_requests(&state.requests).clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_requests_under_the_base_path() {
+        assert_eq!(normalize_path(" /hooks/github/ "), "/hooks/github");
+        assert_eq!(normalize_path("/"), "");
+        assert!(path_matches("", "/anything"));
+        assert!(path_matches("/hooks", "/hooks"));
+        assert!(path_matches("/hooks", "/hooks/github"));
+        assert!(!path_matches("/hooks", "/hooksmith"));
+        assert!(!path_matches("/hooks", "/"));
+
+        let mut headers = HeaderMap::new();
+        headers.append("x-signature", "a".parse().unwrap());
+        headers.append("x-signature", "b".parse().unwrap());
+        let uri: Uri = "/hooks/github?delivery=1".parse().unwrap();
+        let addr: SocketAddr = "127.0.0.1:5000".parse().unwrap();
+
+        let text = capture(&Method::POST, &uri, &headers, b"{\"ok\":true}", addr);
+        assert_eq!(text.path, "/hooks/github");
+        assert_eq!(text.query, "delivery=1");
+        assert_eq!(text.headers.len(), 2);
+        assert_eq!(text.body_encoding, BodyEncoding::Text);
+        assert_eq!(text.remote_addr, "127.0.0.1:5000");
+
+        let binary = capture(&Method::PUT, &uri, &HeaderMap::new(), &[0xff, 0x00], addr);
+        assert_eq!(binary.body_encoding, BodyEncoding::Base64);
+        assert_eq!(binary.body, "/wA=");
+        assert_eq!(binary.size, 2);
+
+        let requests = RequestLog::default();
+        for _ in 0..MAX_REQUESTS {
+            record(&requests, text.clone());
+        }
+        record(&requests, binary.clone());
+        let kept = lock_requests(&requests);
+        assert_eq!(kept.len(), MAX_REQUESTS);
+        assert_eq!(kept.back().unwrap().id, binary.id);
+    }
+}
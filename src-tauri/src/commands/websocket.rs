@@ -2,13 +2,15 @@ use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::{mpsc, Mutex};
 use tokio_tungstenite::{
     connect_async,
     tungstenite::{client::IntoClientRequest, protocol::Message},
 };
 
+use super::proxy::ProxyState;
+
 #[derive(Clone)]
 struct WebSocketConnection {
     sender: mpsc::UnboundedSender<WebSocketCommand>,
@@ -85,6 +87,7 @@ async fn establish_connection(
     url: String,
     headers: HashMap<String, String>,
 ) -> Result<mpsc::UnboundedSender<WebSocketCommand>, String> {
+    app.state::<ProxyState>().check_offline(&url)?;
     let mut request = url
         .clone()
         .into_client_request()
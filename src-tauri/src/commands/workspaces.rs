@@ -0,0 +1,325 @@
+//! Workspaces: fully separate sets of collections, environments, proxies and
+//! other settings.
+//!
+//! Each workspace has its own store file and its own collections directory.
+//! The built-in "default" workspace keeps the original locations
+//! (`resonance-store.json`, `<app data>/collections`) so existing installs
+//! need no migration. The workspace list and the active workspace live in a
+//! separate registry store that is shared by all workspaces.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_store::StoreExt;
+
+use super::monitors::{start_saved_monitors, MonitorState};
+use super::proxy::ProxyState;
+use super::store::{lock_store, open_store, save_store_file, SavedStore};
+
+const REGISTRY_FILE: &str = "resonance-workspaces.json";
+const WORKSPACES_KEY: &str = "workspaces";
+const ACTIVE_WORKSPACE_KEY: &str = "activeWorkspaceId";
+const DEFAULT_STORE_FILE: &str = "resonance-store.json";
+const WORKSPACES_DIR: &str = "workspaces";
+
+const DEFAULT_WORKSPACE_ID: &str = "default";
+
+/// Store keys copied into a new workspace so it keeps the look and request
+/// defaults of the one it was created from.
+const INHERITED_KEYS: &[&str] = &["settings", "theme", "accentColor", "language"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// Unix timestamp (ms)
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceList {
+    pub workspaces: Vec<Workspace>,
+    pub active_workspace_id: String,
+}
+
+fn default_workspace() -> Workspace {
+    Workspace {
+        id: DEFAULT_WORKSPACE_ID.to_string(),
+        name: "Default".to_string(),
+        created_at: 0,
+    }
+}
+
+/// Workspace ids become file and directory names.
+fn is_valid_workspace_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn store_file_for(workspace_id: &str) -> String {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        DEFAULT_STORE_FILE.to_string()
+    } else {
+        format!("resonance-store-{}.json", workspace_id)
+    }
+}
+
+fn read_workspaces(app: &AppHandle) -> Vec<Workspace> {
+    let mut workspaces: Vec<Workspace> = app
+        .store(REGISTRY_FILE)
+        .ok()
+        .and_then(|store| store.get(WORKSPACES_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    if !workspaces.iter().any(|w| w.id == DEFAULT_WORKSPACE_ID) {
+        workspaces.insert(0, default_workspace());
+    }
+    workspaces
+}
+
+fn write_registry(
+    app: &AppHandle,
+    workspaces: &[Workspace],
+    active_id: &str,
+) -> Result<(), String> {
+    let store = app
+        .store(REGISTRY_FILE)
+        .map_err(|e| format!("Failed to access workspace registry: {}", e))?;
+    store.set(
+        WORKSPACES_KEY.to_string(),
+        serde_json::to_value(workspaces).map_err(|e| e.to_string())?,
+    );
+    store.set(
+        ACTIVE_WORKSPACE_KEY.to_string(),
+        Value::String(active_id.to_string()),
+    );
+    save_store_file(app, REGISTRY_FILE)
+}
+
+/// Load the registry and the active workspace's store before anything else
+/// touches them, so both are only written through `save_store_file`.
+pub fn open_workspace_stores(app: &AppHandle) -> Result<(), String> {
+    open_store(app, REGISTRY_FILE)?;
+    open_store(app, &store_file(app))
+}
+
+/// Id of the active workspace, falling back to the default one when the
+/// registry is missing or points at a deleted workspace.
+pub(crate) fn active_workspace_id(app: &AppHandle) -> String {
+    let active = app
+        .store(REGISTRY_FILE)
+        .ok()
+        .and_then(|store| store.get(ACTIVE_WORKSPACE_KEY));
+    active_id(active, &read_workspaces(app))
+}
+
+/// The saved active workspace id when it names one of `workspaces`, else the
+/// default workspace's.
+fn active_id(active: Option<Value>, workspaces: &[Workspace]) -> String {
+    let active = active
+        .and_then(|v| v.as_str().map(str::to_string))
+        .filter(|id| is_valid_workspace_id(id));
+    match active {
+        Some(id) if id == DEFAULT_WORKSPACE_ID || workspaces.iter().any(|w| w.id == id) => id,
+        _ => DEFAULT_WORKSPACE_ID.to_string(),
+    }
+}
+
+/// The active workspace's store read from `app_data_dir` without the app, for
+/// the headless CLI.
+pub(crate) fn read_active_store(app_data_dir: &Path) -> SavedStore {
+    let registry = SavedStore::read(&app_data_dir.join(REGISTRY_FILE));
+    let workspaces: Vec<Workspace> = registry
+        .get(WORKSPACES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let id = active_id(registry.get(ACTIVE_WORKSPACE_KEY), &workspaces);
+    SavedStore::read(&app_data_dir.join(store_file_for(&id)))
+}
+
+/// Store file of the active workspace. Every backend read/write of app state
+/// goes through this.
+pub(crate) fn store_file(app: &AppHandle) -> String {
+    store_file_for(&active_workspace_id(app))
+}
+
+/// Root directory for file-based data (collections, run history) of the
+/// active workspace.
+pub(crate) fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let id = active_workspace_id(app);
+    if id == DEFAULT_WORKSPACE_ID {
+        Ok(app_data_dir)
+    } else {
+        Ok(app_data_dir.join(WORKSPACES_DIR).join(id))
+    }
+}
+
+#[tauri::command]
+pub async fn workspaces_list(app: AppHandle) -> Result<WorkspaceList, String> {
+    Ok(WorkspaceList {
+        workspaces: read_workspaces(&app),
+        active_workspace_id: active_workspace_id(&app),
+    })
+}
+
+#[tauri::command]
+pub async fn workspace_create(app: AppHandle, name: String) -> Result<Workspace, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Workspace name is required".to_string());
+    }
+
+    let workspace = Workspace {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    let current = app
+        .store(store_file(&app))
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    let target_file = store_file_for(&workspace.id);
+    open_store(&app, &target_file)?;
+    let target = app
+        .store(&target_file)
+        .map_err(|e| format!("Failed to create workspace store: {}", e))?;
+    for key in INHERITED_KEYS {
+        if let Some(value) = current.get(key) {
+            target.set(key.to_string(), value);
+        }
+    }
+    save_store_file(&app, &target_file)?;
+
+    let _guard = lock_store();
+    let mut workspaces = read_workspaces(&app);
+    workspaces.push(workspace.clone());
+    write_registry(&app, &workspaces, &active_workspace_id(&app))?;
+    Ok(workspace)
+}
+
+#[tauri::command]
+pub async fn workspace_rename(
+    app: AppHandle,
+    workspace_id: String,
+    name: String,
+) -> Result<Workspace, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Workspace name is required".to_string());
+    }
+    let _guard = lock_store();
+    let mut workspaces = read_workspaces(&app);
+    let workspace = workspaces
+        .iter_mut()
+        .find(|w| w.id == workspace_id)
+        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
+    workspace.name = name.to_string();
+    let renamed = workspace.clone();
+    write_registry(&app, &workspaces, &active_workspace_id(&app))?;
+    Ok(renamed)
+}
+
+/// Make `workspace_id` the active workspace. The frontend reloads afterwards
+/// so every view reads from the new workspace's store.
+#[tauri::command]
+pub async fn workspace_switch(
+    app: AppHandle,
+    proxy_state: State<'_, ProxyState>,
+    monitor_state: State<'_, MonitorState>,
+    workspace_id: String,
+) -> Result<WorkspaceList, String> {
+    let workspaces = read_workspaces(&app);
+    if !workspaces.iter().any(|w| w.id == workspace_id) {
+        return Err(format!("Workspace not found: {}", workspace_id));
+    }
+    // Stop the old workspace's monitors before the store changes underneath them
+    monitor_state.stop_all().await;
+    write_registry(&app, &workspaces, &workspace_id)?;
+    open_store(&app, &store_file(&app))?;
+
+    proxy_state.reload_from_store(&app);
+    start_saved_monitors(app.clone());
+
+    Ok(WorkspaceList {
+        workspaces,
+        active_workspace_id: workspace_id,
+    })
+}
+
+/// Delete a workspace with its store and collections. The default and the
+/// active workspace cannot be deleted.
+#[tauri::command]
+pub async fn workspace_delete(app: AppHandle, workspace_id: String) -> Result<(), String> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Err("The default workspace cannot be deleted".to_string());
+    }
+    if workspace_id == active_workspace_id(&app) {
+        return Err("Switch to another workspace before deleting this one".to_string());
+    }
+    if !is_valid_workspace_id(&workspace_id) {
+        return Err(format!("Invalid workspace id: {}", workspace_id));
+    }
+
+    let _guard = lock_store();
+    let mut workspaces = read_workspaces(&app);
+    let before = workspaces.len();
+    workspaces.retain(|w| w.id != workspace_id);
+    if workspaces.len() == before {
+        return Err(format!("Workspace not found: {}", workspace_id));
+    }
+
+    let file = store_file_for(&workspace_id);
+    if let Some(store) = app.get_store(&file) {
+        store.close_resource();
+    }
+    let store_path = tauri_plugin_store::resolve_store_path(&app, &file)
+        .map_err(|e| format!("Failed to resolve workspace store: {}", e))?;
+    if store_path.exists() {
+        std::fs::remove_file(&store_path)
+            .map_err(|e| format!("Failed to delete workspace store: {}", e))?;
+    }
+
+    let workspace_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(WORKSPACES_DIR)
+        .join(&workspace_id);
+    if workspace_dir.exists() {
+        std::fs::remove_dir_all(&workspace_dir)
+            .map_err(|e| format!("Failed to delete workspace data: {}", e))?;
+    }
+
+    write_registry(&app, &workspaces, &active_workspace_id(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_workspace_keeps_the_original_store_file() {
+        assert_eq!(store_file_for(DEFAULT_WORKSPACE_ID), "resonance-store.json");
+        assert_eq!(store_file_for("abc-123"), "resonance-store-abc-123.json");
+    }
+
+    #[test]
+    fn workspace_ids_are_safe_file_names() {
+        assert!(is_valid_workspace_id(
+            "0b6c1f2e-6d7a-4f7e-9a57-2b1c0e8e7a10"
+        ));
+        assert!(!is_valid_workspace_id(""));
+        assert!(!is_valid_workspace_id("../escape"));
+        assert!(!is_valid_workspace_id("a/b"));
+    }
+}
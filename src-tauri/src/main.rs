@@ -1,11 +1,19 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
 mod commands;
 
 use commands::{
     api_request::{cancel_api_request, pick_upload_file, send_api_request, RequestState},
     app::app_get_version,
+    bulk_edit::{bulk_edit_parse, bulk_edit_serialize},
     certificates::pick_certificate_file,
+    client_pool::connection_prewarm,
+    codegen::codegen_generate,
+    collection_history::{
+        collection_history_diff, collection_history_list, collection_history_rollback,
+    },
+    collection_watcher::{start_collection_watcher, CollectionWatchState},
     collections::{
         collection_delete, collection_delete_endpoint_data, collection_get,
         collection_get_endpoint_data, collection_get_variables, collection_save,
@@ -13,45 +21,144 @@ use commands::{
         collections_get_path, collections_list, collections_migrate, collections_needs_migration,
         collections_pick_directory,
     },
+    command_auth::{auth_command_clear_cache, auth_command_token, CommandAuthState},
+    command_palette::{command_palette_refresh, command_palette_search, CommandPaletteState},
+    cookie_jar::{cookies_clear_domain, cookies_delete, cookies_list, cookies_set},
+    crash_reports::{
+        crash_report_frontend_error, crash_report_get, crash_reports_clear, crash_reports_list,
+        start_crash_reporter, track_commands,
+    },
+    deep_link::{
+        deep_link_take_pending, launch_file_args, receive_files, start_deep_links, DeepLinkState,
+    },
+    diagnostics::{network_dns_lookup, network_ping, network_traceroute},
+    duplicate::{collection_duplicate, endpoint_duplicate, folder_duplicate},
+    electron_migration::{
+        electron_migration_detect, electron_migration_dismiss, electron_migration_run,
+    },
+    endpoint_drafts::{
+        endpoint_discard_draft, endpoint_drafts_list, endpoint_get, endpoint_save_draft,
+        endpoint_update,
+    },
+    endpoint_examples::{endpoint_example_delete, endpoint_example_save, endpoint_examples_list},
+    environment_vault::{
+        environment_decrypt, environment_encrypt, environment_lock, environment_save_encrypted,
+        environment_unlock, environment_unlocked_variables, EnvironmentVaultState,
+    },
     graphql_subscription::{
         graphql_subscription_close, graphql_subscription_send, GraphqlSubscriptionState,
     },
+    graphql_validation::graphql_validate,
+    grpc_docs::{grpc_docs_capture, grpc_docs_delete, grpc_docs_get},
     grpc_proto::{
-        grpc_list_loaded_protos, grpc_parse_proto_file, grpc_proto_get_input_skeleton,
-        grpc_proto_invoke_unary, grpc_select_proto_file, grpc_unload_proto, ProtoState,
+        grpc_list_loaded_protos, grpc_parse_proto_file, grpc_proto_decode_response,
+        grpc_proto_get_input_skeleton, grpc_proto_invoke_unary, grpc_proto_list_messages,
+        grpc_select_proto_file, grpc_unload_proto, ProtoState,
     },
     grpc_reflection::{
         grpc_get_input_skeleton, grpc_invoke_unary, grpc_reflection_list_methods,
         grpc_reflection_list_services,
     },
-    grpc_streaming::{grpc_stream_cancel, grpc_stream_send, grpc_stream_start, GrpcStreamingState},
+    grpc_streaming::{
+        grpc_invoke_server_stream, grpc_stream_cancel, grpc_stream_send, grpc_stream_start,
+        GrpcStreamingState,
+    },
+    header_metadata::headers_metadata,
+    history_replay::replay_history_entry,
+    hypermedia::hypermedia_links,
     import_export::{
-        collections_pick_import_file, export_openapi, export_postman, import_openapi_file,
-        import_postman_collection, import_postman_environment, save_documentation,
-        save_json_export,
+        collections_pick_import_file, export_http_file, export_markdown, export_openapi,
+        export_postman, export_shell_script, import_curl, import_har_file, import_http_file,
+        import_openapi_file, import_openapi_url, import_postman_collection, import_postman_dump,
+        import_postman_environment, save_documentation, save_json_export,
     },
+    json_view::{json_document_open, json_document_page, JsonDocumentState},
+    jwt::{jwt_decode, jwt_generate},
+    login_flow::login_flow_run,
     mock_server::{
-        mock_server_clear_logs, mock_server_logs, mock_server_reload_settings, mock_server_start,
-        mock_server_status, mock_server_stop,
+        mock_server_clear_logs, mock_server_logs, mock_server_reload_settings,
+        mock_server_set_endpoint_enabled, mock_server_start, mock_server_status, mock_server_stop,
+    },
+    monitors::{
+        monitor_delete, monitor_results, monitor_run_now, monitor_save, monitors_list,
+        start_saved_monitors, MonitorState,
     },
     mqtt::{mqtt_close, mqtt_connect, mqtt_publish, MqttState},
     oauth::{
-        oauth2_build_authorization_url, oauth2_generate_pkce, oauth2_generate_state,
+        oauth2_build_authorization_url, oauth2_device_cancel, oauth2_device_poll,
+        oauth2_device_start, oauth2_discover, oauth2_generate_pkce, oauth2_generate_state,
         oauth2_get_pkce_verifier, oauth2_get_token, oauth2_store_pkce_verifier, OAuth2State,
     },
-    proxy::{proxy_get, proxy_set, proxy_test, ProxyState},
-    scripts::{script_execute_pre_request, script_execute_test, script_get, script_save},
-    secrets::{secret_delete, secret_get, secret_keychain_available, secret_set},
+    oauth_redirect::{oauth2_await_redirect, oauth2_cancel_redirect, OAuthRedirectState},
+    pagination::pagination_follow,
+    proxy::{offline_get, offline_set, proxy_get, proxy_set, proxy_test, ProxyState},
+    remote_sync::{sync_pull, sync_push, sync_settings_get, sync_settings_set},
+    request_bundle::{
+        export_request_bundle, request_bundle_import, request_bundle_link, request_bundle_open,
+    },
+    response_file::save_response_to_file,
+    response_search::search_response_bodies,
+    runner::{
+        health_check, run_cancel, run_collection, runner_export_report, runner_history_delete,
+        runner_history_diff, runner_history_get, runner_history_list, send_folder, RunnerState,
+    },
+    schema_inference::endpoint_infer_response_schema,
+    scripts::{
+        global_scripts_get, global_scripts_set, script_execute_pre_request, script_execute_test,
+        script_get, script_save,
+    },
+    secrets::{secret_delete, secret_get, secret_keychain_available, secret_mask, secret_set},
+    security_headers::audit_security_headers,
+    settings_transfer::{settings_export, settings_import},
+    soap::{soap_build_envelope, soap_unwrap_response},
+    spec_sync::{spec_sync_accept, spec_sync_check, start_spec_sync},
     sse::{sse_close, sse_connect, SseState},
     store::{settings_get, settings_set, store_get, store_set},
+    test_generation::script_generate_tests,
+    tls_probe::network_tls_probe,
+    token_manager::{oauth2_token_get, oauth2_token_revoke, oauth2_token_save, oauth2_tokens_list},
+    trash::{purge_expired, trash_endpoint, trash_list, trash_purge, trash_restore},
+    trusted_hosts::{
+        trusted_hosts_add, trusted_hosts_inspect, trusted_hosts_list, trusted_hosts_remove,
+    },
     updater::{
         updater_check, updater_download_and_install, updater_get_install_info, PendingUpdate,
     },
+    usage_stats::{usage_stats_clear, usage_stats_query},
+    variable_usage::variables_usage_report,
+    webhook_receiver::{
+        webhook_receiver_clear, webhook_receiver_requests, webhook_receiver_start,
+        webhook_receiver_status, webhook_receiver_stop, WebhookReceiverState,
+    },
     websocket::{websocket_close, websocket_send, WebSocketState},
+    workspaces::{
+        open_workspace_stores, workspace_create, workspace_delete, workspace_rename,
+        workspace_switch, workspaces_list,
+    },
 };
+use std::path::Path;
+use tauri::Manager;
 
 fn main() {
+    // `resonance send ...` runs headless and exits without starting the UI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
+        // Must come first so a second launch hands its link or files over before doing anything else
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            receive_files(
+                app,
+                launch_file_args(args.into_iter().skip(1), Path::new(&cwd)),
+            );
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -70,39 +177,135 @@ fn main() {
         .manage(ProtoState::default())
         .manage(GrpcStreamingState::default())
         .manage(WebSocketState::default())
+        .manage(WebhookReceiverState::default())
         .manage(GraphqlSubscriptionState::default())
         .manage(SseState::default())
         .manage(MqttState::default())
         .manage(PendingUpdate::default())
         .manage(OAuth2State::default())
-        .invoke_handler(tauri::generate_handler![
+        .manage(OAuthRedirectState::default())
+        .manage(CommandAuthState::default())
+        .manage(RunnerState::default())
+        .manage(MonitorState::default())
+        .manage(CollectionWatchState::default())
+        .manage(EnvironmentVaultState::default())
+        .manage(JsonDocumentState::default())
+        .manage(DeepLinkState::default())
+        .manage(CommandPaletteState::default())
+        .setup(|app| {
+            start_crash_reporter(app.handle())?;
+            open_workspace_stores(app.handle())?;
+            start_saved_monitors(app.handle().clone());
+            start_collection_watcher(app.handle().clone());
+            start_spec_sync(app.handle().clone());
+            purge_expired(app.handle());
+            start_deep_links(app.handle())?;
+            if let Ok(cwd) = std::env::current_dir() {
+                receive_files(
+                    app.handle(),
+                    launch_file_args(std::env::args().skip(1), &cwd),
+                );
+            }
+            Ok(())
+        })
+        .invoke_handler(track_commands(tauri::generate_handler![
             // App
             app_get_version,
+            // Crash reports
+            crash_report_frontend_error,
+            crash_reports_list,
+            crash_report_get,
+            crash_reports_clear,
+            // Deep links
+            deep_link_take_pending,
+            // Command palette
+            command_palette_refresh,
+            command_palette_search,
             // Store
             store_get,
             store_set,
             settings_get,
             settings_set,
+            // Settings transfer
+            settings_export,
+            settings_import,
             // Secrets (OS keychain)
             secret_set,
             secret_get,
             secret_delete,
             secret_keychain_available,
+            secret_mask,
+            // Encrypted environments
+            environment_encrypt,
+            environment_unlock,
+            environment_lock,
+            environment_unlocked_variables,
+            environment_save_encrypted,
+            environment_decrypt,
+            // Variable usage
+            variables_usage_report,
             // API Requests
             send_api_request,
             cancel_api_request,
             pick_upload_file,
+            save_response_to_file,
+            pagination_follow,
+            replay_history_entry,
+            search_response_bodies,
+            // Cookie jar
+            cookies_list,
+            cookies_set,
+            cookies_delete,
+            cookies_clear_domain,
+            // Security header audit
+            audit_security_headers,
+            // Header autocomplete
+            headers_metadata,
+            // Hypermedia links
+            hypermedia_links,
+            // SOAP
+            soap_build_envelope,
+            soap_unwrap_response,
+            spec_sync_check,
+            spec_sync_accept,
+            // Large JSON responses
+            json_document_open,
+            json_document_page,
             // Proxy
             proxy_get,
             proxy_set,
             proxy_test,
+            offline_get,
+            offline_set,
+            // Trusted hosts
+            trusted_hosts_list,
+            trusted_hosts_inspect,
+            trusted_hosts_add,
+            trusted_hosts_remove,
+            // Network diagnostics
+            network_ping,
+            network_traceroute,
+            network_dns_lookup,
+            connection_prewarm,
+            network_tls_probe,
+            // Usage dashboard
+            usage_stats_query,
+            usage_stats_clear,
             // Import/Export
             import_openapi_file,
+            import_openapi_url,
             import_postman_collection,
             import_postman_environment,
+            import_postman_dump,
+            import_har_file,
+            import_http_file,
+            import_curl,
             collections_pick_import_file,
             export_openapi,
             export_postman,
+            export_http_file,
+            export_shell_script,
+            export_markdown,
             save_json_export,
             save_documentation,
             // gRPC Reflection
@@ -110,6 +313,10 @@ fn main() {
             grpc_reflection_list_methods,
             grpc_invoke_unary,
             grpc_get_input_skeleton,
+            // gRPC API documentation
+            grpc_docs_capture,
+            grpc_docs_get,
+            grpc_docs_delete,
             // gRPC Proto Files
             grpc_select_proto_file,
             grpc_parse_proto_file,
@@ -117,12 +324,17 @@ fn main() {
             grpc_proto_invoke_unary,
             grpc_list_loaded_protos,
             grpc_unload_proto,
+            grpc_proto_list_messages,
+            grpc_proto_decode_response,
             // Client certificates (mTLS)
             pick_certificate_file,
+            // Code snippets
+            codegen_generate,
             // gRPC Streaming
             grpc_stream_start,
             grpc_stream_send,
             grpc_stream_cancel,
+            grpc_invoke_server_stream,
             // Mock Server
             mock_server_start,
             mock_server_stop,
@@ -130,16 +342,53 @@ fn main() {
             mock_server_logs,
             mock_server_clear_logs,
             mock_server_reload_settings,
+            mock_server_set_endpoint_enabled,
+            // Webhook receiver
+            webhook_receiver_start,
+            webhook_receiver_stop,
+            webhook_receiver_status,
+            webhook_receiver_requests,
+            webhook_receiver_clear,
+            // Bulk edit
+            bulk_edit_parse,
+            bulk_edit_serialize,
             // Scripts
             script_get,
             script_save,
             script_execute_pre_request,
             script_execute_test,
+            global_scripts_get,
+            global_scripts_set,
+            script_generate_tests,
+            // Collection Runner
+            run_collection,
+            run_cancel,
+            runner_export_report,
+            runner_history_list,
+            runner_history_get,
+            runner_history_delete,
+            runner_history_diff,
+            send_folder,
+            health_check,
+            // Monitors
+            monitors_list,
+            monitor_save,
+            monitor_delete,
+            monitor_results,
+            monitor_run_now,
+            // Workspaces
+            workspaces_list,
+            workspace_create,
+            workspace_rename,
+            workspace_switch,
+            workspace_delete,
             // WebSocket
             websocket_send,
             websocket_close,
             graphql_subscription_send,
             graphql_subscription_close,
+            // GraphQL validation
+            graphql_validate,
             // SSE
             sse_connect,
             sse_close,
@@ -154,6 +403,24 @@ fn main() {
             oauth2_get_pkce_verifier,
             oauth2_build_authorization_url,
             oauth2_get_token,
+            oauth2_device_start,
+            oauth2_device_poll,
+            oauth2_device_cancel,
+            oauth2_discover,
+            oauth2_await_redirect,
+            oauth2_cancel_redirect,
+            oauth2_token_save,
+            oauth2_token_get,
+            oauth2_tokens_list,
+            oauth2_token_revoke,
+            // Command auth
+            auth_command_token,
+            auth_command_clear_cache,
+            // JWT
+            jwt_decode,
+            jwt_generate,
+            // Login flows
+            login_flow_run,
             // Updater
             updater_check,
             updater_download_and_install,
@@ -173,7 +440,58 @@ fn main() {
             collections_migrate,
             collections_get_path,
             collections_pick_directory,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            // Electron migration
+            electron_migration_detect,
+            electron_migration_run,
+            electron_migration_dismiss,
+            // Request drafts
+            endpoint_get,
+            endpoint_update,
+            endpoint_save_draft,
+            endpoint_discard_draft,
+            endpoint_drafts_list,
+            // Saved examples
+            endpoint_examples_list,
+            endpoint_example_save,
+            endpoint_example_delete,
+            // Response schema inference
+            endpoint_infer_response_schema,
+            // Collection history
+            collection_history_list,
+            collection_history_diff,
+            collection_history_rollback,
+            // Duplicate
+            collection_duplicate,
+            folder_duplicate,
+            endpoint_duplicate,
+            // Request bundles
+            export_request_bundle,
+            request_bundle_link,
+            request_bundle_open,
+            request_bundle_import,
+            // Remote sync
+            sync_settings_get,
+            sync_settings_set,
+            sync_push,
+            sync_pull,
+            // Trash
+            trash_endpoint,
+            trash_list,
+            trash_restore,
+            trash_purge,
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, _event| {
+            // macOS hands files opened from Finder over as an event, not as arguments
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                receive_files(
+                    _app,
+                    urls.iter()
+                        .filter_map(|url| url.to_file_path().ok())
+                        .collect(),
+                );
+            }
+        });
 }
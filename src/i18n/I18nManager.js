@@ -6,6 +6,8 @@ export class I18nManager {
     constructor() {
         this.currentLanguage = 'en';
         this.translations = {};
+        // Strings a locale does not translate yet are shown in English
+        this.fallbackTranslations = {};
         this.fallbackLanguage = 'en';
         this.supportedLanguages = {
             'en': 'English',
@@ -50,11 +52,10 @@ export class I18nManager {
         }
 
         try {
-            const response = await fetch(`src/i18n/locales/${language}.json`);
-            if (!response.ok) {
-                throw new Error(`Failed to load language ${language}`);
-            }
-            this.translations = await response.json();
+            this.translations = await this.fetchLocale(language);
+            this.fallbackTranslations = language === this.fallbackLanguage
+                ? {}
+                : await this.fetchLocale(this.fallbackLanguage).catch(() => ({}));
             this.currentLanguage = language;
         } catch (error) {
             if (language !== this.fallbackLanguage) {
@@ -63,6 +64,14 @@ export class I18nManager {
         }
     }
 
+    async fetchLocale(language) {
+        const response = await fetch(`src/i18n/locales/${language}.json`);
+        if (!response.ok) {
+            throw new Error(`Failed to load language ${language}`);
+        }
+        return response.json();
+    }
+
     async setLanguage(language) {
         if (language === this.currentLanguage) {return;}
         
@@ -76,14 +85,9 @@ export class I18nManager {
     }
 
     t(key, params = {}) {
-        const keys = key.split('.');
-        let value = this.translations;
-        
-        for (const k of keys) {
-            value = value?.[k];
-            if (value === undefined) {break;}
-        }
-        
+        const value = this.lookup(this.translations, key)
+            ?? this.lookup(this.fallbackTranslations, key);
+
         if (value === undefined) {
             return key;
         }
@@ -91,6 +95,15 @@ export class I18nManager {
         return this.interpolate(value, params);
     }
 
+    lookup(translations, key) {
+        let value = translations;
+        for (const k of key.split('.')) {
+            value = value?.[k];
+            if (value === undefined) {break;}
+        }
+        return typeof value === 'string' ? value : undefined;
+    }
+
     interpolate(template, params) {
         return template.replace(/\{\{(\w+)\}\}/g, (match, key) => params[key] !== undefined ? params[key] : match);
     }
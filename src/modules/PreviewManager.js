@@ -22,7 +22,7 @@ export class PreviewManager {
      * @param {HTMLElement} previewBtn - Preview view button
      */
     initializeForTab(tabId, previewContainer, codeContainer, responseEditor, codeBtn, previewBtn) {
-        const renderer = new PreviewRenderer(previewContainer);
+        const renderer = new PreviewRenderer(previewContainer, tabId);
 
         this.containers.set(tabId, {
             previewContainer,
@@ -8,6 +8,9 @@
 import { createLazyEditorProxy } from './editorLoader.js';
 import { templateLoader } from './templateLoader.js';
 import { attachCopyHandler, attachHeadersCopyHandler } from './copyHandler.js';
+import { attachSaveResponseHandler } from './responseSaveHandler.js';
+import { attachSaveExampleHandler } from './responseExampleHandler.js';
+import { attachProtobufDecodeHandler } from './protobufResponse.js';
 import { PreviewManager } from './PreviewManager.js';
 
 export class ResponseContainerManager {
@@ -108,6 +111,8 @@ export class ResponseContainerManager {
         wrapper.querySelector('[data-role="response-cookies"]').id = `response-cookies-${tabId}`;
         wrapper.querySelector('[data-role="response-trailers"]').id = `response-trailers-${tabId}`;
         wrapper.querySelector('[data-role="response-performance"]').id = `response-performance-${tabId}`;
+        wrapper.querySelector('[data-role="response-security"]').id = `response-security-${tabId}`;
+        wrapper.querySelector('[data-role="response-links"]').id = `response-links-${tabId}`;
         wrapper.querySelector('[data-role="response-scripts"]').id = `response-scripts-${tabId}`;
 
         wrapper.querySelector('.language-selector').dataset.tabId = tabId;
@@ -123,6 +128,8 @@ export class ResponseContainerManager {
         wrapper.querySelector('.response-cookies-display').dataset.tabId = tabId;
         wrapper.querySelector('.response-trailers-display').dataset.tabId = tabId;
         wrapper.querySelector('.response-performance-display').dataset.tabId = tabId;
+        wrapper.querySelector('.response-security-display').dataset.tabId = tabId;
+        wrapper.querySelector('.response-links-display').dataset.tabId = tabId;
         wrapper.querySelector('.response-scripts-display').dataset.tabId = tabId;
 
         this.parentContainer.appendChild(wrapper);
@@ -175,6 +182,21 @@ export class ResponseContainerManager {
             attachCopyHandler(copyBtn, tabId);
         }
 
+        const decodeBtn = wrapper.querySelector('.decode-protobuf-btn');
+        if (decodeBtn) {
+            attachProtobufDecodeHandler(decodeBtn, tabId);
+        }
+
+        const saveBtn = wrapper.querySelector('.save-response-btn');
+        if (saveBtn) {
+            attachSaveResponseHandler(saveBtn, tabId);
+        }
+
+        const saveExampleBtn = wrapper.querySelector('.save-example-btn');
+        if (saveExampleBtn) {
+            attachSaveExampleHandler(saveExampleBtn, tabId);
+        }
+
         const copyHeadersBtn = wrapper.querySelector('.copy-headers-btn');
         if (copyHeadersBtn) {
             attachHeadersCopyHandler(copyHeadersBtn, tabId);
@@ -190,6 +212,8 @@ export class ResponseContainerManager {
             cookiesDisplay: wrapper.querySelector('.response-cookies-display'),
             trailersDisplay: wrapper.querySelector('.response-trailers-display'),
             performanceDisplay: wrapper.querySelector('.response-performance-display'),
+            securityDisplay: wrapper.querySelector('.response-security-display'),
+            linksDisplay: wrapper.querySelector('.response-links-display'),
             scriptsDisplay: wrapper.querySelector('.response-scripts-display'),
             languageSelector,
             copyBtn,
@@ -6,6 +6,8 @@
 import { app } from './appContext.js';
 import { extractCookies, renderCookies } from './cookieParser.js';
 import { displayPerformanceMetrics, clearPerformanceMetrics } from './performanceMetrics.js';
+import { clearSecurityAudit } from './securityAudit.js';
+import { clearHypermediaLinks } from './hypermediaLinks.js';
 
 /**
  * Resolves the correct response-container elements for a given tab,
@@ -16,7 +18,7 @@ import { displayPerformanceMetrics, clearPerformanceMetrics } from './performanc
  * @param {HTMLElement}  [globalElements.headersDisplay]
  * @param {HTMLElement}  [globalElements.cookiesDisplay]
  * @param {HTMLElement}  [globalElements.performanceDisplay]
- * @returns {{ headersEditor: Object|null, cookiesDisplay: HTMLElement|null, performanceDisplay: HTMLElement|null, isPerTab: boolean }}
+ * @returns {{ headersEditor: Object|null, cookiesDisplay: HTMLElement|null, performanceDisplay: HTMLElement|null, securityDisplay: HTMLElement|null, linksDisplay: HTMLElement|null, isPerTab: boolean }}
  */
 export function getResponseElements(tabId, globalElements = {}) {
     const containerElements = tabId
@@ -28,6 +30,8 @@ export function getResponseElements(tabId, globalElements = {}) {
             headersEditor: containerElements.headersEditor || null,
             cookiesDisplay: containerElements.cookiesDisplay || null,
             performanceDisplay: containerElements.performanceDisplay || null,
+            securityDisplay: containerElements.securityDisplay || null,
+            linksDisplay: containerElements.linksDisplay || null,
             isPerTab: true
         };
     }
@@ -37,13 +41,15 @@ export function getResponseElements(tabId, globalElements = {}) {
         headersEditor: null,
         cookiesDisplay: globalElements.cookiesDisplay || null,
         performanceDisplay: globalElements.performanceDisplay || null,
+        securityDisplay: null,
+        linksDisplay: null,
         _headersDisplayFallback: globalElements.headersDisplay || null,
         isPerTab: false
     };
 }
 
 /**
- * Clears the headers, cookies, and performance panes for a response container.
+ * Clears the headers, cookies, performance, security and links panes for a response container.
  *
  * @param {string|null} tabId          - Workspace tab ID (null for global)
  * @param {Object}      globalElements - Global DOM fallback references
@@ -55,6 +61,8 @@ export function clearResponsePanes(tabId, globalElements = {}) {
         if (els.headersEditor) { els.headersEditor.setContent('', 'application/json'); }
         if (els.cookiesDisplay) { renderCookies(els.cookiesDisplay, []); }
         if (els.performanceDisplay) { clearPerformanceMetrics(els.performanceDisplay); }
+        clearSecurityAudit(els.securityDisplay);
+        clearHypermediaLinks(els.linksDisplay);
     } else {
         if (els._headersDisplayFallback) { els._headersDisplayFallback.textContent = ''; }
         if (els.cookiesDisplay) { renderCookies(els.cookiesDisplay, []); }
@@ -71,8 +79,10 @@ export function clearResponsePanes(tabId, globalElements = {}) {
  * @param {Object|null} opts.headers   - Response headers object
  * @param {Object|null} opts.timings   - Performance timings
  * @param {number|null} opts.size      - Response size in bytes
+ * @param {Object|null} [opts.remoteAddress] - Peer address of the response
+ * @param {Object|null} [opts.traceIds] - Generated `X-Request-Id` / `traceparent` of the request
  */
-export function displayResponsePanes(tabId, globalElements, { headers, timings, size }) {
+export function displayResponsePanes(tabId, globalElements, { headers, timings, size, remoteAddress = null, traceIds = null }) {
     const els = getResponseElements(tabId, globalElements);
 
     const headersString = headers
@@ -94,7 +104,7 @@ export function displayResponsePanes(tabId, globalElements, { headers, timings,
 
     if (timings) {
         if (els.performanceDisplay) {
-            displayPerformanceMetrics(els.performanceDisplay, timings, size);
+            displayPerformanceMetrics(els.performanceDisplay, timings, size, remoteAddress, traceIds);
         }
     } else if (els.performanceDisplay) {
         clearPerformanceMetrics(els.performanceDisplay);
@@ -14,7 +14,11 @@ import logger from './logger.js';
 
 const _log = logger.scope('WorkspaceTabStateManager');
 import { displayPerformanceMetrics, clearPerformanceMetrics } from './performanceMetrics.js';
+import { displaySecurityAudit, clearSecurityAudit } from './securityAudit.js';
+import { displayHypermediaLinks, clearHypermediaLinks } from './hypermediaLinks.js';
 import { formatCookiesAsHtml } from './cookieParser.js';
+import { formatResponseBody } from './largeJson.js';
+import { handleProtobufResponse } from './protobufResponse.js';
 import { activateTab } from './tabManager.js';
 import { setRequestBodyContent, getRequestBodyContent } from './requestBodyHelper.js';
 import { setRequestMode, RequestMode } from './requestModeManager.js';
@@ -213,6 +217,11 @@ export class WorkspaceTabStateManager {
                 mode: 'binary',
                 ...app.formBodyManager.getBinaryBody()
             };
+        } else if (currentBodyMode === 'soap' && app.formBodyManager) {
+            bodyData = {
+                mode: 'soap',
+                ...app.formBodyManager.getSoapBody()
+            };
         } else if (currentBodyMode === 'text') {
             bodyData = {
                 mode: 'text',
@@ -560,6 +569,9 @@ export class WorkspaceTabStateManager {
             } else if (mode === 'binary' && app.formBodyManager) {
                 this.graphqlBodyManager?.switchMode('binary');
                 app.formBodyManager.setBinaryBody(request.body);
+            } else if (mode === 'soap' && app.formBodyManager) {
+                this.graphqlBodyManager?.switchMode('soap');
+                app.formBodyManager.setSoapBody(request.body);
             } else if (mode === 'text') {
                 this.graphqlBodyManager?.switchMode('text');
                 if (app.requestBodyTextEditor) {
@@ -696,13 +708,10 @@ export class WorkspaceTabStateManager {
         const containerElements = app.responseContainerManager?.getOrCreateContainer(tabId);
 
         if (response.data) {
-            const isStructured = typeof response.data !== 'string';
-            const formattedResponse = isStructured
-                ? JSON.stringify(response.data, null, 2)
-                : response.data;
+            const { text, languageHint } = await formatResponseBody(response.data, response.size, tabId);
             const contentType = response.headers?.['content-type'] || null;
-            const languageHint = isStructured ? 'json' : undefined;
-            displayResponseWithLineNumbersForTab(formattedResponse, contentType, tabId, languageHint);
+            displayResponseWithLineNumbersForTab(text, contentType, tabId, languageHint);
+            await handleProtobufResponse(tabId, contentType);
         } else {
             clearResponseDisplayForTab(tabId);
         }
@@ -727,12 +736,15 @@ export class WorkspaceTabStateManager {
             if (response.performanceHTML) {
                 containerElements.performanceDisplay.innerHTML = response.performanceHTML;
             } else if (response.timings) {
-                displayPerformanceMetrics(containerElements.performanceDisplay, response.timings, response.size);
+                displayPerformanceMetrics(containerElements.performanceDisplay, response.timings, response.size, response.remoteAddress, response.traceIds);
             } else {
                 clearPerformanceMetrics(containerElements.performanceDisplay);
             }
         }
 
+        displaySecurityAudit(containerElements?.securityDisplay, response.securityAudit);
+        displayHypermediaLinks(containerElements?.linksDisplay, response.hypermediaLinks);
+
         if (response.status) {
             updateStatusDisplay(`Status: ${response.status} ${response.statusText || ''}`, response.status);
         } else if (response.websocket?.state === 'open') {
@@ -773,6 +785,9 @@ export class WorkspaceTabStateManager {
         if (containerElements?.performanceDisplay) {
             clearPerformanceMetrics(containerElements.performanceDisplay);
         }
+
+        clearSecurityAudit(containerElements?.securityDisplay);
+        clearHypermediaLinks(containerElements?.linksDisplay);
     }
 
     /**
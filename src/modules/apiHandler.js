@@ -30,9 +30,14 @@ import { VariableService } from './services/VariableService.js';
 import { StatusDisplayAdapter } from './interfaces/IStatusDisplay.js';
 import { authManager } from './authManager.js';
 import { resolveEffectiveAuthConfig } from './auth/authInheritance.js';
+import { applyCommandToken, applyManagedToken } from './auth/managedTokens.js';
 import { CodeSnippetDialog } from './ui/CodeSnippetDialog.js';
+import { ConfirmDialog } from './ui/ConfirmDialog.js';
+import { TrustCertificateDialog } from './ui/TrustCertificateDialog.js';
 import { createLazyEditorProxy } from './editorLoader.js';
 import { extractCookies } from './cookieParser.js';
+import { formatResponseBody } from './largeJson.js';
+import { handleProtobufResponse } from './protobufResponse.js';
 import { getRequestBodyContent } from './requestBodyHelper.js';
 import { MockServerRepository } from './storage/MockServerRepository.js';
 import { MockServerService } from './services/MockServerService.js';
@@ -49,7 +54,11 @@ import {
 import { selectActiveOperationType } from './graphqlTransportWs.js';
 import { cancelStream as cancelGrpcStream, hasActiveStream as hasActiveGrpcStream } from './grpcStreamHandler.js';
 import { RequestBuilderService } from './services/RequestBuilderService.js';
-import { clearResponsePanes, displayResponsePanes, displayErrorResponsePanes } from './ResponseDisplayHelper.js';
+import { clearResponsePanes, displayResponsePanes, displayErrorResponsePanes, getResponseElements } from './ResponseDisplayHelper.js';
+import { auditResponseSecurity, displaySecurityAudit } from './securityAudit.js';
+import { fetchHypermediaLinks, displayHypermediaLinks } from './hypermediaLinks.js';
+import { validateGraphQLDocument, formatGraphQLValidationErrors } from './graphqlValidation.js';
+import { buildSoapEnvelope, mergeSoapHeaders, unwrapSoapResponse } from './soapBody.js';
 import { getIntrospectionQuery, buildClientSchema } from 'graphql';
 
 let responseEditor = null;
@@ -135,7 +144,21 @@ async function generateEffectiveAuthData() {
         endpointId: current?.endpointId,
         repository: getCollectionRepository()
     });
-    return authManager.generateAuthData(resolved);
+    const withToken = await applyManagedToken(resolved, window.backendAPI?.oauth2);
+    return authManager.generateAuthData(
+        await applyCommandToken(withToken, window.backendAPI?.commandAuth, current?.collectionId)
+    );
+}
+
+/**
+ * Whether the backend resolves the auth of the current request when sending
+ * it: a collection request whose auth type is 'inherit' gets its folder's or
+ * collection's auth applied there, with secrets and managed tokens.
+ *
+ * @returns {boolean}
+ */
+function inheritsAuthOnSend() {
+    return Boolean(getCurrentEndpoint()?.collectionId) && authManager.getAuthConfig()?.type === 'inherit';
 }
 
 function warnUnresolvedVariables(processor, requestConfig) {
@@ -160,6 +183,41 @@ function warnUnresolvedVariables(processor, requestConfig) {
     }
 }
 
+/**
+ * Warns when the response took longer than the endpoint's time budget
+ *
+ * @param {Object} [timeBudget] - Budget check from the backend ({budgetMs, elapsedMs, exceeded})
+ * @returns {void}
+ */
+function warnTimeBudget(timeBudget) {
+    if (!timeBudget?.exceeded) {
+        return;
+    }
+    toast.warning(app.i18n?.t('time_budget.exceeded', { elapsed: timeBudget.elapsedMs, budget: timeBudget.budgetMs })
+        || `Response took ${timeBudget.elapsedMs} ms, over the ${timeBudget.budgetMs} ms budget`);
+}
+
+/**
+ * Content type to render a response as. The backend sniffs the body, so a
+ * missing or wrong Content-Type header doesn't pick the wrong view.
+ *
+ * @param {Object} response - Backend response ({headers, contentSniff})
+ * @param {boolean} [warn=false] - Warn when the declared header contradicts the body
+ * @returns {string|null} Content type, or null when nothing is known
+ */
+function responseContentType(response, warn = false) {
+    const declared = response.headers?.['content-type'] || null;
+    const sniff = response.contentSniff;
+    if (!sniff?.mismatch) {
+        return declared;
+    }
+    if (warn && declared) {
+        toast.warning(app.i18n?.t('content_sniff.mismatch', { declared, detected: sniff.mimeType })
+            || `Response is declared as ${declared} but looks like ${sniff.mimeType}`);
+    }
+    return sniff.renderContentType;
+}
+
 /**
  * Fetches the GraphQL schema for the current endpoint by POSTing the standard
  * introspection query. Reuses the same URL/header/auth/variable resolution as the
@@ -196,6 +254,8 @@ export async function fetchGraphQLIntrospection() {
     let timeout = 30000;
     let verifySsl = true;
     let followRedirects = true;
+    let ipFamily = null;
+    let traceHeaders = false;
     try {
         if (!_settingsCache) {
             _settingsCache = await window.backendAPI.settings.get();
@@ -205,6 +265,8 @@ export async function fetchGraphQLIntrospection() {
         timeout = savedTimeout === 0 ? null : (savedTimeout ?? 30000);
         verifySsl = settings.verifySsl !== false;
         followRedirects = settings.followRedirects !== false;
+        ipFamily = settings.ipFamily || null;
+        traceHeaders = settings.traceHeaders === true;
     } catch (e) {
         void e;
     }
@@ -217,7 +279,10 @@ export async function fetchGraphQLIntrospection() {
         body: { query: getIntrospectionQuery(), variables: {} },
         timeout,
         verifySsl,
-        followRedirects
+        followRedirects,
+        ipFamily,
+        traceHeaders,
+        collectionId: getCurrentEndpoint()?.collectionId ?? null
     };
 
     if (authData.authConfig) {
@@ -614,7 +679,54 @@ async function handleGraphQLSubscriptionRequest() {
     }
 }
 
-export async function handleSendRequest() {
+/**
+ * Asks whether to send a request the collection's safety policy held back
+ *
+ * @param {Object} requestConfig - The request that was not sent
+ * @param {string} reason - Why the policy wants confirmation
+ * @returns {Promise<boolean>} Whether to send it anyway
+ */
+function confirmGuardedRequest(requestConfig, reason) {
+    return new ConfirmDialog().show(`${reason} Send ${requestConfig.url} anyway?`, {
+        title: 'Send this request?',
+        confirmText: `Send ${requestConfig.method}`,
+        dangerous: true
+    });
+}
+
+/**
+ * Offers to trust the certificate a request's host presented after it failed
+ * verification
+ *
+ * @async
+ * @param {string} url - The request URL
+ * @returns {Promise<boolean>} Whether the certificate is now trusted
+ */
+async function offerCertificateTrust(url) {
+    const trustedHosts = window.backendAPI.trustedHosts;
+    try {
+        const certificate = await trustedHosts.inspect(url);
+        const choice = await new TrustCertificateDialog().show(certificate);
+        if (!choice) {
+            return false;
+        }
+        await trustedHosts.add(certificate.host, certificate.fingerprint, choice === 'always');
+        return true;
+    } catch (error) {
+        toast.error(typeof error === 'string' ? error : (error?.message || 'Could not inspect the certificate'));
+        return false;
+    }
+}
+
+/**
+ * Sends the request of the active tab
+ *
+ * @param {Object} [options]
+ * @param {boolean} [options.reuseIdempotencyKey] - Send the endpoint's last
+ * idempotency key again instead of a new one
+ * @returns {Promise<void>}
+ */
+export async function handleSendRequest(options = {}) {
     if (isGrpcMode()) {
         return handleGrpcSend();
     }
@@ -780,17 +892,21 @@ export async function handleSendRequest() {
     const headers = parseKeyValuePairs(document.getElementById('headers-list'));
     const queryParams = parseKeyValuePairs(document.getElementById('query-params-list'));
 
-    const authData = await generateEffectiveAuthData();
+    const inheritAuth = inheritsAuthOnSend();
+    const authData = inheritAuth
+        ? authManager.generateAuthData({ type: 'none', config: {} })
+        : await generateEffectiveAuthData();
 
     const builder = getRequestBuilderService();
     builder.mergeAuthData(headers, queryParams, authData);
 
     let processor;
     let _resolvedVariables = null;
+    let variableOverrides = {};
     let queryString = '';
     let processedPathParams = {};
     try {
-        ({ variables: _resolvedVariables, processor } = await builder.resolveVariables(
+        ({ variables: _resolvedVariables, processor, variableOverrides } = await builder.resolveVariables(
             getCurrentEndpoint(), headers
         ));
 
@@ -840,7 +956,7 @@ export async function handleSendRequest() {
     }
 
     const bodyMode = document.getElementById('body-mode-select')?.value || 'json';
-    if (['POST', 'PUT', 'PATCH'].includes(method) || bodyMode === 'formdata' || bodyMode === 'urlencoded' || bodyMode === 'binary') {
+    if (['POST', 'PUT', 'PATCH'].includes(method) || bodyMode === 'formdata' || bodyMode === 'urlencoded' || bodyMode === 'binary' || bodyMode === 'soap') {
         try {
             let variables = _resolvedVariables;
             if (variables === null) {
@@ -880,6 +996,15 @@ export async function handleSendRequest() {
                 if (operationName) {
                     body.operationName = operationName;
                 }
+
+                const validationErrors = await validateGraphQLDocument(rawUrl, queryText, parsedVariables, operationName);
+                if (validationErrors.length > 0) {
+                    toast.error(app.i18n?.t('graphql_validation.failed', { count: validationErrors.length })
+                        || `GraphQL validation failed with ${validationErrors.length} error(s)`);
+                    displayResponseWithLineNumbersForTab(formatGraphQLValidationErrors(validationErrors), 'text/plain');
+                    setRequestInProgress(false);
+                    return;
+                }
             } else if ((bodyMode === 'formdata' || bodyMode === 'urlencoded') && app.formBodyManager) {
                 const rows = bodyMode === 'formdata'
                     ? app.formBodyManager.getFormDataRows()
@@ -912,6 +1037,17 @@ export async function handleSendRequest() {
                     filePath: processor.processTemplate(binary.filePath, variables),
                     contentType: binary.contentType || undefined
                 };
+            } else if (bodyMode === 'soap' && app.formBodyManager) {
+                try {
+                    const envelope = await buildSoapEnvelope(app.formBodyManager.getSoapBody(), processor, variables);
+                    body = envelope.body;
+                    mergeSoapHeaders(headers, envelope.headers);
+                } catch (e) {
+                    toast.error(e.message);
+                    clearResponseDisplay();
+                    setRequestInProgress(false);
+                    return;
+                }
             } else if (bodyMode === 'text') {
                 const rawText = app.requestBodyTextEditor
                     ? app.requestBodyTextEditor.getContent()
@@ -946,6 +1082,8 @@ export async function handleSendRequest() {
     let timeout = 30000;
     let verifySsl = true;
     let followRedirects = true;
+    let ipFamily = null;
+    let traceHeaders = false;
     try {
         if (!_settingsCache) {
             _settingsCache = await window.backendAPI.settings.get();
@@ -956,6 +1094,8 @@ export async function handleSendRequest() {
         timeout = savedTimeout === 0 ? null : (savedTimeout ?? 30000);
         verifySsl = settings.verifySsl !== false;
         followRedirects = settings.followRedirects !== false;
+        ipFamily = settings.ipFamily || null;
+        traceHeaders = settings.traceHeaders === true;
     } catch (e) {
         void e;
     }
@@ -968,11 +1108,15 @@ export async function handleSendRequest() {
         queryParams,
         pathParams: processedPathParams,
         body,
-        bodyType: (bodyMode === 'formdata' || bodyMode === 'urlencoded' || bodyMode === 'text' || bodyMode === 'binary') ? bodyMode : undefined,
+        bodyType: bodyMode === 'soap'
+            ? 'text'
+            : (bodyMode === 'formdata' || bodyMode === 'urlencoded' || bodyMode === 'text' || bodyMode === 'binary') ? bodyMode : undefined,
         httpVersion,
         timeout,
         verifySsl,
-        followRedirects
+        followRedirects,
+        ipFamily,
+        traceHeaders
     };
 
     const requestTabId = app.workspaceTabController
@@ -1030,37 +1174,40 @@ export async function handleSendRequest() {
             }
         }
 
-        if (app.cookieController) {
-            const cookieHeader = await app.cookieController.getCookieHeader(requestConfig.url);
-            if (cookieHeader) {
-                requestConfig.headers = requestConfig.headers || {};
-                if (!requestConfig.headers['Cookie'] && !requestConfig.headers['cookie']) {
-                    requestConfig.headers['Cookie'] = cookieHeader;
-                }
-            }
-        }
-
         warnUnresolvedVariables(processor, requestConfig);
 
-        const result = await window.backendAPI.sendApiRequest(requestConfig);
+        requestConfig.collectionId = getCurrentEndpoint()?.collectionId ?? null;
+        requestConfig.endpointId = getCurrentEndpoint()?.endpointId ?? null;
+        requestConfig.inheritAuth = inheritAuth;
+        requestConfig.variableOverrides = variableOverrides;
+        requestConfig.reuseIdempotencyKey = options.reuseIdempotencyKey === true;
+        requestConfig.responseKey = requestTabId;
+
+        let result = await window.backendAPI.sendApiRequest(requestConfig);
+        if (result.confirmationRequired && await confirmGuardedRequest(requestConfig, result.message)) {
+            requestConfig.confirmed = true;
+            result = await window.backendAPI.sendApiRequest(requestConfig);
+        }
+        if (result.untrustedCertificate && await offerCertificateTrust(requestConfig.url)) {
+            result = await window.backendAPI.sendApiRequest(requestConfig);
+        }
+        warnTimeBudget(result.timeBudget);
+        if (result.idempotencyKey?.reused) {
+            toast.info(app.i18n?.t('idempotency_key.resent', { key: result.idempotencyKey.key })
+                || `Resent with idempotency key ${result.idempotencyKey.key}`);
+        }
 
         if (result.success) {
-            let contentType = null;
-            if (result.headers && result.headers['content-type']) {
-                contentType = result.headers['content-type'];
-            }
-
-            let formattedResponse;
-            let languageHint;
-            if (typeof result.data === 'string') {
-                formattedResponse = result.data;
-            } else {
-                formattedResponse = JSON.stringify(result.data, null, 2);
-                languageHint = 'json';
+            const contentType = responseContentType(result, true);
+
+            const soapView = bodyMode === 'soap' ? await unwrapSoapResponse(result.data) : null;
+            const { text, languageHint } = soapView
+                || await formatResponseBody(result.data, result.size, requestTabId);
+            displayResponseWithLineNumbersForTab(text, contentType, requestTabId, languageHint);
+            if (requestTabId) {
+                await handleProtobufResponse(requestTabId, contentType);
             }
 
-            displayResponseWithLineNumbersForTab(formattedResponse, contentType, requestTabId, languageHint);
-
             if (app.schemaController) {
                 app.schemaController.setLastResponseBody(result.data);
                 if (isGraphQLMode()) {
@@ -1076,14 +1223,21 @@ export async function handleSendRequest() {
             displayResponsePanes(requestTabId, globalResponseElements(), {
                 headers: result.headers,
                 timings: result.timings,
-                size: result.size
+                size: result.size,
+                remoteAddress: result.remoteAddress,
+                traceIds: result.traceIds
             });
 
-            if (app.cookieController && result.setCookies && result.setCookies.length > 0) {
-                app.cookieController.handleCookiesFromResponse(result.setCookies, requestConfig.url);
-            }
+            const securityAudit = await auditResponseSecurity(requestConfig.url, result);
+            displaySecurityAudit(getResponseElements(requestTabId).securityDisplay, securityAudit);
 
-            updateStatusDisplay(`Status: ${result.status} ${result.statusText}`, result.status);
+            const hypermediaLinks = await fetchHypermediaLinks(requestConfig.url, result);
+            displayHypermediaLinks(getResponseElements(requestTabId).linksDisplay, hypermediaLinks);
+
+            const transformed = result.responseTransform
+                ? ` ${app.i18n?.t('response_transform.applied') || '(transformed)'}`
+                : '';
+            updateStatusDisplay(`Status: ${result.status} ${result.statusText}${transformed}`, result.status);
             updateResponseTime(result.ttfb);
             updateResponseSize(result.size);
             setRequestInProgress(false);
@@ -1098,7 +1252,11 @@ export async function handleSendRequest() {
                         ttfb: result.ttfb,
                         size: result.size,
                         timings: result.timings,
-                        cookies: extractCookies(result.headers)
+                        remoteAddress: result.remoteAddress,
+                        traceIds: result.traceIds,
+                        cookies: extractCookies(result.headers),
+                        securityAudit,
+                        hypermediaLinks
                     },
                     isModified: false
                 }).catch(() => { });
@@ -1123,13 +1281,17 @@ export async function handleSendRequest() {
                 } catch (error) {
                 }
             }
-        } else if (result.cancelled) {
+        } else if (result.cancelled || result.confirmationRequired) {
             if (await isTabCurrentlyActive(requestTabId)) {
-                updateStatusDisplay('Request cancelled', null);
+                updateStatusDisplay(result.cancelled ? 'Request cancelled' : 'Request not sent', null);
                 updateResponseTime(null);
                 updateResponseSize(null);
             }
-            displayResponseWithLineNumbersForTab('Request was cancelled', null, requestTabId);
+            displayResponseWithLineNumbersForTab(
+                result.cancelled ? 'Request was cancelled' : result.message,
+                null,
+                requestTabId
+            );
             clearResponsePanes(requestTabId, globalResponseElements());
             clearGraphQLErrorsBadge(requestTabId);
             setRequestInProgress(false);
@@ -1158,10 +1320,7 @@ export async function handleSendRequest() {
             errorContent = `Error: ${errorMessage}`;
         }
 
-        let contentType = null;
-        if (error.headers && error.headers['content-type']) {
-            contentType = error.headers['content-type'];
-        }
+        const contentType = responseContentType(error);
 
         displayResponseWithLineNumbersForTab(errorContent, contentType, requestTabId);
 
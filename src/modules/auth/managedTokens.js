@@ -0,0 +1,86 @@
+/**
+ * @fileoverview Bridges OAuth 2.0 auth configs and the backend token manager,
+ * which keeps one token per environment and auth profile and refreshes it
+ * when it expires, and command auth configs and the backend's cache of
+ * tokens printed by their commands.
+ * @module auth/managedTokens
+ */
+
+/**
+ * Maps an OAuth 2.0 auth config to the backend's token request shape.
+ *
+ * @param {Object} config - The `config` of an `oauth2` auth config
+ * @returns {Object} OAuth2Config for the token manager commands
+ */
+export function toTokenConfig(config) {
+    return {
+        grantType: config.grantType || 'client_credentials',
+        tokenUrl: config.tokenUrl || '',
+        clientId: config.clientId || '',
+        clientSecret: config.clientSecret || null,
+        scope: config.scope || null,
+        audience: config.audience || null,
+        username: config.username || null,
+        password: config.password || null,
+        clientAuthMethod: config.clientAuthMethod || 'body'
+    };
+}
+
+/**
+ * Returns the auth config with the managed token in place of the stored one,
+ * refreshing it first when it expired. Other auth types, manual tokens and
+ * profiles without a recorded token pass through unchanged, as does the
+ * config when the refresh fails (the request then fails visibly with 401).
+ *
+ * @async
+ * @param {Object} authConfig - Effective `{ type, config }`
+ * @param {Object} oauth2Api - `backendAPI.oauth2`
+ * @returns {Promise<Object>} The auth config to send with
+ */
+export async function applyManagedToken(authConfig, oauth2Api) {
+    const config = authConfig?.config;
+    if (authConfig?.type !== 'oauth2' || !config?.tokenUrl || config.grantType === 'manual' || !oauth2Api?.tokenGet) {
+        return authConfig;
+    }
+    try {
+        const managed = await oauth2Api.tokenGet(toTokenConfig(config));
+        if (!managed) {
+            return authConfig;
+        }
+        return { ...authConfig, config: { ...config, token: managed.accessToken } };
+    } catch (error) {
+        void error;
+        return authConfig;
+    }
+}
+
+/**
+ * Returns a command auth config with the token its command prints, which the
+ * backend caches for `cacheSeconds`. Other auth types pass through unchanged,
+ * as does the config when the command fails (the request is then sent
+ * without auth and fails visibly).
+ *
+ * @async
+ * @param {Object} authConfig - Effective `{ type, config }`
+ * @param {Object} commandAuthApi - `backendAPI.commandAuth`
+ * @param {string} [collectionId] - Collection whose variables the command may use
+ * @returns {Promise<Object>} The auth config to send with
+ */
+export async function applyCommandToken(authConfig, commandAuthApi, collectionId) {
+    const config = authConfig?.config;
+    if (authConfig?.type !== 'command' || !config?.command || !commandAuthApi?.token) {
+        return authConfig;
+    }
+    try {
+        const token = await commandAuthApi.token({
+            command: config.command,
+            timeoutMs: config.timeoutMs || null,
+            cacheSeconds: config.cacheSeconds ?? null,
+            tokenField: config.tokenField || null
+        }, collectionId);
+        return { ...authConfig, config: { ...config, token } };
+    } catch (error) {
+        void error;
+        return authConfig;
+    }
+}
@@ -13,6 +13,10 @@
  */
 import { templateLoader } from './templateLoader.js';
 import { api } from './ipcBridge.js';
+import { toTokenConfig } from './auth/managedTokens.js';
+
+/** Redirect URIs the backend can listen on to catch the authorization code. */
+const LOOPBACK_REDIRECT = /^http:\/\/(localhost|127\.0\.0\.1|\[::1\])(:\d+)?(\/|$)/i;
 
 export class AuthManager {
     /**
@@ -112,7 +116,7 @@ export class AuthManager {
     /**
      * Renders authentication fields based on selected type
      *
-     * @param {string} authType - The authentication type ('none', 'bearer', 'basic', 'api-key', 'oauth2', 'digest')
+     * @param {string} authType - The authentication type ('none', 'bearer', 'basic', 'api-key', 'oauth2', 'digest', 'negotiate')
      * @returns {void}
      */
     renderAuthFields(authType) {
@@ -148,10 +152,18 @@ export class AuthManager {
                 this.renderDigestAuthFields();
                 break;
 
+            case 'negotiate':
+                this.renderNegotiateFields();
+                break;
+
             case 'aws-v4':
                 this.renderAwsV4Fields();
                 break;
 
+            case 'command':
+                this.renderCommandFields();
+                break;
+
             default:
                 break;
         }
@@ -226,7 +238,9 @@ export class AuthManager {
             'api-key': 'API Key',
             oauth2: 'OAuth 2.0',
             digest: 'Digest Auth',
-            'aws-v4': 'AWS Signature'
+            negotiate: 'Negotiate (Kerberos)',
+            'aws-v4': 'AWS Signature',
+            command: 'External Command'
         };
         return labels[type] || type;
     }
@@ -341,6 +355,7 @@ export class AuthManager {
         const grantTypeSelect = this._el('oauth2-grant-type');
         const tokenUrlInput = this._el('oauth2-token-url');
         const authUrlInput = this._el('oauth2-auth-url');
+        const deviceUrlInput = this._el('oauth2-device-url');
         const clientIdInput = this._el('oauth2-client-id');
         const clientSecretInput = this._el('oauth2-client-secret');
         const usernameInput = this._el('oauth2-username');
@@ -356,10 +371,12 @@ export class AuthManager {
         const prefixInput = this._el('oauth2-header-prefix');
 
         const authUrlGroup = this._el('oauth2-auth-url-group');
+        const deviceUrlGroup = this._el('oauth2-device-url-group');
         const usernamePasswordPairGroup = this._el('oauth2-username-password-pair');
         const redirectUriGroup = this._el('oauth2-redirect-uri-group');
         const pkceGroup = this._el('oauth2-pkce-group');
         const tokenUrlGroup = this._el('oauth2-token-url-group');
+        const issuerGroup = this._el('oauth2-issuer-group');
         const credentialsPairGroup = this._el('oauth2-credentials-pair');
         const scopeGroup = this._el('oauth2-scope-group');
         const audienceGroup = this._el('oauth2-audience-group');
@@ -369,7 +386,7 @@ export class AuthManager {
         const errorMessage = this._el('oauth2-error-message');
 
         const updateGrantTypeUI = (grantType) => {
-            [authUrlGroup, usernamePasswordPairGroup, redirectUriGroup, pkceGroup].forEach(g => {
+            [authUrlGroup, deviceUrlGroup, usernamePasswordPairGroup, redirectUriGroup, pkceGroup].forEach(g => {
                 if (g) {g.classList.add('u-hidden');}
             });
 
@@ -379,8 +396,10 @@ export class AuthManager {
                 if (pkceGroup) {pkceGroup.classList.remove('u-hidden');}
             } else if (grantType === 'password') {
                 if (usernamePasswordPairGroup) {usernamePasswordPairGroup.classList.remove('u-hidden');}
+            } else if (grantType === 'device_code') {
+                if (deviceUrlGroup) {deviceUrlGroup.classList.remove('u-hidden');}
             } else if (grantType === 'manual') {
-                [tokenUrlGroup, credentialsPairGroup, scopeGroup,
+                [issuerGroup, tokenUrlGroup, credentialsPairGroup, scopeGroup,
                  audienceGroup, clientAuthGroup, getTokenGroup].forEach(g => {
                     if (g) {g.classList.add('u-hidden');}
                 });
@@ -388,7 +407,7 @@ export class AuthManager {
                 return;
             }
 
-            [tokenUrlGroup, credentialsPairGroup, scopeGroup,
+            [issuerGroup, tokenUrlGroup, credentialsPairGroup, scopeGroup,
              audienceGroup, clientAuthGroup, getTokenGroup].forEach(g => {
                 if (g) {g.classList.remove('u-hidden');}
             });
@@ -405,6 +424,21 @@ export class AuthManager {
             });
         }
 
+        const issuerInput = this._el('oauth2-issuer');
+        if (issuerInput) {
+            issuerInput.value = this.currentAuthConfig.config.issuer || '';
+            issuerInput.addEventListener('input', (e) => {
+                this.currentAuthConfig.config.issuer = e.target.value;
+            });
+        }
+
+        const discoverBtn = this._el('oauth2-discover-btn');
+        if (discoverBtn) {
+            discoverBtn.addEventListener('click', async () => {
+                await this._handleDiscover(errorGroup, errorMessage);
+            });
+        }
+
         if (tokenUrlInput) {
             tokenUrlInput.value = this.currentAuthConfig.config.tokenUrl || '';
             tokenUrlInput.addEventListener('input', (e) => {
@@ -419,6 +453,13 @@ export class AuthManager {
             });
         }
 
+        if (deviceUrlInput) {
+            deviceUrlInput.value = this.currentAuthConfig.config.deviceAuthorizationUrl || '';
+            deviceUrlInput.addEventListener('input', (e) => {
+                this.currentAuthConfig.config.deviceAuthorizationUrl = e.target.value;
+            });
+        }
+
         if (clientIdInput) {
             clientIdInput.value = this.currentAuthConfig.config.clientId || '';
             clientIdInput.addEventListener('input', (e) => {
@@ -532,6 +573,8 @@ export class AuthManager {
         try {
             if (grantType === 'authorization_code') {
                 await this._handleAuthorizationCodeFlow();
+            } else if (grantType === 'device_code') {
+                await this._handleDeviceCodeFlow(errorGroup, errorMessage);
             } else {
                 const tokenConfig = {
                     grantType: grantType,
@@ -560,7 +603,63 @@ export class AuthManager {
     }
 
     /**
-     * Handles the Authorization Code flow
+     * Fetches the issuer's OpenID Connect discovery document and fills in the
+     * authorization, token and device endpoints. The scope is only filled in
+     * when empty, with the standard scopes the provider supports; the full
+     * list is shown as a hint.
+     *
+     * @private
+     * @async
+     * @param {HTMLElement} errorGroup - Error display group element
+     * @param {HTMLElement} errorMessage - Error message element
+     * @returns {Promise<void>}
+     */
+    async _handleDiscover(errorGroup, errorMessage) {
+        const {config} = this.currentAuthConfig;
+        if (errorGroup) {errorGroup.classList.add('u-hidden');}
+        if (!config.issuer || !config.issuer.trim()) {
+            this._showError(errorGroup, errorMessage, 'Enter the issuer URL first');
+            return;
+        }
+
+        try {
+            const discovered = await api.oauth2.discover(config.issuer.trim());
+            if (this.currentAuthConfig.config !== config) {
+                return;
+            }
+            const fill = (inputId, key, value) => {
+                if (!value) {
+                    return;
+                }
+                config[key] = value;
+                const input = this._el(inputId);
+                if (input) {input.value = value;}
+            };
+            fill('oauth2-token-url', 'tokenUrl', discovered.tokenEndpoint);
+            fill('oauth2-auth-url', 'authorizationUrl', discovered.authorizationEndpoint);
+            fill('oauth2-device-url', 'deviceAuthorizationUrl', discovered.deviceAuthorizationEndpoint);
+
+            const scopes = discovered.scopesSupported || [];
+            if (!config.scope) {
+                const standard = ['openid', 'profile', 'email'].filter(scope => scopes.includes(scope));
+                fill('oauth2-scope', 'scope', standard.join(' '));
+            }
+
+            const hint = this._el('oauth2-issuer-hint');
+            if (hint) {
+                hint.textContent = scopes.length > 0
+                    ? `Discovered ${discovered.issuer}. Supported scopes: ${scopes.join(' ')}`
+                    : `Discovered ${discovered.issuer}`;
+            }
+        } catch (error) {
+            this._showError(errorGroup, errorMessage, error.message || String(error));
+        }
+    }
+
+    /**
+     * Handles the Authorization Code flow. When the redirect URI is on
+     * localhost, the backend listens on it and exchanges the code as soon as
+     * the browser comes back; pasting the code stays possible meanwhile.
      *
      * @private
      * @async
@@ -596,9 +695,97 @@ export class AuthManager {
         this.currentAuthConfig.config._pendingState = state;
         this.currentAuthConfig.config._pendingPkce = pkceParams;
 
+        const redirect = LOOPBACK_REDIRECT.test(authUrlParams.redirectUri)
+            ? api.oauth2.awaitRedirect(authUrlParams.redirectUri, state)
+            : null;
+
         window.open(authUrl, '_blank', 'width=600,height=700');
 
         this._showAuthCodeInstructions();
+
+        if (!redirect) {
+            return;
+        }
+        try {
+            const { code } = await redirect;
+            if (this.currentAuthConfig.config === config && config._pendingState === state) {
+                await this._exchangeAuthorizationCode(code);
+            }
+        } catch (error) {
+            // A pasted code already finished this flow
+            if (this.currentAuthConfig.config === config && config._pendingState === state) {
+                this._showError(this._el('oauth2-error-group'), this._el('oauth2-error-message'),
+                    error?.message || String(error));
+            }
+        }
+    }
+
+    /**
+     * Handles the Device Code flow: shows the user code and verification URL,
+     * then waits while the backend polls the token endpoint. The token is only
+     * applied if the same auth config is still being edited when it arrives.
+     *
+     * @private
+     * @async
+     * @param {HTMLElement} errorGroup - Error display group element
+     * @param {HTMLElement} errorMessage - Error message element
+     * @returns {Promise<void>}
+     */
+    async _handleDeviceCodeFlow(errorGroup, errorMessage) {
+        const {config} = this.currentAuthConfig;
+
+        const authorization = await api.oauth2.deviceStart({
+            grantType: 'device_code',
+            tokenUrl: config.tokenUrl,
+            deviceAuthorizationUrl: config.deviceAuthorizationUrl,
+            clientId: config.clientId,
+            clientSecret: config.clientSecret || null,
+            scope: config.scope || null,
+            audience: config.audience || null,
+            clientAuthMethod: config.clientAuthMethod || 'body'
+        });
+
+        this._showDeviceCodeInstructions(authorization);
+
+        const result = await api.oauth2.devicePoll(authorization.flowId);
+        if (this.currentAuthConfig.config !== config) {
+            return;
+        }
+        this._handleTokenResponse(result, errorGroup, errorMessage);
+    }
+
+    /**
+     * Shows the user code and verification link for the device code flow
+     *
+     * @private
+     * @param {Object} authorization - Device authorization from the backend
+     * @returns {void}
+     */
+    _showDeviceCodeInstructions(authorization) {
+        const errorGroup = this._el('oauth2-error-group');
+        const errorMessage = this._el('oauth2-error-message');
+        if (!errorGroup || !errorMessage) {
+            return;
+        }
+
+        errorGroup.classList.remove('u-hidden');
+        errorMessage.className = 'alert alert-info';
+        errorMessage.innerHTML = `
+            <strong>Device Authorization Required</strong><br>
+            Open the verification page and enter this code:<br>
+            <code class="u-mt-2" data-role="device-user-code"></code><br>
+            <button type="button" data-role="device-open" class="btn btn-primary btn-sm u-mt-2">Open Verification Page</button>
+            <button type="button" data-role="device-cancel" class="btn btn-outline btn-sm u-mt-2">Cancel</button>
+        `;
+        errorMessage.querySelector('[data-role="device-user-code"]').textContent = authorization.userCode;
+
+        const verificationUrl = authorization.verificationUriComplete || authorization.verificationUri;
+        errorMessage.querySelector('[data-role="device-open"]').addEventListener('click', () => {
+            window.open(verificationUrl, '_blank', 'width=600,height=700');
+        });
+        errorMessage.querySelector('[data-role="device-cancel"]').addEventListener('click', () => {
+            api.oauth2.deviceCancel(authorization.flowId);
+        });
     }
 
     /**
@@ -617,7 +804,7 @@ export class AuthManager {
             errorMessage.innerHTML = `
                 <strong>Authorization Required</strong><br>
                 A browser window has opened for you to authorize the application.<br>
-                After authorizing, you will be redirected. Copy the authorization code from the URL and paste it below:<br>
+                After authorizing, you will be redirected. A redirect to localhost is picked up automatically; otherwise copy the authorization code from the URL and paste it below:<br>
                 <input type="text" id="oauth2-auth-code-input" class="input-base form-input u-mt-2" placeholder="Paste authorization code here">
                 <button type="button" id="oauth2-exchange-code-btn" class="btn btn-primary btn-sm u-mt-2">Exchange Code for Token</button>
             `;
@@ -664,11 +851,12 @@ export class AuthManager {
                 clientAuthMethod: config.clientAuthMethod || 'body'
             };
 
-            const result = await api.oauth2.getToken(tokenConfig);
-            this._handleTokenResponse(result, errorGroup, errorMessage);
-
             delete config._pendingState;
             delete config._pendingPkce;
+            api.oauth2.cancelRedirect().catch(() => {});
+
+            const result = await api.oauth2.getToken(tokenConfig);
+            this._handleTokenResponse(result, errorGroup, errorMessage);
         } catch (error) {
             this._showError(errorGroup, errorMessage, error.message || 'Failed to exchange code');
         }
@@ -720,6 +908,7 @@ export class AuthManager {
     _handleTokenResponse(result, errorGroup, errorMessage) {
         if (result.success && result.accessToken) {
             this.currentAuthConfig.config.token = result.accessToken;
+            api.oauth2.tokenSave(toTokenConfig(this.currentAuthConfig.config), result).catch(() => {});
 
             const tokenInput = this._el('oauth2-token');
             if (tokenInput) {tokenInput.value = result.accessToken;}
@@ -797,6 +986,19 @@ export class AuthManager {
         }
     }
 
+    /**
+     * Renders the Negotiate (Kerberos) hint; the ticket comes from the OS,
+     * so there is nothing to configure.
+     *
+     * @private
+     * @returns {void}
+     */
+    renderNegotiateFields() {
+        const fragment = this._cloneAuthTemplate('tpl-auth-negotiate');
+        this.authFieldsContainer.innerHTML = '';
+        this.authFieldsContainer.appendChild(fragment);
+    }
+
     /**
      * Renders AWS Signature V4 authentication fields
      *
@@ -850,6 +1052,60 @@ export class AuthManager {
         }
     }
 
+    /**
+     * Renders the fields of command auth, whose token is printed by a
+     * user-configured command and cached by the backend
+     *
+     * @private
+     * @returns {void}
+     */
+    renderCommandFields() {
+        const fragment = this._cloneAuthTemplate('tpl-auth-command');
+        this.authFieldsContainer.innerHTML = '';
+        this.authFieldsContainer.appendChild(fragment);
+
+        const config = this.currentAuthConfig.config;
+        const textFields = {
+            'command-auth-command': 'command',
+            'command-auth-token-field': 'tokenField',
+            'command-auth-header-name': 'headerName',
+            'command-auth-header-prefix': 'headerPrefix'
+        };
+        for (const [id, key] of Object.entries(textFields)) {
+            const input = this._el(id);
+            if (!input) {continue;}
+            if (config[key] !== undefined) {input.value = config[key];}
+            input.addEventListener('input', (e) => {
+                this.currentAuthConfig.config[key] = e.target.value;
+            });
+        }
+
+        const numberFields = {
+            'command-auth-timeout': 'timeoutMs',
+            'command-auth-cache': 'cacheSeconds'
+        };
+        for (const [id, key] of Object.entries(numberFields)) {
+            const input = this._el(id);
+            if (!input) {continue;}
+            if (config[key] !== undefined) {input.value = config[key];}
+            input.addEventListener('input', (e) => {
+                const value = parseInt(e.target.value, 10);
+                if (Number.isNaN(value)) {
+                    delete this.currentAuthConfig.config[key];
+                } else {
+                    this.currentAuthConfig.config[key] = value;
+                }
+            });
+        }
+
+        const refreshButton = this._el('command-auth-refresh');
+        if (refreshButton) {
+            refreshButton.addEventListener('click', () => {
+                window.backendAPI?.commandAuth?.clearCache().catch(() => {});
+            });
+        }
+    }
+
     /**
      * Generates authentication data for API requests
      *
@@ -909,6 +1165,13 @@ export class AuthManager {
                 }
                 break;
 
+            case 'command':
+                if (config.token) {
+                    const prefix = config.headerPrefix ?? 'Bearer';
+                    authData.headers[config.headerName || 'Authorization'] = prefix ? `${prefix} ${config.token}` : config.token;
+                }
+                break;
+
             case 'digest':
                 if (config.username || config.password) {
                     authData.authConfig = {
@@ -918,6 +1181,10 @@ export class AuthManager {
                 }
                 break;
 
+            case 'negotiate':
+                authData.authConfig = { username: '', password: '', authType: 'negotiate' };
+                break;
+
             case 'aws-v4':
                 if (config.accessKeyId && config.secretAccessKey) {
                     authData.awsAuth = {
@@ -1016,6 +1283,7 @@ export class AuthManager {
                 const oauth2GrantType = this._el('oauth2-grant-type');
                 const oauth2TokenUrl = this._el('oauth2-token-url');
                 const oauth2AuthUrl = this._el('oauth2-auth-url');
+                const oauth2DeviceUrl = this._el('oauth2-device-url');
                 const oauth2ClientId = this._el('oauth2-client-id');
                 const oauth2ClientSecret = this._el('oauth2-client-secret');
                 const oauth2Username = this._el('oauth2-username');
@@ -1032,6 +1300,7 @@ export class AuthManager {
                 if (oauth2GrantType && config.grantType) {oauth2GrantType.value = config.grantType;}
                 if (oauth2TokenUrl && config.tokenUrl) {oauth2TokenUrl.value = config.tokenUrl;}
                 if (oauth2AuthUrl && config.authorizationUrl) {oauth2AuthUrl.value = config.authorizationUrl;}
+                if (oauth2DeviceUrl && config.deviceAuthorizationUrl) {oauth2DeviceUrl.value = config.deviceAuthorizationUrl;}
                 if (oauth2ClientId && config.clientId) {oauth2ClientId.value = config.clientId;}
                 if (oauth2ClientSecret && config.clientSecret) {oauth2ClientSecret.value = config.clientSecret;}
                 if (oauth2Username && config.username) {oauth2Username.value = config.username;}
@@ -1079,6 +1348,22 @@ export class AuthManager {
                 break;
             }
 
+            case 'command': {
+                const command = this._el('command-auth-command');
+                const tokenField = this._el('command-auth-token-field');
+                const headerName = this._el('command-auth-header-name');
+                const headerPrefix = this._el('command-auth-header-prefix');
+                const timeout = this._el('command-auth-timeout');
+                const cache = this._el('command-auth-cache');
+                if (command && config.command) {command.value = config.command;}
+                if (tokenField && config.tokenField) {tokenField.value = config.tokenField;}
+                if (headerName && config.headerName) {headerName.value = config.headerName;}
+                if (headerPrefix && config.headerPrefix !== undefined) {headerPrefix.value = config.headerPrefix;}
+                if (timeout && config.timeoutMs) {timeout.value = config.timeoutMs;}
+                if (cache && config.cacheSeconds !== undefined) {cache.value = config.cacheSeconds;}
+                break;
+            }
+
             default:
                 break;
         }
@@ -0,0 +1,108 @@
+/**
+ * @fileoverview Bulk edit for the path param, query param and header lists:
+ * swaps the rows for a `Name: Value` text block and back. Parsing and
+ * validation happen in the backend, so pasted header sets are checked the
+ * same way everywhere.
+ * @module bulkEditManager
+ */
+
+import { app } from './appContext.js';
+import { parseKeyValuePairs, clearKeyValueList, populateKeyValueList, addKeyValueRow } from './keyValueManager.js';
+import { toast } from './ui/Toast.js';
+
+/**
+ * Shows the text block in place of the rows
+ *
+ * @async
+ * @param {HTMLElement} list - Key-value list
+ * @param {HTMLTextAreaElement} textarea - Bulk edit text block
+ * @returns {Promise<void>}
+ */
+async function enterBulkMode(list, textarea) {
+    textarea.value = await window.backendAPI.bulkEdit.serialize(parseKeyValuePairs(list));
+    list.classList.add('is-hidden');
+    textarea.classList.remove('is-hidden');
+    textarea.focus();
+}
+
+/**
+ * Replaces the rows with the text block's entries. Returns false, leaving
+ * bulk mode on, when some lines are invalid.
+ *
+ * @async
+ * @param {HTMLElement} list - Key-value list
+ * @param {HTMLTextAreaElement} textarea - Bulk edit text block
+ * @param {HTMLElement} errorList - Where line errors are listed
+ * @param {string} location - "path", "query" or "header"
+ * @returns {Promise<boolean>} Whether the rows were updated
+ */
+async function applyBulkText(list, textarea, errorList, location) {
+    const result = await window.backendAPI.bulkEdit.parse(textarea.value, location);
+
+    errorList.innerHTML = '';
+    if (result.errors.length > 0) {
+        result.errors.forEach(error => {
+            const item = document.createElement('li');
+            item.textContent = app.i18n?.t('bulk_edit.line_error', { line: error.line, message: error.message })
+                || `Line ${error.line}: ${error.message}`;
+            errorList.appendChild(item);
+        });
+        errorList.classList.remove('is-hidden');
+        return false;
+    }
+    errorList.classList.add('is-hidden');
+
+    clearKeyValueList(list);
+    populateKeyValueList(list, Object.fromEntries(
+        Object.entries(result.parameters).map(([name, param]) => [name, param.example ?? ''])
+    ));
+    if (list.children.length === 0) {
+        addKeyValueRow(list);
+    }
+    // Goes through the list's input listener: URL sync, auto-save, modified marker
+    list.querySelector('.key-input')?.dispatchEvent(new Event('input', { bubbles: true }));
+
+    textarea.classList.add('is-hidden');
+    list.classList.remove('is-hidden');
+    return true;
+}
+
+/**
+ * Wires the Bulk Edit toggles next to the key-value lists
+ *
+ * @returns {void}
+ */
+export function initBulkEdit() {
+    if (!window.backendAPI?.bulkEdit) {
+        return;
+    }
+    document.querySelectorAll('.bulk-edit-toggle').forEach(toggle => {
+        const list = document.getElementById(toggle.dataset.bulkList);
+        const textarea = document.getElementById(`${toggle.dataset.bulkList}-bulk`);
+        const errorList = document.getElementById(`${toggle.dataset.bulkList}-bulk-errors`);
+        const addRowBtn = toggle.parentElement.querySelector('.add-row-btn');
+        if (!list || !textarea || !errorList) {
+            return;
+        }
+
+        toggle.addEventListener('click', async () => {
+            const active = toggle.getAttribute('aria-pressed') === 'true';
+            try {
+                if (active && !await applyBulkText(list, textarea, errorList, toggle.dataset.bulkLocation)) {
+                    return;
+                }
+                if (!active) {
+                    await enterBulkMode(list, textarea);
+                }
+            } catch (error) {
+                toast.error(typeof error === 'string' ? error : (error?.message || 'Bulk edit failed'));
+                return;
+            }
+            toggle.setAttribute('aria-pressed', String(!active));
+            toggle.textContent = active
+                ? (app.i18n?.t('bulk_edit.toggle') || 'Bulk Edit')
+                : (app.i18n?.t('bulk_edit.apply') || 'Key-Value Edit');
+            addRowBtn?.classList.toggle('is-hidden', !active);
+        });
+    });
+}
@@ -82,6 +82,51 @@ export function importPostmanCollection() {
     return controller.importPostmanCollection();
 }
 
+/**
+ * Opens file dialog and imports every collection and environment of a
+ * Postman data dump
+ *
+ * @async
+ * @returns {Promise<Object|null>} Import result or null if cancelled
+ */
+export function importPostmanDump() {
+    const controller = initializeController();
+    return controller.importPostmanDump();
+}
+
+/**
+ * Opens file dialog and imports a request shared as a bundle
+ *
+ * @async
+ * @returns {Promise<Object|null>} Import result or null if cancelled
+ */
+export function importRequestBundle() {
+    const controller = initializeController();
+    return controller.importRequestBundle();
+}
+
+/**
+ * Opens file dialog and imports the requests recorded in a HAR file
+ *
+ * @async
+ * @returns {Promise<Object|null>} Imported collection or null if cancelled
+ */
+export function importHarFile() {
+    const controller = initializeController();
+    return controller.importHarFile();
+}
+
+/**
+ * Opens file dialog and imports the requests of a `.http` file
+ *
+ * @async
+ * @returns {Promise<Object|null>} Imported collection or null if cancelled
+ */
+export function importHttpFile() {
+    const controller = initializeController();
+    return controller.importHttpFile();
+}
+
 /**
  * Opens file dialog and imports Postman environment file
  *
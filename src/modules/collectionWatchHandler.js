@@ -0,0 +1,34 @@
+/**
+ * @fileoverview Reloads the collection tree when collection files change on
+ * disk outside the app (git pull, editor, sync tools). The backend polls the
+ * collection directories and emits `collections-changed`.
+ * @module collectionWatchHandler
+ */
+
+import { app } from './appContext.js';
+import { createBackendEventListener } from './streaming/streamSession.js';
+
+/** Coalesces bursts of file changes (e.g. a checkout) into one reload. */
+const RELOAD_DEBOUNCE_MS = 300;
+
+let reloadTimer = null;
+
+function handleBackendEvent(event) {
+    const payload = event?.payload;
+    if (!payload?.collectionId) {
+        return;
+    }
+
+    app.collectionController?.repository?.invalidate(payload.collectionId);
+
+    clearTimeout(reloadTimer);
+    reloadTimer = setTimeout(() => {
+        app.collectionController?.loadCollectionsWithExpansionState();
+    }, RELOAD_DEBOUNCE_MS);
+}
+
+export const initCollectionWatchHandler = createBackendEventListener(
+    'collections-changed',
+    () => !!window.backendAPI?.collections,
+    handleBackendEvent
+);
@@ -24,11 +24,46 @@ import { VariableManager } from '../ui/VariableManager.js';
 import { CurlImportDialog } from '../ui/CurlImportDialog.js';
 import { CollectionDialogs } from '../ui/CollectionDialogs.js';
 import { CollectionAuthDialog } from '../ui/CollectionAuthDialog.js';
+import { CollectionProxyDialog } from '../ui/CollectionProxyDialog.js';
+import { CollectionLoginFlowDialog } from '../ui/CollectionLoginFlowDialog.js';
+import { CollectionSafetyPolicyDialog } from '../ui/CollectionSafetyPolicyDialog.js';
+import { CollectionGrpcMetadataDialog } from '../ui/CollectionGrpcMetadataDialog.js';
+import { FolderSendResultsDialog } from '../ui/FolderSendResultsDialog.js';
+import { HealthCheckResultsDialog } from '../ui/HealthCheckResultsDialog.js';
+import { IpFamilyDialog } from '../ui/IpFamilyDialog.js';
+import { VariableOverridesDialog } from '../ui/VariableOverridesDialog.js';
+import { IdempotencyKeyDialog } from '../ui/IdempotencyKeyDialog.js';
+import { ResponseTransformDialog } from '../ui/ResponseTransformDialog.js';
+import { GrpcApiDocsDialog } from '../ui/GrpcApiDocsDialog.js';
 import { toast } from '../ui/Toast.js';
 import { StatusDisplayAdapter } from '../interfaces/IStatusDisplay.js';
 import { setRequestBodyContent } from '../requestBodyHelper.js';
 import { DocGeneratorService } from '../services/DocGeneratorService.js';
 
+/**
+ * Puts the outcomes of re-sending held back requests in place of the held
+ * entries of the first batch, and recounts.
+ *
+ * @param {Object} result - The first `send_folder` result
+ * @param {Object} resent - The result of sending the held requests confirmed
+ * @returns {Object} The combined result
+ */
+function mergeFolderSendResults(result, resent) {
+    const byId = new Map(resent.results.map(item => [item.endpointId, item]));
+    const results = result.results.map(item =>
+        (item.confirmationRequired && byId.get(item.endpointId)) || item);
+    const succeeded = results.filter(item => item.success).length;
+    const held = results.filter(item => item.confirmationRequired).length;
+    return {
+        ...result,
+        duration: result.duration + resent.duration,
+        succeeded,
+        failed: results.length - succeeded - held,
+        confirmationRequired: held,
+        results
+    };
+}
+
 /**
  * Controller for coordinating collection operations between UI and services
  *
@@ -62,6 +97,10 @@ export class CollectionController {
         this.confirmDialog = new ConfirmDialog();
         this.variableManager = new VariableManager();
         this.collectionAuthDialog = new CollectionAuthDialog();
+        this.collectionProxyDialog = new CollectionProxyDialog();
+        this.collectionLoginFlowDialog = new CollectionLoginFlowDialog();
+        this.collectionSafetyPolicyDialog = new CollectionSafetyPolicyDialog();
+        this.collectionGrpcMetadataDialog = new CollectionGrpcMetadataDialog();
         this.curlImportDialog = new CurlImportDialog();
         this.collectionDialogs = new CollectionDialogs({
             backendAPI,
@@ -316,6 +355,36 @@ export class CollectionController {
                 iconClass: 'icon-lock',
                 onClick: () => this.handleCollectionAuth(collection)
             },
+            {
+                label: 'Proxy Settings',
+                translationKey: 'context_menu.proxy_settings',
+                iconClass: 'icon-globe',
+                onClick: () => this.handleCollectionProxy(collection)
+            },
+            {
+                label: 'Login Flow',
+                translationKey: 'context_menu.login_flow',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleCollectionLoginFlow(collection)
+            },
+            ...(collection.loginFlow ? [{
+                label: 'Log In Now',
+                translationKey: 'context_menu.run_login_flow',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleRunLoginFlow(collection)
+            }] : []),
+            {
+                label: 'Safety Policy',
+                translationKey: 'context_menu.safety_policy',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleCollectionSafetyPolicy(collection)
+            },
+            {
+                label: 'Health Check',
+                translationKey: 'context_menu.health_check',
+                iconClass: 'icon-play',
+                onClick: () => this.handleHealthCheck(collection)
+            },
             {
                 label: 'Export as OpenAPI (JSON)',
                 translationKey: 'context_menu.export_openapi_json',
@@ -334,12 +403,35 @@ export class CollectionController {
                 iconClass: ContextMenu.createExportIcon(),
                 onClick: () => this.handleExportPostman(collection)
             },
+            {
+                label: 'Export as .http File',
+                translationKey: 'context_menu.export_http_file',
+                iconClass: ContextMenu.createExportIcon(),
+                onClick: () => this.handleExportHttpFile(collection)
+            },
+            {
+                label: 'Export as Shell Script',
+                translationKey: 'context_menu.export_shell_script',
+                iconClass: ContextMenu.createExportIcon(),
+                onClick: () => this.handleExportShellScript(collection)
+            },
             {
                 label: 'Generate Documentation',
                 translationKey: 'context_menu.generate_docs',
                 iconClass: ContextMenu.createDocumentIcon(),
                 onClick: () => this.handleGenerateDocumentation(collection)
             },
+            ...((collection.endpoints || []).some(endpoint => endpoint.method?.toUpperCase() === 'GRPC') ? [{
+                label: 'gRPC API Docs...',
+                translationKey: 'context_menu.grpc_api_docs',
+                iconClass: ContextMenu.createDocumentIcon(),
+                onClick: () => this.handleGrpcApiDocs(collection)
+            }, {
+                label: 'gRPC Metadata...',
+                translationKey: 'context_menu.grpc_metadata',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleCollectionGrpcMetadata(collection)
+            }] : []),
             {
                 label: 'Rename Collection',
                 translationKey: 'context_menu.rename_collection',
@@ -384,6 +476,60 @@ export class CollectionController {
                 iconClass: ContextMenu.createRenameIcon(),
                 onClick: () => this.handleRenameRequest(collection, endpoint)
             },
+            {
+                label: 'Time Budget...',
+                translationKey: 'context_menu.time_budget',
+                iconClass: 'icon-clock',
+                onClick: () => this.handleRequestTimeBudget(collection, endpoint)
+            },
+            {
+                label: 'IP Version...',
+                translationKey: 'context_menu.ip_family',
+                iconClass: 'icon-globe',
+                onClick: () => this.handleRequestIpFamily(collection, endpoint)
+            },
+            {
+                label: endpoint.ignoreTlsErrors ? 'Verify TLS Certificates' : 'Ignore TLS Errors',
+                translationKey: endpoint.ignoreTlsErrors ? 'context_menu.verify_tls' : 'context_menu.ignore_tls_errors',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleToggleIgnoreTlsErrors(collection, endpoint)
+            },
+            {
+                label: 'Variable Overrides...',
+                translationKey: 'context_menu.variable_overrides',
+                iconClass: 'icon-variable',
+                onClick: () => this.handleRequestVariableOverrides(collection, endpoint)
+            },
+            {
+                label: 'Idempotency Key...',
+                translationKey: 'context_menu.idempotency_key',
+                iconClass: 'icon-lock',
+                onClick: () => this.handleRequestIdempotencyKey(collection, endpoint)
+            },
+            {
+                label: 'Response Transform...',
+                translationKey: 'context_menu.response_transform',
+                iconClass: 'icon-code',
+                onClick: () => this.handleRequestResponseTransform(collection, endpoint)
+            },
+            {
+                label: 'Infer Response Schema',
+                translationKey: 'context_menu.infer_response_schema',
+                iconClass: 'icon-spark',
+                onClick: () => this.handleInferResponseSchema(collection, endpoint)
+            },
+            {
+                label: 'Export Request Bundle...',
+                translationKey: 'context_menu.export_request_bundle',
+                iconClass: 'icon-export',
+                onClick: () => this.handleExportRequestBundle(collection, endpoint)
+            },
+            {
+                label: 'Copy Request Link',
+                translationKey: 'context_menu.copy_request_link',
+                iconClass: 'icon-copy',
+                onClick: () => this.handleCopyRequestLink(collection, endpoint)
+            },
             {
                 label: 'Delete Request',
                 translationKey: 'context_menu.delete_request',
@@ -401,6 +547,24 @@ export class CollectionController {
         await this.loadCollectionsWithExpansionState();
     }
 
+    /**
+     * Toggles certificate verification for one request, e.g. a self-signed
+     * development server, without turning it off globally
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleToggleIgnoreTlsErrors(collection, endpoint) {
+        try {
+            await this.service.setRequestIgnoreTlsErrors(collection.id, endpoint.id, !endpoint.ignoreTlsErrors);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
     /**
      * Handles right-click context menu on empty space in collections panel
      *
@@ -428,6 +592,149 @@ export class CollectionController {
         this.contextMenu.show(event, menuItems);
     }
 
+    /**
+     * Asks for the request's response time budget and stores it
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleRequestTimeBudget(collection, endpoint) {
+        const current = String(endpoint.timeBudgetMs ?? 0);
+        const value = await this.renameDialog.show(current, {
+            title: app.i18n?.t('time_budget.title') || 'Response Time Budget',
+            label: app.i18n?.t('time_budget.label') || 'Budget in ms (0 to remove):',
+            confirmText: app.i18n?.t('common.save') || 'Save'
+        });
+        if (value === null || value === current) {
+            return;
+        }
+
+        const budgetMs = Number(value);
+        if (!Number.isInteger(budgetMs) || budgetMs < 0) {
+            toast.error(app.i18n?.t('time_budget.invalid') || 'The budget must be a whole number of milliseconds');
+            return;
+        }
+        try {
+            await this.service.setRequestTimeBudget(collection.id, endpoint.id, budgetMs);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
+    /**
+     * Asks which IP family the request resolves its host to and stores it
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleRequestIpFamily(collection, endpoint) {
+        const value = await new IpFamilyDialog().show(endpoint.ipFamily ?? null);
+        if (value === null || value === (endpoint.ipFamily ?? '')) {
+            return;
+        }
+        try {
+            await this.service.setRequestIpFamily(collection.id, endpoint.id, value || null);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
+    /**
+     * Edits the variable values the request uses instead of the environment's
+     * and stores them
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleRequestVariableOverrides(collection, endpoint) {
+        const overrides = await new VariableOverridesDialog().show(endpoint.variableOverrides ?? null);
+        if (overrides === null) {
+            return;
+        }
+        try {
+            await this.service.setRequestVariableOverrides(collection.id, endpoint.id, overrides);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
+    /**
+     * Edits whether the request sends a generated idempotency key and stores
+     * the settings
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleRequestIdempotencyKey(collection, endpoint) {
+        const result = await new IdempotencyKeyDialog().show(endpoint.idempotencyKey ?? null);
+        if (result === null) {
+            return;
+        }
+        const settings = result.enabled ? { header: result.header, template: result.template } : null;
+        try {
+            await this.service.setRequestIdempotencyKey(collection.id, endpoint.id, settings);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
+    /**
+     * Edits the transform applied to the request's responses and stores it
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleRequestResponseTransform(collection, endpoint) {
+        const transform = await new ResponseTransformDialog().show(endpoint.responseTransform ?? null);
+        if (transform === null) {
+            return;
+        }
+        try {
+            await this.service.setRequestResponseTransform(collection.id, endpoint.id, transform);
+            await this.loadCollectionsWithExpansionState();
+        } catch (error) {
+            toast.error(error.message);
+        }
+    }
+
+    /**
+     * Infers response schemas from the request's recorded responses and saved
+     * examples and stores them in its `responses`, for OpenAPI exports, the
+     * mock server and contract checks
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleInferResponseSchema(collection, endpoint) {
+        try {
+            const inferred = await this.backendAPI.endpoints.inferResponseSchema(collection.id, endpoint.id);
+            // The backend rewrote the collection file
+            this.repository.invalidate(collection.id);
+            await this.loadCollectionsWithExpansionState();
+            const statuses = inferred.map(response => response.status).join(', ');
+            toast.success(app.i18n?.t('schema_inference.done', { statuses })
+                || `Response schema saved for ${statuses}`);
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
     /**
      * Handles collection rename operation
      *
@@ -493,6 +800,104 @@ export class CollectionController {
         }
     }
 
+    /**
+     * Opens the collection proxy dialog and persists the edited override.
+     * The backend reads it from the collection at send time.
+     *
+     * @async
+     * @param {Object} collection - The collection whose proxy to edit
+     * @returns {Promise<void>}
+     */
+    async handleCollectionProxy(collection) {
+        try {
+            const current = await this.repository.getById(collection.id) || collection;
+            const result = await this.collectionProxyDialog.show(current);
+            if (result !== null) {
+                await this.repository.saveCollectionProxy(collection.id, result.proxy);
+            }
+        } catch (error) {
+            void error;
+        }
+    }
+
+    /**
+     * Opens the login flow dialog and persists the edited flow. The backend
+     * reads it from the collection when running it.
+     *
+     * @async
+     * @param {Object} collection - The collection whose login flow to edit
+     * @returns {Promise<void>}
+     */
+    async handleCollectionLoginFlow(collection) {
+        try {
+            const current = await this.repository.getById(collection.id) || collection;
+            const result = await this.collectionLoginFlowDialog.show(current);
+            if (result !== null) {
+                await this.repository.saveCollectionLoginFlow(collection.id, result.loginFlow);
+                await this.loadCollectionsWithExpansionState();
+            }
+        } catch (error) {
+            toast.error(error.message || String(error));
+        }
+    }
+
+    /**
+     * Opens the safety policy dialog and persists the edited policy. The
+     * backend checks it on every send.
+     *
+     * @async
+     * @param {Object} collection - The collection whose safety policy to edit
+     * @returns {Promise<void>}
+     */
+    async handleCollectionSafetyPolicy(collection) {
+        try {
+            const current = await this.repository.getById(collection.id) || collection;
+            const result = await this.collectionSafetyPolicyDialog.show(current);
+            if (result !== null) {
+                await this.repository.saveCollectionSafetyPolicy(collection.id, result.safetyPolicy);
+            }
+        } catch (error) {
+            toast.error(error.message || String(error));
+        }
+    }
+
+    /**
+     * Opens the default gRPC metadata dialog and persists the edited
+     * defaults. The backend merges them into every gRPC call of the collection.
+     *
+     * @async
+     * @param {Object} collection - The collection whose gRPC metadata to edit
+     * @returns {Promise<void>}
+     */
+    async handleCollectionGrpcMetadata(collection) {
+        try {
+            const current = await this.repository.getById(collection.id) || collection;
+            const result = await this.collectionGrpcMetadataDialog.show(current);
+            if (result !== null) {
+                await this.repository.saveCollectionGrpcMetadata(collection.id, result.grpcMetadata);
+            }
+        } catch (error) {
+            toast.error(error.message || String(error));
+        }
+    }
+
+    /**
+     * Sends the collection's login request and stores the token it returns.
+     * The environment reloads through the backend's `environment-changed` event.
+     *
+     * @async
+     * @param {Object} collection - The collection to log in to
+     * @returns {Promise<void>}
+     */
+    async handleRunLoginFlow(collection) {
+        try {
+            const result = await this.backendAPI.loginFlow.run(collection.id);
+            toast.success(`Logged in: stored the token in {{${result.variable}}}`);
+        } catch (error) {
+            toast.error(`Login failed: ${error.message || error}`);
+        }
+    }
+
     /**
      * Handles right-click context menu on a folder header.
      *
@@ -508,10 +913,100 @@ export class CollectionController {
                 translationKey: 'context_menu.edit_auth',
                 iconClass: 'icon-lock',
                 onClick: () => this.handleFolderAuth(collection, folder)
+            },
+            {
+                label: 'Send All Requests',
+                translationKey: 'context_menu.send_folder',
+                iconClass: 'icon-play',
+                onClick: () => this.handleSendFolder(collection, folder, false)
+            },
+            {
+                label: 'Send All in Parallel',
+                translationKey: 'context_menu.send_folder_parallel',
+                iconClass: 'icon-play',
+                onClick: () => this.handleSendFolder(collection, folder, true)
+            },
+            {
+                label: 'Health Check',
+                translationKey: 'context_menu.health_check',
+                iconClass: 'icon-play',
+                onClick: () => this.handleHealthCheck(collection, folder)
+            },
+            {
+                label: 'Export as Shell Script',
+                translationKey: 'context_menu.export_shell_script',
+                iconClass: ContextMenu.createExportIcon(),
+                onClick: () => this.handleExportShellScript(collection, folder)
             }
         ]);
     }
 
+    /**
+     * Sends every request of a folder once, without scripts, and lists the
+     * statuses and timings. Requests the collection's safety policy holds
+     * back are only sent after confirming them.
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} folder - The folder to send
+     * @param {boolean} parallel - Send several requests at a time
+     * @returns {Promise<void>}
+     */
+    async handleSendFolder(collection, folder, parallel) {
+        try {
+            toast.info(`Sending ${folder.name}…`);
+            let result = await this.backendAPI.runner.sendFolder(collection.id, folder.id, parallel);
+            const held = result.results.filter(item => item.confirmationRequired);
+            if (held.length > 0) {
+                const requests = held.map(item => `${item.method} ${item.url}`).join(', ');
+                const confirmed = await this.confirmDialog.show(
+                    `${held[0].error} Send ${requests} anyway?`,
+                    {
+                        title: `Send ${held.length} held back request${held.length === 1 ? '' : 's'}?`,
+                        confirmText: 'Send',
+                        dangerous: true
+                    }
+                );
+                if (confirmed) {
+                    const resent = await this.backendAPI.runner.sendFolder(
+                        collection.id,
+                        folder.id,
+                        parallel,
+                        true,
+                        held.map(item => item.endpointId)
+                    );
+                    result = mergeFolderSendResults(result, resent);
+                }
+            }
+            new FolderSendResultsDialog().show(folder.name, result);
+        } catch (error) {
+            toast.error(`Send failed: ${error.message || error}`);
+        }
+    }
+
+    /**
+     * Checks with HEAD requests that every endpoint of a collection (or one
+     * folder) responds, and lists up/down, status and latency.
+     *
+     * @async
+     * @param {Object} collection - The collection to check
+     * @param {Object} [folder] - Only check this folder
+     * @returns {Promise<void>}
+     */
+    async handleHealthCheck(collection, folder = null) {
+        const name = folder?.name || collection.name;
+        try {
+            toast.info(app.i18n?.t('health_check.checking', { name }) || `Checking ${name}…`);
+            const result = await this.backendAPI.runner.healthCheck({
+                collectionId: collection.id,
+                folderId: folder?.id ?? null
+            });
+            new HealthCheckResultsDialog().show(name, result);
+        } catch (error) {
+            toast.error(`Health check failed: ${error.message || error}`);
+        }
+    }
+
     /**
      * Opens the folder auth dialog and persists the edited config. Folder
      * auth overrides collection auth for the folder's inheriting endpoints;
@@ -724,6 +1219,54 @@ export class CollectionController {
         await this.importExportService.handleExportPostman(collection);
     }
 
+    /**
+     * Handles exporting a collection as a REST Client / JetBrains `.http` file
+     *
+     * @async
+     * @param {Object} collection - The collection to export
+     * @returns {Promise<void>}
+     */
+    async handleExportHttpFile(collection) {
+        await this.importExportService.handleExportHttpFile(collection);
+    }
+
+    /**
+     * Handles exporting a collection, or one of its folders, as a bash script
+     * of curl calls
+     *
+     * @async
+     * @param {Object} collection - The collection to export
+     * @param {Object|null} [folder=null] - Only export this folder
+     * @returns {Promise<void>}
+     */
+    async handleExportShellScript(collection, folder = null) {
+        await this.importExportService.handleExportShellScript(collection, folder);
+    }
+
+    /**
+     * Saves a request as a bundle file for sharing
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint to share
+     * @returns {Promise<void>}
+     */
+    async handleExportRequestBundle(collection, endpoint) {
+        await this.importExportService.handleExportRequestBundle(collection, endpoint);
+    }
+
+    /**
+     * Copies a request as a shareable link
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint to share
+     * @returns {Promise<void>}
+     */
+    async handleCopyRequestLink(collection, endpoint) {
+        await this.importExportService.handleCopyRequestLink(collection, endpoint);
+    }
+
     /**
      * Handles documentation generation for a collection
      *
@@ -737,6 +1280,23 @@ export class CollectionController {
         await this.importExportService.handleGenerateDocumentation(collection);
     }
 
+    /**
+     * Opens the collection's gRPC API documentation. Capturing defaults to the
+     * server of the collection's first gRPC request.
+     *
+     * @async
+     * @param {Object} collection - The collection
+     * @returns {Promise<void>}
+     */
+    async handleGrpcApiDocs(collection) {
+        const grpcEndpoint = (collection.endpoints || []).find(endpoint => endpoint.method?.toUpperCase() === 'GRPC');
+        const grpcData = grpcEndpoint ? await this.repository.getGrpcData(collection.id, grpcEndpoint.id) : null;
+        new GrpcApiDocsDialog().show(collection, {
+            target: grpcData?.target || '',
+            useTls: grpcData?.useTls === true
+        });
+    }
+
     /**
      * Handles request rename operation
      *
@@ -861,11 +1421,42 @@ export class CollectionController {
      * Triggers file picker dialog via IPC and processes the selected file.
      *
      * @async
+     * @param {?string} [filePath=null] - File to preselect instead of picking one
      * @returns {Promise<Object|null>} Created collection object or null if cancelled
      * @throws {Error} If import fails
      */
-    async importOpenApiFile() {
-        return this.importExportService.importOpenApiFile();
+    async importOpenApiFile(filePath = null) {
+        return this.importExportService.importOpenApiFile(filePath);
+    }
+
+    /**
+     * Imports an OpenAPI specification from a URL and creates a collection
+     *
+     * @async
+     * @param {string} url - HTTP(S) URL of the spec
+     * @returns {Promise<Object|null>} Created collection object or null on failure
+     */
+    async importOpenApiUrl(url) {
+        return this.importExportService.importOpenApiUrl(url);
+    }
+
+    /**
+     * Expands a collection in the sidebar and scrolls it into view, clearing
+     * a search that would hide it
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @returns {Promise<void>}
+     */
+    async revealCollection(collectionId) {
+        if (this.searchQuery) {
+            this.searchQuery = '';
+            if (this.collectionsSearchInput) {
+                this.collectionsSearchInput.value = '';
+            }
+            await this.renderCollections(this.allCollections, true);
+        }
+        await this.renderer.revealCollection(collectionId);
     }
 
     /**
@@ -875,11 +1466,60 @@ export class CollectionController {
      * Also imports any collection variables extracted from the Postman collection.
      *
      * @async
+     * @param {?string} [filePath=null] - File to preselect instead of picking one
+     * @returns {Promise<Object|null>} Created collection object or null if cancelled
+     * @throws {Error} If import fails
+     */
+    async importPostmanCollection(filePath = null) {
+        return this.importExportService.importPostmanCollection(filePath);
+    }
+
+    /**
+     * Imports every collection and environment of a Postman data dump zip
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect instead of picking one
+     * @returns {Promise<Object|null>} Import result or null if cancelled
+     * @throws {Error} If import fails
+     */
+    async importPostmanDump(filePath = null) {
+        return this.importExportService.importPostmanDump(filePath);
+    }
+
+    /**
+     * Imports a request shared as a bundle file or link
+     *
+     * @async
+     * @param {?string} [filePath=null] - Bundle file to open instead of picking one
+     * @param {?string} [link=null] - `resonance://bundle` link to read instead of a file
+     * @returns {Promise<Object|null>} Import result or null if cancelled
+     */
+    async importRequestBundle(filePath = null, link = null) {
+        return this.importExportService.importRequestBundle(filePath, link);
+    }
+
+    /**
+     * Imports the requests recorded in a HAR file and creates a collection
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect instead of picking one
+     * @returns {Promise<Object|null>} Created collection object or null if cancelled
+     * @throws {Error} If import fails
+     */
+    async importHarFile(filePath = null) {
+        return this.importExportService.importHarFile(filePath);
+    }
+
+    /**
+     * Imports the requests of a REST Client / JetBrains `.http` file and creates a collection
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect instead of picking one
      * @returns {Promise<Object|null>} Created collection object or null if cancelled
      * @throws {Error} If import fails
      */
-    async importPostmanCollection() {
-        return this.importExportService.importPostmanCollection();
+    async importHttpFile(filePath = null) {
+        return this.importExportService.importHttpFile(filePath);
     }
 
     /**
@@ -3,8 +3,6 @@
  * @module controllers/CookieController
  */
 
-import { app } from '../appContext.js';
-
 export class CookieController {
     constructor(cookieJarService, cookieManagerDialog) {
         this.service = cookieJarService;
@@ -29,33 +27,6 @@ export class CookieController {
         this._activeEnvironmentId = environmentId || 'default';
     }
 
-    /**
-     * Returns the Cookie header string to inject into the request, or null.
-     */
-    async getCookieHeader(requestUrl) {
-        try {
-            const settings = app.getApiHandlerSettingsCache?.() ?? await window.backendAPI?.settings?.get();
-            if (settings?.cookieJarEnabled === false) { return null; }
-            return await this.service.getCookieHeaderForRequest(requestUrl, this._activeEnvironmentId);
-        } catch (_e) {
-            return null;
-        }
-    }
-
-    /**
-     * Persists cookies from a response into the jar.
-     * @param {string[]} setCookieHeaders
-     * @param {string} requestUrl
-     */
-    async handleCookiesFromResponse(setCookieHeaders, requestUrl) {
-        try {
-            const settings = app.getApiHandlerSettingsCache?.() ?? await window.backendAPI?.settings?.get();
-            if (settings?.cookieJarEnabled === false) { return; }
-            await this.service.processCookiesFromResponse(setCookieHeaders, requestUrl, this._activeEnvironmentId);
-        } catch (_e) {
-        }
-    }
-
     openCookieManager() {
         this.dialog.show(this._activeEnvironmentId, this._activeEnvironmentName);
     }
@@ -141,6 +141,36 @@ export class MockServerController {
         }
     }
 
+    /**
+     * Updates how many request logs the server keeps
+     *
+     * @async
+     * @param {number|string} logSize - Number of request logs to keep
+     * @returns {Promise<Object>} Result object with success status
+     */
+    async handleUpdateLogSize(logSize) {
+        try {
+            const errors = this.service.validateLogSize(logSize);
+            if (errors.length > 0) {
+                return {
+                    success: false,
+                    message: errors.join(', ')
+                };
+            }
+
+            await this.service.setLogSize(parseInt(logSize, 10));
+            return {
+                success: true,
+                message: 'Log size updated successfully'
+            };
+        } catch (error) {
+            return {
+                success: false,
+                message: error.message || 'Failed to update log size'
+            };
+        }
+    }
+
     /**
      * Toggles collection enabled state
      *
@@ -196,6 +226,61 @@ export class MockServerController {
         }
     }
 
+    /**
+     * Sets the default delay of every endpoint in a collection
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {number|string} delayMs - Delay in milliseconds
+     * @returns {Promise<Object>} Result object with success status
+     */
+    async handleSetCollectionDelay(collectionId, delayMs) {
+        try {
+            const errors = this.service.validateDelay(delayMs);
+            if (errors.length > 0) {
+                return {
+                    success: false,
+                    message: errors.join(', ')
+                };
+            }
+
+            await this.service.setCollectionDelay(collectionId, parseInt(delayMs, 10));
+            return {
+                success: true,
+                message: 'Collection delay updated successfully'
+            };
+        } catch (error) {
+            return {
+                success: false,
+                message: error.message || 'Failed to set collection delay'
+            };
+        }
+    }
+
+    /**
+     * Enables or disables a specific endpoint
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {boolean} enabled - Whether the endpoint should be served
+     * @returns {Promise<Object>} Result object with success status
+     */
+    async handleSetEndpointEnabled(collectionId, endpointId, enabled) {
+        try {
+            await this.service.setEndpointEnabled(collectionId, endpointId, enabled);
+            return {
+                success: true,
+                message: enabled ? 'Endpoint enabled' : 'Endpoint disabled'
+            };
+        } catch (error) {
+            return {
+                success: false,
+                message: error.message || 'Failed to update endpoint'
+            };
+        }
+    }
+
     /**
      * Sets custom response for a specific endpoint
      *
@@ -368,6 +453,75 @@ export class MockServerController {
         }
     }
 
+    /**
+     * Selects the saved example an endpoint serves by default
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {string|null} exampleId - Saved example ID (null for the first successful one)
+     * @returns {Promise<Object>} Result object with success status
+     */
+    async handleSetSelectedExample(collectionId, endpointId, exampleId) {
+        try {
+            await this.service.setSelectedExample(collectionId, endpointId, exampleId);
+            return {
+                success: true,
+                message: 'Example selected successfully'
+            };
+        } catch (error) {
+            return {
+                success: false,
+                message: error.message || 'Failed to select example'
+            };
+        }
+    }
+
+    /**
+     * Gets the saved examples of an endpoint
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @returns {Promise<Array>} Saved examples, oldest first
+     */
+    async getExamples(collectionId, endpointId) {
+        try {
+            return await window.backendAPI.endpoints.listExamples(collectionId, endpointId);
+        } catch (error) {
+            return [];
+        }
+    }
+
+    /**
+     * Saves a mock response as an example of its endpoint
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {Object} example - Name, status and JSON body of the example
+     * @returns {Promise<Object>} Result object with success status
+     */
+    async handleSaveExample(collectionId, endpointId, { name, status, body }) {
+        try {
+            await window.backendAPI.endpoints.saveExample(collectionId, endpointId, {
+                name,
+                status,
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body, null, 2)
+            });
+            return {
+                success: true,
+                message: 'Example saved successfully'
+            };
+        } catch (error) {
+            return {
+                success: false,
+                message: error.message || String(error)
+            };
+        }
+    }
+
     /**
      * Gets all collections
      *
@@ -383,14 +537,15 @@ export class MockServerController {
     }
 
     /**
-     * Gets request logs
+     * Gets a page of request logs, newest first
      *
      * @async
      * @param {number} limit - Maximum number of logs to return
-     * @returns {Promise<Array>} Array of request log entries
+     * @param {number} offset - Number of newest logs to skip
+     * @returns {Promise<Object>} Page with logs, total (logs kept), received and capacity
      */
-    async getRequestLogs(limit = 20) {
-        return this.service.getRequestLogs(limit);
+    async getRequestLogs(limit = 20, offset = 0) {
+        return this.service.getRequestLogs(limit, offset);
     }
 
     /**
@@ -70,13 +70,12 @@ export class ScriptController {
                 scripts = await this.service.getScripts(collectionId, endpointId);
             }
 
-            if (!scripts.preRequestScript || scripts.preRequestScript.trim() === '') {
-                return requestConfig;
-            }
-
+            // Always dispatch, even with an empty script: the backend also runs
+            // the workspace-level global pre-request script.
             const { modifiedRequest, result } = await this.service.executePreRequestScript(
-                scripts.preRequestScript,
-                requestConfig
+                scripts.preRequestScript || '',
+                requestConfig,
+                collectionId
             );
 
             if (result.logs.length > 0 || result.errors.length > 0) {
@@ -120,14 +119,13 @@ export class ScriptController {
                 scripts = await this.service.getScripts(collectionId, endpointId);
             }
 
-            if (!scripts.testScript || scripts.testScript.trim() === '') {
-                return null;
-            }
-
+            // Always dispatch, even with an empty script: the backend also runs
+            // the workspace-level global post-response script.
             const result = await this.service.executeTestScript(
-                scripts.testScript,
+                scripts.testScript || '',
                 requestConfig,
-                response
+                response,
+                collectionId
             );
 
             if (this.consolePanel) {
@@ -0,0 +1,40 @@
+/**
+ * @fileoverview Forwards uncaught frontend errors to the backend crash
+ * reporter. The first one of a launch is saved as a crash report (it is
+ * usually what leaves a blank window at startup); later ones become context
+ * for the next report.
+ * @module crashReporter
+ */
+
+/**
+ * Sends an uncaught error or rejection reason to the backend
+ *
+ * @param {*} error - Error object, or whatever was thrown
+ * @returns {void}
+ */
+function reportError(error) {
+    const crashReports = window.backendAPI?.crashReports;
+    if (!crashReports) {
+        return;
+    }
+    const message = error instanceof Error
+        ? `${error.name}: ${error.message}`
+        : String(error);
+    const stack = error instanceof Error ? (error.stack || null) : null;
+    crashReports.reportFrontendError(message, stack).catch(() => { });
+}
+
+/**
+ * Starts listening for uncaught errors. Call as early as possible, before
+ * the UI is initialized.
+ *
+ * @returns {void}
+ */
+export function initCrashReporter() {
+    window.addEventListener('error', (event) => {
+        reportError(event.error || `${event.message} (${event.filename}:${event.lineno}:${event.colno})`);
+    });
+    window.addEventListener('unhandledrejection', (event) => {
+        reportError(event.reason);
+    });
+}
@@ -0,0 +1,210 @@
+/**
+ * @fileoverview Handles `resonance://` deep links and files opened with the
+ * app. The backend parses and queues each link or file, then emits
+ * `deep-link-received`; the queue is drained here once at startup (for
+ * whatever launched the app) and on every event after that.
+ * @module deepLinkHandler
+ */
+
+import { app } from './appContext.js';
+import { openProtoFile } from './grpcHandler.js';
+import { createBackendEventListener } from './streaming/streamSession.js';
+import { ConfirmDialog } from './ui/ConfirmDialog.js';
+import { toast } from './ui/Toast.js';
+
+/**
+ * Reveals the collection a link points at, by id or else by name
+ *
+ * @async
+ * @param {{collectionId: ?string, name: ?string}} link - Parsed link
+ * @returns {Promise<void>}
+ */
+async function openCollection(link) {
+    const controller = app.collectionController;
+    if (!controller) {
+        return;
+    }
+    const collections = await controller.service.loadCollections();
+    const collection = collections.find(c => c.id === link.collectionId)
+        || collections.find(c => link.name && c.name.toLowerCase() === link.name.toLowerCase());
+    if (!collection) {
+        toast.error(app.i18n?.t('deep_link.collection_not_found', { name: link.collectionId || link.name })
+            || `Collection not found: ${link.collectionId || link.name}`);
+        return;
+    }
+    controller.revealCollection(collection.id);
+}
+
+/**
+ * Imports the spec a link points at, after the user confirms: links can
+ * come from any web page
+ *
+ * @async
+ * @param {{url: string}} link - Parsed link
+ * @returns {Promise<void>}
+ */
+async function importSpec(link) {
+    const confirmed = await new ConfirmDialog().show(
+        app.i18n?.t('deep_link.import_confirm', { url: link.url }) || `Import the OpenAPI spec at ${link.url}?`,
+        {
+            title: app.i18n?.t('deep_link.import_title') || 'Import from Link',
+            confirmText: app.i18n?.t('deep_link.import') || 'Import',
+            dangerous: false
+        }
+    );
+    if (confirmed && app.collectionController) {
+        await app.collectionController.importOpenApiUrl(link.url);
+    }
+}
+
+/**
+ * Opens a new request tab pre-filled from a link. Nothing is sent.
+ *
+ * @async
+ * @param {{method: string, url: string, headers: Array<[string, string]>, body: ?string}} link - Parsed link
+ * @returns {Promise<void>}
+ */
+async function openRequest(link) {
+    if (!app.workspaceTabController) {
+        return;
+    }
+    let queryParams = {};
+    try {
+        queryParams = Object.fromEntries(new URL(link.url).searchParams);
+    } catch (error) {
+        void error;
+    }
+
+    let bodyMode = 'text';
+    if (link.body) {
+        try {
+            JSON.parse(link.body);
+            bodyMode = 'json';
+        } catch (error) {
+            void error;
+        }
+    }
+
+    await app.workspaceTabController.createNewTab({
+        name: `${link.method} ${link.url}`,
+        request: {
+            protocol: 'http',
+            url: link.url,
+            method: link.method,
+            pathParams: {},
+            queryParams,
+            headers: Object.fromEntries(link.headers),
+            body: { mode: link.body ? bodyMode : 'json', content: link.body || '' },
+            authType: 'none',
+            authConfig: {}
+        }
+    });
+}
+
+/**
+ * Imports a file opened with the app through the flow for its format. The
+ * import dialog still opens, preselected, so the destination can be chosen.
+ *
+ * @async
+ * @param {{path: string, kind: string}} link - Parsed file
+ * @returns {Promise<void>}
+ */
+async function importFile(link) {
+    const controller = app.collectionController;
+    switch (link.kind) {
+        case 'openapi':
+            await controller?.importOpenApiFile(link.path);
+            break;
+        case 'postman':
+            await controller?.importPostmanCollection(link.path);
+            break;
+        case 'postman_dump':
+            await controller?.importPostmanDump(link.path);
+            break;
+        case 'request_bundle':
+            await controller?.importRequestBundle(link.path);
+            break;
+        case 'har':
+            await controller?.importHarFile(link.path);
+            break;
+        case 'http':
+            await controller?.importHttpFile(link.path);
+            break;
+        case 'proto':
+            if (app.workspaceTabController) {
+                await app.workspaceTabController.createNewTab({ protocol: 'grpc' });
+                await openProtoFile(link.path);
+            }
+            break;
+        default:
+            break;
+    }
+}
+
+/**
+ * Imports a request shared as a `resonance://bundle` link; the import asks
+ * for confirmation first
+ *
+ * @async
+ * @param {{link: string}} link - Parsed link
+ * @returns {Promise<void>}
+ */
+async function importBundle(link) {
+    await app.collectionController?.importRequestBundle(null, link.link);
+}
+
+const ACTIONS = {
+    openCollection,
+    importSpec,
+    newRequest: openRequest,
+    importBundle,
+    importFile
+};
+
+/**
+ * Handles the links queued in the backend, oldest first
+ *
+ * @async
+ * @returns {Promise<void>}
+ */
+async function handlePendingLinks() {
+    let links = [];
+    try {
+        links = await window.backendAPI.deepLinks.takePending();
+    } catch (error) {
+        void error;
+        return;
+    }
+    for (const link of links) {
+        if (!link.action) {
+            toast.error(app.i18n?.t('deep_link.invalid', { error: link.error }) || `Could not open link: ${link.error}`);
+            continue;
+        }
+        try {
+            await ACTIONS[link.action.action](link.action);
+        } catch (error) {
+            toast.error(app.i18n?.t('deep_link.invalid', { error: error?.message || error })
+                || `Could not open link: ${error?.message || error}`);
+        }
+    }
+}
+
+const listenForDeepLinks = createBackendEventListener(
+    'deep-link-received',
+    () => !!window.backendAPI?.deepLinks,
+    () => handlePendingLinks()
+);
+
+/**
+ * Starts handling deep links. Call once the collections and tabs are loaded.
+ *
+ * @async
+ * @returns {Promise<void>}
+ */
+export async function initDeepLinkHandler() {
+    if (!window.backendAPI?.deepLinks) {
+        return;
+    }
+    await listenForDeepLinks();
+    await handlePendingLinks();
+}
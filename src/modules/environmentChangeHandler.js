@@ -0,0 +1,23 @@
+/**
+ * @fileoverview Reloads the environments when the backend changes one of
+ * their variables, e.g. a collection login flow storing its token. The
+ * backend emits `environment-changed` after writing the store.
+ * @module environmentChangeHandler
+ */
+
+import { app } from './appContext.js';
+import { createBackendEventListener } from './streaming/streamSession.js';
+
+function handleBackendEvent(event) {
+    const payload = event?.payload;
+    if (!payload?.environmentId) {
+        return;
+    }
+    app.environmentController?.service?.reload(payload.environmentId);
+}
+
+export const initEnvironmentChangeHandler = createBackendEventListener(
+    'environment-changed',
+    () => !!window.backendAPI?.loginFlow,
+    handleBackendEvent
+);
@@ -1,7 +1,9 @@
 /**
- * @fileoverview Manages the form-data, URL-encoded, and binary body modes.
+ * @fileoverview Manages the form-data, URL-encoded, binary and SOAP body modes.
  * Form modes render ordered rows ({ key, value, type, filePath, contentType,
  * enabled }); form-data rows can be file parts picked via the native dialog.
+ * The SOAP mode holds an operation, its namespace, SOAPAction, SOAP version
+ * and JSON parameters.
  * @module formBodyManager
  */
 
@@ -14,6 +16,13 @@ export class FormBodyManager {
         this.urlencodedList = document.getElementById('urlencoded-list');
         this.binaryFilePathInput = document.getElementById('binary-file-path');
         this.binaryContentTypeInput = document.getElementById('binary-content-type');
+        this.soapInputs = {
+            operation: document.getElementById('soap-operation'),
+            namespace: document.getElementById('soap-namespace'),
+            soapAction: document.getElementById('soap-action'),
+            version: document.getElementById('soap-version'),
+            params: document.getElementById('soap-params')
+        };
     }
 
     initialize() {
@@ -39,9 +48,10 @@ export class FormBodyManager {
             list?.addEventListener('input', () => this._markTabModified());
             list?.addEventListener('change', () => this._markTabModified());
         });
-        [this.binaryFilePathInput, this.binaryContentTypeInput].forEach((input) => {
+        [this.binaryFilePathInput, this.binaryContentTypeInput, ...Object.values(this.soapInputs)].forEach((input) => {
             input?.addEventListener('input', () => this._markTabModified());
         });
+        this.soapInputs.version?.addEventListener('change', () => this._markTabModified());
 
         this._addRow(this.formdataList, {}, true);
         this._addRow(this.urlencodedList, {}, false);
@@ -82,6 +92,38 @@ export class FormBodyManager {
         }
     }
 
+    /**
+     * @returns {{operation: string, namespace: string, soapAction: string, version: string, params: string}}
+     */
+    getSoapBody() {
+        return {
+            operation: this.soapInputs.operation?.value.trim() || '',
+            namespace: this.soapInputs.namespace?.value.trim() || '',
+            soapAction: this.soapInputs.soapAction?.value.trim() || '',
+            version: this.soapInputs.version?.value || '1.1',
+            params: this.soapInputs.params?.value || ''
+        };
+    }
+
+    setSoapBody(data) {
+        const { operation, namespace, soapAction, version, params } = this.soapInputs;
+        if (operation) {
+            operation.value = data?.operation || '';
+        }
+        if (namespace) {
+            namespace.value = data?.namespace || '';
+        }
+        if (soapAction) {
+            soapAction.value = data?.soapAction || '';
+        }
+        if (version) {
+            version.value = data?.version === '1.2' ? '1.2' : '1.1';
+        }
+        if (params) {
+            params.value = data?.params || '';
+        }
+    }
+
     _populate(list, fields, allowFile) {
         if (!list) {
             return;
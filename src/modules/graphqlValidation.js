@@ -0,0 +1,53 @@
+/**
+ * @fileoverview Pre-send validation of GraphQL documents: the backend checks
+ * the query and variables against the schema cached for the endpoint, so
+ * typos are reported without a round-trip to the server.
+ * @module graphqlValidation
+ */
+
+import { app } from './appContext.js';
+
+/**
+ * Validates a GraphQL query and its variables against the cached schema
+ *
+ * @async
+ * @param {string} url - Endpoint URL the schema is cached under
+ * @param {string} query - Query document
+ * @param {Object} variables - Parsed variables
+ * @param {string|null} operationName - Operation to run
+ * @returns {Promise<Array<{message: string, line: ?number, column: ?number, path: ?string}>>}
+ *   Validation errors; empty when valid or when validation could not run
+ */
+export async function validateGraphQLDocument(url, query, variables, operationName) {
+    try {
+        const { errors } = await window.backendAPI.graphqlValidate({
+            url,
+            query,
+            variables,
+            operationName: operationName || null
+        });
+        return errors || [];
+    } catch (error) {
+        void error;
+        return [];
+    }
+}
+
+/**
+ * Formats validation errors for the response view, one per line with its
+ * position in the query or path in the variables
+ *
+ * @param {Array<Object>} errors - Validation errors
+ * @returns {string}
+ */
+export function formatGraphQLValidationErrors(errors) {
+    const heading = app.i18n?.t('graphql_validation.heading')
+        || 'The request was not sent: the query does not match the schema.';
+    const lines = errors.map(error => {
+        if (error.line) {
+            return `${error.line}:${error.column}  ${error.message}`;
+        }
+        return error.path ? `${error.path}  ${error.message}` : error.message;
+    });
+    return `${heading}\n\n${lines.join('\n')}`;
+}
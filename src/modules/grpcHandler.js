@@ -1,4 +1,5 @@
 import { app } from './appContext.js';
+import { getCurrentEndpoint } from './state/currentEndpoint.js';
 import {
     grpcTargetInput,
     grpcTlsCheckbox,
@@ -174,9 +175,9 @@ export function grpcHostForCertLookup(target) {
  * Build the TLS options object for gRPC backend commands. Skip-verify follows
  * the global "Verify SSL certificates" setting (same as HTTP requests), and
  * the client certificate/CA resolve from the per-host certificate store.
+ * TLS itself follows the gRPC panel's checkbox unless `useTls` is given.
  */
-async function buildTlsOptions(target) {
-    const useTls = getUseTls();
+export async function buildTlsOptions(target, useTls = getUseTls()) {
     let skipVerify = false;
     try {
         const settings = getSettingsCache() || await window.backendAPI.settings.get();
@@ -301,6 +302,7 @@ export async function handleGrpcSend() {
 
     const metadata = getMetadata();
     const tls = await buildTlsOptions(target);
+    const collectionId = getCurrentEndpoint()?.collectionId ?? null;
     const flags = methodFlagsCache.get(fullMethod);
     const isStreaming = !!(flags && (flags.serverStreaming || flags.clientStreaming));
 
@@ -312,6 +314,7 @@ export async function handleGrpcSend() {
             metadata,
             tls,
             protoPath: protoFileMode ? loadedProtoPath : null,
+            collectionId,
             canSend: !!flags.clientStreaming
         });
         return;
@@ -329,7 +332,8 @@ export async function handleGrpcSend() {
                 requestJson,
                 metadata,
                 deadlineMs: 30000,
-                tls
+                tls,
+                collectionId
             });
         } else {
             result = await window.backendAPI.grpc.invokeUnary({
@@ -338,7 +342,8 @@ export async function handleGrpcSend() {
                 requestJson,
                 metadata,
                 deadlineMs: 30000,
-                tls
+                tls,
+                collectionId
             });
         }
 
@@ -552,14 +557,21 @@ async function onLoadProtoFile() {
             return;
         }
 
-        await loadProtoFile(protoPath);
-        
-        updateProtoUI(true, protoPath);
+        await openProtoFile(protoPath);
     } catch (error) {
         updateStatusDisplay(`Failed to load proto: ${error.message || String(error)}`, null);
     }
 }
 
+/**
+ * Load a proto file into the current gRPC tab, as if picked with the load button
+ * @param {string} protoPath - Path to the .proto file
+ */
+export async function openProtoFile(protoPath) {
+    await loadProtoFile(protoPath);
+    updateProtoUI(true, protoPath);
+}
+
 function onClearProtoFile() {
     clearProtoFile();
     updateProtoUI(false, null);
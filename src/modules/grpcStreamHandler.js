@@ -91,7 +91,7 @@ export function hasActiveStream(tabId) {
 
 /**
  * Start a new stream or push another message into an open client/bidi stream.
- * @param {object} opts - {target, fullMethod, requestJson, metadata, tls, protoPath, canSend}
+ * @param {object} opts - {target, fullMethod, requestJson, metadata, tls, protoPath, collectionId, canSend}
  *   canSend=true for client-streaming or bidi (the stream accepts additional messages).
  */
 export async function startOrSend(opts) {
@@ -130,14 +130,20 @@ export async function startOrSend(opts) {
     displayResponseWithLineNumbersForTab('', null, tabId);
 
     try {
-        await window.backendAPI.grpc.streamStart({
+        // Server-streaming calls take one request, so they go through the
+        // dedicated command; client and bidi streams stay open for sends
+        const start = opts.canSend
+            ? window.backendAPI.grpc.streamStart
+            : window.backendAPI.grpc.invokeServerStream;
+        await start({
             tabId,
             target: opts.target,
             fullMethod: opts.fullMethod,
             requestJson: opts.requestJson,
             metadata: opts.metadata || {},
             tls: opts.tls || { useTls: false, skipVerify: false },
-            protoPath: opts.protoPath || null
+            protoPath: opts.protoPath || null,
+            collectionId: opts.collectionId || null
         });
         if (opts.canSend && opts.requestJson !== undefined && opts.requestJson !== null) {
             await session.append(tabId, 'SENT', formatMessage(opts.requestJson));
@@ -0,0 +1,115 @@
+/**
+ * @fileoverview Autocomplete for request header rows. Header names, their
+ * descriptions and values (common ones plus ones sent before in this
+ * workspace) come from the backend and are offered through native datalists.
+ * @module headerSuggestions
+ */
+
+import { headersList } from './domElements.js';
+
+/** Metadata is refetched after this long, so newly sent values show up. */
+const METADATA_TTL_MS = 60 * 1000;
+
+let metadata = [];
+let loadedAt = 0;
+let loading = null;
+
+/**
+ * Fetches the header metadata unless a recent copy is cached
+ *
+ * @async
+ * @returns {Promise<Array<{name: string, description?: string, values: string[], seenValues: string[]}>>}
+ */
+async function loadMetadata() {
+    if (Date.now() - loadedAt < METADATA_TTL_MS) {
+        return metadata;
+    }
+    if (!loading) {
+        loading = window.backendAPI.headersMetadata()
+            .then((headers) => {
+                metadata = headers;
+                loadedAt = Date.now();
+                return metadata;
+            })
+            .catch(() => metadata)
+            .finally(() => {
+                loading = null;
+            });
+    }
+    return loading;
+}
+
+function findHeader(name) {
+    const wanted = name.trim().toLowerCase();
+    return wanted ? metadata.find((header) => header.name.toLowerCase() === wanted) : undefined;
+}
+
+function ensureDatalist(id) {
+    let datalist = document.getElementById(id);
+    if (!datalist) {
+        datalist = document.createElement('datalist');
+        datalist.id = id;
+        document.body.appendChild(datalist);
+    }
+    return datalist;
+}
+
+function fillDatalist(datalist, options) {
+    datalist.replaceChildren(...options.map(({ value, label }) => {
+        const option = document.createElement('option');
+        option.value = value;
+        if (label) {
+            option.label = label;
+        }
+        return option;
+    }));
+}
+
+async function offerNames(keyInput) {
+    await loadMetadata();
+    const datalist = ensureDatalist('header-name-suggestions');
+    fillDatalist(datalist, metadata.map((header) => ({ value: header.name, label: header.description })));
+    keyInput.setAttribute('list', datalist.id);
+}
+
+async function offerValues(valueInput) {
+    const keyInput = valueInput.closest('.key-value-row')?.querySelector('.key-input');
+    await loadMetadata();
+    const header = keyInput ? findHeader(keyInput.value) : undefined;
+    const datalist = ensureDatalist('header-value-suggestions');
+    const values = header ? [...new Set([...header.seenValues, ...header.values])] : [];
+    fillDatalist(datalist, values.map((value) => ({
+        value,
+        label: header.seenValues.includes(value) ? 'Used before' : ''
+    })));
+    valueInput.setAttribute('list', datalist.id);
+}
+
+function describeHeader(keyInput) {
+    keyInput.title = findHeader(keyInput.value)?.description || '';
+}
+
+/**
+ * Wires header name/value suggestions into the request headers list
+ *
+ * @returns {void}
+ */
+export function initHeaderSuggestions() {
+    if (!headersList || !window.backendAPI?.headersMetadata) {
+        return;
+    }
+
+    headersList.addEventListener('focusin', (event) => {
+        if (event.target.classList.contains('key-input')) {
+            void offerNames(event.target);
+        } else if (event.target.classList.contains('value-input')) {
+            void offerValues(event.target);
+        }
+    });
+
+    headersList.addEventListener('input', (event) => {
+        if (event.target.classList.contains('key-input')) {
+            describeHeader(event.target);
+        }
+    });
+}
@@ -0,0 +1,126 @@
+/**
+ * @fileoverview Links tab of the response panel: the backend extracts the
+ * hypermedia links of a response (HAL, JSON:API, Siren, `Link` header) and
+ * each one opens as a new request tab, so a hypermedia API can be browsed
+ * without copying URLs by hand.
+ * @module hypermediaLinks
+ */
+
+import { app } from './appContext.js';
+
+/**
+ * Extracts the hypermedia links of a response
+ *
+ * @async
+ * @param {string} url - Request URL, relative links are resolved against it
+ * @param {Object} result - Result of `sendApiRequest`
+ * @returns {Promise<Array<Object>>} The links, empty when extraction failed
+ */
+export async function fetchHypermediaLinks(url, result) {
+    try {
+        return await window.backendAPI.hypermediaLinks(url, result.data ?? null, result.headers || {});
+    } catch (error) {
+        void error;
+        return [];
+    }
+}
+
+/**
+ * Resets the Links tab to its empty state
+ *
+ * @param {HTMLElement|null} container - Links display element
+ * @returns {void}
+ */
+export function clearHypermediaLinks(container) {
+    if (!container) {
+        return;
+    }
+    const empty = document.createElement('p');
+    empty.className = 'no-data';
+    empty.textContent = app.i18n?.t('hypermedia_links.no_data') || 'The response advertises no links';
+    container.replaceChildren(empty);
+}
+
+/**
+ * Lists the links of a response in the Links tab
+ *
+ * @param {HTMLElement|null} container - Links display element
+ * @param {Array<Object>|null} links - Links returned by the backend
+ * @returns {void}
+ */
+export function displayHypermediaLinks(container, links) {
+    if (!container) {
+        return;
+    }
+    if (!links?.length) {
+        clearHypermediaLinks(container);
+        return;
+    }
+
+    const list = document.createElement('ul');
+    list.className = 'hypermedia-links';
+    links.forEach(link => {
+        const item = document.createElement('li');
+        item.className = 'hypermedia-link';
+
+        const method = document.createElement('span');
+        method.className = 'hypermedia-link-method';
+        method.textContent = link.method;
+
+        const rel = document.createElement('span');
+        rel.className = 'hypermedia-link-rel';
+        rel.textContent = link.rel || link.source;
+        if (link.title) {
+            rel.title = link.title;
+        }
+
+        const href = document.createElement('span');
+        href.className = 'hypermedia-link-href';
+        href.textContent = link.href;
+        href.title = link.templated
+            ? app.i18n?.t('hypermedia_links.templated') || 'URI template: fill in the variables before sending'
+            : link.href;
+
+        const open = document.createElement('button');
+        open.className = 'btn btn-secondary btn-xs';
+        open.textContent = app.i18n?.t('hypermedia_links.open') || 'Open';
+        open.addEventListener('click', () => {
+            openLink(link).catch(() => { });
+        });
+
+        item.append(method, rel, href, open);
+        list.appendChild(item);
+    });
+
+    container.replaceChildren(list);
+}
+
+/**
+ * Opens a link as a new request tab, keeping the headers and auth of the
+ * request it was found in
+ *
+ * @async
+ * @param {Object} link - Link returned by the backend
+ * @returns {Promise<void>}
+ */
+async function openLink(link) {
+    const controller = app.workspaceTabController;
+    if (!controller) {
+        return;
+    }
+    const source = (await controller.service.getActiveTab())?.request || {};
+    await controller.createNewTab({
+        name: `${link.method} ${link.href}`,
+        request: {
+            protocol: 'http',
+            url: link.href,
+            method: link.method,
+            pathParams: {},
+            queryParams: {},
+            headers: { ...(source.headers || {}) },
+            body: { mode: 'json', content: '' },
+            authType: source.authType || 'none',
+            authConfig: { ...(source.authConfig || {}) }
+        }
+    });
+}
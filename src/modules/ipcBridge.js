@@ -18,6 +18,23 @@ if (isTauri) {
         app: {
             getVersion: () => invoke('app_get_version')
         },
+        bulkEdit: {
+            parse: (text, location) => invoke('bulk_edit_parse', { text, location }),
+            serialize: (parameters) => invoke('bulk_edit_serialize', { parameters })
+        },
+        commandPalette: {
+            refresh: () => invoke('command_palette_refresh'),
+            search: (query, limit = null) => invoke('command_palette_search', { query, limit })
+        },
+        crashReports: {
+            reportFrontendError: (message, stack = null) => invoke('crash_report_frontend_error', { message, stack }),
+            list: () => invoke('crash_reports_list'),
+            get: (id) => invoke('crash_report_get', { id }),
+            clear: () => invoke('crash_reports_clear')
+        },
+        deepLinks: {
+            takePending: () => invoke('deep_link_take_pending')
+        },
         logger: {
             error: (_scope, _message, _meta) => {
                 void _scope;
@@ -47,11 +64,38 @@ if (isTauri) {
         },
         sendApiRequest: (requestOptions) => invoke('send_api_request', { requestOptions }),
         cancelApiRequest: () => invoke('cancel_api_request'),
+        followPagination: (requestOptions, options) =>
+            invoke('pagination_follow', { requestOptions, options }),
+        replayHistoryEntry: (id, confirmed = false) => invoke('replay_history_entry', { id, confirmed }),
+        searchResponseBodies: (query, { regex = false, caseSensitive = false, includeExamples = true, limit = null } = {}) =>
+            invoke('search_response_bodies', { query, regex, caseSensitive, includeExamples, limit }),
         pickUploadFile: () => invoke('pick_upload_file'),
+        auditSecurityHeaders: (url, headers, setCookies) =>
+            invoke('audit_security_headers', { url, headers, setCookies }),
+        headersMetadata: () => invoke('headers_metadata'),
+        hypermediaLinks: (url, body, headers) => invoke('hypermedia_links', { url, body, headers }),
+        saveResponseToFile: ({ responseKey = null, historyId = null } = {}) =>
+            invoke('save_response_to_file', { responseKey, historyId }),
+        jsonDocument: {
+            open: (id, data, options = {}) => invoke('json_document_open', {
+                id,
+                data,
+                indent: options.indent ?? null,
+                pageSize: options.pageSize ?? null
+            }),
+            page: (id, pointer, offset = 0, limit = null) =>
+                invoke('json_document_page', { id, pointer, offset, limit })
+        },
         websocket: {
             send: (request) => invoke('websocket_send', { request }),
             close: (tabId) => invoke('websocket_close', { tabId })
         },
+        soap: {
+            buildEnvelope: (request) => invoke('soap_build_envelope', { request }),
+            unwrapResponse: (body) => invoke('soap_unwrap_response', { body })
+        },
+        graphqlValidate: ({ url, query, variables = {}, operationName = null }) =>
+            invoke('graphql_validate', { url, query, variables, operationName }),
         graphqlSubscription: {
             send: (request) => invoke('graphql_subscription_send', { request }),
             close: (tabId) => invoke('graphql_subscription_close', { tabId })
@@ -69,11 +113,18 @@ if (isTauri) {
             get: (key) => invoke('store_get', { key }),
             set: (key, value) => invoke('store_set', { key, value })
         },
+        cookies: {
+            list: (environmentId = null, domain = null) => invoke('cookies_list', { environmentId, domain }),
+            set: (cookie) => invoke('cookies_set', { cookie }),
+            delete: (id) => invoke('cookies_delete', { id }),
+            clearDomain: (domain, environmentId = null) => invoke('cookies_clear_domain', { domain, environmentId })
+        },
         secrets: {
             keychainAvailable: () => invoke('secret_keychain_available'),
             get: (account) => invoke('secret_get', { account }),
             set: (account, value) => invoke('secret_set', { account, value }),
-            delete: (account) => invoke('secret_delete', { account })
+            delete: (account) => invoke('secret_delete', { account }),
+            mask: (value, collectionId = null) => invoke('secret_mask', { value, collectionId })
         },
         collections: {
             list: () => invoke('collections_list'),
@@ -92,57 +143,188 @@ if (isTauri) {
             pickDirectory: () => invoke('collections_pick_directory'),
             pickImportFile: (importKind) => invoke('collections_pick_import_file', { importKind }),
             importOpenApiFile: (filePath = null, storageParentPath = null) => invoke('import_openapi_file', { filePath, storageParentPath }),
+            importOpenApiUrl: (url, storageParentPath = null) => invoke('import_openapi_url', { url, storageParentPath }),
+            checkSpecSync: (collectionId) => invoke('spec_sync_check', { collectionId }),
+            acceptSpecSync: (collectionId) => invoke('spec_sync_accept', { collectionId }),
             importPostmanCollection: (filePath = null, storageParentPath = null) => invoke('import_postman_collection', { filePath, storageParentPath }),
+            importPostmanDump: (filePath = null, storageParentPath = null) => invoke('import_postman_dump', { filePath, storageParentPath }),
+            importHarFile: (filePath = null, storageParentPath = null) => invoke('import_har_file', { filePath, storageParentPath }),
+            importHttpFile: (filePath = null, storageParentPath = null) => invoke('import_http_file', { filePath, storageParentPath }),
+            importCurl: (command) => invoke('import_curl', { command }),
             importPostmanEnvironment: () => invoke('import_postman_environment'),
             exportOpenApi: (collectionId, format) => invoke('export_openapi', { collectionId, format }),
-            exportPostman: (collectionId) => invoke('export_postman', { collectionId })
+            exportPostman: (collectionId) => invoke('export_postman', { collectionId }),
+            exportHttpFile: (collectionId) => invoke('export_http_file', { collectionId }),
+            exportShellScript: (collectionId, folderId = null) => invoke('export_shell_script', { collectionId, folderId }),
+            duplicate: (collectionId) => invoke('collection_duplicate', { collectionId }),
+            duplicateFolder: (collectionId, folderId) => invoke('folder_duplicate', { collectionId, folderId }),
+            duplicateEndpoint: (collectionId, endpointId) => invoke('endpoint_duplicate', { collectionId, endpointId }),
+            listVersions: (collectionId) => invoke('collection_history_list', { collectionId }),
+            diffVersions: (collectionId, versionId, compareTo = null) => invoke('collection_history_diff', { collectionId, versionId, compareTo }),
+            rollback: (collectionId, versionId) => invoke('collection_history_rollback', { collectionId, versionId })
+        },
+        electronMigration: {
+            detect: () => invoke('electron_migration_detect'),
+            run: () => invoke('electron_migration_run'),
+            dismiss: () => invoke('electron_migration_dismiss')
+        },
+        endpoints: {
+            get: (collectionId, endpointId) => invoke('endpoint_get', { collectionId, endpointId }),
+            update: (collectionId, endpointId, data) => invoke('endpoint_update', { collectionId, endpointId, data }),
+            saveDraft: (collectionId, endpointId, data) => invoke('endpoint_save_draft', { collectionId, endpointId, data }),
+            discardDraft: (collectionId, endpointId) => invoke('endpoint_discard_draft', { collectionId, endpointId }),
+            listDrafts: (collectionId = null) => invoke('endpoint_drafts_list', { collectionId }),
+            listExamples: (collectionId, endpointId) => invoke('endpoint_examples_list', { collectionId, endpointId }),
+            saveExample: (collectionId, endpointId, example) => invoke('endpoint_example_save', { collectionId, endpointId, example }),
+            deleteExample: (collectionId, endpointId, exampleId) => invoke('endpoint_example_delete', { collectionId, endpointId, exampleId }),
+            inferResponseSchema: (collectionId, endpointId) => invoke('endpoint_infer_response_schema', { collectionId, endpointId })
+        },
+        trash: {
+            list: () => invoke('trash_list'),
+            trashEndpoint: (collectionId, endpointId) => invoke('trash_endpoint', { collectionId, endpointId }),
+            restore: (entryId) => invoke('trash_restore', { entryId }),
+            purge: (entryId = null) => invoke('trash_purge', { entryId })
         },
         environments: {
-            saveJsonExport: (defaultFileName, content) => invoke('save_json_export', { defaultFileName, content })
+            saveJsonExport: (defaultFileName, content) => invoke('save_json_export', { defaultFileName, content }),
+            encrypt: (environmentId, passphrase) => invoke('environment_encrypt', { environmentId, passphrase }),
+            unlock: (environmentId, passphrase) => invoke('environment_unlock', { environmentId, passphrase }),
+            lock: (environmentId) => invoke('environment_lock', { environmentId }),
+            unlockedVariables: (environmentId) => invoke('environment_unlocked_variables', { environmentId }),
+            saveEncrypted: (environmentId, variables) => invoke('environment_save_encrypted', { environmentId, variables }),
+            decrypt: (environmentId, passphrase) => invoke('environment_decrypt', { environmentId, passphrase }),
+            usageReport: () => invoke('variables_usage_report')
         },
         docs: {
-            save: (defaultFileName, content, mimeType) => invoke('save_documentation', { defaultFileName, content, mimeType })
+            save: (defaultFileName, content, mimeType) => invoke('save_documentation', { defaultFileName, content, mimeType }),
+            exportMarkdown: (collectionId, format = null) => invoke('export_markdown', { collectionId, format })
         },
         grpc: {
             listServices: (target, tls = null) => invoke('grpc_reflection_list_services', { target, tls }),
             listMethods: (target, serviceName, tls = null) => invoke('grpc_reflection_list_methods', { target, serviceName, tls }),
             invokeUnary: (request) => invoke('grpc_invoke_unary', { request }),
             getInputSkeleton: (target, fullMethod, tls = null) => invoke('grpc_get_input_skeleton', { target, fullMethod, tls }),
+            captureDocs: (collectionId, target, tls = null) => invoke('grpc_docs_capture', { collectionId, target, tls }),
+            getDocs: (collectionId) => invoke('grpc_docs_get', { collectionId }),
+            deleteDocs: (collectionId) => invoke('grpc_docs_delete', { collectionId }),
             selectProtoFile: () => invoke('grpc_select_proto_file'),
             parseProtoFile: (protoPath, includePaths = null) => invoke('grpc_parse_proto_file', { protoPath, includePaths }),
             protoGetInputSkeleton: (protoPath, fullMethod) => invoke('grpc_proto_get_input_skeleton', { protoPath, fullMethod }),
             protoInvokeUnary: (protoPath, request) => invoke('grpc_proto_invoke_unary', { protoPath, request }),
             listLoadedProtos: () => invoke('grpc_list_loaded_protos'),
             unloadProto: (protoPath) => invoke('grpc_unload_proto', { protoPath }),
+            listProtoMessages: () => invoke('grpc_proto_list_messages'),
+            decodeProtobufResponse: (responseKey, messageType) =>
+                invoke('grpc_proto_decode_response', { responseKey, messageType }),
             streamStart: (request) => invoke('grpc_stream_start', { request }),
             streamSend: (tabId, messageJson) => invoke('grpc_stream_send', { tabId, messageJson }),
-            streamCancel: (tabId) => invoke('grpc_stream_cancel', { tabId })
+            streamCancel: (tabId) => invoke('grpc_stream_cancel', { tabId }),
+            invokeServerStream: (request) => invoke('grpc_invoke_server_stream', { request })
         },
         settings: {
             get: () => invoke('settings_get'),
-            set: (settings) => invoke('settings_set', { settings })
+            set: (settings) => invoke('settings_set', { settings }),
+            exportToFile: () => invoke('settings_export'),
+            importFromFile: (filePath = null) => invoke('settings_import', { filePath })
         },
         proxySettings: {
             get: () => invoke('proxy_get'),
             set: (settings) => invoke('proxy_set', { settings }),
             test: () => invoke('proxy_test')
         },
+        offlineMode: {
+            get: () => invoke('offline_get'),
+            set: (enabled) => invoke('offline_set', { enabled })
+        },
+        trustedHosts: {
+            list: () => invoke('trusted_hosts_list'),
+            inspect: (url) => invoke('trusted_hosts_inspect', { url }),
+            add: (host, fingerprint, remember = false) =>
+                invoke('trusted_hosts_add', { host, fingerprint, remember }),
+            remove: (host, fingerprint) => invoke('trusted_hosts_remove', { host, fingerprint })
+        },
+        diagnostics: {
+            ping: (target, method, port = null, count = null) =>
+                invoke('network_ping', { target, method, port, count, timeoutMs: null }),
+            traceroute: (target, maxHops = null) => invoke('network_traceroute', { target, maxHops }),
+            dnsLookup: (target, recordTypes) => invoke('network_dns_lookup', { target, recordTypes }),
+            prewarm: (url, options = null) => invoke('connection_prewarm', { url, options }),
+            tlsProbe: (target, port = null) => invoke('network_tls_probe', { target, port })
+        },
+        requestBundles: {
+            export: (collectionId, endpointId) => invoke('export_request_bundle', { collectionId, endpointId }),
+            link: (collectionId, endpointId) => invoke('request_bundle_link', { collectionId, endpointId }),
+            open: (filePath = null, link = null) => invoke('request_bundle_open', { filePath, link }),
+            import: (bundle) => invoke('request_bundle_import', { bundle })
+        },
+        usageStats: {
+            query: (groupBy, days = null) => invoke('usage_stats_query', { groupBy, days }),
+            clear: () => invoke('usage_stats_clear')
+        },
         certificates: {
             pickFile: (kind) => invoke('pick_certificate_file', { kind })
         },
+        codegen: {
+            generate: (requestOptions, language) => invoke('codegen_generate', { requestOptions, language })
+        },
         mockServer: {
             start: (settings, collections) => invoke('mock_server_start', { settings, collections }),
             stop: () => invoke('mock_server_stop'),
             status: () => invoke('mock_server_status'),
-            logs: (limit) => invoke('mock_server_logs', { limit }),
+            logs: (limit, offset = 0) => invoke('mock_server_logs', { limit, offset }),
             clearLogs: () => invoke('mock_server_clear_logs'),
-            reloadSettings: () => invoke('mock_server_reload_settings')
+            reloadSettings: (settings) => invoke('mock_server_reload_settings', { settings }),
+            setEndpointEnabled: (collectionId, endpointId, enabled) => invoke('mock_server_set_endpoint_enabled', { collectionId, endpointId, enabled })
+        },
+        webhookReceiver: {
+            start: (config) => invoke('webhook_receiver_start', { config }),
+            stop: () => invoke('webhook_receiver_stop'),
+            status: () => invoke('webhook_receiver_status'),
+            requests: () => invoke('webhook_receiver_requests'),
+            clear: () => invoke('webhook_receiver_clear')
         },
         scripts: {
             get: (collectionId, endpointId) => invoke('script_get', { collectionId, endpointId }),
             save: (collectionId, endpointId, scripts) => invoke('script_save', { collectionId, endpointId, scripts }),
-            executePreRequest: (scriptData) => invoke('script_execute_pre_request', { scriptData }),
-            executeTest: (scriptData) => invoke('script_execute_test', { scriptData })
+            generateTests: (collectionId, endpointId) => invoke('script_generate_tests', { collectionId, endpointId }),
+            executePreRequest: (scriptData, collectionId = null) => invoke('script_execute_pre_request', { scriptData, collectionId }),
+            executeTest: (scriptData, collectionId = null) => invoke('script_execute_test', { scriptData, collectionId }),
+            getGlobal: () => invoke('global_scripts_get'),
+            setGlobal: (scripts) => invoke('global_scripts_set', { scripts })
+        },
+        runner: {
+            runCollection: (options) => invoke('run_collection', { options }),
+            cancel: (runId) => invoke('run_cancel', { runId }),
+            exportReport: (result, format) => invoke('runner_export_report', { result, format }),
+            listHistory: (collectionId) => invoke('runner_history_list', { collectionId }),
+            getRun: (runId) => invoke('runner_history_get', { runId }),
+            deleteRun: (runId) => invoke('runner_history_delete', { runId }),
+            diffRuns: (baseRunId, compareRunId) =>
+                invoke('runner_history_diff', { baseRunId, compareRunId }),
+            sendFolder: (collectionId, folderId, parallel = false, confirmed = false, endpointIds = null) =>
+                invoke('send_folder', { collectionId, folderId, parallel, confirmed, endpointIds }),
+            healthCheck: (options) => invoke('health_check', { options })
+        },
+        monitors: {
+            list: () => invoke('monitors_list'),
+            save: (monitor) => invoke('monitor_save', { monitor }),
+            delete: (monitorId) => invoke('monitor_delete', { monitorId }),
+            results: (monitorId) => invoke('monitor_results', { monitorId }),
+            runNow: (monitorId) => invoke('monitor_run_now', { monitorId })
+        },
+        sync: {
+            getSettings: () => invoke('sync_settings_get'),
+            setSettings: (settings) => invoke('sync_settings_set', { settings }),
+            push: (force = false) => invoke('sync_push', { force }),
+            pull: (force = false) => invoke('sync_pull', { force })
+        },
+        workspaces: {
+            list: () => invoke('workspaces_list'),
+            create: (name) => invoke('workspace_create', { name }),
+            rename: (workspaceId, name) => invoke('workspace_rename', { workspaceId, name }),
+            // Callers reload the window afterwards so every view reads the new workspace
+            switch: (workspaceId) => invoke('workspace_switch', { workspaceId }),
+            delete: (workspaceId) => invoke('workspace_delete', { workspaceId })
         },
         updater: {
             check: () => invoke('updater_check'),
@@ -155,7 +337,29 @@ if (isTauri) {
             storePkceVerifier: (stateParam, codeVerifier) => invoke('oauth2_store_pkce_verifier', { stateParam, codeVerifier }),
             getPkceVerifier: (stateParam) => invoke('oauth2_get_pkce_verifier', { stateParam }),
             buildAuthorizationUrl: (params) => invoke('oauth2_build_authorization_url', { params }),
-            getToken: (config) => invoke('oauth2_get_token', { config })
+            getToken: (config) => invoke('oauth2_get_token', { config }),
+            deviceStart: (config) => invoke('oauth2_device_start', { config }),
+            devicePoll: (flowId) => invoke('oauth2_device_poll', { flowId }),
+            deviceCancel: (flowId) => invoke('oauth2_device_cancel', { flowId }),
+            discover: (issuer) => invoke('oauth2_discover', { issuer }),
+            awaitRedirect: (redirectUri, expectedState) => invoke('oauth2_await_redirect', { redirectUri, expectedState }),
+            cancelRedirect: () => invoke('oauth2_cancel_redirect'),
+            tokenSave: (config, token) => invoke('oauth2_token_save', { config, token }),
+            tokenGet: (config) => invoke('oauth2_token_get', { config }),
+            listTokens: () => invoke('oauth2_tokens_list'),
+            revokeToken: (profile) => invoke('oauth2_token_revoke', { profile })
+        },
+        commandAuth: {
+            token: (config, collectionId = null) => invoke('auth_command_token', { config, collectionId }),
+            clearCache: () => invoke('auth_command_clear_cache')
+        },
+        jwt: {
+            decode: (token, key = null) => invoke('jwt_decode', { token, key }),
+            generate: (options) => invoke('jwt_generate', { options })
+        },
+
+        loginFlow: {
+            run: (collectionId) => invoke('login_flow_run', { collectionId })
         }
     };
     
@@ -0,0 +1,46 @@
+/**
+ * @fileoverview Large JSON responses are formatted in the backend and shown
+ * without syntax highlighting, and their preview tree is paged from the
+ * backend node by node, so multi-megabyte bodies don't freeze the webview.
+ * @module largeJson
+ */
+
+/** Bodies from this size on are treated as large */
+export const LARGE_JSON_BYTES = 2 * 1024 * 1024;
+
+/**
+ * Whether a body of `size` bytes (or characters) counts as large
+ *
+ * @param {number|null|undefined} size - Body size
+ * @returns {boolean}
+ */
+export function isLargeJson(size) {
+    return typeof size === 'number' && size >= LARGE_JSON_BYTES;
+}
+
+/**
+ * Formats a response body for the code view. Structured bodies are
+ * pretty-printed, in the backend when they are large; that also keeps the
+ * document open under `tabId` for the preview tree. Text bodies are shown
+ * as they are.
+ *
+ * @async
+ * @param {*} data - Response body as received from the backend
+ * @param {number|null} size - Body size in bytes
+ * @param {string|null} tabId - Workspace tab the response belongs to
+ * @returns {Promise<{text: string, languageHint: (string|undefined)}>}
+ */
+export async function formatResponseBody(data, size, tabId) {
+    if (typeof data === 'string') {
+        return { text: data, languageHint: undefined };
+    }
+    if (tabId && isLargeJson(size) && window.backendAPI?.jsonDocument) {
+        try {
+            const { formatted } = await window.backendAPI.jsonDocument.open(tabId, data);
+            return { text: formatted, languageHint: 'json' };
+        } catch (error) {
+            void error;
+        }
+    }
+    return { text: JSON.stringify(data, null, 2), languageHint: 'json' };
+}
@@ -97,8 +97,10 @@ function createMetric(label, value) {
  * @param {HTMLElement} container - Container element
  * @param {Object} timings - Timing data object
  * @param {number} size - Response size in bytes
+ * @param {Object} [remoteAddress] - Peer address of the response ({ip, port, family})
+ * @param {Object} [traceIds] - Generated correlation ids the request was sent with ({requestId, traceparent})
  */
-export function displayPerformanceMetrics(container, timings, size) {
+export function displayPerformanceMetrics(container, timings, size, remoteAddress = null, traceIds = null) {
     if (!container) {
         return;
     }
@@ -134,6 +136,16 @@ export function displayPerformanceMetrics(container, timings, size) {
         metricsGrid.appendChild(createMetric('Transfer Speed', transferSpeed !== '-' ? `${transferSpeed} KB/s` : '-'));
     }
 
+    if (remoteAddress) {
+        const family = remoteAddress.family === 'ipv6' ? 'IPv6' : 'IPv4';
+        metricsGrid.appendChild(createMetric('Remote Address', `${remoteAddress.ip} (${family})`));
+    }
+
+    if (traceIds) {
+        metricsGrid.appendChild(createMetric('Request ID', traceIds.requestId));
+        metricsGrid.appendChild(createMetric('Trace ID', traceIds.traceparent.split('-')[1] || traceIds.traceparent));
+    }
+
     summary.appendChild(metricsGrid);
     container.appendChild(summary);
 
@@ -273,6 +285,71 @@ export function displayPerformanceMetrics(container, timings, size) {
     container.appendChild(legend);
 }
 
+const LIVE_PHASES = {
+    dns: { label: 'DNS Lookup', color: '#3584e4' },
+    connected: { label: 'TCP Connection', color: '#33d17a' },
+    tls: { label: 'TLS Handshake', color: '#f6d32d' },
+    requestSent: { label: 'Sending', color: '#99c1f1' },
+    firstByte: { label: 'Waiting (TTFB)', color: '#ed333b' },
+    chunk: { label: 'Content Download', color: '#c061cb' },
+    complete: { label: 'Content Download', color: '#c061cb' }
+};
+
+/**
+ * Display the lifecycle events of a request that is still running. Each
+ * phase is drawn from the end of the previous one; chunks extend the
+ * download bar. Replaced by displayPerformanceMetrics once the response
+ * arrives.
+ * @param {HTMLElement} container - Container element
+ * @param {Array<{phase: string, elapsedMs: number, bytes?: number}>} events - Events in arrival order
+ */
+export function displayLiveTimeline(container, events) {
+    if (!container) {
+        return;
+    }
+
+    container.innerHTML = '';
+
+    const last = events[events.length - 1];
+    const elapsed = last ? last.elapsedMs : 0;
+
+    const title = document.createElement('h4');
+    title.textContent = `Live Timeline · ${formatTime(elapsed)}`;
+    container.appendChild(title);
+
+    const segments = [];
+    let previous = 0;
+    events.forEach(event => {
+        const phase = LIVE_PHASES[event.phase];
+        if (!phase) {
+            previous = event.elapsedMs;
+            return;
+        }
+        const open = segments[segments.length - 1];
+        if (open && open.label === phase.label) {
+            open.time += event.elapsedMs - previous;
+        } else {
+            segments.push({ ...phase, time: event.elapsedMs - previous });
+        }
+        previous = event.elapsedMs;
+    });
+
+    const timingBars = document.createElement('div');
+    timingBars.className = 'timing-bars';
+    segments.forEach(segment => {
+        timingBars.appendChild(createTimingBar(segment.label, segment.time, elapsed, segment.color));
+    });
+    container.appendChild(timingBars);
+
+    const received = [...events].reverse().find(event => event.bytes !== undefined && event.bytes !== null);
+    if (received) {
+        const metricsGrid = document.createElement('div');
+        metricsGrid.className = 'metrics-grid';
+        metricsGrid.appendChild(createMetric('Received', `${(received.bytes / 1024).toFixed(2)} KB`));
+        container.appendChild(metricsGrid);
+    }
+}
+
 /**
  * Clear performance metrics display
  * @param {HTMLElement} container - Container element
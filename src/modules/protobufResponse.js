@@ -0,0 +1,92 @@
+/**
+ * @fileoverview Decoding of protobuf HTTP responses with the message types of
+ * the proto files loaded in gRPC mode. The backend keeps each tab's raw
+ * response body, decodes it and the JSON is shown in the response editor.
+ * @module protobufResponse
+ */
+
+import { app } from './appContext.js';
+import { toast } from './ui/Toast.js';
+import { ProtobufDecodeDialog } from './ui/ProtobufDecodeDialog.js';
+import { displayResponseWithLineNumbersForTab } from './apiHandler.js';
+
+/** Message type last used per workspace tab, applied to its next responses */
+const messageTypeByTab = new Map();
+
+/**
+ * Whether a Content-Type denotes a protobuf body
+ *
+ * @param {string|null} contentType - Content-Type header value
+ * @returns {boolean}
+ */
+export function isProtobufContentType(contentType) {
+    return /application\/(x-)?protobuf|application\/vnd\.google\.protobuf/i.test(contentType || '');
+}
+
+/**
+ * Decodes a tab's last response as `messageType` and displays the JSON
+ *
+ * @async
+ * @param {string} tabId - Workspace tab ID
+ * @param {string} messageType - Full name of the message type
+ * @returns {Promise<boolean>} Whether decoding succeeded
+ */
+async function decodeInto(tabId, messageType) {
+    try {
+        const decoded = await window.backendAPI.grpc.decodeProtobufResponse(tabId, messageType);
+        displayResponseWithLineNumbersForTab(JSON.stringify(decoded, null, 2), 'application/json', tabId, 'json');
+        messageTypeByTab.set(tabId, messageType);
+        return true;
+    } catch (error) {
+        toast.error(error?.message || String(error));
+        return false;
+    }
+}
+
+/**
+ * Shows the decode button for protobuf responses and decodes them right
+ * away when a message type was already chosen in this tab
+ *
+ * @async
+ * @param {string} tabId - Workspace tab ID
+ * @param {string|null} contentType - Content-Type of the response
+ * @returns {Promise<void>}
+ */
+export async function handleProtobufResponse(tabId, contentType) {
+    const containerElements = app.responseContainerManager?.getOrCreateContainer(tabId);
+    const button = containerElements?.wrapper?.querySelector('.decode-protobuf-btn');
+    const isProtobuf = isProtobufContentType(contentType);
+    button?.classList.toggle('is-hidden', !isProtobuf);
+
+    if (isProtobuf && messageTypeByTab.has(tabId)) {
+        await decodeInto(tabId, messageTypeByTab.get(tabId));
+    }
+}
+
+/**
+ * Wires a response toolbar button to pick a message type and decode
+ *
+ * @param {HTMLElement} button - The decode button
+ * @param {string} tabId - Workspace tab ID
+ * @returns {void}
+ */
+export function attachProtobufDecodeHandler(button, tabId) {
+    button.addEventListener('click', async () => {
+        let messageTypes = [];
+        try {
+            messageTypes = await window.backendAPI.grpc.listProtoMessages();
+        } catch (error) {
+            void error;
+        }
+        if (messageTypes.length === 0) {
+            toast.info(app.i18n?.t('protobuf_decode.no_protos')
+                || 'Load a .proto file in gRPC mode to decode protobuf responses');
+            return;
+        }
+
+        const messageType = await new ProtobufDecodeDialog().show(messageTypes, messageTypeByTab.get(tabId) || '');
+        if (messageType) {
+            await decodeInto(tabId, messageType);
+        }
+    });
+}
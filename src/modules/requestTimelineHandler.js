@@ -0,0 +1,46 @@
+/**
+ * @fileoverview Renders the backend's `request-timeline` events into the
+ * Performance pane of the tab that sent the request, so the waterfall grows
+ * while the request runs instead of appearing only with the response.
+ * @module requestTimelineHandler
+ */
+
+import { createBackendEventListener } from './streaming/streamSession.js';
+import { getResponseElements } from './ResponseDisplayHelper.js';
+import { displayLiveTimeline } from './performanceMetrics.js';
+
+/** Phases after which the request is over and the response pane takes over */
+const FINAL_PHASES = new Set(['complete', 'failed', 'cancelled']);
+
+/** @type {Map<string, Array<Object>>} Events of the running request, per tab */
+const timelines = new Map();
+
+function handleBackendEvent(event) {
+    const { tabId, phase, elapsedMs, bytes } = event.payload || {};
+    if (!tabId || !phase) {
+        return;
+    }
+    if (phase === 'queued') {
+        timelines.set(tabId, []);
+    }
+    const events = timelines.get(tabId);
+    if (!events) {
+        return;
+    }
+    events.push({ phase, elapsedMs, bytes });
+
+    if (FINAL_PHASES.has(phase)) {
+        timelines.delete(tabId);
+        // The response handler renders the final metrics
+        if (phase !== 'complete') {
+            return;
+        }
+    }
+    displayLiveTimeline(getResponseElements(tabId).performanceDisplay, events);
+}
+
+export const initRequestTimelineHandler = createBackendEventListener(
+    'request-timeline',
+    () => !!window.backendAPI?.sendApiRequest,
+    handleBackendEvent
+);
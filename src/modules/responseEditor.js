@@ -5,6 +5,7 @@ import { xml } from '@codemirror/lang-xml';
 import { html } from '@codemirror/lang-html';
 import { searchKeymap, highlightSelectionMatches, search, openSearchPanel } from '@codemirror/search';
 import { createThemedHighlighting } from './editorTheme.js';
+import { isLargeJson } from './largeJson.js';
 
 /**
  * ResponseEditor - Manages CodeMirror editor for response display
@@ -172,16 +173,19 @@ export class ResponseEditor {
      * @private
      */
     _updateEditorWithLanguage(content, languageType) {
+        // Parsing and wrapping a multi-megabyte document blocks the UI, so
+        // large bodies are shown unwrapped and without highlighting.
+        const large = isLargeJson(content.length);
         const extensions = [
             lineNumbers(),
             EditorView.editable.of(false),
             EditorView.contentAttributes.of({ tabindex: '0' }),
-            EditorView.lineWrapping,
+            ...(large ? [] : [EditorView.lineWrapping]),
             ...this.getThemeExtensions(),
             ...this.getSearchExtensions()
         ];
 
-        if (languageType && languageType !== 'text') {
+        if (languageType && languageType !== 'text' && !large) {
             const language = this.getLanguageExtension(languageType);
             if (language) {
                 extensions.push(language.extension);
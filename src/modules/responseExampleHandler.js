@@ -0,0 +1,89 @@
+/**
+ * @fileoverview Saving a tab's last response as a named example of its
+ * endpoint. Examples are stored with the collection, served by the mock
+ * server and included in OpenAPI and Postman exports.
+ * @module responseExampleHandler
+ */
+
+import { app } from './appContext.js';
+import { toast } from './ui/Toast.js';
+import { ResponseExampleDialog } from './ui/ResponseExampleDialog.js';
+
+/**
+ * The response body as text: parsed JSON bodies are pretty-printed
+ *
+ * @param {*} data - Response data as kept on the tab
+ * @returns {string}
+ */
+function bodyText(data) {
+    if (data === null || data === undefined) {
+        return '';
+    }
+    return typeof data === 'string' ? data : JSON.stringify(data, null, 2);
+}
+
+/**
+ * Asks for a name and saves the last response of a workspace tab as an
+ * example of the tab's endpoint
+ *
+ * @async
+ * @param {string} tabId - Workspace tab ID
+ * @returns {Promise<void>}
+ */
+export async function saveResponseAsExample(tabId) {
+    const tabs = await app.workspaceTabController?.service.getAllTabs() || [];
+    const tab = tabs.find(t => t.id === tabId);
+    const { collectionId, endpointId } = tab?.endpoint || {};
+    if (!collectionId || !endpointId) {
+        toast.info(app.i18n?.t('response_example.no_endpoint')
+            || 'Save the request to a collection to keep example responses');
+        return;
+    }
+    const response = tab.response;
+    if (!response?.status) {
+        toast.info(app.i18n?.t('response_example.no_response')
+            || 'Send the request to save its response as an example');
+        return;
+    }
+
+    const api = window.backendAPI.endpoints;
+    try {
+        const examples = await api.listExamples(collectionId, endpointId);
+        const onDelete = async (exampleId) => {
+            try {
+                return await api.deleteExample(collectionId, endpointId, exampleId);
+            } catch (error) {
+                toast.error(error?.message || String(error));
+                return null;
+            }
+        };
+        const defaultName = `${response.status} ${response.statusText || ''}`.trim();
+        const name = await new ResponseExampleDialog().show(examples, defaultName, onDelete);
+        if (!name) {
+            return;
+        }
+
+        await api.saveExample(collectionId, endpointId, {
+            name,
+            status: response.status,
+            statusText: response.statusText || '',
+            headers: response.headers || {},
+            body: bodyText(response.data)
+        });
+        toast.success(app.i18n?.t('response_example.saved_toast', { name })
+            || `Saved example "${name}"`);
+    } catch (error) {
+        toast.error(error?.message || String(error));
+    }
+}
+
+/**
+ * Wires a response toolbar button to save the tab's last response as an example
+ *
+ * @param {HTMLElement} button - The save-example button
+ * @param {string} tabId - The workspace tab ID
+ * @returns {void}
+ */
+export function attachSaveExampleHandler(button, tabId) {
+    button.addEventListener('click', () => saveResponseAsExample(tabId));
+}
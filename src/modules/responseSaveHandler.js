@@ -0,0 +1,42 @@
+/**
+ * @fileoverview Saving response bodies to files. The backend keeps the raw
+ * bytes of each tab's last response, so binary bodies are saved exactly.
+ * @module responseSaveHandler
+ */
+
+import { app } from './appContext.js';
+import { toast } from './ui/Toast.js';
+
+/**
+ * Saves a response body to a file chosen in a save dialog and reports the
+ * outcome as a toast
+ *
+ * @async
+ * @param {{responseKey?: string, historyId?: string}} target - A workspace
+ *   tab's last response, or a history entry
+ * @returns {Promise<void>}
+ */
+export async function saveResponseToFile(target) {
+    try {
+        const result = await window.backendAPI.saveResponseToFile(target);
+        if (result?.success) {
+            toast.success(app.i18n?.t('response_file.saved', { path: result.filePath })
+                || `Saved to ${result.filePath}`);
+        }
+    } catch (error) {
+        const message = error?.message || String(error);
+        toast.error(app.i18n?.t('response_file.failed', { error: message })
+            || `Could not save the response: ${message}`);
+    }
+}
+
+/**
+ * Wires a response toolbar button to save the tab's last response
+ *
+ * @param {HTMLElement} button - The save button
+ * @param {string} tabId - The workspace tab ID
+ * @returns {void}
+ */
+export function attachSaveResponseHandler(button, tabId) {
+    button.addEventListener('click', () => saveResponseToFile({ responseKey: tabId }));
+}
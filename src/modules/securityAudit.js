@@ -0,0 +1,110 @@
+/**
+ * @fileoverview Security tab of the response panel: the backend audits the
+ * response headers (HSTS, CSP, cookie flags, CORS, ...) and the findings are
+ * listed by severity.
+ * @module securityAudit
+ */
+
+import { app } from './appContext.js';
+
+const SEVERITIES = ['high', 'medium', 'low', 'info', 'pass'];
+
+const SEVERITY_FALLBACKS = {
+    high: 'High',
+    medium: 'Medium',
+    low: 'Low',
+    info: 'Info',
+    pass: 'Pass'
+};
+
+/**
+ * Audits the headers of a response
+ *
+ * @async
+ * @param {string} url - Request URL
+ * @param {Object} result - Result of `sendApiRequest`
+ * @returns {Promise<Object|null>} The audit, or null when it failed
+ */
+export async function auditResponseSecurity(url, result) {
+    try {
+        return await window.backendAPI.auditSecurityHeaders(url, result.headers || {}, result.setCookies || []);
+    } catch (error) {
+        void error;
+        return null;
+    }
+}
+
+/**
+ * Resets the Security tab to its empty state
+ *
+ * @param {HTMLElement|null} container - Security display element
+ * @returns {void}
+ */
+export function clearSecurityAudit(container) {
+    if (!container) {
+        return;
+    }
+    const empty = document.createElement('p');
+    empty.className = 'no-data';
+    empty.textContent = app.i18n?.t('security_audit.no_data') || 'Send a request to audit its security headers';
+    container.replaceChildren(empty);
+}
+
+/**
+ * Lists the findings of an audit in the Security tab
+ *
+ * @param {HTMLElement|null} container - Security display element
+ * @param {Object|null} audit - Audit returned by the backend
+ * @returns {void}
+ */
+export function displaySecurityAudit(container, audit) {
+    if (!container) {
+        return;
+    }
+    if (!audit?.findings) {
+        clearSecurityAudit(container);
+        return;
+    }
+
+    const counts = Object.fromEntries(SEVERITIES.map(severity => [severity, 0]));
+    audit.findings.forEach(finding => { counts[finding.severity] += 1; });
+
+    const summary = document.createElement('div');
+    summary.className = 'security-audit-summary';
+    SEVERITIES.filter(severity => counts[severity] > 0).forEach(severity => {
+        summary.appendChild(createSeverityBadge(severity, counts[severity]));
+    });
+
+    const list = document.createElement('ul');
+    list.className = 'security-audit-findings';
+    audit.findings.forEach(finding => {
+        const item = document.createElement('li');
+        item.className = 'security-audit-finding';
+
+        const header = document.createElement('span');
+        header.className = 'security-audit-header';
+        header.textContent = finding.header;
+
+        const message = document.createElement('span');
+        message.className = 'security-audit-message';
+        message.textContent = finding.message;
+
+        item.append(createSeverityBadge(finding.severity), header, message);
+        list.appendChild(item);
+    });
+
+    container.replaceChildren(summary, list);
+}
+
+/**
+ * @param {string} severity - Finding severity
+ * @param {number} [count] - Number of findings, shown before the label
+ * @returns {HTMLElement}
+ */
+function createSeverityBadge(severity, count) {
+    const badge = document.createElement('span');
+    badge.className = `security-severity security-severity-${severity}`;
+    const label = app.i18n?.t(`security_audit.severity_${severity}`) || SEVERITY_FALLBACKS[severity];
+    badge.textContent = count === undefined ? label : `${count} ${label}`;
+    return badge;
+}
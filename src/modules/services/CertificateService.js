@@ -3,6 +3,21 @@
  * @module services/CertificateService
  */
 
+/** A `sha256/<base64>` public key hash or a hex SHA-256 fingerprint */
+const PIN_PATTERN = /^(sha256\/[A-Za-z0-9+/]{43}=|[0-9A-Fa-f]{2}(:?[0-9A-Fa-f]{2}){31})$/;
+
+/**
+ * Splits the pins of an entry, entered one per line or comma-separated
+ *
+ * @param {string} pins - Raw pins field
+ * @returns {Array<string>}
+ */
+function splitPins(pins) {
+    return typeof pins === 'string'
+        ? pins.split(/[,\n]/).map(pin => pin.trim()).filter(Boolean)
+        : [];
+}
+
 /**
  * Service for managing client certificate configuration business logic
  *
@@ -106,10 +121,10 @@ export class CertificateService {
      *
      * Prefers an enabled entry whose host exactly matches `host:port`, then falls
      * back to an enabled entry matching the bare hostname. Returns only the path
-     * fields needed by the backend, or null when nothing matches.
+     * fields and pins needed by the backend, or null when nothing matches.
      *
      * @param {string} requestHost - The host (or `host:port`) of the request
-     * @returns {{certPath: string, keyPath: string, caPath: string}|null}
+     * @returns {{certPath: string, keyPath: string, caPath: string, pins: Array<string>}|null}
      */
     getForHost(requestHost) {
         if (!requestHost || !Array.isArray(this._cache)) {
@@ -134,19 +149,20 @@ export class CertificateService {
         return {
             certPath: match.certPath || '',
             keyPath: match.keyPath || '',
-            caPath: match.caPath || ''
+            caPath: match.caPath || '',
+            pins: splitPins(match.pins)
         };
     }
 
     /**
-     * Whether an entry has any usable certificate material (client cert or CA)
+     * Whether an entry has any usable certificate material (client cert, CA or pins)
      *
      * @private
      * @param {Object} entry - Certificate entry
      * @returns {boolean}
      */
     _hasMaterial(entry) {
-        return Boolean((entry.certPath && entry.keyPath) || entry.caPath);
+        return Boolean((entry.certPath && entry.keyPath) || entry.caPath || splitPins(entry.pins).length);
     }
 
     /**
@@ -173,6 +189,11 @@ export class CertificateService {
             errors.push('Client certificate requires both a certificate and a key file');
         }
 
+        const invalidPin = splitPins(entry.pins).find(pin => !PIN_PATTERN.test(pin));
+        if (invalidPin) {
+            errors.push(`Invalid pin "${invalidPin}": use sha256/<base64> or a hex SHA-256 fingerprint`);
+        }
+
         return errors;
     }
 }
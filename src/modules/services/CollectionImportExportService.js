@@ -4,6 +4,7 @@
  */
 
 import { app } from '../appContext.js';
+import { ConfirmDialog } from '../ui/ConfirmDialog.js';
 import { toast } from '../ui/Toast.js';
 
 /**
@@ -65,6 +66,108 @@ export class CollectionImportExportService {
         }
     }
 
+    async handleExportHttpFile(collection) {
+        try {
+            await this.collectionService.exportCollectionAsHttpFile(collection.id);
+        } catch (error) {
+            void error;
+        }
+    }
+
+    async handleExportShellScript(collection, folder = null) {
+        try {
+            await this.collectionService.exportCollectionAsShellScript(collection.id, folder?.id ?? null);
+        } catch (error) {
+            void error;
+        }
+    }
+
+    /**
+     * Saves one request as a bundle file to send to someone
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleExportRequestBundle(collection, endpoint) {
+        try {
+            const result = await this.backendAPI.requestBundles.export(collection.id, endpoint.id);
+            if (result.cancelled) {
+                this.statusDisplay.update('Export cancelled', null);
+                return;
+            }
+            toast.success(bundleExportMessage('Request bundle saved', result.strippedSecrets));
+        } catch (error) {
+            toast.error(`Export failed: ${error?.message || error}`);
+        }
+    }
+
+    /**
+     * Copies one request as a `resonance://bundle` link
+     *
+     * @async
+     * @param {Object} collection - The parent collection
+     * @param {Object} endpoint - The endpoint
+     * @returns {Promise<void>}
+     */
+    async handleCopyRequestLink(collection, endpoint) {
+        try {
+            const link = await this.backendAPI.requestBundles.link(collection.id, endpoint.id);
+            await navigator.clipboard.writeText(link);
+            toast.success(app.i18n?.t('request_bundle.link_copied') || 'Request link copied to clipboard');
+        } catch (error) {
+            toast.error(`Export failed: ${error?.message || error}`);
+        }
+    }
+
+    /**
+     * Imports a shared request bundle after the user confirms: bundles and
+     * links come from other people
+     *
+     * @async
+     * @param {?string} [filePath=null] - Bundle file to open instead of picking one
+     * @param {?string} [link=null] - `resonance://bundle` link to read instead of a file
+     * @returns {Promise<Object|null>} The import result, or null if cancelled
+     */
+    async importRequestBundle(filePath = null, link = null) {
+        try {
+            const bundle = await this.backendAPI.requestBundles.open(filePath, link);
+            if (!bundle) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            const name = bundle.endpoint?.name || bundle.endpoint?.path || '';
+            const confirmed = await new ConfirmDialog().show(
+                app.i18n?.t('request_bundle.import_confirm', { name, collection: bundle.collectionName })
+                    || `Add the request "${name}" to the collection "${bundle.collectionName}"?`,
+                {
+                    title: app.i18n?.t('request_bundle.import_title') || 'Import Request',
+                    confirmText: app.i18n?.t('deep_link.import') || 'Import',
+                    dangerous: false
+                }
+            );
+            if (!confirmed) {
+                return null;
+            }
+
+            const result = await this.backendAPI.requestBundles.import(bundle);
+            await this.refreshCollections(false);
+            if (result.needsValues.length > 0) {
+                toast.warning(app.i18n?.t('request_bundle.needs_values', { names: result.needsValues.join(', ') })
+                    || `Imported "${name}". Fill in the secret variables: ${result.needsValues.join(', ')}`);
+            } else {
+                toast.success(`Imported "${name}"`);
+            }
+            return result;
+        } catch (error) {
+            const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown error');
+            toast.error(`Import failed: ${errorMessage}`);
+            throw error;
+        }
+    }
+
     async handleGenerateDocumentation(collection) {
         try {
             if (!this.docGeneratorService.hasHttpEndpoints(collection)) {
@@ -115,10 +218,11 @@ export class CollectionImportExportService {
         }
     }
 
-    async importOpenApiFile() {
+    async importOpenApiFile(filePath = null) {
         try {
             const importOptions = await this.collectionDialogs.showCollectionImportDialog({
-                importKind: 'openapi'
+                importKind: 'openapi',
+                filePath: filePath || ''
             });
             if (!importOptions) {
                 this.statusDisplay.update('Import cancelled', null);
@@ -147,10 +251,37 @@ export class CollectionImportExportService {
         }
     }
 
-    async importPostmanCollection() {
+    /**
+     * Imports an OpenAPI spec from a URL into the default collections directory
+     *
+     * @async
+     * @param {string} url - HTTP(S) URL of the spec
+     * @returns {Promise<Object|null>} The imported collection, or null on failure
+     */
+    async importOpenApiUrl(url) {
+        try {
+            this.statusDisplay.update(`Importing ${url}...`, null);
+            const collection = await this.backendAPI.collections.importOpenApiUrl(url);
+            this.statusDisplay.update('', null);
+
+            await this.refreshCollections(false);
+            await this.saveResponseSchemasFromImport(collection);
+            await this.storeImportedCollectionAuth(collection);
+            toast.success(`Imported "${collection.name}"`);
+            return collection;
+        } catch (error) {
+            this.statusDisplay.update('', null);
+            const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown error');
+            toast.error(`Import failed: ${errorMessage}`);
+            return null;
+        }
+    }
+
+    async importPostmanCollection(filePath = null) {
         try {
             const importOptions = await this.collectionDialogs.showCollectionImportDialog({
-                importKind: 'postman'
+                importKind: 'postman',
+                filePath: filePath || ''
             });
             if (!importOptions) {
                 this.statusDisplay.update('Import cancelled', null);
@@ -178,6 +309,135 @@ export class CollectionImportExportService {
         }
     }
 
+    /**
+     * Imports a Postman data dump: all of its collections, plus its
+     * environments and globals as environments
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect in the import dialog
+     * @returns {Promise<Object|null>} The import result, or null if cancelled
+     */
+    async importPostmanDump(filePath = null) {
+        try {
+            const importOptions = await this.collectionDialogs.showCollectionImportDialog({
+                importKind: 'postman_dump',
+                filePath: filePath || ''
+            });
+            if (!importOptions) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            const result = await this.backendAPI.collections.importPostmanDump(
+                importOptions.filePath,
+                importOptions.storageParentPath
+            );
+
+            if (!result) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            await this.refreshCollections(false);
+            for (const collection of result.collections) {
+                await this.storeImportedCollectionAuth(collection);
+            }
+            if (app.environmentController) {
+                for (const environment of result.environments) {
+                    await app.environmentController.handleImportEnvironment(environment);
+                }
+            }
+
+            const summary = `Imported ${result.collections.length} collections and ${result.environments.length} environments`;
+            if (result.skipped.length > 0) {
+                toast.warning(`${summary}; skipped ${result.skipped.length}: ${result.skipped.join(', ')}`);
+            } else {
+                toast.success(summary);
+            }
+            return result;
+        } catch (error) {
+            const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown error');
+            toast.error(`Import failed: ${errorMessage}`);
+            throw error;
+        }
+    }
+
+    /**
+     * Imports the API requests recorded in a HAR file as a collection
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect in the import dialog
+     * @returns {Promise<Object|null>} The imported collection, or null if cancelled
+     */
+    async importHarFile(filePath = null) {
+        try {
+            const importOptions = await this.collectionDialogs.showCollectionImportDialog({
+                importKind: 'har',
+                filePath: filePath || ''
+            });
+            if (!importOptions) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            const collection = await this.backendAPI.collections.importHarFile(
+                importOptions.filePath,
+                importOptions.storageParentPath
+            );
+
+            if (!collection) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            await this.refreshCollections(false);
+            toast.success(`Imported "${collection.name}"`);
+            return collection;
+        } catch (error) {
+            const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown error');
+            toast.error(`Import failed: ${errorMessage}`);
+            throw error;
+        }
+    }
+
+    /**
+     * Imports the requests of a REST Client / JetBrains `.http` file as a collection
+     *
+     * @async
+     * @param {?string} [filePath=null] - File to preselect in the import dialog
+     * @returns {Promise<Object|null>} The imported collection, or null if cancelled
+     */
+    async importHttpFile(filePath = null) {
+        try {
+            const importOptions = await this.collectionDialogs.showCollectionImportDialog({
+                importKind: 'http',
+                filePath: filePath || ''
+            });
+            if (!importOptions) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            const collection = await this.backendAPI.collections.importHttpFile(
+                importOptions.filePath,
+                importOptions.storageParentPath
+            );
+
+            if (!collection) {
+                this.statusDisplay.update('Import cancelled', null);
+                return null;
+            }
+
+            await this.refreshCollections(false);
+            toast.success(`Imported "${collection.name}"`);
+            return collection;
+        } catch (error) {
+            const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown error');
+            toast.error(`Import failed: ${errorMessage}`);
+            throw error;
+        }
+    }
+
     /**
      * Re-persists imported collection- and folder-level auth configs through
      * the repository so literal credentials move into the SecretStore and
@@ -377,3 +637,18 @@ export class CollectionImportExportService {
         return null;
     }
 }
+
+/**
+ * Toast text for an exported bundle, naming the secrets left out of it
+ *
+ * @param {string} message - Base message
+ * @param {Array<string>} strippedSecrets - Variables exported without a value
+ * @returns {string}
+ */
+function bundleExportMessage(message, strippedSecrets) {
+    if (!strippedSecrets || strippedSecrets.length === 0) {
+        return message;
+    }
+    return app.i18n?.t('request_bundle.secrets_stripped', { message, names: strippedSecrets.join(', ') })
+        || `${message}; secret values left out: ${strippedSecrets.join(', ')}`;
+}
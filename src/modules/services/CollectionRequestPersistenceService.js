@@ -316,6 +316,11 @@ export class CollectionRequestPersistenceService {
                     mode: 'binary',
                     ...app.formBodyManager.getBinaryBody()
                 };
+            } else if (bodyMode === 'soap' && app.formBodyManager) {
+                updatedRequest.body = {
+                    mode: 'soap',
+                    ...app.formBodyManager.getSoapBody()
+                };
             } else if (bodyMode === 'text') {
                 updatedRequest.body = {
                     mode: 'text',
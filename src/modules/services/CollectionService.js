@@ -179,6 +179,60 @@ export class CollectionService {
         }
     }
 
+    async exportCollectionAsHttpFile(collectionId) {
+        try {
+            this.statusDisplay.update('Exporting collection...', null);
+
+            const result = await window.backendAPI.collections.exportHttpFile(collectionId);
+
+            if (result.cancelled) {
+                this.statusDisplay.update('Export cancelled', null);
+                return { success: false, cancelled: true };
+            }
+
+            if (result.success) {
+                let message = 'Collection exported successfully as .http file';
+                if (result.skipped && result.skipped.count > 0) {
+                    message = `${message} (${result.skipped.count} items skipped)`;
+                }
+                this.statusDisplay.update(message, null);
+                return result;
+            }
+
+            throw new Error('Export failed');
+        } catch (error) {
+            this.statusDisplay.update(`Export error: ${error.message}`, null);
+            throw error;
+        }
+    }
+
+    async exportCollectionAsShellScript(collectionId, folderId = null) {
+        try {
+            this.statusDisplay.update('Exporting collection...', null);
+
+            const result = await window.backendAPI.collections.exportShellScript(collectionId, folderId);
+
+            if (result.cancelled) {
+                this.statusDisplay.update('Export cancelled', null);
+                return { success: false, cancelled: true };
+            }
+
+            if (result.success) {
+                let message = 'Collection exported successfully as shell script';
+                if (result.skipped && result.skipped.count > 0) {
+                    message = `${message} (${result.skipped.count} items skipped)`;
+                }
+                this.statusDisplay.update(message, null);
+                return result;
+            }
+
+            throw new Error('Export failed');
+        } catch (error) {
+            this.statusDisplay.update(`Export error: ${error.message}`, null);
+            throw error;
+        }
+    }
+
     /**
      * Creates a new empty collection
      *
@@ -442,6 +496,232 @@ export class CollectionService {
         }
     }
 
+    /**
+     * Sets the response time budget of a request
+     *
+     * The budget is stored on the endpoint in the collection file; responses
+     * slower than it are flagged on send and in collection runs.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {number} budgetMs - Budget in milliseconds, 0 to remove it
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestTimeBudget(collectionId, endpointId, budgetMs) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        endpoints.forEach(endpoint => {
+            if (budgetMs > 0) {
+                endpoint.timeBudgetMs = budgetMs;
+            } else {
+                delete endpoint.timeBudgetMs;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(budgetMs > 0 ? `Time budget set to ${budgetMs} ms` : 'Time budget removed', null);
+    }
+
+    /**
+     * Sets the IP family a request resolves its host to, overriding the
+     * global setting
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {string|null} ipFamily - "auto", "ipv4", "ipv6", "prefer-ipv4" or
+     * "prefer-ipv6"; null to use the global setting
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestIpFamily(collectionId, endpointId, ipFamily) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        endpoints.forEach(endpoint => {
+            if (ipFamily) {
+                endpoint.ipFamily = ipFamily;
+            } else {
+                delete endpoint.ipFamily;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(ipFamily ? `IP version set to ${ipFamily}` : 'IP version follows the global setting', null);
+    }
+
+    /**
+     * Turns certificate verification off (or back on) for one request,
+     * whatever the global setting says
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {boolean} ignore - Whether to accept invalid certificates
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestIgnoreTlsErrors(collectionId, endpointId, ignore) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        endpoints.forEach(endpoint => {
+            if (ignore) {
+                endpoint.ignoreTlsErrors = true;
+            } else {
+                delete endpoint.ignoreTlsErrors;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(ignore ? 'TLS errors ignored for this request' : 'TLS certificates verified for this request', null);
+    }
+
+    /**
+     * Sets the variable values a request uses instead of the environment and
+     * collection ones
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {Object<string, string>} overrides - Values by variable name; an
+     * empty map removes them
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestVariableOverrides(collectionId, endpointId, overrides) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        const count = Object.keys(overrides).length;
+        endpoints.forEach(endpoint => {
+            if (count === 0) {
+                delete endpoint.variableOverrides;
+            } else {
+                endpoint.variableOverrides = overrides;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(count === 0 ? 'Variable overrides removed' : `${count} variable override(s) saved`, null);
+    }
+
+    /**
+     * Sets whether a request sends a generated idempotency key
+     *
+     * The backend generates the key on every send and remembers the last one
+     * so the request can be resent with it.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {{header: string, template: string}|null} settings - Header name
+     * and key template, empty for the defaults; null to stop sending keys
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestIdempotencyKey(collectionId, endpointId, settings) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        endpoints.forEach(endpoint => {
+            if (settings) {
+                endpoint.idempotencyKey = settings;
+            } else {
+                delete endpoint.idempotencyKey;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(settings ? 'Idempotency key enabled' : 'Idempotency key disabled', null);
+    }
+
+    /**
+     * Sets the transform applied to a request's JSON responses before they are
+     * shown and recorded
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {string} endpointId - The endpoint ID
+     * @param {Object} transform - {project: string[], mask: string[], sortKeys: boolean};
+     * an empty transform removes it
+     * @returns {Promise<void>}
+     * @throws {Error} If collection or endpoint is not found or update fails
+     */
+    async setRequestResponseTransform(collectionId, endpointId, transform) {
+        const collection = await this.repository.getById(collectionId);
+        if (!collection) {
+            throw new Error(`Collection with id ${collectionId} not found`);
+        }
+
+        const endpoints = [
+            ...(collection.endpoints || []),
+            ...(collection.folders || []).flatMap(folder => folder.endpoints || [])
+        ].filter(ep => ep.id === endpointId);
+        if (endpoints.length === 0) {
+            throw new Error(`Endpoint with id ${endpointId} not found in collection`);
+        }
+
+        const isEmpty = transform.project.length === 0 && transform.mask.length === 0 && !transform.sortKeys;
+        endpoints.forEach(endpoint => {
+            if (isEmpty) {
+                delete endpoint.responseTransform;
+            } else {
+                endpoint.responseTransform = transform;
+            }
+        });
+        await this.repository.update(collectionId, collection);
+        this.statusDisplay.update(isEmpty ? 'Response transform removed' : 'Response transform saved', null);
+    }
+
     /**
      * Deletes a request from a collection
      *
@@ -807,6 +1087,18 @@ export class CollectionService {
             return;
         }
 
+        if (formBodyData && formBodyData.mode === 'soap') {
+            if (app.graphqlBodyManager) {
+                app.graphqlBodyManager.switchMode('soap');
+            }
+            if (app.formBodyManager) {
+                app.formBodyManager.setSoapBody(formBodyData);
+            }
+            const key = `${collection.id}_${endpoint.id}`;
+            this.originalBodyValues.set(key, JSON.stringify(app.formBodyManager?.getSoapBody() || {}));
+            return;
+        }
+
         if (formBodyData && formBodyData.mode === 'text') {
             if (app.graphqlBodyManager) {
                 app.graphqlBodyManager.switchMode('text');
@@ -939,6 +1231,11 @@ export class CollectionService {
                 mode: 'binary',
                 ...app.formBodyManager.getBinaryBody()
             };
+        } else if (bodyMode === 'soap' && app.formBodyManager) {
+            state.formBodyData = {
+                mode: 'soap',
+                ...app.formBodyManager.getSoapBody()
+            };
         } else if (bodyMode === 'text') {
             state.formBodyData = {
                 mode: 'text',
@@ -254,11 +254,23 @@ export class DocGeneratorService {
         lines.push(`### ${endpoint.method} ${displayName}`);
         lines.push('');
 
+        if (endpoint.deprecated) {
+            lines.push('> **Deprecated** — this endpoint may be removed in a future version.');
+            lines.push('');
+        }
+
         if (endpoint.description || endpoint.summary) {
             lines.push(endpoint.description || endpoint.summary);
             lines.push('');
         }
 
+        if (endpoint.exampleUse) {
+            lines.push('#### Example Use');
+            lines.push('');
+            lines.push(endpoint.exampleUse);
+            lines.push('');
+        }
+
         let fullUrl = endpoint.path;
         if (collection.baseUrl) {
             fullUrl = `${collection.baseUrl}${endpoint.path}`;
@@ -491,10 +503,19 @@ export class DocGeneratorService {
         html.push(`<article class="endpoint" id="${endpointId}">`);
         html.push(`<h3><span class="method method-${endpoint.method.toLowerCase()}">${endpoint.method}</span> ${escapeHtml(displayName)}</h3>`);
 
+        if (endpoint.deprecated) {
+            html.push('<p class="endpoint-deprecated"><strong>Deprecated</strong> — this endpoint may be removed in a future version.</p>');
+        }
+
         if (endpoint.description || endpoint.summary) {
             html.push(`<p class="endpoint-description">${escapeHtml(endpoint.description || endpoint.summary)}</p>`);
         }
 
+        if (endpoint.exampleUse) {
+            html.push('<h4>Example Use</h4>');
+            html.push(`<p class="endpoint-example-use">${escapeHtml(endpoint.exampleUse)}</p>`);
+        }
+
         html.push(`<p class="endpoint-url"><strong>URL:</strong> <code>${escapeHtml(fullUrl)}</code></p>`);
 
         const pathParams = this._getPathParams(endpoint, persistedData);
@@ -383,6 +383,21 @@ export class EnvironmentService {
         }
     }
 
+    /**
+     * Re-reads the environments after the backend changed them (a login flow
+     * storing its token) and notifies listeners.
+     *
+     * @param {string} environmentId - The environment that changed
+     * @returns {void}
+     */
+    reload(environmentId) {
+        this.repository.invalidate();
+        this._notifyListeners({
+            type: 'environment-updated',
+            environmentId
+        });
+    }
+
     /**
      * Delete variable from environment
      */
@@ -425,7 +440,8 @@ export class EnvironmentService {
                 name: environment.name,
                 variables: environment.variables,
                 secretKeys: Array.isArray(environment.secretKeys) ? environment.secretKeys : [],
-                color: environment.color || null
+                color: environment.color || null,
+                ...(environment.encrypted ? { encrypted: environment.encrypted } : {})
             };
         } catch (error) {
             this.statusDisplay.update(`Error exporting environment: ${error.message}`, null);
@@ -445,7 +461,8 @@ export class EnvironmentService {
                     name: env.name,
                     variables: env.variables,
                     secretKeys: Array.isArray(env.secretKeys) ? env.secretKeys : [],
-                    color: env.color || null
+                    color: env.color || null,
+                    ...(env.encrypted ? { encrypted: env.encrypted } : {})
                 }))
             };
         } catch (error) {
@@ -469,7 +486,8 @@ export class EnvironmentService {
                     name: env.name || 'Imported Environment',
                     variables: env.variables || {},
                     secretKeys: Array.isArray(env.secretKeys) ? env.secretKeys : [],
-                    color: this._normalizeColor(env.color)
+                    color: this._normalizeColor(env.color),
+                    ...(env.encrypted ? { encrypted: env.encrypted } : {})
                 }))
             };
 
@@ -40,7 +40,7 @@ export class HistoryService {
      * Creates a new history entry from request and response data
      *
      * Captures complete request/response state including method, URL, headers,
-     * body, status, timing, and size information.
+     * body, status, timing, and size information. Secret variable values are masked.
      *
      * @async
      * @param {Object} requestConfig - The request configuration
@@ -72,6 +72,14 @@ export class HistoryService {
                 rawUrl: requestConfig.rawUrl || requestConfig.url,
                 headers: requestConfig.headers || {},
                 body: requestConfig.body || null,
+                bodyType: requestConfig.bodyType || null,
+                auth: requestConfig.auth || null,
+                awsAuth: requestConfig.awsAuth || null,
+                clientCert: requestConfig.clientCert || null,
+                httpVersion: requestConfig.httpVersion || null,
+                timeout: requestConfig.timeout ?? null,
+                verifySsl: requestConfig.verifySsl ?? null,
+                followRedirects: requestConfig.followRedirects ?? null,
                 collectionId: currentEndpoint?.collectionId || null,
                 endpointId: currentEndpoint?.endpointId || null
             },
@@ -95,7 +103,27 @@ export class HistoryService {
             success: result.success || false
         };
 
-        return this.repository.add(historyEntry);
+        return this.repository.add(await this.maskSecrets(historyEntry));
+    }
+
+    /**
+     * Masks the values of secret variables in a history entry's request and response
+     *
+     * @private
+     * @async
+     * @param {Object} historyEntry - The history entry to mask
+     * @returns {Promise<Object>} The masked history entry
+     */
+    async maskSecrets(historyEntry) {
+        const backendAPI = this.repository.backendAPI;
+        if (!backendAPI?.secrets?.mask) {
+            return historyEntry;
+        }
+        const { request, response } = await backendAPI.secrets.mask(
+            { request: historyEntry.request, response: historyEntry.response },
+            historyEntry.request.collectionId
+        );
+        return { ...historyEntry, request, response };
     }
 
     /**
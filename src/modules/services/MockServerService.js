@@ -223,17 +223,18 @@ export class MockServerService {
     }
 
     /**
-     * Gets request logs
+     * Gets a page of request logs, newest first
      *
      * @async
      * @param {number} limit - Maximum number of logs to return
-     * @returns {Promise<Array>} Array of request log entries
+     * @param {number} offset - Number of newest logs to skip
+     * @returns {Promise<Object>} Page with logs, total (logs kept), received and capacity
      */
-    async getRequestLogs(limit = 20) {
+    async getRequestLogs(limit = 20, offset = 0) {
         try {
-            return await window.backendAPI.mockServer.logs(limit);
+            return await window.backendAPI.mockServer.logs(limit, offset);
         } catch (error) {
-            return [];
+            return { logs: [], total: 0, received: 0, capacity: 0 };
         }
     }
 
@@ -326,6 +327,63 @@ export class MockServerService {
         }
     }
 
+    /**
+     * Enables or disables a specific endpoint
+     *
+     * A running server picks the change up immediately; disabled endpoints answer 404.
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {boolean} enabled - Whether the endpoint should be served
+     * @returns {Promise<Object>} Updated settings
+     * @throws {Error} If update fails
+     */
+    async setEndpointEnabled(collectionId, endpointId, enabled) {
+        try {
+            const result = await this.repository.setEndpointEnabled(collectionId, endpointId, enabled);
+
+            const status = await this.getStatus();
+            if (status.running) {
+                await window.backendAPI.mockServer.setEndpointEnabled(collectionId, endpointId, enabled);
+            }
+
+            return result;
+        } catch (error) {
+            this.statusDisplay.update(`Error updating endpoint: ${error.message}`, null);
+            throw error;
+        }
+    }
+
+    /**
+     * Sets the default delay of every endpoint in a collection
+     *
+     * Endpoint delays take precedence over the collection delay.
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {number} delayMs - Delay in milliseconds (0 removes it)
+     * @returns {Promise<Object>} Updated settings
+     * @throws {Error} If validation fails or update fails
+     */
+    async setCollectionDelay(collectionId, delayMs) {
+        try {
+            const errors = this.validateDelay(delayMs);
+            if (errors.length > 0) {
+                throw new Error(errors.join(', '));
+            }
+
+            const result = await this.repository.setCollectionDelay(collectionId, delayMs);
+
+            await this._reloadServerSettings();
+
+            return result;
+        } catch (error) {
+            this.statusDisplay.update(`Error setting collection delay: ${error.message}`, null);
+            throw error;
+        }
+    }
+
     /**
      * Sets custom response for a specific endpoint
      *
@@ -377,6 +435,45 @@ export class MockServerService {
         return null;
     }
 
+    /**
+     * Selects the saved example an endpoint serves by default
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {string|null} exampleId - Saved example ID (null for the first successful one)
+     * @returns {Promise<Object>} Updated settings
+     * @throws {Error} If update fails
+     */
+    async setSelectedExample(collectionId, endpointId, exampleId) {
+        try {
+            const result = await this.repository.setSelectedExample(collectionId, endpointId, exampleId);
+
+            await this._reloadServerSettings();
+
+            return result;
+        } catch (error) {
+            this.statusDisplay.update(`Error selecting example: ${error.message}`, null);
+            throw error;
+        }
+    }
+
+    /**
+     * Sets how many request logs the server keeps
+     *
+     * @async
+     * @param {number} logSize - Number of request logs to keep
+     * @returns {Promise<Object>} Updated settings
+     * @throws {Error} If update fails
+     */
+    async setLogSize(logSize) {
+        const result = await this.updateSettings({ logSize });
+
+        await this._reloadServerSettings();
+
+        return result;
+    }
+
     /**
      * Sets custom status code for a specific endpoint
      *
@@ -466,6 +563,25 @@ export class MockServerService {
         return errors;
     }
 
+    /**
+     * Validates request log size
+     *
+     * @param {number|string} logSize - Number of request logs to keep
+     * @returns {Array<string>} Array of error messages (empty if valid)
+     */
+    validateLogSize(logSize) {
+        const errors = [];
+        const sizeNum = parseInt(logSize, 10);
+
+        if (isNaN(sizeNum)) {
+            errors.push('Log size must be a number');
+        } else if (sizeNum < 10 || sizeNum > 100000) {
+            errors.push('Log size must be between 10 and 100000');
+        }
+
+        return errors;
+    }
+
     /**
      * Validates delay value
      *
@@ -520,7 +636,7 @@ export class MockServerService {
         try {
             const status = await this.getStatus();
             if (status.running) {
-                await window.backendAPI.mockServer.reloadSettings();
+                await window.backendAPI.mockServer.reloadSettings(await this.repository.getSettings());
             }
         } catch (error) {
         }
@@ -36,7 +36,8 @@ export class RequestBuilderService {
      * @param {Object|null} currentEndpoint - { collectionId, endpointId } or null
      * @param {Object}      headers         - Mutable header map — collection
      *                                        defaultHeaders will be merged in-place
-     * @returns {Promise<{variables: Object, processor: VariableProcessor}>}
+     * @returns {Promise<{variables: Object, processor: VariableProcessor, variableOverrides: Object}>}
+     * The endpoint's variable overrides are merged into `variables`.
      */
     async resolveVariables(currentEndpoint, headers) {
         const variableService = this._getVariableService();
@@ -44,6 +45,7 @@ export class RequestBuilderService {
         processor.clearDynamicCache();
 
         let variables = {};
+        let variableOverrides = {};
 
         if (currentEndpoint) {
             const collection = await this._getCollectionRepository()
@@ -57,11 +59,29 @@ export class RequestBuilderService {
             variables = await variableService.getVariablesForCollection(
                 currentEndpoint.collectionId
             );
+            variableOverrides = this._findVariableOverrides(collection, currentEndpoint.endpointId);
+            variables = { ...variables, ...variableOverrides };
         } else {
             variables = await variableService.getVariables();
         }
 
-        return { variables, processor };
+        return { variables, processor, variableOverrides };
+    }
+
+    /**
+     * The variable overrides saved on an endpoint of a collection
+     *
+     * @private
+     * @param {Object|null} collection - The collection
+     * @param {string} endpointId - The endpoint ID
+     * @returns {Object<string, string>} Values by variable name
+     */
+    _findVariableOverrides(collection, endpointId) {
+        const endpoints = [
+            ...(collection?.endpoints || []),
+            ...(collection?.folders || []).flatMap(folder => folder.endpoints || [])
+        ];
+        return endpoints.find(ep => ep.id === endpointId)?.variableOverrides || {};
     }
 
     /**
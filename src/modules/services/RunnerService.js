@@ -11,6 +11,8 @@ import { CollectionRepository } from '../storage/CollectionRepository.js';
 import { CertificateRepository } from '../storage/CertificateRepository.js';
 import { CertificateService } from './CertificateService.js';
 import { normalizeFormRows } from '../utils/formDataRows.js';
+import { buildSoapEnvelope, mergeSoapHeaders } from '../soapBody.js';
+import { applyCommandToken, applyManagedToken } from '../auth/managedTokens.js';
 
 /**
  * Service for managing collection runner operations and execution
@@ -46,6 +48,8 @@ export class RunnerService {
         this.isRunning = false;
         this.shouldStop = false;
         this.currentRunId = null;
+        /** @type {string|null} Trace the run's `traceparent` headers join */
+        this.currentTraceId = null;
         this.listeners = [];
     }
 
@@ -167,6 +171,7 @@ export class RunnerService {
         this.isRunning = true;
         this.shouldStop = false;
         this.currentRunId = runnerId;
+        this.currentTraceId = crypto.randomUUID().replace(/-/g, '');
 
         const results = {
             runnerId,
@@ -227,6 +232,7 @@ export class RunnerService {
             this.isRunning = false;
             this.shouldStop = false;
             this.currentRunId = null;
+            this.currentTraceId = null;
 
             await this.repository.updateLastRun(runnerId);
 
@@ -256,6 +262,7 @@ export class RunnerService {
         this.isRunning = true;
         this.shouldStop = false;
         this.currentRunId = 'temp';
+        this.currentTraceId = crypto.randomUUID().replace(/-/g, '');
 
         const results = {
             runnerId: null,
@@ -316,6 +323,7 @@ export class RunnerService {
             this.isRunning = false;
             this.shouldStop = false;
             this.currentRunId = null;
+            this.currentTraceId = null;
 
             this._notifyListeners('run-completed', results);
         }
@@ -409,6 +417,7 @@ export class RunnerService {
 
             result.statusCode = response.status;
             result.responseTime = Date.now() - startTime;
+            result.traceIds = response.traceIds || null;
 
             if (response.success) {
                 result.status = 'success';
@@ -420,7 +429,8 @@ export class RunnerService {
                     status: response.status,
                     statusText: response.statusText,
                     headers: response.headers,
-                    body: response.data
+                    body: response.data,
+                    timings: response.timings
                 };
 
                 if (request.postResponseScript) {
@@ -535,6 +545,8 @@ export class RunnerService {
         if (effectiveAuthConfig?.type === 'inherit') {
             effectiveAuthConfig = await this.collectionRepository.getInheritedAuthConfig(collection.id, endpoint.id) || null;
         }
+        effectiveAuthConfig = await applyManagedToken(effectiveAuthConfig, this.backendAPI.oauth2);
+        effectiveAuthConfig = await applyCommandToken(effectiveAuthConfig, this.backendAPI.commandAuth, collection.id);
         
         let url = endpoint.path;
         
@@ -544,7 +556,8 @@ export class RunnerService {
 
         const effectiveVariables = {
             ...variables,
-            baseUrl: variables.baseUrl || collection.baseUrl || ''
+            baseUrl: variables.baseUrl || collection.baseUrl || '',
+            ...endpoint.variableOverrides
         };
 
         if (effectivePathParams.length > 0) {
@@ -635,6 +648,11 @@ export class RunnerService {
                 };
                 bodyType = 'binary';
             }
+        } else if (!overrideBody && persistedFormBodyData && persistedFormBodyData.mode === 'soap') {
+            const envelope = await buildSoapEnvelope(persistedFormBodyData, this.variableProcessor, effectiveVariables);
+            body = envelope.body;
+            bodyType = 'text';
+            mergeSoapHeaders(processedHeaders, envelope.headers);
         } else if (overrideBody || ['POST', 'PUT', 'PATCH'].includes(endpoint.method)) {
             let bodyContent = overrideBody || persistedBody;
             if (!bodyContent && endpoint.requestBody) {
@@ -666,9 +684,15 @@ export class RunnerService {
 
         let httpVersion = 'auto';
         let timeout = 30000;
+        let ipFamily = endpoint.ipFamily || null;
+        let verifySsl = endpoint.ignoreTlsErrors !== true;
+        let traceHeaders = false;
         try {
             const settings = await this.backendAPI.settings.get();
             httpVersion = settings.httpVersion || 'auto';
+            verifySsl = verifySsl && settings.verifySsl !== false;
+            ipFamily = ipFamily || settings.ipFamily || null;
+            traceHeaders = settings.traceHeaders === true;
             const savedTimeout = settings.requestTimeout ?? settings.timeout;
             timeout = savedTimeout === 0 ? null : (savedTimeout ?? 30000);
         } catch (e) {
@@ -690,9 +714,14 @@ export class RunnerService {
             bodyType,
             httpVersion,
             timeout,
+            verifySsl,
+            ipFamily,
             auth: authData.authConfig,
             awsAuth: authData.awsAuth || null,
-            clientCert
+            clientCert,
+            collectionId: collection.id,
+            traceHeaders,
+            traceId: this.currentTraceId
         };
     }
 
@@ -725,7 +754,8 @@ export class RunnerService {
                     status: response.status,
                     statusText: response.statusText,
                     headers: response.headers || {},
-                    body: response.body
+                    body: response.body,
+                    timings: response.timings || {}
                 },
                 environment: currentVariables
             };
@@ -817,6 +847,13 @@ export class RunnerService {
                 }
                 break;
 
+            case 'command':
+                if (config.token) {
+                    const prefix = config.headerPrefix ?? 'Bearer';
+                    authData.headers[config.headerName || 'Authorization'] = prefix ? `${prefix} ${config.token}` : config.token;
+                }
+                break;
+
             case 'digest':
                 if (config.username || config.password) {
                     authData.authConfig = {
@@ -826,6 +863,10 @@ export class RunnerService {
                 }
                 break;
 
+            case 'negotiate':
+                authData.authConfig = { username: '', password: '', authType: 'negotiate' };
+                break;
+
             case 'aws-v4':
                 if (config.accessKeyId && config.secretAccessKey) {
                     authData.awsAuth = {
@@ -59,21 +59,15 @@ export class ScriptService {
      * Execute a pre-request script
      * @param {string} script - The script code
      * @param {Object} requestConfig - Request configuration
+     * @param {string|null} [collectionId=null] - Collection whose secret variables are masked in the output
      * @returns {Promise<Object>} Modified request config and execution result
      */
-    async executePreRequestScript(script, requestConfig) {
-        if (!script || script.trim() === '') {
-            return {
-                modifiedRequest: requestConfig,
-                result: { success: true, logs: [], errors: [], testResults: [] }
-            };
-        }
-
+    async executePreRequestScript(script, requestConfig, collectionId = null) {
         try {
             const environmentVariables = await this.environmentService.getActiveEnvironmentVariables();
 
             const scriptData = {
-                script,
+                script: script || '',
                 request: {
                     url: requestConfig.url,
                     method: requestConfig.method,
@@ -85,7 +79,7 @@ export class ScriptService {
                 environment: environmentVariables || {}
             };
 
-            const result = await window.backendAPI.scripts.executePreRequest(scriptData);
+            const result = await window.backendAPI.scripts.executePreRequest(scriptData, collectionId);
 
             if (result.modifiedEnvironment && Object.keys(result.modifiedEnvironment).length > 0) {
                 await this._applyEnvironmentChanges(result.modifiedEnvironment);
@@ -114,18 +108,10 @@ export class ScriptService {
      * @param {string} script - The script code
      * @param {Object} requestConfig - Request configuration
      * @param {Object} response - Response data
+     * @param {string|null} [collectionId=null] - Collection whose secret variables are masked in the output
      * @returns {Promise<Object>} Execution result
      */
-    async executeTestScript(script, requestConfig, response) {
-        if (!script || script.trim() === '') {
-            return {
-                success: true,
-                logs: [],
-                errors: [],
-                testResults: []
-            };
-        }
-
+    async executeTestScript(script, requestConfig, response, collectionId = null) {
         try {
             const environmentVariables = await this.environmentService.getActiveEnvironmentVariables();
 
@@ -137,7 +123,7 @@ export class ScriptService {
             const cookies = response?.cookies || [];
 
             const scriptData = {
-                script,
+                script: script || '',
                 request: {
                     url: requestConfig.url,
                     method: requestConfig.method,
@@ -157,7 +143,7 @@ export class ScriptService {
                 environment: environmentVariables || {}
             };
 
-            const result = await window.backendAPI.scripts.executeTest(scriptData);
+            const result = await window.backendAPI.scripts.executeTest(scriptData, collectionId);
 
             if (result.modifiedEnvironment && Object.keys(result.modifiedEnvironment).length > 0) {
                 await this._applyEnvironmentChanges(result.modifiedEnvironment);
@@ -0,0 +1,86 @@
+/**
+ * @fileoverview SOAP body mode: the backend builds the envelope from the
+ * operation and its JSON parameters, and unwraps the Body or Fault of the
+ * response into JSON for display.
+ * @module soapBody
+ */
+
+/**
+ * Builds the SOAP envelope and headers of a request
+ *
+ * @async
+ * @param {{operation: string, namespace: string, soapAction: string, version: string, params: string}} soap
+ *   SOAP body fields as edited
+ * @param {Object} processor - Variable processor
+ * @param {Object} variables - Resolved variables
+ * @returns {Promise<{body: string, headers: Object<string, string>}>}
+ * @throws {Error} When the parameters are not valid JSON or the envelope can't be built
+ */
+export async function buildSoapEnvelope(soap, processor, variables) {
+    const resolve = (text) => processor.processTemplate(text || '', variables);
+
+    let params = {};
+    const paramsText = resolve(soap.params).trim();
+    if (paramsText) {
+        try {
+            params = JSON.parse(paramsText);
+        } catch (e) {
+            throw new Error(`Invalid SOAP parameters JSON: ${e.message}`);
+        }
+    }
+
+    try {
+        return await window.backendAPI.soap.buildEnvelope({
+            operation: resolve(soap.operation),
+            namespace: resolve(soap.namespace),
+            soapAction: resolve(soap.soapAction),
+            version: soap.version || '1.1',
+            params
+        });
+    } catch (e) {
+        throw new Error(`SOAP envelope error: ${e?.message || e}`);
+    }
+}
+
+/**
+ * Adds the SOAP headers to the request headers, keeping any the user set
+ *
+ * @param {Object<string, string>} headers - Request headers, updated in place
+ * @param {Object<string, string>} soapHeaders - Headers of the envelope
+ * @returns {void}
+ */
+export function mergeSoapHeaders(headers, soapHeaders) {
+    const present = new Set(Object.keys(headers).map(name => name.toLowerCase()));
+    Object.entries(soapHeaders || {}).forEach(([name, value]) => {
+        if (!present.has(name.toLowerCase())) {
+            headers[name] = value;
+        }
+    });
+}
+
+/**
+ * Unwraps a SOAP response body for the code view
+ *
+ * @async
+ * @param {*} data - Response body as received from the backend
+ * @returns {Promise<{text: string, languageHint: string}|null>} The Body or
+ *   Fault as JSON, or null when the response is not a SOAP envelope
+ */
+export async function unwrapSoapResponse(data) {
+    if (typeof data !== 'string') {
+        return null;
+    }
+    try {
+        const unwrapped = await window.backendAPI.soap.unwrapResponse(data);
+        const view = unwrapped.fault
+            ? { fault: unwrapped.fault }
+            : { body: unwrapped.body };
+        if (unwrapped.header) {
+            view.header = unwrapped.header;
+        }
+        return { text: JSON.stringify(view, null, 2), languageHint: 'json' };
+    } catch (error) {
+        void error;
+        return null;
+    }
+}
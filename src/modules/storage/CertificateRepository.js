@@ -103,6 +103,7 @@ export class CertificateRepository {
             certPath: typeof entry.certPath === 'string' ? entry.certPath.trim() : '',
             keyPath: typeof entry.keyPath === 'string' ? entry.keyPath.trim() : '',
             caPath: typeof entry.caPath === 'string' ? entry.caPath.trim() : '',
+            pins: typeof entry.pins === 'string' ? entry.pins.trim() : '',
             enabled: entry.enabled !== false
         };
     }
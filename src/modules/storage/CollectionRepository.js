@@ -60,6 +60,16 @@ export class CollectionRepository {
         this._byIdCache.set(id, collection);
     }
 
+    /**
+     * Drops a collection from this instance's cache so the next read goes to
+     * the backend, e.g. after the collection files were changed on disk.
+     *
+     * @param {string} id - The collection ID
+     */
+    invalidate(id) {
+        this._byIdCache.delete(id);
+    }
+
     /**
      * Retrieves all collections from storage
      *
@@ -567,6 +577,81 @@ export class CollectionRepository {
         }
     }
 
+    /**
+     * Saves the collection's proxy override, or removes it when `proxy` is
+     * null so the collection follows the global proxy settings again.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {Object|null} proxy - The override ({mode, server, auth, bypassList})
+     * @returns {Promise<void>}
+     * @throws {Error} If save operation fails
+     */
+    async saveCollectionProxy(collectionId, proxy) {
+        try {
+            await this._getByIdFresh(collectionId);
+            await this.update(collectionId, { proxy });
+        } catch (error) {
+            throw new Error(`Failed to save collection proxy: ${error.message || error}`);
+        }
+    }
+
+    /**
+     * Saves the collection's login flow, or removes it when `loginFlow` is null.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {Object|null} loginFlow - The flow ({endpointId, source, expression, variable, retryOnUnauthorized})
+     * @returns {Promise<void>}
+     * @throws {Error} If save operation fails
+     */
+    async saveCollectionLoginFlow(collectionId, loginFlow) {
+        try {
+            await this._getByIdFresh(collectionId);
+            await this.update(collectionId, { loginFlow });
+        } catch (error) {
+            throw new Error(`Failed to save collection login flow: ${error.message || error}`);
+        }
+    }
+
+    /**
+     * Saves the collection's safety policy, or removes it when `safetyPolicy`
+     * is null.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {Object|null} safetyPolicy - The policy ({methods, hostPatterns})
+     * @returns {Promise<void>}
+     * @throws {Error} If save operation fails
+     */
+    async saveCollectionSafetyPolicy(collectionId, safetyPolicy) {
+        try {
+            await this._getByIdFresh(collectionId);
+            await this.update(collectionId, { safetyPolicy });
+        } catch (error) {
+            throw new Error(`Failed to save collection safety policy: ${error.message || error}`);
+        }
+    }
+
+    /**
+     * Saves the collection's default gRPC metadata, or removes it when
+     * `grpcMetadata` is null.
+     *
+     * @async
+     * @param {string} collectionId - The collection ID
+     * @param {Object|null} grpcMetadata - The defaults ({all, methods})
+     * @returns {Promise<void>}
+     * @throws {Error} If save operation fails
+     */
+    async saveCollectionGrpcMetadata(collectionId, grpcMetadata) {
+        try {
+            await this._getByIdFresh(collectionId);
+            await this.update(collectionId, { grpcMetadata });
+        } catch (error) {
+            throw new Error(`Failed to save collection gRPC metadata: ${error.message || error}`);
+        }
+    }
+
     /**
      * Reads a collection directly from the backend, bypassing (and refreshing)
      * this instance's LRU cache. Several repository instances exist at runtime
@@ -1,109 +1,45 @@
 /**
- * @fileoverview Repository for managing cookie jar persistence
+ * @fileoverview Repository for the cookie jar, which the backend keeps and
+ * applies to requests
  * @module storage/CookieRepository
  */
 
 export class CookieRepository {
     constructor(backendAPI) {
         this.backendAPI = backendAPI;
-        this.COOKIE_JAR_KEY = 'cookieJar';
-    }
-
-    async _getArrayFromStore() {
-        try {
-            let data = await this.backendAPI.store.get(this.COOKIE_JAR_KEY);
-            if (!Array.isArray(data)) {
-                data = [];
-                await this.backendAPI.store.set(this.COOKIE_JAR_KEY, data);
-            }
-            return await this._migrateLegacyIds(data);
-        } catch (_e) {
-            return [];
-        }
-    }
-
-    /**
-     * Rewrites cookie ids from the legacy `domain|path|name` format to the
-     * environment-scoped `environmentId|domain|path|name` format, deduplicating
-     * by keeping the most recently updated entry, and persists the result if
-     * anything changed.
-     */
-    async _migrateLegacyIds(cookies) {
-        let changed = false;
-        const byId = new Map();
-        for (const cookie of cookies) {
-            const envId = cookie.environmentId || 'default';
-            const id = `${envId}|${cookie.domain}|${cookie.path}|${cookie.name}`;
-            const migrated = id === cookie.id ? cookie : { ...cookie, id, environmentId: envId };
-            if (migrated !== cookie) {
-                changed = true;
-            }
-            const existing = byId.get(id);
-            if (!existing || (migrated.updatedAt || 0) > (existing.updatedAt || 0)) {
-                byId.set(id, migrated);
-            }
-        }
-        if (byId.size !== cookies.length) {
-            changed = true;
-        }
-        if (!changed) {
-            return cookies;
-        }
-        const result = [...byId.values()];
-        await this._save(result);
-        return result;
-    }
-
-    async _save(cookies) {
-        await this.backendAPI.store.set(this.COOKIE_JAR_KEY, cookies);
     }
 
     /**
      * Returns all cookies for an environment (or all if environmentId is omitted).
      */
     async getAll(environmentId) {
-        const cookies = await this._getArrayFromStore();
-        if (environmentId === undefined) {
-            return cookies;
+        try {
+            return await this.backendAPI.cookies.list(environmentId ?? null);
+        } catch (_e) {
+            return [];
         }
-        return cookies.filter(c => c.environmentId === environmentId);
     }
 
     /**
-     * Insert or update a cookie. ID is `${domain}|${path}|${name}`.
+     * Insert or update a cookie, identified by environment, domain, path and name.
      */
     async upsert(cookie) {
-        const cookies = await this._getArrayFromStore();
-        const idx = cookies.findIndex(c => c.id === cookie.id);
-        if (idx >= 0) {
-            cookies[idx] = { ...cookies[idx], ...cookie, updatedAt: Date.now() };
-        } else {
-            cookies.push({ ...cookie, createdAt: Date.now(), updatedAt: Date.now() });
-        }
-        await this._save(cookies);
+        return this.backendAPI.cookies.set(cookie);
     }
 
     async delete(id) {
-        const cookies = await this._getArrayFromStore();
-        await this._save(cookies.filter(c => c.id !== id));
+        await this.backendAPI.cookies.delete(id);
     }
 
     async deleteAll(environmentId) {
-        const cookies = await this._getArrayFromStore();
-        await this._save(cookies.filter(c => c.environmentId !== environmentId));
+        const cookies = await this.getAll(environmentId);
+        const domains = new Set(cookies.map(c => c.domain));
+        for (const domain of domains) {
+            await this.backendAPI.cookies.clearDomain(domain, environmentId);
+        }
     }
 
     async deleteByDomain(domain, environmentId) {
-        const cookies = await this._getArrayFromStore();
-        await this._save(cookies.filter(c => !(c.domain === domain && c.environmentId === environmentId)));
-    }
-
-    /**
-     * Remove cookies whose absolute expiry has passed.
-     */
-    async deleteExpired() {
-        const now = Date.now();
-        const cookies = await this._getArrayFromStore();
-        await this._save(cookies.filter(c => c.expires === null || c.expires > now));
+        await this.backendAPI.cookies.clearDomain(domain, environmentId);
     }
 }
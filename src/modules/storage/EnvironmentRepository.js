@@ -29,6 +29,14 @@ export class EnvironmentRepository {
         this._cache = null;
     }
 
+    /**
+     * Drops the cached environments so the next read goes to the store, e.g.
+     * after the backend changed a variable.
+     */
+    invalidate() {
+        this._cache = null;
+    }
+
     /**
      * Builds the SecretStore scope string for an environment.
      *
@@ -48,13 +56,18 @@ export class EnvironmentRepository {
      */
     _normalizeEnvironment(environment) {
         const variables = environment?.variables && typeof environment.variables === 'object' ? environment.variables : {};
-        return {
+        const normalized = {
             id: environment?.id,
             name: environment?.name || 'Environment',
             variables: variables,
             secretKeys: this._normalizeSecretKeys(environment?.secretKeys, variables),
             color: this._normalizeColor(environment?.color)
         };
+        // Passphrase-encrypted values; only the backend can open this blob
+        if (environment?.encrypted && typeof environment.encrypted === 'object') {
+            normalized.encrypted = environment.encrypted;
+        }
+        return normalized;
     }
 
     /**
@@ -505,6 +518,7 @@ export class EnvironmentRepository {
      * Returns an environment's variable map with secret values merged back in from
      * the SecretStore. The stored `variables` map holds empty placeholders for secret
      * keys; this resolves them for request building, the runner, and scripts.
+     * Values of an encrypted environment are merged in while it is unlocked.
      *
      * Used only on resolution paths — never on the editor read paths
      * (`getAllEnvironments`/`getEnvironmentById`) which must stay masked.
@@ -515,6 +529,10 @@ export class EnvironmentRepository {
      */
     async _hydrateSecrets(environment) {
         const variables = { ...(environment.variables || {}) };
+        if (environment.encrypted && this.backendAPI.environments?.unlockedVariables) {
+            const unlocked = await this.backendAPI.environments.unlockedVariables(environment.id);
+            Object.assign(variables, unlocked || {});
+        }
         const secretKeys = Array.isArray(environment.secretKeys) ? environment.secretKeys : [];
         if (!this.secretStore || secretKeys.length === 0) {
             return variables;
@@ -8,7 +8,8 @@
  *
  * @class
  * @classdesc Handles CRUD operations for mock server settings with comprehensive validation
- * in the persistent store. Supports port configuration, enabled collections, and per-endpoint delays.
+ * in the persistent store. Supports port configuration, enabled collections, per-collection and
+ * per-endpoint delays, and per-endpoint enable flags.
  * Implements defensive programming with auto-initialization for packaged app compatibility.
  */
 export class MockServerRepository {
@@ -33,8 +34,12 @@ export class MockServerRepository {
      * @returns {Promise<number>} return.port - Server port (1024-65535)
      * @returns {Promise<Array<string>>} return.enabledCollections - Array of enabled collection IDs
      * @returns {Promise<Object>} return.endpointDelays - Per-endpoint delays in milliseconds
+     * @returns {Promise<Object>} return.collectionDelays - Per-collection default delays in milliseconds
      * @returns {Promise<Object>} return.customResponses - Per-endpoint custom response bodies
      * @returns {Promise<Object>} return.customStatusCodes - Per-endpoint custom status codes
+     * @returns {Promise<Object>} return.endpointEnabled - Per-endpoint enable flags (missing means enabled)
+     * @returns {Promise<Object>} return.selectedExamples - Per-endpoint saved example IDs served by default
+     * @returns {Promise<number>} return.logSize - Request logs kept while the server runs
      * @throws {Error} If storage access fails
      */
     async getSettings() {
@@ -60,6 +65,10 @@ export class MockServerRepository {
                 validatedData.endpointDelays = {};
             }
 
+            if (!validatedData.collectionDelays || typeof validatedData.collectionDelays !== 'object') {
+                validatedData.collectionDelays = {};
+            }
+
             if (!validatedData.customResponses || typeof validatedData.customResponses !== 'object') {
                 validatedData.customResponses = {};
             }
@@ -68,6 +77,18 @@ export class MockServerRepository {
                 validatedData.customStatusCodes = {};
             }
 
+            if (!validatedData.endpointEnabled || typeof validatedData.endpointEnabled !== 'object') {
+                validatedData.endpointEnabled = {};
+            }
+
+            if (!validatedData.selectedExamples || typeof validatedData.selectedExamples !== 'object') {
+                validatedData.selectedExamples = {};
+            }
+
+            if (!this._validateLogSize(validatedData.logSize)) {
+                validatedData.logSize = this._getDefaultSettings().logSize;
+            }
+
             return validatedData;
         } catch (error) {
             throw new Error(`Failed to load mock server settings: ${error.message}`);
@@ -125,6 +146,13 @@ export class MockServerRepository {
                 };
             }
 
+            if (updates.collectionDelays) {
+                updatedSettings.collectionDelays = {
+                    ...currentSettings.collectionDelays,
+                    ...updates.collectionDelays
+                };
+            }
+
             if (updates.customResponses) {
                 updatedSettings.customResponses = {
                     ...currentSettings.customResponses,
@@ -139,6 +167,20 @@ export class MockServerRepository {
                 };
             }
 
+            if (updates.endpointEnabled) {
+                updatedSettings.endpointEnabled = {
+                    ...currentSettings.endpointEnabled,
+                    ...updates.endpointEnabled
+                };
+            }
+
+            if (updates.selectedExamples) {
+                updatedSettings.selectedExamples = {
+                    ...currentSettings.selectedExamples,
+                    ...updates.selectedExamples
+                };
+            }
+
             return await this.saveSettings(updatedSettings);
         } catch (error) {
             throw new Error(`Failed to update mock server settings: ${error.message}`);
@@ -194,6 +236,35 @@ export class MockServerRepository {
         }
     }
 
+    /**
+     * Sets the default delay for every endpoint of a collection
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {number} delayMs - Delay in milliseconds (0-30000, 0 removes it)
+     * @returns {Promise<Object>} The updated settings object
+     * @throws {Error} If delay is invalid or save fails
+     */
+    async setCollectionDelay(collectionId, delayMs) {
+        try {
+            if (!this._validateDelay(delayMs)) {
+                throw new Error('Delay must be between 0 and 30000 milliseconds');
+            }
+
+            const settings = await this.getSettings();
+
+            if (delayMs === 0) {
+                delete settings.collectionDelays[collectionId];
+            } else {
+                settings.collectionDelays[collectionId] = delayMs;
+            }
+
+            return await this.saveSettings(settings);
+        } catch (error) {
+            throw new Error(`Failed to set collection delay: ${error.message}`);
+        }
+    }
+
     /**
      * Sets custom response for a specific endpoint
      *
@@ -284,6 +355,80 @@ export class MockServerRepository {
         }
     }
 
+    /**
+     * Selects the saved example an endpoint serves when it has no custom response
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {string|null} exampleId - Saved example ID (null for the first successful one)
+     * @returns {Promise<Object>} The updated settings object
+     * @throws {Error} If save fails
+     */
+    async setSelectedExample(collectionId, endpointId, exampleId) {
+        try {
+            const settings = await this.getSettings();
+            const key = `${collectionId}_${endpointId}`;
+
+            if (exampleId === null) {
+                delete settings.selectedExamples[key];
+            } else {
+                settings.selectedExamples[key] = exampleId;
+            }
+
+            return await this.saveSettings(settings);
+        } catch (error) {
+            throw new Error(`Failed to select example: ${error.message}`);
+        }
+    }
+
+    /**
+     * Enables or disables a specific endpoint
+     *
+     * Only disabled endpoints are stored; enabling removes the entry.
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {boolean} enabled - Whether the endpoint should be served
+     * @returns {Promise<Object>} The updated settings object
+     * @throws {Error} If save fails
+     */
+    async setEndpointEnabled(collectionId, endpointId, enabled) {
+        try {
+            const settings = await this.getSettings();
+            const key = `${collectionId}_${endpointId}`;
+
+            if (enabled) {
+                delete settings.endpointEnabled[key];
+            } else {
+                settings.endpointEnabled[key] = false;
+            }
+
+            return await this.saveSettings(settings);
+        } catch (error) {
+            throw new Error(`Failed to set endpoint enabled state: ${error.message}`);
+        }
+    }
+
+    /**
+     * Checks if a specific endpoint is enabled
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @returns {Promise<boolean>} False only if the endpoint was disabled
+     */
+    async isEndpointEnabled(collectionId, endpointId) {
+        try {
+            const settings = await this.getSettings();
+            const key = `${collectionId}_${endpointId}`;
+            return settings.endpointEnabled[key] !== false;
+        } catch (error) {
+            return true;
+        }
+    }
+
     /**
      * Toggles collection enabled state
      *
@@ -360,8 +505,12 @@ export class MockServerRepository {
                 ? settings.enabledCollections.filter(id => typeof id === 'string' && id.trim())
                 : defaults.enabledCollections,
             endpointDelays: this._validateEndpointDelays(settings.endpointDelays),
+            collectionDelays: this._validateEndpointDelays(settings.collectionDelays),
             customResponses: this._validateCustomResponses(settings.customResponses),
-            customStatusCodes: this._validateCustomStatusCodes(settings.customStatusCodes)
+            customStatusCodes: this._validateCustomStatusCodes(settings.customStatusCodes),
+            endpointEnabled: this._validateEndpointEnabled(settings.endpointEnabled),
+            selectedExamples: this._validateSelectedExamples(settings.selectedExamples),
+            logSize: this._validateLogSize(settings.logSize) ? parseInt(settings.logSize, 10) : defaults.logSize
         };
     }
 
@@ -377,6 +526,18 @@ export class MockServerRepository {
         return !isNaN(portNum) && portNum >= 1024 && portNum <= 65535;
     }
 
+    /**
+     * Validates request log size
+     *
+     * @private
+     * @param {number|string} size - Number of request logs to keep
+     * @returns {boolean} True if size is valid (10-100000)
+     */
+    _validateLogSize(size) {
+        const sizeNum = parseInt(size, 10);
+        return !isNaN(sizeNum) && sizeNum >= 10 && sizeNum <= 100000;
+    }
+
     /**
      * Validates delay value
      *
@@ -455,6 +616,50 @@ export class MockServerRepository {
         return validatedStatusCodes;
     }
 
+    /**
+     * Validates and sanitizes endpoint enable flags, keeping only disabled entries
+     *
+     * @private
+     * @param {Object} flags - Endpoint enable flags object to validate
+     * @returns {Object} Validated endpoint enable flags object
+     */
+    _validateEndpointEnabled(flags) {
+        if (!flags || typeof flags !== 'object') {
+            return {};
+        }
+
+        const validatedFlags = {};
+        for (const [key, value] of Object.entries(flags)) {
+            if (typeof key === 'string' && value === false) {
+                validatedFlags[key] = false;
+            }
+        }
+
+        return validatedFlags;
+    }
+
+    /**
+     * Validates and sanitizes selected example IDs
+     *
+     * @private
+     * @param {Object} selections - Selected examples object to validate
+     * @returns {Object} Validated selected examples object
+     */
+    _validateSelectedExamples(selections) {
+        if (!selections || typeof selections !== 'object') {
+            return {};
+        }
+
+        const validatedSelections = {};
+        for (const [key, value] of Object.entries(selections)) {
+            if (typeof key === 'string' && typeof value === 'string' && value) {
+                validatedSelections[key] = value;
+            }
+        }
+
+        return validatedSelections;
+    }
+
     /**
      * Validates status code
      *
@@ -478,8 +683,12 @@ export class MockServerRepository {
             port: 3000,
             enabledCollections: [],
             endpointDelays: {},
+            collectionDelays: {},
             customResponses: {},
-            customStatusCodes: {}
+            customStatusCodes: {},
+            endpointEnabled: {},
+            selectedExamples: {},
+            logSize: 1000
         };
     }
 }
@@ -36,6 +36,8 @@ export class ProxyRepository {
      * @returns {Promise<string>} return.type - Proxy type (http, https, socks4, socks5)
      * @returns {Promise<string>} return.host - Proxy host
      * @returns {Promise<number>} return.port - Proxy port (1-65535)
+     * @returns {Promise<Object|null>} return.httpsProxy - Proxy for https:// URLs ({ type, host, port })
+     * @returns {Promise<Object|null>} return.grpcProxy - Proxy for gRPC connections ({ type, host, port })
      * @returns {Promise<Object>} return.auth - Authentication settings
      * @returns {Promise<Array<string>>} return.bypassList - Domains to bypass proxy
      * @returns {Promise<number>} return.timeout - Request timeout in milliseconds
@@ -200,6 +202,8 @@ export class ProxyRepository {
             type: this._validateProxyType(settings.type) ? settings.type : defaults.type,
             host: this._sanitizeHost(settings.host),
             port: this._validatePort(settings.port) ? settings.port : defaults.port,
+            httpsProxy: this._validateServer(settings.httpsProxy),
+            grpcProxy: this._validateServer(settings.grpcProxy),
             auth: {
                 enabled: typeof settings.auth?.enabled === 'boolean'
                     ? settings.auth.enabled
@@ -230,6 +234,24 @@ export class ProxyRepository {
         return validTypes.includes(type);
     }
 
+    /**
+     * Validates a scheme-specific proxy server
+     *
+     * @private
+     * @param {Object|null} server - Server object ({ type, host, port })
+     * @returns {Object|null} Sanitized server, or null when unset or invalid
+     */
+    _validateServer(server) {
+        if (!server || typeof server !== 'object') {return null;}
+        const host = this._sanitizeHost(server.host);
+        if (!host || !this._validatePort(server.port)) {return null;}
+        return {
+            type: this._validateProxyType(server.type) ? server.type : 'http',
+            host,
+            port: parseInt(server.port, 10)
+        };
+    }
+
     /**
      * Validates port number
      *
@@ -281,6 +303,8 @@ export class ProxyRepository {
             type: 'http',
             host: '',
             port: 8080,
+            httpsProxy: null,
+            grpcProxy: null,
             auth: {
                 enabled: false,
                 username: '',
@@ -543,7 +543,7 @@ export class CollectionDialogs {
         });
     }
 
-    async showCollectionImportDialog({ importKind }) {
+    async showCollectionImportDialog({ importKind, filePath = '' }) {
         const defaultStoragePath = await this.backendAPI.collections.getPath().catch(() => '');
 
         return new Promise((resolve) => {
@@ -586,6 +586,15 @@ export class CollectionDialogs {
             } else if (importKind === 'postman') {
                 titleElement.textContent = t('import_dialog.title_postman', 'Import Postman Collection');
                 subtitleElement.textContent = t('import_dialog.subtitle_postman', 'Choose a Postman file and where the collection should be stored.');
+            } else if (importKind === 'postman_dump') {
+                titleElement.textContent = t('import_dialog.title_postman_dump', 'Import Postman Data Dump');
+                subtitleElement.textContent = t('import_dialog.subtitle_postman_dump', 'Choose the zip from Postman\'s "Export data" and where its collections should be stored.');
+            } else if (importKind === 'har') {
+                titleElement.textContent = t('import_dialog.title_har', 'Import HAR File');
+                subtitleElement.textContent = t('import_dialog.subtitle_har', 'Choose a HAR file recorded by browser dev tools or a proxy and where the collection should be stored.');
+            } else if (importKind === 'http') {
+                titleElement.textContent = t('import_dialog.title_http', 'Import .http File');
+                subtitleElement.textContent = t('import_dialog.subtitle_http', 'Choose a REST Client or JetBrains .http file and where the collection should be stored.');
             }
 
             const setError = (message = '') => {
@@ -615,7 +624,7 @@ export class CollectionDialogs {
                 destinationCard.classList.toggle('is-selected', Boolean(path));
             };
 
-            setSourceFile('');
+            setSourceFile(filePath);
             setDestinationFolder(selectedStoragePath);
 
             const closeDialog = async (result = null) => {
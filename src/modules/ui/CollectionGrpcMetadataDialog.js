@@ -0,0 +1,152 @@
+/**
+ * @fileoverview Modal dialog for editing a collection's default gRPC
+ * metadata: entries sent with every method, or with one service or method.
+ * @module ui/CollectionGrpcMetadataDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+import { toast } from './Toast.js';
+
+/**
+ * Formats the defaults as `key: value` lines, with a `[scope]` header before
+ * the entries of each service or method.
+ *
+ * @param {Object|null} grpcMetadata - The defaults ({all, methods})
+ * @returns {string}
+ */
+function formatEntries(grpcMetadata) {
+    const block = entries => Object.entries(entries || {}).map(([key, value]) => `${key}: ${value}`);
+    const sections = [block(grpcMetadata?.all)];
+    Object.entries(grpcMetadata?.methods || {}).forEach(([scope, entries]) => {
+        sections.push([`[${scope}]`, ...block(entries)]);
+    });
+    return sections.filter(lines => lines.length > 0).map(lines => lines.join('\n')).join('\n\n');
+}
+
+/**
+ * Parses the lines written by {@link formatEntries}.
+ *
+ * @param {string} text
+ * @returns {Object|null} The defaults ({all, methods}), or null when empty
+ * @throws {Error} On a line that is neither a header nor `key: value`
+ */
+function parseEntries(text) {
+    const all = {};
+    const methods = {};
+    let current = all;
+    text.split('\n').forEach((raw, index) => {
+        const line = raw.trim();
+        if (!line || line.startsWith('#')) {
+            return;
+        }
+        const header = line.match(/^\[(.+)\]$/);
+        if (header) {
+            const scope = header[1].trim();
+            current = methods[scope] || (methods[scope] = {});
+            return;
+        }
+        const separator = line.indexOf(':');
+        const key = separator > 0 ? line.slice(0, separator).trim().toLowerCase() : '';
+        if (!key) {
+            throw new Error(`Line ${index + 1}: expected "key: value"`);
+        }
+        current[key] = line.slice(separator + 1).trim();
+    });
+    const hasMethods = Object.values(methods).some(entries => Object.keys(entries).length > 0);
+    if (Object.keys(all).length === 0 && !hasMethods) {
+        return null;
+    }
+    return { all, methods };
+}
+
+/**
+ * Collection-scoped default gRPC metadata editor.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class CollectionGrpcMetadataDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog for a collection.
+     *
+     * @param {Object} collection - The collection ({id, name, grpcMetadata, ...})
+     * @returns {Promise<{grpcMetadata: Object|null}|null>} The edited defaults
+     *   (null defaults means "none"), or null on cancel
+     */
+    show(collection) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+            this._createDialog(collection);
+        });
+    }
+
+    /**
+     * Builds the dialog and fills it from the collection's current defaults.
+     *
+     * @private
+     * @param {Object} collection
+     * @returns {void}
+     */
+    _createDialog(collection) {
+        const dialog = this.mount({
+            overlayClass: 'collection-grpc-metadata-dialog-overlay',
+            dialogClass: 'collection-grpc-metadata-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/collectionGrpcMetadata.html',
+            templateId: 'tpl-collection-grpc-metadata-dialog'
+        });
+
+        const titleEl = dialog.querySelector('[data-role="title"]');
+        if (titleEl) {
+            titleEl.textContent = `gRPC Metadata — ${collection.name}`;
+        }
+
+        const entriesInput = dialog.querySelector('[name="grpcMetadata"]');
+        entriesInput.value = formatEntries(collection.grpcMetadata || null);
+
+        dialog.querySelector('#collection-grpc-metadata-close-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-grpc-metadata-cancel-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-grpc-metadata-save-btn')?.addEventListener('click', () => {
+            try {
+                this._settle({ grpcMetadata: parseEntries(entriesInput.value) });
+            } catch (error) {
+                toast.error(error.message);
+            }
+        });
+    }
+
+    /**
+     * Resolves the pending promise and tears down.
+     *
+     * @private
+     * @param {Object|null} result
+     * @returns {void}
+     */
+    _settle(result) {
+        const { resolve } = this;
+        this.resolve = null;
+        this.destroy();
+        if (resolve) {
+            resolve(result);
+        }
+    }
+
+    /**
+     * Escape / backdrop click cancels the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+}
@@ -0,0 +1,155 @@
+/**
+ * @fileoverview Modal dialog for editing a collection's login flow: the login
+ * request, where its response carries the token and the environment variable
+ * that receives it.
+ * @module ui/CollectionLoginFlowDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Lists the collection's requests, root first, then folder by folder.
+ *
+ * @param {Object} collection
+ * @returns {Array<{id: string, label: string}>}
+ */
+function listEndpoints(collection) {
+    const label = (endpoint, folder) => {
+        const name = endpoint.name || endpoint.path || endpoint.id;
+        const method = (endpoint.method || 'GET').toUpperCase();
+        return folder ? `${folder.name} / ${method} ${name}` : `${method} ${name}`;
+    };
+    const endpoints = (collection.endpoints || []).map(endpoint => ({
+        id: endpoint.id,
+        label: label(endpoint, null)
+    }));
+    for (const folder of collection.folders || []) {
+        for (const endpoint of folder.endpoints || []) {
+            endpoints.push({ id: endpoint.id, label: label(endpoint, folder) });
+        }
+    }
+    return endpoints;
+}
+
+/**
+ * Collection-scoped login flow editor.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class CollectionLoginFlowDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog for a collection.
+     *
+     * @param {Object} collection - The collection ({id, name, loginFlow, endpoints, folders, ...})
+     * @returns {Promise<{loginFlow: Object|null}|null>} The edited flow (null
+     *   loginFlow removes it), or null on cancel
+     */
+    show(collection) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+            this._createDialog(collection);
+        });
+    }
+
+    /**
+     * Builds the dialog and fills it from the collection's current flow.
+     *
+     * @private
+     * @param {Object} collection
+     * @returns {void}
+     */
+    _createDialog(collection) {
+        const dialog = this.mount({
+            overlayClass: 'collection-login-flow-dialog-overlay',
+            dialogClass: 'collection-login-flow-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/collectionLoginFlow.html',
+            templateId: 'tpl-collection-login-flow-dialog'
+        });
+
+        const titleEl = dialog.querySelector('[data-role="title"]');
+        if (titleEl) {
+            titleEl.textContent = `Login Flow — ${collection.name}`;
+        }
+
+        const field = (name) => dialog.querySelector(`[name="${name}"]`);
+        const endpointSelect = field('endpointId');
+        for (const endpoint of listEndpoints(collection)) {
+            const option = document.createElement('option');
+            option.value = endpoint.id;
+            option.textContent = endpoint.label;
+            endpointSelect.appendChild(option);
+        }
+
+        const flow = collection.loginFlow || null;
+        endpointSelect.value = flow?.endpointId || '';
+        field('source').value = flow?.source === 'header' ? 'header' : 'body';
+        field('expression').value = flow?.expression || '';
+        field('variable').value = flow?.variable || '';
+        field('retryOnUnauthorized').checked = Boolean(flow?.retryOnUnauthorized);
+
+        dialog.querySelector('#collection-login-flow-close-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-login-flow-cancel-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-login-flow-save-btn')?.addEventListener('click', () => {
+            const errorEl = dialog.querySelector('[data-role="error"]');
+            const endpointId = endpointSelect.value;
+            if (!endpointId) {
+                this._settle({ loginFlow: null });
+                return;
+            }
+
+            const expression = field('expression').value.trim();
+            const variable = field('variable').value.trim().replace(/^\{\{\s*|\s*\}\}$/g, '');
+            if (!expression || !variable) {
+                errorEl.textContent = 'A login flow needs an expression and a variable name.';
+                errorEl.hidden = false;
+                return;
+            }
+            this._settle({
+                loginFlow: {
+                    endpointId,
+                    source: field('source').value,
+                    expression,
+                    variable,
+                    retryOnUnauthorized: field('retryOnUnauthorized').checked
+                }
+            });
+        });
+    }
+
+    /**
+     * Resolves the pending promise and tears down.
+     *
+     * @private
+     * @param {Object|null} result
+     * @returns {void}
+     */
+    _settle(result) {
+        const { resolve } = this;
+        this.resolve = null;
+        this.destroy();
+        if (resolve) {
+            resolve(result);
+        }
+    }
+
+    /**
+     * Escape / backdrop click cancels the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+}
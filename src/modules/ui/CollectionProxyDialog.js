@@ -0,0 +1,146 @@
+/**
+ * @fileoverview Modal dialog for editing a collection's proxy override,
+ * applied to its requests instead of the global proxy settings.
+ * @module ui/CollectionProxyDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Collection-scoped proxy editor.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class CollectionProxyDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog for a collection.
+     *
+     * @param {Object} collection - The collection ({id, name, proxy, ...})
+     * @returns {Promise<{proxy: Object|null}|null>} The edited override (null
+     *   proxy means "use global settings"), or null on cancel
+     */
+    show(collection) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+            this._createDialog(collection);
+        });
+    }
+
+    /**
+     * Builds the dialog and fills it from the collection's current override.
+     *
+     * @private
+     * @param {Object} collection
+     * @returns {void}
+     */
+    _createDialog(collection) {
+        const dialog = this.mount({
+            overlayClass: 'collection-proxy-dialog-overlay',
+            dialogClass: 'collection-proxy-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/collectionProxy.html',
+            templateId: 'tpl-collection-proxy-dialog'
+        });
+
+        const titleEl = dialog.querySelector('[data-role="title"]');
+        if (titleEl) {
+            titleEl.textContent = `Collection Proxy — ${collection.name}`;
+        }
+
+        const modeSelect = dialog.querySelector('#collection-proxy-mode-select');
+        const customFields = dialog.querySelector('[data-role="custom-fields"]');
+        const field = (name) => dialog.querySelector(`[name="${name}"]`);
+
+        const proxy = collection.proxy || null;
+        modeSelect.value = proxy?.mode === 'direct' || proxy?.mode === 'custom' ? proxy.mode : 'global';
+        if (proxy?.server) {
+            field('proxyType').value = proxy.server.type || 'http';
+            field('proxyHost').value = proxy.server.host || '';
+            field('proxyPort').value = proxy.server.port || 8080;
+        }
+        field('proxyUsername').value = proxy?.auth?.username || '';
+        field('proxyPassword').value = proxy?.auth?.password || '';
+        field('proxyBypass').value = (proxy?.bypassList || []).join(', ');
+
+        const syncVisibility = () => {
+            customFields.hidden = modeSelect.value !== 'custom';
+        };
+        syncVisibility();
+        modeSelect.addEventListener('change', syncVisibility);
+
+        dialog.querySelector('#collection-proxy-close-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-proxy-cancel-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-proxy-save-btn')?.addEventListener('click', () => {
+            const errorEl = dialog.querySelector('[data-role="error"]');
+            const mode = modeSelect.value;
+            if (mode === 'global') {
+                this._settle({ proxy: null });
+                return;
+            }
+            if (mode === 'direct') {
+                this._settle({ proxy: { mode } });
+                return;
+            }
+
+            const host = field('proxyHost').value.trim();
+            const port = parseInt(field('proxyPort').value, 10);
+            if (!host || !Number.isInteger(port) || port < 1 || port > 65535) {
+                errorEl.textContent = 'A custom proxy needs a host and a port between 1 and 65535.';
+                errorEl.hidden = false;
+                return;
+            }
+            const username = field('proxyUsername').value.trim();
+            this._settle({
+                proxy: {
+                    mode,
+                    server: { type: field('proxyType').value, host, port },
+                    auth: {
+                        enabled: username !== '',
+                        username,
+                        password: field('proxyPassword').value
+                    },
+                    bypassList: field('proxyBypass').value
+                        .split(',')
+                        .map(entry => entry.trim())
+                        .filter(Boolean)
+                }
+            });
+        });
+    }
+
+    /**
+     * Resolves the pending promise and tears down.
+     *
+     * @private
+     * @param {Object|null} result
+     * @returns {void}
+     */
+    _settle(result) {
+        const { resolve } = this;
+        this.resolve = null;
+        this.destroy();
+        if (resolve) {
+            resolve(result);
+        }
+    }
+
+    /**
+     * Escape / backdrop click cancels the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+}
@@ -179,6 +179,23 @@ export class CollectionRenderer {
         });
     }
 
+    /**
+     * Expands a collection and scrolls it into view
+     *
+     * @async
+     * @param {string} collectionId - Collection to reveal
+     * @returns {Promise<void>}
+     */
+    async revealCollection(collectionId) {
+        const collectionElement = this.container.querySelector(`.collection-item[data-collection-id="${collectionId}"]`);
+        if (!collectionElement) {
+            return;
+        }
+        collectionElement.classList.add('expanded');
+        collectionElement.scrollIntoView({ block: 'nearest' });
+        await this.saveExpansionState();
+    }
+
     /**
      * Creates DOM element for a collection
      *
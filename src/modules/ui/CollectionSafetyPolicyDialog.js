@@ -0,0 +1,115 @@
+/**
+ * @fileoverview Modal dialog for editing a collection's safety policy: the
+ * methods and hosts whose requests need confirmation before they are sent.
+ * @module ui/CollectionSafetyPolicyDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Splits a comma-separated field into trimmed, non-empty entries.
+ *
+ * @param {string} value
+ * @returns {string[]}
+ */
+function splitList(value) {
+    return value.split(',').map(entry => entry.trim()).filter(Boolean);
+}
+
+/**
+ * Collection-scoped safety policy editor.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class CollectionSafetyPolicyDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog for a collection.
+     *
+     * @param {Object} collection - The collection ({id, name, safetyPolicy, ...})
+     * @returns {Promise<{safetyPolicy: Object|null}|null>} The edited policy
+     *   (null policy means "no confirmation"), or null on cancel
+     */
+    show(collection) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+            this._createDialog(collection);
+        });
+    }
+
+    /**
+     * Builds the dialog and fills it from the collection's current policy.
+     *
+     * @private
+     * @param {Object} collection
+     * @returns {void}
+     */
+    _createDialog(collection) {
+        const dialog = this.mount({
+            overlayClass: 'collection-safety-policy-dialog-overlay',
+            dialogClass: 'collection-safety-policy-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/collectionSafetyPolicy.html',
+            templateId: 'tpl-collection-safety-policy-dialog'
+        });
+
+        const titleEl = dialog.querySelector('[data-role="title"]');
+        if (titleEl) {
+            titleEl.textContent = `Safety Policy — ${collection.name}`;
+        }
+
+        const methodsInput = dialog.querySelector('[name="safetyMethods"]');
+        const hostsInput = dialog.querySelector('[name="safetyHosts"]');
+        const policy = collection.safetyPolicy || null;
+        methodsInput.value = (policy?.methods || []).join(', ');
+        hostsInput.value = (policy?.hostPatterns || []).join(', ');
+
+        dialog.querySelector('#collection-safety-policy-close-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-safety-policy-cancel-btn')?.addEventListener('click', () => {
+            this.onDismiss();
+        });
+        dialog.querySelector('#collection-safety-policy-save-btn')?.addEventListener('click', () => {
+            const methods = splitList(methodsInput.value).map(method => method.toUpperCase());
+            if (methods.length === 0) {
+                this._settle({ safetyPolicy: null });
+                return;
+            }
+            this._settle({
+                safetyPolicy: { methods, hostPatterns: splitList(hostsInput.value) }
+            });
+        });
+    }
+
+    /**
+     * Resolves the pending promise and tears down.
+     *
+     * @private
+     * @param {Object|null} result
+     * @returns {void}
+     */
+    _settle(result) {
+        const { resolve } = this;
+        this.resolve = null;
+        this.destroy();
+        if (resolve) {
+            resolve(result);
+        }
+    }
+
+    /**
+     * Escape / backdrop click cancels the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+}
@@ -63,11 +63,13 @@ export class EnvironmentManager {
         const closeBtn = this.dialog.querySelector('#env-close-btn');
         const importBtn = this.dialog.querySelector('#env-import-btn');
         const exportAllBtn = this.dialog.querySelector('#env-export-all-btn');
+        const usageReportBtn = this.dialog.querySelector('#env-usage-report-btn');
 
         createBtn.addEventListener('click', () => this.handleCreateEnvironment());
         closeBtn.addEventListener('click', () => this.close(true));
         importBtn.addEventListener('click', () => this.handleImport());
         exportAllBtn.addEventListener('click', () => this.handleExportAll());
+        usageReportBtn.addEventListener('click', () => this.handleUsageReport());
 
         this.dialog.addEventListener('click', (e) => {
             if (e.target === this.dialog) {
@@ -723,6 +725,49 @@ export class EnvironmentManager {
         }
     }
 
+    /**
+     * Show which referenced variables nothing defines and which defined
+     * variables nothing references, across all collections
+     */
+    async handleUsageReport() {
+        try {
+            const report = await window.backendAPI.environments.usageReport();
+
+            this.currentEnvironmentId = null;
+            this.dialog.querySelectorAll('.env-list-item').forEach(item => item.classList.remove('is-selected'));
+
+            const detailsContainer = this.dialog.querySelector('#env-details');
+            detailsContainer.innerHTML = '';
+            const fragment = await templateLoader.clone(
+                './src/templates/environment/environmentManager.html',
+                'tpl-environment-manager-usage-report'
+            );
+            detailsContainer.appendChild(fragment);
+
+            detailsContainer.querySelector('.env-usage-summary').textContent =
+                `${report.referencedCount} variables referenced: ${report.undefined.length} undefined, ` +
+                `${report.incomplete.length} missing in some environments, ${report.unused.length} unused.`;
+
+            const fillSection = (name, entries, describe) => {
+                const section = detailsContainer.querySelector(`.env-usage-section[data-section="${name}"]`);
+                section.classList.toggle('is-hidden', entries.length === 0);
+                const list = section.querySelector('.env-usage-list');
+                for (const entry of entries) {
+                    const item = document.createElement('li');
+                    const code = document.createElement('code');
+                    code.textContent = entry.name;
+                    item.append(code, ` — ${describe(entry)}`);
+                    list.appendChild(item);
+                }
+            };
+            fillSection('undefined', report.undefined, (usage) => `used in ${usage.referencedIn.join(', ')}`);
+            fillSection('incomplete', report.incomplete, (usage) => `missing in ${usage.missingIn.join(', ')}`);
+            fillSection('unused', report.unused, (unused) => `${unused.scope} ${unused.owner}`);
+        } catch (error) {
+            this.showAlert(typeof error === 'string' ? error : error.message);
+        }
+    }
+
     /**
      * Save JSON through the native backend when available.
      */
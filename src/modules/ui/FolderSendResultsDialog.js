@@ -0,0 +1,77 @@
+/**
+ * @fileoverview Modal dialog listing the outcome of sending every request of
+ * a folder at once: status code and total time per request.
+ * @module ui/FolderSendResultsDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Results of a folder batch send.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class FolderSendResultsDialog extends BaseModal {
+    /**
+     * Shows the results of a batch send.
+     *
+     * @param {string} folderName - The folder that was sent
+     * @param {Object} result - `send_folder` result ({duration, total, succeeded, failed, confirmationRequired, results})
+     * @returns {void}
+     */
+    show(folderName, result) {
+        const dialog = this.mount({
+            overlayClass: 'folder-send-results-dialog-overlay',
+            dialogClass: 'folder-send-results-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/folderSendResults.html',
+            templateId: 'tpl-folder-send-results-dialog'
+        });
+
+        dialog.querySelector('[data-role="title"]').textContent = `Sent ${folderName}`;
+        dialog.querySelector('[data-role="summary"]').textContent =
+            `${result.succeeded} of ${result.total} succeeded in ${result.duration} ms`
+            + (result.confirmationRequired ? `, ${result.confirmationRequired} held back` : '')
+            + (result.parallel ? ' (parallel)' : '');
+
+        const list = dialog.querySelector('[data-role="results"]');
+        result.results.forEach(item => {
+            const row = document.createElement('li');
+            row.className = 'folder-send-result';
+            if (item.confirmationRequired) {
+                row.classList.add('is-held');
+            } else {
+                row.classList.add(item.success ? 'is-success' : 'is-failure');
+            }
+
+            const status = document.createElement('span');
+            status.className = 'folder-send-result-status';
+            status.textContent = item.confirmationRequired ? 'HELD' : (item.statusCode ?? 'ERR');
+
+            const name = document.createElement('span');
+            name.className = 'folder-send-result-name';
+            name.textContent = `${item.method} ${item.name}`;
+            name.title = item.error || item.url;
+
+            const time = document.createElement('span');
+            time.className = 'folder-send-result-time';
+            time.textContent = item.timings ? `${item.timings.total} ms` : '';
+
+            row.append(status, name, time);
+            list.appendChild(row);
+        });
+
+        dialog.querySelector('#folder-send-close-btn')?.addEventListener('click', () => this.destroy());
+        dialog.querySelector('#folder-send-done-btn')?.addEventListener('click', () => this.destroy());
+    }
+
+    /**
+     * Escape / backdrop click closes the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this.destroy();
+    }
+}
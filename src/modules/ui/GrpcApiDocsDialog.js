@@ -0,0 +1,254 @@
+/**
+ * @fileoverview Modal dialog browsing the gRPC API documentation stored with
+ * a collection, and capturing it from a server's reflection service
+ * @module ui/GrpcApiDocsDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+import { ConfirmDialog } from './ConfirmDialog.js';
+import { toast } from './Toast.js';
+import { buildTlsOptions } from '../grpcHandler.js';
+
+/**
+ * Streaming kind of a method, as shown by the gRPC panel's badge
+ *
+ * @param {Object} method - Documented method ({clientStreaming, serverStreaming})
+ * @returns {string} "unary", "server-stream", "client-stream" or "bidi"
+ */
+function methodKind(method) {
+    if (method.clientStreaming && method.serverStreaming) {
+        return 'bidi';
+    }
+    if (method.serverStreaming) {
+        return 'server-stream';
+    }
+    if (method.clientStreaming) {
+        return 'client-stream';
+    }
+    return 'unary';
+}
+
+/**
+ * Labelled, pretty-printed JSON skeleton
+ *
+ * @param {string} label - Caption above the skeleton
+ * @param {*} skeleton - Skeleton value
+ * @returns {HTMLElement} The block
+ */
+function skeletonBlock(label, skeleton) {
+    const block = document.createElement('div');
+    block.className = 'grpc-api-docs-skeleton';
+    const caption = document.createElement('span');
+    caption.className = 'form-input-hint';
+    caption.textContent = label;
+    const pre = document.createElement('pre');
+    pre.textContent = JSON.stringify(skeleton, null, 2);
+    block.appendChild(caption);
+    block.appendChild(pre);
+    return block;
+}
+
+/**
+ * gRPC API documentation of one collection
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class GrpcApiDocsDialog extends BaseModal {
+    /**
+     * Shows the dialog
+     *
+     * @param {Object} collection - The collection ({id, name})
+     * @param {Object} [defaults={}] - Capture defaults ({target, useTls}), e.g. from a gRPC request
+     * @returns {void}
+     */
+    show(collection, defaults = {}) {
+        this.collection = collection;
+        const dialog = this.mount({
+            overlayClass: 'grpc-api-docs-dialog-overlay',
+            dialogClass: 'grpc-api-docs-dialog modal-dialog modal-dialog--lg',
+            templatePath: './src/templates/dialogs/grpcApiDocs.html',
+            templateId: 'tpl-grpc-api-docs-dialog'
+        });
+        if (app.i18n && app.i18n.updateUI) {
+            app.i18n.updateUI(dialog);
+        }
+
+        dialog.querySelector('[data-role="collection"]').textContent = collection.name;
+        dialog.querySelector('#grpc-api-docs-target').value = defaults.target || '';
+        dialog.querySelector('#grpc-api-docs-tls').checked = defaults.useTls === true;
+
+        dialog.querySelector('#grpc-api-docs-capture-btn').addEventListener('click', () => this.capture());
+        dialog.querySelector('#grpc-api-docs-delete-btn').addEventListener('click', () => this.remove());
+        dialog.querySelector('#grpc-api-docs-close-btn').addEventListener('click', () => this.destroy());
+        dialog.querySelector('#grpc-api-docs-done-btn').addEventListener('click', () => this.destroy());
+        this.load();
+    }
+
+    /**
+     * Loads and renders the stored documentation
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async load() {
+        try {
+            const docs = await window.backendAPI.grpc.getDocs(this.collection.id);
+            if (this.dialog) {
+                this.render(docs);
+            }
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * Captures the documentation from the entered server, replacing the stored one
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async capture() {
+        const target = this.dialog.querySelector('#grpc-api-docs-target').value.trim();
+        if (!target) {
+            toast.error(app.i18n?.t('grpc_api_docs.target_required') || 'Enter the server to capture from');
+            return;
+        }
+        const button = this.dialog.querySelector('#grpc-api-docs-capture-btn');
+        button.disabled = true;
+        try {
+            const useTls = this.dialog.querySelector('#grpc-api-docs-tls').checked;
+            const tls = await buildTlsOptions(target, useTls);
+            const docs = await window.backendAPI.grpc.captureDocs(this.collection.id, target, tls);
+            if (this.dialog) {
+                this.render(docs);
+            }
+            toast.success(app.i18n?.t('grpc_api_docs.captured', { count: docs.services.length })
+                || `Captured ${docs.services.length} services`);
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        } finally {
+            button.disabled = false;
+        }
+    }
+
+    /**
+     * Deletes the stored documentation after confirmation
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async remove() {
+        const confirmed = await new ConfirmDialog().show(
+            app.i18n?.t('grpc_api_docs.delete_confirm') || 'Delete the stored gRPC API documentation of this collection?',
+            {
+                title: app.i18n?.t('grpc_api_docs.delete') || 'Delete Docs',
+                confirmText: app.i18n?.t('grpc_api_docs.delete') || 'Delete Docs'
+            }
+        );
+        if (!confirmed) {
+            return;
+        }
+        try {
+            await window.backendAPI.grpc.deleteDocs(this.collection.id);
+            if (this.dialog) {
+                this.render(null);
+            }
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * @private
+     * @param {Object|null} docs - Stored documentation ({target, capturedAt, services})
+     * @returns {void}
+     */
+    render(docs) {
+        const summary = this.dialog.querySelector('[data-role="summary"]');
+        const list = this.dialog.querySelector('[data-role="services"]');
+        this.dialog.querySelector('#grpc-api-docs-delete-btn').disabled = !docs;
+        list.innerHTML = '';
+
+        if (!docs) {
+            summary.textContent = app.i18n?.t('grpc_api_docs.empty')
+                || 'No API documentation captured yet. Enter a server with reflection enabled and capture it.';
+            return;
+        }
+
+        const capturedAt = new Date(docs.capturedAt).toLocaleString();
+        summary.textContent = app.i18n?.t('grpc_api_docs.summary', { target: docs.target, date: capturedAt })
+            || `Captured from ${docs.target} on ${capturedAt}`;
+
+        docs.services.forEach(service => {
+            const details = document.createElement('details');
+            details.className = 'grpc-api-docs-service';
+            details.open = docs.services.length === 1;
+
+            const title = document.createElement('summary');
+            title.className = 'grpc-api-docs-service-name';
+            title.textContent = `${service.name} (${service.methods.length})`;
+            details.appendChild(title);
+
+            if (service.comment) {
+                const comment = document.createElement('p');
+                comment.className = 'grpc-api-docs-comment';
+                comment.textContent = service.comment;
+                details.appendChild(comment);
+            }
+
+            service.methods.forEach(method => {
+                const row = document.createElement('div');
+                row.className = 'grpc-api-docs-method';
+
+                const header = document.createElement('div');
+                header.className = 'u-flex u-items-center u-gap-2';
+                const name = document.createElement('span');
+                name.className = 'grpc-api-docs-method-name';
+                name.textContent = method.name;
+                name.title = method.fullMethod;
+                const kind = document.createElement('span');
+                kind.className = 'badge neutral';
+                kind.textContent = methodKind(method);
+                const types = document.createElement('span');
+                types.className = 'grpc-api-docs-types';
+                types.textContent = `${method.inputType} → ${method.outputType}`;
+                header.appendChild(name);
+                header.appendChild(kind);
+                header.appendChild(types);
+                row.appendChild(header);
+
+                if (method.comment) {
+                    const comment = document.createElement('p');
+                    comment.className = 'grpc-api-docs-comment';
+                    comment.textContent = method.comment;
+                    row.appendChild(comment);
+                }
+
+                const skeletons = document.createElement('div');
+                skeletons.className = 'grpc-api-docs-skeletons';
+                skeletons.appendChild(skeletonBlock(app.i18n?.t('grpc_api_docs.input') || 'Input', method.inputSkeleton));
+                skeletons.appendChild(skeletonBlock(app.i18n?.t('grpc_api_docs.output') || 'Output', method.outputSkeleton));
+                row.appendChild(skeletons);
+
+                details.appendChild(row);
+            });
+
+            list.appendChild(details);
+        });
+    }
+
+    /**
+     * Escape / backdrop click closes the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this.destroy();
+    }
+}
@@ -0,0 +1,74 @@
+/**
+ * @fileoverview Modal dialog listing the outcome of a bulk health check:
+ * up/down, status code and latency per URL or endpoint.
+ * @module ui/HealthCheckResultsDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Results of a health check.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class HealthCheckResultsDialog extends BaseModal {
+    /**
+     * Shows the results of a health check.
+     *
+     * @param {string} targetName - What was checked (collection or folder name)
+     * @param {Object} result - `health_check` result ({duration, total, up, down, results})
+     * @returns {void}
+     */
+    show(targetName, result) {
+        const dialog = this.mount({
+            overlayClass: 'health-check-results-dialog-overlay',
+            dialogClass: 'health-check-results-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/healthCheckResults.html',
+            templateId: 'tpl-health-check-results-dialog'
+        });
+
+        dialog.querySelector('[data-role="title"]').textContent =
+            app.i18n?.t('health_check.title', { name: targetName }) || `Health of ${targetName}`;
+        dialog.querySelector('[data-role="summary"]').textContent =
+            app.i18n?.t('health_check.summary', { up: result.up, total: result.total, duration: result.duration })
+            || `${result.up} of ${result.total} up, checked in ${result.duration} ms`;
+
+        const list = dialog.querySelector('[data-role="results"]');
+        result.results.forEach(item => {
+            const row = document.createElement('li');
+            row.className = 'health-check-result';
+            row.classList.add(item.up ? 'is-up' : 'is-down');
+
+            const status = document.createElement('span');
+            status.className = 'health-check-result-status';
+            status.textContent = item.statusCode ?? (app.i18n?.t('health_check.down') || 'DOWN');
+
+            const name = document.createElement('span');
+            name.className = 'health-check-result-name';
+            name.textContent = `${item.method} ${item.name}`;
+            name.title = item.error || item.url;
+
+            const latency = document.createElement('span');
+            latency.className = 'health-check-result-latency';
+            latency.textContent = item.latency !== null && item.latency !== undefined ? `${item.latency} ms` : '';
+
+            row.append(status, name, latency);
+            list.appendChild(row);
+        });
+
+        dialog.querySelector('#health-check-close-btn')?.addEventListener('click', () => this.destroy());
+        dialog.querySelector('#health-check-done-btn')?.addEventListener('click', () => this.destroy());
+    }
+
+    /**
+     * Escape / backdrop click closes the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this.destroy();
+    }
+}
@@ -7,6 +7,9 @@ import { app } from '../appContext.js';
 import { HistoryService } from '../services/HistoryService.js';
 import { ConfirmDialog } from './ConfirmDialog.js';
 import { templateLoader } from '../templateLoader.js';
+import { saveResponseToFile } from '../responseSaveHandler.js';
+import { HistoryReplayDialog } from './HistoryReplayDialog.js';
+import { toast } from './Toast.js';
 
 /**
  * History sidebar UI renderer
@@ -30,6 +33,9 @@ export class HistoryRenderer {
         this.historyItems = [];
         this.container = document.getElementById('history-list');
         this.searchInput = document.getElementById('history-search-input');
+        this.bodySearchToggle = document.getElementById('history-body-search-toggle');
+        this.regexToggle = document.getElementById('history-regex-toggle');
+        this.bodySearchSeq = 0;
         this.clearAllBtn = document.getElementById('clear-all-history-btn');
         this.confirmDialog = new ConfirmDialog();
     }
@@ -46,6 +52,12 @@ export class HistoryRenderer {
             });
         }
 
+        [this.bodySearchToggle, this.regexToggle].forEach(toggle => {
+            toggle?.addEventListener('change', async () => {
+                await this.handleSearch(this.searchInput?.value || '');
+            });
+        });
+
         if (this.clearAllBtn) {
             this.clearAllBtn.addEventListener('click', async () => {
                 await this.handleClearAll();
@@ -54,10 +66,43 @@ export class HistoryRenderer {
     }
 
     async handleSearch(searchTerm) {
+        if (this.bodySearchToggle?.checked && searchTerm.trim() !== '') {
+            await this.handleBodySearch(searchTerm);
+            return;
+        }
+        this.bodySearchSeq++;
         const results = await this.service.searchHistory(searchTerm);
         this.renderHistoryList(results);
     }
 
+    /**
+     * Searches the response bodies of history entries and saved examples
+     *
+     * @async
+     * @param {string} searchTerm - Plain text, or a regular expression when the regex toggle is on
+     * @returns {Promise<void>}
+     */
+    async handleBodySearch(searchTerm) {
+        const seq = ++this.bodySearchSeq;
+        let matches;
+        let message = null;
+        try {
+            matches = await this.historyRepository.backendAPI.searchResponseBodies(searchTerm, {
+                regex: Boolean(this.regexToggle?.checked)
+            });
+        } catch (error) {
+            matches = [];
+            message = String(error?.message || error);
+        }
+        // A newer search started while this one ran
+        if (seq !== this.bodySearchSeq) {
+            return;
+        }
+
+        const history = await this.service.getAllHistory();
+        this.renderResponseMatches(matches, history, message);
+    }
+
     async handleClearAll() {
         const confirmMessage = app.i18n ?
             app.i18n.t('history.confirm_clear') || 'Are you sure you want to clear all request history?\n\nThis action cannot be undone.' :
@@ -116,23 +161,154 @@ export class HistoryRenderer {
         historyItems.forEach(entry => {
             const element = this.container.querySelector(`[data-history-id="${entry.id}"]`);
             if (element) {
-                element.addEventListener('click', () => {
-                    if (this.onHistorySelect) {
-                        this.onHistorySelect(entry);
-                    }
-                });
+                this.attachItemListeners(element, entry);
+            }
+        });
+    }
 
-                const deleteBtn = element.querySelector('.history-item-delete');
-                if (deleteBtn) {
-                    deleteBtn.addEventListener('click', async (e) => {
-                        e.stopPropagation();
-                        await this.handleDeleteEntry(entry.id);
-                    });
-                }
+    /**
+     * Wires selection, replay, save and delete of a rendered history item
+     *
+     * @param {HTMLElement} element - The history item element
+     * @param {Object} entry - The history entry it shows
+     * @returns {void}
+     */
+    attachItemListeners(element, entry) {
+        element.addEventListener('click', () => {
+            if (this.onHistorySelect) {
+                this.onHistorySelect(entry);
+            }
+        });
+
+        const replayBtn = element.querySelector('.history-item-replay');
+        if (replayBtn) {
+            replayBtn.addEventListener('click', async (e) => {
+                e.stopPropagation();
+                await this.handleReplay(entry);
+            });
+        }
+
+        const saveBtn = element.querySelector('.history-item-save');
+        if (saveBtn) {
+            saveBtn.addEventListener('click', async (e) => {
+                e.stopPropagation();
+                await saveResponseToFile({ historyId: entry.id });
+            });
+        }
+
+        const deleteBtn = element.querySelector('.history-item-delete');
+        if (deleteBtn) {
+            deleteBtn.addEventListener('click', async (e) => {
+                e.stopPropagation();
+                await this.handleDeleteEntry(entry.id);
+            });
+        }
+    }
+
+    /**
+     * Renders response body matches: history entries with their snippets,
+     * then saved examples, which open their endpoint when clicked
+     *
+     * @param {Array<Object>} matches - Matches from the backend search
+     * @param {Array<Object>} history - All history entries
+     * @param {string|null} message - Error to show instead, e.g. an invalid regex
+     * @returns {void}
+     */
+    renderResponseMatches(matches, history, message) {
+        if (!this.container) {return;}
+
+        this.container.innerHTML = '';
+        if (message || matches.length === 0) {
+            const fragment = templateLoader.cloneSync(
+                './src/templates/history/historyRenderer.html',
+                'tpl-history-search-message'
+            );
+            const messageEl = fragment.querySelector('[data-role="message"]');
+            messageEl.textContent = message
+                || app.i18n?.t('history.no_body_matches')
+                || 'No response contains this text';
+            this.container.appendChild(fragment);
+            return;
+        }
+
+        const entries = new Map(history.map(entry => [entry.id, entry]));
+        matches.forEach(match => {
+            let element;
+            if (match.source === 'history') {
+                const entry = entries.get(match.historyId);
+                if (!entry) {return;}
+                element = this.renderHistoryItem(entry);
+                this.attachItemListeners(element, entry);
+            } else {
+                element = this.renderExampleMatch(match);
             }
+            match.snippets.forEach(snippet => {
+                element.insertBefore(this.renderSnippet(snippet), element.querySelector('button'));
+            });
+            this.container.appendChild(element);
         });
     }
 
+    /**
+     * Renders a match in a saved example
+     *
+     * @param {Object} match - Example match from the backend search
+     * @returns {HTMLElement}
+     */
+    renderExampleMatch(match) {
+        const fragment = templateLoader.cloneSync(
+            './src/templates/history/historyRenderer.html',
+            'tpl-history-example-match'
+        );
+        const itemEl = fragment.firstElementChild;
+
+        const methodEl = itemEl.querySelector('[data-role="method"]');
+        methodEl.textContent = match.method;
+        methodEl.style.setProperty('--history-method-color', this.service.getMethodColor(match.method));
+        const statusEl = itemEl.querySelector('[data-role="status"]');
+        statusEl.textContent = match.status;
+        statusEl.style.setProperty('--history-status-color', this.service.getStatusColor(match.status));
+        itemEl.querySelector('[data-role="name"]').textContent = match.exampleName;
+        const urlEl = itemEl.querySelector('[data-role="url"]');
+        urlEl.textContent = this.service.truncateUrl(match.url, 60);
+        urlEl.title = match.url;
+
+        itemEl.addEventListener('click', () => this.openExampleEndpoint(match));
+        return itemEl;
+    }
+
+    /**
+     * Renders one snippet with the matched text highlighted
+     *
+     * @param {Object} snippet - Snippet with before, matched and after text
+     * @returns {HTMLElement}
+     */
+    renderSnippet(snippet) {
+        const snippetEl = document.createElement('div');
+        snippetEl.className = 'history-item-snippet';
+        const mark = document.createElement('mark');
+        mark.textContent = snippet.matched;
+        snippetEl.append(snippet.before, mark, snippet.after);
+        return snippetEl;
+    }
+
+    /**
+     * Opens the endpoint a matched example is saved on
+     *
+     * @async
+     * @param {Object} match - Example match from the backend search
+     * @returns {Promise<void>}
+     */
+    async openExampleEndpoint(match) {
+        const controller = app.collectionController;
+        const collection = await controller?.repository.getById(match.collectionId);
+        const endpoint = collection
+            && controller.endpointLoaderService.findEndpointInCollection(collection, match.endpointId);
+        if (endpoint) {
+            await controller.handleEndpointClick(collection, endpoint);
+        }
+    }
+
     renderHistoryItem(entry) {
         const statusColor = this.service.getStatusColor(entry.response?.status);
         const methodColor = this.service.getMethodColor(entry.request.method);
@@ -188,6 +364,34 @@ export class HistoryRenderer {
         return itemEl;
     }
 
+    /**
+     * Re-sends a history entry exactly as recorded and compares the responses
+     *
+     * @async
+     * @param {Object} entry - The history entry
+     * @returns {Promise<void>}
+     */
+    async handleReplay(entry) {
+        const backendAPI = this.historyRepository.backendAPI;
+        try {
+            let replay = await backendAPI.replayHistoryEntry(entry.id);
+            if (replay.current.confirmationRequired) {
+                const confirmed = await this.confirmDialog.show(`${replay.current.message} Send ${entry.request.url} anyway?`, {
+                    title: 'Send this request?',
+                    confirmText: `Send ${entry.request.method}`,
+                    dangerous: true
+                });
+                if (!confirmed) {
+                    return;
+                }
+                replay = await backendAPI.replayHistoryEntry(entry.id, true);
+            }
+            new HistoryReplayDialog().show(replay);
+        } catch (error) {
+            toast.error(String(error?.message || error));
+        }
+    }
+
     async handleDeleteEntry(id) {
         await this.service.deleteHistoryEntry(id);
         await this.render();
@@ -0,0 +1,91 @@
+/**
+ * @fileoverview Modal dialog comparing a history entry's recorded response
+ * with the response to the same request sent again.
+ * @module ui/HistoryReplayDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Recorded vs. replayed response of a history entry.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class HistoryReplayDialog extends BaseModal {
+    /**
+     * Shows the result of a replay.
+     *
+     * @param {Object} replay - `replay_history_entry` result ({sentAt, request, previous, current, statusChanged, bodyChanged})
+     * @returns {void}
+     */
+    show(replay) {
+        const dialog = this.mount({
+            overlayClass: 'history-replay-dialog-overlay',
+            dialogClass: 'history-replay-dialog modal-dialog modal-dialog--lg',
+            templatePath: './src/templates/dialogs/historyReplay.html',
+            templateId: 'tpl-history-replay-dialog'
+        });
+        const t = (key, fallback, params) => app.i18n?.t(key, params) || fallback;
+
+        dialog.querySelector('[data-role="title"]').textContent =
+            `${replay.request.method} ${replay.request.url}`;
+
+        let summary = t('history.replay_unchanged', 'Same status and body as recorded');
+        if (replay.statusChanged && replay.bodyChanged) {
+            summary = t('history.replay_status_body_changed', 'Status and body changed');
+        } else if (replay.statusChanged) {
+            summary = t('history.replay_status_changed', 'Status changed');
+        } else if (replay.bodyChanged) {
+            summary = t('history.replay_body_changed', 'Body changed');
+        }
+        dialog.querySelector('[data-role="summary"]').textContent = summary;
+
+        const recordedAt = replay.sentAt ? new Date(replay.sentAt).toLocaleString() : '';
+        dialog.querySelector('[data-role="previous-heading"]').textContent =
+            `${t('history.replay_recorded', 'Recorded')} ${recordedAt} · ${describeStatus(replay.previous)}`;
+        dialog.querySelector('[data-role="current-heading"]').textContent =
+            `${t('history.replay_now', 'Now')} · ${describeStatus(replay.current)}`;
+        dialog.querySelector('[data-role="previous-body"]').textContent = formatBody(replay.previous);
+        dialog.querySelector('[data-role="current-body"]').textContent = formatBody(replay.current);
+
+        dialog.querySelector('#history-replay-close-btn')?.addEventListener('click', () => this.destroy());
+        dialog.querySelector('#history-replay-done-btn')?.addEventListener('click', () => this.destroy());
+    }
+
+    /**
+     * Escape / backdrop click closes the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this.destroy();
+    }
+}
+
+/**
+ * @param {Object|null} response - Recorded or replayed response
+ * @returns {string} e.g. "200 OK · 84 ms"
+ */
+function describeStatus(response) {
+    if (!response?.status) {
+        return response?.message || 'Error';
+    }
+    const status = `${response.status} ${response.statusText || ''}`.trim();
+    const time = response.timings?.total ?? response.ttfb;
+    return time === null || time === undefined ? status : `${status} · ${time} ms`;
+}
+
+/**
+ * @param {Object|null} response - Recorded or replayed response
+ * @returns {string} The body, pretty-printed when it is JSON
+ */
+function formatBody(response) {
+    const data = response?.data;
+    if (data === null || data === undefined) {
+        return '';
+    }
+    return typeof data === 'string' ? data : JSON.stringify(data, null, 2);
+}
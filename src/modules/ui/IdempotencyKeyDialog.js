@@ -0,0 +1,95 @@
+/**
+ * @fileoverview Dialog editing whether a request sends a generated
+ * idempotency key, in which header and from which template
+ * @module ui/IdempotencyKeyDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Modal editing a request's idempotency key settings
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class IdempotencyKeyDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog
+     *
+     * @param {{header: string, template: string}|null} current - The request's
+     * settings, null when it sends no key
+     * @returns {Promise<{enabled: boolean, header: string, template: string}|null>}
+     * The edited settings, or null when cancelled
+     */
+    show(current) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'idempotency-key-dialog-overlay',
+                dialogClass: 'idempotency-key-dialog modal-dialog',
+                templatePath: './src/templates/dialogs/idempotencyKey.html',
+                templateId: 'tpl-idempotency-key-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI(dialog);
+            }
+
+            const enabled = dialog.querySelector('#idempotency-key-enabled');
+            const header = dialog.querySelector('#idempotency-key-header');
+            const template = dialog.querySelector('#idempotency-key-template');
+            enabled.checked = current !== null;
+            header.value = current?.header || '';
+            template.value = current?.template || '';
+
+            const updateFields = () => {
+                header.disabled = !enabled.checked;
+                template.disabled = !enabled.checked;
+            };
+            enabled.addEventListener('change', updateFields);
+            updateFields();
+
+            dialog.querySelector('[data-role="cancel"]').addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('[data-role="confirm"]').addEventListener('click', () => {
+                this._settle({
+                    enabled: enabled.checked,
+                    header: header.value.trim(),
+                    template: template.value.trim()
+                });
+            });
+            enabled.focus();
+        });
+    }
+
+    /**
+     * Cancels the dialog, resolving with null
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down
+     *
+     * @private
+     * @param {Object|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
@@ -5,6 +5,8 @@
 
 import { createLazyEditorProxy } from '../editorLoader.js';
 import { debounce } from '../utils/debounce.js';
+import { app } from '../appContext.js';
+import { toast } from './Toast.js';
 
 /**
  * Manages inline script editing with auto-save functionality
@@ -19,6 +21,7 @@ export class InlineScriptManager {
     constructor() {
         this.preRequestContainer = document.getElementById('pre-request-script-container');
         this.testScriptContainer = document.getElementById('test-script-container');
+        this.generateTestsBtn = document.getElementById('generate-tests-btn');
         this.preRequestEditor = null;
         this.testScriptEditor = null;
         this.currentCollectionId = null;
@@ -49,9 +52,41 @@ export class InlineScriptManager {
             });
         }
 
+        if (this.generateTestsBtn) {
+            this.generateTestsBtn.addEventListener('click', () => this.generateTests());
+        }
+
         this.initialized = true;
     }
 
+    /**
+     * Append tests generated from the endpoint's last response to its test script
+     * @async
+     */
+    async generateTests() {
+        if (!this.currentCollectionId || !this.currentEndpointId) {
+            toast.error(app.i18n?.t('scripts.generate_tests_no_endpoint') || 'Open a saved request to generate tests for it');
+            return;
+        }
+
+        this.generateTestsBtn.disabled = true;
+        try {
+            await this.flushPendingSave();
+            const scripts = await window.backendAPI.scripts.generateTests(
+                this.currentCollectionId,
+                this.currentEndpointId
+            );
+            if (this.testScriptEditor) {
+                this.testScriptEditor.setContent(scripts.testScript || '');
+            }
+            toast.success(app.i18n?.t('scripts.generate_tests_done') || 'Tests generated from the last response');
+        } catch (error) {
+            toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to generate tests'));
+        } finally {
+            this.generateTestsBtn.disabled = false;
+        }
+    }
+
     /**
      * Load scripts for a specific endpoint
      * @param {string} collectionId - Collection ID
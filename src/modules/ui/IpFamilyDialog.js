@@ -0,0 +1,76 @@
+/**
+ * @fileoverview Dialog choosing the IP family a single request resolves its
+ * host to, overriding the global setting
+ * @module ui/IpFamilyDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Modal with the IP family choices of a request
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class IpFamilyDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog
+     *
+     * @param {string|null} current - The request's current override, if any
+     * @returns {Promise<string|null>} The chosen family, '' to use the global
+     * setting, or null when cancelled
+     */
+    show(current) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'ip-family-dialog-overlay',
+                dialogClass: 'ip-family-dialog modal-dialog modal-dialog--sm',
+                templatePath: './src/templates/dialogs/ipFamily.html',
+                templateId: 'tpl-ip-family-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI(dialog);
+            }
+
+            const select = dialog.querySelector('#ip-family-select');
+            select.value = current || '';
+            dialog.querySelector('[data-role="cancel"]').addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('[data-role="confirm"]').addEventListener('click', () => this._settle(select.value));
+            select.focus();
+        });
+    }
+
+    /**
+     * Cancels the dialog, resolving with null
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down
+     *
+     * @private
+     * @param {string|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
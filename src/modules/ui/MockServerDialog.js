@@ -6,6 +6,41 @@
 import { app } from '../appContext.js';
 import { templateLoader } from '../templateLoader.js';
 import { SchemaProcessor } from '../schema/SchemaProcessor.js';
+import { ResponseExampleDialog } from './ResponseExampleDialog.js';
+import { toast } from './Toast.js';
+
+/**
+ * The body of a saved example as edited in the response editor: parsed JSON,
+ * else the text itself
+ *
+ * @param {Object} example - Saved example
+ * @returns {*} The example body
+ */
+function exampleValue(example) {
+    try {
+        return JSON.parse(example.body);
+    } catch {
+        return example.body;
+    }
+}
+
+/**
+ * The saved example an endpoint serves by default, matching the backend: the
+ * selected one, else the first 2xx, else the first
+ *
+ * @param {Array<Object>} examples - Saved examples of the endpoint
+ * @param {string} selectedId - Selected example ID, or ''
+ * @returns {Object|null} The served example
+ */
+function servedExample(examples, selectedId) {
+    return examples.find(example => example.id === selectedId)
+        || examples.find(example => example.status >= 200 && example.status < 300)
+        || examples[0]
+        || null;
+}
+
+/** Request logs shown per page. */
+const LOG_PAGE_SIZE = 20;
 
 /**
  * UI Dialog for managing mock server
@@ -27,6 +62,7 @@ export class MockServerDialog {
         this.resolve = null;
         this.statusPoller = null;
         this.logsPoller = null;
+        this.logOffset = 0;
         this.escapeHandler = null;
     }
 
@@ -82,6 +118,18 @@ export class MockServerDialog {
             portLabelEl.textContent = `${t('mock_server.port', 'Port')}:`;
         }
 
+        const logSizeLabelEl = dialogContent.querySelector('[data-role="log-size-label"]');
+        if (logSizeLabelEl) {
+            logSizeLabelEl.textContent = `${t('mock_server.log_size', 'Keep logs')}:`;
+        }
+
+        [['newer', 'mock_server.logs_newer', 'Newer requests'], ['older', 'mock_server.logs_older', 'Older requests']]
+            .forEach(([role, key, fallback]) => {
+                const pageBtn = dialogContent.querySelector(`[data-role="${role}"]`);
+                pageBtn?.setAttribute('aria-label', t(key, fallback));
+                pageBtn?.setAttribute('title', t(key, fallback));
+            });
+
         const collectionsHeadingEl = dialogContent.querySelector('[data-role="collections-heading"]');
         if (collectionsHeadingEl) {
             collectionsHeadingEl.textContent = t('mock_server.collections_heading', 'COLLECTIONS TO MOCK');
@@ -124,7 +172,10 @@ export class MockServerDialog {
     setupEventListeners() {
         const toggleBtn = this.dialog.querySelector('#mock-server-toggle-btn');
         const portInput = this.dialog.querySelector('#mock-server-port-input');
+        const logSizeInput = this.dialog.querySelector('#mock-server-log-size-input');
         const clearLogsBtn = this.dialog.querySelector('#mock-server-clear-logs-btn');
+        const newerLogsBtn = this.dialog.querySelector('#mock-server-logs-newer-btn');
+        const olderLogsBtn = this.dialog.querySelector('#mock-server-logs-older-btn');
         const closeBtn = this.dialog.querySelector('#mock-server-close-btn');
 
         toggleBtn.addEventListener('click', () => this.handleToggleServer());
@@ -133,8 +184,22 @@ export class MockServerDialog {
             await this.handlePortChange(e.target.value);
         });
 
+        logSizeInput.addEventListener('change', async (e) => {
+            await this.handleLogSizeChange(e.target.value);
+        });
+
         clearLogsBtn.addEventListener('click', () => this.handleClearLogs());
 
+        newerLogsBtn.addEventListener('click', () => {
+            this.logOffset = Math.max(0, this.logOffset - LOG_PAGE_SIZE);
+            this.updateLogs();
+        });
+
+        olderLogsBtn.addEventListener('click', () => {
+            this.logOffset += LOG_PAGE_SIZE;
+            this.updateLogs();
+        });
+
         closeBtn.addEventListener('click', () => this.close());
 
         this.dialog.addEventListener('click', (e) => {
@@ -166,6 +231,7 @@ export class MockServerDialog {
 
             const portInput = this.dialog.querySelector('#mock-server-port-input');
             portInput.value = settings.port;
+            this.dialog.querySelector('#mock-server-log-size-input').value = settings.logSize;
 
             await this.renderCollections(collections, settings);
 
@@ -251,6 +317,30 @@ export class MockServerDialog {
             toggleLabel.appendChild(toggleTrack);
             toggleLabel.appendChild(labelText);
             headerDiv.appendChild(toggleLabel);
+
+            if (isEnabled) {
+                const delayLabel = document.createElement('label');
+                delayLabel.className = 'mock-server-collection-delay u-flex u-items-center u-gap-1';
+                delayLabel.title = t('mock_server.collection_delay_tooltip', 'Default delay for every endpoint of this collection. Endpoint delays override it.');
+
+                const delayInput = document.createElement('input');
+                delayInput.type = 'number';
+                delayInput.min = '0';
+                delayInput.max = '30000';
+                delayInput.step = '100';
+                delayInput.className = 'input-base field-input mock-server-collection-delay-input';
+                delayInput.value = String(settings.collectionDelays?.[collection.id] || 0);
+                delayInput.addEventListener('change', () => {
+                    this.handleSetCollectionDelay(collection.id, delayInput.value);
+                });
+
+                const unitSpan = document.createElement('span');
+                unitSpan.textContent = t('mock_server.delay_ms', 'ms');
+
+                delayLabel.appendChild(delayInput);
+                delayLabel.appendChild(unitSpan);
+                headerDiv.appendChild(delayLabel);
+            }
             collectionDiv.appendChild(headerDiv);
 
             if (isEnabled && httpEndpoints.length > 0) {
@@ -262,6 +352,18 @@ export class MockServerDialog {
                 for (const endpoint of endpointsToShow) {
                     const endpointDiv = document.createElement('div');
                     endpointDiv.className = 'mock-server-endpoint u-flex u-items-center u-gap-3';
+                    const endpointEnabled = settings.endpointEnabled?.[`${collection.id}_${endpoint.id}`] !== false;
+                    endpointDiv.classList.toggle('is-disabled', !endpointEnabled);
+
+                    const endpointToggle = document.createElement('input');
+                    endpointToggle.type = 'checkbox';
+                    endpointToggle.className = 'mock-server-endpoint-toggle';
+                    endpointToggle.checked = endpointEnabled;
+                    endpointToggle.title = t('mock_server.endpoint_enabled_tooltip', 'Serve this endpoint (disabled endpoints return 404)');
+                    endpointToggle.addEventListener('change', () => {
+                        endpointDiv.classList.toggle('is-disabled', !endpointToggle.checked);
+                        this.handleSetEndpointEnabled(collection.id, endpoint.id, endpointToggle.checked);
+                    });
 
                     const methodSpan = document.createElement('span');
                     methodSpan.className = 'method-pill';
@@ -287,6 +389,7 @@ export class MockServerDialog {
                         this.showResponseEditor(collection, endpoint);
                     });
 
+                    endpointDiv.appendChild(endpointToggle);
                     endpointDiv.appendChild(methodSpan);
                     endpointDiv.appendChild(pathSpan);
                     endpointDiv.appendChild(editResponseBtn);
@@ -390,6 +493,27 @@ export class MockServerDialog {
         }
     }
 
+    /**
+     * Handles request log size change
+     *
+     * @async
+     * @param {string} logSize - New number of request logs to keep
+     */
+    async handleLogSizeChange(logSize) {
+        try {
+            const result = await this.controller.handleUpdateLogSize(logSize);
+            if (!result.success) {
+                this.showAlert(result.message);
+                const settings = await this.controller.getSettings();
+                const logSizeInput = this.dialog.querySelector('#mock-server-log-size-input');
+                logSizeInput.value = settings.logSize;
+            }
+            await this.updateLogs();
+        } catch (error) {
+            void error;
+        }
+    }
+
     /**
      * Handles collection enable/disable toggle
      *
@@ -431,6 +555,43 @@ export class MockServerDialog {
         }
     }
 
+    /**
+     * Handles collection delay change
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} delayMs - Delay in milliseconds
+     */
+    async handleSetCollectionDelay(collectionId, delayMs) {
+        try {
+            const result = await this.controller.handleSetCollectionDelay(collectionId, delayMs);
+            if (!result.success) {
+                this.showAlert(result.message);
+            }
+        } catch (error) {
+            void error;
+        }
+    }
+
+    /**
+     * Handles endpoint enable/disable toggle
+     *
+     * @async
+     * @param {string} collectionId - Collection ID
+     * @param {string} endpointId - Endpoint ID
+     * @param {boolean} enabled - Whether the endpoint should be served
+     */
+    async handleSetEndpointEnabled(collectionId, endpointId, enabled) {
+        try {
+            const result = await this.controller.handleSetEndpointEnabled(collectionId, endpointId, enabled);
+            if (!result.success) {
+                this.showAlert(result.message);
+            }
+        } catch (error) {
+            void error;
+        }
+    }
+
     /**
      * Handles clear logs action
      *
@@ -439,6 +600,7 @@ export class MockServerDialog {
     async handleClearLogs() {
         try {
             await this.controller.clearRequestLogs();
+            this.logOffset = 0;
             await this.updateLogs();
         } catch (error) {
             void error;
@@ -520,14 +682,22 @@ export class MockServerDialog {
      */
     async updateLogs() {
         try {
-            const logs = await this.controller.getRequestLogs(20);
-            const container = this.dialog.querySelector('#mock-server-logs');
+            let page = await this.controller.getRequestLogs(LOG_PAGE_SIZE, this.logOffset);
+            if (page.logs.length === 0 && this.logOffset > 0) {
+                // The page scrolled out of the buffer: show the oldest kept logs
+                this.logOffset = Math.max(0, Math.floor((page.total - 1) / LOG_PAGE_SIZE) * LOG_PAGE_SIZE);
+                page = await this.controller.getRequestLogs(LOG_PAGE_SIZE, this.logOffset);
+            }
+            const logs = page.logs;
+            const container = this.dialog?.querySelector('#mock-server-logs');
             const t = (key, fallback) => app.i18n ? app.i18n.t(key) || fallback : fallback;
 
             if (!container) {
                 return;
             }
 
+            this.updateLogsPager(page);
+
             if (!logs || logs.length === 0) {
                 const fragment = templateLoader.cloneSync(
                     './src/templates/mockServer/mockServerDialog.html',
@@ -584,7 +754,9 @@ export class MockServerDialog {
                 if (methodEl) {methodEl.textContent = log.method;}
                 if (pathEl) {
                     pathEl.textContent = log.path;
-                    pathEl.title = log.path;
+                    const params = Object.entries(log.pathParams || {})
+                        .map(([name, value]) => `${name}=${value}`);
+                    pathEl.title = params.length > 0 ? `${log.path}\n${params.join(', ')}` : log.path;
                 }
                 if (statusEl) {
                     statusEl.textContent = log.responseStatus;
@@ -602,6 +774,33 @@ export class MockServerDialog {
         }
     }
 
+    /**
+     * Updates the shown range and the page buttons of the request log
+     *
+     * @param {Object} page - Page of request logs with total and received counts
+     */
+    updateLogsPager(page) {
+        const rangeEl = this.dialog.querySelector('#mock-server-logs-range');
+        const newerBtn = this.dialog.querySelector('#mock-server-logs-newer-btn');
+        const olderBtn = this.dialog.querySelector('#mock-server-logs-older-btn');
+        const shownTo = this.logOffset + page.logs.length;
+
+        if (rangeEl) {
+            let text = '';
+            if (page.total > 0) {
+                text = app.i18n?.t('mock_server.logs_range', { from: this.logOffset + 1, to: shownTo, total: page.total })
+                    || `${this.logOffset + 1}–${shownTo} of ${page.total}`;
+            }
+            const dropped = page.received - page.total;
+            if (dropped > 0) {
+                text += ` ${app.i18n?.t('mock_server.logs_dropped', { count: dropped }) || `(${dropped} older dropped)`}`;
+            }
+            rangeEl.textContent = text;
+        }
+        if (newerBtn) {newerBtn.disabled = this.logOffset === 0;}
+        if (olderBtn) {olderBtn.disabled = shownTo >= page.total;}
+    }
+
     /**
      * Shows alert dialog
      *
@@ -662,13 +861,18 @@ export class MockServerDialog {
         const delayKey = `${collection.id}_${endpoint.id}`;
         const currentDelay = settings.endpointDelays[delayKey] || 0;
 
+        let examples = await this.controller.getExamples(collection.id, endpoint.id);
+        const selectedExampleId = settings.selectedExamples?.[delayKey] || '';
+        const defaultExample = servedExample(examples, selectedExampleId);
+
         const customStatusCode = await this.controller.getCustomStatusCode(collection.id, endpoint.id);
-        const currentStatusCode = customStatusCode || this.getDefaultStatusCode(endpoint);
+        const currentStatusCode = customStatusCode || defaultExample?.status || this.getDefaultStatusCode(endpoint);
 
         const defaultResponse =
             (await this.controller.getDefaultResponse(collection.id, endpoint.id)) ??
             this.generateDefaultResponse(endpoint);
-        const currentResponse = customResponse || defaultResponse;
+        const currentResponse = customResponse
+            || (defaultExample ? exampleValue(defaultExample) : defaultResponse);
 
         const overlay = document.createElement('div');
         overlay.className = 'modal-overlay';
@@ -713,15 +917,27 @@ export class MockServerDialog {
         if (statusCodeLabelEl) {
             statusCodeLabelEl.textContent = t('mock_server.status_code', 'Status Code');
         }
+        const exampleLabelEl = dialog.querySelector('[data-role="example-label"]');
+        if (exampleLabelEl) {
+            exampleLabelEl.textContent = t('mock_server.saved_example', 'Saved Example');
+        }
         const bodyLabelEl = dialog.querySelector('[data-role="body-label"]');
         if (bodyLabelEl) {
             bodyLabelEl.textContent = t('mock_server.response_body', 'Response Body (JSON)');
         }
+        const bodyHintEl = dialog.querySelector('[data-role="body-hint"]');
+        if (bodyHintEl) {
+            bodyHintEl.textContent = t('mock_server.path_params_hint', 'Use {name} to echo a path parameter, e.g. "id": "{id}"');
+        }
 
         const resetTextEl = dialog.querySelector('[data-role="reset"]');
         if (resetTextEl) {
             resetTextEl.textContent = t('mock_server.reset_to_default', 'Reset to Default');
         }
+        const saveExampleTextEl = dialog.querySelector('[data-role="save-example"]');
+        if (saveExampleTextEl) {
+            saveExampleTextEl.textContent = t('mock_server.save_as_example', 'Save as Example');
+        }
         const cancelTextEl = dialog.querySelector('[data-role="cancel"]');
         if (cancelTextEl) {
             cancelTextEl.textContent = t('common.cancel', 'Cancel');
@@ -741,8 +957,78 @@ export class MockServerDialog {
         const saveBtn = dialog.querySelector('#response-editor-save');
         const cancelBtn = dialog.querySelector('#response-editor-cancel');
         const resetBtn = dialog.querySelector('#response-editor-reset');
+        const saveExampleBtn = dialog.querySelector('#response-editor-save-example');
+        const exampleSelect = dialog.querySelector('#response-editor-example');
         const _closeBtn = dialog.querySelector('#response-editor-close');
 
+        // Body and status last loaded from an example; saving them unchanged
+        // keeps serving the example instead of storing a custom response
+        let examplePreview = customResponse === null && defaultExample
+            ? { text: JSON.stringify(currentResponse, null, 2), status: defaultExample.status }
+            : null;
+
+        const renderExampleOptions = (selectedId) => {
+            dialog.querySelector('[data-role="example-row"]').classList.toggle('is-hidden', examples.length === 0);
+            exampleSelect.replaceChildren();
+            const autoOption = document.createElement('option');
+            autoOption.value = '';
+            autoOption.textContent = t('mock_server.example_auto', 'First successful example');
+            exampleSelect.appendChild(autoOption);
+            examples.forEach(example => {
+                const option = document.createElement('option');
+                option.value = example.id;
+                option.textContent = `${example.name} (${example.status})`;
+                exampleSelect.appendChild(option);
+            });
+            exampleSelect.value = examples.some(example => example.id === selectedId) ? selectedId : '';
+        };
+        renderExampleOptions(selectedExampleId);
+
+        exampleSelect.addEventListener('change', () => {
+            const example = servedExample(examples, exampleSelect.value);
+            if (!example) {
+                return;
+            }
+            textarea.value = JSON.stringify(exampleValue(example), null, 2);
+            statusCodeInput.value = String(example.status);
+            examplePreview = { text: textarea.value, status: example.status };
+            errorDiv.textContent = '';
+            saveBtn.disabled = false;
+        });
+
+        saveExampleBtn.addEventListener('click', async () => {
+            let body;
+            try {
+                body = JSON.parse(textarea.value);
+            } catch (e) {
+                errorDiv.textContent = t('mock_server.invalid_json', `Invalid JSON: ${e.message}`);
+                return;
+            }
+            const status = parseInt(statusCodeInput.value, 10);
+            const onDelete = async (exampleId) => {
+                try {
+                    examples = await window.backendAPI.endpoints.deleteExample(collection.id, endpoint.id, exampleId);
+                    renderExampleOptions(exampleSelect.value);
+                    return examples;
+                } catch (error) {
+                    toast.error(error?.message || String(error));
+                    return null;
+                }
+            };
+            const name = await new ResponseExampleDialog().show(examples, `${status}`, onDelete);
+            if (!name) {
+                return;
+            }
+            const result = await this.controller.handleSaveExample(collection.id, endpoint.id, { name, status, body });
+            if (!result.success) {
+                errorDiv.textContent = result.message;
+                return;
+            }
+            examples = await this.controller.getExamples(collection.id, endpoint.id);
+            renderExampleOptions(exampleSelect.value);
+            toast.success(app.i18n?.t('mock_server.example_saved', { name }) || `Saved example "${name}"`);
+        });
+
         if (delayInput) {
             delayInput.value = String(currentDelay);
         }
@@ -787,11 +1073,22 @@ export class MockServerDialog {
                     return;
                 }
 
+                const fromExample = examplePreview
+                    && textarea.value === examplePreview.text
+                    && statusCode === examplePreview.status;
+
                 const delayResult = await this.controller.handleSetDelay(collection.id, endpoint.id, delay);
-                const statusCodeResult = await this.controller.handleSetCustomStatusCode(collection.id, endpoint.id, statusCode);
-                const responseResult = await this.controller.handleSetCustomResponse(collection.id, endpoint.id, response);
+                const statusCodeResult = await this.controller.handleSetCustomStatusCode(
+                    collection.id, endpoint.id, fromExample ? null : statusCode
+                );
+                const responseResult = await this.controller.handleSetCustomResponse(
+                    collection.id, endpoint.id, fromExample ? null : response
+                );
+                const exampleResult = await this.controller.handleSetSelectedExample(
+                    collection.id, endpoint.id, exampleSelect.value || null
+                );
 
-                if (responseResult.success && delayResult.success && statusCodeResult.success) {
+                if (responseResult.success && delayResult.success && statusCodeResult.success && exampleResult.success) {
                     cleanup();
                     const [updatedSettings, collections] = await Promise.all([
                         this.controller.getSettings(),
@@ -799,7 +1096,8 @@ export class MockServerDialog {
                     ]);
                     await this.renderCollections(collections, updatedSettings);
                 } else {
-                    errorDiv.textContent = responseResult.message || delayResult.message || statusCodeResult.message;
+                    errorDiv.textContent = responseResult.message || delayResult.message
+                        || statusCodeResult.message || exampleResult.message;
                 }
             } catch (e) {
                 errorDiv.textContent = t('mock_server.invalid_json', `Invalid JSON: ${e.message}`);
@@ -810,7 +1108,8 @@ export class MockServerDialog {
             const delayResult = await this.controller.handleSetDelay(collection.id, endpoint.id, 0);
             const statusCodeResult = await this.controller.handleSetCustomStatusCode(collection.id, endpoint.id, null);
             const responseResult = await this.controller.handleSetCustomResponse(collection.id, endpoint.id, null);
-            if (responseResult.success && delayResult.success && statusCodeResult.success) {
+            const exampleResult = await this.controller.handleSetSelectedExample(collection.id, endpoint.id, null);
+            if (responseResult.success && delayResult.success && statusCodeResult.success && exampleResult.success) {
                 cleanup();
                 const [updatedSettings, collections] = await Promise.all([
                     this.controller.getSettings(),
@@ -0,0 +1,241 @@
+/**
+ * @fileoverview Modal dialog running network diagnostics (ping, traceroute,
+ * DNS lookup, TLS check) against the host of a request
+ * @module ui/NetworkDiagnosticsDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+import { app } from '../appContext.js';
+
+/**
+ * Dialog for checking whether a failing request is an API or a network problem
+ *
+ * @class
+ * @classdesc Runs one diagnostics tool at a time against a host or URL and
+ * prints its result in a terminal-like view. The target is pre-filled with
+ * the URL of the current request.
+ * @augments BaseModal
+ */
+export class NetworkDiagnosticsDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {boolean} Whether a tool is running. */
+        this.running = false;
+    }
+
+    /**
+     * Shows the dialog.
+     *
+     * @param {string} [target=''] - Host or URL to pre-fill
+     * @returns {void}
+     */
+    show(target = '') {
+        const dialog = this.mount({
+            overlayClass: 'network-diagnostics-overlay',
+            dialogClass: 'network-diagnostics-dialog modal-dialog modal-dialog--lg',
+            templatePath: './src/templates/dialogs/networkDiagnostics.html',
+            templateId: 'tpl-network-diagnostics-dialog'
+        });
+        if (app.i18n && app.i18n.updateUI) {
+            app.i18n.updateUI();
+        }
+
+        const targetInput = dialog.querySelector('#network-diagnostics-target');
+        const toolSelect = dialog.querySelector('#network-diagnostics-tool');
+        targetInput.value = target;
+
+        const updateFields = () => {
+            const tool = toolSelect.value;
+            dialog.querySelector('[data-role="port-field"]').hidden = tool !== 'tcp' && tool !== 'tls';
+            dialog.querySelector('[data-role="record-types"]').hidden = tool !== 'dns';
+        };
+        toolSelect.addEventListener('change', updateFields);
+        updateFields();
+
+        targetInput.addEventListener('keydown', (e) => {
+            if (e.key === 'Enter') {
+                this.run();
+            }
+        });
+        dialog.querySelector('#network-diagnostics-run-btn').addEventListener('click', () => this.run());
+        dialog.querySelector('#network-diagnostics-close-btn').addEventListener('click', () => this.destroy());
+
+        targetInput.focus();
+        targetInput.select();
+    }
+
+    /**
+     * Runs the selected tool and prints its result.
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async run() {
+        const dialog = this.dialog;
+        if (!dialog || this.running) {
+            return;
+        }
+        const target = dialog.querySelector('#network-diagnostics-target').value.trim();
+        if (!target) {
+            return;
+        }
+        const tool = dialog.querySelector('#network-diagnostics-tool').value;
+        const output = dialog.querySelector('[data-role="output"]');
+        const runBtn = dialog.querySelector('#network-diagnostics-run-btn');
+
+        this.running = true;
+        runBtn.disabled = true;
+        output.classList.remove('diagnostics-output-error');
+        output.textContent = app.i18n?.t('network_diagnostics.running') || 'Running...';
+
+        try {
+            const text = await this.runTool(tool, target);
+            if (this.dialog) {
+                output.textContent = text;
+            }
+        } catch (error) {
+            if (this.dialog) {
+                output.classList.add('diagnostics-output-error');
+                output.textContent = error?.message || String(error);
+            }
+        } finally {
+            this.running = false;
+            runBtn.disabled = false;
+        }
+    }
+
+    /**
+     * @private
+     * @async
+     * @param {string} tool - "tcp", "icmp", "traceroute", "dns" or "tls"
+     * @param {string} target - Host or URL
+     * @returns {Promise<string>} The formatted result
+     */
+    async runTool(tool, target) {
+        const diagnostics = window.backendAPI.diagnostics;
+        if (tool === 'traceroute') {
+            return formatTraceroute(await diagnostics.traceroute(target));
+        }
+        if (tool === 'dns') {
+            const recordTypes = [...this.dialog.querySelectorAll('[data-role="record-types"] input:checked')]
+                .map(input => input.value);
+            return formatDnsLookup(await diagnostics.dnsLookup(target, recordTypes));
+        }
+        const port = parseInt(this.dialog.querySelector('#network-diagnostics-port').value, 10);
+        if (tool === 'tls') {
+            return formatTlsProbe(await diagnostics.tlsProbe(target, Number.isNaN(port) ? null : port));
+        }
+        return formatPing(await diagnostics.ping(target, tool, Number.isNaN(port) ? null : port));
+    }
+}
+
+/**
+ * @param {number|null} ms - Time in milliseconds
+ * @returns {string}
+ */
+function formatMs(ms) {
+    return ms === null || ms === undefined ? '*' : `${ms} ms`;
+}
+
+/**
+ * @param {Object} result - Ping result from the backend
+ * @returns {string}
+ */
+function formatPing(result) {
+    const method = result.method === 'tcp' ? 'TCP' : 'ICMP';
+    const port = result.port ? ` port ${result.port}` : '';
+    const lines = [`${method} ping ${result.host} (${result.address})${port}`, ''];
+
+    result.replies.forEach(reply => {
+        lines.push(reply.timeMs === null
+            ? `seq=${reply.seq}  ${reply.error}`
+            : `seq=${reply.seq}  time=${formatMs(reply.timeMs)}`);
+    });
+
+    const loss = Math.round((1 - result.received / result.sent) * 100);
+    lines.push('', app.i18n?.t('network_diagnostics.ping_summary', {
+        sent: result.sent, received: result.received, loss
+    }) || `${result.sent} sent, ${result.received} received, ${loss}% loss`);
+    if (result.received > 0) {
+        lines.push(`min/avg/max = ${result.minMs}/${result.avgMs}/${result.maxMs} ms`);
+    }
+    return lines.join('\n');
+}
+
+/**
+ * @param {Object} result - Traceroute result from the backend
+ * @returns {string}
+ */
+function formatTraceroute(result) {
+    const lines = [`traceroute ${result.host} (${result.address})`, ''];
+    result.hops.forEach(hop => {
+        const times = [
+            ...hop.timesMs.map(formatMs),
+            ...Array(hop.lost).fill('*')
+        ];
+        lines.push(`${String(hop.hop).padStart(2)}  ${(hop.address || '*').padEnd(39)}  ${times.join('  ')}`);
+    });
+    return lines.join('\n');
+}
+
+/**
+ * @param {Object} result - DNS lookup result from the backend
+ * @returns {string}
+ */
+function formatDnsLookup(result) {
+    const lines = [`DNS ${result.host} (${result.durationMs} ms)`, ''];
+    if (result.records.length === 0 && result.errors.length === 0) {
+        lines.push(app.i18n?.t('network_diagnostics.no_records') || 'No records found');
+    }
+    result.records.forEach(record => {
+        lines.push(`${record.recordType.padEnd(6)} ${record.name}  ${record.ttl}  ${record.value}`);
+    });
+    result.errors.forEach(error => {
+        lines.push(`${error.recordType.padEnd(6)} ${error.message}`);
+    });
+    return lines.join('\n');
+}
+
+/**
+ * @param {Object} result - TLS probe result from the backend
+ * @returns {string}
+ */
+function formatTlsProbe(result) {
+    const mark = supported => (supported ? 'yes' : 'no ');
+    const lines = [`TLS ${result.host}:${result.port} (${result.address}, ${result.durationMs} ms)`, ''];
+    if (result.negotiatedVersion) {
+        lines.push(`Negotiated  ${result.negotiatedVersion}  ${result.negotiatedCipherSuite || ''}`, '');
+    }
+
+    result.versions.forEach(version => {
+        lines.push(`${version.version.padEnd(8)} ${mark(version.supported)}`);
+    });
+    lines.push('');
+    result.cipherSuites.forEach(suite => {
+        lines.push(`${mark(suite.supported)}  ${suite.version.padEnd(8)} ${suite.name}`);
+    });
+
+    lines.push('');
+    result.certificates.forEach((certificate, index) => {
+        lines.push(`${index}  ${certificate.subject ?? certificate.fingerprint}`);
+        if (certificate.issuer) {
+            lines.push(`   issuer  ${certificate.issuer}`);
+            lines.push(`   valid   ${certificate.notBefore} - ${certificate.notAfter}`);
+        }
+    });
+    if (result.verificationProblem) {
+        lines.push(`   ${result.verificationProblem}`);
+    }
+
+    lines.push('', app.i18n?.t('network_diagnostics.ocsp_stapled', {
+        status: result.ocspStapled ? 'yes' : 'no'
+    }) || `OCSP stapling: ${result.ocspStapled ? 'yes' : 'no'}`);
+    const days = result.daysToExpiry;
+    if (days !== null && days !== undefined) {
+        lines.push(days >= 0
+            ? app.i18n?.t('network_diagnostics.expires_in', { days }) || `Certificate expires in ${days} days`
+            : app.i18n?.t('network_diagnostics.expired', { days: -days }) || `Certificate expired ${-days} days ago`);
+    }
+    return lines.join('\n');
+}
@@ -5,10 +5,19 @@
  * Handles HTML (iframe), JSON (tree view), and XML (formatted tree).
  */
 import { templateLoader } from '../templateLoader.js';
+import { app } from '../appContext.js';
+import { isLargeJson } from '../largeJson.js';
 
 export class PreviewRenderer {
-    constructor(containerElement) {
+    /**
+     * @param {HTMLElement} containerElement - Preview container
+     * @param {string|null} documentId - Backend JSON document id (the workspace
+     *   tab id) used to page the tree of large JSON responses
+     */
+    constructor(containerElement, documentId = null) {
         this.container = containerElement;
+        this.documentId = documentId;
+        this._renderGeneration = 0;
     }
 
     /**
@@ -44,6 +53,10 @@ export class PreviewRenderer {
      * @private
      */
     _renderJSON(content) {
+        if (this.documentId && isLargeJson(content.length) && window.backendAPI?.jsonDocument) {
+            this._renderLargeJSON(content);
+            return;
+        }
         try {
             const data = JSON.parse(content);
             const tree = this._buildJSONTree(data);
@@ -54,6 +67,134 @@ export class PreviewRenderer {
         }
     }
 
+    /**
+     * Render a large JSON document as a tree paged from the backend: only
+     * the first level is loaded, deeper levels load when expanded.
+     * @private
+     */
+    async _renderLargeJSON(content) {
+        const generation = this._renderGeneration;
+        const api = window.backendAPI.jsonDocument;
+        let root;
+        try {
+            root = await api.page(this.documentId, '');
+        } catch {
+            try {
+                root = (await api.open(this.documentId, content)).root;
+            } catch {
+                root = null;
+            }
+        }
+        if (generation !== this._renderGeneration) {
+            return;
+        }
+        if (!root) {
+            this._renderError('Invalid JSON');
+            return;
+        }
+
+        const tree = document.createElement('div');
+        tree.className = 'json-tree-node json-tree';
+        const children = document.createElement('div');
+        children.className = 'json-tree-children';
+        this._appendPagedChildren(children, root);
+        tree.append(root.kind === 'array' ? '[' : '{', children, root.kind === 'array' ? ']' : '}');
+        this.container.appendChild(tree);
+    }
+
+    /**
+     * Append the nodes of a backend page, plus a button loading the next page
+     * @private
+     * @param {HTMLElement} children - Children container of the parent node
+     * @param {Object} page - JsonNodePage from `json_document_page`
+     */
+    _appendPagedChildren(children, page) {
+        page.children.forEach(child => children.appendChild(this._buildPagedLine(child)));
+
+        const loaded = page.offset + page.children.length;
+        if (loaded >= page.total) {
+            return;
+        }
+        const more = document.createElement('button');
+        more.type = 'button';
+        more.className = 'btn btn-secondary btn-xs json-tree-more';
+        more.textContent = app.i18n?.t('preview.show_more', { count: page.total - loaded })
+            || `Show more (${page.total - loaded} left)`;
+        more.addEventListener('click', async () => {
+            more.disabled = true;
+            try {
+                const next = await window.backendAPI.jsonDocument.page(this.documentId, page.pointer, loaded);
+                more.remove();
+                this._appendPagedChildren(children, next);
+            } catch {
+                more.disabled = false;
+            }
+        });
+        children.appendChild(more);
+    }
+
+    /**
+     * Build one line of a paged tree; containers load their children from
+     * the backend the first time they are expanded.
+     * @private
+     * @param {Object} node - JsonNode from the backend
+     * @returns {HTMLElement}
+     */
+    _buildPagedLine(node) {
+        const line = document.createElement('div');
+        line.className = 'json-tree-line';
+
+        if (node.key !== null && node.key !== undefined) {
+            const keySpan = document.createElement('span');
+            keySpan.className = 'json-tree-key';
+            keySpan.textContent = `"${node.key}"`;
+            line.append(keySpan, ': ');
+        }
+
+        const valueNode = document.createElement('div');
+        valueNode.className = 'json-tree-node';
+        line.appendChild(valueNode);
+
+        const isContainer = node.kind === 'object' || node.kind === 'array';
+        if (!isContainer || node.childCount === 0) {
+            const valueSpan = document.createElement('span');
+            valueSpan.className = `json-tree-${node.kind}`;
+            valueSpan.textContent = isContainer ? (node.kind === 'array' ? '[]' : '{}') : node.preview;
+            valueNode.appendChild(valueSpan);
+            return line;
+        }
+
+        const toggle = document.createElement('span');
+        toggle.className = 'json-tree-toggle';
+        toggle.textContent = '▶';
+        const summary = document.createElement('span');
+        summary.textContent = node.preview;
+        const children = document.createElement('div');
+        children.className = 'json-tree-children';
+        children.style.display = 'none';
+        valueNode.append(toggle, summary, children);
+
+        let loaded = false;
+        toggle.addEventListener('click', async (e) => {
+            e.stopPropagation();
+            const isExpanded = children.style.display === 'block';
+            if (!isExpanded && !loaded) {
+                try {
+                    this._appendPagedChildren(
+                        children,
+                        await window.backendAPI.jsonDocument.page(this.documentId, node.pointer)
+                    );
+                    loaded = true;
+                } catch {
+                    return;
+                }
+            }
+            children.style.display = isExpanded ? 'none' : 'block';
+            toggle.textContent = isExpanded ? '▶' : '▼';
+        });
+        return line;
+    }
+
     /**
      * Build interactive JSON tree
      * @private
@@ -542,6 +683,7 @@ export class PreviewRenderer {
      * Clear preview container
      */
     clear() {
+        this._renderGeneration++;
         this.container.innerHTML = '';
     }
 }
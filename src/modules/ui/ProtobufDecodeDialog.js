@@ -0,0 +1,109 @@
+/**
+ * @fileoverview Modal dialog for picking the protobuf message type of an HTTP response
+ * @module ui/ProtobufDecodeDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+import { app } from '../appContext.js';
+
+/**
+ * Dialog asking which loaded message type a protobuf body holds
+ *
+ * @class
+ * @classdesc Offers the message types of the loaded proto files as
+ * suggestions for a free-text input, so long lists stay searchable. Enter or
+ * Decode confirms; Escape and click-outside (handled by {@link BaseModal})
+ * cancel.
+ * @augments BaseModal
+ */
+export class ProtobufDecodeDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog and waits for a message type.
+     *
+     * @param {Array<string>} messageTypes - Full names of the loaded message types
+     * @param {string} [current=''] - Type to pre-fill
+     * @returns {Promise<string|null>} The chosen type, or null if cancelled
+     */
+    show(messageTypes, current = '') {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'protobuf-decode-dialog-overlay',
+                dialogClass: 'protobuf-decode-dialog modal-dialog modal-dialog--sm',
+                templatePath: './src/templates/dialogs/protobufDecode.html',
+                templateId: 'tpl-protobuf-decode-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI();
+            }
+
+            const list = dialog.querySelector('[data-role="message-types"]');
+            messageTypes.forEach(type => {
+                const option = document.createElement('option');
+                option.value = type;
+                list.appendChild(option);
+            });
+
+            const input = dialog.querySelector('#protobuf-message-type');
+            input.value = current;
+            input.addEventListener('keydown', (e) => {
+                if (e.key === 'Enter') {
+                    this.confirm(input.value);
+                }
+            });
+            dialog.querySelector('#protobuf-decode-cancel-btn')
+                .addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('#protobuf-decode-confirm-btn')
+                .addEventListener('click', () => this.confirm(input.value));
+
+            input.focus();
+            input.select();
+        });
+    }
+
+    /**
+     * Resolves with the entered type. No-op when empty.
+     *
+     * @private
+     * @param {string} value - The entered type
+     * @returns {void}
+     */
+    confirm(value) {
+        const type = value.trim();
+        if (type) {
+            this._settle(type);
+        }
+    }
+
+    /**
+     * Cancels the dialog, resolving with null.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down.
+     *
+     * @private
+     * @param {string|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
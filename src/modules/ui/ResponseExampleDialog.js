@@ -0,0 +1,154 @@
+/**
+ * @fileoverview Modal dialog for naming a response saved as an endpoint example
+ * @module ui/ResponseExampleDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+import { app } from '../appContext.js';
+
+/**
+ * Dialog asking for the name of a new example, listing the endpoint's saved ones
+ *
+ * @class
+ * @classdesc Saved examples can be deleted from the list; clicking one
+ * pre-fills its name so the response replaces it. Enter or Save confirms;
+ * Escape and click-outside (handled by {@link BaseModal}) cancel.
+ * @augments BaseModal
+ */
+export class ResponseExampleDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog and waits for a name.
+     *
+     * @param {Array<Object>} examples - The endpoint's saved examples
+     * @param {string} defaultName - Name to pre-fill
+     * @param {Function} onDelete - Deletes an example by ID, resolving with the remaining ones
+     * @returns {Promise<string|null>} The entered name, or null if cancelled
+     */
+    show(examples, defaultName, onDelete) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'response-example-dialog-overlay',
+                dialogClass: 'response-example-dialog modal-dialog modal-dialog--sm',
+                templatePath: './src/templates/dialogs/responseExample.html',
+                templateId: 'tpl-response-example-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI();
+            }
+
+            const input = dialog.querySelector('#response-example-name');
+            input.value = defaultName;
+            input.addEventListener('keydown', (e) => {
+                if (e.key === 'Enter') {
+                    this.confirm(input.value);
+                }
+            });
+            dialog.querySelector('#response-example-cancel-btn')
+                .addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('#response-example-save-btn')
+                .addEventListener('click', () => this.confirm(input.value));
+
+            this.renderList(dialog, examples, input, onDelete);
+            input.focus();
+            input.select();
+        });
+    }
+
+    /**
+     * Renders the saved examples with their delete buttons.
+     *
+     * @private
+     * @param {HTMLElement} dialog - The dialog element
+     * @param {Array<Object>} examples - The endpoint's saved examples
+     * @param {HTMLInputElement} input - The name input
+     * @param {Function} onDelete - Deletes an example by ID
+     * @returns {void}
+     */
+    renderList(dialog, examples, input, onDelete) {
+        dialog.querySelector('[data-role="saved-section"]').classList.toggle('is-hidden', examples.length === 0);
+        const list = dialog.querySelector('[data-role="saved-list"]');
+        list.replaceChildren();
+
+        examples.forEach(example => {
+            const item = document.createElement('li');
+            item.className = 'response-example-item u-flex u-items-center';
+
+            const name = document.createElement('button');
+            name.type = 'button';
+            name.className = 'response-example-name';
+            name.textContent = example.name;
+            name.addEventListener('click', () => {
+                input.value = example.name;
+                input.focus();
+            });
+
+            const status = document.createElement('span');
+            status.className = 'response-example-status';
+            status.textContent = String(example.status);
+
+            const remove = document.createElement('button');
+            remove.type = 'button';
+            remove.className = 'btn btn-icon';
+            const label = app.i18n?.t('response_example.delete') || 'Delete example';
+            remove.setAttribute('aria-label', label);
+            remove.title = label;
+            remove.innerHTML = '<span class="icon icon-14 icon-trash"></span>';
+            remove.addEventListener('click', async () => {
+                const remaining = await onDelete(example.id);
+                if (remaining) {
+                    this.renderList(dialog, remaining, input, onDelete);
+                }
+            });
+
+            item.append(name, status, remove);
+            list.appendChild(item);
+        });
+    }
+
+    /**
+     * Resolves with the entered name. No-op when empty.
+     *
+     * @private
+     * @param {string} value - The entered name
+     * @returns {void}
+     */
+    confirm(value) {
+        const name = value.trim();
+        if (name) {
+            this._settle(name);
+        }
+    }
+
+    /**
+     * Cancels the dialog, resolving with null.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down.
+     *
+     * @private
+     * @param {string|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
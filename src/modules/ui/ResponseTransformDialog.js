@@ -0,0 +1,97 @@
+/**
+ * @fileoverview Dialog editing the response transform of a request: JSONPath
+ * projection, masked fields and key sorting, applied by the backend before the
+ * response is shown
+ * @module ui/ResponseTransformDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Splits a textarea into its non-empty trimmed lines
+ *
+ * @param {string} text - Textarea value
+ * @returns {Array<string>} The lines
+ */
+function lines(text) {
+    return text.split('\n').map(line => line.trim()).filter(Boolean);
+}
+
+/**
+ * Modal editing a request's response transform
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class ResponseTransformDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog
+     *
+     * @param {Object|null} current - The request's transform ({project, mask, sortKeys})
+     * @returns {Promise<Object|null>} The edited transform, or null when cancelled
+     */
+    show(current) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'response-transform-dialog-overlay',
+                dialogClass: 'response-transform-dialog modal-dialog',
+                templatePath: './src/templates/dialogs/responseTransform.html',
+                templateId: 'tpl-response-transform-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI(dialog);
+            }
+
+            const project = dialog.querySelector('#response-transform-project');
+            const mask = dialog.querySelector('#response-transform-mask');
+            const sortKeys = dialog.querySelector('#response-transform-sort-keys');
+            project.value = (current?.project || []).join('\n');
+            mask.value = (current?.mask || []).join('\n');
+            sortKeys.checked = current?.sortKeys === true;
+
+            dialog.querySelector('[data-role="cancel"]').addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('[data-role="confirm"]').addEventListener('click', () => {
+                this._settle({
+                    project: lines(project.value),
+                    mask: lines(mask.value),
+                    sortKeys: sortKeys.checked
+                });
+            });
+            project.focus();
+        });
+    }
+
+    /**
+     * Cancels the dialog, resolving with null
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down
+     *
+     * @private
+     * @param {Object|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
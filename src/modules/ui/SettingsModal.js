@@ -5,6 +5,7 @@
 
 import { app } from '../appContext.js';
 import { templateLoader } from '../templateLoader.js';
+import { toast } from './Toast.js';
 
 export class SettingsModal {
     constructor(themeManager, i18nManager = null, httpVersionManager = null, timeoutManager = null, proxyController = null, certificateController = null) {
@@ -58,12 +59,26 @@ export class SettingsModal {
 
         let currentVerifySsl = true;
         let currentFollowRedirects = true;
+        let currentTraceHeaders = false;
+        let currentOtlpEndpoint = '';
+        let currentCaBundlePath = '';
+        let currentIpFamily = 'auto';
         let currentHistoryLimit = 100;
         let currentCheckUpdatesOnLaunch = false;
+        let currentOfflineMode = false;
+        try {
+            currentOfflineMode = await window.backendAPI.offlineMode.get();
+        } catch (e) {
+            void e;
+        }
         try {
             const settings = await window.backendAPI.settings.get();
             currentVerifySsl = settings.verifySsl !== false;
             currentFollowRedirects = settings.followRedirects !== false;
+            currentTraceHeaders = settings.traceHeaders === true;
+            currentOtlpEndpoint = settings.otlpEndpoint || '';
+            currentCaBundlePath = settings.caBundlePath || '';
+            currentIpFamily = settings.ipFamily || 'auto';
             currentHistoryLimit = settings.historyLimit || 100;
             currentCheckUpdatesOnLaunch = settings.checkUpdatesOnLaunch === true;
         } catch (e) {
@@ -80,6 +95,11 @@ export class SettingsModal {
             httpVersionSelect.value = currentHttpVersion;
         }
 
+        const ipFamilySelect = overlay.querySelector('select[name="ipFamily"]');
+        if (ipFamilySelect) {
+            ipFamilySelect.value = currentIpFamily;
+        }
+
         const timeoutInput = overlay.querySelector('input[name="requestTimeout"]');
         if (timeoutInput) {
             timeoutInput.value = currentTimeout;
@@ -95,6 +115,26 @@ export class SettingsModal {
             followRedirectsCheckbox.checked = currentFollowRedirects;
         }
 
+        const traceHeadersCheckbox = overlay.querySelector('input[name="traceHeaders"]');
+        if (traceHeadersCheckbox) {
+            traceHeadersCheckbox.checked = currentTraceHeaders;
+        }
+
+        const otlpEndpointInput = overlay.querySelector('input[name="otlpEndpoint"]');
+        if (otlpEndpointInput) {
+            otlpEndpointInput.value = currentOtlpEndpoint;
+        }
+
+        const caBundleInput = overlay.querySelector('input[name="caBundlePath"]');
+        if (caBundleInput) {
+            caBundleInput.value = currentCaBundlePath;
+        }
+
+        const offlineModeCheckbox = overlay.querySelector('input[name="offlineMode"]');
+        if (offlineModeCheckbox) {
+            offlineModeCheckbox.checked = currentOfflineMode;
+        }
+
         const historyLimitInput = overlay.querySelector('input[name="historyLimit"]');
         if (historyLimitInput) {
             historyLimitInput.value = currentHistoryLimit;
@@ -303,9 +343,41 @@ export class SettingsModal {
             bypassInput.value = (settings.bypassList || []).join(', ');
         }
 
+        const httpsInput = section.querySelector('input[name="proxyHttps"]');
+        if (httpsInput) {
+            httpsInput.value = this.formatProxyServer(settings.httpsProxy);
+        }
+
+        const grpcInput = section.querySelector('input[name="proxyGrpc"]');
+        if (grpcInput) {
+            grpcInput.value = this.formatProxyServer(settings.grpcProxy);
+        }
+
         return section;
     }
 
+    /**
+     * Formats a scheme-specific proxy server as `type://host:port`.
+     *
+     * @param {Object|null} server
+     * @returns {string}
+     */
+    formatProxyServer(server) {
+        return server?.host ? `${server.type || 'http'}://${server.host}:${server.port}` : '';
+    }
+
+    /**
+     * Parses `type://host:port` (type defaults to http) into a proxy server object.
+     *
+     * @param {string} text
+     * @returns {Object|null} `{ type, host, port }`, or null when empty or invalid
+     */
+    parseProxyServer(text) {
+        const match = text.trim().match(/^(?:(https?|socks[45]):\/\/)?([^:/\s]+):(\d+)\/?$/);
+        if (!match) {return null;}
+        return { type: match[1] || 'http', host: match[2], port: parseInt(match[3], 10) };
+    }
+
     async createCertsSectionDOM() {
         const fragment = templateLoader.cloneSync(
             './src/templates/settings/settingsModal.html',
@@ -328,7 +400,7 @@ export class SettingsModal {
         if (addBtn) {
             addBtn.addEventListener('click', () => {
                 const row = this._renderCertEntry({
-                    host: '', certPath: '', keyPath: '', caPath: '', enabled: true
+                    host: '', certPath: '', keyPath: '', caPath: '', pins: '', enabled: true
                 });
                 this._certsListEl.appendChild(row);
                 this._updateCertsEmpty(section);
@@ -350,6 +422,7 @@ export class SettingsModal {
 
         const host = row.querySelector('input[name="certHost"]');
         const enabled = row.querySelector('input[name="certEnabled"]');
+        const pins = row.querySelector('textarea[name="certPins"]');
         const pathInputs = {
             cert: row.querySelector('input[name="certCertPath"]'),
             key: row.querySelector('input[name="certKeyPath"]'),
@@ -361,8 +434,13 @@ export class SettingsModal {
         pathInputs.cert.value = item.certPath || '';
         pathInputs.key.value = item.keyPath || '';
         pathInputs.ca.value = item.caPath || '';
+        pins.value = item.pins || '';
 
         host.addEventListener('input', () => this._saveCerts());
+        pins.addEventListener('input', () => {
+            this._validateRow(row);
+            this._saveCerts();
+        });
         enabled.addEventListener('change', () => this._saveCerts());
 
         row.querySelectorAll('[data-role="cert-pick"]').forEach(btn => {
@@ -410,7 +488,8 @@ export class SettingsModal {
             host: row.querySelector('input[name="certHost"]').value,
             certPath: row.querySelector('input[name="certCertPath"]').value,
             keyPath: row.querySelector('input[name="certKeyPath"]').value,
-            caPath: row.querySelector('input[name="certCaPath"]').value
+            caPath: row.querySelector('input[name="certCaPath"]').value,
+            pins: row.querySelector('textarea[name="certPins"]').value
         });
         const pairing = errors.find(e => e.toLowerCase().includes('key file'));
         if (pairing) {
@@ -431,6 +510,7 @@ export class SettingsModal {
             certPath: row.querySelector('input[name="certCertPath"]').value.trim(),
             keyPath: row.querySelector('input[name="certKeyPath"]').value.trim(),
             caPath: row.querySelector('input[name="certCaPath"]').value.trim(),
+            pins: row.querySelector('textarea[name="certPins"]').value.trim(),
             enabled: row.querySelector('input[name="certEnabled"]').checked
         }));
     }
@@ -537,6 +617,44 @@ export class SettingsModal {
             });
         }
 
+        const caBundleInput = overlay.querySelector('input[name="caBundlePath"]');
+        const saveCaBundlePath = async (path) => {
+            try {
+                const settings = await window.backendAPI.settings.get();
+                settings.caBundlePath = path;
+                await window.backendAPI.settings.set(settings);
+            } catch (err) {
+                void err;
+            }
+        };
+        if (caBundleInput) {
+            caBundleInput.addEventListener('change', (e) => saveCaBundlePath(e.target.value.trim()));
+        }
+        const pickCaBundleButton = overlay.querySelector('#pick-ca-bundle-btn');
+        if (pickCaBundleButton && caBundleInput) {
+            pickCaBundleButton.addEventListener('click', async () => {
+                const pickedPath = await window.backendAPI.certificates.pickFile('ca');
+                if (pickedPath) {
+                    caBundleInput.value = pickedPath;
+                    await saveCaBundlePath(pickedPath);
+                }
+            });
+        }
+
+        const ipFamilySelect = overlay.querySelector('select[name="ipFamily"]');
+        if (ipFamilySelect) {
+            ipFamilySelect.addEventListener('change', async (e) => {
+                try {
+                    const settings = await window.backendAPI.settings.get();
+                    settings.ipFamily = e.target.value;
+                    await window.backendAPI.settings.set(settings);
+                    app.invalidateApiHandlerSettingsCache?.();
+                } catch (err) {
+                    void err;
+                }
+            });
+        }
+
         const followRedirectsCheckbox = overlay.querySelector('input[name="followRedirects"]');
         if (followRedirectsCheckbox) {
             followRedirectsCheckbox.addEventListener('change', async (e) => {
@@ -551,6 +669,53 @@ export class SettingsModal {
             });
         }
 
+        const traceHeadersCheckbox = overlay.querySelector('input[name="traceHeaders"]');
+        if (traceHeadersCheckbox) {
+            traceHeadersCheckbox.addEventListener('change', async (e) => {
+                try {
+                    const settings = await window.backendAPI.settings.get();
+                    settings.traceHeaders = e.target.checked;
+                    await window.backendAPI.settings.set(settings);
+                    app.invalidateApiHandlerSettingsCache?.();
+                } catch (err) {
+                    void err;
+                }
+            });
+        }
+
+        const otlpEndpointInput = overlay.querySelector('input[name="otlpEndpoint"]');
+        if (otlpEndpointInput) {
+            otlpEndpointInput.addEventListener('change', async (e) => {
+                const endpoint = e.target.value.trim();
+                if (endpoint && !/^https?:\/\//i.test(endpoint)) {
+                    toast.error(app.i18n?.t('settings.otlp_endpoint_invalid') || 'The collector address must start with http:// or https://');
+                    return;
+                }
+                try {
+                    const settings = await window.backendAPI.settings.get();
+                    settings.otlpEndpoint = endpoint;
+                    await window.backendAPI.settings.set(settings);
+                } catch (err) {
+                    void err;
+                }
+            });
+        }
+
+        const offlineModeCheckbox = overlay.querySelector('input[name="offlineMode"]');
+        if (offlineModeCheckbox) {
+            offlineModeCheckbox.addEventListener('change', async (e) => {
+                try {
+                    await window.backendAPI.offlineMode.set(e.target.checked);
+                    toast.info(e.target.checked
+                        ? (app.i18n?.t('settings.offline_mode_on') || 'Offline mode on: only localhost and mock servers can be reached')
+                        : (app.i18n?.t('settings.offline_mode_off') || 'Offline mode off'));
+                } catch (error) {
+                    e.target.checked = !e.target.checked;
+                    toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to change offline mode'));
+                }
+            });
+        }
+
         const historyLimitInput = overlay.querySelector('input[name="historyLimit"]');
         if (historyLimitInput) {
             historyLimitInput.addEventListener('change', async (e) => {
@@ -672,6 +837,9 @@ export class SettingsModal {
             });
         }
 
+        this.attachCrashReportListeners(overlay);
+        this.attachSettingsTransferListeners(overlay);
+
         overlay.addEventListener('click', (e) => {
             if (e.target === overlay) {
                 this.hide(overlay);
@@ -687,6 +855,86 @@ export class SettingsModal {
         document.addEventListener('keydown', escapeHandler);
     }
 
+    attachCrashReportListeners(overlay) {
+        const status = overlay.querySelector('#crash-reports-status');
+        const copyBtn = overlay.querySelector('#copy-crash-report-btn');
+        const clearBtn = overlay.querySelector('#clear-crash-reports-btn');
+        if (!status || !copyBtn || !clearBtn || !window.backendAPI?.crashReports) {
+            return;
+        }
+
+        let reports = [];
+        const refresh = async () => {
+            try {
+                reports = await window.backendAPI.crashReports.list();
+            } catch (e) {
+                void e;
+                reports = [];
+            }
+            if (reports.length === 0) {
+                status.textContent = app.i18n?.t('settings.crash_reports_none') || 'No crash reports';
+            } else {
+                const date = new Date(reports[0].createdAt).toLocaleString();
+                status.textContent = app.i18n?.t('settings.crash_reports_count', { count: reports.length, date })
+                    || `${reports.length} saved, latest ${date}. Attach the latest report when filing an issue.`;
+            }
+            copyBtn.disabled = reports.length === 0;
+            clearBtn.disabled = reports.length === 0;
+        };
+
+        copyBtn.addEventListener('click', async () => {
+            try {
+                const report = await window.backendAPI.crashReports.get(reports[0].id);
+                await navigator.clipboard.writeText(report);
+                toast.success(app.i18n?.t('settings.crash_report_copied') || 'Crash report copied to clipboard');
+            } catch (error) {
+                toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to copy crash report'));
+            }
+        });
+
+        clearBtn.addEventListener('click', async () => {
+            try {
+                await window.backendAPI.crashReports.clear();
+            } catch (error) {
+                toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to clear crash reports'));
+            }
+            await refresh();
+        });
+
+        void refresh();
+    }
+
+    attachSettingsTransferListeners(overlay) {
+        const exportBtn = overlay.querySelector('#export-settings-btn');
+        const importBtn = overlay.querySelector('#import-settings-btn');
+        if (!exportBtn || !importBtn) {
+            return;
+        }
+
+        exportBtn.addEventListener('click', async () => {
+            try {
+                const result = await window.backendAPI.settings.exportToFile();
+                if (result?.success) {
+                    toast.success(app.i18n?.t('settings.settings_exported') || 'Settings exported');
+                }
+            } catch (error) {
+                toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to export settings'));
+            }
+        });
+
+        importBtn.addEventListener('click', async () => {
+            try {
+                const result = await window.backendAPI.settings.importFromFile();
+                if (result?.success) {
+                    // Theme, language and the open panels all read the store on startup
+                    window.location.reload();
+                }
+            } catch (error) {
+                toast.error(typeof error === 'string' ? error : (error?.message || 'Failed to import settings'));
+            }
+        });
+    }
+
     attachProxyEventListeners(overlay) {
         const proxyEnabled = overlay.querySelector('input[name="proxyEnabled"]');
         const proxyContent = overlay.querySelector('.proxy-settings-content');
@@ -703,6 +951,8 @@ export class SettingsModal {
         const proxyUsername = overlay.querySelector('input[name="proxyUsername"]');
         const proxyPassword = overlay.querySelector('input[name="proxyPassword"]');
         const proxyBypass = overlay.querySelector('input[name="proxyBypass"]');
+        const proxyHttps = overlay.querySelector('input[name="proxyHttps"]');
+        const proxyGrpc = overlay.querySelector('input[name="proxyGrpc"]');
 
         if (proxyEnabled && proxyContent) {
             proxyEnabled.addEventListener('change', async (e) => {
@@ -725,7 +975,7 @@ export class SettingsModal {
             });
         }
 
-        const proxyFields = [proxyType, proxyHost, proxyPort, proxyUsername, proxyPassword, proxyBypass];
+        const proxyFields = [proxyType, proxyHost, proxyPort, proxyUsername, proxyPassword, proxyBypass, proxyHttps, proxyGrpc];
         proxyFields.forEach(field => {
             if (field) {
                 field.addEventListener('change', async () => {
@@ -777,6 +1027,8 @@ export class SettingsModal {
             const username = overlay.querySelector('input[name="proxyUsername"]')?.value || '';
             const password = overlay.querySelector('input[name="proxyPassword"]')?.value || '';
             const bypassText = overlay.querySelector('input[name="proxyBypass"]')?.value || '';
+            const httpsProxy = this.parseProxyServer(overlay.querySelector('input[name="proxyHttps"]')?.value || '');
+            const grpcProxy = this.parseProxyServer(overlay.querySelector('input[name="proxyGrpc"]')?.value || '');
 
             const bypassList = bypassText
                 .split(',')
@@ -789,6 +1041,8 @@ export class SettingsModal {
                 type,
                 host,
                 port,
+                httpsProxy,
+                grpcProxy,
                 auth: {
                     enabled: authEnabled,
                     username,
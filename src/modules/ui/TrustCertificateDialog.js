@@ -0,0 +1,88 @@
+/**
+ * @fileoverview Modal dialog shown when a server's certificate fails
+ * verification: shows its fingerprint and lets the user trust it for the
+ * host for this session or always.
+ * @module ui/TrustCertificateDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Trust exception prompt for one host's certificate.
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class TrustCertificateDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog for a presented certificate.
+     *
+     * @param {Object} certificate - From `trustedHosts.inspect` ({host, fingerprint, problem?})
+     * @returns {Promise<'once'|'always'|null>} How to trust it, or null on cancel
+     */
+    show(certificate) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+            this._createDialog(certificate);
+        });
+    }
+
+    /**
+     * Builds the dialog.
+     *
+     * @private
+     * @param {Object} certificate
+     * @returns {void}
+     */
+    _createDialog(certificate) {
+        const dialog = this.mount({
+            overlayClass: 'trust-certificate-dialog-overlay',
+            dialogClass: 'trust-certificate-dialog modal-dialog modal-dialog--md',
+            templatePath: './src/templates/dialogs/trustCertificate.html',
+            templateId: 'tpl-trust-certificate-dialog'
+        });
+
+        dialog.querySelector('[data-role="message"]').textContent =
+            `${certificate.host} presented a certificate that could not be verified.`;
+        dialog.querySelector('[data-role="problem"]').textContent = certificate.problem || '';
+        dialog.querySelector('[name="fingerprint"]').value = certificate.fingerprint;
+
+        dialog.querySelector('#trust-certificate-close-btn')?.addEventListener('click', () => this.onDismiss());
+        dialog.querySelector('#trust-certificate-cancel-btn')?.addEventListener('click', () => this.onDismiss());
+        dialog.querySelector('#trust-certificate-once-btn')?.addEventListener('click', () => this._settle('once'));
+        const alwaysBtn = dialog.querySelector('#trust-certificate-always-btn');
+        alwaysBtn?.addEventListener('click', () => this._settle('always'));
+        alwaysBtn?.focus();
+    }
+
+    /**
+     * Dismiss (Escape / backdrop click) cancels.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears down.
+     *
+     * @private
+     * @param {'once'|'always'|null} value
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
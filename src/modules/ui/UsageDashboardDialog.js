@@ -0,0 +1,213 @@
+/**
+ * @fileoverview Modal dialog showing what has been sent lately: request
+ * counts, error rates and average latency per host or collection, with a
+ * daily activity chart for each
+ * @module ui/UsageDashboardDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+import { ConfirmDialog } from './ConfirmDialog.js';
+import { toast } from './Toast.js';
+
+/**
+ * Usage dashboard built from the backend's usage statistics
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class UsageDashboardDialog extends BaseModal {
+    /**
+     * Shows the dialog
+     *
+     * @returns {void}
+     */
+    show() {
+        const dialog = this.mount({
+            overlayClass: 'usage-dashboard-dialog-overlay',
+            dialogClass: 'usage-dashboard-dialog modal-dialog modal-dialog--lg',
+            templatePath: './src/templates/dialogs/usageDashboard.html',
+            templateId: 'tpl-usage-dashboard-dialog'
+        });
+        if (app.i18n && app.i18n.updateUI) {
+            app.i18n.updateUI(dialog);
+        }
+
+        dialog.querySelector('#usage-dashboard-group').addEventListener('change', () => this.refresh());
+        dialog.querySelector('#usage-dashboard-days').addEventListener('change', () => this.refresh());
+        dialog.querySelector('#usage-dashboard-clear-btn').addEventListener('click', () => this.clear());
+        dialog.querySelector('#usage-dashboard-close-btn').addEventListener('click', () => this.destroy());
+        dialog.querySelector('#usage-dashboard-done-btn').addEventListener('click', () => this.destroy());
+        this.refresh();
+    }
+
+    /**
+     * Queries the statistics for the selected grouping and period and renders them
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async refresh() {
+        const dialog = this.dialog;
+        if (!dialog) {
+            return;
+        }
+        const groupBy = dialog.querySelector('#usage-dashboard-group').value;
+        const days = Number(dialog.querySelector('#usage-dashboard-days').value);
+        try {
+            const [report, names] = await Promise.all([
+                window.backendAPI.usageStats.query(groupBy, days),
+                groupBy === 'collection' ? collectionNames() : Promise.resolve(new Map())
+            ]);
+            if (this.dialog) {
+                this.render(report, names);
+            }
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * @private
+     * @param {Object} report - `usage_stats_query` result ({days, totals, groups})
+     * @param {Map<string, string>} names - Collection names by ID
+     * @returns {void}
+     */
+    render(report, names) {
+        const { totals } = report;
+        this.dialog.querySelector('[data-role="summary"]').textContent = app.i18n?.t('usage_dashboard.summary', {
+            count: totals.count,
+            errorRate: percent(totals.errorRate),
+            latency: totals.avgLatencyMs ?? '-'
+        }) || `${totals.count} requests, ${percent(totals.errorRate)} errors, ${totals.avgLatencyMs ?? '-'} ms average`;
+
+        const list = this.dialog.querySelector('[data-role="groups"]');
+        list.innerHTML = '';
+        if (report.groups.length === 0) {
+            const empty = document.createElement('li');
+            empty.className = 'usage-dashboard-empty';
+            empty.textContent = app.i18n?.t('usage_dashboard.empty') || 'Nothing sent in this period';
+            list.appendChild(empty);
+            return;
+        }
+
+        report.groups.forEach(group => {
+            const row = document.createElement('li');
+            row.className = 'usage-dashboard-group';
+
+            const name = document.createElement('span');
+            name.className = 'usage-dashboard-name';
+            name.textContent = names.get(group.key)
+                || (names.size > 0 ? app.i18n?.t('usage_dashboard.deleted_collection') || 'Deleted collection' : group.key);
+            name.title = group.key;
+
+            const count = document.createElement('span');
+            count.className = 'usage-dashboard-metric';
+            count.textContent = app.i18n?.t('usage_dashboard.requests', { count: group.count })
+                || `${group.count} requests`;
+
+            const errors = document.createElement('span');
+            errors.className = 'usage-dashboard-metric';
+            errors.classList.toggle('has-errors', group.errors > 0);
+            errors.textContent = app.i18n?.t('usage_dashboard.errors', { rate: percent(group.errorRate) })
+                || `${percent(group.errorRate)} errors`;
+
+            const latency = document.createElement('span');
+            latency.className = 'usage-dashboard-metric';
+            latency.textContent = group.avgLatencyMs !== null && group.avgLatencyMs !== undefined
+                ? `${group.avgLatencyMs} ms`
+                : '-';
+
+            row.append(name, count, errors, latency, activityChart(group.series));
+            list.appendChild(row);
+        });
+    }
+
+    /**
+     * Clears all statistics after confirmation
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async clear() {
+        const confirmed = await new ConfirmDialog().show(
+            app.i18n?.t('usage_dashboard.clear_confirm') || 'Clear all usage statistics of this workspace?',
+            {
+                title: app.i18n?.t('usage_dashboard.clear') || 'Clear Statistics',
+                confirmText: app.i18n?.t('usage_dashboard.clear') || 'Clear Statistics'
+            }
+        );
+        if (!confirmed) {
+            return;
+        }
+        try {
+            await window.backendAPI.usageStats.clear();
+            await this.refresh();
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * Escape / backdrop click closes the dialog.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this.destroy();
+    }
+}
+
+/**
+ * @param {number} rate - Fraction between 0 and 1
+ * @returns {string} The rate as a percentage
+ */
+function percent(rate) {
+    return `${Math.round(rate * 1000) / 10}%`;
+}
+
+/**
+ * Collection names by ID, for labelling collection groups
+ *
+ * @async
+ * @returns {Promise<Map<string, string>>}
+ */
+async function collectionNames() {
+    const collections = await app.collectionController?.repository.getAll() || [];
+    return new Map(collections.map(collection => [collection.id, collection.name]));
+}
+
+/**
+ * Bar chart of daily request counts, with the failed share of each day marked
+ *
+ * @param {Array<Object>} series - Daily points ({day, count, errors, avgLatencyMs})
+ * @returns {HTMLElement} The chart
+ */
+function activityChart(series) {
+    const chart = document.createElement('span');
+    chart.className = 'usage-dashboard-chart';
+    const max = Math.max(1, ...series.map(point => point.count));
+    series.forEach(point => {
+        const bar = document.createElement('span');
+        bar.className = 'usage-dashboard-bar';
+        bar.style.height = `${(point.count / max) * 100}%`;
+        const latency = point.avgLatencyMs ?? '-';
+        bar.title = app.i18n?.t('usage_dashboard.day_tooltip', {
+            day: point.day,
+            count: point.count,
+            errors: point.errors,
+            latency
+        }) || `${point.day}: ${point.count} requests, ${point.errors} errors, ${latency} ms average`;
+        if (point.errors > 0) {
+            const failed = document.createElement('span');
+            failed.className = 'usage-dashboard-bar-errors';
+            failed.style.height = `${(point.errors / point.count) * 100}%`;
+            bar.appendChild(failed);
+        }
+        chart.appendChild(bar);
+    });
+    return chart;
+}
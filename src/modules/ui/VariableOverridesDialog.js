@@ -0,0 +1,113 @@
+/**
+ * @fileoverview Dialog editing the variable overrides of a request: values
+ * that win over the environment and collection variables for its sends only
+ * @module ui/VariableOverridesDialog
+ */
+
+import { app } from '../appContext.js';
+import { BaseModal } from './BaseModal.js';
+
+/**
+ * Parses `name=value` lines into an overrides map
+ *
+ * @param {string} text - Textarea value
+ * @returns {{overrides: Object<string, string>, invalid: Array<string>}} The
+ * overrides and the lines without a name
+ */
+function parseOverrides(text) {
+    const overrides = {};
+    const invalid = [];
+    text.split('\n').forEach((line) => {
+        if (!line.trim()) {
+            return;
+        }
+        const separator = line.indexOf('=');
+        const name = separator === -1 ? '' : line.slice(0, separator).trim();
+        if (!name) {
+            invalid.push(line.trim());
+            return;
+        }
+        overrides[name] = line.slice(separator + 1).trim();
+    });
+    return { overrides, invalid };
+}
+
+/**
+ * Modal editing a request's variable overrides
+ *
+ * @class
+ * @augments BaseModal
+ */
+export class VariableOverridesDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Function|null} Pending promise resolver. */
+        this.resolve = null;
+    }
+
+    /**
+     * Shows the dialog
+     *
+     * @param {Object<string, string>|null} current - The request's overrides
+     * @returns {Promise<Object<string, string>|null>} The edited overrides
+     * (empty to remove them), or null when cancelled
+     */
+    show(current) {
+        return new Promise((resolve) => {
+            this.resolve = resolve;
+
+            const dialog = this.mount({
+                overlayClass: 'variable-overrides-dialog-overlay',
+                dialogClass: 'variable-overrides-dialog modal-dialog',
+                templatePath: './src/templates/dialogs/variableOverrides.html',
+                templateId: 'tpl-variable-overrides-dialog'
+            });
+            if (app.i18n && app.i18n.updateUI) {
+                app.i18n.updateUI(dialog);
+            }
+
+            const input = dialog.querySelector('#variable-overrides-input');
+            const error = dialog.querySelector('[data-role="error"]');
+            input.value = Object.entries(current || {})
+                .map(([name, value]) => `${name}=${value}`)
+                .join('\n');
+
+            dialog.querySelector('[data-role="cancel"]').addEventListener('click', () => this.onDismiss());
+            dialog.querySelector('[data-role="confirm"]').addEventListener('click', () => {
+                const { overrides, invalid } = parseOverrides(input.value);
+                if (invalid.length > 0) {
+                    error.textContent = app.i18n?.t('variable_overrides.invalid', { line: invalid[0] })
+                        || `"${invalid[0]}" is not a name=value line`;
+                    return;
+                }
+                this._settle(overrides);
+            });
+            input.focus();
+        });
+    }
+
+    /**
+     * Cancels the dialog, resolving with null
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        this._settle(null);
+    }
+
+    /**
+     * Resolves the pending promise once and tears the dialog down
+     *
+     * @private
+     * @param {Object|null} value - Value to resolve with
+     * @returns {void}
+     */
+    _settle(value) {
+        if (this.resolve) {
+            this.resolve(value);
+            this.resolve = null;
+        }
+        this.destroy();
+    }
+}
@@ -0,0 +1,288 @@
+/**
+ * @fileoverview Modal dialog for the built-in webhook receiver, a local
+ * request bin for testing the webhooks a service sends out
+ * @module ui/WebhookReceiverDialog
+ */
+
+import { BaseModal } from './BaseModal.js';
+import { app } from '../appContext.js';
+import { templateLoader } from '../templateLoader.js';
+import { toast } from './Toast.js';
+import { createBackendEventListener } from '../streaming/streamSession.js';
+
+const CONFIG_KEY = 'webhookReceiver';
+const TEMPLATE_PATH = './src/templates/dialogs/webhookReceiver.html';
+
+/** @type {WebhookReceiverDialog|null} The open dialog, which receives new requests. */
+let openDialog = null;
+
+const initWebhookListener = createBackendEventListener(
+    'webhook-request',
+    () => !!window.backendAPI?.webhookReceiver,
+    (event) => openDialog?.addRequest(event.payload)
+);
+
+/**
+ * The body of a captured request for display: JSON is pretty-printed and
+ * binary bodies are shown base64-encoded with a note
+ *
+ * @param {Object} request - Captured request
+ * @returns {string}
+ */
+function formatBody(request) {
+    if (request.bodyEncoding === 'base64') {
+        const note = app.i18n?.t('webhook_receiver.base64_body', { size: request.size })
+            || `Binary body (${request.size} bytes), base64-encoded:`;
+        return `${note}\n${request.body}`;
+    }
+    try {
+        return JSON.stringify(JSON.parse(request.body), null, 2);
+    } catch {
+        return request.body;
+    }
+}
+
+/**
+ * Dialog starting and stopping the webhook receiver and listing what it caught
+ *
+ * @class
+ * @classdesc The receiver keeps running when the dialog closes; captured
+ * requests are kept by the backend and listed again on the next open. New
+ * requests appear as they arrive.
+ * @augments BaseModal
+ */
+export class WebhookReceiverDialog extends BaseModal {
+    constructor() {
+        super();
+        /** @type {Array<Object>} Captured requests, newest first. */
+        this.requests = [];
+        /** @type {string|null} ID of the request shown in the detail view. */
+        this.selectedId = null;
+        /** @type {boolean} Whether the receiver is running. */
+        this.running = false;
+    }
+
+    /**
+     * Shows the dialog.
+     *
+     * @async
+     * @returns {Promise<void>}
+     */
+    async show() {
+        const dialog = this.mount({
+            overlayClass: 'webhook-receiver-overlay',
+            dialogClass: 'webhook-receiver-dialog modal-dialog modal-dialog--lg',
+            templatePath: TEMPLATE_PATH,
+            templateId: 'tpl-webhook-receiver-dialog'
+        });
+        if (app.i18n && app.i18n.updateUI) {
+            app.i18n.updateUI();
+        }
+        openDialog = this;
+
+        dialog.querySelector('#webhook-receiver-close-btn').addEventListener('click', () => this.onDismiss());
+        dialog.querySelector('#webhook-receiver-toggle-btn').addEventListener('click', () => this.toggle());
+        dialog.querySelector('[data-role="clear"]').addEventListener('click', () => this.clear());
+        dialog.querySelector('[data-role="copy-url"]').addEventListener('click', async () => {
+            await navigator.clipboard.writeText(dialog.querySelector('[data-role="url"]').textContent);
+            toast.success(app.i18n?.t('webhook_receiver.url_copied') || 'Webhook URL copied');
+        });
+
+        const api = window.backendAPI.webhookReceiver;
+        try {
+            await initWebhookListener();
+            const [config, status, requests] = await Promise.all([
+                window.backendAPI.store.get(CONFIG_KEY),
+                api.status(),
+                api.requests()
+            ]);
+            this.applyConfig(config);
+            this.requests = requests;
+            this.renderStatus(status);
+            this.renderList();
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * Fills the form with the last used configuration.
+     *
+     * @private
+     * @param {Object|null} config - Saved configuration
+     * @returns {void}
+     */
+    applyConfig(config) {
+        if (!config || typeof config !== 'object') {
+            return;
+        }
+        const dialog = this.dialog;
+        if (config.port) {
+            dialog.querySelector('#webhook-receiver-port').value = config.port;
+        }
+        dialog.querySelector('#webhook-receiver-path').value = config.path || '';
+        if (config.responseStatus) {
+            dialog.querySelector('#webhook-receiver-status').value = config.responseStatus;
+        }
+        dialog.querySelector('#webhook-receiver-all-interfaces').checked = Boolean(config.listenOnAllInterfaces);
+    }
+
+    /**
+     * Starts or stops the receiver.
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async toggle() {
+        const dialog = this.dialog;
+        const api = window.backendAPI.webhookReceiver;
+        try {
+            if (this.running) {
+                this.renderStatus(await api.stop());
+                return;
+            }
+            const config = {
+                port: parseInt(dialog.querySelector('#webhook-receiver-port').value, 10),
+                path: dialog.querySelector('#webhook-receiver-path').value.trim(),
+                responseStatus: parseInt(dialog.querySelector('#webhook-receiver-status').value, 10) || null,
+                listenOnAllInterfaces: dialog.querySelector('#webhook-receiver-all-interfaces').checked
+            };
+            if (Number.isNaN(config.port)) {
+                toast.error(app.i18n?.t('webhook_receiver.invalid_port') || 'Enter a port');
+                return;
+            }
+            const status = await api.start(config);
+            await window.backendAPI.store.set(CONFIG_KEY, config);
+            if (this.dialog) {
+                this.renderStatus(status);
+            }
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * Discards the captured requests.
+     *
+     * @private
+     * @async
+     * @returns {Promise<void>}
+     */
+    async clear() {
+        try {
+            await window.backendAPI.webhookReceiver.clear();
+            this.requests = [];
+            this.selectedId = null;
+            this.renderList();
+        } catch (error) {
+            toast.error(error?.message || String(error));
+        }
+    }
+
+    /**
+     * Adds a request streamed from the backend.
+     *
+     * @param {Object} request - Captured request
+     * @returns {void}
+     */
+    addRequest(request) {
+        if (!this.dialog || !request?.id) {
+            return;
+        }
+        this.requests.unshift(request);
+        this.renderList();
+    }
+
+    /**
+     * Updates the toggle button, form and URL for the receiver status.
+     *
+     * @private
+     * @param {Object} status - Receiver status from the backend
+     * @returns {void}
+     */
+    renderStatus(status) {
+        const dialog = this.dialog;
+        this.running = Boolean(status.running);
+        const toggleBtn = dialog.querySelector('#webhook-receiver-toggle-btn');
+        toggleBtn.textContent = this.running
+            ? app.i18n?.t('webhook_receiver.stop') || 'Stop'
+            : app.i18n?.t('webhook_receiver.start') || 'Start';
+        toggleBtn.classList.toggle('btn-primary', !this.running);
+        toggleBtn.classList.toggle('btn-danger', this.running);
+        dialog.querySelectorAll('.diagnostics-row input, #webhook-receiver-all-interfaces')
+            .forEach(input => {
+                input.disabled = this.running;
+            });
+        dialog.querySelector('[data-role="url"]').textContent = this.running ? status.url : '';
+        dialog.querySelector('[data-role="copy-url"]').classList.toggle('is-hidden', !this.running);
+    }
+
+    /**
+     * Renders the captured requests and the selected one, the newest when
+     * nothing is selected.
+     *
+     * @private
+     * @returns {void}
+     */
+    renderList() {
+        const list = this.dialog.querySelector('[data-role="list"]');
+        list.replaceChildren();
+        const selected = this.requests.find(request => request.id === this.selectedId) || this.requests[0];
+
+        this.requests.forEach(request => {
+            const fragment = templateLoader.cloneSync(TEMPLATE_PATH, 'tpl-webhook-receiver-item');
+            const item = fragment.firstElementChild;
+            item.classList.toggle('is-selected', request === selected);
+            item.querySelector('[data-role="method"]').textContent = request.method;
+            const path = request.query ? `${request.path}?${request.query}` : request.path;
+            item.querySelector('[data-role="path"]').textContent = path;
+            item.querySelector('[data-role="path"]').title = path;
+            item.querySelector('[data-role="time"]').textContent = new Date(request.receivedAt).toLocaleTimeString();
+            item.querySelector('button').addEventListener('click', () => {
+                this.selectedId = request.id;
+                this.renderList();
+            });
+            list.appendChild(item);
+        });
+        this.renderDetail(selected);
+    }
+
+    /**
+     * Shows a request like it went over the wire: request line, headers, body.
+     *
+     * @private
+     * @param {Object|undefined} request - Request to show
+     * @returns {void}
+     */
+    renderDetail(request) {
+        const detail = this.dialog.querySelector('[data-role="detail"]');
+        if (!request) {
+            detail.textContent = this.running
+                ? app.i18n?.t('webhook_receiver.waiting') || 'Waiting for requests...'
+                : app.i18n?.t('webhook_receiver.empty') || 'Start the receiver and point your webhooks at its URL.';
+            return;
+        }
+        const target = request.query ? `${request.path}?${request.query}` : request.path;
+        const lines = [
+            `${request.method} ${target}`,
+            ...request.headers.map(header => `${header.name}: ${header.value}`),
+            '',
+            formatBody(request),
+            '',
+            `# ${request.remoteAddr}, ${new Date(request.receivedAt).toLocaleString()}`
+        ];
+        detail.textContent = lines.join('\n');
+    }
+
+    /**
+     * Closes the dialog; the receiver keeps running.
+     *
+     * @protected
+     * @returns {void}
+     */
+    onDismiss() {
+        openDialog = null;
+        this.destroy();
+    }
+}
@@ -118,7 +118,8 @@ export class VariableProcessor {
      * Walks strings, arrays, and plain objects (keys and values). Static
      * references are unresolved when their name is missing from the given
      * variables map; dynamic references are unresolved when the generator is
-     * unknown, and are reported with a `$` prefix.
+     * unknown, and are reported with a `$` prefix. `{{$secret:...}}` references
+     * are resolved by the backend at send time and never count as unresolved.
      * @param {*} value - String, array, or object to scan
      * @param {Object} [variables={}] - Static variable name/value map
      * @returns {string[]} Unique unresolved variable names
@@ -134,7 +135,7 @@ export class VariableProcessor {
                     }
                 });
                 this.extractDynamicVariableNames(input).forEach(v => {
-                    if (!this.dynamicGenerator.isDynamicVariable(v.name)) {
+                    if (v.name !== 'secret' && !this.dynamicGenerator.isDynamicVariable(v.name)) {
                         unresolved.add(`$${v.name}`);
                     }
                 });
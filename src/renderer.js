@@ -10,10 +10,13 @@
 import { getCurrentEndpoint, setCurrentEndpoint } from './modules/state/currentEndpoint.js';
 import { app } from './modules/appContext.js';
 import './modules/ipcBridge.js';
+import { initCrashReporter } from './modules/crashReporter.js';
 
 import { sendRequestBtn, cancelRequestBtn, curlBtn, importCollectionBtn, urlInput, methodSelect, bodyInput, bodyEditorContainer, bodyTextEditorContainer, grpcBodyInput, grpcBodyEditorContainer } from './modules/domElements.js';
 
 import { initKeyValueListeners, addKeyValueRow, updateQueryParamsFromUrl, setUrlUpdating } from './modules/keyValueManager.js';
+import { initHeaderSuggestions } from './modules/headerSuggestions.js';
+import { initBulkEdit } from './modules/bulkEditManager.js';
 import { initTabListeners, activateTab } from './modules/tabManager.js';
 import { initializeScriptSubTabs } from './modules/scriptSubTabs.js';
 import { updateStatusDisplay } from './modules/statusDisplay.js';
@@ -27,9 +30,16 @@ import { initGraphQLSubscriptionHandler } from './modules/graphqlSubscriptionHan
 import { initSseHandler } from './modules/sseHandler.js';
 import { initMqttHandler, handleMqttCancel } from './modules/mqttHandler.js';
 import { initGrpcStreamHandler } from './modules/grpcStreamHandler.js';
-import { loadCollections, importOpenApiFile, importPostmanCollection, importPostmanEnvironment, importCurl, initializeBodyTracking } from './modules/collectionManager.js';
+import { initRequestTimelineHandler } from './modules/requestTimelineHandler.js';
+import { initCollectionWatchHandler } from './modules/collectionWatchHandler.js';
+import { initDeepLinkHandler } from './modules/deepLinkHandler.js';
+import { initEnvironmentChangeHandler } from './modules/environmentChangeHandler.js';
+import { loadCollections, importOpenApiFile, importPostmanCollection, importPostmanEnvironment, importPostmanDump, importRequestBundle, importHarFile, importHttpFile, importCurl, initializeBodyTracking } from './modules/collectionManager.js';
 import { ThemeManager } from './modules/themeManager.js';
 import { SettingsModal } from './modules/ui/SettingsModal.js';
+import { NetworkDiagnosticsDialog } from './modules/ui/NetworkDiagnosticsDialog.js';
+import { WebhookReceiverDialog } from './modules/ui/WebhookReceiverDialog.js';
+import { UsageDashboardDialog } from './modules/ui/UsageDashboardDialog.js';
 import { HttpVersionManager } from './modules/httpVersionManager.js';
 import { TimeoutManager } from './modules/timeoutManager.js';
 import { initResizer } from './modules/resizer.js';
@@ -162,6 +172,18 @@ function initKeyboardShortcuts() {
         category: 'Request'
     });
 
+    keyboardShortcuts.register('Enter', {
+        ctrl: true,
+        shift: true,
+        handler: () => {
+            if (sendRequestBtn && !sendRequestBtn.disabled) {
+                handleSendRequest({ reuseIdempotencyKey: true });
+            }
+        },
+        description: 'Resend with the same idempotency key',
+        category: 'Request'
+    });
+
     keyboardShortcuts.register('KeyS', {
         ctrl: true,
         handler: async () => {
@@ -476,12 +498,19 @@ function scheduleIdleTask(callback, timeout = 2000) {
     }
 }
 
+initCrashReporter();
+
 document.addEventListener('DOMContentLoaded', async () => {
     
     curlBtn.addEventListener('click', handleGenerateCurl);
     sendRequestBtn.addEventListener('click', handleSendRequest);
     cancelRequestBtn.addEventListener('click', handleCancelRequest);
 
+    const resendIdempotencyBtn = document.getElementById('resend-idempotency-btn');
+    if (resendIdempotencyBtn) {
+        resendIdempotencyBtn.addEventListener('click', () => handleSendRequest({ reuseIdempotencyKey: true }));
+    }
+
     const mqttDisconnectBtn = document.getElementById('mqtt-disconnect-btn');
     if (mqttDisconnectBtn) {
         mqttDisconnectBtn.addEventListener('click', () => handleMqttCancel());
@@ -512,6 +541,30 @@ document.addEventListener('DOMContentLoaded', async () => {
                 icon: '<path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M10.325 4.317c.426-1.756 2.924-1.756 3.35 0a1.724 1.724 0 002.573 1.066c1.543-.94 3.31.826 2.37 2.37a1.724 1.724 0 001.065 2.572c1.756.426 1.756 2.924 0 3.35a1.724 1.724 0 00-1.066 2.573c.94 1.543-.826 3.31-2.37 2.37a1.724 1.724 0 00-2.572 1.065c-.426 1.756-2.924 1.756-3.35 0a1.724 1.724 0 00-2.573-1.066c-1.543.94-3.31-.826-2.37-2.37a1.724 1.724 0 00-1.065-2.572c-1.756-.426-1.756-2.924 0-3.35a1.724 1.724 0 001.066-2.573c-.94-1.543.826-3.31 2.37-2.37.996.608 2.296.07 2.572-1.065z"></path><path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M15 12a3 3 0 11-6 0 3 3 0 016 0z"></path>',
                 onClick: importPostmanEnvironment
             },
+            {
+                label: 'Postman Data Dump',
+                translationKey: 'import.postman_dump',
+                icon: '<path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M5 8h14M5 8a2 2 0 110-4h14a2 2 0 110 4M5 8v10a2 2 0 002 2h10a2 2 0 002-2V8m-9 4h4"></path>',
+                onClick: importPostmanDump
+            },
+            {
+                label: 'HAR File',
+                translationKey: 'import.har',
+                icon: '<path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M12 8v4l3 3m6-3a9 9 0 11-18 0 9 9 0 0118 0z"></path>',
+                onClick: importHarFile
+            },
+            {
+                label: '.http File',
+                translationKey: 'import.http_file',
+                icon: '<path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4"></path>',
+                onClick: importHttpFile
+            },
+            {
+                label: 'Request Bundle',
+                translationKey: 'import.request_bundle',
+                icon: '<path stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round" d="M13.828 10.172a4 4 0 00-5.656 0l-4 4a4 4 0 105.656 5.656l1.102-1.101m-.758-4.899a4 4 0 005.656 0l4-4a4 4 0 00-5.656-5.656l-1.1 1.1"></path>',
+                onClick: importRequestBundle
+            },
             {
                 label: 'cURL Command',
                 translationKey: 'import.curl',
@@ -554,6 +607,13 @@ document.addEventListener('DOMContentLoaded', async () => {
         });
     }
 
+    const webhookReceiverBtn = document.getElementById('webhook-receiver-btn');
+    if (webhookReceiverBtn) {
+        webhookReceiverBtn.addEventListener('click', () => {
+            new WebhookReceiverDialog().show();
+        });
+    }
+
     const runnerBtn = document.getElementById('runner-btn');
     if (runnerBtn) {
         runnerBtn.addEventListener('click', () => {
@@ -600,6 +660,22 @@ document.addEventListener('DOMContentLoaded', async () => {
         });
     }
 
+    const networkDiagnosticsBtn = document.getElementById('network-diagnostics-btn');
+    if (networkDiagnosticsBtn) {
+        networkDiagnosticsBtn.addEventListener('click', () => {
+            // Unresolved {{variables}} are no use as a host, so only a literal URL is pre-filled
+            const url = urlInput.value.trim();
+            new NetworkDiagnosticsDialog().show(url.includes('{{') ? '' : url);
+        });
+    }
+
+    const usageDashboardBtn = document.getElementById('usage-dashboard-btn');
+    if (usageDashboardBtn) {
+        usageDashboardBtn.addEventListener('click', () => {
+            new UsageDashboardDialog().show();
+        });
+    }
+
     // globals remain here.
     app.authManager = authManager;
     authManager.getInheritedAuthInfo = async () => {
@@ -780,6 +856,8 @@ document.addEventListener('DOMContentLoaded', async () => {
     updateStatusDisplay('Ready', null);
 
     initKeyValueListeners();
+    initHeaderSuggestions();
+    initBulkEdit();
     initializeBodyTracking();
     initResizer();
     initializeCopyHandler();
@@ -853,6 +931,20 @@ document.addEventListener('DOMContentLoaded', async () => {
 
         await initGrpcStreamHandler();
 
+        await initRequestTimelineHandler();
+
+        await initCollectionWatchHandler();
+
+        await initDeepLinkHandler();
+
+        await initEnvironmentChangeHandler();
+
+        try {
+            await offerElectronMigration();
+        } catch (error) {
+            toast.error(`Import from the previous version failed: ${error.message}`);
+        }
+
         try {
             if (window.backendAPI?.collections?.needsMigration) {
                 const needsMigration = await window.backendAPI.collections.needsMigration();
@@ -874,6 +966,39 @@ document.addEventListener('DOMContentLoaded', async () => {
     }, 3000);
 });
 
+/**
+ * Offers a one-time import of data left behind by the Electron version of the app.
+ *
+ * @returns {Promise<void>}
+ */
+async function offerElectronMigration() {
+    const migration = window.backendAPI?.electronMigration;
+    if (!migration) {
+        return;
+    }
+    const legacy = await migration.detect();
+    if (!legacy) {
+        return;
+    }
+
+    const { ConfirmDialog } = await import('./modules/ui/ConfirmDialog.js');
+    const confirmed = await new ConfirmDialog().show(
+        `Data from a previous version of Resonance was found (${legacy.collectionCount} collection(s)) at ${legacy.path}. Import it now? Existing data is kept.`,
+        { title: 'Import Previous Data', confirmText: 'Import', cancelText: 'Skip', dangerous: false }
+    );
+    if (!confirmed) {
+        await migration.dismiss();
+        return;
+    }
+
+    const report = await migration.run();
+    const skipped = report.skippedKeys.length > 0 ? `, kept ${report.skippedKeys.length} existing setting(s)` : '';
+    toast.success(`Imported ${report.importedKeys.length} setting(s) and ${report.collections} collection(s)${skipped}`);
+    if (report.importedKeys.length > 0) {
+        await loadCollections();
+    }
+}
+
 window.addEventListener('beforeunload', async (_e) => {
     try {
         const activeTabId = await workspaceTabService.getActiveTabId();
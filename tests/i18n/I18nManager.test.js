@@ -0,0 +1,28 @@
+import { I18nManager } from '../../src/i18n/I18nManager.js';
+
+describe('I18nManager.t', () => {
+    let manager;
+
+    beforeEach(() => {
+        manager = new I18nManager();
+        manager.translations = { common: { save: 'Speichern' } };
+        manager.fallbackTranslations = {
+            common: { save: 'Save', cancel: 'Cancel' },
+            greeting: 'Hello {{name}}'
+        };
+    });
+
+    test('should prefer the current language', () => {
+        expect(manager.t('common.save')).toBe('Speichern');
+    });
+
+    test('should fall back to English for untranslated strings', () => {
+        expect(manager.t('common.cancel')).toBe('Cancel');
+        expect(manager.t('greeting', { name: 'Ada' })).toBe('Hello Ada');
+    });
+
+    test('should return the key when no language has it', () => {
+        expect(manager.t('common.missing')).toBe('common.missing');
+        expect(manager.t('common')).toBe('common');
+    });
+});
@@ -1,5 +1,8 @@
 import { CertificateService } from '../../src/modules/services/CertificateService.js';
 
+const PIN = 'r/mIkG3eEpVdm+u/ko/cwxzOMo1bk4TyHIlByibiA5E=';
+const HEX = 'AB:'.repeat(31) + 'AB';
+
 describe('CertificateService', () => {
     let service;
     let mockRepository;
@@ -19,7 +22,8 @@ describe('CertificateService', () => {
                     { host: 'api.example.com', certPath: '/c.crt', keyPath: '/c.key', caPath: '', enabled: true },
                     { host: 'api.example.com:8443', certPath: '/p.crt', keyPath: '/p.key', caPath: '/ca.pem', enabled: true },
                     { host: 'disabled.example.com', certPath: '/d.crt', keyPath: '/d.key', caPath: '', enabled: false },
-                    { host: 'ca-only.example.com', certPath: '', keyPath: '', caPath: '/ca.pem', enabled: true }
+                    { host: 'ca-only.example.com', certPath: '', keyPath: '', caPath: '/ca.pem', enabled: true },
+                    { host: 'pinned.example.com', certPath: '', keyPath: '', caPath: '', pins: `sha256/${PIN}\n${HEX}`, enabled: true }
                 ]
             });
             await service.getItems(); // warm the cache
@@ -32,13 +36,13 @@ describe('CertificateService', () => {
 
         test('prefers an exact host:port match over a bare host match', () => {
             expect(service.getForHost('api.example.com:8443')).toEqual({
-                certPath: '/p.crt', keyPath: '/p.key', caPath: '/ca.pem'
+                certPath: '/p.crt', keyPath: '/p.key', caPath: '/ca.pem', pins: []
             });
         });
 
         test('falls back to a bare host match', () => {
             expect(service.getForHost('api.example.com:9999')).toEqual({
-                certPath: '/c.crt', keyPath: '/c.key', caPath: ''
+                certPath: '/c.crt', keyPath: '/c.key', caPath: '', pins: []
             });
         });
 
@@ -52,7 +56,13 @@ describe('CertificateService', () => {
 
         test('matches a CA-only entry', () => {
             expect(service.getForHost('ca-only.example.com')).toEqual({
-                certPath: '', keyPath: '', caPath: '/ca.pem'
+                certPath: '', keyPath: '', caPath: '/ca.pem', pins: []
+            });
+        });
+
+        test('matches a pin-only entry and splits its pins', () => {
+            expect(service.getForHost('pinned.example.com')).toEqual({
+                certPath: '', keyPath: '', caPath: '', pins: [`sha256/${PIN}`, HEX]
             });
         });
 
@@ -71,6 +81,12 @@ describe('CertificateService', () => {
             expect(errors.some(e => e.includes('key file'))).toBe(true);
         });
 
+        test('accepts base64 and hex pins and rejects others', () => {
+            expect(service.validateEntry({ host: 'h', pins: `sha256/${PIN}, ${HEX}` })).toEqual([]);
+            const errors = service.validateEntry({ host: 'h', pins: 'sha256/short' });
+            expect(errors.some(e => e.includes('Invalid pin'))).toBe(true);
+        });
+
         test('accepts a CA-only entry', () => {
             expect(service.validateEntry({ host: 'h', caPath: '/ca.pem' })).toEqual([]);
         });
@@ -88,7 +104,7 @@ describe('CertificateService', () => {
             expect(saved).toEqual(items);
             expect(listener).toHaveBeenCalledWith(expect.objectContaining({ type: 'certificates-updated' }));
             // cache is refreshed so resolution works immediately after save
-            expect(service.getForHost('h')).toEqual({ certPath: '/c.crt', keyPath: '/c.key', caPath: '' });
+            expect(service.getForHost('h')).toEqual({ certPath: '/c.crt', keyPath: '/c.key', caPath: '', pins: [] });
         });
     });
 });